@@ -0,0 +1,69 @@
+//! Compares filtering and listing categories of a large todo list using the category
+//! index with scanning all tasks.
+//!
+//! Run with `cargo bench --bench filtering`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::collections::BTreeSet;
+use todotxt_tui_core::todo::{FilterState, ToDo, ToDoCategory, ToDoData};
+
+const TASKS: usize = 5000;
+
+fn todo() -> ToDo {
+    let mut todo = ToDo::default();
+    for i in 0..TASKS {
+        todo.new_task(&format!(
+            "task {i} +project{} @context{} #tag{}",
+            i % 50,
+            i % 20,
+            i % 100
+        ))
+        .unwrap();
+    }
+    todo.toggle_filter(ToDoCategory::Projects, "project7", FilterState::Select);
+    todo.toggle_filter(ToDoCategory::Contexts, "context3", FilterState::Remove);
+    todo
+}
+
+fn filter(c: &mut Criterion) {
+    let todo = todo();
+    let mut group = c.benchmark_group("filter");
+    group.bench_function("scan", |b| {
+        b.iter(|| {
+            let state = todo.get_state();
+            todo.pending
+                .iter()
+                .filter(|task| state.filter_out(task))
+                .count()
+        })
+    });
+    group.bench_function("index", |b| {
+        b.iter(|| TASKS - todo.hidden(black_box(ToDoData::Pending)))
+    });
+    group.finish();
+}
+
+fn categories(c: &mut Criterion) {
+    let todo = todo();
+    let mut group = c.benchmark_group("categories");
+    group.bench_function("scan", |b| {
+        b.iter(|| {
+            todo.pending
+                .iter()
+                .flat_map(|task| &task.hashtags)
+                .collect::<BTreeSet<_>>()
+                .len()
+        })
+    });
+    group.bench_function("index", |b| {
+        b.iter(|| {
+            todo.get_categories(black_box(ToDoCategory::Hashtags))
+                .vec
+                .len()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, filter, categories);
+criterion_main!(benches);
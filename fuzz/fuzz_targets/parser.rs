@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use todotxt_tui_core::engine::{Parser, Styles, ToDo};
+
+// Templates are parsed without panicking, the parsed template fills tasks too.
+fuzz_target!(|template: &str| {
+    if let Ok(parser) = Parser::new(template, Styles::default()) {
+        let mut todo = ToDo::default();
+        let _ = todo.new_task("(A) 2023-04-30 call mom +family @phone #call due:2023-05-01");
+        for task in &todo.pending {
+            let _ = parser.fill_task(task, &todo);
+        }
+    }
+});
@@ -0,0 +1,19 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use todotxt_tui_core::todo::{is_scratch, parse_task};
+
+// A task is read back the same after it is saved, as the file worker saves it.
+fuzz_target!(|content: &str| {
+    for line in content.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let Ok(task) = parse_task(line) else {
+            continue;
+        };
+        if is_scratch(&task) {
+            continue;
+        }
+        let saved = task.to_string();
+        let reloaded = parse_task(saved.trim()).expect("a saved task is read again");
+        assert_eq!(task, reloaded, "{line:?} is saved as {saved:?}");
+    }
+});
@@ -0,0 +1,118 @@
+use crate::todo::Activity;
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, ErrorKind, Write},
+    path::PathBuf,
+};
+
+/// Append-only log of changes of tasks.
+///
+/// Every added, completed, reopened, edited and deleted task is appended to the
+/// file as a line with a timestamp, e.g. `2023-05-01 14:30:00 complete call mom`.
+/// The file is never rewritten, so it can be used for timesheets or to find out
+/// what happened to a task.
+pub struct ActivityLog {
+    path: PathBuf,
+}
+
+impl ActivityLog {
+    /// Creates the activity log stored in the file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the activity log file.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Appends the changes to the end of the file, the file is created if it does not exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `activity` - The changes to append.
+    pub fn append(&self, activity: &[Activity]) -> io::Result<()> {
+        if activity.is_empty() {
+            return Ok(());
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let content: String = activity.iter().map(|item| format!("{item}\n")).collect();
+        file.write_all(content.as_bytes())
+    }
+
+    /// Reads the lines of the log matching the filter, the newest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - Words separated by whitespace, a line matches if it contains
+    ///   all of them. The case is ignored.
+    pub fn read(&self, filter: &str) -> io::Result<Vec<String>> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+        let words: Vec<String> = filter.split_whitespace().map(str::to_lowercase).collect();
+        Ok(content
+            .lines()
+            .rev()
+            .filter(|line| {
+                let line = line.to_lowercase();
+                words.iter().all(|word| line.contains(word))
+            })
+            .map(String::from)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::todo::ActivityKind;
+    use chrono::NaiveDate;
+    use std::{env::temp_dir, process};
+
+    #[test]
+    fn append_and_read() -> io::Result<()> {
+        let path = temp_dir().join(format!("todotxt-tui-activity-test-{}.txt", process::id()));
+        let _ = fs::remove_file(&path);
+        let log = ActivityLog::new(&path);
+        assert!(log.read("")?.is_empty());
+
+        let activity = |day, kind, task: &str| Activity {
+            time: NaiveDate::from_ymd_opt(2023, 5, day)
+                .unwrap()
+                .and_hms_opt(9, 0, 0)
+                .unwrap(),
+            kind,
+            task: String::from(task),
+            previous: None,
+        };
+        log.append(&[
+            activity(1, ActivityKind::Add, "call mom"),
+            activity(1, ActivityKind::Add, "write the report +work"),
+        ])?;
+        log.append(&[])?;
+        log.append(&[activity(
+            2,
+            ActivityKind::Complete,
+            "x write the report +work",
+        )])?;
+
+        assert_eq!(
+            log.read("")?,
+            vec![
+                "2023-05-02 09:00:00 complete x write the report +work",
+                "2023-05-01 09:00:00 add write the report +work",
+                "2023-05-01 09:00:00 add call mom",
+            ]
+        );
+        assert_eq!(
+            log.read("+WORK 2023-05-01")?,
+            vec!["2023-05-01 09:00:00 add write the report +work"]
+        );
+        fs::remove_file(&path)
+    }
+}
@@ -0,0 +1,102 @@
+use chrono::{Local, NaiveDate, NaiveDateTime};
+use std::time::Duration;
+
+/// The source of the current date of date-dependent features, e.g. due dates of recurring
+/// tasks, overdue tasks, reminders or routines.
+///
+/// The date can be fixed by `--today`, so the todo list can be previewed as it will look
+/// on another day. The time of the day always follows the system clock.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Clock {
+    today: Option<NaiveDate>,
+}
+
+impl Clock {
+    /// Creates a clock with the date fixed to the given day.
+    pub fn fixed(today: NaiveDate) -> Self {
+        Self { today: Some(today) }
+    }
+
+    /// Checks whether the date is fixed instead of following the system clock.
+    pub fn is_fixed(&self) -> bool {
+        self.today.is_some()
+    }
+
+    /// Gets the current date in the local time zone or the fixed date.
+    pub fn today(&self) -> NaiveDate {
+        self.today.unwrap_or_else(|| Local::now().date_naive())
+    }
+
+    /// Gets the current local date and time, the date is replaced by the fixed date.
+    pub fn now(&self) -> NaiveDateTime {
+        let now = Local::now().naive_local();
+        match self.today {
+            Some(today) => today.and_time(now.time()),
+            None => now,
+        }
+    }
+}
+
+/// Gets the number of whole intervals since the start of the epoch, the moments of the same
+/// interval are in the same tick.
+fn tick(interval: Duration, time: NaiveDateTime) -> i64 {
+    let interval = interval.as_millis().max(1) as i64;
+    time.and_utc().timestamp_millis().div_euclid(interval)
+}
+
+/// Checks whether a multiple of the interval, e.g. a full minute, was passed between
+/// the two moments. Things refreshed at the same interval are refreshed together.
+///
+/// # Arguments
+///
+/// * `interval` - The refresh interval.
+/// * `from` - The moment of the last refresh.
+/// * `to` - The current moment.
+pub fn ticked(interval: Duration, from: NaiveDateTime, to: NaiveDateTime) -> bool {
+    tick(interval, from) != tick(interval, to)
+}
+
+/// Gets the time until the next multiple of the interval, see [`ticked`].
+pub fn until_tick(interval: Duration, now: NaiveDateTime) -> Duration {
+    let interval = interval.as_millis().max(1) as i64;
+    let left = interval - now.and_utc().timestamp_millis().rem_euclid(interval);
+    Duration::from_millis(left as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed() {
+        let today = NaiveDate::from_ymd_opt(2023, 5, 10).unwrap();
+        let clock = Clock::fixed(today);
+        assert!(clock.is_fixed());
+        assert_eq!(clock.today(), today);
+        assert_eq!(clock.now().date(), today);
+
+        let clock = Clock::default();
+        assert!(!clock.is_fixed());
+        assert_eq!(clock.today(), Local::now().date_naive());
+    }
+
+    #[test]
+    fn ticks() {
+        let at = |min, sec| {
+            NaiveDate::from_ymd_opt(2023, 5, 10)
+                .unwrap()
+                .and_hms_opt(14, min, sec)
+                .unwrap()
+        };
+        let minute = Duration::from_secs(60);
+        assert!(!ticked(minute, at(5, 0), at(5, 59)));
+        assert!(ticked(minute, at(5, 59), at(6, 0)));
+        assert!(ticked(Duration::from_secs(1), at(5, 0), at(5, 1)));
+        assert_eq!(until_tick(minute, at(5, 15)), Duration::from_secs(45));
+        assert_eq!(until_tick(minute, at(6, 0)), minute);
+        assert_eq!(
+            until_tick(Duration::from_secs(300), at(6, 0)),
+            Duration::from_secs(240)
+        );
+    }
+}
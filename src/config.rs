@@ -1,27 +1,53 @@
-mod colors;
+mod color_support;
+mod keybinds;
 mod keycode;
 mod logger;
+mod named_style;
+mod power_mode;
+mod setup;
 mod styles;
-mod text_modifier;
-mod text_style;
 mod todo_config;
+mod watcher;
+mod widget_settings;
+mod widget_style;
 
+pub use self::color_support::ColorSupport;
+pub use self::keybinds::{key_name, parse_key_name, KeybindScope};
 pub use self::keycode::KeyCodeDef;
 pub use self::logger::Logger;
-pub use self::styles::Styles;
-pub use self::styles::StylesValue;
-pub use self::text_style::TextStyle;
-pub use self::text_style::TextStyleList;
-pub use self::todo_config::ToDoConfig;
+pub use self::named_style::NamedStyle;
+pub use self::setup::{is_first_run, Setup};
+pub use self::watcher::ConfigWatcher;
+pub use self::widget_settings::WidgetSettings;
+pub use self::widget_style::WidgetStyle;
+
+pub use crate::style::{Styles, TextStyle, TextStyleList};
+pub use crate::todo::ToDoConfig;
 
-use self::colors::opt_color;
 use crate::{
-    layout::widget::widget_type::WidgetType,
-    todo::task_list::TaskSort,
+    clock::Clock,
+    error::{ToDoError, ToDoIoError, ToDoRes},
+    file_worker::FileWorker,
+    instance::RunningInstance,
+    layout::{
+        self,
+        widget::{
+            task_view::{TaskColumn, TaskDivider, TaskView},
+            widget_type::WidgetType,
+            ScrollIndicator,
+        },
+    },
+    signals::Signal,
+    style::{opt_color, TextModifier},
+    todo::{
+        preprocess::preprocess, sort::TaskSort, EscalationRule, ExportFormat, ImportFormat,
+        Parser as TemplateParser, PriorityAging, TaskQuery, TaskRule, ToDo,
+    },
     ui::{EventHandlerUI, UIEvent},
 };
 use clap::{arg, CommandFactory, Parser};
 
+use chrono::NaiveDate;
 use clap_complete::{generate, shells::Bash};
 use crossterm::event::KeyCode;
 use log::LevelFilter;
@@ -30,16 +56,18 @@ use std::{
     collections::HashMap,
     env::var,
     error::Error,
-    fs::File,
+    fs::{self, File},
     io::{self, Read, Write},
     num::ParseIntError,
-    path::PathBuf,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, Mutex},
     time::Duration,
 };
 use tui::style::Color;
 
 /// Configuration struct for the ToDo TUI application.
-#[derive(Serialize, Deserialize, Default, Parser)]
+#[derive(Serialize, Deserialize, Default, Clone, Parser)]
 #[command(author, version, about, long_about = None)]
 #[cfg_attr(test, derive(PartialEq, Debug))]
 pub struct Config {
@@ -66,14 +94,42 @@ pub struct Config {
     #[arg(long, value_name = "FILE", help_heading = "export")]
     export_default_config: Option<PathBuf>,
 
+    /// Export the tasks to given file path as JSON (.json),
+    /// a Markdown checklist (.md) or an iCalendar file (.ics).
+    /// The tasks are filtered by `init_filter` if it is set.
+    #[serde(skip)]
+    #[arg(long, value_name = "FILE", help_heading = "export")]
+    export_tasks: Option<PathBuf>,
+
+    /// Import the tasks of a Taskwarrior JSON export (.json) or a Markdown
+    /// checklist (.md) to the todo list without opening the user interface.
+    #[serde(skip)]
+    #[arg(long, value_name = "FILE", help_heading = "export")]
+    import_tasks: Option<PathBuf>,
+
+    /// Add the task to the todo list without opening the user interface.
+    /// The task goes to the running instance editing the todo file if there is one.
+    #[serde(skip)]
+    #[arg(long, value_name = "TASK", help_heading = "export")]
+    add: Option<String>,
+
     #[serde(default, with = "opt_color")]
     #[arg(long, value_name = "COLOR")]
     active_color: Option<Color>,
 
+    /// Colors the terminal can show, colors it cannot are rendered as the nearest one.
+    /// Detected from the terminal by default.
+    #[arg(long, value_name = "COLOR_SUPPORT")]
+    color_support: Option<ColorSupport>,
+
     /// Widget that will be active after start of the application.
     #[arg(short, long, value_name = "WIDGET_TYPE")]
     init_widget: Option<WidgetType>,
 
+    /// Name of a query from `list_queries` whose projects, contexts and hashtags are the filters after start.
+    #[arg(long, value_name = "QUERY")]
+    init_filter: Option<String>,
+
     /// Title of window with opened todo-tui {env!("CARGO_PKG_NAME")} {AAAA}
     #[arg(short = 'T', long, value_name = "STRING")]
     window_title: Option<String>,
@@ -84,9 +140,114 @@ pub struct Config {
     #[arg(short, long, value_name = "STRING")]
     archive_path: Option<String>,
 
+    /// URL of the todo file on a WebDAV (http/https) or SSH (ssh/sftp/scp) server, the todo file is its local copy
+    #[arg(long, value_name = "URL")]
+    todo_url: Option<String>,
+
+    #[arg(long, value_name = "FILE")]
+    done_path: Option<String>,
+
+    #[arg(long, value_name = "FILE")]
+    metadata_path: Option<String>,
+
+    /// Todo files to switch between by the names of the profiles, e.g. `work = "~/work/todo.txt"`.
+    #[clap(skip)]
+    profiles: Option<HashMap<String, String>>,
+
+    /// Path the todo list is exported to while the todo file cannot be written.
+    #[arg(long, value_name = "FILE")]
+    unsaved_path: Option<String>,
+
+    /// Number of days since the creation or the last review after which a task is stale.
+    #[arg(long, value_name = "DAYS")]
+    review_age: Option<usize>,
+
+    /// Reload the configuration when its file changes while the application is running.
+    #[arg(long, value_name = "FLAG")]
+    watch_config: Option<bool>,
+
+    /// Fill the selected project and context filters in the quick-add popup.
+    #[arg(long, value_name = "FLAG")]
+    quick_add_filters: Option<bool>,
+
+    /// Path to the inbox file with captured items waiting for triage.
+    #[arg(long, value_name = "STRING")]
+    inbox_path: Option<String>,
+
+    /// Path to the file other programs append signals to, e.g. `done backup`.
+    #[arg(long, value_name = "FILE")]
+    signal_path: Option<String>,
+
+    /// Path to the activity log, every added, completed, edited and deleted task is appended to it.
+    #[arg(long, value_name = "STRING")]
+    activity_log_path: Option<String>,
+
+    /// Number of deleted tasks kept in the trash to be restored, 0 deletes tasks right away.
+    #[arg(long, value_name = "NUMBER")]
+    trash_size: Option<usize>,
+
+    /// Path to the file the tasks flushed from the trash are appended to.
+    #[arg(long, value_name = "FILE")]
+    trash_path: Option<String>,
+
+    /// Path to the history of lines entered in the new-task input, next to the configuration by default.
+    #[arg(long, value_name = "FILE")]
+    input_history_path: Option<String>,
+
+    /// Directory of the Lua scripts, next to the configuration by default.
+    #[arg(long, value_name = "DIR")]
+    scripts_path: Option<String>,
+
+    /// Scripts run by keys, e.g. `g = "errands"` runs `errands.lua`.
+    #[clap(skip)]
+    script_keybind: Option<HashMap<String, String>>,
+
+    /// Number of lines kept in the input history, 0 turns the history off.
+    #[arg(long, value_name = "NUMBER")]
+    input_history_size: Option<usize>,
+
+    /// Path to the file with descriptions, goals and deadlines of projects,
+    /// `projects.toml` next to the todo file by default.
+    #[arg(long, value_name = "STRING")]
+    projects_path: Option<String>,
+
+    /// Warn about projects with open tasks this many days before their deadline.
+    #[arg(long, value_name = "DAYS")]
+    deadline_warning_days: Option<usize>,
+
+    /// Forecast the completion of projects by the tasks completed in this many recent days.
+    #[arg(long, value_name = "DAYS")]
+    forecast_days: Option<usize>,
+
+    /// Sort unprioritized tasks one priority higher for every this many days of their age,
+    /// 0 disables boosting.
+    #[arg(long, value_name = "DAYS")]
+    priority_boost_days: Option<usize>,
+
+    /// Sort priorities above the aging range one priority lower for every this many days
+    /// of the task age, 0 disables decaying.
+    #[arg(long, value_name = "DAYS")]
+    priority_decay_days: Option<usize>,
+
+    /// Length of the pomodoro in minutes, the timer of a task stops after it, 0 disables it.
+    #[arg(long, value_name = "MINUTES")]
+    pomodoro_minutes: Option<usize>,
+
+    /// Priorities boosted tasks climb through and decayed tasks stop at, e.g. `C-B`.
+    #[arg(long, value_name = "RANGE")]
+    priority_aging_range: Option<String>,
+
     #[arg(long)] // TODO value type
     priority_colors: Option<TextStyleList>,
 
+    /// Labels shown instead of priority letters, e.g. `A = "Critical"`.
+    #[clap(skip)]
+    priority_labels: Option<HashMap<String, String>>,
+
+    /// Weights of the parts of the urgency score, e.g. `due = 12.0` or `"+work" = 3.0`.
+    #[clap(skip)]
+    urgency_weights: Option<HashMap<String, f64>>,
+
     #[arg(short, long, value_name = "FLAG")]
     wrap_preview: Option<bool>,
 
@@ -99,9 +260,75 @@ pub struct Config {
     #[arg(long, value_name = "TEXT_STYLE")]
     done_active_color: Option<TextStyle>,
 
+    /// Style of the selected row in category lists.
+    #[arg(long, value_name = "TEXT_STYLE")]
+    category_active_color: Option<TextStyle>,
+
+    /// Border, title and highlight styles of widgets by their names in the layout.
+    #[clap(skip)]
+    widget_styles: Option<HashMap<String, WidgetStyle>>,
+
+    /// Title, scrolling, key bindings and done tasks of widgets by their names in the layout.
+    #[clap(skip)]
+    widget_settings: Option<HashMap<String, WidgetSettings>>,
+
+    /// Style of pending tasks whose due date has passed.
+    #[arg(long, value_name = "TEXT_STYLE")]
+    overdue_style: Option<TextStyle>,
+
+    /// Style of pending tasks due today.
+    #[arg(long, value_name = "TEXT_STYLE")]
+    due_today_style: Option<TextStyle>,
+
+    /// Style of pending tasks due in the next seven days.
+    #[arg(long, value_name = "TEXT_STYLE")]
+    due_week_style: Option<TextStyle>,
+
+    /// Style of every second row in list widgets.
+    #[arg(long, value_name = "TEXT_STYLE")]
+    list_zebra_color: Option<TextStyle>,
+
+    /// Style of every second row in the pending list, combined with `list_zebra_color`.
+    #[arg(long, value_name = "TEXT_STYLE")]
+    pending_zebra_color: Option<TextStyle>,
+
+    /// Style of every second row in the done list, combined with `list_zebra_color`.
+    #[arg(long, value_name = "TEXT_STYLE")]
+    done_zebra_color: Option<TextStyle>,
+
+    /// Style of every second row in category lists, combined with `list_zebra_color`.
+    #[arg(long, value_name = "TEXT_STYLE")]
+    category_zebra_color: Option<TextStyle>,
+
+    /// Style of characters of tasks matching the search.
+    #[arg(long, value_name = "TEXT_STYLE")]
+    search_match_style: Option<TextStyle>,
+
+    /// Style of divider rows between groups of tasks.
+    #[arg(long, value_name = "TEXT_STYLE")]
+    divider_style: Option<TextStyle>,
+
+    /// Style of the scrollbar and the position indicator of lists.
+    #[arg(long, value_name = "TEXT_STYLE")]
+    scroll_indicator_style: Option<TextStyle>,
+
+    /// Style of scratch tasks, which are kept only until the application quits.
+    #[arg(long, value_name = "TEXT_STYLE")]
+    scratch_style: Option<TextStyle>,
+
+    /// Style of tasks marked for bulk actions.
+    #[arg(long, value_name = "TEXT_STYLE")]
+    marked_style: Option<TextStyle>,
+
     #[arg(short = 'd', long, value_parser = parse_duration, value_name = "DURATION")]
     autosave_duration: Option<Duration>,
 
+    #[arg(long, value_parser = parse_duration, value_name = "DURATION")]
+    save_delay: Option<Duration>,
+
+    #[arg(long, value_name = "BOOL", help_heading = "export")]
+    save_state: Option<bool>,
+
     #[arg(long, value_name = "FILE", help_heading = "export")]
     save_state_path: Option<PathBuf>,
 
@@ -117,9 +344,152 @@ pub struct Config {
     #[arg(short, long, value_name = "FLAG")]
     file_watcher: Option<bool>,
 
+    /// Insert new tasks at the position given by the sorting of the list instead of appending them.
+    #[arg(long, value_name = "FLAG")]
+    sort_on_insert: Option<bool>,
+
+    /// Show tasks as raw todo.txt lines and add new tasks exactly as typed.
+    #[arg(long, value_name = "FLAG")]
+    raw_lines: Option<bool>,
+
+    /// Your name in `assignee:` and `by:` tags of a shared todo list, used to show only your tasks or only tasks of others.
+    #[arg(long, value_name = "STRING")]
+    my_name: Option<String>,
+
+    /// Metadata added by default to new tasks of a project, e.g. `client-x = "@work due:+14d"`.
+    #[clap(skip)]
+    project_defaults: Option<HashMap<String, String>>,
+
+    /// Named queries of task lists in the layout, e.g. `week = "due<7"` used as `List(week)`.
+    #[clap(skip)]
+    list_queries: Option<HashMap<String, String>>,
+
+    /// Rules escalating old or overdue tasks, e.g. `"overdue>7" = "(A)"` or `"age>30" = "#stale"`.
+    #[clap(skip)]
+    escalation_rules: Option<HashMap<String, String>>,
+
+    /// Rules styling and tagging tasks, e.g. `"priority >= B and overdue" = "style=alert; add @urgent"`.
+    #[clap(skip)]
+    task_rules: Option<HashMap<String, String>>,
+
+    /// Working hours of contexts, e.g. `office = "9-17 weekdays"`. Tasks of the context
+    /// are dimmed outside its hours.
+    #[clap(skip)]
+    context_hours: Option<HashMap<String, String>>,
+
+    /// Style of tasks outside the working hours of their contexts.
+    #[arg(long, value_name = "TEXT_STYLE")]
+    off_hours_style: Option<TextStyle>,
+
+    /// Enable the power mode, a compact layout and single key complete, defer and prioritize.
+    #[arg(long, value_name = "FLAG")]
+    power_mode: Option<bool>,
+
+    /// Command run with the task subject when a `remind:` time comes, e.g. `notify-send`. An empty string disables desktop notifications.
+    #[arg(long, value_name = "STRING")]
+    reminder_command: Option<String>,
+
+    /// Notify of tasks that became due or overdue and of the `notify:` times before deadlines.
+    #[arg(long, value_name = "FLAG")]
+    due_notifications: Option<bool>,
+
+    /// Command the copied text is piped to, e.g. `wl-copy`. The text is sent to the terminal by the OSC 52 escape sequence if it is empty.
+    #[arg(long, value_name = "STRING")]
+    clipboard_command: Option<String>,
+
+    /// Show the changes of the files in a popup to confirm them before a manual save.
+    #[arg(long, value_name = "FLAG")]
+    review_save: Option<bool>,
+
+    /// Look for a newer release on GitHub at startup.
+    #[arg(long, value_name = "FLAG")]
+    update_check: Option<bool>,
+
+    /// Ask before a task adds a project or context no task uses yet, offering similar existing ones.
+    #[arg(long, value_name = "FLAG")]
+    confirm_new_categories: Option<bool>,
+
+    /// Ask before deleting tasks.
+    #[arg(long, value_name = "FLAG")]
+    confirm_delete: Option<bool>,
+
+    /// Ask before archiving completed tasks to the done file.
+    #[arg(long, value_name = "FLAG")]
+    confirm_archive: Option<bool>,
+
+    /// Ask before quitting with unsaved changes, otherwise the changes are saved.
+    #[arg(long, value_name = "FLAG")]
+    confirm_quit: Option<bool>,
+
+    /// Preview the changes of the task rules and ask before they are made.
+    #[arg(long, value_name = "FLAG")]
+    confirm_task_rules: Option<bool>,
+
+    /// Show the profile, the todo file, the filters and the sorting in a line above the lists.
+    #[arg(long, value_name = "FLAG")]
+    breadcrumb: Option<bool>,
+
+    /// Show the input in the middle of the window only while a task is typed instead of a fixed pane.
+    #[arg(long, value_name = "FLAG")]
+    input_overlay: Option<bool>,
+
+    /// Always show the filter bar above the lists instead of only while it filters the tasks.
+    #[arg(long, value_name = "FLAG")]
+    filter_bar: Option<bool>,
+
+    /// Show the common actions of the focused list with their keys in a line at the bottom.
+    #[arg(long, value_name = "FLAG")]
+    action_bar: Option<bool>,
+
+    /// Show the focused list, the filters, the number of shown tasks, unsaved changes
+    /// and the last message in a line at the bottom.
+    #[arg(long, value_name = "FLAG")]
+    status_bar: Option<bool>,
+
+    /// Number the tasks of the lists.
+    #[arg(long, value_name = "FLAG")]
+    line_numbers: Option<bool>,
+
+    /// Scrollbar or position of the selected item drawn on the borders of lists.
+    #[arg(long, value_name = "SCROLL_INDICATOR")]
+    scroll_indicator: Option<ScrollIndicator>,
+
+    /// Wrap long tasks of the lists across multiple rows instead of cutting them.
+    #[arg(long, value_name = "FLAG")]
+    wrap_tasks: Option<bool>,
+
+    /// Number of spaces the continuation rows of wrapped tasks are indented by.
+    #[arg(long, value_name = "NUMBER")]
+    wrap_indent: Option<usize>,
+
+    /// Events shown in the action bar of task lists, separated by comma.
+    #[arg(long, value_name = "UI_EVENT", value_delimiter = ',')]
+    tasks_actions: Option<Vec<UIEvent>>,
+
+    /// Events shown in the action bar of category lists, separated by comma.
+    #[arg(long, value_name = "UI_EVENT", value_delimiter = ',')]
+    category_actions: Option<Vec<UIEvent>>,
+
+    /// Keep all changes in memory and never write the todo list to the disk.
+    #[arg(long, value_name = "FLAG", num_args = 0..=1, default_missing_value = "true")]
+    dry_run: Option<bool>,
+
+    /// Use the date instead of the current date, e.g. to preview due and overdue tasks.
+    #[serde(skip)]
+    #[arg(long, value_name = "YYYY-MM-DD")]
+    today: Option<NaiveDate>,
+
     #[arg(short = 'L', long, value_parser = parse_duration, value_name = "DURATION")]
     list_refresh_rate: Option<Duration>,
 
+    /// Seconds between redraws of panes by their names in the layout, e.g. `List = 60`.
+    #[clap(skip)]
+    widget_refresh_rates: Option<HashMap<String, u64>>,
+
+    /// Format of the clock in the input bar, e.g. `%H:%M`, the clock is hidden if it is empty.
+    #[arg(long, value_name = "FORMAT")]
+    clock_format: Option<String>,
+
     #[arg(short, long, value_name = "NUMBER")]
     list_shift: Option<usize>,
 
@@ -129,9 +499,76 @@ pub struct Config {
     #[arg(long, value_name = "TASK_SORT")]
     done_sort: Option<TaskSort>,
 
+    /// Sorting used in order to break ties of the pending sort, separated by comma.
+    #[arg(long, value_name = "TASK_SORT", value_delimiter = ',')]
+    pending_sort_ties: Option<Vec<TaskSort>>,
+
+    /// Sorting used in order to break ties of the done sort, separated by comma.
+    #[arg(long, value_name = "TASK_SORT", value_delimiter = ',')]
+    done_sort_ties: Option<Vec<TaskSort>>,
+
+    /// Sorting options the task lists cycle through, separated by comma.
+    #[arg(long, value_name = "TASK_SORT", value_delimiter = ',')]
+    sort_cycle: Option<Vec<TaskSort>>,
+
     #[arg(short, long, value_name = "STRING")]
     preview_format: Option<String>,
 
+    /// Show all details of the selected task in the preview instead of `preview_format`.
+    #[arg(long, value_name = "FLAG")]
+    preview_details: Option<bool>,
+
+    /// Template used to render tasks in the pending list.
+    /// Tasks are rendered with highlighted categories if not set.
+    #[arg(long, value_name = "STRING")]
+    pending_format: Option<String>,
+
+    /// Template used to render tasks in the done list.
+    /// Tasks are rendered with highlighted categories if not set.
+    #[arg(long, value_name = "STRING")]
+    done_format: Option<String>,
+
+    /// Template used to render tasks in Markdown and iCalendar exports.
+    /// Tasks are exported with their subject if not set.
+    #[arg(long, value_name = "STRING")]
+    export_task_format: Option<String>,
+
+    /// Display tasks in the task list widgets as a list or as a table.
+    #[arg(long, value_name = "TASK_VIEW")]
+    task_view: Option<TaskView>,
+
+    /// Columns displayed in the table view, separated by comma.
+    #[arg(long, value_name = "TASK_COLUMN", value_delimiter = ',')]
+    table_columns: Option<Vec<TaskColumn>>,
+
+    /// Groupings of tasks divided by divider rows in the task lists, separated by comma.
+    #[arg(long, value_name = "TASK_DIVIDER", value_delimiter = ',')]
+    list_dividers: Option<Vec<TaskDivider>>,
+
+    /// Message shown in the pending list if there are no tasks.
+    #[arg(long, value_name = "STRING")]
+    pending_empty_message: Option<String>,
+
+    /// Message shown in the done list if there are no tasks.
+    #[arg(long, value_name = "STRING")]
+    done_empty_message: Option<String>,
+
+    /// Message shown in task lists if no task matches active filters.
+    #[arg(long, value_name = "STRING")]
+    filtered_empty_message: Option<String>,
+
+    /// Message shown in category lists if there are no categories.
+    #[arg(long, value_name = "STRING")]
+    category_empty_message: Option<String>,
+
+    /// Show a bar with the share of open tasks next to every category.
+    #[arg(long, value_name = "FLAG")]
+    category_bars: Option<bool>,
+
+    /// Show the number of categories and how many of them have overdue tasks in the title of category panes.
+    #[arg(long, value_name = "FLAG")]
+    category_stats: Option<bool>,
+
     #[arg(long, value_name = "STRING")]
     layout: Option<String>,
 
@@ -147,6 +584,9 @@ pub struct Config {
     #[clap(skip)]
     window_keybind: Option<EventHandlerUI>,
 
+    #[clap(skip)]
+    review_keybind: Option<EventHandlerUI>,
+
     #[arg(long, value_name = "TEXT_STYLE")]
     category_style: Option<TextStyle>,
 
@@ -167,6 +607,10 @@ pub struct Config {
 
     #[clap(skip)]
     custom_category_style: Option<HashMap<String, TextStyle>>,
+
+    /// Named styles that can be used in templates and style settings instead of full style.
+    #[clap(skip)]
+    styles: Option<HashMap<String, NamedStyle>>,
 }
 
 impl Config {
@@ -175,7 +619,7 @@ impl Config {
         if let Ok(load_config) = config.load_config() {
             config = config.merge(load_config);
         }
-        config
+        config.apply_power_mode()
     }
 
     /// Loads the default configuration settings.
@@ -190,27 +634,76 @@ impl Config {
     }
 
     pub fn load_config(&self) -> io::Result<Self> {
-        match &self.config_path {
-            Some(path) => Config::load(path),
-            None => Self::load_default(),
-        }
+        Config::load(&self.get_config_path())
     }
 
-    /// Returns the default configuration file path based on environment variables.
-    ///
-    /// The configuration file path is determined based on the XDG_CONFIG_HOME and HOME environment variables.
+    /// Loads the configuration file again while the application is running. The command
+    /// line options are applied over it as at the start, the files of the opened todo list
+    /// stay the same, they change only by switching the profile.
     ///
     /// # Returns
     ///
-    /// A `Result` containing the default configuration file path (`Ok`) or an error (`Err`) if the path cannot be determined.
-    pub fn load_default() -> io::Result<Self> {
+    /// The reloaded configuration or an error if the file cannot be read, parsed
+    /// or has invalid rules, so the running configuration can be kept.
+    pub fn reload(&self) -> ToDoRes<Self> {
+        let path = self.get_config_path();
+        let buf = fs::read_to_string(&path).map_err(|err| ToDoIoError {
+            path: path.clone(),
+            err,
+        })?;
+        let loaded = Self::parse_config(&buf)
+            .map_err(|e| ToDoError::ParseConfig(path.display().to_string(), e.to_string()))?;
+        let config = Config::try_parse()
+            .unwrap_or_default()
+            .merge(loaded)
+            .apply_power_mode();
+        let config = Config {
+            config_path: self.config_path.clone(),
+            todo_path: self.todo_path.clone(),
+            archive_path: self.archive_path.clone(),
+            todo_url: self.todo_url.clone(),
+            dry_run: self.dry_run,
+            ..config
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks the rules of the configuration which are parsed when they are used.
+    pub fn validate(&self) -> ToDoRes<()> {
+        EscalationRule::parse_all(&self.get_escalation_rules())?;
+        TaskRule::parse_all(&self.get_task_rules(), &Styles::from(self))?;
+        self.get_priority_aging()?;
+        self.export_template()?;
+        Ok(())
+    }
+
+    /// Returns the path to the configuration file. If the path is not given,
+    /// the default path is determined based on the XDG_CONFIG_HOME and HOME environment variables.
+    pub fn get_config_path(&self) -> PathBuf {
+        self.config_path
+            .clone()
+            .unwrap_or_else(Self::default_config_path)
+    }
+
+    fn default_config_path() -> PathBuf {
         const CONFIG_FOLDER: &str = "/.config/";
         const CONFIG_NAME: &str = "todo-tui.toml";
         let path = var("XDG_CONFIG_HOME")
+            .map(|config| config + "/")
             .or_else(|_| var("HOME").map(|home| format!("{home}{CONFIG_FOLDER}")))
             .unwrap_or(String::from("~") + CONFIG_FOLDER)
             + CONFIG_NAME;
-        Ok(Self::load_from_buffer(File::open(path)?))
+        PathBuf::from(path)
+    }
+
+    /// Loads the configuration from the default configuration file.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the loaded configuration (`Ok`) or an error (`Err`) if loading fails.
+    pub fn load_default() -> io::Result<Self> {
+        Self::load(&Self::default_config_path())
     }
 
     /// Loads a configuration from a provided reader.
@@ -231,7 +724,7 @@ impl Config {
             log::error!("Cannot load config: {}", e);
             return Self::default();
         }
-        match toml::from_str(buf.as_str()) {
+        match Self::parse_config(buf.as_str()) {
             Ok(c) => c,
             Err(e) => {
                 log::error!("Cannot parse config: {}", e);
@@ -240,38 +733,145 @@ impl Config {
         }
     }
 
+    /// Parses the configuration and replaces references to named styles.
+    fn parse_config(buf: &str) -> Result<Self, Box<dyn Error>> {
+        let mut table: toml::Table = toml::from_str(buf)?;
+        NamedStyle::replace_references(&mut table)?;
+        Ok(toml::Value::Table(table).try_into()?)
+    }
+
     pub fn merge(self, other: Config) -> Self {
         Self {
             config_path: self.config_path.or(other.config_path),
             generate_autocomplete: self.generate_autocomplete.or(other.generate_autocomplete),
             export_config: self.export_config.or(other.export_config),
             export_default_config: self.export_default_config.or(other.export_default_config),
+            export_tasks: self.export_tasks.or(other.export_tasks),
+            import_tasks: self.import_tasks.or(other.import_tasks),
+            add: self.add.or(other.add),
             active_color: self.active_color.or(other.active_color),
+            color_support: self.color_support.or(other.color_support),
             init_widget: self.init_widget.or(other.init_widget),
+            init_filter: self.init_filter.or(other.init_filter),
             window_title: self.window_title.or(other.window_title),
             todo_path: self.todo_path.or(other.todo_path),
             archive_path: self.archive_path.or(other.archive_path),
+            todo_url: self.todo_url.or(other.todo_url),
+            done_path: self.done_path.or(other.done_path),
+            metadata_path: self.metadata_path.or(other.metadata_path),
+            profiles: self.profiles.or(other.profiles),
+            unsaved_path: self.unsaved_path.or(other.unsaved_path),
+            review_age: self.review_age.or(other.review_age),
+            quick_add_filters: self.quick_add_filters.or(other.quick_add_filters),
+            watch_config: self.watch_config.or(other.watch_config),
+            inbox_path: self.inbox_path.or(other.inbox_path),
+            signal_path: self.signal_path.or(other.signal_path),
+            activity_log_path: self.activity_log_path.or(other.activity_log_path),
+            trash_size: self.trash_size.or(other.trash_size),
+            trash_path: self.trash_path.or(other.trash_path),
+            input_history_path: self.input_history_path.or(other.input_history_path),
+            input_history_size: self.input_history_size.or(other.input_history_size),
+            scripts_path: self.scripts_path.or(other.scripts_path),
+            script_keybind: self.script_keybind.or(other.script_keybind),
+            projects_path: self.projects_path.or(other.projects_path),
+            deadline_warning_days: self.deadline_warning_days.or(other.deadline_warning_days),
+            forecast_days: self.forecast_days.or(other.forecast_days),
+            priority_boost_days: self.priority_boost_days.or(other.priority_boost_days),
+            priority_decay_days: self.priority_decay_days.or(other.priority_decay_days),
+            priority_aging_range: self.priority_aging_range.or(other.priority_aging_range),
+            pomodoro_minutes: self.pomodoro_minutes.or(other.pomodoro_minutes),
             priority_colors: self.priority_colors.or(other.priority_colors),
+            priority_labels: self.priority_labels.or(other.priority_labels),
+            urgency_weights: self.urgency_weights.or(other.urgency_weights),
             wrap_preview: self.wrap_preview.or(other.wrap_preview),
             list_active_color: self.list_active_color.or(other.list_active_color),
             pending_active_color: self.pending_active_color.or(other.pending_active_color),
             done_active_color: self.done_active_color.or(other.done_active_color),
+            category_active_color: self.category_active_color.or(other.category_active_color),
+            widget_styles: self.widget_styles.or(other.widget_styles),
+            widget_settings: self.widget_settings.or(other.widget_settings),
+            overdue_style: self.overdue_style.or(other.overdue_style),
+            due_today_style: self.due_today_style.or(other.due_today_style),
+            due_week_style: self.due_week_style.or(other.due_week_style),
+            list_zebra_color: self.list_zebra_color.or(other.list_zebra_color),
+            pending_zebra_color: self.pending_zebra_color.or(other.pending_zebra_color),
+            done_zebra_color: self.done_zebra_color.or(other.done_zebra_color),
+            category_zebra_color: self.category_zebra_color.or(other.category_zebra_color),
+            search_match_style: self.search_match_style.or(other.search_match_style),
+            divider_style: self.divider_style.or(other.divider_style),
+            scroll_indicator_style: self.scroll_indicator_style.or(other.scroll_indicator_style),
+            scratch_style: self.scratch_style.or(other.scratch_style),
+            marked_style: self.marked_style.or(other.marked_style),
             autosave_duration: self.autosave_duration.or(other.autosave_duration),
+            save_delay: self.save_delay.or(other.save_delay),
+            save_state: self.save_state.or(other.save_state),
             save_state_path: self.save_state_path.or(other.save_state_path),
             log_file: self.log_file.or(other.log_file),
             log_format: self.log_format.or(other.log_format),
             log_level: self.log_level.or(other.log_level),
             file_watcher: self.file_watcher.or(other.file_watcher),
+            sort_on_insert: self.sort_on_insert.or(other.sort_on_insert),
+            raw_lines: self.raw_lines.or(other.raw_lines),
+            my_name: self.my_name.or(other.my_name),
+            project_defaults: self.project_defaults.or(other.project_defaults),
+            list_queries: self.list_queries.or(other.list_queries),
+            escalation_rules: self.escalation_rules.or(other.escalation_rules),
+            task_rules: self.task_rules.or(other.task_rules),
+            context_hours: self.context_hours.or(other.context_hours),
+            off_hours_style: self.off_hours_style.or(other.off_hours_style),
+            power_mode: self.power_mode.or(other.power_mode),
+            reminder_command: self.reminder_command.or(other.reminder_command),
+            due_notifications: self.due_notifications.or(other.due_notifications),
+            clipboard_command: self.clipboard_command.or(other.clipboard_command),
+            review_save: self.review_save.or(other.review_save),
+            update_check: self.update_check.or(other.update_check),
+            confirm_new_categories: self.confirm_new_categories.or(other.confirm_new_categories),
+            confirm_delete: self.confirm_delete.or(other.confirm_delete),
+            confirm_archive: self.confirm_archive.or(other.confirm_archive),
+            confirm_quit: self.confirm_quit.or(other.confirm_quit),
+            confirm_task_rules: self.confirm_task_rules.or(other.confirm_task_rules),
+            breadcrumb: self.breadcrumb.or(other.breadcrumb),
+            input_overlay: self.input_overlay.or(other.input_overlay),
+            filter_bar: self.filter_bar.or(other.filter_bar),
+            action_bar: self.action_bar.or(other.action_bar),
+            status_bar: self.status_bar.or(other.status_bar),
+            line_numbers: self.line_numbers.or(other.line_numbers),
+            scroll_indicator: self.scroll_indicator.or(other.scroll_indicator),
+            wrap_tasks: self.wrap_tasks.or(other.wrap_tasks),
+            wrap_indent: self.wrap_indent.or(other.wrap_indent),
+            tasks_actions: self.tasks_actions.or(other.tasks_actions),
+            category_actions: self.category_actions.or(other.category_actions),
+            dry_run: self.dry_run.or(other.dry_run),
+            today: self.today.or(other.today),
             list_refresh_rate: self.list_refresh_rate.or(other.list_refresh_rate),
+            widget_refresh_rates: self.widget_refresh_rates.or(other.widget_refresh_rates),
+            clock_format: self.clock_format.or(other.clock_format),
             list_shift: self.list_shift.or(other.list_shift),
             pending_sort: self.pending_sort.or(other.pending_sort),
             done_sort: self.done_sort.or(other.done_sort),
+            pending_sort_ties: self.pending_sort_ties.or(other.pending_sort_ties),
+            done_sort_ties: self.done_sort_ties.or(other.done_sort_ties),
+            sort_cycle: self.sort_cycle.or(other.sort_cycle),
             preview_format: self.preview_format.or(other.preview_format),
+            preview_details: self.preview_details.or(other.preview_details),
+            pending_format: self.pending_format.or(other.pending_format),
+            done_format: self.done_format.or(other.done_format),
+            export_task_format: self.export_task_format.or(other.export_task_format),
+            task_view: self.task_view.or(other.task_view),
+            table_columns: self.table_columns.or(other.table_columns),
+            list_dividers: self.list_dividers.or(other.list_dividers),
+            pending_empty_message: self.pending_empty_message.or(other.pending_empty_message),
+            done_empty_message: self.done_empty_message.or(other.done_empty_message),
+            filtered_empty_message: self.filtered_empty_message.or(other.filtered_empty_message),
+            category_empty_message: self.category_empty_message.or(other.category_empty_message),
+            category_bars: self.category_bars.or(other.category_bars),
+            category_stats: self.category_stats.or(other.category_stats),
             layout: self.layout.or(other.layout),
             tasks_keybind: self.tasks_keybind.or(other.tasks_keybind),
             category_keybind: self.category_keybind.or(other.category_keybind),
             list_keybind: self.list_keybind.or(other.list_keybind),
             window_keybind: self.window_keybind.or(other.window_keybind),
+            review_keybind: self.review_keybind.or(other.review_keybind),
             category_style: self.category_style.or(other.category_style),
             category_select_style: self.category_select_style.or(other.category_select_style),
             category_remove_style: self.category_remove_style.or(other.category_remove_style),
@@ -279,6 +879,7 @@ impl Config {
             contexts_style: self.contexts_style.or(other.contexts_style),
             hashtags_style: self.hashtags_style.or(other.hashtags_style),
             custom_category_style: self.custom_category_style.or(other.custom_category_style),
+            styles: self.styles.or(other.styles),
         }
     }
 
@@ -288,32 +889,132 @@ impl Config {
             generate_autocomplete: self.generate_autocomplete.clone(),
             export_config: self.export_config.clone(),
             export_default_config: self.export_default_config.clone(),
+            export_tasks: self.export_tasks.clone(),
+            import_tasks: self.import_tasks.clone(),
+            add: self.add.clone(),
             active_color: Some(self.get_active_color()),
+            color_support: Some(self.get_color_support()),
             init_widget: Some(self.get_init_widget()),
+            init_filter: self.get_init_filter(),
             window_title: Some(self.get_window_title()),
             todo_path: Some(self.get_todo_path()),
             archive_path: self.get_archive_path(),
+            todo_url: self.get_todo_url(),
+            done_path: Some(self.get_done_path()),
+            metadata_path: Some(self.get_metadata_path()),
+            profiles: Some(self.get_profiles()),
+            unsaved_path: Some(self.get_unsaved_path()),
+            review_age: Some(self.get_review_age()),
+            quick_add_filters: Some(self.get_quick_add_filters()),
+            watch_config: Some(self.get_watch_config()),
+            inbox_path: self.get_inbox_path(),
+            signal_path: self.get_signal_path(),
+            activity_log_path: self.get_activity_log_path(),
+            trash_size: Some(self.get_trash_size()),
+            trash_path: self.get_trash_path(),
+            input_history_path: Some(self.get_input_history_path()),
+            input_history_size: Some(self.get_input_history_size()),
+            scripts_path: Some(self.get_scripts_path()),
+            script_keybind: Some(self.get_script_keybind()),
+            projects_path: Some(self.get_projects_path()),
+            deadline_warning_days: Some(self.get_deadline_warning_days()),
+            forecast_days: Some(self.get_forecast_days()),
+            priority_boost_days: Some(self.get_priority_boost_days()),
+            priority_decay_days: Some(self.get_priority_decay_days()),
+            priority_aging_range: Some(self.get_priority_aging_range()),
+            pomodoro_minutes: Some(self.get_pomodoro_minutes()),
             priority_colors: Some(self.get_priority_colors()),
+            priority_labels: Some(self.get_priority_labels()),
+            urgency_weights: Some(self.get_urgency_weights()),
             wrap_preview: Some(self.get_wrap_preview()),
             list_active_color: Some(self.get_list_active_color()),
             pending_active_color: Some(self.get_pending_active_color()),
             done_active_color: Some(self.get_done_active_color()),
+            category_active_color: Some(self.get_category_active_color()),
+            widget_styles: Some(self.widget_styles.clone().unwrap_or_default()),
+            widget_settings: Some(self.widget_settings.clone().unwrap_or_default()),
+            overdue_style: Some(self.get_overdue_style()),
+            due_today_style: Some(self.get_due_today_style()),
+            due_week_style: Some(self.get_due_week_style()),
+            list_zebra_color: Some(self.get_list_zebra_color()),
+            pending_zebra_color: Some(self.get_pending_zebra_color()),
+            done_zebra_color: Some(self.get_done_zebra_color()),
+            category_zebra_color: Some(self.get_category_zebra_color()),
+            search_match_style: Some(self.get_search_match_style()),
+            divider_style: Some(self.get_divider_style()),
+            scroll_indicator_style: Some(self.get_scroll_indicator_style()),
+            scratch_style: Some(self.get_scratch_style()),
+            marked_style: Some(self.get_marked_style()),
             autosave_duration: Some(self.get_autosave_duration()),
+            save_delay: Some(self.get_save_delay()),
+            save_state: Some(self.get_save_state()),
             save_state_path: self.get_save_state_path(),
             log_file: Some(self.get_log_file()),
             log_format: Some(self.get_log_format()),
             log_level: Some(self.get_log_level()),
             file_watcher: Some(self.get_file_watcher()),
+            sort_on_insert: Some(self.get_sort_on_insert()),
+            raw_lines: Some(self.get_raw_lines()),
+            my_name: self.get_my_name(),
+            project_defaults: Some(self.get_project_defaults()),
+            list_queries: Some(self.get_list_queries()),
+            escalation_rules: Some(self.get_escalation_rules()),
+            task_rules: Some(self.get_task_rules()),
+            context_hours: Some(self.get_context_hours()),
+            off_hours_style: Some(self.get_off_hours_style()),
+            power_mode: Some(self.get_power_mode()),
+            reminder_command: Some(self.get_reminder_command()),
+            due_notifications: Some(self.get_due_notifications()),
+            clipboard_command: Some(self.get_clipboard_command()),
+            review_save: Some(self.get_review_save()),
+            update_check: Some(self.get_update_check()),
+            confirm_new_categories: Some(self.get_confirm_new_categories()),
+            confirm_delete: Some(self.get_confirm_delete()),
+            confirm_archive: Some(self.get_confirm_archive()),
+            confirm_quit: Some(self.get_confirm_quit()),
+            confirm_task_rules: Some(self.get_confirm_task_rules()),
+            breadcrumb: Some(self.get_breadcrumb()),
+            input_overlay: Some(self.get_input_overlay()),
+            filter_bar: Some(self.get_filter_bar()),
+            action_bar: Some(self.get_action_bar()),
+            status_bar: Some(self.get_status_bar()),
+            line_numbers: Some(self.get_line_numbers()),
+            scroll_indicator: Some(self.get_scroll_indicator()),
+            wrap_tasks: Some(self.get_wrap_tasks()),
+            wrap_indent: Some(self.get_wrap_indent()),
+            tasks_actions: Some(self.get_tasks_actions()),
+            category_actions: Some(self.get_category_actions()),
+            dry_run: Some(self.get_dry_run()),
+            today: self.today,
             list_refresh_rate: Some(self.get_list_refresh_rate()),
+            widget_refresh_rates: Some(self.widget_refresh_rates.clone().unwrap_or_default()),
+            clock_format: Some(self.get_clock_format()),
             list_shift: Some(self.get_list_shift()),
             pending_sort: Some(self.get_pending_sort()),
             done_sort: Some(self.get_done_sort()),
+            pending_sort_ties: Some(self.get_pending_sort_ties()),
+            done_sort_ties: Some(self.get_done_sort_ties()),
+            sort_cycle: Some(self.get_sort_cycle()),
             preview_format: Some(self.get_preview_format()),
+            preview_details: Some(self.get_preview_details()),
+            pending_format: self.get_pending_format(),
+            done_format: self.get_done_format(),
+            export_task_format: self.get_export_task_format(),
+            task_view: Some(self.get_task_view()),
+            table_columns: Some(self.get_table_columns()),
+            list_dividers: Some(self.get_list_dividers()),
+            pending_empty_message: Some(self.get_pending_empty_message()),
+            done_empty_message: Some(self.get_done_empty_message()),
+            filtered_empty_message: Some(self.get_filtered_empty_message()),
+            category_empty_message: Some(self.get_category_empty_message()),
+            category_bars: Some(self.get_category_bars()),
+            category_stats: Some(self.get_category_stats()),
             layout: Some(self.get_layout()),
             tasks_keybind: Some(self.get_tasks_keybind()),
             category_keybind: Some(self.get_category_keybind()),
             list_keybind: Some(self.get_list_keybind()),
             window_keybind: Some(self.get_window_keybind()),
+            review_keybind: Some(self.get_review_keybind()),
             category_style: Some(self.get_category_style()),
             category_select_style: Some(self.get_category_select_style()),
             category_remove_style: Some(self.get_category_remove_style()),
@@ -321,6 +1022,7 @@ impl Config {
             contexts_style: Some(self.get_contexts_style()),
             hashtags_style: Some(self.get_hashtags_style()),
             custom_category_style: Some(self.get_custom_category_style()),
+            styles: Some(self.get_styles()),
         }
     }
 
@@ -349,17 +1051,112 @@ impl Config {
             )?;
             ret = true
         }
+        if let Some(path) = &self.export_tasks {
+            let format = ExportFormat::from_path(path)?;
+            let todo = Arc::new(Mutex::new(ToDo::new(
+                ToDoConfig::from(self),
+                Styles::from(self),
+            )));
+            let mut file_worker =
+                FileWorker::new(self.get_todo_path(), self.get_archive_path(), todo.clone());
+            if let Some(url) = self.get_todo_url() {
+                file_worker.set_remote_url(&url)?;
+            }
+            file_worker.load()?;
+            let mut todo = todo.lock().unwrap();
+            if let Some(name) = self.get_init_filter() {
+                todo.set_filters(self.get_list_query(&name)?.filters());
+            }
+            let template = self.export_template()?;
+            fs::write(
+                path,
+                todo.export(format, self.get_clock().now(), template.as_ref()),
+            )?;
+            ret = true
+        }
+        if let Some(path) = &self.import_tasks {
+            self.import_tasks(path)?;
+            ret = true
+        }
+        if let Some(task) = &self.add {
+            self.add_task(task)?;
+            ret = true
+        }
         Ok(ret)
     }
 
+    /// Adds the task given by `--add`. It is forwarded to the running instance editing
+    /// the todo file, so the instance does not overwrite it, or appended to the file.
+    fn add_task(&self, task: &str) -> Result<(), Box<dyn Error>> {
+        let todo_path = self.get_todo_path();
+        if let Some(running) = RunningInstance::find(&todo_path)? {
+            running.forward(&Signal::Add(task.to_string()))?;
+            println!("The task was sent to the running instance {}.", running.pid);
+            return Ok(());
+        }
+        let task = preprocess(task, &ToDoConfig::from(self), self.get_clock().today())?;
+        self.append_tasks(&[task.to_string()])
+    }
+
+    /// Imports the tasks of the file given by `--import-tasks`. Like `--add`, the tasks
+    /// are forwarded to the running instance editing the todo file or appended to the file.
+    fn import_tasks(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let format = ImportFormat::from_path(path)?;
+        let tasks = format.parse(&fs::read_to_string(path)?, self.get_clock().today())?;
+        if let Some(running) = RunningInstance::find(&self.get_todo_path())? {
+            for task in &tasks {
+                running.forward(&Signal::Add(task.clone()))?;
+            }
+            println!(
+                "{} tasks were sent to the running instance {}.",
+                tasks.len(),
+                running.pid
+            );
+            return Ok(());
+        }
+        self.append_tasks(&tasks)?;
+        println!("{} tasks were imported.", tasks.len());
+        Ok(())
+    }
+
+    /// Appends the lines of the tasks to the todo file, in the dry run they are printed instead.
+    fn append_tasks(&self, tasks: &[String]) -> Result<(), Box<dyn Error>> {
+        if self.get_dry_run() {
+            tasks.iter().for_each(|task| println!("{task}"));
+            return Ok(());
+        }
+        let todo_path = self.get_todo_path();
+        let mut content = match fs::read_to_string(&todo_path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        for task in tasks {
+            content.push_str(&format!("{task}\n"));
+        }
+        fs::write(&todo_path, content)?;
+        Ok(())
+    }
+
     pub fn get_active_color(&self) -> Color {
         self.active_color.unwrap_or(Color::Red)
     }
 
+    pub fn get_color_support(&self) -> ColorSupport {
+        self.color_support.unwrap_or_default()
+    }
+
     pub fn get_init_widget(&self) -> WidgetType {
         self.init_widget.unwrap_or(WidgetType::List)
     }
 
+    pub fn get_init_filter(&self) -> Option<String> {
+        self.init_filter.clone()
+    }
+
     pub fn get_window_title(&self) -> String {
         self.window_title
             .clone()
@@ -376,10 +1173,184 @@ impl Config {
         self.archive_path.clone()
     }
 
+    pub fn get_todo_url(&self) -> Option<String> {
+        self.todo_url.clone()
+    }
+
+    pub fn get_review_age(&self) -> usize {
+        self.review_age.unwrap_or(30)
+    }
+
+    pub fn get_quick_add_filters(&self) -> bool {
+        self.quick_add_filters.unwrap_or(true)
+    }
+
+    pub fn get_watch_config(&self) -> bool {
+        self.watch_config.unwrap_or(true)
+    }
+
+    pub fn get_inbox_path(&self) -> Option<String> {
+        self.inbox_path.clone()
+    }
+
+    pub fn get_signal_path(&self) -> Option<String> {
+        self.signal_path.clone()
+    }
+
+    pub fn get_activity_log_path(&self) -> Option<String> {
+        self.activity_log_path.clone()
+    }
+
+    pub fn get_trash_size(&self) -> usize {
+        self.trash_size.unwrap_or(50)
+    }
+
+    pub fn get_trash_path(&self) -> Option<String> {
+        self.trash_path.clone()
+    }
+
+    pub fn get_input_history_path(&self) -> String {
+        self.input_history_path.clone().unwrap_or_else(|| {
+            self.get_config_path()
+                .with_file_name("todo-tui-history.txt")
+                .to_string_lossy()
+                .to_string()
+        })
+    }
+
+    pub fn get_input_history_size(&self) -> usize {
+        self.input_history_size.unwrap_or(100)
+    }
+
+    pub fn get_scripts_path(&self) -> String {
+        self.scripts_path.clone().unwrap_or_else(|| {
+            self.get_config_path()
+                .with_file_name("todo-tui-scripts")
+                .to_string_lossy()
+                .to_string()
+        })
+    }
+
+    pub fn get_script_keybind(&self) -> HashMap<String, String> {
+        self.script_keybind.clone().unwrap_or_default()
+    }
+
+    pub fn get_done_path(&self) -> String {
+        self.done_path.clone().unwrap_or_else(|| {
+            Path::new(&self.get_todo_path())
+                .with_file_name("done.txt")
+                .to_string_lossy()
+                .to_string()
+        })
+    }
+
+    pub fn get_metadata_path(&self) -> String {
+        self.metadata_path.clone().unwrap_or_else(|| {
+            Path::new(&self.get_todo_path())
+                .with_extension("meta.toml")
+                .to_string_lossy()
+                .to_string()
+        })
+    }
+
+    pub fn get_profiles(&self) -> HashMap<String, String> {
+        self.profiles.clone().unwrap_or_default()
+    }
+
+    /// Gets the configuration for another todo file, paths derived from the path
+    /// of the todo file, e.g. the done file, follow it unless they are set.
+    ///
+    /// # Arguments
+    ///
+    /// * `todo_path` - The path to the todo file.
+    /// * `archive_path` - The path to the archive file of the todo file.
+    pub fn with_todo_file(&self, todo_path: &str, archive_path: Option<&str>) -> Config {
+        Config {
+            todo_path: Some(todo_path.to_string()),
+            archive_path: archive_path.map(String::from),
+            todo_url: None,
+            ..self.clone()
+        }
+    }
+
+    /// Gets the configuration keeping the changes only in memory, e.g. for the todo file
+    /// edited by another instance.
+    pub fn with_dry_run(&self) -> Config {
+        Config {
+            dry_run: Some(true),
+            ..self.clone()
+        }
+    }
+
+    pub fn get_unsaved_path(&self) -> String {
+        self.unsaved_path.clone().unwrap_or_else(|| {
+            std::env::temp_dir()
+                .join("todotxt-tui-unsaved.txt")
+                .to_string_lossy()
+                .to_string()
+        })
+    }
+
+    pub fn get_projects_path(&self) -> String {
+        self.projects_path.clone().unwrap_or_else(|| {
+            Path::new(&self.get_todo_path())
+                .with_file_name("projects.toml")
+                .to_string_lossy()
+                .to_string()
+        })
+    }
+
+    pub fn get_deadline_warning_days(&self) -> usize {
+        self.deadline_warning_days.unwrap_or(7)
+    }
+
+    pub fn get_forecast_days(&self) -> usize {
+        self.forecast_days.unwrap_or(28).max(1)
+    }
+
+    pub fn get_priority_boost_days(&self) -> usize {
+        self.priority_boost_days.unwrap_or(0)
+    }
+
+    pub fn get_priority_decay_days(&self) -> usize {
+        self.priority_decay_days.unwrap_or(0)
+    }
+
+    pub fn get_priority_aging_range(&self) -> String {
+        self.priority_aging_range
+            .clone()
+            .unwrap_or_else(|| String::from("C-B"))
+    }
+
+    pub fn get_pomodoro_minutes(&self) -> usize {
+        self.pomodoro_minutes.unwrap_or(0)
+    }
+
+    pub fn get_priority_aging(&self) -> ToDoRes<PriorityAging> {
+        PriorityAging::new(
+            self.get_priority_boost_days(),
+            self.get_priority_decay_days(),
+            &self.get_priority_aging_range(),
+        )
+    }
+
     fn get_priority_colors(&self) -> TextStyleList {
         self.priority_colors.clone().unwrap_or_default()
     }
 
+    pub fn get_priority_labels(&self) -> HashMap<String, String> {
+        self.priority_labels
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(priority, label)| (priority.to_uppercase(), label))
+            .collect()
+    }
+
+    pub fn get_urgency_weights(&self) -> HashMap<String, f64> {
+        self.urgency_weights.clone().unwrap_or_default()
+    }
+
     pub fn get_wrap_preview(&self) -> bool {
         self.wrap_preview.unwrap_or(true)
     }
@@ -397,12 +1368,111 @@ impl Config {
         self.done_active_color.unwrap_or_default()
     }
 
+    pub fn get_category_active_color(&self) -> TextStyle {
+        self.category_active_color
+            .unwrap_or(TextStyle::default().bg(Color::LightRed))
+    }
+
+    /// Gets the styles of the widget from `widget_styles`, the names are not case sensitive.
+    pub fn get_widget_style(&self, widget_type: &WidgetType) -> WidgetStyle {
+        self.widget_styles
+            .iter()
+            .flatten()
+            .find(|(name, _)| WidgetType::from_str(name).is_ok_and(|t| t == *widget_type))
+            .map(|(_, style)| *style)
+            .unwrap_or_default()
+    }
+
+    /// Gets the settings of the widget from `widget_settings`, the names are not case sensitive.
+    pub fn get_widget_settings(&self, widget_type: &WidgetType) -> WidgetSettings {
+        self.widget_settings
+            .iter()
+            .flatten()
+            .find(|(name, _)| WidgetType::from_str(name).is_ok_and(|t| t == *widget_type))
+            .map(|(_, settings)| settings.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn get_overdue_style(&self) -> TextStyle {
+        self.overdue_style.unwrap_or_default()
+    }
+
+    pub fn get_due_today_style(&self) -> TextStyle {
+        self.due_today_style.unwrap_or_default()
+    }
+
+    pub fn get_due_week_style(&self) -> TextStyle {
+        self.due_week_style.unwrap_or_default()
+    }
+
+    pub fn get_search_match_style(&self) -> TextStyle {
+        self.search_match_style.unwrap_or_else(|| {
+            TextStyle::default()
+                .fg(Color::Yellow)
+                .modifier(TextModifier::Underlined)
+        })
+    }
+
+    pub fn get_divider_style(&self) -> TextStyle {
+        self.divider_style
+            .unwrap_or_else(|| TextStyle::default().fg(Color::DarkGray))
+    }
+
+    pub fn get_scroll_indicator_style(&self) -> TextStyle {
+        self.scroll_indicator_style.unwrap_or_default()
+    }
+
+    pub fn get_scratch_style(&self) -> TextStyle {
+        self.scratch_style.unwrap_or_else(|| {
+            TextStyle::default()
+                .fg(Color::DarkGray)
+                .modifier(TextModifier::Italic)
+        })
+    }
+
+    pub fn get_marked_style(&self) -> TextStyle {
+        self.marked_style.unwrap_or_else(|| {
+            TextStyle::default()
+                .bg(Color::DarkGray)
+                .modifier(TextModifier::Bold)
+        })
+    }
+
+    pub fn get_list_zebra_color(&self) -> TextStyle {
+        self.list_zebra_color.unwrap_or_default()
+    }
+
+    pub fn get_pending_zebra_color(&self) -> TextStyle {
+        self.pending_zebra_color.unwrap_or_default()
+    }
+
+    pub fn get_done_zebra_color(&self) -> TextStyle {
+        self.done_zebra_color.unwrap_or_default()
+    }
+
+    pub fn get_category_zebra_color(&self) -> TextStyle {
+        self.category_zebra_color.unwrap_or_default()
+    }
+
     pub fn get_autosave_duration(&self) -> Duration {
         self.autosave_duration.unwrap_or(Duration::from_secs(900))
     }
 
+    pub fn get_save_delay(&self) -> Duration {
+        self.save_delay.unwrap_or(Duration::from_secs(2))
+    }
+
+    pub fn get_save_state(&self) -> bool {
+        self.save_state.unwrap_or(true)
+    }
+
+    /// Gets the file the UI state is saved to, `None` if the state is not saved.
     pub fn get_save_state_path(&self) -> Option<PathBuf> {
-        self.save_state_path.clone()
+        self.get_save_state().then(|| {
+            self.save_state_path
+                .clone()
+                .unwrap_or_else(|| self.get_config_path().with_file_name("todo-tui-state.toml"))
+        })
     }
 
     fn get_log_file(&self) -> PathBuf {
@@ -423,10 +1493,186 @@ impl Config {
         self.file_watcher.unwrap_or(true)
     }
 
+    pub fn get_sort_on_insert(&self) -> bool {
+        self.sort_on_insert.unwrap_or(false)
+    }
+
+    pub fn get_raw_lines(&self) -> bool {
+        self.raw_lines.unwrap_or(false)
+    }
+
+    pub fn get_my_name(&self) -> Option<String> {
+        self.my_name.clone()
+    }
+
+    pub fn get_project_defaults(&self) -> HashMap<String, String> {
+        self.project_defaults.clone().unwrap_or_default()
+    }
+
+    pub fn get_list_queries(&self) -> HashMap<String, String> {
+        self.list_queries.clone().unwrap_or_default()
+    }
+
+    /// Gets the query of `list_queries` with the name, the case of the name is ignored.
+    pub fn get_list_query(&self, name: &str) -> ToDoRes<TaskQuery> {
+        self.get_list_queries()
+            .into_iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .ok_or_else(|| ToDoError::UnknownQuery(name.to_string()))?
+            .1
+            .parse()
+    }
+
+    pub fn get_escalation_rules(&self) -> HashMap<String, String> {
+        self.escalation_rules.clone().unwrap_or_default()
+    }
+
+    pub fn get_task_rules(&self) -> HashMap<String, String> {
+        self.task_rules.clone().unwrap_or_default()
+    }
+
+    pub fn get_context_hours(&self) -> HashMap<String, String> {
+        self.context_hours.clone().unwrap_or_default()
+    }
+
+    pub fn get_off_hours_style(&self) -> TextStyle {
+        self.off_hours_style
+            .unwrap_or_else(|| TextStyle::default().modifier(TextModifier::Dim))
+    }
+
+    pub fn get_power_mode(&self) -> bool {
+        self.power_mode.unwrap_or(false)
+    }
+
+    pub fn get_due_notifications(&self) -> bool {
+        self.due_notifications.unwrap_or(true)
+    }
+
+    pub fn get_reminder_command(&self) -> String {
+        self.reminder_command
+            .clone()
+            .unwrap_or_else(|| String::from("notify-send"))
+    }
+
+    pub fn get_clipboard_command(&self) -> String {
+        self.clipboard_command.clone().unwrap_or_default()
+    }
+
+    pub fn get_review_save(&self) -> bool {
+        self.review_save.unwrap_or(false)
+    }
+
+    pub fn get_update_check(&self) -> bool {
+        self.update_check.unwrap_or(false)
+    }
+
+    pub fn get_confirm_new_categories(&self) -> bool {
+        self.confirm_new_categories.unwrap_or(false)
+    }
+
+    pub fn get_confirm_delete(&self) -> bool {
+        self.confirm_delete.unwrap_or(true)
+    }
+
+    pub fn get_confirm_archive(&self) -> bool {
+        self.confirm_archive.unwrap_or(true)
+    }
+
+    pub fn get_confirm_quit(&self) -> bool {
+        self.confirm_quit.unwrap_or(true)
+    }
+
+    pub fn get_confirm_task_rules(&self) -> bool {
+        self.confirm_task_rules.unwrap_or(false)
+    }
+
+    pub fn get_breadcrumb(&self) -> bool {
+        self.breadcrumb.unwrap_or(true)
+    }
+
+    pub fn get_input_overlay(&self) -> bool {
+        self.input_overlay.unwrap_or(false)
+    }
+
+    pub fn get_filter_bar(&self) -> bool {
+        self.filter_bar.unwrap_or(false)
+    }
+
+    pub fn get_action_bar(&self) -> bool {
+        self.action_bar.unwrap_or(false)
+    }
+
+    pub fn get_status_bar(&self) -> bool {
+        self.status_bar.unwrap_or(false)
+    }
+
+    pub fn get_line_numbers(&self) -> bool {
+        self.line_numbers.unwrap_or(false)
+    }
+
+    pub fn get_scroll_indicator(&self) -> ScrollIndicator {
+        self.scroll_indicator.unwrap_or_default()
+    }
+
+    pub fn get_wrap_tasks(&self) -> bool {
+        self.wrap_tasks.unwrap_or(false)
+    }
+
+    pub fn get_wrap_indent(&self) -> usize {
+        self.wrap_indent.unwrap_or(2)
+    }
+
+    pub fn get_tasks_actions(&self) -> Vec<UIEvent> {
+        self.tasks_actions.clone().unwrap_or(vec![
+            UIEvent::MoveItem,
+            UIEvent::EditMode,
+            UIEvent::DeferItem,
+            UIEvent::PriorityUp,
+            UIEvent::PriorityDown,
+            UIEvent::InsertMode,
+            UIEvent::RemoveItem,
+        ])
+    }
+
+    pub fn get_category_actions(&self) -> Vec<UIEvent> {
+        self.category_actions.clone().unwrap_or(vec![
+            UIEvent::Select,
+            UIEvent::Remove,
+            UIEvent::ToggleFilterMode,
+        ])
+    }
+
+    pub fn get_dry_run(&self) -> bool {
+        self.dry_run.unwrap_or(false)
+    }
+
+    pub fn get_clock(&self) -> Clock {
+        self.today.map(Clock::fixed).unwrap_or_default()
+    }
+
     pub fn get_list_refresh_rate(&self) -> Duration {
         self.list_refresh_rate.unwrap_or(Duration::from_secs(5))
     }
 
+    /// Gets the interval the widget is redrawn at from `widget_refresh_rates`,
+    /// the names are not case sensitive.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the widget is redrawn only when something changes.
+    pub fn get_widget_refresh_rate(&self, widget_type: &WidgetType) -> Option<Duration> {
+        self.widget_refresh_rates
+            .iter()
+            .flatten()
+            .find(|(name, _)| WidgetType::from_str(name).is_ok_and(|t| t == *widget_type))
+            .filter(|(_, secs)| **secs > 0)
+            .map(|(_, secs)| Duration::from_secs(*secs))
+    }
+
+    pub fn get_clock_format(&self) -> String {
+        self.clock_format.clone().unwrap_or_default()
+    }
+
     pub fn get_list_shift(&self) -> usize {
         self.list_shift.unwrap_or(4)
     }
@@ -436,7 +1682,26 @@ impl Config {
     }
 
     pub fn get_done_sort(&self) -> TaskSort {
-        self.done_sort.unwrap_or(TaskSort::None)
+        self.done_sort.unwrap_or(TaskSort::CompletedReverse)
+    }
+
+    pub fn get_pending_sort_ties(&self) -> Vec<TaskSort> {
+        self.pending_sort_ties.clone().unwrap_or_default()
+    }
+
+    pub fn get_done_sort_ties(&self) -> Vec<TaskSort> {
+        self.done_sort_ties.clone().unwrap_or_default()
+    }
+
+    pub fn get_sort_cycle(&self) -> Vec<TaskSort> {
+        self.sort_cycle.clone().unwrap_or(vec![
+            TaskSort::None,
+            TaskSort::Priority,
+            TaskSort::Due,
+            TaskSort::Created,
+            TaskSort::Project,
+            TaskSort::Alphanumeric,
+        ])
     }
 
     pub fn get_preview_format(&self) -> String {
@@ -449,34 +1714,110 @@ Link: $link",
         ))
     }
 
-    pub fn get_layout(&self) -> String {
-        self.layout.clone().unwrap_or(String::from(
-            "
-[
-    Direction: Horizontal,
-    Size: 50%,
-    [
-        List: 20%,
-        Preview: 80%,
-    ],
-    [ Direction: Vertical,
-      Done: 60%,
-      [ 
-        Contexts: 10%,
-        Projects: 90%,
-      ],
-    ],
-]
-",
+    pub fn get_preview_details(&self) -> bool {
+        self.preview_details.unwrap_or(false)
+    }
+
+    pub fn get_pending_format(&self) -> Option<String> {
+        self.pending_format.clone()
+    }
+
+    pub fn get_done_format(&self) -> Option<String> {
+        self.done_format.clone()
+    }
+
+    pub fn get_export_task_format(&self) -> Option<String> {
+        self.export_task_format.clone()
+    }
+
+    /// Parses the template of tasks in exports.
+    ///
+    /// # Returns
+    ///
+    /// `None` if `export_task_format` is not set.
+    pub fn export_template(&self) -> ToDoRes<Option<TemplateParser>> {
+        self.get_export_task_format()
+            .map(|template| TemplateParser::new(&template, Styles::from(self)))
+            .transpose()
+    }
+
+    pub fn get_task_view(&self) -> TaskView {
+        self.task_view.unwrap_or_default()
+    }
+
+    pub fn get_table_columns(&self) -> Vec<TaskColumn> {
+        self.table_columns
+            .clone()
+            .unwrap_or_else(TaskColumn::default_columns)
+    }
+
+    pub fn get_list_dividers(&self) -> Vec<TaskDivider> {
+        self.list_dividers.clone().unwrap_or_default()
+    }
+
+    pub fn get_pending_empty_message(&self) -> String {
+        self.pending_empty_message.clone().unwrap_or(String::from(
+            "No tasks. Press '{key:InsertMode}' to add one.",
         ))
     }
 
+    pub fn get_done_empty_message(&self) -> String {
+        self.done_empty_message
+            .clone()
+            .unwrap_or(String::from("No completed tasks yet."))
+    }
+
+    pub fn get_filtered_empty_message(&self) -> String {
+        self.filtered_empty_message
+            .clone()
+            .unwrap_or(String::from("No tasks match the active filters."))
+    }
+
+    pub fn get_category_empty_message(&self) -> String {
+        self.category_empty_message
+            .clone()
+            .unwrap_or(String::from("Nothing to filter by yet."))
+    }
+
+    pub fn get_category_bars(&self) -> bool {
+        self.category_bars.unwrap_or(true)
+    }
+
+    pub fn get_category_stats(&self) -> bool {
+        self.category_stats.unwrap_or(true)
+    }
+
+    pub fn get_layout(&self) -> String {
+        self.layout
+            .clone()
+            .unwrap_or(String::from(layout::DEFAULT_LAYOUT))
+    }
+
     pub fn get_tasks_keybind(&self) -> EventHandlerUI {
         self.tasks_keybind.clone().unwrap_or(EventHandlerUI::new(&[
             (KeyCode::Char('U'), UIEvent::SwapUpItem),
             (KeyCode::Char('D'), UIEvent::SwapDownItem),
+            (KeyCode::Char('T'), UIEvent::MoveItemTop),
+            (KeyCode::Char('B'), UIEvent::MoveItemBottom),
             (KeyCode::Char('x'), UIEvent::RemoveItem),
+            (KeyCode::Char('='), UIEvent::DuplicateItem),
             (KeyCode::Char('d'), UIEvent::MoveItem),
+            (KeyCode::Char('+'), UIEvent::FilterProject),
+            (KeyCode::Char('@'), UIEvent::FilterContext),
+            (KeyCode::Char('v'), UIEvent::ShowArchive),
+            (KeyCode::Char(']'), UIEvent::NextPage),
+            (KeyCode::Char('['), UIEvent::PrevPage),
+            (KeyCode::Char('s'), UIEvent::CycleSort),
+            (KeyCode::Char('('), UIEvent::PriorityUp),
+            (KeyCode::Char(')'), UIEvent::PriorityDown),
+            (KeyCode::Char('0'), UIEvent::PriorityClear),
+            (KeyCode::Char('w'), UIEvent::DeferItem),
+            (KeyCode::Char('f'), UIEvent::DeferWeek),
+            (KeyCode::Char('m'), UIEvent::DueNextMonday),
+            (KeyCode::Char('z'), UIEvent::ToggleTimer),
+            (KeyCode::Char(' '), UIEvent::ToggleMark),
+            (KeyCode::Char('r'), UIEvent::VisualMode),
+            (KeyCode::Char('c'), UIEvent::ClearMarks),
             (KeyCode::Enter, UIEvent::Select),
         ]))
     }
@@ -487,6 +1828,8 @@ Link: $link",
             .unwrap_or(EventHandlerUI::new(&[
                 (KeyCode::Enter, UIEvent::Select),
                 (KeyCode::Backspace, UIEvent::Remove),
+                (KeyCode::Char('o'), UIEvent::ToggleFilterMode),
+                (KeyCode::Char(' '), UIEvent::ToggleCollapse),
             ]))
     }
 
@@ -509,7 +1852,55 @@ Link: $link",
             (KeyCode::Char('K'), UIEvent::MoveUp),
             (KeyCode::Char('J'), UIEvent::MoveDown),
             (KeyCode::Char('I'), UIEvent::InsertMode),
+            (KeyCode::Char('i'), UIEvent::InsertScratch),
             (KeyCode::Char('E'), UIEvent::EditMode),
+            (KeyCode::Char('e'), UIEvent::EditItem),
+            (KeyCode::Char('y'), UIEvent::CopyView),
+            (KeyCode::Char('t'), UIEvent::DueMode),
+            (KeyCode::Char(':'), UIEvent::CommandMode),
+            (KeyCode::Char('M'), UIEvent::CycleMyTasks),
+            (KeyCode::Char('N'), UIEvent::TriageMode),
+            (KeyCode::Char('R'), UIEvent::Reveal),
+            (KeyCode::Char('F'), UIEvent::ToggleFilters),
+            (KeyCode::Char('C'), UIEvent::ClearFilters),
+            (KeyCode::Char('P'), UIEvent::PushFilters),
+            (KeyCode::Char('p'), UIEvent::PopFilters),
+            (KeyCode::Char('O'), UIEvent::ToggleSortOnInsert),
+            (KeyCode::Char('X'), UIEvent::ReopenLast),
+            (KeyCode::Char('A'), UIEvent::ShowActivity),
+            (KeyCode::Char('|'), UIEvent::SplitHorizontal),
+            (KeyCode::Char('-'), UIEvent::SplitVertical),
+            (KeyCode::Char('Z'), UIEvent::CloseSplit),
+            (KeyCode::Char('<'), UIEvent::BreadcrumbBack),
+            (KeyCode::Char('\''), UIEvent::OpenSwitcher),
+            (KeyCode::Char('/'), UIEvent::SearchMode),
+            (KeyCode::Char('V'), UIEvent::ToggleRawLines),
+            (KeyCode::F(2), UIEvent::EditKeybinds),
+            (KeyCode::F(3), UIEvent::ShowChangelog),
+            (KeyCode::F(4), UIEvent::ExportMode),
+            (KeyCode::F(5), UIEvent::ShowHistory),
+            (KeyCode::F(6), UIEvent::CompareMode),
+            (KeyCode::F(7), UIEvent::ShowPlanner),
+            (KeyCode::F(8), UIEvent::ShowTrash),
+            (KeyCode::F(9), UIEvent::ReviewMode),
+            (KeyCode::F(10), UIEvent::QuickAdd),
+            (KeyCode::Char('b'), UIEvent::FilterMode),
+            (KeyCode::Char('W'), UIEvent::ShowDeadlines),
+            (KeyCode::Char('Y'), UIEvent::ShowEffortReport),
+            (KeyCode::Char('%'), UIEvent::ShowForecast),
+            (KeyCode::Char('a'), UIEvent::Archive),
+            (KeyCode::Tab, UIEvent::NextProfile),
+            (KeyCode::BackTab, UIEvent::PrevProfile),
+        ]))
+    }
+
+    pub fn get_review_keybind(&self) -> EventHandlerUI {
+        self.review_keybind.clone().unwrap_or(EventHandlerUI::new(&[
+            (KeyCode::Enter, UIEvent::Select),
+            (KeyCode::Char('d'), UIEvent::RemoveItem),
+            (KeyCode::Char('-'), UIEvent::PriorityClear),
+            (KeyCode::Char('s'), UIEvent::DueMode),
+            (KeyCode::Down, UIEvent::ListDown),
         ]))
     }
 
@@ -519,12 +1910,12 @@ Link: $link",
 
     fn get_category_select_style(&self) -> TextStyle {
         self.category_select_style
-            .unwrap_or_else(|| TextStyle::default().fg(Color::Green))
+            .unwrap_or_else(|| Styles::default().category_select_style)
     }
 
     fn get_category_remove_style(&self) -> TextStyle {
         self.category_remove_style
-            .unwrap_or_else(|| TextStyle::default().fg(Color::Red))
+            .unwrap_or_else(|| Styles::default().category_remove_style)
     }
 
     fn get_projects_style(&self) -> TextStyle {
@@ -540,15 +1931,20 @@ Link: $link",
     }
 
     fn get_custom_category_style(&self) -> HashMap<String, TextStyle> {
-        let default = || {
-            let mut custom_category_style = HashMap::new();
-            custom_category_style.insert(
-                String::from("+todo-tui"),
-                TextStyle::default().fg(Color::LightBlue),
-            );
-            custom_category_style
-        };
-        self.custom_category_style.clone().unwrap_or_else(default)
+        self.custom_category_style
+            .clone()
+            .unwrap_or_else(|| Styles::default().custom_category_style)
+    }
+
+    fn get_styles(&self) -> HashMap<String, NamedStyle> {
+        self.styles.clone().unwrap_or_default()
+    }
+
+    pub fn get_named_styles(&self) -> HashMap<String, TextStyle> {
+        NamedStyle::resolve(&self.get_styles()).unwrap_or_else(|e| {
+            log::error!("Cannot resolve named styles: {}", e);
+            HashMap::new()
+        })
     }
 }
 
@@ -605,11 +2001,140 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_load_named_styles() {
+        let s = r#"
+        list_active_color = "highlight"
+
+        [styles.base]
+        fg = "White"
+
+        [styles.highlight]
+        inherit = "base"
+        bg = "Blue"
+        "#;
+
+        let c = Config::load_from_buffer(s.as_bytes());
+        assert_eq!(
+            c.get_list_active_color(),
+            TextStyle::default().fg(Color::White).bg(Color::Blue)
+        );
+        assert_eq!(
+            c.get_named_styles().get("highlight"),
+            Some(&TextStyle::default().fg(Color::White).bg(Color::Blue))
+        );
+    }
+
     #[test]
     fn help_can_be_generated() {
         Config::parse();
     }
 
+    #[test]
+    fn dry_run_flag() {
+        let config = Config::parse_from(["todotxt-tui", "--dry-run"]);
+        assert!(config.get_dry_run());
+        let config = Config::parse_from(["todotxt-tui", "--dry-run", "false"]);
+        assert!(!config.get_dry_run());
+        assert!(!Config::parse_from(["todotxt-tui"]).get_dry_run());
+    }
+
+    #[test]
+    fn reload() -> std::result::Result<(), Box<dyn Error>> {
+        let path =
+            std::env::temp_dir().join(format!("todotxt-tui-reload-{}.toml", std::process::id()));
+        fs::write(&path, "todo_path = \"other.txt\"\nwrap_tasks = true\n")?;
+        let config = Config {
+            config_path: Some(path.clone()),
+            todo_path: Some(String::from("todo.txt")),
+            ..Config::default()
+        };
+        let reloaded = config.reload()?;
+        assert!(reloaded.get_wrap_tasks());
+        // The opened todo file stays the same.
+        assert_eq!(reloaded.get_todo_path(), "todo.txt");
+        assert_eq!(reloaded.get_config_path(), path);
+
+        fs::write(&path, "wrap_tasks = 1\n")?;
+        assert!(matches!(config.reload(), Err(ToDoError::ParseConfig(..))));
+        fs::write(&path, "priority_aging_range = \"C\"\n")?;
+        assert!(config.reload().is_err());
+        fs::remove_file(&path)?;
+        assert!(matches!(
+            config.reload(),
+            Err(ToDoError::IOoperationFailed(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn add_task() -> std::result::Result<(), Box<dyn Error>> {
+        let path =
+            std::env::temp_dir().join(format!("todotxt-tui-add-test-{}.txt", std::process::id()));
+        fs::write(&path, "buy milk")?;
+        let path = path.to_string_lossy().to_string();
+        let args = ["todotxt-tui", "--todo-path", &path, "--today", "2024-07-01"];
+        let config = Config::parse_from(args.iter().chain(&["--add", "call mom due:today "]));
+        assert!(config.export()?);
+        assert_eq!(
+            fs::read_to_string(&path)?,
+            "buy milk\n2024-07-01 call mom due:2024-07-01\n"
+        );
+        fs::remove_file(&path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn import_tasks() -> std::result::Result<(), Box<dyn Error>> {
+        let path = std::env::temp_dir().join(format!(
+            "todotxt-tui-import-test-{}.txt",
+            std::process::id()
+        ));
+        let checklist =
+            std::env::temp_dir().join(format!("todotxt-tui-import-test-{}.md", std::process::id()));
+        fs::write(&path, "buy milk\n")?;
+        fs::write(&checklist, "- [ ] call mom\n- [x] post letter\n")?;
+        let config = Config::parse_from([
+            "todotxt-tui",
+            "--todo-path",
+            &path.to_string_lossy(),
+            "--today",
+            "2024-07-01",
+            "--import-tasks",
+            &checklist.to_string_lossy(),
+        ]);
+        assert!(config.export()?);
+        assert_eq!(
+            fs::read_to_string(&path)?,
+            "buy milk\ncall mom\nx 2024-07-01 post letter\n"
+        );
+        fs::remove_file(&path)?;
+        fs::remove_file(&checklist)?;
+        Ok(())
+    }
+
+    #[test]
+    fn today() {
+        let config = Config::parse_from(["todotxt-tui", "--today", "2023-05-10"]);
+        let clock = config.get_clock();
+        assert!(clock.is_fixed());
+        assert_eq!(clock.today(), NaiveDate::from_ymd_opt(2023, 5, 10).unwrap());
+        assert!(!Config::default().get_clock().is_fixed());
+        assert!(Config::try_parse_from(["todotxt-tui", "--today", "2023-13-01"]).is_err());
+    }
+
+    #[test]
+    fn sort_ties() {
+        let config = Config::parse_from(["todotxt-tui", "--pending-sort-ties", "priority,created"]);
+        assert_eq!(
+            config.get_pending_sort_ties(),
+            vec![TaskSort::Priority, TaskSort::Created]
+        );
+        let config = Config::load_from_buffer(b"done_sort_ties = [\"Alphanumeric\"]".as_slice());
+        assert_eq!(config.get_done_sort_ties(), vec![TaskSort::Alphanumeric]);
+        assert!(config.get_pending_sort_ties().is_empty());
+    }
+
     #[test]
     fn test_parse_duration() {
         assert_eq!(parse_duration("1000"), Ok(Duration::from_secs(1000)));
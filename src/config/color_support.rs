@@ -0,0 +1,220 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::env;
+use tui::{buffer::Buffer, layout::Rect, style::Color, widgets::Widget};
+
+/// The 16 basic colors with the RGB values of the xterm palette.
+const BASIC: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// The levels of the red, green and blue parts of the 6x6x6 color cube of the 256 colors.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Represents the colors the terminal can show. Colors of the theme the terminal
+/// cannot show are rendered as the nearest color it can.
+#[derive(Clone, Copy, Serialize, Deserialize, Default, ValueEnum, PartialEq, Eq, Debug)]
+pub enum ColorSupport {
+    /// Detected from the `COLORTERM` and `TERM` environment variables.
+    #[default]
+    Auto,
+    /// All 24-bit RGB colors.
+    TrueColor,
+    /// The 256 indexed colors.
+    Ansi256,
+    /// The 16 basic colors.
+    Ansi16,
+}
+
+impl ColorSupport {
+    /// Detects the colors the terminal supports.
+    ///
+    /// # Arguments
+    ///
+    /// * `colorterm` - The value of the `COLORTERM` environment variable.
+    /// * `term` - The value of the `TERM` environment variable.
+    pub fn detect(colorterm: Option<&str>, term: Option<&str>) -> Self {
+        match (colorterm, term) {
+            (Some("truecolor" | "24bit"), _) => Self::TrueColor,
+            (_, Some(term)) if term.ends_with("-direct") => Self::TrueColor,
+            (_, Some(term)) if term.contains("256color") => Self::Ansi256,
+            (_, Some(_)) => Self::Ansi16,
+            // The Windows console does not set `TERM` and shows all colors.
+            (_, None) if cfg!(windows) => Self::TrueColor,
+            (_, None) => Self::Ansi16,
+        }
+    }
+
+    /// Gets the detected support if the support is `Auto`, otherwise the support itself.
+    pub fn resolve(self) -> Self {
+        match self {
+            Self::Auto => {
+                let colorterm = env::var("COLORTERM").ok();
+                let term = env::var("TERM").ok();
+                let support = Self::detect(colorterm.as_deref(), term.as_deref());
+                log::info!("Detected color support: {support:?}");
+                support
+            }
+            support => support,
+        }
+    }
+
+    /// Gets the nearest color the terminal can show.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The color of the theme.
+    pub fn degrade(&self, color: Color) -> Color {
+        match (self, color) {
+            (Self::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_indexed((r, g, b))),
+            (Self::Ansi16, Color::Indexed(index)) if index < 16 => BASIC[index as usize].0,
+            (Self::Ansi16, Color::Indexed(index)) => nearest_basic(indexed_rgb(index)),
+            (Self::Ansi16, Color::Rgb(r, g, b)) => nearest_basic((r, g, b)),
+            _ => color,
+        }
+    }
+}
+
+/// Rendered over the whole frame as the last widget, it replaces the colors
+/// of the rendered frame the terminal cannot show.
+impl Widget for ColorSupport {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if matches!(self, Self::Auto | Self::TrueColor) {
+            return;
+        }
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let cell = buf.get_mut(x, y);
+                cell.fg = self.degrade(cell.fg);
+                cell.bg = self.degrade(cell.bg);
+            }
+        }
+    }
+}
+
+/// Gets the squared distance of two RGB colors.
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let part = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    part(a.0, b.0) + part(a.1, b.1) + part(a.2, b.2)
+}
+
+/// Gets the RGB value of an indexed color of the xterm palette.
+fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => BASIC[index as usize].1,
+        16..=231 => {
+            let index = index - 16;
+            (
+                CUBE_LEVELS[(index / 36) as usize],
+                CUBE_LEVELS[(index / 6 % 6) as usize],
+                CUBE_LEVELS[(index % 6) as usize],
+            )
+        }
+        _ => {
+            let gray = 8 + 10 * (index - 232);
+            (gray, gray, gray)
+        }
+    }
+}
+
+/// Gets the index of the nearest color of the color cube or the grayscale ramp.
+fn nearest_indexed(rgb: (u8, u8, u8)) -> u8 {
+    let level = |value: u8| {
+        CUBE_LEVELS
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, level)| (value as i32 - **level as i32).abs())
+            .map_or(0, |(index, _)| index as u8)
+    };
+    let cube = 16 + 36 * level(rgb.0) + 6 * level(rgb.1) + level(rgb.2);
+    let average = (rgb.0 as u32 + rgb.1 as u32 + rgb.2 as u32) / 3;
+    let gray = 232 + (average.saturating_sub(3) / 10).min(23) as u8;
+    match distance(indexed_rgb(gray), rgb) < distance(indexed_rgb(cube), rgb) {
+        true => gray,
+        false => cube,
+    }
+}
+
+/// Gets the nearest of the 16 basic colors.
+fn nearest_basic(rgb: (u8, u8, u8)) -> Color {
+    BASIC
+        .iter()
+        .min_by_key(|(_, basic)| distance(*basic, rgb))
+        .map_or(Color::Reset, |(color, _)| *color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tui::layout::Rect;
+
+    #[test]
+    fn detect() {
+        use ColorSupport::*;
+        assert_eq!(
+            ColorSupport::detect(Some("truecolor"), Some("xterm-256color")),
+            TrueColor
+        );
+        assert_eq!(ColorSupport::detect(Some("24bit"), None), TrueColor);
+        assert_eq!(ColorSupport::detect(None, Some("xterm-direct")), TrueColor);
+        assert_eq!(ColorSupport::detect(None, Some("xterm-256color")), Ansi256);
+        assert_eq!(
+            ColorSupport::detect(Some("yes"), Some("screen-256color")),
+            Ansi256
+        );
+        assert_eq!(ColorSupport::detect(None, Some("linux")), Ansi16);
+        assert_eq!(ColorSupport::Ansi16.resolve(), Ansi16);
+    }
+
+    #[test]
+    fn degrade() {
+        use ColorSupport::*;
+        let orange = Color::Rgb(255, 135, 0);
+        assert_eq!(TrueColor.degrade(orange), orange);
+        assert_eq!(Ansi256.degrade(orange), Color::Indexed(208));
+        assert_eq!(Ansi256.degrade(Color::Rgb(255, 0, 0)), Color::Indexed(196));
+        assert_eq!(
+            Ansi256.degrade(Color::Rgb(128, 128, 128)),
+            Color::Indexed(244)
+        );
+        assert_eq!(Ansi256.degrade(Color::Rgb(0, 0, 0)), Color::Indexed(16));
+        assert_eq!(Ansi256.degrade(Color::Indexed(208)), Color::Indexed(208));
+        assert_eq!(Ansi256.degrade(Color::Cyan), Color::Cyan);
+
+        assert_eq!(Ansi16.degrade(Color::Rgb(250, 10, 10)), Color::LightRed);
+        assert_eq!(Ansi16.degrade(Color::Rgb(30, 30, 30)), Color::Black);
+        assert_eq!(Ansi16.degrade(Color::Rgb(120, 120, 130)), Color::DarkGray);
+        assert_eq!(Ansi16.degrade(Color::Indexed(1)), Color::Red);
+        assert_eq!(Ansi16.degrade(Color::Indexed(196)), Color::LightRed);
+        assert_eq!(Ansi16.degrade(Color::Indexed(244)), Color::DarkGray);
+        assert_eq!(Ansi16.degrade(Color::Reset), Color::Reset);
+    }
+
+    #[test]
+    fn render() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 2, 1));
+        buffer.content[0].fg = Color::Rgb(255, 0, 0);
+        buffer.content[1].bg = Color::Indexed(21);
+        ColorSupport::TrueColor.render(buffer.area, &mut buffer);
+        assert_eq!(buffer.content[0].fg, Color::Rgb(255, 0, 0));
+        ColorSupport::Ansi16.render(buffer.area, &mut buffer);
+        assert_eq!(buffer.content[0].fg, Color::LightRed);
+        assert_eq!(buffer.content[1].bg, Color::Blue);
+        assert_eq!(buffer.content[1].fg, Color::Reset);
+    }
+}
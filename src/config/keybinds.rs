@@ -0,0 +1,433 @@
+use super::Config;
+use crate::ui::{EventHandlerUI, UIEvent};
+use crossterm::event::KeyCode;
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    fs, io,
+};
+
+/// The group of keybindings, each group is a table of the configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum KeybindScope {
+    /// Keys handled by the window in any widget, `window_keybind`.
+    Window,
+    /// Keys moving in lists, `list_keybind`.
+    List,
+    /// Keys of task lists, `tasks_keybind`.
+    Tasks,
+    /// Keys of category lists, `category_keybind`.
+    Category,
+    /// Keys deciding about the task shown by the review mode, `review_keybind`.
+    Review,
+}
+
+impl KeybindScope {
+    pub const ALL: [KeybindScope; 5] = [
+        KeybindScope::Window,
+        KeybindScope::List,
+        KeybindScope::Tasks,
+        KeybindScope::Category,
+        KeybindScope::Review,
+    ];
+
+    /// Gets the name of the table of the keybindings in the configuration.
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeybindScope::Window => "window_keybind",
+            KeybindScope::List => "list_keybind",
+            KeybindScope::Tasks => "tasks_keybind",
+            KeybindScope::Category => "category_keybind",
+            KeybindScope::Review => "review_keybind",
+        }
+    }
+
+    /// Gets the scopes whose keys are looked up before the keys of this scope in
+    /// the same widget. The window handles its keys first, task and category lists
+    /// handle their keys before the keys moving in lists. The review mode handles
+    /// only its own keys.
+    fn handled_after(&self) -> &'static [KeybindScope] {
+        match self {
+            KeybindScope::Window | KeybindScope::Review => &[],
+            KeybindScope::List => &[
+                KeybindScope::Window,
+                KeybindScope::Tasks,
+                KeybindScope::Category,
+            ],
+            KeybindScope::Tasks | KeybindScope::Category => &[KeybindScope::Window],
+        }
+    }
+}
+
+/// Represents a key bound in two scopes active in the same widget, so only one of
+/// the bindings can be used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeybindConflict {
+    pub key: KeyCode,
+    /// The scope and the event of the binding used by the key.
+    pub used: (KeybindScope, UIEvent),
+    /// The scope and the event of the binding the key never reaches.
+    pub hidden: (KeybindScope, UIEvent),
+}
+
+impl Display for KeybindConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} triggers {:?} of {}, {:?} of {} is never used",
+            key_name(self.key),
+            self.used.1,
+            self.used.0.name(),
+            self.hidden.1,
+            self.hidden.0.name(),
+        )
+    }
+}
+
+/// Represents a key of `script_keybind` also bound in a scope of the normal mode. A bound
+/// script runs before any other binding of the key, so the other binding is never used.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScriptKeybindConflict {
+    pub key: KeyCode,
+    /// The name of the script run by the key.
+    pub script: String,
+    /// The scope and the event of the binding the key never reaches.
+    pub hidden: (KeybindScope, UIEvent),
+}
+
+impl Display for ScriptKeybindConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} runs the script '{}' of script_keybind, {:?} of {} is never used",
+            key_name(self.key),
+            self.script,
+            self.hidden.1,
+            self.hidden.0.name(),
+        )
+    }
+}
+
+/// Gets the name of the key as it is shown to the user, e.g. `q`, `Space` or `F2`.
+pub fn key_name(key: KeyCode) -> String {
+    match key {
+        KeyCode::Char(' ') => String::from("Space"),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(n) => format!("F{n}"),
+        key => format!("{key:?}"),
+    }
+}
+
+/// Parses the name of a key as it is shown by [`key_name`], e.g. `g`, `Space`, `F5` or `Enter`.
+pub fn parse_key_name(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+    Some(match name {
+        "Space" => KeyCode::Char(' '),
+        "Enter" => KeyCode::Enter,
+        "Esc" => KeyCode::Esc,
+        "Tab" => KeyCode::Tab,
+        "BackTab" => KeyCode::BackTab,
+        "Backspace" => KeyCode::Backspace,
+        "Delete" => KeyCode::Delete,
+        "Insert" => KeyCode::Insert,
+        "Home" => KeyCode::Home,
+        "End" => KeyCode::End,
+        "PageUp" => KeyCode::PageUp,
+        "PageDown" => KeyCode::PageDown,
+        "Up" => KeyCode::Up,
+        "Down" => KeyCode::Down,
+        "Left" => KeyCode::Left,
+        "Right" => KeyCode::Right,
+        name => KeyCode::F(name.strip_prefix('F')?.parse().ok()?),
+    })
+}
+
+impl Config {
+    /// Gets the keybindings of the scope.
+    pub fn get_keybind(&self, scope: KeybindScope) -> EventHandlerUI {
+        match scope {
+            KeybindScope::Window => self.get_window_keybind(),
+            KeybindScope::List => self.get_list_keybind(),
+            KeybindScope::Tasks => self.get_tasks_keybind(),
+            KeybindScope::Category => self.get_category_keybind(),
+            KeybindScope::Review => self.get_review_keybind(),
+        }
+    }
+
+    /// Replaces the keybindings of the scope.
+    pub fn set_keybind(&mut self, scope: KeybindScope, keybind: EventHandlerUI) {
+        let keybind = Some(keybind);
+        match scope {
+            KeybindScope::Window => self.window_keybind = keybind,
+            KeybindScope::List => self.list_keybind = keybind,
+            KeybindScope::Tasks => self.tasks_keybind = keybind,
+            KeybindScope::Category => self.category_keybind = keybind,
+            KeybindScope::Review => self.review_keybind = keybind,
+        }
+    }
+
+    /// Replaces the `{key:EVENT}` placeholders of the text with the key bound to the event,
+    /// e.g. `{key:InsertMode}` with `I`. Keys are looked up in the scopes of lists in the
+    /// order the keys are handled, a placeholder of an unknown or unbound event is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text with placeholders, e.g. a message shown in an empty list.
+    pub fn fill_keys(&self, text: &str) -> String {
+        let scopes = [
+            KeybindScope::Window,
+            KeybindScope::Tasks,
+            KeybindScope::Category,
+            KeybindScope::List,
+        ];
+        let mut filled = String::new();
+        let mut rest = text;
+        while let Some(start) = rest.find("{key:") {
+            let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+                break;
+            };
+            let name = &rest[start + "{key:".len()..end];
+            let key = scopes.iter().find_map(|scope| {
+                self.get_keybind(*scope)
+                    .bindings()
+                    .into_iter()
+                    .find(|(_, event)| format!("{event:?}") == name)
+                    .map(|(key, _)| key_name(key))
+            });
+            filled += &rest[..start];
+            filled += key.as_deref().unwrap_or(&rest[start..=end]);
+            rest = &rest[end + 1..];
+        }
+        filled + rest
+    }
+
+    /// Finds keys bound in two scopes active in the same widget, e.g. a key of
+    /// `window_keybind` also bound in `tasks_keybind`.
+    ///
+    /// # Returns
+    ///
+    /// The conflicts ordered by the hidden scope and the key.
+    pub fn keybind_conflicts(&self) -> Vec<KeybindConflict> {
+        let mut conflicts = Vec::new();
+        for scope in KeybindScope::ALL {
+            for (key, event) in self.get_keybind(scope).bindings() {
+                let used = scope.handled_after().iter().find_map(|first| {
+                    match self.get_keybind(*first).get_event(&key) {
+                        UIEvent::None => None,
+                        used => Some((*first, used)),
+                    }
+                });
+                if let Some(used) = used.filter(|_| event != UIEvent::None) {
+                    conflicts.push(KeybindConflict {
+                        key,
+                        used,
+                        hidden: (scope, event),
+                    });
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// Finds keys of `script_keybind` also bound in the window, list, task or category
+    /// keybindings. Keys that cannot be parsed are left out, they never run a script.
+    ///
+    /// # Returns
+    ///
+    /// The conflicts ordered by the key and the hidden scope.
+    pub fn script_keybind_conflicts(&self) -> Vec<ScriptKeybindConflict> {
+        let mut scripts: Vec<(KeyCode, String)> = self
+            .get_script_keybind()
+            .into_iter()
+            .filter_map(|(key, script)| Some((parse_key_name(&key)?, script)))
+            .collect();
+        scripts.sort_by_key(|(key, _)| key_name(*key));
+        let mut conflicts = Vec::new();
+        for (key, script) in scripts {
+            for scope in KeybindScope::ALL {
+                match (scope, self.get_keybind(scope).get_event(&key)) {
+                    (KeybindScope::Review, _) | (_, UIEvent::None) => {}
+                    (scope, event) => conflicts.push(ScriptKeybindConflict {
+                        key,
+                        script: script.clone(),
+                        hidden: (scope, event),
+                    }),
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// Writes all keybindings to the configuration file, the file is created if it does
+    /// not exist. Other settings of the file are kept, but the file is rewritten, so its
+    /// comments and formatting are lost.
+    pub fn save_keybinds(&self) -> Result<(), Box<dyn Error>> {
+        let path = self.get_config_path();
+        let mut table: toml::Table = match fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => toml::Table::new(),
+            Err(e) => return Err(e.into()),
+        };
+        for scope in KeybindScope::ALL {
+            table.insert(
+                scope.name().to_string(),
+                toml::Value::try_from(self.get_keybind(scope))?,
+            );
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, toml::to_string_pretty(&table)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{collections::HashMap, env::temp_dir, process};
+
+    #[test]
+    fn conflicts() {
+        let mut config = Config::default();
+        assert_eq!(config.keybind_conflicts(), []);
+
+        let mut tasks = config.get_tasks_keybind().bindings();
+        tasks.push((KeyCode::Char('q'), UIEvent::SwapUpItem));
+        tasks.push((KeyCode::Char('j'), UIEvent::SwapDownItem));
+        config.set_keybind(KeybindScope::Tasks, EventHandlerUI::new(&tasks));
+        let conflicts = config.keybind_conflicts();
+        assert_eq!(
+            conflicts,
+            [
+                KeybindConflict {
+                    key: KeyCode::Char('j'),
+                    used: (KeybindScope::Tasks, UIEvent::SwapDownItem),
+                    hidden: (KeybindScope::List, UIEvent::ListDown),
+                },
+                KeybindConflict {
+                    key: KeyCode::Char('q'),
+                    used: (KeybindScope::Window, UIEvent::Quit),
+                    hidden: (KeybindScope::Tasks, UIEvent::SwapUpItem),
+                },
+            ]
+        );
+        assert_eq!(
+            conflicts[1].to_string(),
+            "q triggers Quit of window_keybind, SwapUpItem of tasks_keybind is never used"
+        );
+    }
+
+    #[test]
+    fn script_conflicts() {
+        let mut config = Config {
+            script_keybind: Some(HashMap::from([
+                (String::from("F12"), String::from("errands")),
+                (String::from("F11"), String::from("report")),
+            ])),
+            ..Default::default()
+        };
+        assert_eq!(config.script_keybind_conflicts(), []);
+
+        config.script_keybind = Some(HashMap::from([
+            (String::from("q"), String::from("errands")),
+            (String::from("j"), String::from("report")),
+            (String::from("Ctrl"), String::from("report")),
+        ]));
+        let conflicts = config.script_keybind_conflicts();
+        assert_eq!(
+            conflicts,
+            [
+                ScriptKeybindConflict {
+                    key: KeyCode::Char('j'),
+                    script: String::from("report"),
+                    hidden: (KeybindScope::List, UIEvent::ListDown),
+                },
+                ScriptKeybindConflict {
+                    key: KeyCode::Char('q'),
+                    script: String::from("errands"),
+                    hidden: (KeybindScope::Window, UIEvent::Quit),
+                },
+            ]
+        );
+        assert_eq!(
+            conflicts[1].to_string(),
+            "q runs the script 'errands' of script_keybind, Quit of window_keybind is never used"
+        );
+    }
+
+    #[test]
+    fn fill_keys() {
+        let mut config = Config::default();
+        assert_eq!(
+            config.fill_keys("Press '{key:InsertMode}' to add, {key:Select} to select."),
+            "Press 'I' to add, Enter to select."
+        );
+        assert_eq!(
+            config.fill_keys("{key:Unknown} {key:"),
+            "{key:Unknown} {key:"
+        );
+
+        config.set_keybind(
+            KeybindScope::Window,
+            EventHandlerUI::new(&[(KeyCode::Char('a'), UIEvent::InsertMode)]),
+        );
+        assert_eq!(config.fill_keys("Press {key:InsertMode}."), "Press a.");
+        config.set_keybind(KeybindScope::Window, EventHandlerUI::new(&[]));
+        assert_eq!(
+            config.fill_keys("Press {key:InsertMode}."),
+            "Press {key:InsertMode}."
+        );
+    }
+
+    #[test]
+    fn key_names() {
+        for key in [
+            KeyCode::Char('g'),
+            KeyCode::Char(' '),
+            KeyCode::F(5),
+            KeyCode::Enter,
+            KeyCode::PageDown,
+        ] {
+            assert_eq!(parse_key_name(&key_name(key)), Some(key));
+        }
+        assert_eq!(parse_key_name("Fx"), None);
+        assert_eq!(parse_key_name("Ctrl"), None);
+        assert_eq!(parse_key_name(""), None);
+    }
+
+    #[test]
+    fn save_keybinds() -> Result<(), Box<dyn Error>> {
+        let path = temp_dir().join(format!("todotxt-tui-keybinds-{}.toml", process::id()));
+        fs::write(&path, "todo_path = \"todo.txt\"\n")?;
+        let mut config = Config {
+            config_path: Some(path.clone()),
+            ..Config::default()
+        };
+        config.set_keybind(
+            KeybindScope::Window,
+            EventHandlerUI::new(&[(KeyCode::Char('Q'), UIEvent::Quit)]),
+        );
+        config.save_keybinds()?;
+
+        let saved = Config::load(&path)?;
+        assert_eq!(saved.get_todo_path(), "todo.txt");
+        assert_eq!(
+            saved.get_window_keybind().get_event(&KeyCode::Char('Q')),
+            UIEvent::Quit
+        );
+        assert_eq!(
+            saved.get_window_keybind().get_event(&KeyCode::Char('q')),
+            UIEvent::None
+        );
+        assert_eq!(
+            saved.get_list_keybind(),
+            Config::default().get_list_keybind()
+        );
+        fs::remove_file(path)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,251 @@
+use super::TextStyle;
+use crate::{ToDoError, ToDoRes};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use toml::{Table, Value};
+
+/// Settings holding a text style which can be replaced by a name of a style.
+const STYLE_KEYS: [&str; 23] = [
+    "list_active_color",
+    "pending_active_color",
+    "done_active_color",
+    "category_active_color",
+    "overdue_style",
+    "due_today_style",
+    "due_week_style",
+    "off_hours_style",
+    "list_zebra_color",
+    "pending_zebra_color",
+    "done_zebra_color",
+    "category_zebra_color",
+    "search_match_style",
+    "divider_style",
+    "scroll_indicator_style",
+    "scratch_style",
+    "marked_style",
+    "category_style",
+    "category_select_style",
+    "category_remove_style",
+    "projects_style",
+    "contexts_style",
+    "hashtags_style",
+];
+
+/// Settings holding a table of text styles which can be replaced by a name of a style.
+const STYLE_TABLE_KEYS: [&str; 2] = ["priority_colors", "custom_category_style"];
+
+/// Represents a style defined in the `styles` table of the configuration.
+///
+/// The style can inherit properties of another named style and override some of them.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct NamedStyle {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    inherit: Option<String>,
+    #[serde(flatten)]
+    style: TextStyle,
+}
+
+impl NamedStyle {
+    /// Resolves the inheritance of all named styles.
+    ///
+    /// # Parameters
+    ///
+    /// - `styles`: Named styles as they are defined in the configuration.
+    ///
+    /// # Returns
+    ///
+    /// Final text styles by their names or an error if a style inherits from
+    /// an unknown style or the inheritance is cyclic.
+    pub fn resolve(styles: &HashMap<String, NamedStyle>) -> ToDoRes<HashMap<String, TextStyle>> {
+        let mut resolved = HashMap::new();
+        for name in styles.keys() {
+            Self::resolve_style(name, styles, &mut resolved, &mut Vec::new())?;
+        }
+        Ok(resolved)
+    }
+
+    fn resolve_style(
+        name: &str,
+        styles: &HashMap<String, NamedStyle>,
+        resolved: &mut HashMap<String, TextStyle>,
+        visited: &mut Vec<String>,
+    ) -> ToDoRes<TextStyle> {
+        if let Some(style) = resolved.get(name) {
+            return Ok(*style);
+        }
+        if visited.iter().any(|visited| visited == name) {
+            return Err(ToDoError::CyclicStyleInheritance(name.to_string()));
+        }
+        let named = styles
+            .get(name)
+            .ok_or_else(|| ToDoError::UnknownStyle(name.to_string()))?;
+        visited.push(name.to_string());
+        let style = match &named.inherit {
+            Some(parent) => {
+                Self::resolve_style(parent, styles, resolved, visited)?.combine(&named.style)
+            }
+            None => named.style,
+        };
+        resolved.insert(name.to_string(), style);
+        Ok(style)
+    }
+
+    /// Replaces names of styles used in style settings of the configuration
+    /// with the styles defined in the `styles` table.
+    ///
+    /// # Parameters
+    ///
+    /// - `config`: The configuration table loaded from the file.
+    pub fn replace_references(config: &mut Table) -> Result<(), Box<dyn std::error::Error>> {
+        let styles: HashMap<String, NamedStyle> = match config.get("styles") {
+            Some(styles) => styles.clone().try_into()?,
+            None => HashMap::new(),
+        };
+        let styles = Self::resolve(&styles)?;
+        let replace = |value: &mut Value| -> Result<(), Box<dyn std::error::Error>> {
+            if let Value::String(name) = value {
+                let style = styles
+                    .get(name)
+                    .ok_or_else(|| ToDoError::UnknownStyle(name.clone()))?;
+                *value = Value::try_from(style)?;
+            }
+            Ok(())
+        };
+        for key in STYLE_KEYS {
+            if let Some(value) = config.get_mut(key) {
+                replace(value)?;
+            }
+        }
+        for key in STYLE_TABLE_KEYS {
+            if let Some(Value::Table(table)) = config.get_mut(key) {
+                for (_, value) in table.iter_mut() {
+                    replace(value)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::Config, style::TextModifier};
+    use clap::CommandFactory;
+    use tui::style::{Color, Modifier, Style};
+
+    fn styles() -> HashMap<String, NamedStyle> {
+        toml::from_str(
+            r#"
+            [muted]
+            fg = "Gray"
+            modifier = "Dim"
+
+            [muted_warning]
+            inherit = "muted"
+            fg = "Yellow"
+
+            [warning_bg]
+            inherit = "muted_warning"
+            bg = "Red"
+
+            [loud]
+            inherit = "muted"
+            modifiers = ["bold", "underlined"]
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn resolve() -> ToDoRes<()> {
+        let resolved = NamedStyle::resolve(&styles())?;
+        assert_eq!(
+            resolved["muted"],
+            TextStyle::default()
+                .fg(Color::Gray)
+                .modifier(TextModifier::Dim)
+        );
+        assert_eq!(
+            resolved["muted_warning"],
+            TextStyle::default()
+                .fg(Color::Yellow)
+                .modifier(TextModifier::Dim)
+        );
+        assert_eq!(
+            resolved["warning_bg"],
+            TextStyle::default()
+                .fg(Color::Yellow)
+                .bg(Color::Red)
+                .modifier(TextModifier::Dim)
+        );
+        assert_eq!(
+            resolved["loud"].get_style(),
+            Style::default()
+                .fg(Color::Gray)
+                .add_modifier(Modifier::DIM | Modifier::BOLD | Modifier::UNDERLINED)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_errors() {
+        let mut styles = styles();
+        styles.get_mut("muted").unwrap().inherit = Some(String::from("warning_bg"));
+        assert!(matches!(
+            NamedStyle::resolve(&styles),
+            Err(ToDoError::CyclicStyleInheritance(_))
+        ));
+
+        styles.get_mut("muted").unwrap().inherit = Some(String::from("unknown"));
+        assert_eq!(
+            NamedStyle::resolve(&styles),
+            Err(ToDoError::UnknownStyle(String::from("unknown")))
+        );
+    }
+
+    #[test]
+    fn style_keys() {
+        let mut settings: Vec<_> = Config::command()
+            .get_arguments()
+            .filter(|arg| {
+                arg.get_value_names()
+                    .is_some_and(|names| names.iter().any(|name| name == "TEXT_STYLE"))
+            })
+            .map(|arg| arg.get_id().to_string())
+            .collect();
+        settings.sort();
+        let mut keys = STYLE_KEYS.to_vec();
+        keys.sort();
+        assert_eq!(settings, keys);
+    }
+
+    #[test]
+    fn replace_references() -> Result<(), Box<dyn std::error::Error>> {
+        let mut config: Table = toml::from_str(
+            r#"
+            window_title = "muted"
+            category_style = "muted"
+
+            [custom_category_style]
+            "+project" = "muted"
+
+            [styles.muted]
+            fg = "Gray"
+            "#,
+        )?;
+        NamedStyle::replace_references(&mut config)?;
+        assert_eq!(config["window_title"], Value::String(String::from("muted")));
+        let style: TextStyle = config["category_style"].clone().try_into()?;
+        assert_eq!(style, TextStyle::default().fg(Color::Gray));
+        let style: TextStyle = config["custom_category_style"]["+project"]
+            .clone()
+            .try_into()?;
+        assert_eq!(style, TextStyle::default().fg(Color::Gray));
+
+        let mut config: Table = toml::from_str(r#"category_style = "unknown""#)?;
+        assert!(NamedStyle::replace_references(&mut config).is_err());
+        Ok(())
+    }
+}
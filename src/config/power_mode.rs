@@ -0,0 +1,76 @@
+use super::Config;
+use crate::ui::{EventHandlerUI, UIEvent};
+use crossterm::event::KeyCode;
+
+/// Compact layout used in the power mode, the task list with a narrow list of projects.
+const POWER_MODE_LAYOUT: &str = "[Direction: Horizontal, List: 75%, Projects: 25%]";
+
+impl Config {
+    /// Gets the settings of the power mode, a bundle of settings for rapid triage
+    /// of tasks with a compact layout and single key complete, defer and prioritize.
+    pub fn power_mode_profile() -> Config {
+        Config {
+            layout: Some(String::from(POWER_MODE_LAYOUT)),
+            tasks_keybind: Some(EventHandlerUI::new(&[
+                (KeyCode::Char('U'), UIEvent::SwapUpItem),
+                (KeyCode::Char('D'), UIEvent::SwapDownItem),
+                (KeyCode::Char('T'), UIEvent::MoveItemTop),
+                (KeyCode::Char('B'), UIEvent::MoveItemBottom),
+                (KeyCode::Char('x'), UIEvent::RemoveItem),
+                (KeyCode::Char('='), UIEvent::DuplicateItem),
+                (KeyCode::Char('d'), UIEvent::MoveItem),
+                (KeyCode::Char('c'), UIEvent::MoveItem),
+                (KeyCode::Char('p'), UIEvent::CyclePriority),
+                (KeyCode::Char('w'), UIEvent::DeferItem),
+                (KeyCode::Char('f'), UIEvent::DeferWeek),
+                (KeyCode::Char('m'), UIEvent::DueNextMonday),
+                (KeyCode::Char('+'), UIEvent::FilterProject),
+                (KeyCode::Char('@'), UIEvent::FilterContext),
+                (KeyCode::Char('v'), UIEvent::ShowArchive),
+                (KeyCode::Char(']'), UIEvent::NextPage),
+                (KeyCode::Char('['), UIEvent::PrevPage),
+                (KeyCode::Char('s'), UIEvent::CycleSort),
+                (KeyCode::Char('('), UIEvent::PriorityUp),
+                (KeyCode::Char(')'), UIEvent::PriorityDown),
+                (KeyCode::Char('0'), UIEvent::PriorityClear),
+                (KeyCode::Char(' '), UIEvent::ToggleMark),
+                (KeyCode::Char('r'), UIEvent::VisualMode),
+                (KeyCode::Enter, UIEvent::Select),
+            ])),
+            ..Default::default()
+        }
+    }
+
+    /// Applies the power mode settings if the power mode is enabled.
+    /// Settings given in the configuration or on the command line take precedence.
+    pub fn apply_power_mode(self) -> Self {
+        if self.get_power_mode() {
+            self.merge(Self::power_mode_profile())
+        } else {
+            self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_power_mode() {
+        let config = || Config::load_from_buffer("layout = \"[List: 100%]\"".as_bytes());
+        assert_eq!(config().apply_power_mode(), config());
+
+        let config =
+            Config::load_from_buffer("power_mode = true\nlayout = \"[List: 100%]\"".as_bytes())
+                .apply_power_mode();
+        assert_eq!(config.get_layout(), "[List: 100%]");
+        let keybind = config.get_tasks_keybind();
+        assert_eq!(
+            keybind.get_event(&KeyCode::Char('p')),
+            UIEvent::CyclePriority
+        );
+        assert_eq!(keybind.get_event(&KeyCode::Char('w')), UIEvent::DeferItem);
+        assert_eq!(keybind.get_event(&KeyCode::Char('c')), UIEvent::MoveItem);
+    }
+}
@@ -0,0 +1,203 @@
+use super::{Config, TextStyle};
+use crate::style::TextModifier;
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, Write},
+    path::Path,
+};
+use tui::style::Color;
+
+/// Color themes offered during the first run setup.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Theme {
+    Dark,
+    Light,
+    Monochrome,
+}
+
+impl Theme {
+    const ALL: [Theme; 3] = [Theme::Dark, Theme::Light, Theme::Monochrome];
+
+    /// Gets the name of the theme shown to the user.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Theme::Dark => "Dark (default colors)",
+            Theme::Light => "Light (for terminals with a light background)",
+            Theme::Monochrome => "Monochrome (no colors, only text modifiers)",
+        }
+    }
+
+    /// Sets style settings of the theme to the configuration.
+    ///
+    /// # Parameters
+    ///
+    /// - `config`: The configuration to modify.
+    pub fn apply(&self, config: &mut Config) {
+        match self {
+            Theme::Dark => {}
+            Theme::Light => {
+                config.active_color = Some(Color::Blue);
+                config.list_active_color = Some(TextStyle::default().bg(Color::LightBlue));
+                config.category_active_color = Some(TextStyle::default().bg(Color::LightBlue));
+                config.category_select_style = Some(TextStyle::default().fg(Color::Blue));
+                config.category_remove_style = Some(TextStyle::default().fg(Color::Magenta));
+            }
+            Theme::Monochrome => {
+                config.active_color = Some(Color::Reset);
+                config.list_active_color = Some(TextStyle::default().modifier(TextModifier::Bold));
+                config.category_active_color =
+                    Some(TextStyle::default().modifier(TextModifier::Bold));
+                config.category_select_style =
+                    Some(TextStyle::default().modifier(TextModifier::Underlined));
+                config.category_remove_style =
+                    Some(TextStyle::default().modifier(TextModifier::Dim));
+                config.priority_colors = Some(Default::default());
+                config.custom_category_style = Some(Default::default());
+            }
+        }
+    }
+}
+
+/// Interactive setup run when the application is started for the first time.
+///
+/// The setup asks for the path to the todo.txt file, creates the file if needed,
+/// lets the user pick a theme and writes a starter configuration.
+pub struct Setup<R: BufRead, W: Write> {
+    reader: R,
+    writer: W,
+}
+
+impl<R: BufRead, W: Write> Setup<R, W> {
+    /// Creates a new `Setup` reading answers from `reader` and writing questions to `writer`.
+    pub fn new(reader: R, writer: W) -> Self {
+        Self { reader, writer }
+    }
+
+    /// Runs the setup.
+    ///
+    /// # Parameters
+    ///
+    /// - `config`: The configuration loaded from command line arguments. The starter
+    ///   configuration is written to its configuration path.
+    ///
+    /// # Returns
+    ///
+    /// The configuration merged with the starter configuration.
+    pub fn run(mut self, config: Config) -> io::Result<Config> {
+        writeln!(self.writer, "Welcome to todo.txt TUI! Let's set it up.")?;
+        let mut starter = Config::default();
+
+        let default_path = config.get_todo_path();
+        let todo_path = self.ask(&format!("Path to todo.txt file [{default_path}]: "))?;
+        let todo_path = if todo_path.is_empty() {
+            default_path
+        } else {
+            todo_path
+        };
+        if !Path::new(&todo_path).exists() {
+            create_file(Path::new(&todo_path))?;
+            writeln!(self.writer, "Created {todo_path}")?;
+        }
+        starter.todo_path = Some(todo_path);
+
+        writeln!(self.writer, "Themes:")?;
+        for (i, theme) in Theme::ALL.iter().enumerate() {
+            writeln!(self.writer, "  {}) {}", i + 1, theme.name())?;
+        }
+        let theme = loop {
+            let answer = self.ask("Pick a theme [1]: ")?;
+            if answer.is_empty() {
+                break Theme::Dark;
+            }
+            match answer.parse::<usize>() {
+                Ok(i) if (1..=Theme::ALL.len()).contains(&i) => break Theme::ALL[i - 1],
+                _ => writeln!(self.writer, "Please enter a number from the list.")?,
+            }
+        };
+        theme.apply(&mut starter);
+
+        let config_path = config.get_config_path();
+        create_file(&config_path)?;
+        let content = toml::to_string_pretty(&starter)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(&config_path, content)?;
+        writeln!(
+            self.writer,
+            "Configuration written to {}",
+            config_path.display()
+        )?;
+
+        Ok(config.merge(starter))
+    }
+
+    /// Prints the question and reads a trimmed answer.
+    /// Fails if the input ends before the answer is given.
+    fn ask(&mut self, question: &str) -> io::Result<String> {
+        write!(self.writer, "{question}")?;
+        self.writer.flush()?;
+        let mut answer = String::new();
+        if self.reader.read_line(&mut answer)? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "Setup was interrupted",
+            ));
+        }
+        Ok(answer.trim().to_string())
+    }
+}
+
+/// Creates the file and its parent directories if they do not exist.
+fn create_file(path: &Path) -> io::Result<File> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// Checks whether the setup should be offered, that is neither the configuration
+/// nor the todo.txt file exists yet.
+///
+/// # Parameters
+///
+/// - `config`: The configuration loaded from command line arguments.
+pub fn is_first_run(config: &Config) -> bool {
+    !config.get_config_path().exists() && !Path::new(&config.get_todo_path()).exists()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env::temp_dir, io::Cursor, process};
+
+    #[test]
+    fn run() -> io::Result<()> {
+        let dir = temp_dir().join(format!("todotxt-tui-setup-test-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let todo_path = dir.join("todo").join("todo.txt");
+        let config_path = dir.join("config").join("todo-tui.toml");
+        let config = || Config {
+            config_path: Some(config_path.clone()),
+            todo_path: Some(todo_path.to_str().unwrap().to_string()),
+            ..Default::default()
+        };
+        assert!(is_first_run(&config()));
+
+        let input = "\nfoo\n7\n3\n";
+        let mut output = Vec::new();
+        let config = Setup::new(Cursor::new(input), &mut output).run(config())?;
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(todo_path.exists());
+        assert_eq!(config.get_todo_path(), todo_path.to_str().unwrap());
+        assert_eq!(config.get_active_color(), Color::Reset);
+        assert_eq!(output.matches("Please enter a number").count(), 2);
+        let written = Config::load(&config_path)?;
+        assert_eq!(written.todo_path, config.todo_path);
+        assert_eq!(written.active_color, Some(Color::Reset));
+        assert!(!is_first_run(&config));
+
+        let result = Setup::new(Cursor::new(""), Vec::new()).run(config);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+        fs::remove_dir_all(&dir)
+    }
+}
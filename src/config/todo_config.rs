@@ -1,18 +1,68 @@
-use super::Config;
-use crate::todo::task_list::TaskSort;
+use super::{Config, Styles};
+use crate::todo::{
+    ContextHours, EscalationRule, PriorityAging, TaskRule, ToDoConfig, UrgencyWeights,
+};
 
-pub struct ToDoConfig {
-    pub use_done: bool,
-    pub pending_sort: TaskSort,
-    pub done_sort: TaskSort,
-}
-
-impl ToDoConfig {
-    pub fn new(config: &Config) -> Self {
+impl From<&Config> for ToDoConfig {
+    fn from(config: &Config) -> Self {
         Self {
             use_done: false, // TODO add to config
             pending_sort: config.get_pending_sort(),
             done_sort: config.get_done_sort(),
+            pending_sort_ties: config.get_pending_sort_ties(),
+            done_sort_ties: config.get_done_sort_ties(),
+            sort_on_insert: config.get_sort_on_insert(),
+            raw_lines: config.get_raw_lines(),
+            my_name: config.get_my_name(),
+            project_defaults: config.get_project_defaults(),
+            priority_labels: config.get_priority_labels(),
+            activity_log: config.get_activity_log_path().is_some(),
+            trash_size: config.get_trash_size(),
+            escalation_rules: EscalationRule::parse_all(&config.get_escalation_rules())
+                .unwrap_or_else(|e| {
+                    log::error!("Escalation rules are not applied: {}", e);
+                    Vec::new()
+                }),
+            task_rules: TaskRule::parse_all(&config.get_task_rules(), &Styles::from(config))
+                .unwrap_or_else(|e| {
+                    log::error!("Task rules are not applied: {}", e);
+                    Vec::new()
+                }),
+            priority_aging: config.get_priority_aging().unwrap_or_else(|e| {
+                log::error!("Priorities do not age: {}", e);
+                PriorityAging::default()
+            }),
+            urgency_weights: UrgencyWeights::new(&config.get_urgency_weights()),
+            pomodoro_minutes: config.get_pomodoro_minutes(),
+            deadline_warning_days: config.get_deadline_warning_days() as i64,
+            context_hours: ContextHours::parse_all(&config.get_context_hours()).unwrap_or_else(
+                |e| {
+                    log::error!("Working hours of contexts are not applied: {}", e);
+                    ContextHours::default()
+                },
+            ),
+            clock: config.get_clock(),
+            done_path: config.get_done_path(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults() {
+        let config = ToDoConfig::from(&Config::default());
+        let default = ToDoConfig::default();
+        assert_eq!(config.pending_sort, default.pending_sort);
+        assert_eq!(config.done_sort, default.done_sort);
+        assert_eq!(config.trash_size, default.trash_size);
+        assert_eq!(config.priority_aging, default.priority_aging);
+        assert_eq!(config.urgency_weights, default.urgency_weights);
+        assert_eq!(config.pomodoro_minutes, default.pomodoro_minutes);
+        assert_eq!(config.deadline_warning_days, default.deadline_warning_days);
+        assert_eq!(config.clock, default.clock);
+        assert_eq!(config.done_path, default.done_path);
+    }
+}
@@ -0,0 +1,148 @@
+use notify::{
+    event::{AccessKind, AccessMode, CreateKind, EventKind, ModifyKind},
+    Config as NotifyConfig, Event, RecommendedWatcher, RecursiveMode, Watcher,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver},
+    time::SystemTime,
+};
+
+/// Watches the configuration file for changes made while the application is running.
+///
+/// The directory of the file is watched the same way as the todo file, so a file replaced
+/// by renaming another file over it is detected too. The watcher sends a message through
+/// a channel when the file is written, only then the modification time and the size
+/// of the file are compared to tell the writes of the application itself.
+pub struct ConfigWatcher {
+    path: PathBuf,
+    stamp: Option<(SystemTime, u64)>,
+    writes: Receiver<()>,
+    _watcher: RecommendedWatcher,
+}
+
+impl ConfigWatcher {
+    /// Creates a new `ConfigWatcher`, the file as it is now is not a change.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the configuration file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory of the file cannot be watched.
+    pub fn new(path: PathBuf) -> notify::Result<Self> {
+        let (tx, writes) = mpsc::channel();
+        let name = path.file_name().map(ToOwned::to_owned);
+        let mut watcher = RecommendedWatcher::new(
+            move |res: notify::Result<Event>| {
+                let Ok(event) = res else {
+                    return;
+                };
+                let written = matches!(
+                    event.kind,
+                    EventKind::Access(AccessKind::Close(AccessMode::Write))
+                        | EventKind::Create(CreateKind::File | CreateKind::Any)
+                        | EventKind::Modify(ModifyKind::Name(_))
+                );
+                if written
+                    && event
+                        .paths
+                        .iter()
+                        .any(|changed| changed.file_name() == name.as_deref())
+                {
+                    log::trace!("Configuration file changed");
+                    let _ = tx.send(());
+                }
+            },
+            NotifyConfig::default(),
+        )?;
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        let mut watcher = Self {
+            path,
+            stamp: None,
+            writes,
+            _watcher: watcher,
+        };
+        watcher.sync();
+        Ok(watcher)
+    }
+
+    fn current(&self) -> Option<(SystemTime, u64)> {
+        let metadata = fs::metadata(&self.path).ok()?;
+        Some((metadata.modified().ok()?, metadata.len()))
+    }
+
+    /// Remembers the file as it is now, e.g. after the application wrote it.
+    pub fn sync(&mut self) {
+        self.stamp = self.current();
+    }
+
+    /// Checks whether the file was written since the last check. A removed file
+    /// is not a change, the running configuration is kept.
+    pub fn changed(&mut self) -> bool {
+        if self.writes.try_iter().count() == 0 {
+            return false;
+        }
+        let current = self.current();
+        if current.is_none() || current == self.stamp {
+            return false;
+        }
+        self.stamp = current;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        env::temp_dir,
+        io, thread,
+        time::{Duration, Instant},
+    };
+
+    /// Waits until the watcher reports a change or a second passes.
+    fn wait_changed(watcher: &mut ConfigWatcher) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(1);
+        while Instant::now() < deadline {
+            if watcher.changed() {
+                return true;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        false
+    }
+
+    #[test]
+    fn changed() -> io::Result<()> {
+        let path = temp_dir().join(format!(
+            "todotxt-tui-config-watcher-{}.toml",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        let mut watcher = ConfigWatcher::new(path.clone()).map_err(io::Error::other)?;
+        assert!(!watcher.changed());
+
+        fs::write(&path, "wrap_tasks = true\n")?;
+        assert!(wait_changed(&mut watcher));
+        assert!(!watcher.changed());
+
+        fs::write(&path, "wrap_tasks = false\n")?;
+        assert!(wait_changed(&mut watcher));
+
+        // A file written by the application is not a change.
+        fs::write(&path, "wrap_tasks = true\n")?;
+        watcher.sync();
+        assert!(!wait_changed(&mut watcher));
+
+        fs::remove_file(&path)?;
+        assert!(!wait_changed(&mut watcher));
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+use crate::ui::EventHandlerUI;
+use serde::{Deserialize, Serialize};
+
+/// Represents the settings of a widget set in the `widget_settings` table by the name
+/// of the widget in the layout, e.g. `[widget_settings.Done]`.
+///
+/// Settings that are not set fall back to the global ones, the key bindings are
+/// added to the global tables and take precedence over them.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(test, derive(PartialEq, Debug))]
+pub struct WidgetSettings {
+    /// Title of the widget instead of its name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Number of rows the list scrolls by, see `list_shift`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub list_shift: Option<usize>,
+    /// Key bindings added to `list_keybind`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub list_keybind: Option<EventHandlerUI>,
+    /// Key bindings added to `tasks_keybind` or `category_keybind` by the type of the widget.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keybind: Option<EventHandlerUI>,
+    /// Whether category lists include the categories of done tasks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_done: Option<bool>,
+}
@@ -0,0 +1,24 @@
+use super::TextStyle;
+use serde::{Deserialize, Serialize};
+
+/// Represents the styles of a widget set in the `widget_styles` table by the name
+/// of the widget in the layout, e.g. `[widget_styles.Done]`.
+///
+/// Styles that are not set fall back to the global settings, `active_color`
+/// for the border of the focused widget and the active colors of lists.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default)]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct WidgetStyle {
+    /// Style of the border of the widget when it is not focused.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub border: Option<TextStyle>,
+    /// Style of the border of the focused widget.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_border: Option<TextStyle>,
+    /// Style of the title of the widget.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<TextStyle>,
+    /// Style of the selected row of the widget.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<TextStyle>,
+}
@@ -0,0 +1,31 @@
+//! Task engine of the application usable without the terminal user interface.
+//!
+//! The engine keeps the todo list with its filters and sorting, applies
+//! [`Action`]s to it, formats tasks using templates and loads and saves
+//! todo.txt files. Other tools can embed it to work with the same todo list
+//! the way the application does.
+//!
+//! ```
+//! use todotxt_tui_core::engine::{Action, Styles, ToDo, ToDoConfig, ToDoData};
+//!
+//! let config = ToDoConfig {
+//!     my_name: Some(String::from("me")),
+//!     ..ToDoConfig::default()
+//! };
+//! let mut todo = ToDo::new(config, Styles::default());
+//! todo.apply(Action::NewTask {
+//!     task: String::from("(A) write documentation +todotxt"),
+//! })
+//! .unwrap();
+//! assert_eq!(todo.len(ToDoData::Pending), 1);
+//! ```
+
+pub use crate::{
+    error::{ToDoError, ToDoRes},
+    file_worker::{FileWorker, FileWorkerCommands},
+    style::Styles,
+    todo::{
+        autocomplete, Action, CategoryList, FilterState, Parser, TaskId, TaskList, TaskSort, ToDo,
+        ToDoCategory, ToDoConfig, ToDoData, ToDoState,
+    },
+};
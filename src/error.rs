@@ -22,10 +22,18 @@ pub enum ToDoError {
     ParseUnknowBeforeContainer(String),
     #[error("Direction '{0}' is invalid.")]
     ParseInvalidDirection(String),
+    #[error("Size '{0}' is larger than 100%.")]
+    ParseInvalidSize(String),
+    #[error("A container must contain at least one widget.")]
+    ParseEmptyContainer,
     #[error("Style '{0}' is invalid")]
     ParseTextStyle(String),
     #[error("Modifier '{0}' is invalid.")]
     ParseTextModifier(String),
+    #[error("Color '{0}' is invalid, expected '#rrggbb' with six hex digits.")]
+    ParseHexColor(String),
+    #[error("Color index '{0}' is invalid, expected a number from 0 to 255.")]
+    ParseColorIndex(String),
     #[error("Block '{0}' have escape on the end.")]
     ParseBlockEscapeOnEnd(String),
     #[error("Block '{0}' is not closed.")]
@@ -34,6 +42,62 @@ pub enum ToDoError {
     ParseVariableNotClosed(String),
     #[error("Block '{0}' constraint empty variable name.")]
     EmptyVariableName(String),
+    #[error("Style '{0}' is not defined.")]
+    UnknownStyle(String),
+    #[error("Style '{0}' inherits from itself.")]
+    CyclicStyleInheritance(String),
+    #[error("Unknown variable '${0}'.")]
+    ParseUnknownVariable(String),
+    #[error("Template error at line {line}, column {column}: {err}")]
+    ParseTemplate {
+        line: usize,
+        column: usize,
+        err: Box<ToDoError>,
+    },
+    #[error("Unknown command '{0}'.")]
+    ParseCommand(String),
+    #[error("Command is not supported by the selected widget.")]
+    CommandNotSupported,
+    #[error("Profile '{0}' does not exist or is open.")]
+    UnknownProfile(String),
+    #[error("No task matches '{0}'.")]
+    TaskNotFound(String),
+    #[error("Query '{0}' is invalid.")]
+    ParseQuery(String),
+    #[error("Query '{0}' is not defined in list_queries.")]
+    UnknownQuery(String),
+    #[error("Widget {0} cannot have a query.")]
+    QueryNotSupported(String),
+    #[error("Widget Tags needs the key of the tag, e.g. Tags(due).")]
+    MissingTagKey,
+    #[error("Widget {0} cannot be split.")]
+    SplitNotSupported(String),
+    #[error("Escalation rule '{0}' is invalid.")]
+    ParseEscalation(String),
+    #[error("Task rule '{0}' is invalid.")]
+    ParseTaskRule(String),
+    #[error("Working hours '{0}' are invalid, use e.g. 9-17 weekdays.")]
+    ParseWorkingHours(String),
+    #[error("Script '{0}' does not exist.")]
+    UnknownScript(String),
+    #[error("Script '{0}' failed: {1}")]
+    Script(String, String),
+    #[error("Recurrence '{0}' is invalid, use e.g. 3d, 1w, +1m or 1y.")]
+    ParseRecurrence(String),
+    #[error("Date '{0}' is invalid, use e.g. 2023-06-01, today, 3d, +1w, mon or none.")]
+    ParseDate(String),
+    #[error("Export format of '{0}' is unknown, use a .json, .md or .ics file.")]
+    ParseExportFormat(String),
+    #[error(
+        "Import format of '{0}' is unknown, use a Taskwarrior .json export or a .md checklist."
+    )]
+    ParseImportFormat(String),
+    #[error("Cannot import the tasks: {0}.")]
+    ParseImport(String),
+    #[error("Priority aging range '{0}' is invalid, use e.g. C-B.")]
+    ParseAgingRange(String),
+    #[error("Configuration file {0} is invalid: {1}")]
+    ParseConfig(String, String),
     #[error("Invalid state, active container is not widget.")]
     ActiveIsNotWidget,
     #[error("{0}")]
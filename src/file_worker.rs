@@ -1,33 +1,143 @@
-use crate::{config::Config, todo::ToDo};
+mod diff;
+mod health;
+mod merge;
+mod remote;
+mod storage;
+
+pub use diff::unified_diff;
+pub use health::{check_line, Issue, Problem};
+pub use merge::{merge_lines, Conflict, Merged};
+pub use remote::RemoteFile;
+pub use storage::{FileStamp, LocalFile, Storage};
+
+use crate::{
+    status,
+    todo::{is_scratch, parse_task, TaskId, ToDo, ToDoData},
+};
 use notify::{
-    event::{AccessKind, AccessMode, EventKind},
+    event::{AccessKind, AccessMode, CreateKind, EventKind, ModifyKind},
     Config as NotifyConfig, RecommendedWatcher, RecursiveMode, Watcher,
 };
-use std::fs::File;
-use std::io::{BufRead, BufReader, BufWriter, Read, Result as ioResult, Write};
+use std::fs;
+use std::io::{BufRead, BufReader, BufWriter, ErrorKind, Read, Result as ioResult, Write};
 use std::path::Path;
-use std::str::FromStr;
 use std::sync::mpsc::Sender;
 use std::sync::{mpsc, Arc, Mutex};
-use std::{thread, time::Duration};
+use std::thread;
+use std::time::{Duration, SystemTime};
 use todo_txt::Task;
 
 /// Commands that can be sent to the `FileWorker` for various file-related operations.
 pub enum FileWorkerCommands {
+    /// Saves the todo list even if the file was changed by another program.
+    /// It is refused while the todo list could not be loaded.
     ForceSave,
+    /// Saves the todo list even if it could not be loaded, the file is overwritten.
+    SaveAnyway,
+    /// Saves the todo list if it has changed, changes of the file by another program are merged.
     Save,
+    /// Loads the todo list if the file was changed, unsaved changes of the todo list are merged.
     Load,
+    /// Loads the todo list, unsaved changes are discarded.
+    ForceLoad,
+    /// Merges the unsaved changes of the todo list with the changes of the file, the lines
+    /// of both are kept where they conflict.
+    KeepBoth,
     Exit,
 }
 
+/// Errors reported by the `FileWorker` thread.
+#[derive(Debug, PartialEq, Eq)]
+pub enum FileWorkerError {
+    /// A file operation failed.
+    Failed(String),
+    /// Another program changed the same lines of the file as the unsaved changes of the todo list.
+    /// Neither is overwritten until the user chooses which one to keep.
+    Conflict(Vec<Conflict>),
+    /// The todo file cannot be written, e.g. it is read-only or the disk is full.
+    /// Automatic saving stops and the unsaved changes are exported instead.
+    ReadOnly(String),
+    /// The todo list could not be loaded, saving it would overwrite the file.
+    /// It is saved only when the user chooses to save anyway.
+    LoadFailed,
+}
+
+impl std::fmt::Display for FileWorkerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileWorkerError::Failed(message) | FileWorkerError::ReadOnly(message) => {
+                write!(f, "{message}")
+            }
+            FileWorkerError::Conflict(_) => write!(
+                f,
+                "The todo file was changed by another program in the same tasks as your unsaved changes."
+            ),
+            FileWorkerError::LoadFailed => write!(
+                f,
+                "The todo file could not be loaded, saving the todo list would overwrite it."
+            ),
+        }
+    }
+}
+
+impl From<std::io::Error> for FileWorkerError {
+    fn from(e: std::io::Error) -> Self {
+        FileWorkerError::Failed(e.to_string())
+    }
+}
+
+/// The stamps of the file(s) with the time they were read.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Stamp {
+    taken: Option<SystemTime>,
+    /// The stamp of each file, `None` for a missing file.
+    files: Vec<Option<FileStamp>>,
+}
+
+impl Stamp {
+    /// The time a file changed again might keep the same modification time.
+    const GRANULARITY: Duration = Duration::from_secs(2);
+
+    /// Checks whether the files have not changed since the stamp was taken, files modified
+    /// shortly before it might have changed again with the same modification time.
+    fn unchanged(&self, current: &Stamp) -> bool {
+        let Some(taken) = self.taken else {
+            return false;
+        };
+        self.files == current.files
+            && self.files.iter().flatten().all(|stamp| match stamp {
+                FileStamp::Modified(modified, _) => *modified + Self::GRANULARITY < taken,
+                FileStamp::Tag(_) => true,
+            })
+    }
+}
+
 /// Manages file operations for the todo list and archive.
 pub struct FileWorker {
     todo_path: String,
     archive_path: Option<String>,
     todo: Arc<Mutex<ToDo>>,
+    /// The todo file followed by the archive file.
+    files: Vec<Box<dyn Storage>>,
+    load_failed: bool,
+    dry_run: bool,
+    problems: Vec<Problem>,
+    /// The content of the file(s) when the todo list was loaded or saved last time.
+    synced: String,
+    /// The modification times and sizes of the file(s) when `synced` was read.
+    synced_stamp: Stamp,
+    /// The version of the todo list when it was loaded or saved last time.
+    version: usize,
+    conflict: bool,
+    /// The file(s) cannot be written, changes are only exported until a save succeeds.
+    read_only: bool,
+    unsaved_path: Option<String>,
 }
 
 impl FileWorker {
+    /// The duration between the checks of a remote todo file for changes.
+    const REMOTE_POLL: Duration = Duration::from_secs(60);
+
     /// Creates a new `FileWorker` instance.
     ///
     /// # Arguments
@@ -49,32 +159,147 @@ impl FileWorker {
             todo_path,
             archive_path
         );
+        let files = std::iter::once(&todo_path)
+            .chain(&archive_path)
+            .map(|path| Box::new(LocalFile::new(path)) as Box<dyn Storage>)
+            .collect();
         FileWorker {
             todo_path,
             archive_path,
             todo,
+            files,
+            load_failed: false,
+            dry_run: false,
+            problems: Vec::new(),
+            synced: String::new(),
+            synced_stamp: Stamp::default(),
+            version: 0,
+            conflict: false,
+            read_only: false,
+            unsaved_path: None,
         }
     }
 
+    /// Sets the dry run, the todo list is never written to the file(s) in the dry run.
+    ///
+    /// # Arguments
+    ///
+    /// * `dry_run` - A flag indicating whether changes are kept only in memory.
+    pub fn set_dry_run(&mut self, dry_run: bool) {
+        self.dry_run = dry_run;
+    }
+
+    /// Sets the URL of the todo file on a server, the todo file becomes its local copy,
+    /// see [`RemoteFile`].
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL of the remote todo file.
+    pub fn set_remote_url(&mut self, url: &str) -> ioResult<()> {
+        self.files[0] = Box::new(RemoteFile::new(url, &self.todo_path)?);
+        Ok(())
+    }
+
+    /// Sets the file the todo list is exported to while the todo file cannot be written.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the export file.
+    pub fn set_unsaved_path(&mut self, path: String) {
+        self.unsaved_path = Some(path);
+    }
+
     /// Loads todo list data from the file(s).
     ///
     /// This method loads data from the main todo list file and optionally from an archive file.
+    /// If loading fails on anything else than a missing file, automatic saving is disabled
+    /// until the data is loaded successfully, so the file is not overwritten.
+    /// Lines with problems found by the health check are available in [`FileWorker::problems`].
     ///
     /// # Returns
     ///
     /// An `ioResult` indicating success or an error if file operations fail.
-    pub fn load(&self) -> ioResult<()> {
-        let mut todo = ToDo::new(&Config::default()); // TODO this can be improved
-        Self::load_tasks(File::open(&self.todo_path)?, &mut todo)?;
-        log::info!("Load tasks from file {}", self.todo_path);
-        if let Some(path) = &self.archive_path {
+    pub fn load(&mut self) -> ioResult<()> {
+        let result = self.load_files();
+        self.load_failed = matches!(&result, Err(e) if e.kind() != ErrorKind::NotFound);
+        self.problems = result?;
+        self.sync()?;
+        let mut todo = self.todo.lock().unwrap();
+        todo.mark_saved();
+        self.version = todo.get_version();
+        Ok(())
+    }
+
+    /// Gets the stamps of the todo file and the archive file.
+    fn stamp(&mut self) -> ioResult<Stamp> {
+        let taken = SystemTime::now();
+        let files = self
+            .files
+            .iter_mut()
+            .map(|file| file.stamp())
+            .collect::<ioResult<_>>()?;
+        Ok(Stamp {
+            taken: Some(taken),
+            files,
+        })
+    }
+
+    /// Remembers the content of the file(s) as the base of the next merge.
+    fn sync(&mut self) -> ioResult<()> {
+        // The stamp is taken first, so a change made while reading is found next time.
+        self.synced_stamp = self.stamp()?;
+        self.synced = self.disk_content()?;
+        Ok(())
+    }
+
+    /// Reads the todo file followed by the archive file, a missing file is empty.
+    fn disk_content(&mut self) -> ioResult<String> {
+        let mut content = String::new();
+        for file in &mut self.files {
+            match file.read() {
+                Ok(file) => content += &file,
+                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(content)
+    }
+
+    /// Checks whether the file(s) were changed since the todo list was loaded or saved.
+    /// The content is read only if the modification time or the size of a file differs,
+    /// or the files were modified too recently to trust their modification times.
+    fn changed_on_disk(&mut self) -> ioResult<bool> {
+        let stamp = self.stamp()?;
+        if self.synced_stamp.unchanged(&stamp) {
+            return Ok(false);
+        }
+        let changed = self.disk_content()? != self.synced;
+        if !changed {
+            self.synced_stamp = stamp;
+        }
+        Ok(changed)
+    }
+
+    /// Gets lines of the todo file(s) with problems found during the last load.
+    pub fn problems(&self) -> &[Problem] {
+        &self.problems
+    }
+
+    fn load_files(&mut self) -> ioResult<Vec<Problem>> {
+        let mut todo = ToDo::default();
+        let content = self.files[0].read()?;
+        let mut problems = Self::load_tasks(content.as_bytes(), &self.todo_path, &mut todo)?;
+        log::info!("Load tasks from file {}", self.files[0].location());
+        if let (Some(path), Some(file)) = (&self.archive_path, self.files.get_mut(1)) {
+            // The completed lines of the todo file are saved back to it.
+            todo.set_todo_file_done(todo.done.iter().map(Task::to_string).collect());
             log::info!("Load tasks from achive file {}", path);
-            Self::load_tasks(File::open(path)?, &mut todo)?;
+            problems.extend(Self::load_tasks(file.read()?.as_bytes(), path, &mut todo)?);
         }
         log::debug!("Loaded pending {}x tasks", todo.pending.len());
         log::debug!("Loaded done {}x tasks", todo.done.len());
         self.todo.lock().unwrap().move_data(todo);
-        Ok(())
+        Ok(problems)
     }
 
     /// Loads tasks from a given reader and adds them to the provided `ToDo` instance.
@@ -82,24 +307,47 @@ impl FileWorker {
     /// # Arguments
     ///
     /// * `reader` - A readable source (e.g., a file) to load tasks from.
+    /// * `path` - The path to the file reported with problems of its lines.
     /// * `todo` - A mutable reference to the `ToDo` instance where tasks will be added.
     ///
     /// # Returns
     ///
-    /// An `ioResult` indicating success or an error if file operations fail.
-    fn load_tasks<R: Read>(reader: R, todo: &mut ToDo) -> ioResult<()> {
-        for line in BufReader::new(reader).lines() {
+    /// Lines with problems found by the health check or an error if file operations fail.
+    fn load_tasks<R: Read>(reader: R, path: &str, todo: &mut ToDo) -> ioResult<Vec<Problem>> {
+        let mut problems = Vec::new();
+        for (number, line) in BufReader::new(reader).lines().enumerate() {
             let line = line?;
             let line = line.trim();
             if line.is_empty() {
                 continue;
             }
-            match Task::from_str(line) {
-                Ok(task) => todo.add_task(task),
-                Err(e) => log::warn!("Task cannot be load due {e}: {line}"),
+            let (task, issue) = match parse_task(line) {
+                Ok(task) => {
+                    let data = if task.finished {
+                        ToDoData::Done
+                    } else {
+                        ToDoData::Pending
+                    };
+                    let id = TaskId::new(data.get_data(todo).len(), &task);
+                    todo.add_task(task);
+                    (Some((data, id)), check_line(line))
+                }
+                Err(e) => {
+                    log::warn!("Task cannot be load due {e}: {line}");
+                    (None, Some(Issue::Unparseable(e.to_string())))
+                }
+            };
+            if let Some(issue) = issue {
+                problems.push(Problem {
+                    path: path.to_string(),
+                    line: number + 1,
+                    text: line.to_string(),
+                    issue,
+                    task,
+                });
             }
         }
-        Ok(())
+        Ok(problems)
     }
 
     /// Saves todo list data to the file(s).
@@ -109,21 +357,202 @@ impl FileWorker {
     /// # Returns
     ///
     /// An `ioResult` indicating success or an error if file operations fail.
-    fn save(&self) -> ioResult<()> {
-        let mut f = File::create(&self.todo_path)?;
-        let todo = self.todo.lock().unwrap();
+    fn save(&mut self) -> Result<(), FileWorkerError> {
+        let todo = self.todo.clone();
+        let mut todo = todo.lock().unwrap();
+        // The time tracked so far is saved, the timer keeps running.
+        todo.flush_timer();
+        if let Err(e) = self.save_files(&todo) {
+            return Err(self.unwritable(e, &todo));
+        }
+        self.read_only = false;
+        self.sync()?;
+        todo.mark_saved();
+        self.version = todo.get_version();
+        status::info("Todo list saved");
+        Ok(())
+    }
+
+    /// Writes the todo list to the file(s).
+    fn save_files(&mut self, todo: &ToDo) -> ioResult<()> {
         log::info!(
             "Saving todo task to {}{}",
-            self.todo_path,
+            self.files[0].location(),
             self.archive_path
                 .as_ref()
                 .map_or(String::from(""), |p| String::from(" and") + &p.clone()),
         );
-        Self::save_tasks(&mut f, &todo.pending)?;
-        match &self.archive_path {
-            Some(s) => Self::save_tasks(&mut File::create(s)?, &todo.done),
-            None => Self::save_tasks(&mut f, &todo.done),
+        let contents = Self::file_contents(&self.todo_path, self.archive_path.as_deref(), todo)?;
+        for (file, (_, content)) in self.files.iter_mut().zip(contents) {
+            file.write(&content)?;
         }
+        Ok(())
+    }
+
+    /// Checks whether the error means the file cannot be written at all, so saving
+    /// again does not help until the user resolves it.
+    fn is_unwritable(e: &std::io::Error) -> bool {
+        matches!(
+            e.kind(),
+            ErrorKind::PermissionDenied
+                | ErrorKind::ReadOnlyFilesystem
+                | ErrorKind::StorageFull
+                | ErrorKind::QuotaExceeded
+        )
+    }
+
+    /// Handles an error of writing the todo list. If the file cannot be written at all,
+    /// the worker switches to the read-only mode and exports the unsaved todo list.
+    ///
+    /// # Arguments
+    ///
+    /// * `e` - The error of writing the file(s).
+    /// * `todo` - The todo list that was not saved.
+    ///
+    /// # Returns
+    ///
+    /// The error reported to the user.
+    fn unwritable(&mut self, e: std::io::Error, todo: &ToDo) -> FileWorkerError {
+        if !Self::is_unwritable(&e) {
+            return e.into();
+        }
+        self.read_only = true;
+        let exported = match self.export(todo) {
+            Ok(Some(path)) => format!(" Your changes are exported to {path}."),
+            Ok(None) => String::new(),
+            Err(export) => format!(" Your changes cannot be exported either: {export}."),
+        };
+        FileWorkerError::ReadOnly(format!(
+            "Cannot write the todo file {}: {e}. Changes are kept in memory only until a save succeeds.{exported}",
+            self.todo_path
+        ))
+    }
+
+    /// Writes the todo list to the export file while the todo file cannot be written.
+    ///
+    /// # Returns
+    ///
+    /// The path to the export file or `None` if no export file is set.
+    fn export(&self, todo: &ToDo) -> ioResult<Option<&str>> {
+        let Some(path) = &self.unsaved_path else {
+            return Ok(None);
+        };
+        fs::write(path, self.serialize(todo)?)?;
+        log::info!("Unsaved todo list exported to {}", path);
+        Ok(Some(path))
+    }
+
+    /// Shows what saving the todo list would change in the file(s).
+    ///
+    /// # Arguments
+    ///
+    /// * `todo_path` - The path to the todo list file.
+    /// * `archive_path` - The optional path to the archive file.
+    /// * `todo` - The todo list that would be saved.
+    ///
+    /// # Returns
+    ///
+    /// A unified diff between the files on the disk and the content that would be written,
+    /// an empty string if nothing changes.
+    pub fn save_preview(
+        todo_path: &str,
+        archive_path: Option<&str>,
+        todo: &ToDo,
+    ) -> ioResult<String> {
+        let mut diff = String::new();
+        for (path, content) in Self::file_contents(todo_path, archive_path, todo)? {
+            let old = match fs::read_to_string(path) {
+                Ok(old) => old,
+                Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+                Err(e) => return Err(e),
+            };
+            diff += &unified_diff(path, &old, &String::from_utf8_lossy(&content));
+        }
+        Ok(diff)
+    }
+
+    /// Gets the content written to every file when the todo list is saved.
+    fn file_contents<'a>(
+        todo_path: &'a str,
+        archive_path: Option<&'a str>,
+        todo: &ToDo,
+    ) -> ioResult<Vec<(&'a str, Vec<u8>)>> {
+        let mut pending = Vec::new();
+        Self::save_tasks(&mut pending, &todo.pending)?;
+        let mut files = vec![(todo_path, pending)];
+        match archive_path {
+            Some(path) => {
+                let (kept, archived) = todo.split_done();
+                Self::save_tasks(&mut files[0].1, kept)?;
+                let mut done = Vec::new();
+                Self::save_tasks(&mut done, archived)?;
+                files.push((path, done));
+            }
+            None => Self::save_tasks(&mut files[0].1, &todo.done)?,
+        }
+        Ok(files)
+    }
+
+    /// Gets the content of the file(s) the todo list would be saved as, one after another.
+    fn serialize(&self, todo: &ToDo) -> ioResult<String> {
+        Ok(
+            Self::file_contents(&self.todo_path, self.archive_path.as_deref(), todo)?
+                .into_iter()
+                .map(|(_, content)| String::from_utf8_lossy(&content).into_owned())
+                .collect(),
+        )
+    }
+
+    /// Loads the content of the file(s) and serializes it again, so contents written
+    /// by other programs have the same order and formatting of lines as the todo list.
+    fn normalize(&self, content: &str) -> ioResult<String> {
+        let mut todo = ToDo::default();
+        Self::load_tasks(content.as_bytes(), &self.todo_path, &mut todo)?;
+        self.serialize(&todo)
+    }
+
+    /// Merges the unsaved changes of the todo list with the changes of the file(s)
+    /// made by another program and saves the result.
+    ///
+    /// Both are compared line by line with the content loaded or saved last time. If both
+    /// changed the same task, nothing is changed and the conflicts are reported instead,
+    /// unless the lines of both are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `keep_both` - A flag indicating whether conflicting lines of both are kept.
+    fn merge(&mut self, keep_both: bool) -> Result<(), FileWorkerError> {
+        let shared = self.todo.clone();
+        let mut todo = shared.lock().unwrap();
+        let stamp = self.stamp()?;
+        let disk = self.disk_content()?;
+        let merged = merge_lines(
+            &self.normalize(&self.synced)?,
+            &self.serialize(&todo)?,
+            &self.normalize(&disk)?,
+        );
+        if !merged.conflicts.is_empty() && !keep_both {
+            self.conflict = true;
+            return Err(FileWorkerError::Conflict(merged.conflicts));
+        }
+        let mut merged_todo = ToDo::default();
+        Self::load_tasks(merged.content.as_bytes(), &self.todo_path, &mut merged_todo)?;
+        merged_todo.set_todo_file_done(todo.todo_file_done().to_vec());
+        todo.move_data(merged_todo);
+        if self.dry_run {
+            self.synced_stamp = stamp;
+            self.synced = disk;
+        } else {
+            if let Err(e) = self.save_files(&todo) {
+                return Err(self.unwritable(e, &todo));
+            }
+            self.sync()?;
+            todo.mark_saved();
+        }
+        self.version = todo.get_version();
+        self.conflict = false;
+        log::info!("Changes of the file merged with the todo list.");
+        Ok(())
     }
 
     /// Saves a list of tasks to the provided writer.
@@ -136,9 +565,12 @@ impl FileWorker {
     /// # Returns
     ///
     /// An `ioResult` indicating success or an error if file operations fail.
-    fn save_tasks<W: Write>(writer: &mut W, tasks: &[Task]) -> ioResult<()> {
+    fn save_tasks<'a, W: Write>(
+        writer: &mut W,
+        tasks: impl IntoIterator<Item = &'a Task>,
+    ) -> ioResult<()> {
         let mut writer = BufWriter::new(writer);
-        for task in tasks.iter() {
+        for task in tasks.into_iter().filter(|task| !is_scratch(task)) {
             writer.write_all((task.to_string() + "\n").as_bytes())?;
         }
         Ok(())
@@ -153,14 +585,16 @@ impl FileWorker {
     ///
     /// * `autosave_duration` - The duration between automatic saves of todo data.
     /// * `handle_changes` - A flag indicating whether to handle file change events.
+    /// * `errors` - A sender used to report errors of file operations.
     ///
     /// # Returns
     ///
     /// A `Sender` that can be used to send commands to the `FileWorker` thread.
     pub fn run(
-        self,
+        mut self,
         autosave_duration: Duration,
         handle_changes: bool,
+        errors: Sender<FileWorkerError>,
     ) -> Sender<FileWorkerCommands> {
         use FileWorkerCommands::*;
         let (tx, rx) = mpsc::channel::<FileWorkerCommands>();
@@ -169,52 +603,106 @@ impl FileWorker {
         }
 
         if handle_changes {
-            Self::spawn_watcher(tx.clone(), self.todo_path.clone());
-            if let Some(path) = &self.archive_path {
-                Self::spawn_watcher(tx.clone(), path.clone());
+            for file in &self.files {
+                if file.is_local() {
+                    let path = file.location().to_string();
+                    Self::spawn_watcher(tx.clone(), errors.clone(), path);
+                } else {
+                    Self::spawn_poller(tx.clone(), Self::REMOTE_POLL);
+                }
             }
         }
 
         thread::spawn(move || {
-            let mut version = self.todo.lock().unwrap().get_version();
-            let mut skip_count: usize = 0;
+            self.version = self.todo.lock().unwrap().get_version();
             for received in rx {
-                if let Err(e) = match received {
-                    Save => {
-                        let act_version = self.todo.lock().unwrap().get_version();
-                        if version == act_version {
-                            log::debug!("File Worker: Todo list is actual.");
-                            Ok(())
-                        } else {
-                            skip_count += 2;
-                            version = act_version;
-                            self.save()
-                        }
-                    }
-                    ForceSave => {
-                        skip_count += 2;
-                        self.save()
-                    }
-                    Load => {
-                        if skip_count > 0 {
-                            skip_count -= 1;
-                            log::debug!("Load file 'skip_count': {}", skip_count);
-                            continue;
-                        }
-                        let result = self.load();
-                        version = self.todo.lock().unwrap().get_version();
-                        log::info!("Todo list updated from file.");
-                        result
+                if let Exit = received {
+                    break;
+                }
+                if let Err(e) = self.handle_command(received) {
+                    log::error!("File Worker: {}", e);
+                    if errors.send(e).is_err() {
+                        break;
                     }
-                    Exit => break,
-                } {
-                    log::error!("File Worker: {}", e.kind());
                 }
             }
         });
         tx
     }
 
+    /// Handles the command of the `FileWorker` thread.
+    ///
+    /// The todo list and the file(s) are compared with their state when the todo list was loaded
+    /// or saved last time. If both were changed, the changes are merged. If both changed
+    /// the same task, neither is overwritten, the conflict is reported and automatic saving
+    /// stops until the user forces saving or loading, or keeps the lines of both. If the file(s)
+    /// cannot be written, automatic saves only export the todo list until a forced save succeeds.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The command to handle, `Exit` is handled by the thread.
+    fn handle_command(&mut self, command: FileWorkerCommands) -> Result<(), FileWorkerError> {
+        use FileWorkerCommands::*;
+        match command {
+            Save | ForceSave | SaveAnyway if self.dry_run => {
+                log::debug!("File Worker: Save skipped in the dry run.");
+            }
+            Save => {
+                if self.version == self.todo.lock().unwrap().get_version() {
+                    log::debug!("File Worker: Todo list is actual.");
+                } else if self.load_failed {
+                    log::warn!("File Worker: Autosave skipped, todo list was not loaded.");
+                } else if self.conflict {
+                    log::warn!("File Worker: Autosave skipped, the file has a conflict.");
+                } else if self.read_only {
+                    log::warn!("File Worker: Autosave skipped, the file is read-only.");
+                    self.export(&self.todo.lock().unwrap())?;
+                } else if self.changed_on_disk()? {
+                    self.merge(false)?;
+                } else {
+                    self.save()?;
+                }
+            }
+            ForceSave if self.load_failed => {
+                log::warn!("File Worker: Save refused, todo list was not loaded.");
+                return Err(FileWorkerError::LoadFailed);
+            }
+            ForceSave => {
+                self.conflict = false;
+                self.save()?;
+            }
+            SaveAnyway => {
+                self.load_failed = false;
+                self.conflict = false;
+                self.save()?;
+            }
+            Load if self.read_only && self.version != self.todo.lock().unwrap().get_version() => {
+                log::warn!("File Worker: Load skipped, unsaved changes cannot be written.");
+            }
+            Load => {
+                if !self.changed_on_disk()? {
+                    log::debug!("File Worker: File is actual.");
+                } else if self.version != self.todo.lock().unwrap().get_version() {
+                    self.merge(false)?;
+                } else {
+                    self.load()?;
+                    status::info("Todo list updated from file");
+                }
+            }
+            ForceLoad => {
+                self.conflict = false;
+                self.load()?;
+                status::info("Todo list updated from file");
+            }
+            KeepBoth => {
+                self.conflict = false;
+                self.merge(true)?;
+            }
+            Exit => {}
+        }
+        Ok(())
+    }
+
     /// Spawns an autosave thread that periodically saves the todo list data.
     ///
     /// # Arguments
@@ -233,32 +721,85 @@ impl FileWorker {
         });
     }
 
+    /// Spawns a thread that periodically checks a remote file for changes,
+    /// as remote files cannot be watched.
+    ///
+    /// # Arguments
+    ///
+    /// * `tx` - A sender for sending `FileWorkerCommands` to the `FileWorker` thread.
+    /// * `duration` - The duration between the checks.
+    fn spawn_poller(tx: Sender<FileWorkerCommands>, duration: Duration) {
+        log::trace!("Start remote file poller");
+        thread::spawn(move || loop {
+            thread::sleep(duration);
+            if tx.send(FileWorkerCommands::Load).is_err() {
+                log::trace!("Remote file poller end");
+                break;
+            }
+        });
+    }
+
     /// Spawns a file watcher thread to monitor changes to a specific file.
     ///
+    /// The directory of the file is watched, so a file replaced by renaming another file
+    /// over it, as editors and sync tools do, is detected too.
+    ///
     /// # Arguments
     ///
     /// * `tx` - A sender for sending `FileWorkerCommands` to the `FileWorker` thread.
+    /// * `errors` - A sender used to report that the file cannot be watched.
     /// * `path` - The path to the file to be watched for changes.
-    fn spawn_watcher(tx: Sender<FileWorkerCommands>, path: String) {
+    fn spawn_watcher(
+        tx: Sender<FileWorkerCommands>,
+        errors: Sender<FileWorkerError>,
+        path: String,
+    ) {
         log::trace!("Start file watcher");
         thread::spawn(move || {
+            let file = Path::new(&path);
+            let dir = match file.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => dir,
+                _ => Path::new("."),
+            };
             let (tx_handle, rx_handle) = std::sync::mpsc::channel();
-            let mut watcher: RecommendedWatcher =
-                Watcher::new(tx_handle, NotifyConfig::default()).unwrap();
-            watcher
-                .watch(Path::new(&path), RecursiveMode::NonRecursive)
-                .unwrap();
+            let watcher = RecommendedWatcher::new(tx_handle, NotifyConfig::default()).and_then(
+                |mut watcher| {
+                    watcher.watch(dir, RecursiveMode::NonRecursive)?;
+                    Ok(watcher)
+                },
+            );
+            let _watcher = match watcher {
+                Ok(watcher) => watcher,
+                Err(e) => {
+                    log::error!("Cannot watch file {}: {}", path, e);
+                    let _ = errors.send(FileWorkerError::Failed(format!(
+                        "Cannot watch file {path} for changes: {e}"
+                    )));
+                    return;
+                }
+            };
             for res in rx_handle {
                 match res {
-                    Ok(event) => match event.kind {
-                        EventKind::Access(AccessKind::Close(AccessMode::Write)) => {
-                            log::trace!("File {} changed", path);
-                            if tx.send(FileWorkerCommands::Load).is_err() {
-                                break;
-                            };
+                    Ok(event) => {
+                        if !event
+                            .paths
+                            .iter()
+                            .any(|changed| changed.file_name() == file.file_name())
+                        {
+                            continue;
+                        }
+                        match event.kind {
+                            EventKind::Access(AccessKind::Close(AccessMode::Write))
+                            | EventKind::Create(CreateKind::File | CreateKind::Any)
+                            | EventKind::Modify(ModifyKind::Name(_)) => {
+                                log::trace!("File {} changed", path);
+                                if tx.send(FileWorkerCommands::Load).is_err() {
+                                    break;
+                                };
+                            }
+                            _ => log::debug!("Change: {event:?}"),
                         }
-                        _ => log::debug!("Change: {event:?}"),
-                    },
+                    }
                     Err(error) => log::error!("Error: {error:?}"),
                 }
             }
@@ -269,6 +810,9 @@ impl FileWorker {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::todo::scratch_line;
+    use proptest::prelude::*;
+    use std::fs::File;
 
     const TESTING_STRING: &str = r#"
         x (A) 2023-05-21 2023-04-30 measure space for 1 +project1 @context1 #hashtag1 due:2023-06-30
@@ -282,7 +826,7 @@ mod tests {
     #[test]
     fn test_load_tasks() -> ioResult<()> {
         let mut todo = ToDo::default();
-        FileWorker::load_tasks(TESTING_STRING.as_bytes(), &mut todo)?;
+        FileWorker::load_tasks(TESTING_STRING.as_bytes(), "todo.txt", &mut todo)?;
         assert_eq!(todo.pending.len(), 4);
         assert_eq!(todo.done.len(), 2);
         assert_eq!(
@@ -318,7 +862,7 @@ mod tests {
     #[test]
     fn test_write_tasks() -> ioResult<()> {
         let mut todo = ToDo::default();
-        FileWorker::load_tasks(TESTING_STRING.as_bytes(), &mut todo)?;
+        FileWorker::load_tasks(TESTING_STRING.as_bytes(), "todo.txt", &mut todo)?;
         let get_expected = |line: fn(&String) -> bool| {
             TESTING_STRING
                 .trim()
@@ -357,4 +901,347 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn load_failed() -> ioResult<()> {
+        let todo = Arc::new(Mutex::new(ToDo::default()));
+        let dir =
+            std::env::temp_dir().join(format!("todotxt-tui-load-failed-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let missing = dir.join("todo.txt");
+        let mut worker = FileWorker::new(missing.to_str().unwrap().to_string(), None, todo.clone());
+        assert!(worker.load().is_err());
+        assert!(!worker.load_failed);
+
+        let mut worker = FileWorker::new(dir.to_str().unwrap().to_string(), None, todo);
+        assert!(worker.load().is_err());
+        assert!(worker.load_failed);
+
+        // A forced save is refused as well, only saving anyway overwrites the file.
+        assert_eq!(
+            worker.handle_command(FileWorkerCommands::ForceSave),
+            Err(FileWorkerError::LoadFailed)
+        );
+        assert!(worker.load_failed);
+        fs::remove_dir_all(&dir)
+    }
+
+    #[test]
+    fn save_preview() -> ioResult<()> {
+        let dir =
+            std::env::temp_dir().join(format!("todotxt-tui-save-preview-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("todo.txt");
+        let path = path.to_str().unwrap();
+        std::fs::write(path, "task 1\ntask 2\nx done task\n")?;
+
+        let mut todo = ToDo::default();
+        FileWorker::load_tasks(File::open(path)?, "todo.txt", &mut todo)?;
+        assert_eq!(FileWorker::save_preview(path, None, &todo)?, "");
+        todo.new_task(&scratch_line("stretch")).unwrap();
+        assert_eq!(FileWorker::save_preview(path, None, &todo)?, "");
+
+        todo.add_task(parse_task("task 3").unwrap());
+        todo.remove_task(crate::todo::ToDoData::Pending, 0);
+        assert_eq!(
+            FileWorker::save_preview(path, None, &todo)?,
+            format!("--- {path}\n+++ {path}\n@@ -1,3 +1,3 @@\n-task 1\n task 2\n+task 3\n x done task\n")
+        );
+
+        fs::remove_dir_all(&dir)
+    }
+
+    #[test]
+    fn routines_are_not_archived() -> ioResult<()> {
+        let dir = std::env::temp_dir().join(format!("todotxt-tui-routines-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("todo.txt");
+        let archive = dir.join("done.txt");
+        let (path, archive) = (path.to_str().unwrap(), archive.to_str().unwrap());
+        std::fs::write(path, "")?;
+        std::fs::write(archive, "")?;
+
+        let mut todo = ToDo::default();
+        FileWorker::load_tasks(
+            "task\nx 2023-05-03 stretch routine:daily\nx 2023-05-03 done task\n".as_bytes(),
+            "todo.txt",
+            &mut todo,
+        )?;
+        assert_eq!(
+            FileWorker::save_preview(path, Some(archive), &todo)?,
+            format!(
+                "--- {path}\n+++ {path}\n@@ -0,0 +1,2 @@\n+task\n+x 2023-05-03 stretch routine:daily\n\
+                 --- {archive}\n+++ {archive}\n@@ -0,0 +1,1 @@\n+x 2023-05-03 done task\n"
+            )
+        );
+
+        fs::remove_dir_all(&dir)
+    }
+
+    #[test]
+    fn done_tasks_keep_their_file() -> Result<(), FileWorkerError> {
+        let dir =
+            std::env::temp_dir().join(format!("todotxt-tui-provenance-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("todo.txt");
+        let archive = dir.join("done.txt");
+        let (path, archive) = (path.to_str().unwrap(), archive.to_str().unwrap());
+        fs::write(path, "task\nx 2023-05-03 done in todo\n")?;
+        fs::write(
+            archive,
+            "x 2023-05-01 archived 1\nx 2023-05-02 archived 2\n",
+        )?;
+        let todo = Arc::new(Mutex::new(ToDo::default()));
+        let mut worker = FileWorker::new(path.to_string(), Some(archive.to_string()), todo.clone());
+        worker.load()?;
+        {
+            let mut todo = todo.lock().unwrap();
+            assert_eq!(todo.done.len(), 3);
+            // Nothing changed, both files are saved as they are.
+            assert_eq!(FileWorker::save_preview(path, Some(archive), &todo)?, "");
+
+            // The reopened task leaves the archive for the todo file.
+            let index = todo
+                .get_filtered_and_sorted(ToDoData::Done)
+                .vec
+                .iter()
+                .position(|(_, task)| task.subject == "archived 1");
+            todo.move_task(ToDoData::Done, index.unwrap());
+        }
+        worker.save()?;
+        assert_eq!(
+            fs::read_to_string(path)?,
+            "task\n2023-05-01 archived 1\nx 2023-05-03 done in todo\n"
+        );
+        assert_eq!(fs::read_to_string(archive)?, "x 2023-05-02 archived 2\n");
+
+        Ok(fs::remove_dir_all(&dir)?)
+    }
+
+    #[test]
+    fn conflicts() -> Result<(), FileWorkerError> {
+        use FileWorkerCommands::*;
+        let dir =
+            std::env::temp_dir().join(format!("todotxt-tui-conflicts-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("todo.txt");
+        let path = path.to_str().unwrap();
+        fs::write(path, "task 1\n")?;
+        let todo = Arc::new(Mutex::new(ToDo::default()));
+        let mut worker = FileWorker::new(path.to_string(), None, todo.clone());
+        worker.load()?;
+        let add = |task: &str| todo.lock().unwrap().add_task(parse_task(task).unwrap());
+
+        // Our own write does not reload the todo list.
+        add("task 2");
+        worker.handle_command(Save)?;
+        worker.handle_command(Load)?;
+        assert_eq!(fs::read_to_string(path)?, "task 1\ntask 2\n");
+
+        // A change by another program is loaded.
+        fs::write(path, "task 3\n")?;
+        worker.handle_command(Load)?;
+        assert_eq!(todo.lock().unwrap().pending[0].subject, "task 3");
+
+        // Both the file and the todo list have changed, the changes are merged.
+        add("task 4");
+        fs::write(path, "task 3\ntask 5\n")?;
+        worker.handle_command(Save)?;
+        assert_eq!(fs::read_to_string(path)?, "task 3\ntask 5\ntask 4\n");
+        assert_eq!(todo.lock().unwrap().pending.len(), 3);
+
+        // Both changed the same task.
+        todo.lock().unwrap().remove_task(ToDoData::Pending, 0);
+        add("task 3 edited");
+        fs::write(path, "task 3 changed\ntask 5\ntask 4\n")?;
+        assert_eq!(
+            worker.handle_command(Load),
+            Err(FileWorkerError::Conflict(vec![Conflict {
+                local: Vec::new(),
+                remote: vec![String::from("task 3 changed")],
+            }]))
+        );
+        assert_eq!(todo.lock().unwrap().pending[2].subject, "task 3 edited");
+        // Saving stays blocked until the user resolves the conflict.
+        worker.handle_command(Save)?;
+        assert_eq!(
+            fs::read_to_string(path)?,
+            "task 3 changed\ntask 5\ntask 4\n"
+        );
+
+        worker.handle_command(KeepBoth)?;
+        assert_eq!(
+            fs::read_to_string(path)?,
+            "task 3 changed\ntask 5\ntask 4\ntask 3 edited\n"
+        );
+
+        fs::write(path, "task 3 changed\ntask 5\n")?;
+        worker.handle_command(ForceLoad)?;
+        assert_eq!(todo.lock().unwrap().pending[0].subject, "task 3 changed");
+        add("task 6");
+        worker.handle_command(Save)?;
+        assert_eq!(
+            fs::read_to_string(path)?,
+            "task 3 changed\ntask 5\ntask 6\n"
+        );
+
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn stamp() -> ioResult<()> {
+        let dir = std::env::temp_dir().join(format!("todotxt-tui-stamp-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("todo.txt");
+        let path = path.to_str().unwrap();
+        fs::write(path, "task 1\n")?;
+        let mut worker = FileWorker::new(path.to_string(), None, Arc::default());
+        worker.load()?;
+        // The file was just written, its modification time might not change
+        // on the next write, so the content is compared.
+        fs::write(path, "task 2\n")?;
+        assert!(worker.changed_on_disk()?);
+
+        let old = SystemTime::now() - Duration::from_secs(60);
+        File::options().write(true).open(path)?.set_modified(old)?;
+        worker.load()?;
+        assert!(!worker.changed_on_disk()?);
+        let stamp = worker.stamp()?;
+        assert!(worker.synced_stamp.unchanged(&stamp));
+        // A change of the size is found without reading the file.
+        fs::write(path, "task 2 edited\n")?;
+        assert!(worker.changed_on_disk()?);
+        fs::remove_dir_all(&dir)
+    }
+
+    #[test]
+    fn read_only() -> Result<(), FileWorkerError> {
+        use FileWorkerCommands::*;
+        let dir =
+            std::env::temp_dir().join(format!("todotxt-tui-read-only-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("todo.txt");
+        let path = path.to_str().unwrap();
+        let unsaved = dir.join("unsaved.txt");
+        fs::write(path, "task 1\n")?;
+        let todo = Arc::new(Mutex::new(ToDo::default()));
+        let mut worker = FileWorker::new(path.to_string(), None, todo.clone());
+        worker.set_unsaved_path(unsaved.to_str().unwrap().to_string());
+        worker.load()?;
+        let add = |task: &str| todo.lock().unwrap().add_task(parse_task(task).unwrap());
+
+        add("task 2");
+        let error = std::io::Error::from(ErrorKind::StorageFull);
+        assert!(matches!(
+            worker.unwritable(error, &todo.lock().unwrap()),
+            FileWorkerError::ReadOnly(_)
+        ));
+        assert_eq!(fs::read_to_string(&unsaved)?, "task 1\ntask 2\n");
+
+        // Autosave only exports the changes, the file is not reloaded over them.
+        add("task 3");
+        worker.handle_command(Save)?;
+        assert_eq!(fs::read_to_string(path)?, "task 1\n");
+        assert_eq!(fs::read_to_string(&unsaved)?, "task 1\ntask 2\ntask 3\n");
+        fs::write(path, "task 4\n")?;
+        worker.handle_command(Load)?;
+        assert_eq!(todo.lock().unwrap().pending.len(), 3);
+
+        // A forced save that succeeds leaves the read-only mode.
+        worker.handle_command(ForceSave)?;
+        assert!(!worker.read_only);
+        assert_eq!(fs::read_to_string(path)?, "task 1\ntask 2\ntask 3\n");
+        assert!(matches!(
+            worker.unwritable(std::io::Error::from(ErrorKind::NotFound), &ToDo::default()),
+            FileWorkerError::Failed(_)
+        ));
+        assert!(!worker.read_only);
+
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn load_problems() -> ioResult<()> {
+        let content = "task 1\n\ntask 2 start:2023-13-01\nx 2023-05-01 task 3 review:2023-02-30\n";
+        let mut todo = ToDo::default();
+        let problems = FileWorker::load_tasks(content.as_bytes(), "todo.txt", &mut todo)?;
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].line, 3);
+        assert_eq!(problems[0].text, "task 2 start:2023-13-01");
+        assert_eq!(
+            problems[0].issue,
+            Issue::InvalidDate(String::from("start:2023-13-01"))
+        );
+        let (data, id) = problems[0].task.clone().unwrap();
+        assert_eq!(data, ToDoData::Pending);
+        assert_eq!(todo.find_task(data, &id), Some(1));
+
+        assert_eq!(problems[1].line, 4);
+        let (data, id) = problems[1].task.clone().unwrap();
+        assert_eq!(data, ToDoData::Done);
+        assert_eq!(todo.find_task(data, &id), Some(0));
+        Ok(())
+    }
+
+    /// Loads tasks from the content, saves them and loads them again.
+    fn round_trip(content: &str) -> ioResult<(ToDo, ToDo)> {
+        let mut loaded = ToDo::default();
+        FileWorker::load_tasks(content.as_bytes(), "todo.txt", &mut loaded)?;
+        let mut buf = Vec::new();
+        FileWorker::save_tasks(&mut buf, &loaded.pending)?;
+        FileWorker::save_tasks(&mut buf, &loaded.done)?;
+        let mut reloaded = ToDo::default();
+        FileWorker::load_tasks(buf.as_slice(), "todo.txt", &mut reloaded)?;
+        Ok((loaded, reloaded))
+    }
+
+    #[test]
+    fn round_trip_tricky_lines() -> ioResult<()> {
+        let content = std::fs::read_to_string(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("test_files/tricky_lines.txt"),
+        )?;
+        let (loaded, reloaded) = round_trip(&content)?;
+        assert!(!loaded.pending.is_empty());
+        assert!(!loaded.done.is_empty());
+        assert_eq!(loaded.pending, reloaded.pending);
+        assert_eq!(loaded.done, reloaded.done);
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip_generated_lines(
+            lines in prop::collection::vec(
+                (
+                    prop::option::of("x "),
+                    prop::option::of("\\([A-Z]\\) "),
+                    prop::option::of("20[0-9]{2}-(0[1-9]|1[0-2])-(0[1-9]|1[0-9]|2[0-8]) "),
+                    prop::collection::vec(
+                        prop_oneof![
+                            "[a-zA-Z0-9.,!?()\\[\\]$\\\\]{1,8}",
+                            "\\+[a-zA-Z0-9_-]{1,8}",
+                            "@[a-zA-Z0-9_-]{1,8}",
+                            "#[a-zA-Z0-9_-]{1,8}",
+                            "[a-z]{1,5}:[a-zA-Z0-9-]{1,8}",
+                            "\\PC{1,4}",
+                        ],
+                        1..8,
+                    ),
+                )
+                    .prop_map(|(done, priority, date, words)| {
+                        done.unwrap_or_default()
+                            + &priority.unwrap_or_default()
+                            + &date.unwrap_or_default()
+                            + &words.join(" ")
+                    }),
+                1..10,
+            )
+        ) {
+            let (loaded, reloaded) = round_trip(&lines.join("\n")).unwrap();
+            prop_assert_eq!(loaded.pending, reloaded.pending);
+            prop_assert_eq!(loaded.done, reloaded.done);
+        }
+    }
 }
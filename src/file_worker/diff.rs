@@ -0,0 +1,156 @@
+/// Number of unchanged lines shown around every change.
+const CONTEXT: usize = 3;
+
+/// A line of the diff.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum Op<'a> {
+    Equal(&'a str),
+    Delete(&'a str),
+    Insert(&'a str),
+}
+
+/// Creates a unified diff between the old and the new content of a file.
+///
+/// # Arguments
+///
+/// * `path` - The path of the file shown in the diff header.
+/// * `old` - The content of the file on the disk.
+/// * `new` - The content that will be written to the file.
+///
+/// # Returns
+///
+/// The diff or an empty string if the contents have the same lines.
+pub fn unified_diff(path: &str, old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let ops = diff_lines(&old, &new);
+
+    // Positions in the old and the new file before every op.
+    let mut positions = Vec::with_capacity(ops.len() + 1);
+    let (mut old_pos, mut new_pos) = (0, 0);
+    for op in &ops {
+        positions.push((old_pos, new_pos));
+        match op {
+            Op::Equal(_) => {
+                old_pos += 1;
+                new_pos += 1;
+            }
+            Op::Delete(_) => old_pos += 1,
+            Op::Insert(_) => new_pos += 1,
+        }
+    }
+    positions.push((old_pos, new_pos));
+
+    let changes: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| !matches!(op, Op::Equal(_)))
+        .map(|(i, _)| i)
+        .collect();
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    let mut diff = format!("--- {path}\n+++ {path}\n");
+    let mut i = 0;
+    while i < changes.len() {
+        let first = changes[i];
+        let mut last = first;
+        while i + 1 < changes.len() && changes[i + 1] - last <= 2 * CONTEXT + 1 {
+            i += 1;
+            last = changes[i];
+        }
+        i += 1;
+        let start = first.saturating_sub(CONTEXT);
+        let end = (last + CONTEXT + 1).min(ops.len());
+        let (old_start, new_start) = positions[start];
+        let (old_end, new_end) = positions[end];
+        diff += &format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(old_start, old_end - old_start),
+            hunk_range(new_start, new_end - new_start)
+        );
+        for op in &ops[start..end] {
+            let (sign, line) = match op {
+                Op::Equal(line) => (' ', line),
+                Op::Delete(line) => ('-', line),
+                Op::Insert(line) => ('+', line),
+            };
+            diff.push(sign);
+            diff += line;
+            diff.push('\n');
+        }
+    }
+    diff
+}
+
+/// Formats the line range of a hunk, lines are counted from 1.
+fn hunk_range(start: usize, count: usize) -> String {
+    if count == 0 {
+        format!("{start},0")
+    } else {
+        format!("{},{count}", start + 1)
+    }
+}
+
+/// Computes the shortest edit of the old lines to the new lines
+/// using the longest common subsequence.
+pub(super) fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Op<'a>> {
+    let (n, m) = (old.len(), new.len());
+    // lcs[i][j] is the length of the longest common subsequence of old[i..] and new[j..].
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old[i] == new[j] {
+            ops.push(Op::Equal(old[i]));
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(Op::Delete(old[i]));
+            i += 1;
+        } else {
+            ops.push(Op::Insert(new[j]));
+            j += 1;
+        }
+    }
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff() {
+        assert_eq!(unified_diff("todo.txt", "a\nb\n", "a\nb\n"), "");
+        assert_eq!(
+            unified_diff("todo.txt", "a\nb\nc\n", "a\nB\nc\nd\n"),
+            "--- todo.txt\n+++ todo.txt\n@@ -1,3 +1,4 @@\n a\n-b\n+B\n c\n+d\n"
+        );
+        assert_eq!(
+            unified_diff("todo.txt", "", "a\n"),
+            "--- todo.txt\n+++ todo.txt\n@@ -0,0 +1,1 @@\n+a\n"
+        );
+
+        // Distant changes are in separate hunks.
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n";
+        let new = "0\n1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n";
+        assert_eq!(
+            unified_diff("todo.txt", old, new),
+            "--- todo.txt\n+++ todo.txt\n\
+            @@ -1,3 +1,4 @@\n+0\n 1\n 2\n 3\n\
+            @@ -9,4 +10,3 @@\n 9\n 10\n 11\n-12\n"
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use crate::todo::{TaskId, ToDoData};
+use chrono::NaiveDate;
+use std::fmt;
+
+/// Describes what is wrong with a line of the todo file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Issue {
+    /// The line cannot be parsed as a task, it is not loaded.
+    Unparseable(String),
+    /// The line contains something that looks like a date but is not a valid date.
+    InvalidDate(String),
+    /// The line starts with something that looks like a priority but is not `(A)` to `(Z)`.
+    MalformedPriority(String),
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::Unparseable(e) => write!(f, "cannot be parsed ({e})"),
+            Issue::InvalidDate(date) => write!(f, "invalid date {date}"),
+            Issue::MalformedPriority(priority) => write!(f, "malformed priority {priority}"),
+        }
+    }
+}
+
+/// A line of the todo file found by the health check.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Problem {
+    /// Path to the file with the line.
+    pub path: String,
+    /// Number of the line counted from 1.
+    pub line: usize,
+    /// The line as it is written in the file.
+    pub text: String,
+    pub issue: Issue,
+    /// The task loaded from the line, `None` if the line was not loaded.
+    pub task: Option<(ToDoData, TaskId)>,
+}
+
+/// Checks a line of the todo file for suspicious content, lines that cannot
+/// be parsed at all are reported by the loader.
+///
+/// # Arguments
+///
+/// * `line` - The trimmed line of the todo file.
+///
+/// # Returns
+///
+/// The first issue found in the line or `None` if the line is healthy.
+pub fn check_line(line: &str) -> Option<Issue> {
+    let rest = line.strip_prefix("x ").unwrap_or(line);
+    if let Some(priority) = rest.strip_prefix('(') {
+        if let Some(end) = priority.find(')').filter(|end| *end <= 3) {
+            let inner = &priority[..end];
+            let valid = inner.len() == 1 && inner.chars().all(|c| c.is_ascii_uppercase());
+            if !valid || !priority[end + 1..].starts_with(' ') {
+                return Some(Issue::MalformedPriority(format!("({inner})")));
+            }
+        }
+    }
+    line.split_whitespace()
+        .find_map(|token| match token.split_once(':') {
+            Some(("due" | "t", value)) if !value.is_empty() && !is_date(value) => {
+                Some(token.to_string())
+            }
+            Some((_, value)) if looks_like_date(value) && !is_date(value) => {
+                Some(token.to_string())
+            }
+            None if looks_like_date(token) && !is_date(token) => Some(token.to_string()),
+            _ => None,
+        })
+        .map(Issue::InvalidDate)
+}
+
+/// Checks whether the text has the shape of a date, `NNNN-NN-NN`.
+fn looks_like_date(text: &str) -> bool {
+    let parts: Vec<&str> = text.split('-').collect();
+    parts.len() == 3
+        && parts[0].len() == 4
+        && parts
+            .iter()
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn is_date(text: &str) -> bool {
+    NaiveDate::parse_from_str(text, "%Y-%m-%d").is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check() {
+        assert_eq!(check_line("(A) 2023-04-30 call mom due:2023-05-02"), None);
+        assert_eq!(check_line("x 2023-05-01 2023-04-30 pay the rent"), None);
+        assert_eq!(check_line("note (important) remind:2024-07-01T14:30"), None);
+        assert_eq!(
+            check_line("(a) call mom"),
+            Some(Issue::MalformedPriority(String::from("(a)")))
+        );
+        assert_eq!(
+            check_line("(AB) call mom"),
+            Some(Issue::MalformedPriority(String::from("(AB)")))
+        );
+        assert_eq!(
+            check_line("(A)call mom"),
+            Some(Issue::MalformedPriority(String::from("(A)")))
+        );
+        assert_eq!(
+            check_line("2023-02-30 call mom"),
+            Some(Issue::InvalidDate(String::from("2023-02-30")))
+        );
+        assert_eq!(
+            check_line("call mom due:2023-13-01"),
+            Some(Issue::InvalidDate(String::from("due:2023-13-01")))
+        );
+        assert_eq!(
+            check_line("call mom t:tomorrow"),
+            Some(Issue::InvalidDate(String::from("t:tomorrow")))
+        );
+    }
+}
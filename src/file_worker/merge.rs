@@ -0,0 +1,211 @@
+use super::diff::{diff_lines, Op};
+
+/// Lines of the base replaced by one side of the merge.
+#[derive(Debug, PartialEq, Eq)]
+struct Hunk<'a> {
+    /// The first replaced line of the base.
+    start: usize,
+    /// The line of the base after the replaced lines.
+    end: usize,
+    lines: Vec<&'a str>,
+}
+
+/// Gets the hunks changing the base lines to the new lines.
+fn hunks<'a>(base: &[&'a str], new: &[&'a str]) -> Vec<Hunk<'a>> {
+    let mut hunks: Vec<Hunk> = Vec::new();
+    let mut pos = 0;
+    let mut changed = false;
+    for op in diff_lines(base, new) {
+        if !changed && !matches!(op, Op::Equal(_)) {
+            hunks.push(Hunk {
+                start: pos,
+                end: pos,
+                lines: Vec::new(),
+            });
+        }
+        changed = !matches!(op, Op::Equal(_));
+        match op {
+            Op::Equal(_) => pos += 1,
+            Op::Delete(_) => {
+                pos += 1;
+                hunks.last_mut().unwrap().end = pos;
+            }
+            Op::Insert(line) => hunks.last_mut().unwrap().lines.push(line),
+        }
+    }
+    hunks
+}
+
+/// Lines changed differently by both sides of the merge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    /// The lines of the todo list.
+    pub local: Vec<String>,
+    /// The lines of the file changed by another program.
+    pub remote: Vec<String>,
+}
+
+/// The result of merging two versions of a file.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Merged {
+    /// The merged content, the lines of both sides are kept where they conflict.
+    pub content: String,
+    pub conflicts: Vec<Conflict>,
+}
+
+/// A hunk with the side of the merge that made it.
+struct Change<'a> {
+    remote: bool,
+    hunk: Hunk<'a>,
+}
+
+/// Gets the lines of one side between the `start` and `end` lines of the base.
+fn side_lines<'a>(
+    base: &[&'a str],
+    start: usize,
+    end: usize,
+    changes: &[&Change<'a>],
+    remote: bool,
+) -> Vec<&'a str> {
+    let mut lines = Vec::new();
+    let mut pos = start;
+    for change in changes.iter().filter(|change| change.remote == remote) {
+        lines.extend(&base[pos..change.hunk.start]);
+        lines.extend(&change.hunk.lines);
+        pos = change.hunk.end;
+    }
+    lines.extend(&base[pos..end]);
+    lines
+}
+
+/// Merges the changes of two versions of a file made since their common base version.
+///
+/// Changes of different lines are combined, lines added by both sides at the same place
+/// are kept from both, the remote lines first. An identical change made by both sides
+/// is applied once. Lines changed or removed differently by both sides are a conflict,
+/// the merged content has the remote lines followed by the local lines there.
+///
+/// # Arguments
+///
+/// * `base` - The content both versions were made from.
+/// * `local` - The content changed in the application.
+/// * `remote` - The content changed by another program.
+///
+/// # Returns
+///
+/// The merged content with the conflicts.
+pub fn merge_lines(base: &str, local: &str, remote: &str) -> Merged {
+    let base: Vec<&str> = base.lines().collect();
+    let local: Vec<&str> = local.lines().collect();
+    let remote: Vec<&str> = remote.lines().collect();
+
+    let mut changes: Vec<Change> = hunks(&base, &remote)
+        .into_iter()
+        .map(|hunk| Change { remote: true, hunk })
+        .collect();
+    changes.extend(hunks(&base, &local).into_iter().map(|hunk| Change {
+        remote: false,
+        hunk,
+    }));
+    // The sort is stable, so remote lines come first.
+    changes.sort_by_key(|change| (change.hunk.start, change.hunk.end));
+
+    // Overlapping changes and identical changes of both sides are grouped together.
+    let mut groups: Vec<(usize, usize, Vec<&Change>)> = Vec::new();
+    for change in &changes {
+        match groups.last_mut() {
+            Some((_, end, group))
+                if change.hunk.start < *end
+                    || group.last().is_some_and(|last| last.hunk == change.hunk) =>
+            {
+                *end = (*end).max(change.hunk.end);
+                group.push(change);
+            }
+            _ => groups.push((change.hunk.start, change.hunk.end, vec![change])),
+        }
+    }
+
+    let mut merged = Merged {
+        content: String::new(),
+        conflicts: Vec::new(),
+    };
+    let mut pos = 0;
+    for (start, end, group) in &groups {
+        let first = group[0];
+        let lines = if group.iter().all(|change| change.hunk == first.hunk) {
+            side_lines(&base, *start, *end, &group[..1], first.remote)
+        } else {
+            let remote = side_lines(&base, *start, *end, group, true);
+            let local = side_lines(&base, *start, *end, group, false);
+            merged.conflicts.push(Conflict {
+                local: local.iter().map(|line| line.to_string()).collect(),
+                remote: remote.iter().map(|line| line.to_string()).collect(),
+            });
+            remote.into_iter().chain(local).collect()
+        };
+        for line in base[pos..*start].iter().chain(&lines) {
+            merged.content += line;
+            merged.content.push('\n');
+        }
+        pos = *end;
+    }
+    for line in &base[pos..] {
+        merged.content += line;
+        merged.content.push('\n');
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn merged(base: &str, local: &str, remote: &str) -> Option<String> {
+        let merged = merge_lines(base, local, remote);
+        merged.conflicts.is_empty().then_some(merged.content)
+    }
+
+    #[test]
+    fn merge() {
+        let base = "a\nb\nc\nd\n";
+        assert_eq!(merged(base, base, base).as_deref(), Some(base));
+        // Different lines were changed.
+        assert_eq!(
+            merged(base, "a\nB\nc\nd\ne\n", "0\na\nb\nc\nD\n").as_deref(),
+            Some("0\na\nB\nc\nD\ne\n")
+        );
+        // Both sides added lines at the end.
+        assert_eq!(
+            merged(base, "a\nb\nc\nd\nlocal\n", "a\nb\nc\nd\nremote\n").as_deref(),
+            Some("a\nb\nc\nd\nremote\nlocal\n")
+        );
+        // The same change made by both sides.
+        assert_eq!(
+            merged(base, "a\nB\nc\nd\n", "a\nB\nc\nd\n").as_deref(),
+            Some("a\nB\nc\nd\n")
+        );
+        // The same line was changed differently or changed and removed.
+        assert_eq!(merged(base, "a\nlocal\nc\nd\n", "a\nremote\nc\nd\n"), None);
+        assert_eq!(merged(base, "a\nlocal\nc\nd\n", "a\nc\nd\n"), None);
+        assert_eq!(merged("", "local\n", "").as_deref(), Some("local\n"));
+    }
+
+    #[test]
+    fn conflicts() {
+        let base = "a\nb\nc\nd\n";
+        // The remote side changed two lines, the local side one of them.
+        let merged = merge_lines(base, "a\nlocal\nc\nd\ne\n", "a\nB\nC\nd\n");
+        assert_eq!(
+            merged.conflicts,
+            [Conflict {
+                local: vec![String::from("local"), String::from("c")],
+                remote: vec![String::from("B"), String::from("C")],
+            }]
+        );
+        assert_eq!(merged.content, "a\nB\nC\nlocal\nc\nd\ne\n");
+
+        let merged = merge_lines(base, "a\nlocal\nc\nd\n", "a\nc\nd\n");
+        assert_eq!(merged.conflicts[0].remote, Vec::<String>::new());
+        assert_eq!(merged.content, "a\nlocal\nc\nd\n");
+    }
+}
@@ -0,0 +1,404 @@
+use super::storage::{FileStamp, LocalFile, Storage};
+use crate::status;
+use std::{
+    io::{Error, ErrorKind, Result as ioResult, Write},
+    process::{Command, Output, Stdio},
+};
+
+/// The way the remote file is transferred.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Protocol {
+    /// HTTP requests of `curl` to a WebDAV server, e.g. `https://dav.example.com/todo.txt`.
+    WebDav,
+    /// Commands run by `ssh` on the host, e.g. `ssh://me@example.com/todo.txt`.
+    Ssh {
+        destination: String,
+        port: Option<String>,
+        path: String,
+    },
+}
+
+/// A todo file on a WebDAV server or reachable over SSH, kept in a local file.
+///
+/// The remote file is downloaded when the todo list is loaded and uploaded when it is saved,
+/// the local copy is used while the server cannot be reached. Changes of the remote file are
+/// found by its tag, the ETag of the WebDAV server or the checksum of the file over SSH, and
+/// a write is refused if the remote file changed since its tag was taken.
+pub struct RemoteFile {
+    url: String,
+    protocol: Protocol,
+    cache: LocalFile,
+    /// The tag of the remote file when it was checked last time.
+    tag: Option<String>,
+}
+
+impl RemoteFile {
+    /// Creates the storage of the remote file.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL of the file, `http(s)://` for WebDAV, `ssh://`, `sftp://` or `scp://`
+    ///   with `[user@]host[:port]/path` for SSH. A path starting with `/~/` is relative
+    ///   to the home directory.
+    /// * `cache` - The path to the local copy of the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of the kind `InvalidInput` if the URL is not supported.
+    pub fn new(url: &str, cache: &str) -> ioResult<Self> {
+        Ok(Self {
+            url: url.to_string(),
+            protocol: Self::protocol(url).ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("URL {url} is not supported, use http(s):// or ssh://"),
+                )
+            })?,
+            cache: LocalFile::new(cache),
+            tag: None,
+        })
+    }
+
+    fn protocol(url: &str) -> Option<Protocol> {
+        if url.starts_with("http://") || url.starts_with("https://") {
+            return Some(Protocol::WebDav);
+        }
+        let (_, address) = url
+            .split_once("://")
+            .filter(|(scheme, _)| matches!(*scheme, "ssh" | "sftp" | "scp"))?;
+        let (host, path) = address.split_once('/')?;
+        let (destination, port) = match host.rsplit_once(':') {
+            Some((destination, port)) => (destination, Some(port.to_string())),
+            None => (host, None),
+        };
+        let path = path
+            .strip_prefix("~/")
+            .unwrap_or(&format!("/{path}"))
+            .to_string();
+        // A destination starting with `-` would be read by `ssh` as an option.
+        let valid = !destination.is_empty() && !destination.starts_with('-');
+        (valid && !path.is_empty()).then(|| Protocol::Ssh {
+            destination: destination.to_string(),
+            port,
+            path,
+        })
+    }
+
+    /// Creates the `curl` command of a request to the WebDAV server.
+    fn curl(&self) -> Command {
+        let mut command = Command::new("curl");
+        command.args(["--silent", "--show-error", "--location", "--netrc-optional"]);
+        command
+    }
+
+    /// Runs the script with the quoted path of the file on the SSH host.
+    fn ssh(&self, script: &str, input: Option<&[u8]>) -> ioResult<Output> {
+        let Protocol::Ssh {
+            destination,
+            port,
+            path,
+        } = &self.protocol
+        else {
+            unreachable!("SSH command of a WebDAV file");
+        };
+        let mut command = Command::new("ssh");
+        command.args(["-o", "BatchMode=yes"]);
+        if let Some(port) = port {
+            command.args(["-p", port]);
+        }
+        let quoted = format!("'{}'", path.replace('\'', "'\\''"));
+        command.args(["--", destination, &script.replace("{}", &quoted)]);
+        run(command, input)
+    }
+
+    /// Downloads the remote file.
+    fn download(&self) -> ioResult<String> {
+        let output = match &self.protocol {
+            Protocol::WebDav => {
+                let mut command = self.curl();
+                command.args(["--write-out", "\n%{http_code}", &self.url]);
+                let stdout = curl_output(command, None)?;
+                let (content, code) = stdout.rsplit_once('\n').unwrap_or_default();
+                match code {
+                    "200" => content.to_string(),
+                    "404" => return Err(not_found(&self.url)),
+                    code => return Err(Error::other(format!("HTTP status {code}"))),
+                }
+            }
+            Protocol::Ssh { .. } => {
+                let output = self.ssh("cat -- {}", None)?;
+                match output.status.success() {
+                    true => String::from_utf8_lossy(&output.stdout).into_owned(),
+                    false => return Err(ssh_error(&output, &self.url)),
+                }
+            }
+        };
+        Ok(output)
+    }
+
+    /// Gets the tag of the remote file, `None` if it does not exist.
+    fn remote_tag(&self) -> ioResult<Option<String>> {
+        match &self.protocol {
+            Protocol::WebDav => {
+                let mut command = self.curl();
+                command.args(["--head", &self.url]);
+                parse_head(&curl_output(command, None)?)
+            }
+            Protocol::Ssh { .. } => {
+                let output = self.ssh("cksum < {}", None)?;
+                match output.status.success() {
+                    true => Ok(Some(
+                        String::from_utf8_lossy(&output.stdout).trim().to_string(),
+                    )),
+                    false => match ssh_error(&output, &self.url) {
+                        e if e.kind() == ErrorKind::NotFound => Ok(None),
+                        e => Err(e),
+                    },
+                }
+            }
+        }
+    }
+
+    /// Uploads the content unless the remote file changed since its tag was taken.
+    fn upload(&self, content: &[u8]) -> ioResult<()> {
+        let changed = || {
+            Error::other(format!(
+                "{} was changed on the server, it is merged with the next save",
+                self.url
+            ))
+        };
+        match &self.protocol {
+            Protocol::WebDav => {
+                let mut command = self.curl();
+                command.args(["--upload-file", "-", "--write-out", "\n%{http_code}"]);
+                // A tag made of the modification time is not an ETag the server can match.
+                if let Some(tag) = self.tag.as_ref().filter(|tag| tag.ends_with('"')) {
+                    command.args(["--header", &format!("If-Match: {tag}")]);
+                }
+                command.arg(&self.url);
+                let stdout = curl_output(command, Some(content))?;
+                match stdout.rsplit_once('\n').unwrap_or_default().1 {
+                    "200" | "201" | "204" => Ok(()),
+                    "412" => Err(changed()),
+                    code => Err(Error::other(format!("HTTP status {code}"))),
+                }
+            }
+            Protocol::Ssh { .. } => {
+                if self.remote_tag()? != self.tag {
+                    return Err(changed());
+                }
+                let output = self.ssh("cat > {}", Some(content))?;
+                match output.status.success() {
+                    true => Ok(()),
+                    false => Err(ssh_error(&output, &self.url)),
+                }
+            }
+        }
+    }
+}
+
+impl Storage for RemoteFile {
+    fn location(&self) -> &str {
+        &self.url
+    }
+
+    fn read(&mut self) -> ioResult<String> {
+        match self.download() {
+            Ok(content) => {
+                self.cache.write(content.as_bytes())?;
+                Ok(content)
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Err(e),
+            Err(e) => {
+                log::error!("Cannot download {}: {}", self.url, e);
+                status::error(format!(
+                    "Cannot download {}, the local copy is used",
+                    self.url
+                ));
+                self.cache.read()
+            }
+        }
+    }
+
+    fn write(&mut self, content: &[u8]) -> ioResult<()> {
+        self.cache.write(content)?;
+        self.upload(content).map_err(|e| {
+            Error::new(
+                e.kind(),
+                format!(
+                    "Cannot upload {}: {e}. The changes are kept in {}.",
+                    self.url,
+                    self.cache.location()
+                ),
+            )
+        })
+    }
+
+    fn stamp(&mut self) -> ioResult<Option<FileStamp>> {
+        match self.remote_tag() {
+            Ok(tag) => self.tag = tag,
+            // Offline the remote file is taken as unchanged, the local copy is used.
+            Err(e) => log::warn!("Cannot check {}: {}", self.url, e),
+        }
+        Ok(self.tag.clone().map(FileStamp::Tag))
+    }
+
+    fn is_local(&self) -> bool {
+        false
+    }
+}
+
+/// Runs the command, the input is written to its standard input.
+fn run(mut command: Command, input: Option<&[u8]>) -> ioResult<Output> {
+    log::debug!("Run {:?}", command);
+    let program = command.get_program().to_string_lossy().into_owned();
+    // A missing program is not a missing file.
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| Error::other(format!("Cannot run {program}: {e}")))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.unwrap_or_default())?;
+    }
+    child.wait_with_output()
+}
+
+/// Runs the `curl` command and gets its output, it fails if the server cannot be reached.
+fn curl_output(command: Command, input: Option<&[u8]>) -> ioResult<String> {
+    let output = run(command, input)?;
+    match output.status.success() {
+        true => Ok(String::from_utf8_lossy(&output.stdout).into_owned()),
+        false => Err(Error::other(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        )),
+    }
+}
+
+fn not_found(url: &str) -> Error {
+    Error::new(ErrorKind::NotFound, format!("{url} does not exist"))
+}
+
+/// Gets the error of a failed SSH command, a missing file is an error of the kind `NotFound`.
+fn ssh_error(output: &Output, url: &str) -> Error {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    match stderr.contains("No such file") {
+        true => not_found(url),
+        false => Error::other(stderr.trim().to_string()),
+    }
+}
+
+/// Gets the tag of the file from the response headers of a `HEAD` request, the ETag
+/// or the modification time and the size if the server sends no ETag.
+///
+/// # Returns
+///
+/// `None` if the file does not exist.
+fn parse_head(head: &str) -> ioResult<Option<String>> {
+    // Redirects are followed, the headers of the last response are used.
+    let response = head
+        .split("\r\n\r\n")
+        .map(str::trim)
+        .filter(|response| !response.is_empty())
+        .last()
+        .unwrap_or_default();
+    let mut lines = response.lines();
+    let status = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .unwrap_or_default();
+    let headers: Vec<(String, &str)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_lowercase(), value.trim()))
+        .collect();
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(header, _)| header == name)
+            .map(|(_, value)| *value)
+    };
+    match status {
+        "200" => Ok(Some(header("etag").map(String::from).unwrap_or_else(
+            || {
+                format!(
+                    "{} {}",
+                    header("last-modified").unwrap_or_default(),
+                    header("content-length").unwrap_or_default()
+                )
+            },
+        ))),
+        "404" => Ok(None),
+        status => Err(Error::other(format!("HTTP status {status}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn protocol() {
+        assert_eq!(
+            RemoteFile::protocol("https://dav.example.com/todo.txt"),
+            Some(Protocol::WebDav)
+        );
+        assert_eq!(
+            RemoteFile::protocol("ssh://me@example.com:2222/~/todo.txt"),
+            Some(Protocol::Ssh {
+                destination: String::from("me@example.com"),
+                port: Some(String::from("2222")),
+                path: String::from("todo.txt"),
+            })
+        );
+        assert_eq!(
+            RemoteFile::protocol("sftp://example.com/srv/todo.txt"),
+            Some(Protocol::Ssh {
+                destination: String::from("example.com"),
+                port: None,
+                path: String::from("/srv/todo.txt"),
+            })
+        );
+        assert_eq!(RemoteFile::protocol("ftp://example.com/todo.txt"), None);
+        assert_eq!(RemoteFile::protocol("ssh://example.com"), None);
+        assert_eq!(
+            RemoteFile::protocol("ssh://-oProxyCommand=touch_pwned/todo.txt"),
+            None
+        );
+        assert!(RemoteFile::new("todo.txt", "todo.txt").is_err());
+    }
+
+    #[test]
+    fn head() -> ioResult<()> {
+        let head = "HTTP/1.1 301 Moved Permanently\r\nLocation: /todo.txt\r\n\r\n\
+                    HTTP/2 200\r\netag: \"5f2-1a\"\r\ncontent-length: 42\r\n\r\n";
+        assert_eq!(parse_head(head)?, Some(String::from("\"5f2-1a\"")));
+        let head = "HTTP/1.1 200 OK\r\nLast-Modified: Mon, 01 May 2023 10:00:00 GMT\r\n\
+                    Content-Length: 42\r\n\r\n";
+        assert_eq!(
+            parse_head(head)?,
+            Some(String::from("Mon, 01 May 2023 10:00:00 GMT 42"))
+        );
+        assert_eq!(parse_head("HTTP/1.1 404 Not Found\r\n\r\n")?, None);
+        assert!(parse_head("HTTP/1.1 401 Unauthorized\r\n\r\n").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn offline() -> ioResult<()> {
+        let cache = std::env::temp_dir().join(format!(
+            "todotxt-tui-remote-cache-{}.txt",
+            std::process::id()
+        ));
+        fs::write(&cache, "cached task\n")?;
+        // Nothing listens on the discard port, the server cannot be reached.
+        let mut file = RemoteFile::new("http://127.0.0.1:9/todo.txt", cache.to_str().unwrap())?;
+        assert_eq!(file.read()?, "cached task\n");
+        assert_eq!(file.stamp()?, None);
+        assert!(file.write(b"changed task\n").is_err());
+        assert_eq!(fs::read_to_string(&cache)?, "changed task\n");
+        fs::remove_file(cache)?;
+        Ok(())
+    }
+}
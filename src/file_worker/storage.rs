@@ -0,0 +1,72 @@
+use std::{
+    fs,
+    io::{ErrorKind, Result as ioResult},
+    time::SystemTime,
+};
+
+/// The version of a stored file, compared to find out whether another program changed it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FileStamp {
+    /// The modification time and the size of a local file.
+    Modified(SystemTime, u64),
+    /// A tag of a remote file changed by every write, e.g. the ETag of a WebDAV server.
+    Tag(String),
+}
+
+/// Reads and writes one file of the todo list, e.g. a local file or a file on a server.
+/// The [`super::FileWorker`] accesses the files only through this trait.
+pub trait Storage: Send {
+    /// Gets the path or the URL of the file shown to the user.
+    fn location(&self) -> &str;
+
+    /// Reads the content of the file, a missing file is an error of the kind `NotFound`.
+    fn read(&mut self) -> ioResult<String>;
+
+    /// Replaces the content of the file.
+    fn write(&mut self, content: &[u8]) -> ioResult<()>;
+
+    /// Gets the stamp of the file, `None` for a missing file.
+    fn stamp(&mut self) -> ioResult<Option<FileStamp>>;
+
+    /// Checks whether the file is on the local disk, so its changes can be watched.
+    fn is_local(&self) -> bool {
+        true
+    }
+}
+
+/// A file on the local disk.
+pub struct LocalFile {
+    path: String,
+}
+
+impl LocalFile {
+    /// Creates the storage of the file at the path.
+    pub fn new(path: impl Into<String>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl Storage for LocalFile {
+    fn location(&self) -> &str {
+        &self.path
+    }
+
+    fn read(&mut self) -> ioResult<String> {
+        fs::read_to_string(&self.path)
+    }
+
+    fn write(&mut self, content: &[u8]) -> ioResult<()> {
+        fs::write(&self.path, content)
+    }
+
+    fn stamp(&mut self) -> ioResult<Option<FileStamp>> {
+        match fs::metadata(&self.path) {
+            Ok(metadata) => Ok(Some(FileStamp::Modified(
+                metadata.modified()?,
+                metadata.len(),
+            ))),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::PathBuf,
+};
+
+/// Items captured in the inbox file waiting to be triaged.
+///
+/// The inbox is a plain text file with one item per line. Items can be appended
+/// to it by any tool, e.g. `echo "call mom" >> inbox.txt`. During triage the items
+/// are walked through one by one and moved to the todo list.
+pub struct Inbox {
+    path: PathBuf,
+    items: Vec<String>,
+    index: usize,
+}
+
+impl Inbox {
+    /// Loads the inbox from the file. A missing file is an empty inbox.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the inbox file.
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+        let items = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(String::from)
+            .collect();
+        Ok(Self {
+            path,
+            items,
+            index: 0,
+        })
+    }
+
+    /// Gets the item being triaged or `None` if all items were walked through.
+    pub fn current(&self) -> Option<&str> {
+        self.items.get(self.index).map(String::as_str)
+    }
+
+    /// Gets the position of the current item counted from 1 and the number of items.
+    pub fn position(&self) -> (usize, usize) {
+        (self.index + 1, self.items.len())
+    }
+
+    /// Leaves the current item in the inbox and continues with the next one.
+    pub fn skip(&mut self) {
+        self.index += 1;
+    }
+
+    /// Removes the current item from the inbox file, it was moved to the todo list.
+    /// The file is read again, so items captured during the triage are kept.
+    pub fn take(&mut self) -> io::Result<()> {
+        if self.index >= self.items.len() {
+            return Ok(());
+        }
+        let item = self.items.remove(self.index);
+        let content = fs::read_to_string(&self.path)?;
+        let mut lines: Vec<&str> = content.lines().collect();
+        if let Some(index) = lines.iter().position(|line| line.trim() == item) {
+            lines.remove(index);
+        }
+        let mut content = lines.join("\n");
+        if !content.is_empty() {
+            content.push('\n');
+        }
+        fs::write(&self.path, content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env::temp_dir, process};
+
+    #[test]
+    fn triage() -> io::Result<()> {
+        let path = temp_dir().join(format!("todotxt-tui-inbox-test-{}.txt", process::id()));
+        fs::write(&path, "call mom\n\n  buy milk \nfix the bike\n")?;
+
+        let mut inbox = Inbox::load(&path)?;
+        assert_eq!(inbox.current(), Some("call mom"));
+        assert_eq!(inbox.position(), (1, 3));
+        inbox.take()?;
+        assert_eq!(inbox.current(), Some("buy milk"));
+        assert_eq!(inbox.position(), (1, 2));
+        inbox.skip();
+        assert_eq!(inbox.current(), Some("fix the bike"));
+        fs::write(&path, fs::read_to_string(&path)? + "captured later\n")?;
+        inbox.take()?;
+        assert_eq!(inbox.current(), None);
+        assert_eq!(
+            fs::read_to_string(&path)?,
+            "\n  buy milk \ncaptured later\n"
+        );
+
+        fs::remove_file(&path)?;
+        let inbox = Inbox::load(&path)?;
+        assert_eq!(inbox.current(), None);
+        Ok(())
+    }
+}
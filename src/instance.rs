@@ -0,0 +1,253 @@
+use crate::signals::Signal;
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, ErrorKind, Write},
+    path::{Path, PathBuf},
+    process,
+};
+
+/// Gets the path of a hidden file next to the todo file, e.g. `.todo.txt.lock` next to `todo.txt`.
+fn sibling(todo_path: &str, extension: &str) -> PathBuf {
+    let path = Path::new(todo_path);
+    let name = path.file_name().map_or(String::from("todo.txt"), |name| {
+        name.to_string_lossy().to_string()
+    });
+    path.with_file_name(format!(".{name}.{extension}"))
+}
+
+/// Gets the path of the signal file the instance editing the todo file listens on
+/// when `signal_path` is not set, e.g. `.todo.txt.signals` next to `todo.txt`.
+pub fn default_signal_path(todo_path: &str) -> PathBuf {
+    sibling(todo_path, "signals")
+}
+
+/// Reads the lock file.
+///
+/// # Returns
+///
+/// `None` if there is no lock file.
+fn read_lock(path: &Path) -> io::Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Checks whether the process is still running.
+#[cfg(unix)]
+fn is_alive(pid: u32) -> bool {
+    // Signal 0 only checks the process exists, EPERM means it belongs to another user.
+    let result = unsafe { libc::kill(pid as libc::pid_t, 0) };
+    result == 0 || io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+/// Checks whether the process is still running, the lock is never considered stale.
+#[cfg(not(unix))]
+fn is_alive(_pid: u32) -> bool {
+    true
+}
+
+/// The instance of the application editing the todo file, found by its lock file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RunningInstance {
+    pub pid: u32,
+    /// The signal file the instance listens on, see [`crate::signals::Signals`].
+    pub signal_path: PathBuf,
+}
+
+impl RunningInstance {
+    /// Finds the running instance editing the todo file. A lock file left behind
+    /// by an instance that did not exit cleanly is ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `todo_path` - The path to the todo file.
+    ///
+    /// # Returns
+    ///
+    /// `None` if no other instance edits the todo file.
+    pub fn find(todo_path: &str) -> io::Result<Option<Self>> {
+        Ok(read_lock(&sibling(todo_path, "lock"))?.and_then(|content| Self::parse(&content)))
+    }
+
+    /// Parses the content of the lock file.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the lock is of this process or of a process that is not running anymore.
+    fn parse(content: &str) -> Option<Self> {
+        let mut lines = content.lines();
+        let pid = lines.next().and_then(|pid| pid.trim().parse::<u32>().ok());
+        let signal_path = lines.next().map(str::trim).filter(|path| !path.is_empty());
+        match (pid, signal_path) {
+            (Some(pid), Some(path)) if pid != process::id() && is_alive(pid) => Some(Self {
+                pid,
+                signal_path: PathBuf::from(path),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Forwards the signal to the instance, it is applied with the next check of its
+    /// signal file.
+    ///
+    /// # Arguments
+    ///
+    /// * `signal` - The forwarded signal.
+    pub fn forward(&self, signal: &Signal) -> io::Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.signal_path)?;
+        writeln!(file, "{signal}")
+    }
+}
+
+/// The lock file marking the todo file as edited by this instance, so instances
+/// launched later on the same file do not overwrite its changes. The file holds
+/// the process id and the signal file the instance listens on. It is removed
+/// when the lock is dropped.
+#[derive(Debug)]
+pub struct InstanceLock {
+    path: PathBuf,
+    signal_path: PathBuf,
+}
+
+impl InstanceLock {
+    /// Locks the todo file for this instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `todo_path` - The path to the todo file.
+    /// * `signal_path` - The signal file this instance listens on.
+    ///
+    /// # Returns
+    ///
+    /// The lock, or the running instance which has locked the file already.
+    pub fn acquire(
+        todo_path: &str,
+        signal_path: &Path,
+    ) -> io::Result<Result<Self, RunningInstance>> {
+        let path = sibling(todo_path, "lock");
+        // The lock is written to a temporary file first and linked to its place, the link
+        // fails when the lock exists as creating the file with `O_EXCL` does. So of two
+        // instances launched together only one gets the lock and the content is never partial.
+        let temporary = path.with_extension(format!("{}", process::id()));
+        fs::write(
+            &temporary,
+            format!("{}\n{}\n", process::id(), signal_path.display()),
+        )?;
+        let result = Self::link(&temporary, &path);
+        let _ = fs::remove_file(&temporary);
+        Ok(match result? {
+            None => Ok(Self {
+                path,
+                signal_path: signal_path.to_path_buf(),
+            }),
+            Some(running) => Err(running),
+        })
+    }
+
+    /// Links the written lock to its place, a stale lock is replaced.
+    ///
+    /// # Returns
+    ///
+    /// The running instance if the file is locked by another instance.
+    fn link(temporary: &Path, path: &Path) -> io::Result<Option<RunningInstance>> {
+        loop {
+            match fs::hard_link(temporary, path) {
+                Ok(()) => return Ok(None),
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {}
+                Err(e) => return Err(e),
+            }
+            let Some(stale) = read_lock(path)? else {
+                continue;
+            };
+            if let Some(running) = RunningInstance::parse(&stale) {
+                return Ok(Some(running));
+            }
+            // Another instance may have replaced the stale lock in the meantime,
+            // the lock is removed only if it is still the stale one.
+            if read_lock(path)?.as_ref() == Some(&stale) {
+                match fs::remove_file(path) {
+                    Err(e) if e.kind() != ErrorKind::NotFound => return Err(e),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// Gets the signal file this instance listens on.
+    pub fn signal_path(&self) -> &Path {
+        &self.signal_path
+    }
+
+    /// Checks whether the lock file is still the lock of this instance.
+    fn is_own(&self) -> bool {
+        fs::read_to_string(&self.path).is_ok_and(|content| {
+            content.lines().next().map(str::trim) == Some(&process::id().to_string())
+        })
+    }
+}
+
+impl Drop for InstanceLock {
+    fn drop(&mut self) {
+        if self.is_own() {
+            if let Err(e) = fs::remove_file(&self.path) {
+                log::error!("Cannot remove the lock file {}: {}", self.path.display(), e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env::temp_dir;
+
+    #[test]
+    fn paths() {
+        assert_eq!(
+            default_signal_path("/home/user/todo.txt"),
+            PathBuf::from("/home/user/.todo.txt.signals")
+        );
+        assert_eq!(sibling("work.txt", "lock"), PathBuf::from(".work.txt.lock"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn lock() -> io::Result<()> {
+        let dir = temp_dir().join(format!("todotxt-tui-instance-test-{}", process::id()));
+        fs::create_dir_all(&dir)?;
+        let todo_path = dir.join("todo.txt").to_string_lossy().to_string();
+        let lock_path = sibling(&todo_path, "lock");
+        let signal_path = default_signal_path(&todo_path);
+
+        let lock = InstanceLock::acquire(&todo_path, &signal_path)?.unwrap();
+        assert!(lock_path.exists());
+        // The lock of this process does not block it, e.g. after switching profiles.
+        assert_eq!(RunningInstance::find(&todo_path)?, None);
+        drop(lock);
+        assert!(!lock_path.exists());
+
+        // The lock of another running process is found, the parent of the tests runs.
+        let parent = unsafe { libc::getppid() } as u32;
+        fs::write(&lock_path, format!("{parent}\n{}\n", signal_path.display()))?;
+        let running = InstanceLock::acquire(&todo_path, &signal_path)?.unwrap_err();
+        assert_eq!(running.pid, parent);
+        running.forward(&Signal::Add(String::from("buy milk")))?;
+        assert_eq!(fs::read_to_string(&signal_path)?, "add buy milk\n");
+        fs::remove_file(&signal_path)?;
+
+        // A stale lock is replaced.
+        fs::write(
+            &lock_path,
+            format!("{}\n{}\n", u32::MAX / 2, signal_path.display()),
+        )?;
+        let lock = InstanceLock::acquire(&todo_path, &signal_path)?.unwrap();
+        drop(lock);
+        assert!(!lock_path.exists());
+        fs::remove_dir_all(&dir)
+    }
+}
@@ -1,13 +1,22 @@
 mod container;
 mod render_trait;
+#[cfg(test)]
+mod snapshot;
 pub mod widget;
 
 use crate::{
-    config::Config, layout::widget::State, todo::ToDo, ui::HandleEvent, ToDoError, ToDoRes,
+    clock::{ticked, until_tick},
+    config::Config,
+    layout::widget::{ListPosition, State},
+    todo::{ToDo, ToDoCategory},
+    ui::{Command, HandleEvent, UIEvent},
+    ToDoError, ToDoRes,
 };
+use chrono::NaiveDateTime;
 use container::Container;
-use crossterm::event::KeyEvent;
-use std::{fmt::Debug, sync::Arc, sync::Mutex};
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
+use std::{fmt::Debug, sync::Arc, sync::Mutex, time::Duration};
+use todo_txt::Task;
 use widget::{widget_type::WidgetType, Widget};
 
 pub use render_trait::Render;
@@ -25,6 +34,25 @@ const ARG_SEPARATOR: char = ':';
 const START_CONTAINER: char = '[';
 const END_CONTAINER: char = ']';
 
+/// The layout used when the layout is not configured or the configured one is not valid.
+pub const DEFAULT_LAYOUT: &str = "
+[
+    Direction: Horizontal,
+    Size: 50%,
+    [
+        List: 20%,
+        Preview: 80%,
+    ],
+    [ Direction: Vertical,
+      Done: 60%,
+      [ 
+        Contexts: 10%,
+        Projects: 90%,
+      ],
+    ],
+]
+";
+
 const LEFT: Site = Site {
     direction: Direction::Horizontal,
     function: Container::previous_item,
@@ -84,6 +112,7 @@ impl Holder {
 pub struct Layout {
     containers: Vec<Container>,
     act: usize,
+    chunk: Option<Rect>,
 }
 
 impl Layout {
@@ -97,20 +126,25 @@ impl Layout {
     ///
     /// Returns a `ToDoRes` containing the converted `Constraint` or an error if parsing fails.
     fn value_from_string(value: Option<&str>) -> ToDoRes<Constraint> {
-        if value.is_none() {
-            return Ok(Constraint::Percentage(50));
-        }
+        let value = match value {
+            Some(value) => value,
+            None => return Ok(Constraint::Percentage(50)),
+        };
 
-        // TODO unwrap
-        match value.unwrap().find('%') {
+        match value.find('%') {
             Some(i) => {
-                if i + 1 < value.unwrap().len() {
-                    Err(ToDoError::ParseUnknownValue(value.unwrap().to_string()))
+                if i + 1 < value.len() {
+                    Err(ToDoError::ParseUnknownValue(value.to_string()))
                 } else {
-                    Ok(Constraint::Percentage(value.unwrap()[..i].parse()?))
+                    match value[..i].parse()? {
+                        percentage if percentage > 100 => {
+                            Err(ToDoError::ParseInvalidSize(value.to_string()))
+                        }
+                        percentage => Ok(Constraint::Percentage(percentage)),
+                    }
                 }
             }
-            None => Ok(Constraint::Length(value.unwrap().parse()?)),
+            None => Ok(Constraint::Length(value.parse()?)),
         }
     }
 
@@ -137,11 +171,25 @@ impl Layout {
             }
             "size" => Ok(Some(Self::value_from_string(x.1)?)),
             _ => {
-                container.add_widget(Widget::new(
-                    WidgetType::from_str(x.0)?,
-                    data.clone(),
-                    config,
-                )?);
+                // A task list may have a named query in brackets, e.g. `List(week)`,
+                // and a tag pane the key of its tag, e.g. `Tags(due)`.
+                let widget = match x.0.strip_suffix(')').and_then(|w| w.split_once('(')) {
+                    Some((widget, _)) if WidgetType::from_str(widget)? == WidgetType::Tag => {
+                        // The key keeps its case, tags are case sensitive.
+                        let key = item
+                            .split_once('(')
+                            .and_then(|(_, key)| key.split_once(')'));
+                        Widget::with_tag(key.unwrap_or_default().0.trim(), data.clone(), config)?
+                    }
+                    Some((widget, query)) => Widget::with_query(
+                        WidgetType::from_str(widget)?,
+                        query,
+                        data.clone(),
+                        config,
+                    )?,
+                    None => Widget::new(WidgetType::from_str(x.0)?, data.clone(), config)?,
+                };
+                container.add_widget(widget);
                 Ok(Some(Self::value_from_string(x.1)?))
             }
         }
@@ -179,6 +227,7 @@ impl Layout {
         let mut layout = Layout {
             act: Container::add_container(&mut containers, Container::default()),
             containers,
+            chunk: None,
         };
 
         for ch in template.chars() {
@@ -202,28 +251,8 @@ impl Layout {
                     layout.act = Container::add_container(&mut layout.containers, cont);
                     constraints_stack.push(Vec::new());
                 }
-                END_CONTAINER => {
-                    log::trace!(
-                        "Act: {}, Constraints: {:?}",
-                        layout.act,
-                        constraints_stack.last()
-                    );
-                    layout
-                        .act_mut()
-                        .set_constraints(constraints_stack.pop().unwrap());
-                    layout.act = match layout.act().parent {
-                        Some(parent) => parent,
-                        // We are at root. Return created layout.
-                        None => {
-                            Container::actualize_layout(&mut layout);
-                            layout.act_mut().actual_mut().unwrap().focus();
-                            return Ok(layout);
-                        }
-                    };
-                    string.clear();
-                }
-                ITEM_SEPARATOR => {
-                    // Skip leading ITEM_SEPARATOR
+                ITEM_SEPARATOR | END_CONTAINER => {
+                    // Skip leading ITEM_SEPARATOR, the last item may not be followed by it
                     if !string.is_empty() {
                         if let Some(constrain) =
                             Self::process_item(&string, layout.act_mut(), data.clone(), config)?
@@ -233,6 +262,28 @@ impl Layout {
                         }
                         string.clear();
                     }
+                    if ch == END_CONTAINER {
+                        if layout.act().item_count() == 0 {
+                            return Err(ToDoError::ParseEmptyContainer);
+                        }
+                        log::trace!(
+                            "Act: {}, Constraints: {:?}",
+                            layout.act,
+                            constraints_stack.last()
+                        );
+                        layout
+                            .act_mut()
+                            .set_constraints(constraints_stack.pop().unwrap());
+                        layout.act = match layout.act().parent {
+                            Some(parent) => parent,
+                            // We are at root. Return created layout.
+                            None => {
+                                Container::actualize_layout(&mut layout);
+                                layout.act_mut().actual_mut().unwrap().focus();
+                                return Ok(layout);
+                            }
+                        };
+                    }
                 }
                 ' ' => {}
                 '\n' => {}
@@ -242,6 +293,30 @@ impl Layout {
         Err(ToDoError::ParseNotEnd)
     }
 
+    /// Creates the layout given by the `layout` setting, the default layout is used
+    /// when the setting is not valid, so a broken setting does not prevent the start.
+    ///
+    /// # Parameters
+    ///
+    /// - `data`: An `Arc<Mutex<ToDo>>` representing the shared to-do data.
+    /// - `config`: The configuration with the layout template.
+    ///
+    /// # Returns
+    ///
+    /// The layout and the error of the configured layout if the default one is used instead.
+    pub fn from_config(
+        data: Arc<Mutex<ToDo>>,
+        config: &Config,
+    ) -> ToDoRes<(Self, Option<ToDoError>)> {
+        match Self::from_str(&config.get_layout(), data.clone(), config) {
+            Ok(layout) => Ok((layout, None)),
+            Err(e) => {
+                log::error!("The layout is not valid, the default layout is used: {e}");
+                Ok((Self::from_str(DEFAULT_LAYOUT, data, config)?, Some(e)))
+            }
+        }
+    }
+
     fn act(&self) -> &Container {
         &self.containers[self.act]
     }
@@ -352,6 +427,132 @@ impl Layout {
         self.move_focus(&DOWN)
     }
 
+    /// Jumps from the focused pane to the related item in another pane.
+    ///
+    /// From a category pane the task list is focused with the first task of the
+    /// selected category selected. From a task list the projects pane is focused
+    /// with the first project of the selected task selected.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the focus has moved.
+    pub fn reveal(&mut self) -> bool {
+        let task = match self.act().actual() {
+            Some(Widget::Category(categories)) => categories.first_task(),
+            _ => None,
+        };
+        if let Some(task) = task {
+            return self.reveal_in(WidgetType::List, |widget| match widget {
+                Widget::List(list) => {
+                    list.select_task(task);
+                    true
+                }
+                _ => false,
+            });
+        }
+        let project = match self.act().actual() {
+            Some(Widget::List(list)) => list.selected_category(ToDoCategory::Projects),
+            _ => None,
+        };
+        match project {
+            Some(project) => self.reveal_in(WidgetType::Project, |widget| match widget {
+                Widget::Category(categories) => categories.select_name(&project),
+                _ => false,
+            }),
+            None => false,
+        }
+    }
+
+    /// Focuses the first widget of the type in the layout.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the layout does not have a focusable widget of the type, the focus
+    /// stays where it was.
+    pub fn focus_widget(&mut self, widget_type: WidgetType) -> bool {
+        if self.act().get_active_type() == Some(widget_type) {
+            return true;
+        }
+        self.reveal_in(widget_type, |widget| widget.get_base().focus)
+    }
+
+    /// Focuses the widget of the type and selects an item in it. The focus
+    /// stays where it was if the layout does not have the widget or the item
+    /// cannot be selected.
+    fn reveal_in(
+        &mut self,
+        widget_type: WidgetType,
+        select: impl FnOnce(&mut Widget) -> bool,
+    ) -> bool {
+        let old = Holder::new(self);
+        if Container::select_widget(self, widget_type).is_err() {
+            return false;
+        }
+        Container::actualize_layout(self);
+        let selected = match self.act_mut().actual_mut() {
+            Some(widget) => {
+                widget.focus();
+                if select(widget) {
+                    true
+                } else {
+                    widget.unfocus();
+                    false
+                }
+            }
+            None => false,
+        };
+        if selected {
+            old.unfocus(self);
+        } else {
+            old.set_old_back(self);
+        }
+        selected
+    }
+
+    /// Splits the focused task list into two views of the same tasks, like a split
+    /// window in vim. Both views scroll independently and the focus stays in the
+    /// original view.
+    ///
+    /// # Parameters
+    ///
+    /// - `direction`: `Horizontal` places the views side by side, `Vertical` one above the other.
+    /// - `config`: The configuration the new view is created with.
+    ///
+    /// # Returns
+    ///
+    /// An error if the focused widget is not a task list.
+    pub fn split(&mut self, direction: Direction, config: &Config) -> ToDoRes<()> {
+        let twin = self
+            .act()
+            .actual()
+            .ok_or(ToDoError::ActiveIsNotWidget)?
+            .twin(config)?;
+        Container::split(self, direction, twin);
+        self.refresh_chunks();
+        Ok(())
+    }
+
+    /// Closes the focused view of a split task list, the other view takes the space of both.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the view was closed, `false` if the focused widget is not a split view.
+    pub fn close_split(&mut self) -> bool {
+        if !Container::close_split(self) {
+            return false;
+        }
+        self.focus();
+        self.refresh_chunks();
+        true
+    }
+
+    /// Splits the area of the layout again after containers have changed.
+    fn refresh_chunks(&mut self) {
+        if let Some(chunk) = self.chunk {
+            self.update_chunk(chunk);
+        }
+    }
+
     /// Handle a key event.
     ///
     /// This method is used to handle key events within the layout. It passes the key event to the
@@ -367,6 +568,164 @@ impl Layout {
         }
     }
 
+    /// Passes the event to the currently focused widget.
+    ///
+    /// # Parameters
+    ///
+    /// - `event`: The UI event to be handled.
+    ///
+    /// # Returns
+    ///
+    /// A boolean indicating whether the event was handled.
+    pub fn handle_event(&mut self, event: UIEvent) -> bool {
+        match self.act_mut().actual_mut() {
+            Some(widget) => widget.handle_event(event),
+            None => false,
+        }
+    }
+
+    /// Handle a command entered in the command palette.
+    ///
+    /// The command is passed to the currently focused widget.
+    ///
+    /// # Parameters
+    ///
+    /// - `command`: A reference to the `Command` to be handled.
+    pub fn handle_command(&mut self, command: &Command) -> ToDoRes<()> {
+        match self.act_mut().actual_mut() {
+            Some(widget) => widget.handle_command(command),
+            None => Err(ToDoError::ActiveIsNotWidget),
+        }
+    }
+
+    /// Notifies widgets that the todo list has changed since they were refreshed last time.
+    pub fn data_changed(&mut self) {
+        self.containers
+            .iter_mut()
+            .flat_map(Container::widgets_mut)
+            .for_each(|widget| {
+                if widget.get_base_mut().take_changes() {
+                    widget.data_event();
+                }
+            });
+    }
+
+    /// Refreshes the widgets whose refresh interval from `widget_refresh_rates` has passed
+    /// between the two moments, e.g. to keep a template showing the time up to date.
+    ///
+    /// # Parameters
+    ///
+    /// - `from`: The moment of the last refresh.
+    /// - `to`: The current moment.
+    ///
+    /// # Returns
+    ///
+    /// `true` if a widget was refreshed and needs to be redrawn.
+    pub fn refresh(&mut self, from: NaiveDateTime, to: NaiveDateTime) -> bool {
+        let mut refreshed = false;
+        self.containers
+            .iter_mut()
+            .flat_map(Container::widgets_mut)
+            .filter(|widget| {
+                widget
+                    .get_base()
+                    .refresh_rate
+                    .is_some_and(|rate| ticked(rate, from, to))
+            })
+            .for_each(|widget| {
+                widget.data_event();
+                refreshed = true;
+            });
+        refreshed
+    }
+
+    /// Gets the time until the next widget is refreshed, see [`Layout::refresh`].
+    pub fn until_refresh(&self, now: NaiveDateTime) -> Option<Duration> {
+        self.containers
+            .iter()
+            .flat_map(Container::widgets)
+            .filter_map(|widget| widget.get_base().refresh_rate)
+            .map(|rate| until_tick(rate, now))
+            .min()
+    }
+
+    /// Handle a mouse event.
+    ///
+    /// The mouse event is passed to the widget placed under the mouse cursor.
+    ///
+    /// # Parameters
+    ///
+    /// - `event`: A reference to the `MouseEvent` to be handled.
+    pub fn handle_mouse(&mut self, event: &MouseEvent) -> bool {
+        let (column, row) = (event.column, event.row);
+        self.containers
+            .iter_mut()
+            .flat_map(Container::widgets_mut)
+            .find(|widget| {
+                let chunk = widget.get_base().chunk;
+                (chunk.x..chunk.x + chunk.width).contains(&column)
+                    && (chunk.y..chunk.y + chunk.height).contains(&row)
+            })
+            .is_some_and(|widget| widget.handle_mouse(event))
+    }
+
+    /// Gets the content of the currently focused widget as plain text, see [`State::text`].
+    pub fn active_text(&self) -> Option<String> {
+        self.act().actual().and_then(|widget| widget.text())
+    }
+
+    /// Gets the tasks shown by the currently focused widget, see [`State::shown_tasks`].
+    pub fn active_tasks(&self) -> Option<Vec<Task>> {
+        self.act().actual().and_then(|widget| widget.shown_tasks())
+    }
+
+    /// Gets the tasks the actions of the currently focused widget apply to, see [`State::target_tasks`].
+    pub fn target_tasks(&self) -> Vec<String> {
+        self.act()
+            .actual()
+            .map(|widget| widget.target_tasks())
+            .unwrap_or_default()
+    }
+
+    /// Gets the event the key triggers in the currently focused widget.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The pressed key.
+    pub fn get_event(&self, key: &KeyCode) -> UIEvent {
+        self.act()
+            .actual()
+            .map_or(UIEvent::None, |widget| widget.get_event(key))
+    }
+
+    /// Gets the scroll offsets and selections of the list widgets, see [`State::position`].
+    pub fn positions(&self) -> Vec<ListPosition> {
+        self.containers
+            .iter()
+            .flat_map(Container::widgets)
+            .filter_map(|widget| widget.position())
+            .collect()
+    }
+
+    /// Restores the scroll offsets and selections of the list widgets. Every position is
+    /// restored to the next widget of its type, so lists of the same type keep their order.
+    ///
+    /// # Parameters
+    ///
+    /// - `positions`: The positions returned by [`Layout::positions`].
+    pub fn restore_positions(&mut self, positions: &[ListPosition]) {
+        let mut positions: Vec<&ListPosition> = positions.iter().collect();
+        self.containers
+            .iter_mut()
+            .flat_map(Container::widgets_mut)
+            .for_each(|widget| {
+                let widget_type = widget.widget_type();
+                if let Some(index) = positions.iter().position(|p| p.widget == widget_type) {
+                    widget.restore_position(positions.remove(index));
+                }
+            });
+    }
+
     pub fn get_active_widget(&self) -> WidgetType {
         match self.act().get_active_type() {
             Some(widget_type) => widget_type,
@@ -395,6 +754,7 @@ impl Render for Layout {
     }
 
     fn update_chunk(&mut self, chunk: Rect) {
+        self.chunk = Some(chunk);
         Container::update_chunk(chunk, &mut self.containers, 0);
     }
 }
@@ -402,6 +762,8 @@ impl Render for Layout {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::todo::{FilterState, ToDoData};
+    use crossterm::event::KeyCode;
 
     fn mock_layout() -> Layout {
         let mock_layout = r#"
@@ -462,6 +824,180 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn focus_widget() {
+        let mut l = mock_layout();
+        assert!(l.focus_widget(WidgetType::Project));
+        assert_eq!(l.get_active_widget(), WidgetType::Project);
+        assert!(l.focus_widget(WidgetType::Project));
+        assert_eq!(l.get_active_widget(), WidgetType::Project);
+        assert!(!l.focus_widget(WidgetType::Hashtag));
+        assert!(!l.focus_widget(WidgetType::Preview));
+        assert_eq!(l.get_active_widget(), WidgetType::Project);
+        assert!(l.left());
+        assert_eq!(l.get_active_widget(), WidgetType::Context);
+    }
+
+    #[test]
+    fn split() -> ToDoRes<()> {
+        let config = Config::default();
+        let mut l = mock_layout();
+        l.split(Direction::Horizontal, &config)?;
+        assert_eq!(l.get_active_widget(), WidgetType::List);
+        assert!(l.right());
+        assert_eq!(l.get_active_widget(), WidgetType::List);
+        assert!(l.right());
+        assert_eq!(l.get_active_widget(), WidgetType::Done);
+        assert!(!l.close_split());
+        assert!(l.left());
+        assert_eq!(l.get_active_widget(), WidgetType::List);
+
+        assert!(l.close_split());
+        assert_eq!(l.get_active_widget(), WidgetType::List);
+        assert!(l.right());
+        assert_eq!(l.get_active_widget(), WidgetType::Done);
+        assert!(l.left());
+
+        l.split(Direction::Vertical, &config)?;
+        assert!(l.down());
+        assert_eq!(l.get_active_widget(), WidgetType::List);
+        assert!(l.up());
+        assert!(l.close_split());
+        assert!(!l.close_split());
+
+        assert!(l.right());
+        assert!(l.down());
+        assert_eq!(l.get_active_widget(), WidgetType::Context);
+        assert_eq!(
+            l.split(Direction::Vertical, &config).unwrap_err(),
+            ToDoError::SplitNotSupported(WidgetType::Context.to_string())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn reveal() {
+        let mut todo = ToDo::default();
+        for task in ["a +alpha", "b @home", "c +beta @home", "d +beta"] {
+            todo.add_task(todo_txt::Task::from_str(task).unwrap());
+        }
+        let mut l = Layout::from_str(
+            "[Direction: Horizontal, List: 50%, Projects: 25%, Contexts: 25%]",
+            Arc::new(Mutex::new(todo)),
+            &Config::default(),
+        )
+        .unwrap();
+        l.update_chunk(Rect::new(0, 0, 80, 20));
+        let selected_project = |l: &Layout| match l.act().actual() {
+            Some(Widget::List(list)) => list.selected_category(ToDoCategory::Projects),
+            _ => None,
+        };
+
+        assert!(l.reveal());
+        assert_eq!(l.get_active_widget(), WidgetType::Project);
+        l.handle_key(&KeyEvent::from(crossterm::event::KeyCode::Char('j')));
+        assert!(l.reveal());
+        assert_eq!(l.get_active_widget(), WidgetType::List);
+        assert_eq!(selected_project(&l), Some(String::from("beta")));
+
+        // The task of the context does not have a project to reveal.
+        assert!(l.right());
+        assert!(l.right());
+        assert_eq!(l.get_active_widget(), WidgetType::Context);
+        assert!(l.reveal());
+        assert_eq!(l.get_active_widget(), WidgetType::List);
+        assert_eq!(selected_project(&l), None);
+        assert!(!l.reveal());
+        assert_eq!(l.get_active_widget(), WidgetType::List);
+    }
+
+    #[test]
+    fn list_query() {
+        let mut todo = ToDo::default();
+        for task in ["a +work", "b +home", "c +work @phone"] {
+            todo.add_task(todo_txt::Task::from_str(task).unwrap());
+        }
+        todo.toggle_filter(ToDoCategory::Projects, "home", FilterState::Select);
+        let data = Arc::new(Mutex::new(todo));
+        let config =
+            Config::load_from_buffer("[list_queries]\nWork = \"+work -@phone\"".as_bytes());
+        let mut l = Layout::from_str(
+            "[Direction: Horizontal, List, List(work)]",
+            data.clone(),
+            &config,
+        )
+        .unwrap();
+        l.update_chunk(Rect::new(0, 0, 80, 20));
+        let selected = |l: &Layout| match l.act().actual() {
+            Some(Widget::List(list)) => {
+                (list.len(), list.selected_category(ToDoCategory::Projects))
+            }
+            _ => (0, None),
+        };
+
+        assert_eq!(selected(&l), (1, Some(String::from("home"))));
+        assert!(l.right());
+        assert_eq!(selected(&l), (1, Some(String::from("work"))));
+
+        assert_eq!(
+            Layout::from_str("[List(none)]", data.clone(), &config).unwrap_err(),
+            ToDoError::UnknownQuery(String::from("none"))
+        );
+        assert_eq!(
+            Layout::from_str("[Projects(work)]", data, &config).unwrap_err(),
+            ToDoError::QueryNotSupported(String::from("Projects"))
+        );
+    }
+
+    #[test]
+    fn tag_widget() {
+        let mut todo = ToDo::default();
+        for task in ["a Person:ann", "b Person:bob", "c"] {
+            todo.add_task(todo_txt::Task::from_str(task).unwrap());
+        }
+        let data = Arc::new(Mutex::new(todo));
+        let config = Config::default();
+        let mut l = Layout::from_str("[List, Tags(Person)]", data.clone(), &config).unwrap();
+        l.update_chunk(Rect::new(0, 0, 80, 20));
+        assert!(l.focus_widget(WidgetType::Tag));
+        l.handle_key(&KeyEvent::from(KeyCode::Char('j')));
+        l.handle_key(&KeyEvent::from(KeyCode::Enter));
+        let todo = data.lock().unwrap();
+        assert_eq!(todo.len(ToDoData::Pending), 1);
+        assert_eq!(
+            todo.get_filtered_and_sorted(ToDoData::Pending)[0].subject,
+            "b"
+        );
+        drop(todo);
+
+        assert_eq!(
+            Layout::from_str("[Tags]", data, &config).unwrap_err(),
+            ToDoError::MissingTagKey
+        );
+    }
+
+    #[test]
+    fn filter_selected() {
+        let mut todo = ToDo::default();
+        for task in ["a +alpha", "b @home", "c +beta @home", "d +beta"] {
+            todo.add_task(todo_txt::Task::from_str(task).unwrap());
+        }
+        let data = Arc::new(Mutex::new(todo));
+        let mut l = Layout::from_str("[List: 100%]", data.clone(), &Config::default()).unwrap();
+        l.update_chunk(Rect::new(0, 0, 80, 20));
+        let len = |data: &Arc<Mutex<ToDo>>| data.lock().unwrap().len(ToDoData::Pending);
+
+        l.handle_key(&KeyEvent::from(KeyCode::Char('j')));
+        l.handle_key(&KeyEvent::from(KeyCode::Char('j')));
+        l.handle_key(&KeyEvent::from(KeyCode::Char('+')));
+        assert_eq!(len(&data), 2);
+        l.handle_key(&KeyEvent::from(KeyCode::Char('@')));
+        assert_eq!(len(&data), 1);
+        l.handle_key(&KeyEvent::from(KeyCode::Char('+')));
+        l.handle_key(&KeyEvent::from(KeyCode::Char('@')));
+        assert_eq!(len(&data), 4);
+    }
+
     #[test]
     fn test_from_string() -> ToDoRes<()> {
         let str_layout = r#"
@@ -518,4 +1054,30 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn invalid_layout() {
+        let data = Arc::new(Mutex::new(ToDo::default()));
+        let config = Config::default();
+        let parse = |template: &str| Layout::from_str(template, data.clone(), &config);
+        assert_eq!(
+            parse("[List: 120%]").unwrap_err(),
+            ToDoError::ParseInvalidSize(String::from("120%"))
+        );
+        assert_eq!(
+            parse("[List, []]").unwrap_err(),
+            ToDoError::ParseEmptyContainer
+        );
+        assert_eq!(parse("[]").unwrap_err(), ToDoError::ParseEmptyContainer);
+        assert!(parse("[Direction: Horizontal, Projects: 20%, List: 80%]\n").is_ok());
+
+        let config = Config::load_from_buffer("layout = \"[List, Unknown]\"".as_bytes());
+        let (layout, error) = Layout::from_config(data.clone(), &config).unwrap();
+        assert_eq!(
+            error,
+            Some(ToDoError::ParseWidgetType(String::from("unknown")))
+        );
+        assert_eq!(layout.get_active_widget(), WidgetType::List);
+        assert_eq!(layout.containers.len(), 4);
+    }
 }
@@ -24,6 +24,7 @@ pub struct Container {
     direction: Direction,
     pub parent: Option<usize>,
     act_index: usize,
+    split: bool,
 }
 
 impl Container {
@@ -86,6 +87,22 @@ impl Container {
         }
     }
 
+    /// Returns an iterator over the widgets directly held by the container.
+    pub fn widgets(&self) -> impl Iterator<Item = &Widget> {
+        self.items.iter().filter_map(|item| match item {
+            It::Item(w) => Some(w),
+            It::Cont(_) => None,
+        })
+    }
+
+    /// Returns an iterator over mutable references to the widgets directly held by the container.
+    pub fn widgets_mut(&mut self) -> impl Iterator<Item = &mut Widget> {
+        self.items.iter_mut().filter_map(|item| match item {
+            It::Item(w) => Some(w),
+            It::Cont(_) => None,
+        })
+    }
+
     /// Returns a reference to the currently active item within the container.
     ///
     /// # Returns
@@ -179,7 +196,6 @@ impl Container {
     ///
     /// A result containing either an updated reference to the container with the selected widget
     /// type as the active item, or an error if the widget type is not found within the container.
-    #[allow(dead_code)]
     pub fn select_widget(layout: &mut Layout, widget_type: WidgetType) -> ToDoRes<()> {
         let mut index_item = 0;
         let (index_container, _) = layout
@@ -212,6 +228,72 @@ impl Container {
         Ok(())
     }
 
+    /// Splits the active widget of the layout, the widget and its twin are placed
+    /// into a new container in the direction. The widget keeps the focus.
+    ///
+    /// # Parameters
+    ///
+    /// - `layout`: The layout whose active widget is split.
+    /// - `direction`: The direction the widget and its twin are placed in.
+    /// - `twin`: Another view of the widget.
+    pub fn split(layout: &mut Layout, direction: Direction, twin: Widget) {
+        let parent = layout.act;
+        let index = layout.containers.len();
+        let position = layout.containers[parent].act_index;
+        let widget = std::mem::replace(
+            &mut layout.containers[parent].items[position],
+            It::Cont(index),
+        );
+        let mut container = Container {
+            items: vec![widget, It::Item(twin)],
+            parent: Some(parent),
+            split: true,
+            ..Container::default()
+        };
+        container.set_direction(direction);
+        container.set_constraints(vec![Constraint::Percentage(50), Constraint::Percentage(50)]);
+        layout.containers.push(container);
+        layout.act = index;
+    }
+
+    /// Closes the active widget of the layout if it is a view created by [`Container::split`],
+    /// the other view takes the place of the split container.
+    ///
+    /// The closed container stays in the list of containers with no items,
+    /// so indexes of other containers do not change.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the view was closed.
+    pub fn close_split(layout: &mut Layout) -> bool {
+        let index = layout.act;
+        let container = &mut layout.containers[index];
+        let parent = match container.parent {
+            Some(parent) if container.split => parent,
+            _ => return false,
+        };
+        container.items.remove(container.act_index);
+        let remaining = match container.items.pop() {
+            Some(item) => item,
+            None => return false,
+        };
+        container.split = false;
+        if let It::Cont(child) = remaining {
+            layout.containers[child].parent = Some(parent);
+        }
+        let parent_container = &mut layout.containers[parent];
+        let position = parent_container
+            .items
+            .iter()
+            .position(|item| matches!(item, It::Cont(cont) if *cont == index))
+            .expect("Split container is not in its parent");
+        parent_container.items[position] = remaining;
+        parent_container.act_index = position;
+        layout.act = parent;
+        Self::actualize_layout(layout);
+        true
+    }
+
     pub fn get_active_type(&self) -> Option<WidgetType> {
         Some(self.actual()?.widget_type())
     }
@@ -246,6 +328,7 @@ impl Default for Container {
             direction: Direction::Vertical,
             parent: None,
             act_index: 0,
+            split: false,
         }
     }
 }
@@ -291,6 +374,7 @@ mod tests {
         let index = Container::add_container(&mut containers, cont);
 
         Layout {
+            chunk: None,
             containers,
             act: index,
         }
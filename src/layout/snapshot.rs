@@ -0,0 +1,189 @@
+//! Snapshot tests of rendered frames.
+//!
+//! A layout is rendered to the `TestBackend` and the resulting frame, its text
+//! and styles, is compared with a snapshot stored in `test_files/snapshots`.
+//! Run the tests with the `UPDATE_SNAPSHOTS` environment variable set to write
+//! new snapshots after an intended change of rendering.
+
+use super::{Layout, Render};
+use crate::{
+    config::{Config, Styles, ToDoConfig},
+    todo::{parse_task, ToDo},
+};
+use std::{
+    env, fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+use tui::{backend::TestBackend, buffer::Buffer, layout::Rect, style::Color, Terminal};
+
+const WIDTH: u16 = 80;
+const HEIGHT: u16 = 20;
+
+const TASKS: &str = "
+(A) 2023-04-30 call the plumber +home @phone due:2023-05-02
+(C) write the report +work @office #quarterly
+buy milk @shop
+x 2023-05-01 2023-04-30 pay the rent +home
+";
+
+/// Renders the layout defined in the configuration with the test tasks.
+///
+/// # Parameters
+///
+/// - `config`: The configuration of the rendered layout, in the TOML format.
+/// - `tasks`: The todo.txt content of the rendered todo list.
+fn render(config: &str, tasks: &str) -> Buffer {
+    let config = Config::load_from_buffer(config.as_bytes());
+    let mut todo = ToDo::new(ToDoConfig::from(&config), Styles::from(&config));
+    tasks
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .for_each(|line| todo.add_task(parse_task(line).unwrap()));
+    let todo = Arc::new(Mutex::new(todo));
+    let mut layout = Layout::from_str(&config.get_layout(), todo, &config).unwrap();
+    layout.update_chunk(Rect::new(0, 0, WIDTH, HEIGHT));
+
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+    terminal.draw(|f| layout.render(f)).unwrap();
+    terminal.backend().buffer().clone()
+}
+
+/// Converts the buffer to its text followed by a list of styled cell ranges.
+fn to_snapshot(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut text = String::new();
+    let mut styles = String::new();
+    for y in area.top()..area.bottom() {
+        let mut run: Option<(u16, u16, String)> = None;
+        for x in area.left()..area.right() {
+            let cell = buffer.get(x, y);
+            text.push_str(&cell.symbol);
+            let style =
+                if cell.fg == Color::Reset && cell.bg == Color::Reset && cell.modifier.is_empty() {
+                    None
+                } else {
+                    Some(format!(
+                        "fg={:?} bg={:?} modifier={:#x}",
+                        cell.fg,
+                        cell.bg,
+                        cell.modifier.bits()
+                    ))
+                };
+            run = match (run, style) {
+                (Some((start, _, current)), Some(style)) if current == style => {
+                    Some((start, x, current))
+                }
+                (run, style) => {
+                    if let Some((start, end, style)) = run {
+                        styles.push_str(&format!("{y}:{start}-{end} {style}\n"));
+                    }
+                    style.map(|style| (x, x, style))
+                }
+            };
+        }
+        if let Some((start, end, style)) = run {
+            styles.push_str(&format!("{y}:{start}-{end} {style}\n"));
+        }
+        text.push('\n');
+    }
+    format!("{text}---\n{styles}")
+}
+
+/// Compares the rendered buffer with the stored snapshot of the given name.
+///
+/// The snapshot is written instead when the `UPDATE_SNAPSHOTS` environment variable is set,
+/// a missing snapshot fails the test otherwise.
+fn assert_snapshot(name: &str, buffer: &Buffer) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("test_files/snapshots")
+        .join(format!("{name}.txt"));
+    let actual = to_snapshot(buffer);
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&path).unwrap_or_else(|e| {
+        panic!(
+            "Snapshot {name} cannot be read: {e}\n\nACTUAL:\n{actual}\n\
+            Run tests with UPDATE_SNAPSHOTS=1 to write it."
+        )
+    });
+    assert!(
+        expected == actual,
+        "Snapshot {name} does not match.\n\nEXPECTED:\n{expected}\nACTUAL:\n{actual}\n\
+        Run tests with UPDATE_SNAPSHOTS=1 if the change is intended."
+    );
+}
+
+#[test]
+fn default_layout() {
+    assert_snapshot("default_layout", &render("", TASKS));
+}
+
+#[test]
+fn list_with_categories() {
+    let config = r#"
+        layout = "[Direction: Horizontal, List: 60%, Projects: 40%]"
+        priority_colors = { A = { fg = "Red" }, C = { fg = "Yellow" } }
+        projects_style = { fg = "Magenta" }
+    "#;
+    assert_snapshot("list_with_categories", &render(config, TASKS));
+}
+
+#[test]
+fn table_view() {
+    let config = r#"
+        layout = "[List: 100%]"
+        task_view = "Table"
+        table_columns = ["Priority", "Due", "Subject"]
+        pending_sort = "Priority"
+    "#;
+    assert_snapshot("table_view", &render(config, TASKS));
+}
+
+#[test]
+fn table_priority_labels() {
+    let config = r#"
+        layout = "[List: 100%]"
+        task_view = "Table"
+        table_columns = ["Priority", "Subject"]
+        priority_labels = { A = "Critical", C = "Normal" }
+    "#;
+    assert_snapshot("table_priority_labels", &render(config, TASKS));
+}
+
+#[test]
+fn list_template() {
+    let config = r#"
+        layout = "[List: 100%]"
+        pending_format = "[$priority](bold) [$subject](blue)[ due $due_date](red)"
+        list_zebra_color = { bg = "DarkGray" }
+    "#;
+    assert_snapshot("list_template", &render(config, TASKS));
+}
+
+#[test]
+fn theme() {
+    let config = r#"
+        layout = "[Direction: Horizontal, List: 60%, Projects: 40%]"
+        overdue_style = { modifier = "Italic" }
+
+        [widget_styles.List]
+        active_border = { fg = "Green" }
+        title = { fg = "Yellow", modifier = "Bold" }
+
+        [widget_styles.projects]
+        border = { fg = "DarkGray" }
+    "#;
+    assert_snapshot("theme", &render(config, TASKS));
+}
+
+#[test]
+fn empty_lists() {
+    let config = r#"
+        layout = "[Direction: Horizontal, List: 50%, Contexts: 50%]"
+    "#;
+    assert_snapshot("empty_lists", &render(config, ""));
+}
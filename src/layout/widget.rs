@@ -1,6 +1,7 @@
 mod state_categories;
 mod state_list;
 mod state_preview;
+pub mod task_view;
 mod widget_base;
 mod widget_list;
 pub mod widget_trait;
@@ -8,20 +9,22 @@ pub mod widget_type;
 
 use crate::{
     config::Config,
-    error::ToDoRes,
+    error::{ToDoError, ToDoRes},
     layout::widget::widget_list::WidgetList,
-    todo::{ToDo, ToDoCategory, ToDoData},
-    ui::UIEvent,
+    todo::{TagKey, ToDo, ToDoCategory, ToDoData},
+    ui::{Command, UIEvent},
 };
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, MouseEvent};
 use state_categories::StateCategories;
 use state_list::StateList;
 use state_preview::StatePreview;
 use std::fmt::Debug;
 use std::sync::{Arc, Mutex};
+use todo_txt::Task;
 use tui::widgets::Block;
 use tui::{backend::Backend, Frame};
 use widget_base::WidgetBase;
+pub use widget_list::{ListPosition, ScrollIndicator};
 pub use widget_trait::State;
 use widget_type::WidgetType;
 
@@ -29,6 +32,8 @@ use widget_type::WidgetType;
 pub type RCToDo = Arc<Mutex<ToDo>>;
 
 /// Implement the enum_dispatch macro for the State trait.
+/// Widgets are created once with the layout, so their size does not matter.
+#[allow(clippy::large_enum_variant)]
 #[enum_dispatch(State)]
 pub enum Widget {
     List(StateList),
@@ -56,24 +61,33 @@ impl Widget {
                 WidgetList::new(&widget_type, data, config),
                 ToDoData::Pending,
                 config,
-            )),
+            )?),
             Done => Self::List(StateList::new(
                 WidgetList::new(&widget_type, data, config),
                 ToDoData::Done,
                 config,
-            )),
+            )?),
             Project => Self::Category(StateCategories::new(
                 WidgetList::new(&widget_type, data, config),
                 ToDoCategory::Projects,
+                config,
             )),
             Context => Self::Category(StateCategories::new(
                 WidgetList::new(&widget_type, data, config),
                 ToDoCategory::Contexts,
+                config,
             )),
             Hashtag => Self::Category(StateCategories::new(
                 WidgetList::new(&widget_type, data, config),
                 ToDoCategory::Hashtags,
+                config,
+            )),
+            Assignee => Self::Category(StateCategories::new(
+                WidgetList::new(&widget_type, data, config),
+                ToDoCategory::Assignees,
+                config,
             )),
+            Tag => return Err(ToDoError::MissingTagKey),
             Preview => Self::Preview(StatePreview::new(
                 WidgetBase::new(&widget_type, data, config),
                 config,
@@ -81,6 +95,69 @@ impl Widget {
         })
     }
 
+    /// Create a new task list widget showing tasks selected by a named query
+    /// from `list_queries` instead of the active filters.
+    ///
+    /// # Parameters
+    ///
+    /// - `widget_type`: The type of widget to create, only task lists support queries.
+    /// - `name`: The name of the query in `list_queries`, the case is ignored.
+    /// - `data`: A shared mutable reference to the ToDo data.
+    ///
+    /// # Returns
+    ///
+    /// Returns the widget or an error if the query is not defined or cannot be parsed.
+    pub fn with_query(
+        widget_type: WidgetType,
+        name: &str,
+        data: RCToDo,
+        config: &Config,
+    ) -> ToDoRes<Self> {
+        let query = config.get_list_query(name)?;
+        match Self::new(widget_type, data, config)? {
+            Self::List(list) => Ok(Self::List(list.with_query(name, query))),
+            _ => Err(ToDoError::QueryNotSupported(widget_type.to_string())),
+        }
+    }
+
+    /// Create a new category widget listing the values of a `key:value` tag,
+    /// e.g. the due dates of `due:` or the people of `person:`.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key of the tag without the colon.
+    /// - `data`: A shared mutable reference to the ToDo data.
+    ///
+    /// # Returns
+    ///
+    /// Returns the widget or an error if the key is empty.
+    pub fn with_tag(key: &str, data: RCToDo, config: &Config) -> ToDoRes<Self> {
+        if key.is_empty() {
+            return Err(ToDoError::MissingTagKey);
+        }
+        Ok(Self::Category(StateCategories::new(
+            WidgetList::new(&WidgetType::Tag, data, config),
+            ToDoCategory::Tag(TagKey::new(key)),
+            config,
+        )))
+    }
+
+    /// Creates another view of the widget, e.g. for a split of the task list.
+    ///
+    /// # Parameters
+    ///
+    /// - `config`: The configuration the view is created with.
+    ///
+    /// # Returns
+    ///
+    /// Returns the view or an error if the widget is not a task list.
+    pub fn twin(&self, config: &Config) -> ToDoRes<Self> {
+        match self {
+            Self::List(list) => Ok(Self::List(list.twin(config)?)),
+            _ => Err(ToDoError::SplitNotSupported(self.widget_type().to_string())),
+        }
+    }
+
     /// Get the type of the widget.
     ///
     /// This function returns the type of the widget.
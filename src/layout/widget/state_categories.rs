@@ -1,19 +1,35 @@
-use super::{widget_base::WidgetBase, widget_list::WidgetList, widget_trait::State};
+use super::{
+    widget_base::WidgetBase,
+    widget_list::{ListPosition, WidgetList},
+    widget_trait::State,
+};
 use crate::{
-    todo::{FilterState, ToDoCategory},
+    config::Config,
+    todo::{Action, CategoryList, FilterState, TaskId, ToDo, ToDoCategory, ToDoData},
     ui::{HandleEvent, UIEvent},
 };
 use crossterm::event::KeyCode;
+use std::collections::BTreeSet;
 use tui::{
     backend::Backend,
-    style::{Color, Style},
-    widgets::List,
+    style::Style,
+    widgets::{List, ListItem},
     Frame,
 };
 
 /// Represents the state for a widget that displays categories.
 pub struct StateCategories {
     base: WidgetList,
+    empty_message: String,
+    bars: bool,
+    stats: bool,
+    style: Style,
+    /// Whether the categories of done tasks are listed, the global setting if not set.
+    include_done: Option<bool>,
+    /// Projects at risk of missing their deadline, refreshed when the todo list changes.
+    warnings: BTreeSet<String>,
+    /// Parent projects whose subprojects are hidden.
+    collapsed: BTreeSet<String>,
     pub category: ToDoCategory,
 }
 
@@ -24,12 +40,46 @@ impl StateCategories {
     ///
     /// - `base`: The base properties shared among different widget types.
     /// - `category`: The category of tasks to display.
+    /// - `config`: Configuration with the message shown when there are no categories,
+    ///   whether bars with the share of open tasks and stats in the title are shown
+    ///   and the style of the selected row.
     ///
     /// # Returns
     ///
     /// A new `StateCategories` instance.
-    pub fn new(base: WidgetList, category: ToDoCategory) -> Self {
-        Self { base, category }
+    pub fn new(mut base: WidgetList, category: ToDoCategory, config: &Config) -> Self {
+        let settings = config.get_widget_settings(&category.into());
+        if let (ToDoCategory::Tag(key), None) = (category, &settings.title) {
+            base.title = format!("{key}:");
+        }
+        Self {
+            base,
+            empty_message: config.fill_keys(&config.get_category_empty_message()),
+            bars: config.get_category_bars(),
+            stats: config.get_category_stats(),
+            style: config
+                .get_widget_style(&category.into())
+                .highlight
+                .unwrap_or_else(|| config.get_category_active_color())
+                .get_style(),
+            include_done: settings.include_done,
+            warnings: BTreeSet::new(),
+            collapsed: BTreeSet::new(),
+            category,
+        }
+    }
+
+    /// Gets the categories listed by the widget, projects are arranged as a tree.
+    ///
+    /// # Parameters
+    ///
+    /// - `todo`: The locked `ToDo` data.
+    fn categories<'a>(&self, todo: &'a ToDo) -> CategoryList<'a> {
+        match self.include_done {
+            Some(include_done) => todo.get_categories_with(self.category, include_done),
+            None => todo.get_categories(self.category),
+        }
+        .into_tree(self.category, &self.collapsed)
     }
 
     /// Returns the number of items in the category associated with this widget.
@@ -38,60 +88,184 @@ impl StateCategories {
     ///
     /// The number of items in the category.
     pub fn len(&self) -> usize {
-        self.base.data().get_categories(self.category).len()
+        self.categories(&self.base.data()).len()
+    }
+
+    /// Gets the first pending task of the selected category as the task list shows it.
+    pub fn first_task(&self) -> Option<TaskId> {
+        let todo = self.base.data();
+        let categories = self.categories(&todo);
+        if categories.is_empty() {
+            return None;
+        }
+        let name = categories.get_name(self.base.index());
+        let index = todo
+            .get_filtered_and_sorted(ToDoData::Pending)
+            .vec
+            .iter()
+            .position(|(_, task)| {
+                self.category
+                    .get_data(task)
+                    .iter()
+                    .any(|n| self.category.matches(n, name))
+            })?;
+        todo.task_id(ToDoData::Pending, index)
+    }
+
+    /// Selects the category of the name.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the category to select.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the category is in the list.
+    pub fn select_name(&mut self, name: &str) -> bool {
+        let index = {
+            let todo = self.base.data();
+            let categories = self.categories(&todo);
+            (0..categories.len()).find(|i| categories.get_name(*i) == name)
+        };
+        match index {
+            Some(index) => {
+                self.base.len = self.len();
+                self.base.select(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Hides or shows the subprojects of the selected project. The parent of a selected
+    /// subproject is collapsed and selected instead.
+    fn toggle_collapse(&mut self) {
+        let (name, node) = {
+            let todo = self.base.data();
+            let categories = self.categories(&todo);
+            let index = self.base.index();
+            match categories.nodes.get(index) {
+                Some(node) => (categories.get_name(index).to_string(), *node),
+                None => return,
+            }
+        };
+        if node.parent {
+            if !self.collapsed.remove(&name) {
+                self.collapsed.insert(name);
+            }
+            self.base.len = self.len();
+        } else if let Some(parent) = self.category.parents(&name).last() {
+            self.collapsed.insert(parent.to_string());
+            self.select_name(parent);
+        }
+    }
+
+    /// Toggles the filter of the selected category.
+    ///
+    /// # Parameters
+    ///
+    /// - `state`: Whether the category is selected or removed by the filter.
+    fn toggle_filter(&mut self, state: FilterState) {
+        let name = self
+            .categories(&self.base.data())
+            .get_name(self.base.index())
+            .to_string();
+        self.base.apply(Action::ToggleFilter {
+            category: self.category,
+            name,
+            state,
+        });
+        self.base.len = self.len();
+    }
+
+    /// Gets the title of the pane followed by the number of categories and how many
+    /// of them have overdue tasks if the stats are shown, e.g. `Projects (12, 3 overdue)`.
+    /// The title ends with `[any]` when the selected filters match tasks having any of them.
+    ///
+    /// # Parameters
+    ///
+    /// - `todo`: The todo list the overdue tasks are counted in.
+    /// - `count`: The number of categories in the pane.
+    fn title(&self, todo: &ToDo, count: usize) -> String {
+        let mode = match todo.get_state().is_any(self.category) {
+            true => " [any]",
+            false => "",
+        };
+        if !self.stats {
+            return format!("{}{mode}", self.base.title);
+        }
+        match todo.overdue_categories(self.category, todo.clock().today()) {
+            0 => format!("{} ({count}){mode}", self.base.title),
+            overdue => format!("{} ({count}, {overdue} overdue){mode}", self.base.title),
+        }
+    }
+
+    /// Shows the highlighted project in the preview, the preview is reset when `focus` is `false`.
+    fn select_project(&self, focus: bool) {
+        if self.category != ToDoCategory::Projects {
+            return;
+        }
+        let name = focus
+            .then(|| {
+                let todo = self.base.data();
+                let categories = self.categories(&todo);
+                (!categories.is_empty()).then(|| categories.get_name(self.base.index()).to_string())
+            })
+            .flatten();
+        self.base.apply(Action::SelectProject { name });
     }
 }
 
 impl State for StateCategories {
     fn handle_event_state(&mut self, event: UIEvent) -> bool {
         if self.base.handle_event(event) {
+            self.select_project(true);
             return true;
         }
         match event {
-            // TODO improve doubled code
-            UIEvent::Select => {
-                let name;
-                {
-                    let todo = self.base.data();
-                    name = todo
-                        .get_categories(self.category)
-                        .get_name(self.base.act())
-                        .clone();
-                }
-                self.base
-                    .data()
-                    .toggle_filter(self.category, &name, FilterState::Select);
-                self.base.len = self.len();
-            }
-            UIEvent::Remove => {
-                let name;
-                {
-                    let todo = self.base.data();
-                    name = todo
-                        .get_categories(self.category)
-                        .get_name(self.base.act())
-                        .clone();
-                }
-                self.base
-                    .data()
-                    .toggle_filter(self.category, &name, FilterState::Remove);
-                self.base.len = self.len();
-            }
+            UIEvent::Select => self.toggle_filter(FilterState::Select),
+            UIEvent::Remove => self.toggle_filter(FilterState::Remove),
+            UIEvent::ToggleCollapse => self.toggle_collapse(),
+            UIEvent::ToggleFilterMode => self.base.apply(Action::ToggleFilterMode {
+                category: self.category,
+            }),
             _ => return false,
         }
+        self.select_project(true);
         true
     }
 
     fn render<B: Backend>(&self, f: &mut Frame<B>) {
         let todo = self.base.data();
-        let data = todo.get_categories(self.category);
-        let list = List::new(data).block(self.get_block());
+        let mut categories = self.categories(&todo);
+        if categories.is_empty() {
+            return self.render_message(f, &self.empty_message);
+        }
+        categories.warnings = self.warnings.clone();
+        let count = categories.len();
+        // Only the visible categories are turned into list items.
+        let (first, last) = self.base.range();
+        let categories = categories.slice(first, last);
+        let items: Vec<ListItem> = if self.bars {
+            // Two chars are borders.
+            let width = self.base.chunk.width.saturating_sub(2) as usize;
+            categories.with_bars(&todo.category_counts(self.category), width)
+        } else {
+            categories.into()
+        };
+        let items: Vec<ListItem> = items
+            .into_iter()
+            .enumerate()
+            .map(|(i, item)| item.style(self.base.row_style(first + i)))
+            .collect();
+        let list = List::new(items).block(self.base.block(self.title(&todo, count)));
         if !self.base.focus {
             f.render_widget(list, self.base.chunk)
         } else {
-            let list = list.highlight_style(Style::default().bg(Color::LightRed)); // TODO add to config
+            let list = list.highlight_style(self.style);
             f.render_stateful_widget(list, self.base.chunk, &mut self.base.state());
         }
+        self.base.render_scroll_indicator(f);
     }
 
     fn get_base(&self) -> &WidgetBase {
@@ -104,14 +278,57 @@ impl State for StateCategories {
 
     fn focus_event(&mut self) -> bool {
         self.base.len = self.len();
+        self.select_project(true);
         true
     }
 
+    fn unfocus_event(&mut self) {
+        self.select_project(false);
+    }
+
+    fn data_event(&mut self) {
+        if self.category == ToDoCategory::Projects {
+            let data = self.base.data();
+            self.warnings = data
+                .at_risk_projects(data.clock().today())
+                .into_iter()
+                .map(|risk| risk.name)
+                .collect();
+        }
+    }
+
     fn update_chunk_event(&mut self) {
         self.base.set_size(self.base.chunk.height - 2); // Two chars are borders.
     }
 
+    fn position(&self) -> Option<ListPosition> {
+        let name = {
+            let todo = self.base.data();
+            let categories = self.categories(&todo);
+            (self.base.index() < categories.len())
+                .then(|| categories.get_name(self.base.index()).to_string())
+        };
+        Some(self.base.position(self.category.into(), name))
+    }
+
+    fn restore_position(&mut self, position: &ListPosition) {
+        self.base.len = self.len();
+        let found = position.item.as_ref().and_then(|item| {
+            let todo = self.base.data();
+            let categories = self.categories(&todo);
+            categories.vec.iter().position(|(name, _)| name == item)
+        });
+        let index = found.unwrap_or(position.index);
+        // The category moved in the list keeps its place on the screen.
+        let first = (position.first + index).saturating_sub(position.index);
+        self.base.scroll_to(first, index);
+    }
+
     fn get_internal_event(&self, key: &KeyCode) -> UIEvent {
         self.base.get_event(key)
     }
+
+    fn push_count(&mut self, key: &KeyCode, event: UIEvent) -> bool {
+        self.base.push_count(key, event)
+    }
 }
@@ -1,16 +1,98 @@
-use super::{widget_base::WidgetBase, widget_list::WidgetList, widget_trait::State};
+use super::{
+    task_view::{wrap_spans, TaskColumn, TaskDivider, TaskView},
+    widget_base::WidgetBase,
+    widget_list::{ListPosition, WidgetList},
+    widget_trait::State,
+};
 use crate::{
-    config::Config,
-    todo::{ToDo, ToDoData},
-    ui::{HandleEvent, UIEvent},
+    config::{Config, Styles, ToDoConfig},
+    error::{ToDoError, ToDoRes},
+    todo::{
+        archive::ArchiveIndex,
+        is_scratch,
+        search::{highlight, highlight_words},
+        Action, FilterState, Parser, TaskId, TaskList, TaskQuery, TaskSort, ToDo, ToDoCategory,
+        ToDoData,
+    },
+    ui::{Command, HandleEvent, MoveTarget, UIEvent},
+};
+use crossterm::event::{KeyCode, MouseButton, MouseEvent, MouseEventKind};
+use std::{
+    collections::{BTreeSet, HashSet},
+    ops::Deref,
+    sync::MutexGuard,
+};
+use todo_txt::Task;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Cell, List, ListItem, Row, Table, TableState},
+    Frame,
 };
-use crossterm::event::KeyCode;
-use tui::{backend::Backend, style::Style, widgets::List, Frame};
+
+/// Number of spaces between columns in the table view.
+const COLUMN_SPACING: u16 = 1;
+
+/// The todo list the tasks of the list are taken from.
+enum Source<'a> {
+    Shared(MutexGuard<'a, ToDo>),
+    /// The tasks archived in the done file, they are only shown.
+    Archive(&'a ToDo),
+}
+
+impl Deref for Source<'_> {
+    type Target = ToDo;
+
+    fn deref(&self) -> &ToDo {
+        match self {
+            Source::Shared(todo) => todo,
+            Source::Archive(todo) => todo,
+        }
+    }
+}
 
 /// Represents the state for a list widget that displays tasks.
 pub struct StateList {
     base: WidgetList,
     style: Style,
+    parser: Option<Parser>,
+    view: TaskView,
+    columns: Vec<TaskColumn>,
+    priority_width: u16,
+    empty_message: String,
+    filtered_message: String,
+    selected: Option<TaskId>,
+    hidden: usize,
+    filters_cleared: bool,
+    query: Option<TaskQuery>,
+    archive: ToDo,
+    archive_index: ArchiveIndex,
+    archive_month: usize,
+    show_archive: bool,
+    sort: TaskSort,
+    sort_cycle: Vec<TaskSort>,
+    search: Option<String>,
+    search_style: Style,
+    dividers: Vec<TaskDivider>,
+    divider_style: Style,
+    divider_rows: Vec<(usize, String)>,
+    scratch_style: Style,
+    /// Tasks marked for bulk actions.
+    marked: Vec<TaskId>,
+    /// The task the range of the visual mode starts at, the range ends at the selected task.
+    visual: Option<TaskId>,
+    marked_style: Style,
+    overdue_style: Style,
+    due_today_style: Style,
+    due_week_style: Style,
+    off_hours_style: Style,
+    /// Whether the tasks are numbered, the numbers can be used by `{count}G`.
+    line_numbers: bool,
+    /// Whether long tasks of the list view are wrapped across rows, see [`wrap_spans`].
+    wrap: bool,
+    wrap_indent: usize,
     pub data_type: ToDoData,
 }
 
@@ -21,23 +103,106 @@ impl StateList {
     ///
     /// - `base`: The base properties shared among different widget types.
     /// - `data_type`: The type of task data to display (e.g., Pending or Done tasks).
-    /// - `style`: The style used to render the list widget.
+    /// - `config`: Configuration with styles and task template of the list widget.
     ///
     /// # Returns
     ///
-    /// A new `StateList` instance.
-    pub fn new(base: WidgetList, data_type: ToDoData, config: &Config) -> Self {
-        Self {
+    /// A new `StateList` instance or an error if the task template cannot be parsed.
+    pub fn new(base: WidgetList, data_type: ToDoData, config: &Config) -> ToDoRes<Self> {
+        let format = match data_type {
+            ToDoData::Pending => config.get_pending_format(),
+            ToDoData::Done => config.get_done_format(),
+        };
+        Ok(Self {
             base,
             style: config
-                .get_list_active_color()
-                .combine(&match data_type {
-                    ToDoData::Done => config.get_done_active_color(),
-                    ToDoData::Pending => config.get_pending_active_color(),
+                .get_widget_style(&data_type.into())
+                .highlight
+                .unwrap_or_else(|| {
+                    config.get_list_active_color().combine(&match data_type {
+                        ToDoData::Done => config.get_done_active_color(),
+                        ToDoData::Pending => config.get_pending_active_color(),
+                    })
                 })
                 .get_style(),
+            parser: match format {
+                Some(format) => Some(Parser::new(&format, Styles::from(config))?),
+                None => None,
+            },
+            view: config.get_task_view(),
+            columns: config.get_table_columns(),
+            priority_width: config
+                .get_priority_labels()
+                .values()
+                .map(|label| label.chars().count() as u16)
+                .fold(5, u16::max),
+            empty_message: config.fill_keys(&match data_type {
+                ToDoData::Pending => config.get_pending_empty_message(),
+                ToDoData::Done => config.get_done_empty_message(),
+            }),
+            filtered_message: config.fill_keys(&config.get_filtered_empty_message()),
+            selected: None,
+            hidden: 0,
+            filters_cleared: false,
+            query: None,
+            archive: ToDo::new(ToDoConfig::from(config), Styles::from(config)),
+            archive_index: ArchiveIndex::default(),
+            archive_month: 0,
+            show_archive: false,
+            sort: TaskSort::None,
+            sort_cycle: config.get_sort_cycle(),
+            search: None,
+            search_style: config.get_search_match_style().get_style(),
+            dividers: config.get_list_dividers(),
+            divider_style: config.get_divider_style().get_style(),
+            divider_rows: Vec::new(),
+            scratch_style: config.get_scratch_style().get_style(),
+            marked: Vec::new(),
+            visual: None,
+            marked_style: config.get_marked_style().get_style(),
+            overdue_style: config.get_overdue_style().get_style(),
+            due_today_style: config.get_due_today_style().get_style(),
+            due_week_style: config.get_due_week_style().get_style(),
+            off_hours_style: config.get_off_hours_style().get_style(),
+            line_numbers: config.get_line_numbers(),
+            wrap: config.get_wrap_tasks(),
+            wrap_indent: config.get_wrap_indent(),
             data_type,
-        }
+        })
+    }
+
+    /// Shows tasks selected by the query in the list instead of tasks selected by the active filters.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the query shown in the title of the list.
+    /// - `query`: The query selecting the tasks.
+    pub fn with_query(mut self, name: &str, query: TaskQuery) -> Self {
+        self.base.title = format!("{} ({name})", self.base.title);
+        self.query = Some(query);
+        self
+    }
+
+    /// Creates another view of the same tasks with its own selection and scrolling.
+    ///
+    /// # Parameters
+    ///
+    /// - `config`: The configuration the view is created with.
+    ///
+    /// # Returns
+    ///
+    /// The new view or an error if the task template cannot be parsed.
+    pub fn twin(&self, config: &Config) -> ToDoRes<Self> {
+        let widget_type = self.data_type.into();
+        let mut twin = Self::new(
+            WidgetList::new(&widget_type, self.base.data.clone(), config),
+            self.data_type,
+            config,
+        )?;
+        twin.base.title = self.base.title.clone();
+        twin.view = self.view;
+        twin.query = self.query.clone();
+        Ok(twin)
     }
 
     /// Gets the number of tasks in the list.
@@ -46,77 +211,1070 @@ impl StateList {
     ///
     /// The number of tasks in the list.
     pub fn len(&self) -> usize {
-        self.base.data().len(self.data_type)
+        self.tasks(&self.source()).len()
+    }
+
+    /// Gets the divider rows between groups of the shown tasks, see [`TaskDivider::rows`].
+    /// Raw lines are shown without dividers.
+    fn compute_divider_rows(&self) -> Vec<(usize, String)> {
+        let data = self.source();
+        if self.dividers.is_empty() || data.raw_lines() {
+            return Vec::new();
+        }
+        let tasks = self.tasks(&data);
+        TaskDivider::rows(
+            &self.dividers,
+            tasks.vec.iter().map(|(_, task)| *task),
+            data.clock().today(),
+            tasks.styles,
+        )
+    }
+
+    /// Gets the number of terminal rows of every row of the list, empty if every row
+    /// takes one terminal row as tasks are not wrapped.
+    fn compute_heights(&self) -> Vec<usize> {
+        let data = self.source();
+        if !self.wrap || self.view(&data) != TaskView::List {
+            return Vec::new();
+        }
+        let tasks = self.tasks(&data);
+        let number_width = tasks.vec.len().to_string().len();
+        (0..self.base.len)
+            .map(|row| {
+                let Some((index, (_, task))) = self
+                    .task_at(row)
+                    .and_then(|index| Some((index, tasks.vec.get(index)?)))
+                else {
+                    return 1;
+                };
+                let spans = self.task_line(index, task, &data, tasks.styles, number_width);
+                wrap_spans(spans, self.text_width(), self.wrap_indent).len()
+            })
+            .collect()
+    }
+
+    /// Refreshes the divider rows, the number of rows of the underlying list
+    /// and the heights of the rows.
+    fn update_rows(&mut self) {
+        self.divider_rows = self.compute_divider_rows();
+        self.base
+            .set_separators(self.divider_rows.iter().map(|(row, _)| *row).collect());
+        self.base.len = self.len() + self.divider_rows.len();
+        let heights = self.compute_heights();
+        self.base.set_heights(heights);
+    }
+
+    /// Gets the number of columns tasks are rendered in, two columns are borders.
+    fn text_width(&self) -> usize {
+        self.base.chunk.width.saturating_sub(2) as usize
+    }
+
+    /// Gets the index of the task shown in the row of the list.
+    ///
+    /// # Parameters
+    ///
+    /// - `row`: The index of the row among tasks and divider rows.
+    ///
+    /// # Returns
+    ///
+    /// The index of the task in the list or `None` if the row is a divider.
+    fn task_at(&self, row: usize) -> Option<usize> {
+        let before = self
+            .divider_rows
+            .partition_point(|(divider, _)| *divider < row);
+        match self.divider_rows.get(before) {
+            Some((divider, _)) if *divider == row => None,
+            _ => Some(row - before),
+        }
+    }
+
+    /// Gets the row the task is shown in, divider rows before the task are counted.
+    ///
+    /// # Parameters
+    ///
+    /// - `index`: The index of the task in the list.
+    fn row_of(&self, index: usize) -> usize {
+        self.divider_rows
+            .iter()
+            .fold(index, |row, (divider, _)| match *divider <= row {
+                true => row + 1,
+                false => row,
+            })
+    }
+
+    /// Gets the index of the selected task in the list.
+    fn selected_index(&self) -> Option<usize> {
+        self.task_at(self.base.index())
+    }
+
+    /// Gets the todo list the tasks of the list are taken from.
+    fn source(&self) -> Source<'_> {
+        match self.show_archive {
+            true => Source::Archive(&self.archive),
+            false => Source::Shared(self.base.data()),
+        }
+    }
+
+    /// Switches the list of completed tasks between the todo list and the tasks archived
+    /// in the done file. The archive is read again every time it is shown, starting with
+    /// the tasks completed in the latest month.
+    fn toggle_archive(&mut self) {
+        self.marked.clear();
+        self.visual = None;
+        if self.show_archive {
+            self.archive.done.clear();
+            self.archive_index = ArchiveIndex::default();
+            self.show_archive = false;
+        } else {
+            let done_path = self.base.data().done_path().to_string();
+            match ArchiveIndex::read(&done_path) {
+                Ok(index) => {
+                    self.archive_index = index;
+                    self.show_archive = true;
+                    return self.show_archive_month(0);
+                }
+                Err(e) => {
+                    log::error!("Cannot read the archive {}: {}", done_path, e);
+                    return;
+                }
+            }
+        }
+        self.selected = None;
+        self.data_event();
+        self.base.first();
+        self.remember_selected();
+    }
+
+    /// Shows the archived tasks completed in the month, they are loaded from the done file
+    /// only when the month is shown.
+    ///
+    /// # Parameters
+    ///
+    /// - `month`: The index of the month, the latest month is the first one.
+    fn show_archive_month(&mut self, month: usize) {
+        match self.archive_index.load(month) {
+            Ok(tasks) => {
+                self.archive.done = tasks;
+                self.archive_month = month;
+            }
+            Err(e) => log::error!("Cannot read the archive: {}", e),
+        }
+        self.selected = None;
+        self.data_event();
+        self.base.first();
+        self.remember_selected();
+    }
+
+    /// Shows the archived tasks of the next or the previous month, older months are next.
+    fn turn_archive_page(&mut self, older: bool) {
+        let months = self.archive_index.months().len();
+        let month = match older {
+            true => self.archive_month + 1,
+            false => self.archive_month.wrapping_sub(1),
+        };
+        if month < months {
+            self.show_archive_month(month);
+        }
+    }
+
+    /// Gets the tasks shown in the list, selected by the query of the list or by the active filters.
+    ///
+    /// # Parameters
+    ///
+    /// - `todo`: The todo list with the tasks.
+    fn tasks<'a>(&self, todo: &'a ToDo) -> TaskList<'a> {
+        todo.get_queried_and_sorted(self.data_type, self.query.as_ref())
+    }
+
+    /// Gets the first category of the given type of the selected task.
+    ///
+    /// # Parameters
+    ///
+    /// - `category`: The type of category, e.g. projects.
+    pub fn selected_category(&self, category: ToDoCategory) -> Option<String> {
+        let index = self.task_id(self.selected_index()?)?.index;
+        category
+            .get_data(&self.data_type.get_data(&self.base.data())[index])
+            .first()
+            .cloned()
+    }
+
+    /// Toggles the filter of the first category of the selected task,
+    /// the list shows everything related to the task without going to the category pane.
+    ///
+    /// # Parameters
+    ///
+    /// - `category`: The type of category to filter by.
+    fn filter_selected(&mut self, category: ToDoCategory) {
+        match self.selected_category(category) {
+            Some(name) => {
+                log::info!("Toggle filter {name} of the selected task.");
+                self.base.apply(Action::ToggleFilter {
+                    category,
+                    name,
+                    state: FilterState::Select,
+                });
+                self.data_event();
+            }
+            None => log::info!("The selected task has no {:?} to filter by.", category),
+        }
+    }
+
+    /// Selects the task in the list.
+    ///
+    /// # Parameters
+    ///
+    /// - `task`: The identity of the task to select.
+    pub fn select_task(&mut self, task: TaskId) {
+        self.selected = Some(task);
+        self.data_event();
+    }
+
+    /// Stores the identity of the selected task, the selection stays on the task when the list changes.
+    fn remember_selected(&mut self) {
+        self.selected = self.selected_index().and_then(|index| self.task_id(index));
+    }
+
+    /// Gets the identity of the task shown at the index of the list.
+    ///
+    /// # Parameters
+    ///
+    /// - `index`: The index of the task in the list.
+    fn task_id(&self, index: usize) -> Option<TaskId> {
+        let data = self.source();
+        let (index, task) = *self.tasks(&data).vec.get(index)?;
+        Some(TaskId::new(index, task))
     }
 
-    /// Swaps tasks in the list at the selected and previous indices.
+    /// Gets the index of the task in the list.
     ///
     /// # Parameters
     ///
-    /// - `first`: The index of the first task to swap.
-    /// - `second`: The index of the second task to swap.
+    /// - `id`: The identity of the task.
+    ///
+    /// # Returns
+    ///
+    /// The index of the task or `None` if the task does not exist or is not in the list.
+    fn task_index(&self, id: &TaskId) -> Option<usize> {
+        let data = self.source();
+        let index = data.find_task(self.data_type, id)?;
+        self.tasks(&data)
+            .vec
+            .iter()
+            .position(|(actual, _)| *actual == index)
+    }
+
+    /// Creates an action for the tasks shown at the indices of the list
+    /// and applies it. Nothing is done if any of the tasks does not exist.
+    ///
+    /// # Parameters
+    ///
+    /// - `from`: The index of the task to move.
+    /// - `to`: The index of the task whose position is the target.
+    /// - `action`: The function creating the action (e.g., swap or reorder).
+    fn apply_pair(
+        &mut self,
+        from: usize,
+        to: usize,
+        action: fn(ToDoData, TaskId, TaskId) -> Action,
+    ) {
+        if let (Some(from), Some(to)) = (self.task_id(from), self.task_id(to)) {
+            self.base.apply(action(self.data_type, from, to));
+        }
+    }
+
+    /// Swaps tasks in the list at the selected and previous rows.
+    ///
+    /// # Parameters
+    ///
+    /// - `first`: The row of the first task to swap.
+    /// - `second`: The row of the second task to swap.
     fn swap_tasks(&mut self, first: usize, second: usize) {
-        log::trace!("Swap tasks with indexes: {}, {}", first, second);
-        self.base.data().swap_tasks(self.data_type, first, second);
+        log::trace!("Swap tasks in rows: {}, {}", first, second);
+        let (Some(first), Some(second)) = (self.task_at(first), self.task_at(second)) else {
+            return;
+        };
+        self.apply_pair(first, second, |data, from, to| Action::SwapTasks {
+            data,
+            from,
+            to,
+        });
+    }
+
+    /// Moves the task in the list to the position of another task.
+    ///
+    /// # Parameters
+    ///
+    /// - `from`: The index of the task to move.
+    /// - `to`: The index the task is moved to.
+    fn reorder(&mut self, from: usize, to: usize) {
+        log::trace!("Reorder task from index {} to {}", from, to);
+        self.apply_pair(from, to, |data, from, to| Action::ReorderTask {
+            data,
+            from,
+            to,
+        });
+    }
+
+    /// Moves the selected task to the top or the bottom of the list
+    /// and keeps it selected, scrolling the list along.
+    ///
+    /// # Parameters
+    ///
+    /// - `top`: Move the task to the top, otherwise to the bottom.
+    fn reorder_task(&mut self, top: bool) {
+        self.update_rows();
+        let len = self.len();
+        let Some(from) = self.selected_index().filter(|_| len > 0) else {
+            return;
+        };
+        let to = if top { 0 } else { len - 1 };
+        self.reorder(from, to);
+        self.update_rows();
+        if top {
+            self.base.first();
+        } else {
+            self.base.last();
+        }
     }
 
-    /// Moves the currently selected task using the specified function.
+    /// Moves the selected task to the target position and keeps it selected.
     ///
     /// # Parameters
     ///
-    /// - `move_fn`: The function to move the task (e.g., remove or move).
-    fn move_task(&mut self, r#move: fn(&mut ToDo, ToDoData, usize)) {
-        let index = self.base.index();
-        log::info!("Remove task with index {index}.");
-        r#move(&mut self.base.data(), self.data_type, index);
+    /// - `target`: The position the task is moved to.
+    fn move_task_to(&mut self, target: &MoveTarget) -> ToDoRes<()> {
+        self.update_rows();
         let len = self.len();
-        if len <= index && len > 0 {
+        let Some(from) = self.selected_index().filter(|_| len > 0) else {
+            return Ok(());
+        };
+        let to = match target {
+            MoveTarget::Index(index) => index.saturating_sub(1).min(len - 1),
+            MoveTarget::After(text) => {
+                let data = self.base.data();
+                let tasks = self.tasks(&data);
+                let needle = text.to_lowercase();
+                let after = (0..len)
+                    .find(|&i| i != from && tasks[i].subject.to_lowercase().contains(&needle))
+                    .ok_or_else(|| ToDoError::TaskNotFound(text.clone()))?;
+                if after < from {
+                    after + 1
+                } else {
+                    after
+                }
+            }
+        };
+        self.reorder(from, to);
+        self.update_rows();
+        self.base.select(self.row_of(to));
+        Ok(())
+    }
+
+    /// Moves the currently selected task, or all marked tasks, out of the list using
+    /// the action created by the function.
+    ///
+    /// # Parameters
+    ///
+    /// - `action`: The function creating the action (e.g., remove or complete).
+    fn move_task(&mut self, action: fn(ToDoData, TaskId) -> Action) {
+        if self.apply_marked(action, false) {
+            return;
+        }
+        let row = self.base.index();
+        if let Some(index) = self.task_at(row) {
+            log::info!("Move task with index {index}.");
+            if let Some(task) = self.task_id(index) {
+                self.base.apply(action(self.data_type, task));
+            }
+        }
+        self.update_rows();
+        let rows = self.base.len;
+        if rows > 0 && (rows <= row || self.base.is_separator(row)) {
             self.base.up();
         }
-        self.base.len = len;
     }
-}
 
-impl State for StateList {
-    fn handle_event_state(&mut self, event: UIEvent) -> bool {
-        if self.base.handle_event(event) {
-            return true;
+    /// Inserts a copy of the selected task after it and selects the copy,
+    /// so a variant of the task can be edited right away.
+    fn duplicate_task(&mut self) {
+        let Some(task) = self.selected_index().and_then(|index| self.task_id(index)) else {
+            return;
+        };
+        let index = task.index + 1;
+        self.base.apply(Action::DuplicateTask {
+            data: self.data_type,
+            task,
+        });
+        self.selected = self
+            .data_type
+            .get_data(&self.base.data())
+            .get(index)
+            .map(|task| TaskId::new(index, task));
+        self.data_event();
+    }
+
+    /// Gets the positions of the marked tasks in the list, the tasks in the range
+    /// of the visual mode included.
+    ///
+    /// # Parameters
+    ///
+    /// - `data`: The todo list with the tasks.
+    /// - `tasks`: The tasks of the list.
+    fn marked_positions(&self, data: &ToDo, tasks: &TaskList) -> BTreeSet<usize> {
+        if self.marked.is_empty() && self.visual.is_none() {
+            return BTreeSet::new();
+        }
+        let position = |id: &TaskId| {
+            let index = data.find_task(self.data_type, id)?;
+            tasks.vec.iter().position(|(actual, _)| *actual == index)
+        };
+        let marked: HashSet<usize> = self.marked.iter().filter_map(position).collect();
+        let mut positions: BTreeSet<usize> = marked.into_iter().collect();
+        let anchor = self.visual.as_ref().and_then(position);
+        let cursor = self.selected_index().filter(|index| *index < tasks.len());
+        if let (Some(anchor), Some(cursor)) = (anchor, cursor) {
+            positions.extend(anchor.min(cursor)..=anchor.max(cursor));
+        }
+        positions
+    }
+
+    /// Gets the identities of the marked tasks in the order of the list.
+    fn marked_tasks(&self) -> Vec<TaskId> {
+        let data = self.source();
+        let tasks = self.tasks(&data);
+        self.marked_positions(&data, &tasks)
+            .into_iter()
+            .filter_map(|position| tasks.vec.get(position))
+            .map(|(index, task)| TaskId::new(*index, task))
+            .collect()
+    }
+
+    /// Marks the selected task for bulk actions or removes its mark.
+    fn toggle_mark(&mut self) {
+        let Some(task) = self.selected_index().and_then(|index| self.task_id(index)) else {
+            return;
+        };
+        let position = {
+            let data = self.source();
+            let index = data.find_task(self.data_type, &task);
+            self.marked
+                .iter()
+                .position(|id| data.find_task(self.data_type, id) == index)
+        };
+        match position {
+            Some(position) => {
+                self.marked.remove(position);
+            }
+            None => self.marked.push(task),
         }
+    }
+
+    /// Starts marking the range of tasks from the selected task to the task the selection
+    /// is moved to, or ends it and keeps the tasks of the range marked.
+    fn toggle_visual(&mut self) {
+        if self.visual.is_some() {
+            self.marked = self.marked_tasks();
+            self.visual = None;
+        } else {
+            self.visual = self.selected_index().and_then(|index| self.task_id(index));
+        }
+    }
+
+    /// Applies the action created by the function to every marked task as one batch.
+    ///
+    /// # Parameters
+    ///
+    /// - `action`: The function creating the action for a task.
+    /// - `keep`: Keep the marks on the changed tasks, the tasks are changed in place.
+    ///
+    /// # Returns
+    ///
+    /// `false` if no task is marked.
+    fn apply_marked(&mut self, action: impl Fn(ToDoData, TaskId) -> Action, keep: bool) -> bool {
+        let tasks = self.marked_tasks();
+        if tasks.is_empty() {
+            return false;
+        }
+        log::info!("Apply the action to {} marked tasks.", tasks.len());
+        let indices: Vec<usize> = tasks.iter().map(|task| task.index).collect();
+        let actions = tasks
+            .into_iter()
+            .map(|task| action(self.data_type, task))
+            .collect();
+        self.base.apply(Action::Batch { actions });
+        self.visual = None;
+        self.marked = match keep {
+            true => {
+                let data = self.base.data();
+                let tasks = self.data_type.get_data(&data);
+                indices
+                    .into_iter()
+                    .filter_map(|index| Some(TaskId::new(index, tasks.get(index)?)))
+                    .collect()
+            }
+            false => Vec::new(),
+        };
+        self.data_event();
+        true
+    }
+
+    /// Applies the action created by the function to the marked tasks,
+    /// or to the selected task if no task is marked.
+    ///
+    /// # Parameters
+    ///
+    /// - `action`: The function creating the action (e.g., cycle priority).
+    fn apply_tasks(&mut self, action: impl Fn(ToDoData, TaskId) -> Action) {
+        if !self.apply_marked(&action, true) {
+            self.apply_selected(action);
+        }
+    }
+
+    /// Applies the action created by the function to the selected task.
+    ///
+    /// # Parameters
+    ///
+    /// - `action`: The function creating the action (e.g., cycle priority).
+    fn apply_selected(&mut self, action: impl FnOnce(ToDoData, TaskId) -> Action) {
+        if let Some(task) = self.selected_index().and_then(|index| self.task_id(index)) {
+            let index = task.index;
+            self.base.apply(action(self.data_type, task));
+            // The task is changed in place, remember its new content to keep it selected.
+            self.selected = self
+                .data_type
+                .get_data(&self.base.data())
+                .get(index)
+                .map(|task| TaskId::new(index, task));
+            self.data_event();
+        }
+    }
+
+    /// Toggles the sorting of the list by the given column.
+    ///
+    /// # Parameters
+    ///
+    /// - `column`: The column to sort the list by.
+    fn toggle_sort(&mut self, column: TaskColumn) {
+        let sort = column.toggle_sort(self.base.data().get_sort(self.data_type));
+        log::info!("Sort list by column {:?}.", column);
+        self.base.apply(Action::SetSort {
+            data: self.data_type,
+            sort,
+        });
+        self.data_event();
+    }
+
+    /// Sorts the list by the next sorting option of the configured cycle.
+    fn cycle_sort(&mut self) {
+        let sort = self
+            .base
+            .data()
+            .get_sort(self.data_type)
+            .next_in(&self.sort_cycle);
+        log::info!("Sort list by {}.", sort.name());
+        self.base.apply(Action::SetSort {
+            data: self.data_type,
+            sort,
+        });
+        self.data_event();
+    }
+
+    /// Gets the width constraints of the table columns.
+    /// Columns with a minimal width share the space left by other columns.
+    fn widths(&self) -> Vec<Constraint> {
+        let width = self.base.chunk.width.saturating_sub(2); // Two chars are borders.
+        let spacing = COLUMN_SPACING * self.columns.len().saturating_sub(1) as u16;
+        // Priority labels may be wider than the priority letters.
+        let column_width = |column: &TaskColumn| match column {
+            TaskColumn::Priority => Constraint::Length(self.priority_width),
+            column => column.width(),
+        };
+        let fixed: u16 = self
+            .columns
+            .iter()
+            .map(|column| match column_width(column) {
+                Constraint::Length(length) => length,
+                Constraint::Percentage(percentage) => width * percentage / 100,
+                _ => 0,
+            })
+            .sum();
+        self.columns
+            .iter()
+            .map(|column| match column_width(column) {
+                Constraint::Min(min) => {
+                    Constraint::Length(width.saturating_sub(fixed + spacing).max(min))
+                }
+                width => width,
+            })
+            .collect()
+    }
+
+    /// Gets the table column rendered at the given horizontal position.
+    ///
+    /// # Parameters
+    ///
+    /// - `x`: The horizontal position in the terminal.
+    ///
+    /// # Returns
+    ///
+    /// The column at the position or `None` if the position is between columns.
+    fn column_at(&self, x: u16) -> Option<TaskColumn> {
+        let mut constraints = Vec::new();
+        for width in self.widths() {
+            if !constraints.is_empty() {
+                constraints.push(Constraint::Length(COLUMN_SPACING));
+            }
+            constraints.push(width);
+        }
+        let mut area = self.base.chunk;
+        area.x += 1; // Borders
+        area.width = area.width.saturating_sub(2);
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(constraints)
+            .split(area)
+            .iter()
+            .step_by(2)
+            .position(|chunk| (chunk.x..chunk.x + chunk.width).contains(&x))
+            .map(|index| self.columns[index])
+    }
+
+    /// Selects the next or the previous task matching the search, the selection wraps around.
+    /// All shown tasks match the search, as tasks not matching it are hidden.
+    fn select_match(&mut self, forward: bool) {
+        self.update_rows();
+        let len = self.len();
+        let Some(index) = self.selected_index().filter(|_| len > 0) else {
+            return;
+        };
+        let index = match forward {
+            true => (index + 1) % len,
+            false => (index + len - 1) % len,
+        };
+        self.base.select(self.row_of(index));
+    }
+
+    /// Highlights the characters matching the search and the words of the filter bar
+    /// and of the query of the list in the rendered task.
+    fn highlight<'a>(&self, spans: Vec<Span<'a>>, data: &ToDo) -> Vec<Span<'a>> {
+        let spans = match data.search() {
+            Some(search) => highlight(spans, search, self.search_style),
+            None => spans,
+        };
+        let words: Vec<&str> = data
+            .filter_bar()
+            .into_iter()
+            .chain(self.query.as_ref())
+            .flat_map(TaskQuery::words)
+            .collect();
+        highlight_words(spans, &words, self.search_style)
+    }
+
+    /// Gets the view the tasks are rendered in, raw todo.txt lines are always shown as a list.
+    fn view(&self, data: &ToDo) -> TaskView {
+        match data.raw_lines() {
+            true => TaskView::List,
+            false => self.view,
+        }
+    }
+
+    /// Gets the name of the group following the divider row, `None` if the row is a task.
+    ///
+    /// # Parameters
+    ///
+    /// - `row`: The index of the row among tasks and divider rows.
+    fn divider_at(&self, row: usize) -> Option<&str> {
+        self.divider_rows
+            .binary_search_by_key(&row, |(divider, _)| *divider)
+            .ok()
+            .map(|index| self.divider_rows[index].1.as_str())
+    }
+
+    /// Gets the rows shown in the list, the tasks are given with their index in the list.
+    ///
+    /// # Parameters
+    ///
+    /// - `filtered`: The tasks of the list.
+    ///
+    /// # Returns
+    ///
+    /// The divider rows as `Err` with the name of the following group and the tasks as `Ok`.
+    fn shown_rows<'a>(
+        &'a self,
+        filtered: &'a TaskList<'a>,
+    ) -> impl Iterator<Item = Result<(usize, &'a Task), &'a str>> + 'a {
+        let (first, last) = self.base.range();
+        (first..last).map_while(move |row| match self.divider_at(row) {
+            Some(group) => Some(Err(group)),
+            None => {
+                let index = self.task_at(row)?;
+                filtered.vec.get(index).map(|(_, task)| Ok((index, *task)))
+            }
+        })
+    }
+
+    /// Gets the text of the divider row shown before the group.
+    fn divider_text(group: &str) -> String {
+        format!("── {group} ──")
+    }
+
+    /// Renders the task as a line of the list view, by the task template if there is one.
+    ///
+    /// # Parameters
+    ///
+    /// - `task`: The rendered task.
+    /// - `data`: The todo list with the task.
+    /// - `styles`: Styles used to highlight priority and categories without the template.
+    fn task_spans<'a>(&self, task: &'a Task, data: &ToDo, styles: &'a Styles) -> Vec<Span<'a>> {
+        match &self.parser {
+            _ if data.raw_lines() => vec![Span::raw(task.to_string())],
+            Some(parser) => parser
+                .fill_task(task, data)
+                .into_iter()
+                .flatten()
+                .map(|(text, style)| Span::styled(text, style))
+                .collect(),
+            None => TaskList::parse_task_string(task, styles),
+        }
+    }
+
+    /// Renders the task as a line of the list view with its number if the tasks are numbered,
+    /// the search and the words of the filters are highlighted.
+    ///
+    /// # Parameters
+    ///
+    /// - `index`: The index of the task in the list.
+    /// - `task`: The rendered task.
+    /// - `data`: The todo list with the task.
+    /// - `styles`: Styles used to highlight priority and categories without the template.
+    /// - `number_width`: The width of the numbers of the tasks.
+    fn task_line<'a>(
+        &self,
+        index: usize,
+        task: &'a Task,
+        data: &ToDo,
+        styles: &'a Styles,
+        number_width: usize,
+    ) -> Vec<Span<'a>> {
+        let spans = self.task_spans(task, data, styles);
+        let mut spans = self.highlight(spans, data);
+        if self.line_numbers {
+            spans.insert(
+                0,
+                Span::styled(
+                    format!("{:>number_width$} ", index + 1),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            );
+        }
+        spans
+    }
+
+    /// Gets the style of the row of the task. Pending tasks are styled by their due date
+    /// and dimmed outside the working hours of their contexts, scratch tasks are marked
+    /// by `scratch_style` and marked tasks by `marked_style`.
+    ///
+    /// # Parameters
+    ///
+    /// - `index`: The index of the row.
+    /// - `task`: The task shown in the row.
+    /// - `data`: The todo list of the task, it gives the current date and working hours.
+    /// - `marked`: The task is marked for bulk actions.
+    fn task_style(&self, index: usize, task: &Task, data: &ToDo, marked: bool) -> Style {
+        let today = data.clock().today();
+        let mut style = self.base.row_style(index);
+        if let Some(due) = task.due_date.filter(|_| !task.finished) {
+            style = style.patch(match (due - today).num_days() {
+                ..=-1 => self.overdue_style,
+                0 => self.due_today_style,
+                1..=7 => self.due_week_style,
+                _ => Style::default(),
+            });
+        }
+        style = style.patch(data.rule_style(task));
+        if !task.finished && data.is_off_hours(task) {
+            style = style.patch(self.off_hours_style);
+        }
+        if is_scratch(task) {
+            style = style.patch(self.scratch_style);
+        }
+        if marked {
+            style = style.patch(self.marked_style);
+        }
+        style
+    }
+
+    /// Renders the tasks as a list with one task per line, or several rows if long tasks
+    /// are wrapped, only the rows in the visible range of the list are styled.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `data`: The todo list the tasks are taken from.
+    /// - `filtered`: The filtered and sorted tasks of the list.
+    fn render_list<B: Backend>(&self, f: &mut Frame<B>, data: &ToDo, filtered: &TaskList) {
+        let marked = self.marked_positions(data, filtered);
+        let number_width = filtered.vec.len().to_string().len();
+        let items: Vec<ListItem> = self
+            .shown_rows(filtered)
+            .map(|row| {
+                let (index, task) = match row {
+                    Ok(task) => task,
+                    Err(group) => {
+                        return ListItem::new(Self::divider_text(group)).style(self.divider_style)
+                    }
+                };
+                let spans = self.task_line(index, task, data, filtered.styles, number_width);
+                let item = match self.wrap {
+                    true => ListItem::new(wrap_spans(spans, self.text_width(), self.wrap_indent)),
+                    false => ListItem::new(Line::from(spans)),
+                };
+                item.style(self.task_style(index, task, data, marked.contains(&index)))
+            })
+            .collect();
+        let list = List::new(items).block(self.get_block());
+        if !self.base.focus {
+            f.render_widget(list, self.base.chunk)
+        } else {
+            let list = list.highlight_style(self.style);
+            f.render_stateful_widget(list, self.base.chunk, &mut self.base.state());
+        }
+    }
+
+    /// Renders the tasks as a table with configured columns, only the rows in the visible
+    /// range of the list are styled.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `data`: The todo list the tasks are taken from.
+    /// - `filtered`: The filtered and sorted tasks of the list.
+    fn render_table<B: Backend>(&self, f: &mut Frame<B>, data: &ToDo, filtered: &TaskList) {
+        let sort = data.get_sort(self.data_type);
+        let marked = self.marked_positions(data, filtered);
+        let header = Row::new(self.columns.iter().map(|column| column.header(sort)))
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        let rows = self.shown_rows(filtered).map(|row| {
+            let (index, task) = match row {
+                Ok(task) => task,
+                // The name of the group is shown in the subject column.
+                Err(group) => {
+                    return Row::new(self.columns.iter().map(|column| match column {
+                        TaskColumn::Subject => Cell::from(Self::divider_text(group)),
+                        _ => Cell::from(""),
+                    }))
+                    .style(self.divider_style)
+                }
+            };
+            Row::new(self.columns.iter().map(|column| match column {
+                TaskColumn::Subject => Cell::from(Line::from(
+                    self.highlight(TaskList::parse_task_string(task, filtered.styles), data),
+                )),
+                _ => column.cell(task, filtered.styles),
+            }))
+            .style(self.task_style(index, task, data, marked.contains(&index)))
+        });
+        let widths = self.widths();
+        let table = Table::new(rows)
+            .header(header)
+            .block(self.get_block())
+            .widths(&widths)
+            .column_spacing(COLUMN_SPACING);
+        if !self.base.focus {
+            f.render_widget(table, self.base.chunk)
+        } else {
+            let table = table.highlight_style(self.style);
+            let mut state = TableState::default();
+            state.select(self.base.state().selected());
+            f.render_stateful_widget(table, self.base.chunk, &mut state);
+        }
+    }
+}
+
+impl StateList {
+    /// Handles an event of the task list, events of the underlying list are handled before.
+    ///
+    /// # Parameters
+    ///
+    /// - `event`: The UI event to be handled.
+    fn handle_list_event(&mut self, event: UIEvent) -> bool {
         match event {
             UIEvent::SwapUpItem => {
+                self.update_rows();
                 if let Some((first, second)) = self.base.prev() {
                     self.swap_tasks(first, second)
                 }
             }
             UIEvent::SwapDownItem => {
+                self.update_rows();
                 if let Some((first, second)) = self.base.next() {
                     self.swap_tasks(first, second)
                 }
             }
-            UIEvent::RemoveItem => self.move_task(ToDo::remove_task),
-            UIEvent::MoveItem => self.move_task(ToDo::move_task),
+            UIEvent::MoveItemTop => self.reorder_task(true),
+            UIEvent::MoveItemBottom => self.reorder_task(false),
+            UIEvent::RemoveItem => self.move_task(|data, task| Action::RemoveTask { data, task }),
+            UIEvent::DuplicateItem => self.duplicate_task(),
+            UIEvent::MoveItem => self.move_task(|data, task| Action::CompleteTask { data, task }),
+            UIEvent::CyclePriority => {
+                self.apply_tasks(|data, task| Action::CyclePriority { data, task })
+            }
+            UIEvent::PriorityUp => self.apply_tasks(|data, task| Action::ShiftPriority {
+                data,
+                task,
+                steps: 1,
+            }),
+            UIEvent::PriorityDown => self.apply_tasks(|data, task| Action::ShiftPriority {
+                data,
+                task,
+                steps: -1,
+            }),
+            UIEvent::PriorityClear => {
+                self.apply_tasks(|data, task| Action::ClearPriority { data, task })
+            }
+            UIEvent::DeferItem => self.apply_tasks(|data, task| Action::DeferTask {
+                data,
+                task,
+                days: 1,
+            }),
+            UIEvent::DeferWeek => self.apply_tasks(|data, task| Action::SetDue {
+                data,
+                task,
+                due: String::from("+1w"),
+            }),
+            UIEvent::DueNextMonday => self.apply_tasks(|data, task| Action::SetDue {
+                data,
+                task,
+                due: String::from("next monday"),
+            }),
+            UIEvent::ToggleMark => self.toggle_mark(),
+            UIEvent::VisualMode => self.toggle_visual(),
+            UIEvent::ClearMarks => {
+                self.marked.clear();
+                self.visual = None;
+            }
+            UIEvent::ToggleTimer => {
+                self.apply_selected(|data, task| Action::ToggleTimer { data, task })
+            }
+            UIEvent::SortPriority => self.toggle_sort(TaskColumn::Priority),
+            UIEvent::SortDue => self.toggle_sort(TaskColumn::Due),
+            UIEvent::SortSubject => self.toggle_sort(TaskColumn::Subject),
+            UIEvent::SortProjects => self.toggle_sort(TaskColumn::Projects),
+            UIEvent::CycleSort => self.cycle_sort(),
+            UIEvent::NextMatch => self.select_match(true),
+            UIEvent::PrevMatch => self.select_match(false),
+            UIEvent::FilterProject => self.filter_selected(ToDoCategory::Projects),
+            UIEvent::FilterContext => self.filter_selected(ToDoCategory::Contexts),
             UIEvent::Select => {
-                log::trace!("Set item on index {} active.", self.base.index());
-                self.base
-                    .data()
-                    .set_active(self.data_type, self.base.index());
+                log::trace!("Set item on row {} active.", self.base.index());
+                if let Some(task) = self.selected_index().and_then(|index| self.task_id(index)) {
+                    self.base.apply(Action::SetActive {
+                        data: self.data_type,
+                        task,
+                    });
+                }
             }
             _ => return false,
         }
         true
     }
+}
+
+impl State for StateList {
+    fn handle_event_state(&mut self, event: UIEvent) -> bool {
+        if event == UIEvent::ShowArchive && self.data_type == ToDoData::Done {
+            self.toggle_archive();
+            return true;
+        }
+        // Archived tasks are only shown, they cannot be changed.
+        if self.show_archive {
+            match event {
+                UIEvent::NextPage => self.turn_archive_page(true),
+                UIEvent::PrevPage => self.turn_archive_page(false),
+                _ => return self.base.handle_event(event),
+            }
+            return true;
+        }
+        let handled = self.base.handle_event(event) || self.handle_list_event(event);
+        self.update_rows();
+        self.remember_selected();
+        handled
+    }
+
+    fn get_block(&self) -> Block<'_> {
+        let mut notes = Vec::new();
+        if self.show_archive {
+            match self.archive_index.months().get(self.archive_month) {
+                Some(month) => notes.push(format!(
+                    "archive {}, {}/{}",
+                    month.name(),
+                    self.archive_month + 1,
+                    self.archive_index.months().len()
+                )),
+                None => notes.push(String::from("archive")),
+            }
+        } else if self.filters_cleared {
+            notes.push(String::from("filters cleared"));
+        } else if self.hidden > 0 {
+            notes.push(format!("{} hidden", self.hidden));
+        }
+        if self.sort != TaskSort::None {
+            notes.push(format!("sort: {}", self.sort.name()));
+        }
+        if let Some(search) = self.search.as_ref().filter(|_| !self.show_archive) {
+            notes.push(format!("search: {search}"));
+        }
+        if self.visual.is_some() {
+            notes.push(String::from("visual"));
+        }
+        if !self.marked.is_empty() {
+            notes.push(format!("{} marked", self.marked.len()));
+        }
+        let title = match notes.is_empty() {
+            true => self.base.title.clone(),
+            false => format!("{} ({})", self.base.title, notes.join(", ")),
+        };
+        self.base.block(title)
+    }
 
     fn render<B: Backend>(&self, f: &mut Frame<B>) {
-        let data = self.base.data();
-        let filtered = data.get_filtered_and_sorted(self.data_type);
-        let (first, last) = self.base.range();
-        let filtered = filtered.slice(first, last);
-        let list = List::new(filtered).block(self.get_block());
-        if !self.base.focus {
-            f.render_widget(list, self.base.chunk)
-        } else {
-            let list = list.highlight_style(self.style);
-            f.render_stateful_widget(list, self.base.chunk, &mut self.base.state());
+        let data = self.source();
+        // The tasks are filtered and sorted once per frame, the views style only the visible rows.
+        let filtered = self.tasks(&data);
+        if filtered.is_empty() {
+            let message = if self.show_archive {
+                "The archive is empty"
+            } else if self.data_type.get_data(&data).is_empty() {
+                &self.empty_message
+            } else {
+                &self.filtered_message
+            };
+            return self.render_message(f, message);
+        }
+        match self.view(&data) {
+            TaskView::List => self.render_list(f, &data, &filtered),
+            TaskView::Table => self.render_table(f, &data, &filtered),
+        }
+        self.base.render_scroll_indicator(f);
+    }
+
+    fn handle_command(&mut self, command: &Command) -> ToDoRes<()> {
+        match command {
+            Command::Move(target) => self.move_task_to(target),
+            Command::Duplicate => {
+                self.duplicate_task();
+                Ok(())
+            }
+            Command::Tag(categories) => {
+                self.apply_tasks(|data, task| Action::AddCategories {
+                    data,
+                    task,
+                    categories: categories.clone(),
+                });
+                Ok(())
+            }
+            _ => Err(ToDoError::CommandNotSupported),
         }
     }
 
@@ -129,19 +1287,145 @@ impl State for StateList {
     }
 
     fn focus_event(&mut self) -> bool {
-        let len = self.len();
-        self.base.len = len;
-        if self.base.act() >= len && len > 0 {
-            self.base.last();
-        }
+        self.data_event();
         true
     }
 
+    fn data_event(&mut self) {
+        self.update_rows();
+        let len = self.base.len;
+        let (sort, search) = {
+            let data = self.source();
+            (
+                data.get_sort(self.data_type),
+                data.search().map(String::from),
+            )
+        };
+        self.sort = sort;
+        self.search = search;
+        if !self.show_archive {
+            let data = self.base.data();
+            if self.query.is_none() {
+                self.hidden = data.hidden(self.data_type);
+                self.filters_cleared = data.filters_cleared();
+            }
+            // Marks of tasks removed or edited meanwhile are dropped.
+            self.marked
+                .retain(|id| data.find_task(self.data_type, id).is_some());
+        }
+        let index = self
+            .selected
+            .as_ref()
+            .and_then(|task| self.task_index(task))
+            .map(|index| self.row_of(index));
+        match index {
+            Some(index) if index != self.base.index() => self.base.select(index),
+            Some(_) => {}
+            None if len > 0 && self.base.index() >= len => self.base.last(),
+            None => {}
+        }
+        self.remember_selected();
+    }
+
     fn update_chunk_event(&mut self) {
-        self.base.set_size(self.base.chunk.height - 2); // Two chars are borders.
+        // Wrapped tasks take another number of rows in the new width.
+        self.update_rows();
+        let view = self.view(&self.base.data());
+        match view {
+            TaskView::List => self.base.set_size(self.base.chunk.height - 2), // Two chars are borders.
+            TaskView::Table => self.base.set_size(self.base.chunk.height.saturating_sub(3)), // Borders and header.
+        }
+    }
+
+    fn handle_mouse(&mut self, event: &MouseEvent) -> bool {
+        if self.show_archive
+            || self.view(&self.base.data()) != TaskView::Table
+            || event.kind != MouseEventKind::Down(MouseButton::Left)
+            || event.row != self.base.chunk.y + 1
+        {
+            return false;
+        }
+        match self.column_at(event.column) {
+            Some(column) => {
+                self.toggle_sort(column);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn text(&self) -> Option<String> {
+        let data = self.source();
+        let filtered = self.tasks(&data);
+        let mut text = String::new();
+        let mut dividers = self.divider_rows.iter().peekable();
+        let mut shown = 0;
+        for (index, (_, task)) in filtered.vec.iter().enumerate() {
+            if let Some((_, group)) = dividers.next_if(|(row, _)| *row == index + shown) {
+                text += &Self::divider_text(group);
+                text.push('\n');
+                shown += 1;
+            }
+            for span in self.task_spans(task, &data, filtered.styles) {
+                text += &span.content;
+            }
+            text.push('\n');
+        }
+        (!text.is_empty()).then_some(text)
+    }
+
+    fn target_tasks(&self) -> Vec<String> {
+        let marked = self.marked_tasks();
+        let tasks = match marked.is_empty() {
+            true => self
+                .selected_index()
+                .and_then(|index| self.task_id(index))
+                .into_iter()
+                .collect(),
+            false => marked,
+        };
+        tasks.into_iter().map(|task| task.task).collect()
+    }
+
+    fn shown_tasks(&self) -> Option<Vec<Task>> {
+        let data = self.source();
+        let tasks = self.tasks(&data);
+        Some(tasks.vec.iter().map(|(_, task)| (*task).clone()).collect())
+    }
+
+    fn position(&self) -> Option<ListPosition> {
+        // The archive is read again when it is shown, its position is not kept.
+        if self.show_archive {
+            return None;
+        }
+        let task = self
+            .selected_index()
+            .and_then(|index| self.task_id(index))
+            .map(|id| id.task);
+        Some(self.base.position(self.data_type.into(), task))
+    }
+
+    fn restore_position(&mut self, position: &ListPosition) {
+        self.update_rows();
+        let found = position.item.as_ref().and_then(|item| {
+            let data = self.source();
+            self.tasks(&data)
+                .vec
+                .iter()
+                .position(|(_, task)| task.to_string() == *item)
+        });
+        let index = found.map_or(position.index, |index| self.row_of(index));
+        // The task moved in the list keeps its place on the screen.
+        let first = (position.first + index).saturating_sub(position.index);
+        self.base.scroll_to(first, index);
+        self.remember_selected();
     }
 
     fn get_internal_event(&self, key: &KeyCode) -> UIEvent {
         self.base.get_event(key)
     }
+
+    fn push_count(&mut self, key: &KeyCode, event: UIEvent) -> bool {
+        self.base.push_count(key, event)
+    }
 }
@@ -2,21 +2,26 @@ use super::{widget_base::WidgetBase, widget_trait::State};
 use crate::{
     config::{Config, Styles},
     error::ToDoRes,
-    todo::Parser,
+    todo::{project_info::time_left, FilterState, Parser, ToDo},
     ui::UIEvent,
 };
+use todo_txt::Task;
 use tui::{
     backend::Backend,
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Paragraph, Wrap},
     Frame,
 };
 
 /// Represents the state for a preview widget that displays task details.
+/// While a project is highlighted in the projects widget, its information
+/// from the projects file is displayed instead.
 pub struct StatePreview {
     base: WidgetBase,
     parser: Parser,
     wrap_preview: bool,
+    details: bool,
 }
 
 impl StatePreview {
@@ -33,10 +38,98 @@ impl StatePreview {
     pub fn new(base: WidgetBase, config: &Config) -> ToDoRes<Self> {
         Ok(StatePreview {
             base,
-            parser: Parser::new(&config.get_preview_format(), Styles::new(config))?,
+            parser: Parser::new(&config.get_preview_format(), Styles::from(config))?,
             wrap_preview: config.get_wrap_preview(),
+            details: config.get_preview_details(),
         })
     }
+
+    /// Gets the lines describing the task in full: its raw line, priority, dates,
+    /// tags and the active filters with whether the task matches them.
+    fn detail_lines(todo: &ToDo, task: &Task) -> Vec<Line<'static>> {
+        let label = Style::default().fg(Color::DarkGray);
+        let field = |name: &str, value: String| {
+            Line::from(vec![
+                Span::styled(format!("{name}: "), label),
+                Span::raw(value),
+            ])
+        };
+        let today = todo.clock().today();
+        let mut lines = vec![
+            Line::from(Span::styled(
+                task.to_string(),
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+        ];
+        if !task.priority.is_lowest() {
+            lines.push(field("Priority", task.priority.to_string()));
+        }
+        let dates = [
+            ("Created", task.create_date),
+            ("Finished", task.finish_date),
+            ("Threshold", task.threshold_date),
+        ];
+        for (name, date) in dates {
+            lines.extend(date.map(|date| field(name, date.to_string())));
+        }
+        lines.extend(
+            task.due_date
+                .map(|due| field("Due", format!("{due} ({})", time_left(due, today)))),
+        );
+        let mut tags: Vec<_> = task.tags.iter().collect();
+        tags.sort();
+        lines.extend(
+            tags.into_iter()
+                .map(|(key, value)| field(key, value.clone())),
+        );
+
+        let filters = todo.get_state().matched_filters(task);
+        if !filters.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled("Filters:", label)));
+        }
+        lines.extend(filters.into_iter().map(|(category, name, state, matches)| {
+            let negation = match state {
+                FilterState::Select => "",
+                FilterState::Remove => "-",
+            };
+            let (result, color) = match matches {
+                true => ("matches", Color::Green),
+                false => ("does not match", Color::Red),
+            };
+            Line::from(vec![
+                Span::raw(format!("{negation}{}{name} ", category.prefix())),
+                Span::styled(result, Style::default().fg(color)),
+            ])
+        }));
+        lines
+    }
+
+    /// Gets the lines describing the project.
+    fn project_lines(todo: &ToDo, name: &str) -> Vec<Line<'static>> {
+        let mut lines = vec![Line::from(Span::styled(
+            format!("+{name}"),
+            Style::default().add_modifier(Modifier::BOLD),
+        ))];
+        let fields = todo
+            .project_info(name)
+            .map(|info| info.fields(todo.clock().today()))
+            .unwrap_or_default();
+        if fields.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "No information in the projects file",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        lines.extend(fields.into_iter().map(|(label, value)| {
+            Line::from(vec![
+                Span::styled(format!("{label}: "), Style::default().fg(Color::DarkGray)),
+                Span::raw(value),
+            ])
+        }));
+        lines
+    }
 }
 
 impl State for StatePreview {
@@ -45,20 +138,25 @@ impl State for StatePreview {
     }
 
     fn render<B: Backend>(&self, f: &mut Frame<B>) {
-        let lines = self.parser.fill(&self.base.data());
-        let mut paragraph = Paragraph::new(
-            lines
-                .iter()
+        let data = self.base.data();
+        let lines = match (data.selected_project(), data.get_active()) {
+            (Some(name), _) => Self::project_lines(&data, name),
+            (None, Some(task)) if self.details => Self::detail_lines(&data, task),
+            (None, _) => self
+                .parser
+                .fill(&data)
+                .into_iter()
                 .map(|line| Line {
                     spans: line
-                        .iter()
-                        .map(|(text, style)| Span::styled(text, *style))
+                        .into_iter()
+                        .map(|(text, style)| Span::styled(text, style))
                         .collect::<Vec<_>>(),
                     ..Default::default()
                 })
                 .collect::<Vec<_>>(),
-        )
-        .block(self.get_block());
+        };
+        drop(data);
+        let mut paragraph = Paragraph::new(lines).block(self.get_block());
         if self.wrap_preview {
             paragraph = paragraph.wrap(Wrap { trim: true })
         }
@@ -77,3 +175,61 @@ impl State for StatePreview {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::todo::{Action, ToDoCategory};
+
+    #[test]
+    fn details() {
+        let mut todo = ToDo::default();
+        todo.new_task(
+            "(B) 2023-05-01 call the plumber +home @phone due:2023-05-02 t:2023-05-01 ref:42",
+        )
+        .unwrap();
+        for (category, name, state) in [
+            (ToDoCategory::Projects, "home", FilterState::Select),
+            (ToDoCategory::Contexts, "phone", FilterState::Remove),
+            (ToDoCategory::Contexts, "office", FilterState::Remove),
+        ] {
+            todo.apply(Action::ToggleFilter {
+                category,
+                name: name.to_string(),
+                state,
+            })
+            .unwrap();
+        }
+
+        // Lists with a query show tasks regardless of the filters.
+        let task = &todo.pending[0];
+        let lines: Vec<String> = StatePreview::detail_lines(&todo, task)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(lines[0], task.to_string());
+        assert_eq!(
+            lines[2..],
+            [
+                "Priority: B",
+                "Created: 2023-05-01",
+                "Threshold: 2023-05-01",
+                &format!(
+                    "Due: 2023-05-02 ({})",
+                    time_left(task.due_date.unwrap(), todo.clock().today())
+                ),
+                "ref: 42",
+                "",
+                "Filters:",
+                "+home matches",
+                "-@office matches",
+                "-@phone does not match",
+            ]
+        );
+    }
+}
@@ -0,0 +1,407 @@
+use crate::{
+    config::Styles,
+    todo::{AgendaGroup, TaskList, TaskSort},
+};
+use chrono::NaiveDate;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use todo_txt::Task;
+use tui::{
+    layout::Constraint,
+    style::Style,
+    text::{Line, Span},
+    widgets::Cell,
+};
+
+/// Represents the way tasks are displayed in the task list widgets.
+#[derive(Clone, Copy, Serialize, Deserialize, Default, ValueEnum, PartialEq, Eq, Debug)]
+pub enum TaskView {
+    /// Every task is rendered as a single line, or as several rows if `wrap_tasks` is set.
+    #[default]
+    List,
+    /// Tasks are rendered as rows of a table with aligned columns.
+    Table,
+}
+
+/// Represents a column of the table view.
+#[derive(Clone, Copy, Serialize, Deserialize, ValueEnum, PartialEq, Eq, Debug)]
+pub enum TaskColumn {
+    Priority,
+    Due,
+    Subject,
+    Projects,
+}
+
+impl TaskColumn {
+    /// Gets the columns displayed when no columns are configured.
+    pub fn default_columns() -> Vec<Self> {
+        use TaskColumn::*;
+        vec![Priority, Due, Subject, Projects]
+    }
+
+    /// Gets the title displayed in the table header.
+    pub fn title(&self) -> &'static str {
+        use TaskColumn::*;
+        match self {
+            Priority => "Pri",
+            Due => "Due",
+            Subject => "Subject",
+            Projects => "Projects",
+        }
+    }
+
+    /// Gets the width constraint of the column.
+    pub fn width(&self) -> Constraint {
+        use TaskColumn::*;
+        match self {
+            Priority => Constraint::Length(5),
+            Due => Constraint::Length(12),
+            Subject => Constraint::Min(10),
+            Projects => Constraint::Percentage(20),
+        }
+    }
+
+    /// Gets the ascending and descending sorting of the column.
+    fn sorts(&self) -> (TaskSort, TaskSort) {
+        use TaskColumn::*;
+        match self {
+            Priority => (TaskSort::Priority, TaskSort::PriorityReverse),
+            Due => (TaskSort::Due, TaskSort::DueReverse),
+            Subject => (TaskSort::Alphanumeric, TaskSort::AlphanumericReverse),
+            Projects => (TaskSort::Project, TaskSort::ProjectReverse),
+        }
+    }
+
+    /// Gets the sorting following the actual one when the sort of the column is toggled.
+    /// Sorting cycles through ascending, descending and no sorting.
+    ///
+    /// # Parameters
+    ///
+    /// - `sort`: The sorting currently applied to the list.
+    ///
+    /// # Returns
+    ///
+    /// The new sorting of the list.
+    pub fn toggle_sort(&self, sort: TaskSort) -> TaskSort {
+        let (ascending, descending) = self.sorts();
+        if sort == ascending {
+            descending
+        } else if sort == descending {
+            TaskSort::None
+        } else {
+            ascending
+        }
+    }
+
+    /// Gets the header title with a mark if the list is sorted by the column.
+    pub fn header(&self, sort: TaskSort) -> String {
+        let (ascending, descending) = self.sorts();
+        if sort == ascending {
+            format!("{} ▲", self.title())
+        } else if sort == descending {
+            format!("{} ▼", self.title())
+        } else {
+            self.title().to_string()
+        }
+    }
+
+    /// Creates the cell of the column for the given task.
+    ///
+    /// # Parameters
+    ///
+    /// - `task`: The task displayed in the row.
+    /// - `styles`: Styles used to highlight priority and categories.
+    ///
+    /// # Returns
+    ///
+    /// The cell with the content of the column.
+    pub fn cell<'a>(&self, task: &'a Task, styles: &'a Styles) -> Cell<'a> {
+        use TaskColumn::*;
+        match self {
+            Priority if task.priority.is_lowest() => Cell::from(""),
+            Priority => {
+                let text = match styles.priority_labels.get(&task.priority.to_string()) {
+                    Some(label) => label.clone(),
+                    None => format!("({})", task.priority),
+                };
+                Cell::from(Span::styled(
+                    text,
+                    styles
+                        .priority_style
+                        .get_style(u8::from(task.priority.clone())),
+                ))
+            }
+            Due => Cell::from(
+                task.due_date
+                    .map(|date| date.to_string())
+                    .unwrap_or_default(),
+            ),
+            Subject => Cell::from(Line::from(TaskList::parse_task_string(task, styles))),
+            Projects => {
+                let mut spans = Vec::new();
+                for project in task.projects() {
+                    if !spans.is_empty() {
+                        spans.push(Span::raw(" "));
+                    }
+                    let project = format!("+{project}");
+                    let style = styles.get_category_style(&project).get_style();
+                    spans.push(Span::styled(project, style));
+                }
+                Cell::from(Line::from(spans))
+            }
+        }
+    }
+}
+
+/// Represents a grouping of tasks, a divider row is rendered between neighbouring tasks
+/// of different groups, e.g. between overdue and upcoming tasks.
+#[derive(Clone, Copy, Serialize, Deserialize, ValueEnum, PartialEq, Eq, Debug)]
+pub enum TaskDivider {
+    /// Overdue tasks, tasks due today, upcoming tasks and tasks without the due date.
+    Due,
+    /// Sections of a daily agenda: overdue tasks, tasks due today, tomorrow, later this
+    /// week, after this week and tasks without the due date.
+    Agenda,
+    /// Tasks of the same priority.
+    Priority,
+}
+
+impl TaskDivider {
+    /// Gets the name of the group of the task shown in the divider row.
+    ///
+    /// # Parameters
+    ///
+    /// - `task`: The task to get the group of.
+    /// - `today`: The date the due date is compared with.
+    /// - `styles`: Styles with the labels of priorities.
+    pub fn group(&self, task: &Task, today: NaiveDate, styles: &Styles) -> String {
+        match self {
+            TaskDivider::Due => match task.due_date {
+                Some(due) if due < today => "overdue",
+                Some(due) if due == today => "today",
+                Some(_) => "upcoming",
+                None => "no due date",
+            }
+            .to_string(),
+            TaskDivider::Agenda => AgendaGroup::of(task, today).to_string(),
+            TaskDivider::Priority if task.priority.is_lowest() => String::from("no priority"),
+            TaskDivider::Priority => match styles.priority_labels.get(&task.priority.to_string()) {
+                Some(label) => label.clone(),
+                None => format!("({})", task.priority),
+            },
+        }
+    }
+
+    /// Gets the divider rows of the tasks shown in the given order.
+    ///
+    /// # Parameters
+    ///
+    /// - `dividers`: The groupings dividing the tasks, the task is in a new group
+    ///   if any of its groups differs from the groups of the previous task.
+    /// - `tasks`: The tasks in the order they are shown.
+    /// - `today`: The date due dates are compared with.
+    /// - `styles`: Styles with the labels of priorities.
+    ///
+    /// # Returns
+    ///
+    /// The indices of divider rows among the rows of tasks and dividers, with the names
+    /// of the groups following the dividers.
+    pub fn rows<'a>(
+        dividers: &[Self],
+        tasks: impl IntoIterator<Item = &'a Task>,
+        today: NaiveDate,
+        styles: &Styles,
+    ) -> Vec<(usize, String)> {
+        let mut rows = Vec::new();
+        let mut previous: Option<Vec<String>> = None;
+        for (index, task) in tasks.into_iter().enumerate() {
+            let groups: Vec<String> = dividers
+                .iter()
+                .map(|divider| divider.group(task, today, styles))
+                .collect();
+            if previous
+                .as_ref()
+                .is_some_and(|previous| *previous != groups)
+            {
+                rows.push((index + rows.len(), groups.join(", ")));
+            }
+            previous = Some(groups);
+        }
+        rows
+    }
+}
+
+/// Wraps the rendered task to rows of the width at whitespace, words longer than a row
+/// are split. The whitespace a row is wrapped at is left out.
+///
+/// # Parameters
+///
+/// - `spans`: The rendered task.
+/// - `width`: The number of columns of a row, the task is not wrapped if it is zero.
+/// - `indent`: The number of spaces the rows after the first one start with,
+///   at most half of the width.
+///
+/// # Returns
+///
+/// The rows of the task, at least one.
+pub fn wrap_spans(spans: Vec<Span<'_>>, width: usize, indent: usize) -> Vec<Line<'static>> {
+    // Runs of whitespace or other characters of the same style.
+    let mut pieces: Vec<(bool, String, Style)> = Vec::new();
+    for span in &spans {
+        for c in span.content.chars() {
+            let space = c.is_whitespace();
+            match pieces.last_mut() {
+                Some((s, text, style)) if *s == space && *style == span.style => text.push(c),
+                _ => pieces.push((space, c.to_string(), span.style)),
+            }
+        }
+    }
+    if width == 0 {
+        let spans = pieces
+            .into_iter()
+            .map(|(_, text, style)| Span::styled(text, style));
+        return vec![Line::from(spans.collect::<Vec<_>>())];
+    }
+    let indent = indent.min(width / 2);
+    let mut rows: Vec<Vec<Span<'static>>> = vec![Vec::new()];
+    let mut used = 0;
+    let new_row = |rows: &mut Vec<Vec<Span<'static>>>, used: &mut usize| {
+        rows.push(Vec::new());
+        if indent > 0 {
+            rows.last_mut().unwrap().push(Span::raw(" ".repeat(indent)));
+        }
+        *used = indent;
+    };
+    let mut rest = pieces.as_slice();
+    while let Some((space, text, style)) = rest.first() {
+        if *space {
+            let len = text.chars().count();
+            if used + len > width {
+                new_row(&mut rows, &mut used);
+            } else {
+                rows.last_mut()
+                    .unwrap()
+                    .push(Span::styled(text.clone(), *style));
+                used += len;
+            }
+            rest = &rest[1..];
+            continue;
+        }
+        let word = rest.iter().take_while(|(space, _, _)| !space).count();
+        let len: usize = rest[..word]
+            .iter()
+            .map(|(_, text, _)| text.chars().count())
+            .sum();
+        let row_start = if rows.len() == 1 { 0 } else { indent };
+        if used + len > width && used > row_start {
+            new_row(&mut rows, &mut used);
+        }
+        for (_, text, style) in &rest[..word] {
+            let mut part = String::new();
+            for c in text.chars() {
+                if used == width {
+                    rows.last_mut()
+                        .unwrap()
+                        .push(Span::styled(std::mem::take(&mut part), *style));
+                    new_row(&mut rows, &mut used);
+                }
+                part.push(c);
+                used += 1;
+            }
+            rows.last_mut().unwrap().push(Span::styled(part, *style));
+        }
+        rest = &rest[word..];
+    }
+    rows.into_iter().map(Line::from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use std::str::FromStr;
+
+    #[test]
+    fn toggle_sort() {
+        let column = TaskColumn::Due;
+        let sort = column.toggle_sort(TaskSort::Priority);
+        assert_eq!(sort, TaskSort::Due);
+        assert_eq!(column.header(sort), "Due ▲");
+        let sort = column.toggle_sort(sort);
+        assert_eq!(sort, TaskSort::DueReverse);
+        assert_eq!(column.header(sort), "Due ▼");
+        let sort = column.toggle_sort(sort);
+        assert_eq!(sort, TaskSort::None);
+        assert_eq!(column.header(sort), "Due");
+    }
+
+    #[test]
+    fn divider_rows() {
+        let tasks: Vec<Task> = [
+            "(A) pay rent due:2023-05-01",
+            "(A) call mom due:2023-05-01",
+            "(B) water plants due:2023-05-03",
+            "write the report due:2023-05-10",
+            "clean the desk",
+        ]
+        .into_iter()
+        .map(|task| Task::from_str(task).unwrap())
+        .collect();
+        let today = NaiveDate::from_ymd_opt(2023, 5, 3).unwrap();
+        let styles = Styles::from(&Config::default());
+        assert_eq!(
+            TaskDivider::rows(&[TaskDivider::Due], &tasks, today, &styles),
+            vec![
+                (2, String::from("today")),
+                (4, String::from("upcoming")),
+                (6, String::from("no due date")),
+            ]
+        );
+        assert_eq!(
+            TaskDivider::rows(&[TaskDivider::Priority], &tasks, today, &styles),
+            vec![(2, String::from("(B)")), (4, String::from("no priority"))]
+        );
+        assert_eq!(
+            TaskDivider::rows(&[TaskDivider::Agenda], &tasks, today, &styles),
+            vec![
+                (2, String::from("today")),
+                (4, String::from("later")),
+                (6, String::from("no due date")),
+            ]
+        );
+        assert!(TaskDivider::rows(&[], &tasks, today, &styles).is_empty());
+    }
+
+    #[test]
+    fn wrap() {
+        let rows = |spans: Vec<Span<'static>>, width, indent| -> Vec<String> {
+            wrap_spans(spans, width, indent)
+                .iter()
+                .map(|line| {
+                    line.spans
+                        .iter()
+                        .map(|span| span.content.as_ref())
+                        .collect()
+                })
+                .collect()
+        };
+        let task = || vec![Span::raw("(A) call mom about the "), Span::raw("+birthday")];
+        assert_eq!(rows(task(), 40, 2), ["(A) call mom about the +birthday"]);
+        assert_eq!(
+            rows(task(), 14, 2),
+            ["(A) call mom ", "  about the ", "  +birthday"]
+        );
+        assert_eq!(rows(task(), 0, 2), ["(A) call mom about the +birthday"]);
+        // Long words are split.
+        assert_eq!(
+            rows(vec![Span::raw("abcdefghij xy")], 4, 1),
+            ["abcd", " efg", " hij", " xy"]
+        );
+
+        // Styles are kept.
+        let bold = Style::default().add_modifier(tui::style::Modifier::BOLD);
+        let lines = wrap_spans(vec![Span::raw("buy "), Span::styled("milk", bold)], 5, 0);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1].spans, [Span::styled("milk", bold)]);
+    }
+}
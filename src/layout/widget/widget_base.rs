@@ -1,16 +1,34 @@
 use super::{widget_type::WidgetType, RCToDo};
-use crate::{config::Config, todo::ToDo, ui::EventHandlerUI};
-use std::sync::MutexGuard;
-use tui::{prelude::Rect, style::Color};
+use crate::{
+    config::Config,
+    status,
+    todo::{Action, Change, ToDo},
+    ui::EventHandlerUI,
+};
+use std::{
+    sync::{mpsc::Receiver, MutexGuard},
+    time::Duration,
+};
+use tui::{
+    prelude::Rect,
+    style::Style,
+    text::Span,
+    widgets::{Block, BorderType, Borders},
+};
 
 /// Represents the base properties shared among different widget types.
 pub struct WidgetBase {
     pub title: String,
-    pub active_color: Color,
+    pub title_style: Style,
+    pub border_style: Style,
+    pub active_border_style: Style,
     pub focus: bool,
     pub chunk: Rect,
     pub data: RCToDo,
     pub event_handler: EventHandlerUI,
+    pub refresh_rate: Option<Duration>,
+    changes: Receiver<Change>,
+    refreshed: bool,
 }
 
 impl WidgetBase {
@@ -25,24 +43,68 @@ impl WidgetBase {
     ///
     /// A new `WidgetBase` instance.
     pub fn new(widget_type: &WidgetType, data: RCToDo, config: &Config) -> Self {
+        let settings = config.get_widget_settings(widget_type);
         let event_handler = match widget_type {
             WidgetType::List => config.get_tasks_keybind(),
             WidgetType::Done => config.get_tasks_keybind(),
             WidgetType::Project => config.get_category_keybind(),
             WidgetType::Context => config.get_category_keybind(),
             WidgetType::Hashtag => config.get_category_keybind(),
+            WidgetType::Assignee => config.get_category_keybind(),
+            WidgetType::Tag => config.get_category_keybind(),
             WidgetType::Preview => EventHandlerUI::default(),
         };
+        let event_handler = match &settings.keybind {
+            Some(keybind) => event_handler.merge(keybind),
+            None => event_handler,
+        };
+        let changes = data.lock().unwrap().subscribe();
+        let style = config.get_widget_style(widget_type);
         Self {
-            title: widget_type.to_string(),
-            active_color: config.get_active_color(),
+            title: settings.title.unwrap_or_else(|| widget_type.to_string()),
+            title_style: style.title.unwrap_or_default().get_style(),
+            border_style: style.border.unwrap_or_default().get_style(),
+            active_border_style: match style.active_border {
+                Some(active_border) => active_border.get_style(),
+                None => Style::default().fg(config.get_active_color()),
+            },
             focus: false,
             chunk: Rect::default(),
             data,
             event_handler,
+            refresh_rate: config.get_widget_refresh_rate(widget_type),
+            changes,
+            refreshed: false,
         }
     }
 
+    /// Takes the changes of the todo list received since the last call.
+    /// A new widget is out of date until it is refreshed for the first time.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the widget needs to be refreshed.
+    pub fn take_changes(&mut self) -> bool {
+        let changed = self.changes.try_iter().count() > 0;
+        !std::mem::replace(&mut self.refreshed, true) || changed
+    }
+
+    /// Creates the block with the border and the title of the widget in its styles.
+    ///
+    /// # Parameters
+    ///
+    /// - `title`: The title of the block, the title of the widget with notes.
+    pub fn block(&self, title: String) -> Block<'static> {
+        Block::default()
+            .borders(Borders::ALL)
+            .title(Span::styled(title, self.title_style))
+            .border_type(BorderType::Rounded)
+            .border_style(match self.focus {
+                true => self.active_border_style,
+                false => self.border_style,
+            })
+    }
+
     /// Gets a mutable reference to the `ToDo` data stored in the widget.
     ///
     /// # Returns
@@ -51,4 +113,15 @@ impl WidgetBase {
     pub fn data(&self) -> MutexGuard<'_, ToDo> {
         self.data.lock().unwrap()
     }
+
+    /// Applies the action emitted by the widget to the shared `ToDo` data.
+    ///
+    /// # Parameters
+    ///
+    /// - `action`: The action to apply.
+    pub fn apply(&self, action: Action) {
+        if let Err(e) = self.data().apply(action) {
+            status::error(format!("Cannot apply action: {e}"));
+        }
+    }
 }
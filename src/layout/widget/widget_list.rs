@@ -1,9 +1,60 @@
 use super::{RCToDo, WidgetBase, WidgetType};
 use crate::config::Config;
 use crate::ui::{EventHandlerUI, HandleEvent, UIEvent};
+use clap::ValueEnum;
 use crossterm::event::KeyCode;
+use serde::{Deserialize, Serialize};
 use std::ops::{Deref, DerefMut};
-use tui::widgets::ListState;
+use tui::{
+    backend::Backend,
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    widgets::{ListState, Widget},
+    Frame,
+};
+
+/// The indicator of the scroll position drawn on the borders of list widgets.
+#[derive(Clone, Copy, Serialize, Deserialize, Default, ValueEnum, PartialEq, Eq, Debug)]
+pub enum ScrollIndicator {
+    /// No indicator.
+    #[default]
+    None,
+    /// A scrollbar on the right border, drawn only if the list does not fit.
+    Bar,
+    /// The number of the selected item and the number of items, e.g. `12/384`,
+    /// on the bottom border.
+    Position,
+    /// Both the scrollbar and the position.
+    Both,
+}
+
+/// The symbol of the scroll indicator written over every row of its area,
+/// the cells next to it, e.g. the rest of the border, are kept.
+struct ScrollMark<'a> {
+    symbol: &'a str,
+    style: Style,
+}
+
+impl Widget for ScrollMark<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for y in area.top()..area.bottom() {
+            buf.set_stringn(area.x, y, self.symbol, area.width as usize, self.style);
+        }
+    }
+}
+
+/// The scroll offset and the selection of a list widget, kept between launches.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ListPosition {
+    pub widget: WidgetType,
+    /// The index of the first shown item.
+    pub first: usize,
+    /// The index of the selected item within the entire list.
+    pub index: usize,
+    /// The selected item, e.g. the task or the category, found again if the list has changed.
+    pub item: Option<String>,
+}
 
 /// Represents a widget that displays a list of items.
 pub struct WidgetList {
@@ -14,6 +65,15 @@ pub struct WidgetList {
     size: usize,
     event_handler: EventHandlerUI,
     list_shift: usize,
+    zebra_style: Style,
+    separators: Vec<usize>,
+    /// The number of rows of each item, e.g. of a wrapped task. Items without a height
+    /// take one row.
+    heights: Vec<usize>,
+    scroll_indicator: ScrollIndicator,
+    scroll_style: Style,
+    /// The count typed before a motion, e.g. `12` of `12j`.
+    count: Option<usize>,
 }
 
 impl WidgetList {
@@ -23,19 +83,46 @@ impl WidgetList {
     ///
     /// - `widget_type`: The type of widget.
     /// - `data`: A reference-counted mutex of `ToDo` data.
+    /// - `config`: The configuration, the `widget_settings` of the widget take precedence
+    ///   over the global key bindings and scrolling.
     ///
     /// # Returns
     ///
     /// A new `WidgetList` instance.
     pub fn new(widget_type: &WidgetType, data: RCToDo, config: &Config) -> Self {
+        let settings = config.get_widget_settings(widget_type);
+        let event_handler = config.get_list_keybind();
         let mut def = Self {
             base: WidgetBase::new(widget_type, data, config),
             state: ListState::default(),
             len: 0,
             first: 0,
             size: 0,
-            event_handler: config.get_list_keybind(),
-            list_shift: config.get_list_shift(),
+            event_handler: match &settings.list_keybind {
+                Some(keybind) => event_handler.merge(keybind),
+                None => event_handler,
+            },
+            list_shift: settings
+                .list_shift
+                .unwrap_or_else(|| config.get_list_shift()),
+            zebra_style: config
+                .get_list_zebra_color()
+                .combine(&match widget_type {
+                    WidgetType::List => config.get_pending_zebra_color(),
+                    WidgetType::Done => config.get_done_zebra_color(),
+                    WidgetType::Project
+                    | WidgetType::Context
+                    | WidgetType::Hashtag
+                    | WidgetType::Assignee
+                    | WidgetType::Tag => config.get_category_zebra_color(),
+                    WidgetType::Preview => Default::default(),
+                })
+                .get_style(),
+            separators: Vec::new(),
+            heights: Vec::new(),
+            scroll_indicator: config.get_scroll_indicator(),
+            scroll_style: config.get_scroll_indicator_style().get_style(),
+            count: None,
         };
         def.state.select(Some(0));
         def
@@ -75,24 +162,158 @@ impl WidgetList {
     /// - `size`: The size of the list widget.
     pub fn set_size(&mut self, size: u16) {
         self.size = size as usize;
+        // The selection stays visible when the list is smaller than the selected row.
+        self.keep_visible();
+    }
+
+    /// Sets the number of rows each item takes, the list scrolls by items
+    /// so that the shown items fit to the size of the list.
+    ///
+    /// # Parameters
+    ///
+    /// - `heights`: The number of rows of each item within the entire list,
+    ///   an empty vector if every item takes one row.
+    pub fn set_heights(&mut self, heights: Vec<usize>) {
+        self.heights = heights;
+        self.keep_visible();
+    }
+
+    /// Gets the number of rows of the item, at least one.
+    ///
+    /// # Parameters
+    ///
+    /// - `index`: The index of the item within the entire list.
+    fn height(&self, index: usize) -> usize {
+        self.heights.get(index).map_or(1, |height| (*height).max(1))
+    }
+
+    /// Gets the number of rows of the items in the range.
+    fn rows(&self, from: usize, to: usize) -> usize {
+        match self.heights.is_empty() {
+            true => to.saturating_sub(from),
+            false => (from..to).map(|index| self.height(index)).sum(),
+        }
+    }
+
+    /// Gets the number of items shown from the first shown item, at least one.
+    /// Rows after the end of the list count as items of one row.
+    fn shown(&self) -> usize {
+        let mut rows = 0;
+        let mut shown = 0;
+        while rows + self.height(self.first + shown) <= self.size {
+            rows += self.height(self.first + shown);
+            shown += 1;
+        }
+        shown.max(1)
+    }
+
+    /// Gets the first shown item for which the item at the index is the last shown item.
+    ///
+    /// # Parameters
+    ///
+    /// - `index`: The index of the item within the entire list.
+    fn first_showing(&self, index: usize) -> usize {
+        let mut first = index;
+        let mut rows = self.height(index);
+        while first > 0 && rows + self.height(first - 1) <= self.size {
+            first -= 1;
+            rows += self.height(first);
+        }
+        first
+    }
+
+    /// Scrolls the list down until all rows of the selected item are shown.
+    fn keep_visible(&mut self) {
+        if self.size == 0 {
+            return;
+        }
+        let index = self.index();
+        self.first = self.first.max(self.first_showing(index));
+        self.state.select(Some(index - self.first));
+    }
+
+    /// Sets the rows that only separate items, e.g. section dividers.
+    /// The selection skips them when it moves.
+    ///
+    /// # Parameters
+    ///
+    /// - `separators`: The sorted indices of the separator rows within the entire list.
+    pub fn set_separators(&mut self, separators: Vec<usize>) {
+        self.separators = separators;
+    }
+
+    /// Checks whether the row at the index only separates items and cannot be selected.
+    ///
+    /// # Parameters
+    ///
+    /// - `index`: The index of the row within the entire list.
+    pub fn is_separator(&self, index: usize) -> bool {
+        self.separators.binary_search(&index).is_ok()
+    }
+
+    /// Moves the selection in the direction until an item that is not a separator is selected.
+    ///
+    /// # Parameters
+    ///
+    /// - `step`: The function moving the selection by one row.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the end of the list was reached on a separator.
+    fn skip_separators(&mut self, step: fn(&mut Self)) -> bool {
+        while self.is_separator(self.index()) {
+            let index = self.index();
+            step(self);
+            if self.index() == index {
+                return false;
+            }
+        }
+        true
     }
 
-    /// Moves the selection down the list.
+    /// Moves the selection by one row in the direction and over the following separators.
+    /// The selection stays where it was if there is no item to select.
+    ///
+    /// # Parameters
+    ///
+    /// - `step`: The function moving the selection by one row.
+    fn move_by(&mut self, step: fn(&mut Self)) {
+        let (first, selected) = (self.first, self.state.selected());
+        step(self);
+        if !self.skip_separators(step) {
+            self.first = first;
+            self.state.select(selected);
+        }
+    }
+
+    /// Moves the selection down the list, separators are skipped.
     pub fn down(&mut self) {
+        self.move_by(Self::step_down);
+    }
+
+    /// Moves the selection up the list, separators are skipped.
+    pub fn up(&mut self) {
+        self.move_by(Self::step_up);
+    }
+
+    /// Moves the selection down by one row.
+    fn step_down(&mut self) {
         let act = self.act();
-        if self.len <= self.size {
+        let shown = self.shown();
+        if self.rows(0, self.len) <= self.size {
             if self.len > act + 1 {
                 self.state.select(Some(act + 1));
             }
-        } else if self.size <= act + 1 + self.list_shift {
-            if self.first + self.size < self.len {
+        } else if shown <= act + 1 + self.list_shift {
+            if self.first + shown < self.len {
                 self.first += 1;
-            } else if self.size > act + 1 {
+            } else if shown > act + 1 {
                 self.state.select(Some(act + 1));
             }
         } else {
             self.state.select(Some(act + 1));
         }
+        self.keep_visible();
         log::trace!(
             "List go down: act: {}, size: {} len: {}, shift: {}",
             act,
@@ -102,8 +323,8 @@ impl WidgetList {
         );
     }
 
-    /// Moves the selection up the list.
-    pub fn up(&mut self) {
+    /// Moves the selection up by one row.
+    fn step_up(&mut self) {
         let act = self.act();
         if act <= self.list_shift {
             if self.first > 0 {
@@ -114,6 +335,7 @@ impl WidgetList {
         } else {
             self.state.select(Some(act - 1));
         }
+        self.keep_visible();
         log::trace!("List go up: act: {}", act);
     }
 
@@ -125,13 +347,13 @@ impl WidgetList {
     /// An `Option` containing the indices of the (old, new) selections,
     /// or `None` if the list is at the end.
     pub fn next(&mut self) -> Option<(usize, usize)> {
-        log::error!("len: {}, index: {}", self.len, self.index());
+        log::trace!("List next: len: {}, index: {}", self.len, self.index());
         if self.len <= self.index() + 1 {
             None
         } else {
             let old = self.index();
             self.down();
-            Some((old, self.index()))
+            (old != self.index()).then_some((old, self.index()))
         }
     }
 
@@ -143,12 +365,12 @@ impl WidgetList {
     /// An `Option` containing the indices of the old and new selections,
     /// or `None` if the list is at the beginning.
     pub fn prev(&mut self) -> Option<(usize, usize)> {
-        if self.act() == 0 {
+        if self.index() == 0 {
             None
         } else {
             let old = self.index();
             self.up();
-            Some((old, self.index()))
+            (old != self.index()).then_some((old, self.index()))
         }
     }
 
@@ -156,17 +378,136 @@ impl WidgetList {
     pub fn first(&mut self) {
         self.state.select(Some(0));
         self.first = 0;
+        self.skip_separators(Self::step_down);
     }
 
     /// Moves the selection to the last item in the list.
     pub fn last(&mut self) {
-        let shown_items = self.len - 1;
-        if self.size > shown_items {
-            self.first = 0;
-            self.state.select(Some(shown_items));
+        let last = self.len.saturating_sub(1);
+        self.first = self.first_showing(last);
+        self.state.select(Some(last - self.first));
+        self.skip_separators(Self::step_up);
+    }
+
+    /// Selects the item at the index, the list is scrolled
+    /// to show the item in the middle if it is not visible.
+    ///
+    /// # Parameters
+    ///
+    /// - `index`: The index of the item within the entire list.
+    pub fn select(&mut self, index: usize) {
+        let index = index.min(self.len.saturating_sub(1));
+        if index < self.first || index >= self.first + self.shown() {
+            let mut first = index;
+            let mut above = 0;
+            while first > 0 && above + self.height(first - 1) <= self.size / 2 {
+                first -= 1;
+                above += self.height(first);
+            }
+            self.first = first.min(self.first_showing(self.len.saturating_sub(1)));
+        }
+        self.state.select(Some(index - self.first));
+        self.keep_visible();
+    }
+
+    /// Selects the item by its number counted from 1, as line numbers show it.
+    /// Separators are not counted.
+    ///
+    /// # Parameters
+    ///
+    /// - `number`: The number of the item, the last item is selected if it is too high.
+    pub fn select_item(&mut self, number: usize) {
+        let mut index = number.saturating_sub(1);
+        for separator in &self.separators {
+            if *separator > index {
+                break;
+            }
+            index += 1;
+        }
+        self.select(index);
+        self.skip_separators(Self::step_up);
+    }
+
+    /// Adds the digit of the key to the count of the next motion, e.g. `12j` moves
+    /// the selection 12 rows down and `12G` selects the 12th item. A digit bound to
+    /// an event, like `0`, does not start a count but it continues one as in vim.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The pressed key.
+    /// - `event`: The event the key is bound to.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the key is a digit of the count.
+    pub fn push_count(&mut self, key: &KeyCode, event: UIEvent) -> bool {
+        let digit = match key {
+            KeyCode::Char(c) => match c.to_digit(10) {
+                Some(digit) => digit as usize,
+                None => return false,
+            },
+            _ => return false,
+        };
+        if self.count.is_none() && (digit == 0 || event != UIEvent::None) {
+            return false;
+        }
+        let count = self.count.unwrap_or(0).saturating_mul(10);
+        self.count = Some(count.saturating_add(digit));
+        true
+    }
+
+    /// Gets the count typed for the next motion.
+    #[cfg(test)]
+    pub fn count(&self) -> Option<usize> {
+        self.count
+    }
+
+    /// Gets the scroll offset and the selection of the list.
+    ///
+    /// # Parameters
+    ///
+    /// - `widget`: The type of the widget showing the list.
+    /// - `item`: The selected item.
+    pub fn position(&self, widget: WidgetType, item: Option<String>) -> ListPosition {
+        ListPosition {
+            widget,
+            first: self.first,
+            index: self.index(),
+            item,
+        }
+    }
+
+    /// Scrolls the list and selects the item, both are moved into the list if it is
+    /// shorter now. The selected item is kept visible.
+    ///
+    /// # Parameters
+    ///
+    /// - `first`: The index of the first shown item.
+    /// - `index`: The index of the selected item within the entire list.
+    pub fn scroll_to(&mut self, first: usize, index: usize) {
+        let index = index.min(self.len.saturating_sub(1));
+        let mut first = first.min(index);
+        if self.size > 0 {
+            first = first.max(self.first_showing(index));
+        }
+        self.first = first;
+        self.state.select(Some(index - first));
+    }
+
+    /// Gets the style of the row, every second row is striped with the zebra style.
+    ///
+    /// # Parameters
+    ///
+    /// - `index`: The index of the item within the entire list.
+    ///
+    /// # Returns
+    ///
+    /// The style of the row.
+    pub fn row_style(&self, index: usize) -> Style {
+        if index % 2 == 1 {
+            self.zebra_style
         } else {
-            self.first = self.len - self.size;
-            self.state.select(Some(self.size - 1));
+            Style::default()
         }
     }
 
@@ -174,9 +515,85 @@ impl WidgetList {
     ///
     /// # Returns
     ///
-    /// A tuple containing the indices of the (first, last) items displayed.
+    /// A tuple containing the indices of the (first, last) items displayed, only items
+    /// whose rows fit to the list are displayed.
     pub fn range(&self) -> (usize, usize) {
-        (self.first, self.first + self.size)
+        (self.first, self.first + self.shown())
+    }
+
+    /// Gets the rows of the scrollbar thumb.
+    ///
+    /// # Parameters
+    ///
+    /// - `track`: The number of rows of the scrollbar.
+    ///
+    /// # Returns
+    ///
+    /// The first row of the thumb within the scrollbar and its number of rows,
+    /// `None` if all items are shown.
+    fn thumb(&self, track: usize) -> Option<(usize, usize)> {
+        let (first, last) = self.range();
+        let shown = last.min(self.len).saturating_sub(first);
+        let hidden = self.len.saturating_sub(shown);
+        if hidden == 0 || track == 0 {
+            return None;
+        }
+        let length = (track * shown / self.len).clamp(1, track);
+        Some(((track - length) * first.min(hidden) / hidden, length))
+    }
+
+    /// Gets the text of the position indicator, separators are not counted.
+    fn position_text(&self) -> String {
+        let index = self.index();
+        let before = self
+            .separators
+            .partition_point(|separator| *separator < index);
+        let items = self.len.saturating_sub(self.separators.len());
+        format!(" {}/{} ", (index + 1 - before).min(items), items)
+    }
+
+    /// Draws the scroll indicator set by `scroll_indicator` on the borders of the list.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: The frame the list is rendered to.
+    pub fn render_scroll_indicator<B: Backend>(&self, f: &mut Frame<B>) {
+        let chunk = self.chunk;
+        if self.len == 0 || chunk.width < 3 || chunk.height < 3 {
+            return;
+        }
+        let right = chunk.x + chunk.width - 1;
+        if matches!(
+            self.scroll_indicator,
+            ScrollIndicator::Bar | ScrollIndicator::Both
+        ) {
+            if let Some((start, length)) = self.thumb(chunk.height as usize - 2) {
+                let mark = ScrollMark {
+                    symbol: "┃",
+                    style: self.scroll_style,
+                };
+                f.render_widget(
+                    mark,
+                    Rect::new(right, chunk.y + 1 + start as u16, 1, length as u16),
+                );
+            }
+        }
+        if matches!(
+            self.scroll_indicator,
+            ScrollIndicator::Position | ScrollIndicator::Both
+        ) {
+            let text = self.position_text();
+            let width = text.chars().count() as u16;
+            // The corners of the border are kept.
+            if width + 2 < chunk.width {
+                let mark = ScrollMark {
+                    symbol: &text,
+                    style: self.scroll_style,
+                };
+                let bottom = chunk.y + chunk.height - 1;
+                f.render_widget(mark, Rect::new(right - 1 - width, bottom, width, 1));
+            }
+        }
     }
 }
 
@@ -186,11 +603,14 @@ impl HandleEvent for WidgetList {
     }
 
     fn handle_event(&mut self, event: UIEvent) -> bool {
-        match event {
-            UIEvent::ListDown => self.down(),
-            UIEvent::ListUp => self.up(),
-            UIEvent::ListFirst => self.first(),
-            UIEvent::ListLast => self.last(),
+        // Any other key cancels the count.
+        let count = self.count.take();
+        match (event, count) {
+            (UIEvent::ListDown, count) => (0..count.unwrap_or(1)).for_each(|_| self.down()),
+            (UIEvent::ListUp, count) => (0..count.unwrap_or(1)).for_each(|_| self.up()),
+            (UIEvent::ListFirst | UIEvent::ListLast, Some(number)) => self.select_item(number),
+            (UIEvent::ListFirst, None) => self.first(),
+            (UIEvent::ListLast, None) => self.last(),
             _ => return false,
         }
         true
@@ -217,6 +637,7 @@ mod tests {
     use crate::todo::ToDo;
     use std::sync::{Arc, Mutex};
     use test_log::test;
+    use tui::style::Color;
 
     fn testing_widget(len: usize) -> WidgetList {
         let mut todo = ToDo::default();
@@ -392,6 +813,28 @@ mod tests {
         assert_eq!(widget.next(), Some((48, 49)));
     }
 
+    #[test]
+    fn move_task_scrolled() {
+        let mut widget = testing_widget(50);
+        widget.list_shift = 0;
+        n_times(15, WidgetList::down, &mut widget);
+        assert_eq!(widget.act(), 9);
+        assert_eq!(widget.first, 6);
+
+        // The moved task scrolls the list at the bottom border.
+        assert_eq!(widget.next(), Some((15, 16)));
+        assert_eq!(widget.act(), 9);
+        assert_eq!(widget.first, 7);
+
+        // The moved task scrolls the list at the top border.
+        n_times(9, WidgetList::up, &mut widget);
+        assert_eq!(widget.act(), 0);
+        assert_eq!(widget.first, 7);
+        assert_eq!(widget.prev(), Some((7, 6)));
+        assert_eq!(widget.act(), 0);
+        assert_eq!(widget.first, 6);
+    }
+
     #[test]
     fn first_and_last_item() {
         // Long list
@@ -419,12 +862,118 @@ mod tests {
         assert_eq!(widget.first, 0);
     }
 
+    #[test]
+    fn select() {
+        let mut widget = testing_widget(50);
+        widget.select(5);
+        assert_eq!(widget.index(), 5);
+        assert_eq!(widget.first, 0);
+
+        widget.select(30);
+        assert_eq!(widget.index(), 30);
+        assert_eq!(widget.act(), 5);
+        assert_eq!(widget.first, 25);
+
+        widget.select(28);
+        assert_eq!(widget.index(), 28);
+        assert_eq!(widget.first, 25);
+
+        widget.select(100);
+        assert_eq!(widget.index(), 49);
+        assert_eq!(widget.first, 40);
+    }
+
+    #[test]
+    fn scroll_to() {
+        let mut widget = testing_widget(50);
+        widget.scroll_to(20, 25);
+        assert_eq!(widget.position(WidgetType::List, None).first, 20);
+        assert_eq!(widget.index(), 25);
+        assert_eq!(widget.act(), 5);
+
+        // The selected item is kept visible.
+        widget.scroll_to(0, 30);
+        assert_eq!((widget.first, widget.index()), (21, 30));
+
+        // The list got shorter.
+        widget.scroll_to(60, 70);
+        assert_eq!((widget.first, widget.index()), (49, 49));
+
+        widget.scroll_to(40, 48);
+        widget.set_size(5);
+        assert_eq!((widget.first, widget.index()), (44, 48));
+        assert_eq!(widget.act(), 4);
+    }
+
     #[test]
     fn range() {
         let widget = testing_widget(50);
         assert_eq!(widget.range(), (0, 10));
     }
 
+    #[test]
+    fn heights() {
+        let mut widget = testing_widget(20);
+        widget.list_shift = 0;
+        let mut heights = vec![1; 20];
+        heights[3] = 4;
+        heights[10] = 3;
+        widget.set_heights(heights);
+        assert_eq!(widget.range(), (0, 7));
+
+        // The tall item is scrolled to be shown entirely.
+        n_times(6, WidgetList::down, &mut widget);
+        assert_eq!(widget.index(), 6);
+        assert_eq!(widget.range(), (0, 7));
+        widget.down();
+        assert_eq!((widget.first, widget.index()), (1, 7));
+        n_times(3, WidgetList::down, &mut widget);
+        assert_eq!((widget.first, widget.index()), (4, 10));
+        assert_eq!(widget.range(), (4, 12));
+
+        widget.last();
+        assert_eq!((widget.first, widget.index()), (11, 19));
+        widget.select(3);
+        assert_eq!((widget.first, widget.index()), (0, 3));
+        widget.scroll_to(0, 10);
+        assert_eq!((widget.first, widget.index()), (4, 10));
+
+        // A smaller list keeps the selected item shown.
+        widget.set_size(5);
+        assert_eq!((widget.first, widget.index()), (8, 10));
+        assert_eq!(widget.act(), 2);
+    }
+
+    #[test]
+    fn scroll_indicator() {
+        let mut widget = testing_widget(50);
+        assert_eq!(widget.thumb(10), Some((0, 2)));
+        assert_eq!(widget.position_text(), " 1/50 ");
+        widget.select(30);
+        assert_eq!(widget.thumb(10), Some((5, 2)));
+        widget.last();
+        assert_eq!(widget.thumb(10), Some((8, 2)));
+        assert_eq!(widget.position_text(), " 50/50 ");
+
+        // Separators are not counted.
+        widget.set_separators(vec![0, 10]);
+        widget.select(12);
+        assert_eq!(widget.position_text(), " 11/48 ");
+
+        // The thumb is not shown if all items fit.
+        let widget = testing_widget(5);
+        assert_eq!(widget.thumb(10), None);
+
+        let mut buffer = Buffer::with_lines(vec!["│ │", "│ │", "└─┘"]);
+        let style = Style::default();
+        ScrollMark {
+            symbol: "┃", style
+        }
+        .render(Rect::new(2, 0, 1, 2), &mut buffer);
+        ScrollMark { symbol: "1", style }.render(Rect::new(1, 2, 1, 1), &mut buffer);
+        assert_eq!(buffer, Buffer::with_lines(vec!["│ ┃", "│ ┃", "└1┘"]));
+    }
+
     #[test]
     fn handle_event() {
         let mut widget = testing_widget(50);
@@ -450,4 +999,149 @@ mod tests {
 
         assert!(!widget.handle_event(UIEvent::None));
     }
+
+    #[test]
+    fn separators() {
+        let mut widget = testing_widget(20);
+        widget.set_separators(vec![0, 2, 3, 19]);
+        widget.first();
+        assert_eq!(widget.index(), 1);
+        widget.down();
+        assert_eq!(widget.index(), 4);
+        widget.up();
+        assert_eq!(widget.index(), 1);
+        widget.up();
+        assert_eq!(widget.index(), 1);
+
+        widget.last();
+        assert_eq!(widget.index(), 18);
+        widget.down();
+        assert_eq!(widget.index(), 18);
+        assert_eq!(widget.next(), None);
+        assert!(widget.is_separator(19));
+        assert!(!widget.is_separator(18));
+    }
+
+    #[test]
+    fn zebra_rows() {
+        let widget = testing_widget(5);
+        assert_eq!(widget.row_style(0), Style::default());
+        assert_eq!(widget.row_style(1), Style::default());
+
+        let config = Config::load_from_buffer(
+            r#"
+            list_zebra_color = { bg = "DarkGray" }
+            done_zebra_color = { fg = "Gray" }
+            "#
+            .as_bytes(),
+        );
+        let todo = Arc::new(Mutex::new(ToDo::default()));
+        let widget = WidgetList::new(&WidgetType::List, todo.clone(), &config);
+        assert_eq!(widget.row_style(0), Style::default());
+        assert_eq!(widget.row_style(1), Style::default().bg(Color::DarkGray));
+        assert_eq!(widget.row_style(2), Style::default());
+
+        let widget = WidgetList::new(&WidgetType::Done, todo, &config);
+        assert_eq!(
+            widget.row_style(3),
+            Style::default().bg(Color::DarkGray).fg(Color::Gray)
+        );
+    }
+
+    #[test]
+    fn widget_settings() {
+        let config = Config::load_from_buffer(
+            r#"
+            list_shift = 3
+
+            [widget_settings.done]
+            title = "Completed"
+            list_shift = 1
+
+            [[widget_settings.done.list_keybind.events]]
+            key.Char = "n"
+            event = "ListDown"
+
+            [[widget_settings.done.list_keybind.events]]
+            key.Char = "j"
+            event = "None"
+
+            [[widget_settings.done.keybind.events]]
+            key.Char = "x"
+            event = "None"
+            "#
+            .as_bytes(),
+        );
+        let todo = Arc::new(Mutex::new(ToDo::default()));
+        let done = WidgetList::new(&WidgetType::Done, todo.clone(), &config);
+        assert_eq!(done.title, "Completed");
+        assert_eq!(done.list_shift, 1);
+        assert_eq!(done.get_event(&KeyCode::Char('n')), UIEvent::ListDown);
+        assert_eq!(done.get_event(&KeyCode::Char('j')), UIEvent::None);
+        assert_eq!(done.get_event(&KeyCode::Char('k')), UIEvent::ListUp);
+        assert_eq!(
+            done.base.event_handler.get_event(&KeyCode::Char('x')),
+            UIEvent::None
+        );
+        assert_eq!(
+            done.base.event_handler.get_event(&KeyCode::Char('d')),
+            UIEvent::MoveItem
+        );
+
+        // Other widgets keep the global settings.
+        let list = WidgetList::new(&WidgetType::List, todo, &config);
+        assert_eq!(list.title, "List");
+        assert_eq!(list.list_shift, 3);
+        assert_eq!(list.get_event(&KeyCode::Char('n')), UIEvent::None);
+        assert_eq!(list.get_event(&KeyCode::Char('j')), UIEvent::ListDown);
+        assert_eq!(
+            list.base.event_handler.get_event(&KeyCode::Char('x')),
+            UIEvent::RemoveItem
+        );
+    }
+
+    #[test]
+    fn count_motions() {
+        let mut widget = testing_widget(50);
+        let key = |c| KeyCode::Char(c);
+        assert!(!widget.push_count(&key('0'), UIEvent::None));
+        assert!(!widget.push_count(&key('j'), UIEvent::ListDown));
+        assert!(widget.push_count(&key('1'), UIEvent::None));
+        // A bound digit continues the count.
+        assert!(widget.push_count(&key('0'), UIEvent::PriorityClear));
+        assert_eq!(widget.count(), Some(10));
+        widget.handle_event(UIEvent::ListDown);
+        assert_eq!(widget.index(), 10);
+        assert_eq!(widget.count(), None);
+        widget.handle_event(UIEvent::ListDown);
+        assert_eq!(widget.index(), 11);
+
+        widget.push_count(&key('3'), UIEvent::None);
+        widget.handle_event(UIEvent::ListUp);
+        assert_eq!(widget.index(), 8);
+        widget.push_count(&key('4'), UIEvent::None);
+        widget.push_count(&key('2'), UIEvent::None);
+        widget.handle_event(UIEvent::ListLast);
+        assert_eq!(widget.index(), 41);
+        widget.push_count(&key('1'), UIEvent::None);
+        widget.handle_event(UIEvent::ListFirst);
+        assert_eq!(widget.index(), 0);
+        widget.push_count(&key('9'), UIEvent::None);
+        widget.push_count(&key('9'), UIEvent::None);
+        widget.handle_event(UIEvent::ListLast);
+        assert_eq!(widget.index(), 49);
+
+        // Other events cancel the count.
+        widget.push_count(&key('5'), UIEvent::None);
+        assert!(!widget.handle_event(UIEvent::Select));
+        widget.handle_event(UIEvent::ListUp);
+        assert_eq!(widget.index(), 48);
+
+        // Separators are not counted.
+        widget.set_separators(vec![0, 5]);
+        widget.select_item(1);
+        assert_eq!(widget.index(), 1);
+        widget.select_item(5);
+        assert_eq!(widget.index(), 6);
+    }
 }
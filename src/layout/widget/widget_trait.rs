@@ -1,12 +1,18 @@
 use super::super::Render;
 use super::widget_base::WidgetBase;
-use crate::ui::{HandleEvent, UIEvent};
-use crossterm::event::KeyCode;
+use super::widget_list::ListPosition;
+use crate::{
+    ui::{Command, HandleEvent, UIEvent},
+    ToDoError, ToDoRes,
+};
+use crossterm::event::{KeyCode, MouseEvent};
+use todo_txt::Task;
 use tui::{
     backend::Backend,
+    layout::Alignment,
     prelude::Rect,
-    style::Style,
-    widgets::{Block, BorderType, Borders},
+    style::{Color, Style},
+    widgets::{Block, Paragraph, Wrap},
     Frame,
 };
 
@@ -40,14 +46,23 @@ pub trait State {
     // Retrieves the block (border and title) for rendering the widget.
     fn get_block(&self) -> Block {
         let base = self.get_base();
-        let mut block = Block::default()
-            .borders(Borders::ALL)
-            .title(base.title.clone())
-            .border_type(BorderType::Rounded);
-        if base.focus {
-            block = block.border_style(Style::default().fg(base.active_color));
-        }
-        block
+        base.block(base.title.clone())
+    }
+
+    /// Renders the widget's block with a message in place of its content.
+    /// Used when there is nothing to display in the widget.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `message`: The message to display.
+    fn render_message<B: Backend>(&self, f: &mut Frame<B>, message: &str) {
+        let paragraph = Paragraph::new(message.to_string())
+            .block(self.get_block())
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .style(Style::default().fg(Color::DarkGray));
+        f.render_widget(paragraph, self.get_base().chunk);
     }
 
     /// Called when the widget receives focus.
@@ -61,6 +76,82 @@ pub trait State {
     /// Called when the widget's rendering area (chunk) is updated.
     fn update_chunk_event(&mut self) {}
 
+    /// Called when the todo list has changed, e.g. tasks were added or removed,
+    /// filters were changed or the file was reloaded.
+    fn data_event(&mut self) {}
+
+    /// Handles a mouse event that occurred inside the widget's area.
+    ///
+    /// # Parameters
+    ///
+    /// - `event`: The mouse event to be handled.
+    ///
+    /// # Returns
+    ///
+    /// A boolean indicating whether the event was successfully handled.
+    fn handle_mouse(&mut self, _: &MouseEvent) -> bool {
+        false
+    }
+
+    /// Handles a command entered in the command palette.
+    ///
+    /// # Parameters
+    ///
+    /// - `command`: The command to be handled.
+    ///
+    /// # Returns
+    ///
+    /// A result indicating whether the command was handled successfully.
+    fn handle_command(&mut self, _: &Command) -> ToDoRes<()> {
+        Err(ToDoError::CommandNotSupported)
+    }
+
+    /// Gets the content of the widget as plain text, e.g. to copy it to the clipboard.
+    ///
+    /// # Returns
+    ///
+    /// The lines of the widget or `None` if the widget has no text to copy.
+    fn text(&self) -> Option<String> {
+        None
+    }
+
+    /// Gets the tasks the task actions of the widget apply to, e.g. to confirm deleting them.
+    ///
+    /// # Returns
+    ///
+    /// The marked tasks, the selected task if no task is marked or nothing if the
+    /// widget does not show tasks.
+    fn target_tasks(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Gets the tasks shown by the widget in the order they are shown, e.g. to export them.
+    ///
+    /// # Returns
+    ///
+    /// The shown tasks or `None` if the widget does not show tasks.
+    fn shown_tasks(&self) -> Option<Vec<Task>> {
+        None
+    }
+
+    /// Gets the scroll offset and the selection of the widget, e.g. to restore them
+    /// on the next launch.
+    ///
+    /// # Returns
+    ///
+    /// The position or `None` if the widget is not a list.
+    fn position(&self) -> Option<ListPosition> {
+        None
+    }
+
+    /// Scrolls the widget and selects the item of the saved position. The item is looked
+    /// up by its content first, the saved index is used if it is not found.
+    ///
+    /// # Parameters
+    ///
+    /// - `position`: The saved position.
+    fn restore_position(&mut self, _: &ListPosition) {}
+
     /// Retrieves an internal UI event based on a key code.
     /// This can be used for custom event handling within a state.
     ///
@@ -74,6 +165,20 @@ pub trait State {
     fn get_internal_event(&self, _: &KeyCode) -> UIEvent {
         UIEvent::None
     }
+
+    /// Adds the pressed digit to the count of the next list motion, e.g. `12j`.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The pressed key.
+    /// - `event`: The event the key is bound to.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the key was taken as a digit of the count.
+    fn push_count(&mut self, _key: &KeyCode, _event: UIEvent) -> bool {
+        false
+    }
 }
 
 impl<S: State> HandleEvent for S {
@@ -89,6 +194,12 @@ impl<S: State> HandleEvent for S {
     fn handle_event(&mut self, event: UIEvent) -> bool {
         self.handle_event_state(event)
     }
+
+    fn handle_key(&mut self, key: &KeyCode) -> bool {
+        let event = self.get_event(key);
+        log::trace!("EventHandler: Key '{:?}' cause event '{:?}'", key, event);
+        self.push_count(key, event) || self.handle_event(event)
+    }
 }
 
 impl<S: State> Render for S {
@@ -17,6 +17,9 @@ pub enum WidgetType {
     Project,
     Context,
     Hashtag,
+    Assignee,
+    /// Values of a `key:value` tag, the key is given in the layout, e.g. `Tags(due)`.
+    Tag,
     Preview,
 }
 
@@ -29,6 +32,8 @@ impl fmt::Display for WidgetType {
             Project => write!(f, "Projects"),
             Context => write!(f, "Contexts"),
             Hashtag => write!(f, "Hashtags"),
+            Assignee => write!(f, "Assignees"),
+            Tag => write!(f, "Tags"),
             Preview => write!(f, "Preview"),
         }
     }
@@ -41,6 +46,8 @@ impl From<ToDoCategory> for WidgetType {
             Projects => WidgetType::Project,
             Contexts => WidgetType::Context,
             Hashtags => WidgetType::Hashtag,
+            Assignees => WidgetType::Assignee,
+            Tag(_) => WidgetType::Tag,
         }
     }
 }
@@ -65,6 +72,8 @@ impl FromStr for WidgetType {
             "projects" => Project,
             "contexts" => Context,
             "hashtags" => Hashtag,
+            "assignees" => Assignee,
+            "tags" => Tag,
             "preview" => Preview,
             _ => return Err(ToDoError::ParseWidgetType(s.to_string())),
         })
@@ -1,11 +1,15 @@
-pub mod config;
+//! The task engine of Todo.txt TUI, a terminal application for managing todo.txt files.
+//!
+//! The library keeps the todo list with its filters and sorting, parses task templates
+//! and loads and saves todo.txt files. The terminal user interface is the `todotxt-tui`
+//! binary built on top of it, the public API is summarized in the [`engine`] module.
+
+pub mod clock;
+pub mod engine;
 pub mod error;
 pub mod file_worker;
-pub mod layout;
+pub mod status;
+pub mod style;
 pub mod todo;
-pub mod ui;
 
 pub use error::*;
-
-#[macro_use]
-extern crate enum_dispatch;
@@ -1,13 +1,34 @@
-use std::error::Error;
-use todotxt_tui::{
-    config::{Config, Logger},
-    ui::UI,
+mod activity_log;
+mod config;
+mod inbox;
+mod instance;
+mod layout;
+mod profiles;
+mod scripts;
+mod signals;
+mod ui;
+mod update_check;
+
+#[macro_use]
+extern crate enum_dispatch;
+
+use config::{is_first_run, Config, Logger, Setup};
+use std::{
+    error::Error,
+    io::{stdin, stdout, IsTerminal},
+};
+use todotxt_tui_core::{
+    clock, error, file_worker, status, style, todo, ToDoError, ToDoIoError, ToDoRes,
 };
+use ui::UI;
 
 fn main() {
-    let config = Config::new();
     let run = || -> Result<(), Box<dyn Error>> {
+        let mut config = Config::new();
         if !config.export()? {
+            if is_first_run(&config) && stdin().is_terminal() {
+                config = Setup::new(stdin().lock(), stdout()).run(config)?;
+            }
             Logger::new(&config).init()?;
             log::trace!("===== START LOGGING =====");
             let mut ui = UI::build(&config)?;
@@ -0,0 +1,311 @@
+use crate::{
+    config::Config,
+    file_worker::{LocalFile, RemoteFile, Storage},
+    instance::RunningInstance,
+    signals::Signal,
+    todo::{ToDo, ToDoState},
+    ToDoError, ToDoIoError, ToDoRes,
+};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+};
+
+/// A todo file the user can switch to, e.g. the work or the personal todo list.
+struct Profile {
+    name: String,
+    todo_path: String,
+    archive_path: Option<String>,
+    /// The URL of the remote todo file `todo_path` is the local copy of, see [`RemoteFile`].
+    todo_url: Option<String>,
+    /// The filters and the sorting of the profile while another profile is open.
+    state: Option<ToDoState>,
+}
+
+/// Manages the todo files of the `profiles` table. Only one profile is open at a time,
+/// its tasks are loaded in the todo list shared by the widgets, so the layout stays
+/// as it is when the profile is switched.
+pub struct Profiles {
+    profiles: Vec<Profile>,
+    active: usize,
+}
+
+impl Profiles {
+    /// Creates the profiles of the configuration. The profile of `todo_path` is open
+    /// first, it is added under the name of its file if no profile has its path.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration with `todo_path` and the `profiles` table.
+    pub fn new(config: &Config) -> Self {
+        let mut profiles: Vec<Profile> = config
+            .get_profiles()
+            .into_iter()
+            .map(|(name, todo_path)| Profile {
+                name,
+                todo_path,
+                archive_path: None,
+                todo_url: None,
+                state: None,
+            })
+            .collect();
+        profiles.sort_by(|a, b| a.name.cmp(&b.name));
+        let todo_path = config.get_todo_path();
+        let active = match profiles.iter().position(|p| p.todo_path == todo_path) {
+            Some(active) => active,
+            None => {
+                let name = Path::new(&todo_path)
+                    .file_stem()
+                    .map_or_else(String::new, |name| name.to_string_lossy().to_string());
+                profiles.insert(
+                    0,
+                    Profile {
+                        name,
+                        todo_path,
+                        archive_path: None,
+                        todo_url: None,
+                        state: None,
+                    },
+                );
+                0
+            }
+        };
+        // The archive file and the URL belong to the todo file they are configured with.
+        profiles[active].archive_path = config.get_archive_path();
+        profiles[active].todo_url = config.get_todo_url();
+        Self { profiles, active }
+    }
+
+    /// Gets the number of profiles.
+    fn len(&self) -> usize {
+        self.profiles.len()
+    }
+
+    /// Checks whether there is another profile to switch to.
+    pub fn can_switch(&self) -> bool {
+        self.len() > 1
+    }
+
+    /// Gets the name of the open profile.
+    pub fn name(&self) -> &str {
+        &self.profiles[self.active].name
+    }
+
+    /// Gets the names of the profiles that are not open.
+    pub fn others(&self) -> Vec<&str> {
+        self.profiles
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index != self.active)
+            .map(|(_, profile)| profile.name.as_str())
+            .collect()
+    }
+
+    /// Appends the tasks to the todo file of a profile that is not open, e.g. to move
+    /// tasks between todo files. Tasks are not changed, so they move as they are.
+    ///
+    /// The tasks are forwarded to the instance running on the todo file, so its changes
+    /// are not overwritten. Otherwise they are appended to the file, the file is replaced
+    /// at once by renaming, a remote todo file is uploaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the profile.
+    /// * `tasks` - The todo.txt lines of the tasks.
+    pub fn send(&self, name: &str, tasks: &[String]) -> ToDoRes<()> {
+        let profile = self
+            .profiles
+            .iter()
+            .enumerate()
+            .find(|(index, profile)| *index != self.active && profile.name == name)
+            .map(|(_, profile)| profile)
+            .ok_or_else(|| ToDoError::UnknownProfile(name.to_string()))?;
+        let io_error = |err| ToDoIoError {
+            path: PathBuf::from(&profile.todo_path),
+            err,
+        };
+        if let Some(running) = RunningInstance::find(&profile.todo_path).map_err(io_error)? {
+            return tasks
+                .iter()
+                .try_for_each(|task| running.forward(&Signal::Add(task.clone())))
+                .map_err(|e| io_error(e).into());
+        }
+        let mut storage: Box<dyn Storage> = match &profile.todo_url {
+            Some(url) => Box::new(RemoteFile::new(url, &profile.todo_path).map_err(io_error)?),
+            None => Box::new(LocalFile::new(&profile.todo_path)),
+        };
+        let mut content = match storage.read() {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(io_error(e).into()),
+        };
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        for task in tasks {
+            content.push_str(task);
+            content.push('\n');
+        }
+        match storage.is_local() {
+            true => replace_file(Path::new(&profile.todo_path), &content),
+            false => storage.write(content.as_bytes()),
+        }
+        .map_err(|e| io_error(e).into())
+    }
+
+    /// Gets the index of the profile following the open one, the first follows the last.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The number of profiles to move by, negative to move backwards.
+    pub fn offset(&self, offset: isize) -> usize {
+        (self.active as isize + offset).rem_euclid(self.len() as isize) as usize
+    }
+
+    /// Gets the configuration of the open profile, its paths replace the paths
+    /// of the todo file and the archive file.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration of the application.
+    pub fn config(&self, config: &Config) -> Config {
+        let profile = &self.profiles[self.active];
+        config.with_todo_file(&profile.todo_path, profile.archive_path.as_deref())
+    }
+
+    /// Opens another profile. The filters and the sorting of the open profile are kept
+    /// and those of the other profile are restored, tasks are loaded by the caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the opened profile.
+    /// * `todo` - The todo list shared by the widgets.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the profile is open already or does not exist.
+    pub fn switch(&mut self, index: usize, todo: &mut ToDo) -> bool {
+        if index == self.active || index >= self.len() {
+            return false;
+        }
+        self.profiles[self.active].state = Some(todo.get_state().clone());
+        self.active = index;
+        todo.update_state(self.profiles[index].state.take().unwrap_or_default());
+        true
+    }
+}
+
+/// Replaces the content of the file at once, a program reading the file meanwhile reads
+/// either the old or the new content. The content is written to a temporary file next
+/// to the file, which is renamed to the file.
+fn replace_file(path: &Path, content: &str) -> io::Result<()> {
+    // The target of a link is replaced, so the link is kept.
+    let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut temporary = path.clone().into_os_string();
+    temporary.push(format!(".{}.tmp", process::id()));
+    let temporary = PathBuf::from(temporary);
+    let result = fs::write(&temporary, content)
+        .and_then(|()| match fs::metadata(&path) {
+            Ok(metadata) => fs::set_permissions(&temporary, metadata.permissions()),
+            Err(_) => Ok(()),
+        })
+        .and_then(|()| fs::rename(&temporary, &path));
+    if result.is_err() {
+        let _ = fs::remove_file(&temporary);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::todo::{FilterState, ToDoCategory};
+
+    #[test]
+    fn switching() {
+        let config = Config::load_from_buffer(
+            r#"
+            todo_path = "/home/me/todo.txt"
+            archive_path = "/home/me/archive.txt"
+
+            [profiles]
+            work = "/home/me/work/todo.txt"
+            side = "/home/me/side/todo.txt"
+            "#
+            .as_bytes(),
+        );
+        let mut profiles = Profiles::new(&config);
+        assert_eq!(profiles.len(), 3);
+        assert_eq!(profiles.name(), "todo");
+        assert_eq!(profiles.offset(-1), 2);
+
+        let mut todo = ToDo::default();
+        todo.toggle_filter(ToDoCategory::Projects, "home", FilterState::Select);
+        let filters = |todo: &ToDo| todo.get_state().project_filters.len();
+        assert!(profiles.switch(profiles.offset(1), &mut todo));
+        assert_eq!(profiles.name(), "side");
+        assert_eq!(filters(&todo), 0);
+        let side = profiles.config(&config);
+        assert_eq!(side.get_todo_path(), "/home/me/side/todo.txt");
+        assert_eq!(side.get_archive_path(), None);
+        assert_eq!(side.get_done_path(), "/home/me/side/done.txt");
+
+        assert!(!profiles.switch(1, &mut todo));
+        assert!(profiles.switch(0, &mut todo));
+        assert_eq!(filters(&todo), 1);
+        let main = profiles.config(&config);
+        assert_eq!(
+            main.get_archive_path().as_deref(),
+            Some("/home/me/archive.txt")
+        );
+    }
+
+    #[test]
+    fn send() -> ToDoRes<()> {
+        let dir = std::env::temp_dir().join(format!("todotxt-tui-profiles-send-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let work = dir.join("work.txt");
+        fs::write(&work, "call boss").unwrap();
+        let config = Config::load_from_buffer(
+            format!(
+                "todo_path = \"{}\"\n[profiles]\nwork = \"{}\"",
+                dir.join("todo.txt").display(),
+                work.display()
+            )
+            .as_bytes(),
+        );
+        let profiles = Profiles::new(&config);
+        assert_eq!(profiles.others(), ["work"]);
+        profiles.send("work", &[String::from("(A) write report +work")])?;
+        assert_eq!(
+            fs::read_to_string(&work).unwrap(),
+            "call boss\n(A) write report +work\n"
+        );
+        assert_eq!(
+            profiles.send("todo", &[]),
+            Err(ToDoError::UnknownProfile(String::from("todo")))
+        );
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        #[cfg(unix)]
+        {
+            // The tasks go to the instance editing the file, the parent of the tests runs.
+            let signal_path = dir.join("signals");
+            let parent = unsafe { libc::getppid() };
+            fs::write(
+                dir.join(".work.txt.lock"),
+                format!("{parent}\n{}\n", signal_path.display()),
+            )
+            .unwrap();
+            profiles.send("work", &[String::from("buy milk")])?;
+            assert_eq!(
+                fs::read_to_string(&work).unwrap(),
+                "call boss\n(A) write report +work\n"
+            );
+            assert_eq!(fs::read_to_string(&signal_path).unwrap(), "add buy milk\n");
+        }
+        fs::remove_dir_all(dir).unwrap();
+        Ok(())
+    }
+}
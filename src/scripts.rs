@@ -0,0 +1,399 @@
+//! Lua scripts changing the todo list, e.g. moving all errands due today to the top.
+//!
+//! Scripts are the `*.lua` files of the scripts directory loaded at startup. A script
+//! is run by its key of `script_keybind` or by the `run` command and uses the global
+//! `todo` table:
+//!
+//! - `todo.tasks([query])`: The pending tasks matching the query, see [`TaskQuery`],
+//!   in the order of the file. A task is a table with `id`, its position counted from 1,
+//!   `line`, `subject`, `priority`, `due`, `projects`, `contexts` and `hashtags`.
+//! - `todo.add(line)`: Adds a new task.
+//! - `todo.set(task, line)`: Replaces the task with a task parsed from the line.
+//! - `todo.complete(task)` and `todo.remove(task)`: Completes or removes the task.
+//! - `todo.move(task, position)`: Moves the task to the position counted from 1.
+//! - `todo.filter([query])`: Sets the query of the filter bar, `nil` clears it.
+//! - `todo.message(text)`: Shows the text in the status bar.
+//!
+//! A task is given by its table or by its position. Every change goes through
+//! [`ToDo::apply`], so a task table refers to the same task after other tasks were moved.
+//!
+//! Scripts run on the UI thread, a script running longer than [`SCRIPT_TIMEOUT`] is
+//! stopped with an error, so a script stuck in a loop cannot freeze the application.
+
+use crate::{
+    config::{parse_key_name, Config},
+    status,
+    todo::{Action, TaskId, TaskQuery, ToDo, ToDoData},
+    ToDoError, ToDoRes,
+};
+use crossterm::event::KeyCode;
+use mlua::{HookTriggers, Lua, Table, Value};
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    fs,
+    io::ErrorKind,
+    path::Path,
+    time::{Duration, Instant},
+};
+use todo_txt::Task;
+
+/// The longest time a script may run before it is stopped.
+pub const SCRIPT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// The number of Lua instructions between checks of the running time of a script.
+const TIMEOUT_CHECK_INSTRUCTIONS: u32 = 10_000;
+
+/// The scripts loaded from the scripts directory and the keys running them.
+#[derive(Default)]
+pub struct Scripts {
+    sources: BTreeMap<String, String>,
+    keys: Vec<(KeyCode, String)>,
+}
+
+impl Scripts {
+    /// Loads the scripts from the `scripts_path` directory and binds them to the keys
+    /// of `script_keybind`. A script is named by its file without the `.lua` extension.
+    /// A missing directory has no scripts, keys that cannot be parsed or are bound
+    /// to unknown scripts are logged and left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration with the directory and the keys.
+    pub fn new(config: &Config) -> Self {
+        let mut scripts = Self::load(Path::new(&config.get_scripts_path()));
+        for (key, name) in config.get_script_keybind() {
+            match (parse_key_name(&key), scripts.sources.contains_key(&name)) {
+                (Some(code), true) => scripts.keys.push((code, name)),
+                (None, _) => log::warn!("Key '{key}' of script '{name}' is invalid"),
+                (_, false) => log::warn!("Script '{name}' bound to '{key}' does not exist"),
+            }
+        }
+        scripts
+    }
+
+    /// Loads the `*.lua` files of the directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory of the scripts.
+    fn load(dir: &Path) -> Self {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                if e.kind() != ErrorKind::NotFound {
+                    log::error!("Cannot read the scripts in {}: {}", dir.display(), e);
+                }
+                return Self::default();
+            }
+        };
+        let mut sources = BTreeMap::new();
+        for path in entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+        {
+            let name = match (path.extension(), path.file_stem()) {
+                (Some(extension), Some(name)) if extension == "lua" => name.to_string_lossy(),
+                _ => continue,
+            };
+            match fs::read_to_string(&path) {
+                Ok(source) => {
+                    sources.insert(name.to_string(), source);
+                }
+                Err(e) => log::error!("Cannot read the script {}: {}", path.display(), e),
+            }
+        }
+        log::info!("Loaded {} scripts from {}", sources.len(), dir.display());
+        Self {
+            sources,
+            keys: Vec::new(),
+        }
+    }
+
+    /// Gets the names of the scripts in alphabetical order.
+    pub fn names(&self) -> Vec<&str> {
+        self.sources.keys().map(String::as_str).collect()
+    }
+
+    /// Gets the name of the script bound to the key.
+    pub fn bound(&self, key: &KeyCode) -> Option<&str> {
+        self.keys
+            .iter()
+            .find(|(code, _)| code == key)
+            .map(|(_, name)| name.as_str())
+    }
+
+    /// Runs the script on the todo list. Changes made before an error are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the script.
+    /// * `todo` - The todo list the script works with.
+    ///
+    /// # Returns
+    ///
+    /// An error if the script does not exist, fails or runs longer than [`SCRIPT_TIMEOUT`].
+    pub fn run(&self, name: &str, todo: &mut ToDo) -> ToDoRes<()> {
+        self.run_with_timeout(name, todo, SCRIPT_TIMEOUT)
+    }
+
+    fn run_with_timeout(&self, name: &str, todo: &mut ToDo, timeout: Duration) -> ToDoRes<()> {
+        let source = self
+            .sources
+            .get(name)
+            .ok_or_else(|| ToDoError::UnknownScript(name.to_string()))?;
+        run_script(name, source, todo, timeout).map_err(|e| {
+            // The first line is the message, a stack traceback follows.
+            let message = e.to_string();
+            let message = message.lines().next().unwrap_or_default();
+            ToDoError::Script(name.to_string(), message.to_string())
+        })
+    }
+}
+
+/// Runs the source with the `todo` table bound to the todo list, the script is stopped
+/// with an error after the timeout.
+fn run_script(name: &str, source: &str, todo: &mut ToDo, timeout: Duration) -> mlua::Result<()> {
+    let lua = Lua::new();
+    let start = Instant::now();
+    lua.set_hook(
+        HookTriggers::new().every_nth_instruction(TIMEOUT_CHECK_INSTRUCTIONS),
+        move |_, _| match start.elapsed() > timeout {
+            true => Err(runtime_error(format!(
+                "Script ran longer than {} ms",
+                timeout.as_millis()
+            ))),
+            false => Ok(()),
+        },
+    );
+    let todo = RefCell::new(todo);
+    let apply = |action: Action| todo.borrow_mut().apply(action).map_err(runtime_error);
+    lua.scope(|scope| {
+        let api = lua.create_table()?;
+        api.set(
+            "tasks",
+            scope.create_function(|lua, query: Option<String>| {
+                let query = query
+                    .filter(|query| !query.trim().is_empty())
+                    .map(|query| query.parse::<TaskQuery>())
+                    .transpose()
+                    .map_err(runtime_error)?;
+                let todo = todo.borrow();
+                let tasks = match &query {
+                    Some(query) => todo.query_tasks(ToDoData::Pending, query),
+                    None => ToDoData::Pending
+                        .get_data(&todo)
+                        .iter()
+                        .enumerate()
+                        .collect(),
+                };
+                let tables = tasks
+                    .into_iter()
+                    .map(|(index, task)| task_table(lua, index, task))
+                    .collect::<mlua::Result<Vec<Table>>>()?;
+                lua.create_sequence_from(tables)
+            })?,
+        )?;
+        api.set(
+            "add",
+            scope.create_function(|_, line: String| apply(Action::NewTask { task: line }))?,
+        )?;
+        api.set(
+            "set",
+            scope.create_function(|_, (task, line): (Value, String)| {
+                let task = task_id(&todo.borrow(), task)?;
+                apply(Action::UpdateTask {
+                    data: ToDoData::Pending,
+                    task,
+                    line,
+                })
+            })?,
+        )?;
+        api.set(
+            "complete",
+            scope.create_function(|_, task: Value| {
+                let task = task_id(&todo.borrow(), task)?;
+                apply(Action::CompleteTask {
+                    data: ToDoData::Pending,
+                    task,
+                })
+            })?,
+        )?;
+        api.set(
+            "remove",
+            scope.create_function(|_, task: Value| {
+                let task = task_id(&todo.borrow(), task)?;
+                apply(Action::RemoveTask {
+                    data: ToDoData::Pending,
+                    task,
+                })
+            })?,
+        )?;
+        api.set(
+            "move",
+            scope.create_function(|_, (task, position): (Value, i64)| {
+                let from = task_id(&todo.borrow(), task)?;
+                let to = task_id(&todo.borrow(), Value::Integer(position))?;
+                apply(Action::ReorderTask {
+                    data: ToDoData::Pending,
+                    from,
+                    to,
+                })
+            })?,
+        )?;
+        api.set(
+            "filter",
+            scope.create_function(|_, query: Option<String>| {
+                if let Some(query) = &query {
+                    query.parse::<TaskQuery>().map_err(runtime_error)?;
+                }
+                apply(Action::SetFilterBar { query })
+            })?,
+        )?;
+        api.set(
+            "message",
+            scope.create_function(|_, text: String| {
+                status::info(text);
+                Ok(())
+            })?,
+        )?;
+        lua.globals().set("todo", api)?;
+        lua.load(source).set_name(name).exec()
+    })
+}
+
+/// Creates the table of the task given to scripts.
+fn task_table<'lua>(lua: &'lua Lua, index: usize, task: &Task) -> mlua::Result<Table<'lua>> {
+    let table = lua.create_table()?;
+    table.set("id", index + 1)?;
+    table.set("line", task.to_string())?;
+    table.set("subject", task.subject.clone())?;
+    table.set(
+        "priority",
+        (!task.priority.is_lowest()).then(|| task.priority.to_string()),
+    )?;
+    table.set("due", task.due_date.map(|date| date.to_string()))?;
+    table.set("projects", task.projects().to_vec())?;
+    table.set("contexts", task.contexts().to_vec())?;
+    table.set("hashtags", task.hashtags.clone())?;
+    Ok(table)
+}
+
+/// Gets the identity of the pending task given by its table or by its position.
+fn task_id(todo: &ToDo, task: Value) -> mlua::Result<TaskId> {
+    match task {
+        Value::Table(table) => Ok(TaskId {
+            index: table.get::<_, usize>("id")?.saturating_sub(1),
+            task: table.get("line")?,
+        }),
+        Value::Integer(position) => {
+            let tasks = ToDoData::Pending.get_data(todo);
+            usize::try_from(position - 1)
+                .ok()
+                .and_then(|index| Some(TaskId::new(index, tasks.get(index)?)))
+                .ok_or_else(|| runtime_error(format!("No task at position {position}")))
+        }
+        value => Err(runtime_error(format!(
+            "Expected a task or a position, got {}",
+            value.type_name()
+        ))),
+    }
+}
+
+fn runtime_error(e: impl ToString) -> mlua::Error {
+    mlua::Error::RuntimeError(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scripts(name: &str, source: &str) -> Scripts {
+        Scripts {
+            sources: BTreeMap::from([(name.to_string(), source.to_string())]),
+            keys: Vec::new(),
+        }
+    }
+
+    fn subjects(todo: &ToDo) -> Vec<&str> {
+        todo.pending
+            .iter()
+            .map(|task| task.subject.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn run() -> ToDoRes<()> {
+        let mut todo = ToDo::default();
+        for task in [
+            "call mom",
+            "buy milk @errands due:2000-01-01",
+            "water plants",
+            "post letter @errands due:2000-01-02",
+            "buy shoes @errands due:2999-01-01",
+        ] {
+            todo.new_task(task).unwrap();
+        }
+        let errands = "for i, task in ipairs(todo.tasks('@errands due:..today')) do \
+                           todo.move(task, i) \
+                       end";
+        scripts("errands", errands).run("errands", &mut todo)?;
+        assert_eq!(
+            subjects(&todo),
+            [
+                "buy milk @errands",
+                "post letter @errands",
+                "call mom",
+                "water plants",
+                "buy shoes @errands"
+            ]
+        );
+
+        let source = "local task = todo.tasks('water')[1] \
+                      todo.set(task, '(A) ' .. task.subject) \
+                      todo.complete(1) \
+                      todo.add('new task') \
+                      todo.filter('!A')";
+        scripts("edit", source).run("edit", &mut todo)?;
+        assert_eq!(todo.pending.len(), 5);
+        assert_eq!(todo.done.len(), 1);
+        assert_eq!(todo.pending[2].subject, "water plants");
+        assert!(!todo.pending[2].priority.is_lowest());
+        assert_eq!(
+            todo.filter_bar().map(|query| query.to_string()),
+            Some(String::from("!A"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn errors() {
+        let mut todo = ToDo::default();
+        let scripts = scripts("move", "todo.move(3, 1)");
+        assert_eq!(
+            scripts.run("other", &mut todo),
+            Err(ToDoError::UnknownScript(String::from("other")))
+        );
+        match scripts.run("move", &mut todo) {
+            Err(ToDoError::Script(name, message)) => {
+                assert_eq!(name, "move");
+                assert!(message.contains("No task at position 3"), "{message}");
+            }
+            result => panic!("Unexpected result {result:?}"),
+        }
+    }
+
+    #[test]
+    fn timeout() {
+        let mut todo = ToDo::default();
+        let scripts = scripts("loop", "todo.add('task') while true do end");
+        match scripts.run_with_timeout("loop", &mut todo, Duration::from_millis(100)) {
+            Err(ToDoError::Script(name, message)) => {
+                assert_eq!(name, "loop");
+                assert!(message.contains("longer than 100 ms"), "{message}");
+            }
+            result => panic!("Unexpected result {result:?}"),
+        }
+        assert_eq!(todo.pending.len(), 1);
+    }
+}
@@ -0,0 +1,251 @@
+use crate::todo::{Action, TaskId, ToDo, ToDoData};
+use std::{
+    fmt::{self, Display},
+    fs,
+    io::{self, ErrorKind},
+    path::PathBuf,
+};
+
+/// The tag identifying a task for other programs, e.g. `id:backup`.
+pub const ID_TAG: &str = "id";
+
+/// A request of another program to change the todo list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Signal {
+    /// Completes the pending task with the `id:` tag, e.g. `done backup`.
+    Done(String),
+    /// Adds the task to the todo list, e.g. `add call mom due:tomorrow`.
+    Add(String),
+}
+
+impl Signal {
+    /// Parses the signal from a line of the signal file.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the line is not a known signal.
+    pub fn parse(line: &str) -> Option<Self> {
+        let (command, id) = line.trim().split_once(char::is_whitespace)?;
+        match (command, id.trim()) {
+            ("done", id) if !id.is_empty() && !id.contains(char::is_whitespace) => {
+                Some(Self::Done(id.to_string()))
+            }
+            ("add", task) if !task.is_empty() => Some(Self::Add(task.to_string())),
+            _ => None,
+        }
+    }
+
+    /// Applies the signal to the todo list the same way as the user interface does,
+    /// so a recurring task is followed by its next occurrence.
+    ///
+    /// # Arguments
+    ///
+    /// * `todo` - The changed todo list.
+    ///
+    /// # Returns
+    ///
+    /// The description of the change, e.g. `Completed: back up the laptop`,
+    /// or of the reason nothing was changed.
+    pub fn apply(&self, todo: &mut ToDo) -> String {
+        match self {
+            Self::Done(id) => {
+                let Some((index, task)) = todo
+                    .pending
+                    .iter()
+                    .enumerate()
+                    .find(|(_, task)| task.tags.get(ID_TAG) == Some(id))
+                else {
+                    return format!("No pending task has {ID_TAG}:{id}");
+                };
+                let subject = task.subject.clone();
+                let action = Action::CompleteTask {
+                    data: ToDoData::Pending,
+                    task: TaskId::new(index, task),
+                };
+                match todo.apply(action) {
+                    Ok(()) => format!("Completed: {subject}"),
+                    Err(e) => format!("Cannot complete {subject}: {e}"),
+                }
+            }
+            Self::Add(task) => match todo.apply(Action::NewTask { task: task.clone() }) {
+                Ok(()) => format!("Added: {task}"),
+                Err(e) => format!("Cannot add {task}: {e}"),
+            },
+        }
+    }
+}
+
+impl Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Done(id) => write!(f, "done {id}"),
+            Self::Add(task) => write!(f, "add {task}"),
+        }
+    }
+}
+
+/// The file other programs append signals to, e.g. `echo "done backup" >> signals.txt`
+/// when a backup job succeeds. Every line is a signal, see [`Signal`].
+pub struct Signals {
+    path: PathBuf,
+    /// The length of the taken file read by the last call, `None` if nothing was taken.
+    read: Option<usize>,
+}
+
+impl Signals {
+    /// Creates the signals read from the file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the signal file.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            read: None,
+        }
+    }
+
+    /// Takes the signals appended since the last call. The file is moved to a taken file
+    /// before it is read, a signal appended after the move goes to a new file. A program
+    /// that opened the file before the move still appends to the taken file, so the taken
+    /// file is kept until the next call, which reads what was appended to it meanwhile.
+    ///
+    /// # Returns
+    ///
+    /// The signals and the lines that are not a known signal. A missing file has none.
+    pub fn take(&mut self) -> io::Result<(Vec<Signal>, Vec<String>)> {
+        let taken = self.path.with_extension("taken");
+        let mut content = String::new();
+        // A taken file not read by this instance was read before it quit.
+        let read = self.read.take().unwrap_or(usize::MAX);
+        match fs::read_to_string(&taken) {
+            Ok(late) => {
+                content += late.get(read..).unwrap_or_default();
+                if !content.is_empty() && !content.ends_with('\n') {
+                    content.push('\n');
+                }
+                fs::remove_file(&taken)?;
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        match fs::rename(&self.path, &taken) {
+            Ok(()) => {
+                let new = fs::read_to_string(&taken)?;
+                // A line still being written is read with the rest of it by the next call.
+                let end = new.rfind('\n').map_or(0, |index| index + 1);
+                self.read = Some(end);
+                content += &new[..end];
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        let mut signals = Vec::new();
+        let mut unknown = Vec::new();
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            match Signal::parse(line) {
+                Some(signal) => signals.push(signal),
+                None => unknown.push(line.to_string()),
+            }
+        }
+        Ok((signals, unknown))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env::temp_dir, io::Write, process};
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            Signal::parse("done backup"),
+            Some(Signal::Done(String::from("backup")))
+        );
+        assert_eq!(
+            Signal::parse("  done   backup \n"),
+            Some(Signal::Done(String::from("backup")))
+        );
+        assert_eq!(Signal::parse("done"), None);
+        assert_eq!(Signal::parse("done two ids"), None);
+        assert_eq!(Signal::parse("remove backup"), None);
+        assert_eq!(
+            Signal::parse("add call mom +family"),
+            Some(Signal::Add(String::from("call mom +family")))
+        );
+        assert_eq!(Signal::parse("add   "), None);
+        let signal = Signal::Add(String::from("call mom"));
+        assert_eq!(Signal::parse(&signal.to_string()), Some(signal));
+    }
+
+    #[test]
+    fn complete() -> io::Result<()> {
+        let dir = temp_dir().join(format!("todotxt-tui-signals-test-{}", process::id()));
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("signals.txt");
+        fs::write(&path, "done backup\n\nreboot\ndone laundry\n")?;
+        let (signals, unknown) = Signals::new(&path).take()?;
+        assert!(!path.exists());
+        assert_eq!(unknown, ["reboot"]);
+        assert_eq!(Signals::new(&path).take()?, (Vec::new(), Vec::new()));
+        fs::remove_dir_all(&dir)?;
+
+        let mut todo = ToDo::default();
+        todo.new_task("back up the laptop id:backup rec:1w due:2024-07-01")
+            .unwrap();
+        todo.new_task("water plants").unwrap();
+        let changes: Vec<String> = signals
+            .iter()
+            .map(|signal| signal.apply(&mut todo))
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                "Completed: back up the laptop",
+                "No pending task has id:laundry"
+            ]
+        );
+        assert_eq!(todo.done.len(), 1);
+        // The recurring task is followed by its next occurrence with the same id.
+        assert_eq!(todo.pending.len(), 2);
+        assert!(todo
+            .pending
+            .iter()
+            .any(|task| task.tags.get(ID_TAG).map(String::as_str) == Some("backup")));
+        Ok(())
+    }
+
+    #[test]
+    fn late_signals() -> io::Result<()> {
+        let dir = temp_dir().join(format!("todotxt-tui-late-signals-test-{}", process::id()));
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("signals.txt");
+        let taken = path.with_extension("taken");
+        let mut signals = Signals::new(&path);
+        fs::write(&path, "done backup\ndone laun")?;
+        // The program appending the signal opened the file before it was taken.
+        let mut writer = fs::OpenOptions::new().append(true).open(&path)?;
+        assert_eq!(
+            signals.take()?,
+            (vec![Signal::Done(String::from("backup"))], Vec::new())
+        );
+        writer.write_all(b"dry\ndone dishes\n")?;
+        fs::write(&path, "add call mom\n")?;
+        assert_eq!(
+            signals.take()?,
+            (
+                vec![
+                    Signal::Done(String::from("laundry")),
+                    Signal::Done(String::from("dishes")),
+                    Signal::Add(String::from("call mom")),
+                ],
+                Vec::new()
+            )
+        );
+        assert_eq!(signals.take()?, (Vec::new(), Vec::new()));
+        assert!(!taken.exists());
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
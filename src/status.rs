@@ -0,0 +1,74 @@
+//! Short status messages posted by any part of the application, e.g. errors or
+//! "task added", and shown in the status bar of the user interface for a while.
+
+use std::{sync::Mutex, time::Instant};
+
+/// The messages posted since the status bar took them last.
+static MESSAGES: Mutex<Vec<StatusMessage>> = Mutex::new(Vec::new());
+
+/// The kind of a status message, errors are highlighted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatusLevel {
+    Info,
+    Error,
+}
+
+/// A message posted to the status bar.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StatusMessage {
+    pub level: StatusLevel,
+    pub text: String,
+    pub posted: Instant,
+}
+
+impl StatusMessage {
+    /// Creates a new `StatusMessage` posted now.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - The kind of the message.
+    /// * `text` - The text of the message.
+    pub fn new(level: StatusLevel, text: impl Into<String>) -> Self {
+        Self {
+            level,
+            text: text.into(),
+            posted: Instant::now(),
+        }
+    }
+}
+
+/// Posts a message to the status bar. The message is also logged, so it is not lost
+/// when the status bar is not shown.
+///
+/// # Arguments
+///
+/// * `level` - The kind of the message.
+/// * `text` - The text of the message.
+pub fn post(level: StatusLevel, text: impl Into<String>) {
+    let message = StatusMessage::new(level, text);
+    match level {
+        StatusLevel::Info => log::info!("Status: {}", message.text),
+        StatusLevel::Error => log::error!("Status: {}", message.text),
+    }
+    if let Ok(mut messages) = MESSAGES.lock() {
+        messages.push(message);
+    }
+}
+
+/// Posts an informational message, e.g. `Task added`.
+pub fn info(text: impl Into<String>) {
+    post(StatusLevel::Info, text)
+}
+
+/// Posts an error message.
+pub fn error(text: impl Into<String>) {
+    post(StatusLevel::Error, text)
+}
+
+/// Takes the messages posted since the last call, the oldest first.
+pub fn take() -> Vec<StatusMessage> {
+    MESSAGES
+        .lock()
+        .map(|mut messages| std::mem::take(&mut *messages))
+        .unwrap_or_default()
+}
@@ -0,0 +1,11 @@
+//! Styles of tasks and categories used by templates and task lists.
+
+mod colors;
+mod styles;
+mod text_modifier;
+mod text_style;
+
+pub use self::colors::opt_color;
+pub use self::styles::{Styles, StylesValue};
+pub use self::text_modifier::TextModifier;
+pub use self::text_style::{TextStyle, TextStyleList};
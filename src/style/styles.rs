@@ -0,0 +1,227 @@
+use std::{collections::HashMap, str::FromStr};
+
+use super::{text_style::TextStyleList, TextStyle};
+use todo_txt::Task;
+use tui::style::{Color, Style};
+
+use crate::error::{ToDoError, ToDoRes};
+
+pub struct Styles {
+    pub priority_style: TextStyleList,
+    pub priority_labels: HashMap<String, String>,
+    pub projects_style: TextStyle,
+    pub contexts_style: TextStyle,
+    pub hashtags_style: TextStyle,
+    pub category_style: TextStyle,
+    pub category_select_style: TextStyle,
+    pub category_remove_style: TextStyle,
+    pub custom_category_style: HashMap<String, TextStyle>,
+    pub named_styles: HashMap<String, TextStyle>,
+}
+
+#[derive(Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum StylesValue {
+    Const(Style),
+    CustomCategory,
+    Priority,
+}
+
+impl StylesValue {
+    pub fn get_style(&self, task: &Task, styles: &Styles) -> Style {
+        use StylesValue::*;
+        match self {
+            Const(style) => style.to_owned(),
+            CustomCategory => {
+                let mut text_style = TextStyle::default();
+                let mut process_projects = |prefix: &str, data: &[String]| {
+                    data.iter().for_each(|category: &String| {
+                        if let Some(style) = styles
+                            .custom_category_style
+                            .get(&(prefix.to_string() + category))
+                        {
+                            text_style = text_style.combine(style);
+                        }
+                    });
+                };
+                process_projects("+", task.projects());
+                process_projects("@", task.contexts());
+                process_projects("#", &task.hashtags);
+
+                text_style.get_style()
+            }
+            Priority => styles
+                .priority_style
+                .get_style(task.priority.clone().into()),
+        }
+    }
+}
+
+impl Default for Styles {
+    fn default() -> Self {
+        Self {
+            priority_style: TextStyleList::default(),
+            priority_labels: HashMap::new(),
+            projects_style: TextStyle::default(),
+            contexts_style: TextStyle::default(),
+            hashtags_style: TextStyle::default(),
+            category_style: TextStyle::default(),
+            category_select_style: TextStyle::default().fg(Color::Green),
+            category_remove_style: TextStyle::default().fg(Color::Red),
+            custom_category_style: HashMap::from([(
+                String::from("+todo-tui"),
+                TextStyle::default().fg(Color::LightBlue),
+            )]),
+            named_styles: HashMap::new(),
+        }
+    }
+}
+
+impl Styles {
+    /// Gets the label of the priority from `priority_labels`.
+    ///
+    /// # Returns
+    ///
+    /// The label, the letter of the priority if it has no label
+    /// or `None` if the task does not have a priority.
+    pub fn priority_label(&self, priority: &todo_txt::Priority) -> Option<String> {
+        if priority.is_lowest() {
+            return None;
+        }
+        let letter = priority.to_string();
+        Some(self.priority_labels.get(&letter).cloned().unwrap_or(letter))
+    }
+
+    pub fn get_style_default(&self) -> StylesValue {
+        StylesValue::Const(Style::default())
+    }
+
+    pub fn get_style_from_style(&self, style: Style) -> StylesValue {
+        StylesValue::Const(style)
+    }
+
+    pub fn get_style(&self, name: &str) -> ToDoRes<StylesValue> {
+        use StylesValue::*;
+        Ok(match name {
+            "priority" => Priority,
+            "custom_category" => CustomCategory,
+            "projects" => Const(self.projects_style.get_style()),
+            "contexts" => Const(self.contexts_style.get_style()),
+            "hashtags" => Const(self.hashtags_style.get_style()),
+            "category" => Const(self.category_style.get_style()),
+            _ => {
+                if let Some(priority) = name.strip_prefix("priority:") {
+                    let priority = priority.to_uppercase();
+                    if priority.len() != 1 || !priority.chars().all(|c| c.is_ascii_uppercase()) {
+                        return Err(ToDoError::ParseTextStyle(name.to_string()));
+                    }
+                    return Ok(Const(
+                        match self.priority_style.get_style_from_str(&priority) {
+                            Some(style) => style.get_style(),
+                            None => Style::default(),
+                        },
+                    ));
+                } else if name.starts_with("custom_category:") {
+                    if let Some(custom_category) = name.get("custom_category:".len()..) {
+                        if let Some(custom_category) =
+                            self.custom_category_style.get(custom_category)
+                        {
+                            return Ok(Const(custom_category.get_style()));
+                        }
+                    }
+                }
+                Const(self.get_text_style(name)?.get_style())
+            }
+        })
+    }
+
+    /// Parses the style from string where every word is either a name of
+    /// a named style or a style token (color, background or modifier).
+    fn get_text_style(&self, s: &str) -> ToDoRes<TextStyle> {
+        s.split_whitespace()
+            .try_fold(TextStyle::default(), |style, word| {
+                Ok(style.combine(&match self.named_styles.get(word) {
+                    Some(named) => *named,
+                    None => TextStyle::from_str(word)?,
+                }))
+            })
+    }
+
+    pub fn get_category_style(&self, category: &str) -> TextStyle {
+        match self.custom_category_style.get(category) {
+            Some(style) => *style,
+            None => self.get_category_base_style(category),
+        }
+    }
+
+    /// Gets the style of the category kind given by the first character of the category,
+    /// the style of the category itself is not applied.
+    pub fn get_category_base_style(&self, category: &str) -> TextStyle {
+        match category.chars().next() {
+            Some('+') => self.projects_style,
+            Some('@') => self.contexts_style,
+            Some('#') => self.hashtags_style,
+            _ => self.category_style,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use tui::style::Modifier;
+
+    #[test]
+    fn get_style() -> ToDoRes<()> {
+        let task = Task::from_str("(A) Task name +project #hashtag").unwrap();
+        println!("{:#?}", task);
+        let styles = Styles::default();
+        assert_eq!(
+            Style::default(),
+            styles.get_style("")?.get_style(&task, &styles)
+        );
+        assert!(styles.get_style("Unknown").is_err());
+        assert_eq!(
+            Style::default(),
+            styles.get_style("hashtags")?.get_style(&task, &styles)
+        );
+        assert_eq!(
+            Style::default().fg(Color::Red),
+            styles.get_style("priority:A")?.get_style(&task, &styles)
+        );
+        assert_eq!(
+            Style::default().fg(Color::Red),
+            styles.get_style("priority")?.get_style(&task, &styles)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_named_style() -> ToDoRes<()> {
+        let task = Task::from_str("Task name").unwrap();
+        let mut styles = Styles::default();
+        styles
+            .named_styles
+            .insert(String::from("muted"), TextStyle::from_str("gray italic")?);
+        assert_eq!(
+            Style::default()
+                .fg(Color::Gray)
+                .add_modifier(Modifier::ITALIC),
+            styles.get_style("muted")?.get_style(&task, &styles)
+        );
+        assert_eq!(
+            Style::default()
+                .fg(Color::Gray)
+                .bg(Color::Blue)
+                .add_modifier(Modifier::BOLD),
+            styles
+                .get_style("muted ^blue bold")?
+                .get_style(&task, &styles)
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+use std::str::FromStr;
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use tui::style::Modifier;
+
+use crate::ToDoError;
+
+/// Serialization and deserialization support for the TUI text modifier type.
+///
+/// This enum is used to serialize and deserialize TUI `Modifier` objects.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum TextModifier {
+    Bold,
+    Italic,
+    Underlined,
+    Dim,
+    Strikethrough,
+}
+
+// TODO coverage
+impl FromStr for TextModifier {
+    type Err = ToDoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "bold" => Ok(Self::Bold),
+            "italic" => Ok(Self::Italic),
+            "underline" | "underlined" => Ok(Self::Underlined),
+            "dim" => Ok(Self::Dim),
+            "strikethrough" => Ok(Self::Strikethrough),
+            _ => Err(ToDoError::ParseTextModifier(s.to_string())),
+        }
+    }
+}
+
+impl From<TextModifier> for Modifier {
+    fn from(val: TextModifier) -> Self {
+        use TextModifier::*;
+        match val {
+            Bold => Modifier::BOLD,
+            Italic => Modifier::ITALIC,
+            Underlined => Modifier::UNDERLINED,
+            Dim => Modifier::DIM,
+            Strikethrough => Modifier::CROSSED_OUT,
+        }
+    }
+}
+
+/// A set of text modifiers written as a list of their names, e.g. `["dim", "bold"]`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TextModifiers(u8);
+
+impl TextModifiers {
+    const ALL: [TextModifier; 5] = [
+        TextModifier::Bold,
+        TextModifier::Italic,
+        TextModifier::Underlined,
+        TextModifier::Dim,
+        TextModifier::Strikethrough,
+    ];
+
+    /// Adds the modifier to the set.
+    pub fn insert(&mut self, modifier: TextModifier) {
+        self.0 |= 1 << modifier as u8;
+    }
+
+    /// Gets the set with the modifiers of both sets.
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Gets the modifiers of the set.
+    pub fn iter(self) -> impl Iterator<Item = TextModifier> {
+        Self::ALL
+            .into_iter()
+            .filter(move |modifier| self.0 & (1 << *modifier as u8) != 0)
+    }
+}
+
+impl From<TextModifiers> for Modifier {
+    fn from(val: TextModifiers) -> Self {
+        val.iter().fold(Modifier::empty(), |modifiers, modifier| {
+            modifiers | modifier.into()
+        })
+    }
+}
+
+impl Serialize for TextModifiers {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+/// The names of the modifiers are not case sensitive, as in templates.
+impl<'de> Deserialize<'de> for TextModifiers {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut modifiers = Self::default();
+        for name in Vec::<String>::deserialize(deserializer)? {
+            modifiers.insert(TextModifier::from_str(&name).map_err(D::Error::custom)?);
+        }
+        Ok(modifiers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_text_modifier() {
+        let bold = TextModifier::Bold;
+        assert_eq!(Modifier::from(bold), Modifier::BOLD);
+
+        let italic = TextModifier::Italic;
+        assert_eq!(Modifier::from(italic), Modifier::ITALIC);
+
+        let underline = TextModifier::Underlined;
+        assert_eq!(Modifier::from(underline), Modifier::UNDERLINED);
+
+        let dim = TextModifier::Dim;
+        assert_eq!(Modifier::from(dim), Modifier::DIM);
+
+        let strikethrough = TextModifier::Strikethrough;
+        assert_eq!(Modifier::from(strikethrough), Modifier::CROSSED_OUT);
+    }
+
+    #[test]
+    fn text_modifiers() {
+        #[derive(Deserialize, Serialize)]
+        struct Style {
+            modifiers: TextModifiers,
+        }
+
+        let style: Style = toml::from_str(r#"modifiers = ["dim", "Bold"]"#).unwrap();
+        assert_eq!(
+            Modifier::from(style.modifiers),
+            Modifier::DIM | Modifier::BOLD
+        );
+        assert_eq!(
+            toml::to_string(&style).unwrap(),
+            "modifiers = [\"Bold\", \"Dim\"]\n"
+        );
+        assert!(toml::from_str::<Style>(r#"modifiers = ["blink"]"#).is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use super::colors::opt_color;
-use super::text_modifier::TextModifier;
-use crate::ToDoError;
+use super::text_modifier::{TextModifier, TextModifiers};
+use crate::{ToDoError, ToDoRes};
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, str::FromStr};
 use tui::style::{Color, Style};
@@ -9,14 +9,16 @@ use tui::style::{Color, Style};
 ///
 /// This struct defines the style for text elements, including background color, foreground color,
 /// and text modifiers.
-#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
 pub struct TextStyle {
     #[serde(default, with = "opt_color")]
     bg: Option<Color>,
     #[serde(default, with = "opt_color")]
     fg: Option<Color>,
     modifier: Option<TextModifier>,
+    /// Modifiers added to `modifier`, e.g. `modifiers = ["dim", "bold"]`.
+    #[serde(default, skip_serializing_if = "TextModifiers::is_empty")]
+    modifiers: TextModifiers,
 }
 
 const PRIORITIES: [&str; 27] = [
@@ -76,7 +78,10 @@ impl TextStyle {
     /// `false` otherwise.
     #[allow(dead_code)]
     pub fn is_some(&self) -> bool {
-        self.bg.is_some() || self.fg.is_some() || self.modifier.is_some()
+        self.bg.is_some()
+            || self.fg.is_some()
+            || self.modifier.is_some()
+            || !self.modifiers.is_empty()
     }
 
     /// Combine two text styles into a new text style.
@@ -107,6 +112,7 @@ impl TextStyle {
         if let Some(modifier) = additional.modifier {
             self.modifier = Some(modifier);
         }
+        self.modifiers = self.modifiers.union(additional.modifiers);
     }
 
     /// Get the TUI `Style` corresponding to the text style.
@@ -125,30 +131,56 @@ impl TextStyle {
         if let Some(s) = self.modifier {
             style = style.add_modifier(s.into());
         }
-        style
+        style.add_modifier(self.modifiers.into())
     }
 }
 
-// TODO doc comment
+/// Parses a color of a style word: a name, `#rrggbb` truecolor or an index
+/// of the terminal palette from 0 to 255.
+///
+/// # Returns
+///
+/// `None` if the word is not a color name, an error if it is a malformed
+/// truecolor or index.
+fn parse_color(s: &str) -> ToDoRes<Option<Color>> {
+    if let Some(hex) = s.strip_prefix('#') {
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ToDoError::ParseHexColor(s.to_string()));
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap();
+        return Ok(Some(Color::Rgb(channel(0), channel(2), channel(4))));
+    }
+    if !s.is_empty() && s.chars().all(|c| c.is_ascii_digit()) {
+        return match s.parse::<u8>() {
+            Ok(index) => Ok(Some(Color::Indexed(index))),
+            Err(_) => Err(ToDoError::ParseColorIndex(s.to_string())),
+        };
+    }
+    Ok(Color::from_str(s).ok())
+}
+
+/// Parses the style from whitespace separated words. A word is a foreground color,
+/// a background color prefixed by `bg:` or `^`, a modifier or the name of a priority.
+/// Colors are names, `#rrggbb` truecolors or indexes of the terminal palette.
 impl FromStr for TextStyle {
     type Err = ToDoError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut ret = TextStyle::default();
         for word in s.split_whitespace() {
-            if let Some(stripped) = word.strip_prefix('^') {
-                match Color::from_str(stripped) {
-                    Ok(c) => ret = ret.bg(c),
-                    Err(_) => return Err(ToDoError::ParseTextStyle(word.to_string())),
-                }
-            } else if let Ok(color) = Color::from_str(word) {
+            let invalid = || ToDoError::ParseTextStyle(word.to_string());
+            if let Some(bg) = word.strip_prefix('^').or_else(|| word.strip_prefix("bg:")) {
+                ret = ret.bg(parse_color(bg)?.ok_or_else(invalid)?);
+            } else if let Some(fg) = word.strip_prefix("fg:") {
+                ret = ret.fg(parse_color(fg)?.ok_or_else(invalid)?);
+            } else if let Some(color) = parse_color(word)? {
                 ret = ret.fg(color);
             } else if let Ok(modifier) = TextModifier::from_str(word) {
                 ret = ret.modifier(modifier);
             } else {
                 match TextStyleList::default().0.get(word) {
                     Some(style) => ret = ret.combine(style),
-                    None => return Err(ToDoError::ParseTextStyle(word.to_string())),
+                    None => return Err(invalid()),
                 }
             }
         }
@@ -160,8 +192,7 @@ impl FromStr for TextStyle {
 /// Represents a list of text styles for priorities.
 ///
 /// This struct maintains a list of text styles for different priority levels.
-#[derive(Serialize, Deserialize, Clone, Debug)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct TextStyleList(HashMap<String, TextStyle>);
 
 impl TextStyleList {
@@ -334,6 +365,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn from_str_extended() -> ToDoRes<()> {
+        assert_eq!(
+            TextStyle::from_str("#ff8800 bg:blue")?,
+            TextStyle::default()
+                .fg(Color::Rgb(255, 136, 0))
+                .bg(Color::Blue)
+        );
+        assert_eq!(
+            TextStyle::from_str("fg:208 bg:#00FF7f")?,
+            TextStyle::default()
+                .fg(Color::Indexed(208))
+                .bg(Color::Rgb(0, 255, 127))
+        );
+        assert_eq!(
+            TextStyle::from_str("^0")?,
+            TextStyle::default().bg(Color::Indexed(0))
+        );
+        assert_eq!(
+            TextStyle::from_str("dim")?,
+            TextStyle::default().modifier(TextModifier::Dim)
+        );
+        assert_eq!(
+            TextStyle::from_str("strikethrough")?,
+            TextStyle::default().modifier(TextModifier::Strikethrough)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn from_str_extended_err() {
+        assert_eq!(
+            TextStyle::from_str("#ff88").unwrap_err(),
+            ToDoError::ParseHexColor("#ff88".to_string())
+        );
+        assert_eq!(
+            TextStyle::from_str("bg:#gg0000").unwrap_err(),
+            ToDoError::ParseHexColor("#gg0000".to_string())
+        );
+        assert_eq!(
+            TextStyle::from_str("256").unwrap_err(),
+            ToDoError::ParseColorIndex("256".to_string())
+        );
+        assert_eq!(
+            TextStyle::from_str("bg:").unwrap_err(),
+            ToDoError::ParseTextStyle("bg:".to_string())
+        );
+        assert_eq!(
+            TextStyle::from_str("fg:bold").unwrap_err(),
+            ToDoError::ParseTextStyle("fg:bold".to_string())
+        );
+    }
+
     #[test]
     fn text_style_list_from_str() -> ToDoRes<()> {
         let mut expected = HashMap::<String, TextStyle>::new();
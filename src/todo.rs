@@ -1,25 +1,157 @@
+pub mod action;
+pub mod activity;
+pub mod agenda;
+pub mod aging;
+pub mod alarm;
+pub mod archive;
 pub mod autocomplete;
+pub mod bulk;
+pub mod category_index;
 pub mod category_list;
+pub mod category_tree;
+pub mod change;
+pub mod countdown;
+pub mod date_expr;
+pub mod effort;
+pub mod escalation;
+pub mod export;
+pub mod filter_bar;
+pub mod forecast;
+pub mod history;
+pub mod import;
+pub mod new_categories;
 pub mod parser;
+pub mod planner;
+pub mod preprocess;
+pub mod project_info;
+pub mod recurrence;
+pub mod reminder;
+pub mod review;
+pub mod routine;
+pub mod scratch;
+pub mod search;
+pub mod sort;
 pub mod task_list;
+pub mod task_meta;
+pub mod task_query;
+pub mod task_rule;
+pub mod timer;
+pub mod todo_config;
 pub mod todo_state;
+pub mod trash;
+pub mod urgency;
+pub mod working_hours;
 
 pub use self::{
-    autocomplete::autocomplete, category_list::CategoryList, parser::Parser, task_list::TaskList,
+    action::{Action, TaskId},
+    activity::{Activity, ActivityKind},
+    agenda::AgendaGroup,
+    aging::PriorityAging,
+    autocomplete::{autocomplete, CategoryToken},
+    category_index::CategoryIndex,
+    category_list::CategoryList,
+    category_tree::TreeNode,
+    change::Change,
+    date_expr::{expand_dates, DateExpr},
+    effort::EffortRow,
+    escalation::EscalationRule,
+    export::ExportFormat,
+    forecast::Forecast,
+    history::{History, HistoryCache},
+    import::ImportFormat,
+    new_categories::NewCategory,
+    parser::Parser,
+    planner::{week_start, DateTag, PlannedTask},
+    project_info::{ProjectInfo, ProjectRisk},
+    recurrence::Recurrence,
+    review::{Review, ReviewAction},
+    routine::Routine,
+    scratch::{is_scratch, scratch_line},
+    sort::TaskSort,
+    task_list::TaskList,
+    task_meta::{MetaStore, TaskMeta},
+    task_query::TaskQuery,
+    task_rule::TaskRule,
+    timer::Timer,
+    todo_config::ToDoConfig,
     todo_state::*,
+    trash::TrashedTask,
+    urgency::{UrgencyCache, UrgencyWeights},
+    working_hours::ContextHours,
 };
 
-use crate::config::{Config, Styles, ToDoConfig};
-use chrono::Utc;
-use std::{collections::btree_set::BTreeSet, str::FromStr};
-use todo_txt::Task;
+use crate::clock::Clock;
+use crate::style::Styles;
+use chrono::{Duration, NaiveDate};
+use std::{
+    borrow::Cow,
+    cell::{Ref, RefCell},
+    cmp::Ordering,
+    collections::{btree_set::BTreeSet, BTreeMap, HashMap},
+    str::FromStr,
+    sync::mpsc::Sender,
+};
+use todo_txt::{Priority, Task};
+
+/// Parses the todo.txt line of a task.
+///
+/// The only date of a completed task is its completion date, e.g. `x 2023-05-03 call mom`,
+/// the todo.txt parser reads it as the creation date. Values of `due:` and `t:` that
+/// are not dates are kept as tags, the parser drops them.
+///
+/// # Arguments
+///
+/// * `line` - The todo.txt line of the task.
+pub fn parse_task(line: &str) -> Result<Task, todo_txt::Error> {
+    let mut task = Task::from_str(line)?;
+    if task.finished && task.finish_date.is_none() {
+        task.finish_date = task.create_date.take();
+    }
+    let (mut due, mut threshold) = (None, None);
+    for (key, value) in line
+        .split_whitespace()
+        .filter_map(|word| word.split_once(':'))
+        .filter(|(_, value)| !value.is_empty() && !value.starts_with('/'))
+    {
+        match key {
+            "due" => due = Some(value),
+            "t" => threshold = Some(value),
+            _ => {}
+        }
+    }
+    if let (Some(due), None) = (due, task.due_date) {
+        task.tags.insert(String::from("due"), due.to_string());
+    }
+    if let (Some(threshold), None) = (threshold, task.threshold_date) {
+        task.tags.insert(String::from("t"), threshold.to_string());
+    }
+    Ok(task)
+}
 
 /// Struct to manage ToDo tasks and theirs state.
 pub struct ToDo {
     pub pending: Vec<Task>,
     pub done: Vec<Task>,
+    /// The completed lines loaded from the todo file, see [`ToDo::split_done`].
+    todo_file_done: Vec<String>,
     version: usize,
+    saved_version: usize,
+    index: RefCell<CategoryIndex>,
+    urgency: RefCell<UrgencyCache>,
     state: ToDoState,
+    cleared_filters: Option<ToDoState>,
+    filter_stack: Vec<ToDoState>,
+    last_completed: Option<String>,
+    selected_project: Option<String>,
+    search: Option<String>,
+    filter_bar: Option<TaskQuery>,
+    timer: Option<Timer>,
+    projects: HashMap<String, ProjectInfo>,
+    activity: Vec<Activity>,
+    meta: MetaStore,
+    trash: Vec<TrashedTask>,
+    flushed: Vec<TrashedTask>,
+    subscribers: Vec<Sender<Change>>,
     config: ToDoConfig,
     styles: Styles,
 }
@@ -29,27 +161,64 @@ impl ToDo {
     ///
     /// # Arguments
     ///
-    /// * `use_done` - A boolean indicating whether to include done tasks in the ToDo data.
-    pub fn new(config: &Config) -> Self {
+    /// * `config` - The options of the todo list, e.g. its sorting.
+    /// * `styles` - The styles used by templates and task rules.
+    pub fn new(config: ToDoConfig, styles: Styles) -> Self {
         Self {
             pending: Vec::new(),
             done: Vec::new(),
+            todo_file_done: Vec::new(),
             version: 0,
+            saved_version: 0,
+            index: RefCell::default(),
+            urgency: RefCell::default(),
             state: ToDoState::default(),
-            config: ToDoConfig::new(config),
-            styles: Styles::new(config),
+            cleared_filters: None,
+            filter_stack: Vec::new(),
+            last_completed: None,
+            selected_project: None,
+            search: None,
+            filter_bar: None,
+            timer: None,
+            projects: HashMap::new(),
+            activity: Vec::new(),
+            meta: MetaStore::default(),
+            trash: Vec::new(),
+            flushed: Vec::new(),
+            subscribers: Vec::new(),
+            config,
+            styles,
         }
     }
 
-    /// Moves data from another ToDo instance into this one.
+    /// Moves data from another ToDo instance into this one, the scratch tasks
+    /// of this instance are kept.
     ///
     /// # Arguments
     ///
     /// * `other` - The other ToDo instance to move data from.
-    pub fn move_data(&mut self, other: Self) {
+    pub fn move_data(&mut self, mut other: Self) {
+        self.keep_scratch(&mut other);
         self.pending = other.pending;
         self.done = other.done;
+        self.todo_file_done = other.todo_file_done;
         self.version += 1;
+        self.notify(Change::Reloaded);
+    }
+
+    /// Replaces the configuration of the todo list, e.g. when another todo file is opened.
+    pub fn set_config(&mut self, config: ToDoConfig) {
+        self.config = config;
+    }
+
+    /// Gets the path to the done file the completed tasks are archived to.
+    pub fn done_path(&self) -> &str {
+        &self.config.done_path
+    }
+
+    /// Gets the clock giving the current date of date-dependent features.
+    pub fn clock(&self) -> Clock {
+        self.config.clock
     }
 
     /// Gets the current version of the ToDo data.
@@ -58,6 +227,16 @@ impl ToDo {
         self.version
     }
 
+    /// Remembers that the todo list is the same as the todo file, after it was loaded or saved.
+    pub fn mark_saved(&mut self) {
+        self.saved_version = self.version;
+    }
+
+    /// Checks whether the todo list has changes that are not saved to the todo file.
+    pub fn is_dirty(&self) -> bool {
+        self.version != self.saved_version
+    }
+
     /// Gets the actual index of an item in the ToDo data without filters.
     ///
     /// # Arguments
@@ -78,12 +257,61 @@ impl ToDo {
     ///
     /// * `task` - The `Task` to be added to the ToDo list.
     pub fn add_task(&mut self, task: Task) {
+        let data = match task.finished {
+            true => ToDoData::Done,
+            false => ToDoData::Pending,
+        };
+        let index = self.index.get_mut();
+        if index.is_current(self.version, self.pending.len(), self.done.len()) {
+            index.push(self.version + 1, data, &task);
+        }
         self.version += 1;
-        if task.finished {
-            self.done.push(task);
-        } else {
-            self.pending.push(task);
+        match data {
+            ToDoData::Done => self.done.push(task),
+            ToDoData::Pending => self.pending.push(task),
+        }
+    }
+
+    /// Gets the index of categories to tasks, the index is built again if the tasks
+    /// have changed since it was built.
+    fn category_index(&self) -> Ref<'_, CategoryIndex> {
+        let current =
+            self.index
+                .borrow()
+                .is_current(self.version, self.pending.len(), self.done.len());
+        if !current {
+            *self.index.borrow_mut() = CategoryIndex::new(self.version, &self.pending, &self.done);
         }
+        self.index.borrow()
+    }
+
+    /// Gets the urgency scores of the tasks, the scores are computed again if the tasks
+    /// or the date have changed since they were computed.
+    fn urgency_cache(&self) -> Ref<'_, UrgencyCache> {
+        let today = self.config.clock.today();
+        let current = self.urgency.borrow().is_current(
+            self.version,
+            today,
+            self.pending.len(),
+            self.done.len(),
+        );
+        if !current {
+            *self.urgency.borrow_mut() = UrgencyCache::new(
+                self.version,
+                today,
+                &self.config.urgency_weights,
+                &self.pending,
+                &self.done,
+            );
+        }
+        self.urgency.borrow()
+    }
+
+    /// Computes the urgency score of the task, see [`UrgencyWeights`].
+    pub fn urgency(&self, task: &Task) -> f64 {
+        self.config
+            .urgency_weights
+            .score(task, self.config.clock.today())
     }
 
     /// Gets a filtered list of categories from the ToDo data.
@@ -96,27 +324,127 @@ impl ToDo {
     ///
     /// A `CategoryList` containing the filtered categories and their selection status.
     pub fn get_categories(&self, category: ToDoCategory) -> CategoryList {
-        let tasks = if self.config.use_done {
-            vec![&self.pending, &self.done]
+        self.get_categories_with(category, self.config.use_done)
+    }
+
+    /// Gets a filtered list of categories of pending tasks and optionally of done tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `category` - The type of category to retrieve.
+    /// * `use_done` - Whether the categories of done tasks are included.
+    ///
+    /// # Returns
+    ///
+    /// A `CategoryList` containing the filtered categories and their selection status.
+    pub fn get_categories_with(&self, category: ToDoCategory, use_done: bool) -> CategoryList<'_> {
+        let data = if use_done {
+            vec![ToDoData::Pending, ToDoData::Done]
         } else {
-            vec![&self.pending]
+            vec![ToDoData::Pending]
         };
 
+        let index = self.category_index();
         let selected = self.state.get_category(category);
         CategoryList {
-            vec: tasks
-                .iter()
-                .flat_map(|list| list.iter())
-                .flat_map(|task| category.get_data(task).iter())
-                .chain(self.state.get_category(category).keys())
-                .collect::<BTreeSet<&String>>()
+            vec: data
                 .iter()
-                .map(|item| (*item, selected.get(*item).cloned()))
+                .flat_map(|data| self.indexed_names(&index, *data, category))
+                .chain(selected.keys().map(|name| Cow::Borrowed(name.as_str())))
+                .collect::<BTreeSet<Cow<str>>>()
+                .into_iter()
+                .map(|item| {
+                    let state = selected.get(item.as_ref()).cloned();
+                    (item, state)
+                })
                 .collect(),
             styles: &self.styles,
+            warnings: BTreeSet::new(),
+            nodes: Vec::new(),
         }
     }
 
+    /// Counts pending tasks of every category, filters are not applied. The tasks
+    /// of a project include the tasks of its subprojects, see [`ToDoCategory::parents`].
+    ///
+    /// # Arguments
+    ///
+    /// * `category` - The type of category to count.
+    ///
+    /// # Returns
+    ///
+    /// The number of pending tasks for every category and the number of all pending tasks.
+    pub fn category_counts(
+        &self,
+        category: ToDoCategory,
+    ) -> (BTreeMap<Cow<'_, str>, usize>, usize) {
+        let index = self.category_index();
+        let mut tasks: BTreeMap<Cow<str>, BTreeSet<usize>> = BTreeMap::new();
+        for (name, (_, indices)) in self
+            .indexed_names(&index, ToDoData::Pending, category)
+            .into_iter()
+            .zip(index.names(ToDoData::Pending, category))
+        {
+            for parent in category.parents(&name) {
+                tasks
+                    .entry(Cow::Owned(parent.to_string()))
+                    .or_default()
+                    .extend(indices);
+            }
+            tasks.entry(name).or_default().extend(indices);
+        }
+        let counts = tasks
+            .into_iter()
+            .map(|(name, tasks)| (name, tasks.len()))
+            .collect();
+        (counts, self.pending.len())
+    }
+
+    /// Gets the indexed names of the category borrowed from the tasks, so they outlive
+    /// the borrow of the index. Names that are not in the tasks as they are shown,
+    /// e.g. due dates, are copied.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The current index of categories.
+    /// * `data` - The type of ToDo data.
+    /// * `category` - The type of category.
+    fn indexed_names(
+        &self,
+        index: &CategoryIndex,
+        data: ToDoData,
+        category: ToDoCategory,
+    ) -> Vec<Cow<'_, str>> {
+        let tasks = data.get_data(self);
+        index
+            .names(data, category)
+            .filter_map(
+                |(name, indices)| match category.get_data(&tasks[indices[0]]) {
+                    Cow::Borrowed(names) => names
+                        .iter()
+                        .find(|other| *other == name)
+                        .map(|name| Cow::Borrowed(name.as_str())),
+                    Cow::Owned(_) => Some(Cow::Owned(name.clone())),
+                },
+            )
+            .collect()
+    }
+
+    /// Counts categories with an overdue pending task, filters are not applied.
+    ///
+    /// # Arguments
+    ///
+    /// * `category` - The type of category to count.
+    /// * `today` - The date the due dates are compared with.
+    pub fn overdue_categories(&self, category: ToDoCategory, today: NaiveDate) -> usize {
+        self.pending
+            .iter()
+            .filter(|task| task.due_date.is_some_and(|due| due < today))
+            .flat_map(|task| category.get_data(task).into_owned())
+            .collect::<BTreeSet<_>>()
+            .len()
+    }
+
     /// Moves a task from one section (Pending or Done) to the other.
     ///
     /// # Arguments
@@ -124,29 +452,76 @@ impl ToDo {
     /// * `data` - The type of ToDo data from which to move the task.
     /// * `index` - The index of the task to be moved in the specified data.
     pub fn move_task(&mut self, data: ToDoData, index: usize) {
-        self.version += 1;
-        let index = match self.get_actual_index(data, index) {
-            Some(index) => index,
-            None => {
-                log::warn!("Cannot move task Layout::get_actual_index is None");
-                return;
-            }
-        };
+        match self.get_actual_index(data, index) {
+            Some(index) => self.move_task_at(data, index),
+            None => log::warn!("Cannot move task Layout::get_actual_index is None"),
+        }
+    }
 
-        let move_task_logic = |from: &mut Vec<Task>, to: &mut Vec<_>| {
-            if from.len() <= index {
-                return;
-            }
-            let mut task = from.remove(index);
-            task.finished = !task.finished;
-            to.push(task)
-        };
+    /// Moves a task from one section (Pending or Done) to the other.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data from which to move the task.
+    /// * `index` - The index of the task in the unfiltered data.
+    fn move_task_at(&mut self, data: ToDoData, index: usize) {
+        if data.get_data(self).len() <= index {
+            return;
+        }
+        self.version += 1;
+        let mut task = data.get_data_mut(self).remove(index);
+        task.finished = !task.finished;
+        let mut next = None;
         use ToDoData::*;
         match data {
-            Pending => move_task_logic(&mut self.pending, &mut self.done),
-            Done => move_task_logic(&mut self.done, &mut self.pending),
+            Pending => {
+                let today = self.config.clock.today();
+                if Routine::of(&task).is_some() {
+                    if task.finish_date.is_none() {
+                        // The completion date tells when the routine is reset.
+                        task.finish_date = Some(today);
+                    }
+                } else if let Some(recurrence) = Recurrence::of(&task) {
+                    next = Some(recurrence.next_task(&task, today));
+                }
+                self.record(ActivityKind::Complete, &task, None);
+                self.last_completed = Some(task.to_string());
+                self.done.push(task);
+            }
+            Done => {
+                self.record(ActivityKind::Reopen, &task, None);
+                self.pending.push(task);
+            }
         };
-        self.fix_active(index)
+        self.fix_active(data, index);
+        if let Some(next) = next {
+            self.record(ActivityKind::Add, &next, None);
+            self.insert_task(Pending, next);
+        }
+    }
+
+    /// Moves the most recently completed task back to pending. It is the task completed
+    /// last since the start of the application, otherwise the done task with the newest
+    /// completion date.
+    ///
+    /// # Returns
+    ///
+    /// The reopened task or `None` if there is no done task.
+    pub fn reopen_last_completed(&mut self) -> Option<Task> {
+        let index = self
+            .last_completed
+            .take()
+            .and_then(|last| self.done.iter().rposition(|task| task.to_string() == last))
+            .or_else(|| {
+                self.done
+                    .iter()
+                    .enumerate()
+                    .max_by_key(|(_, task)| task.finish_date)
+                    .map(|(index, _)| index)
+            })?;
+        let task = self.done[index].clone();
+        self.move_task_at(ToDoData::Done, index);
+        Some(task)
     }
 
     /// Toggles a filter for a specific category.
@@ -164,11 +539,172 @@ impl ToDo {
         self.state.set_filter(category, filter, filter_state)
     }
 
-    fn get_filtered_tasks(&self, data: ToDoData) -> Vec<(usize, &Task)> {
+    /// Switches the selected filters of the category between matching tasks having
+    /// all of them and tasks having any of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `category` - The category whose filter mode is switched.
+    pub fn toggle_filter_mode(&mut self, category: ToDoCategory) {
+        self.state.toggle_any(category)
+    }
+
+    /// Switches the filter of the assignees between all tasks, tasks assigned to the user
+    /// and tasks assigned to others. Nothing is done if the name of the user is not set.
+    pub fn cycle_my_tasks(&mut self) {
+        let name = match &self.config.my_name {
+            Some(name) => name.clone(),
+            None => {
+                log::warn!("Cannot filter my tasks, my name is not set");
+                return;
+            }
+        };
+        let state = match self.state.assignee_filters.get(&name) {
+            None => FilterState::Select,
+            Some(_) => FilterState::Remove,
+        };
+        self.toggle_filter(ToDoCategory::Assignees, &name, state);
+    }
+
+    /// Clears all filters for a while, calling it again restores the cleared filters.
+    pub fn toggle_filters(&mut self) {
+        let active = self.state.active;
+        match self.cleared_filters.take() {
+            Some(filters) => self.state = filters,
+            None => self.cleared_filters = Some(std::mem::take(&mut self.state)),
+        }
+        self.state.active = active;
+    }
+
+    /// Saves the filters to the filter stack and clears all of them,
+    /// [`ToDo::pop_filters`] restores them.
+    pub fn clear_filters(&mut self) {
+        self.push_filters();
+        let active = self.state.active;
+        self.state = ToDoState {
+            active,
+            ..Default::default()
+        };
+    }
+
+    /// Saves a copy of the filters to the filter stack, so drilling into
+    /// categories can be reverted by [`ToDo::pop_filters`]. Filters cleared
+    /// by [`ToDo::toggle_filters`] are restored first.
+    pub fn push_filters(&mut self) {
+        self.restore_cleared_filters();
+        self.filter_stack.push(ToDoState {
+            active: None,
+            ..self.state.clone()
+        });
+    }
+
+    /// Saves the filters to the filter stack and replaces them by the given ones,
+    /// [`ToDo::pop_filters`] restores them.
+    ///
+    /// # Arguments
+    ///
+    /// * `filters` - The categories and their filter states.
+    pub fn set_filters(&mut self, filters: Vec<(ToDoCategory, String, FilterState)>) {
+        self.clear_filters();
+        for (category, name, state) in filters {
+            self.state.set_filter(category, &name, state);
+        }
+    }
+
+    /// Restores the filters saved last to the filter stack.
+    /// Nothing is done if the stack is empty.
+    pub fn pop_filters(&mut self) {
+        match self.filter_stack.pop() {
+            Some(filters) => {
+                self.cleared_filters = None;
+                let active = self.state.active;
+                self.state = ToDoState { active, ..filters };
+            }
+            None => log::info!("The filter stack is empty"),
+        }
+    }
+
+    /// Gets the number of filter states saved to the filter stack.
+    pub fn filter_stack_len(&self) -> usize {
+        self.filter_stack.len()
+    }
+
+    fn restore_cleared_filters(&mut self) {
+        if self.cleared_filters.is_some() {
+            self.toggle_filters();
+        }
+    }
+
+    /// Checks whether the filters are cleared by [`ToDo::toggle_filters`].
+    pub fn filters_cleared(&self) -> bool {
+        self.cleared_filters.is_some()
+    }
+
+    /// Gets the selected project and context filters as the words of a task,
+    /// e.g. `+work @office`, so a new task matches the filters.
+    pub fn selected_categories(&self) -> String {
+        [(ToDoCategory::Projects, "+"), (ToDoCategory::Contexts, "@")]
+            .into_iter()
+            .flat_map(|(category, prefix)| {
+                self.state
+                    .get_category(category)
+                    .iter()
+                    .filter(|(_, state)| **state == FilterState::Select)
+                    .map(move |(name, _)| format!("{prefix}{name}"))
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Gets the number of tasks hidden by the filters.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data for which to count the tasks.
+    pub fn hidden(&self, data: ToDoData) -> usize {
+        data.get_data(self).len() - self.get_filtered_tasks(data, None).len()
+    }
+
+    /// Gets the tasks matching the query regardless of the filters and the search,
+    /// in the order of the file.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data to query.
+    /// * `query` - The query the tasks must match.
+    ///
+    /// # Returns
+    ///
+    /// The matching tasks with their indexes in the unfiltered list.
+    pub fn query_tasks(&self, data: ToDoData, query: &TaskQuery) -> Vec<(usize, &Task)> {
+        let now = self.config.clock.now();
         data.get_data(self)
             .iter()
             .enumerate()
-            .filter(|(_, task)| self.state.filter_out(task))
+            .filter(|(_, task)| query.matches(task, now, &self.config.context_hours))
+            .collect()
+    }
+
+    fn get_filtered_tasks(&self, data: ToDoData, query: Option<&TaskQuery>) -> Vec<(usize, &Task)> {
+        let now = self.config.clock.now();
+        let tasks = data.get_data(self);
+        let candidates: Vec<(usize, &Task)> = match query {
+            Some(query) => tasks
+                .iter()
+                .enumerate()
+                .filter(|(_, task)| query.matches(task, now, &self.config.context_hours))
+                .collect(),
+            None => self
+                .category_index()
+                .filter(data, &self.state)
+                .into_iter()
+                .map(|index| (index, &tasks[index]))
+                .collect(),
+        };
+        candidates
+            .into_iter()
+            .filter(|(_, task)| self.matches_search(task))
+            .filter(|(_, task)| self.matches_filter_bar(task, now))
             .collect()
     }
 
@@ -182,15 +718,60 @@ impl ToDo {
     /// # Returns
     ///
     /// A `TaskList` containing the filtered tasks.
-    pub fn get_filtered_and_sorted(&self, data: ToDoData) -> TaskList {
+    pub fn get_filtered_and_sorted(&self, data: ToDoData) -> TaskList<'_> {
+        self.get_queried_and_sorted(data, None)
+    }
+
+    /// Gets a sorted list of tasks selected by the query instead of the active filters.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data to query.
+    /// * `query` - The query selecting the tasks, active filters are used if it is `None`.
+    ///
+    /// # Returns
+    ///
+    /// A `TaskList` containing the selected tasks.
+    pub fn get_queried_and_sorted(
+        &self,
+        data: ToDoData,
+        query: Option<&TaskQuery>,
+    ) -> TaskList<'_> {
         let mut task_list = TaskList {
-            vec: self.get_filtered_tasks(data),
+            vec: self.get_filtered_tasks(data, query),
             styles: &self.styles,
         };
-        task_list.sort(data.get_sorting(&self.config));
+        let sort = data.get_sorting(&self.config);
+        self.config.priority_aging.sort(
+            &mut task_list,
+            sort,
+            data.get_sort_ties(&self.config),
+            self.config.clock.today(),
+        );
+        if matches!(sort, TaskSort::Urgency | TaskSort::UrgencyReverse) {
+            self.urgency_cache().sort(&mut task_list, data, sort);
+        }
         task_list
     }
 
+    /// Gets the sorting currently applied to the given ToDo data.
+    pub fn get_sort(&self, data: ToDoData) -> TaskSort {
+        data.get_sorting(&self.config)
+    }
+
+    /// Changes the sorting of the given ToDo data.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data to sort.
+    /// * `sort` - The new sorting criteria.
+    pub fn set_sort(&mut self, data: ToDoData, sort: TaskSort) {
+        match data {
+            ToDoData::Pending => self.config.pending_sort = sort,
+            ToDoData::Done => self.config.done_sort = sort,
+        }
+    }
+
     /// Adds a new task to the ToDo list using a task string.
     ///
     /// # Arguments
@@ -202,23 +783,83 @@ impl ToDo {
     /// A `Result` indicating success or an error if the task string cannot be parsed.
     pub fn new_task(&mut self, task: &str) -> Result<(), todo_txt::Error> {
         self.version += 1;
-        let task = task.replace(
-            "due:today ",
-            &format!("due:{}", Utc::now().naive_utc().date()),
-        );
-        let task = task.replace("due: ", &format!("due:{}", Utc::now().naive_utc().date()));
-        let mut task = Task::from_str(&task)?;
-        if task.create_date.is_none() {
-            task.create_date = Some(Utc::now().naive_utc().date());
-        }
-        if task.finished {
-            self.done.push(task);
+        let task = match self.config.raw_lines {
+            true => parse_task(task)?,
+            false => preprocess::preprocess(task, &self.config, self.config.clock.today())?,
+        };
+        self.record(ActivityKind::Add, &task, None);
+        let data = if task.finished {
+            ToDoData::Done
         } else {
-            self.pending.push(task);
-        }
+            ToDoData::Pending
+        };
+        self.insert_task(data, task);
+        Ok(())
+    }
+
+    /// Adds a task parsed from the line without any preprocessing, so the task is the same
+    /// as in the file it is copied from.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The todo.txt line of the task.
+    pub fn copy_task(&mut self, task: &str) -> Result<(), todo_txt::Error> {
+        self.version += 1;
+        let task = parse_task(task)?;
+        self.record(ActivityKind::Add, &task, None);
+        let data = match task.finished {
+            true => ToDoData::Done,
+            false => ToDoData::Pending,
+        };
+        self.insert_task(data, task);
         Ok(())
     }
 
+    /// Inserts a new task to the ToDo data. The task is appended, or inserted at
+    /// the position given by the sorting of the data if sorting on insert is enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data to insert the task to.
+    /// * `task` - The task to insert.
+    fn insert_task(&mut self, data: ToDoData, task: Task) {
+        let sort = data.get_sorting(&self.config);
+        let ties = data.get_sort_ties(&self.config);
+        let tasks = data.get_data(self);
+        let index = match self.config.sort_on_insert {
+            true => tasks
+                .iter()
+                .position(|other| sort.compare_with_ties(ties, &task, other) == Ordering::Less)
+                .unwrap_or(tasks.len()),
+            false => tasks.len(),
+        };
+        data.get_data_mut(self).insert(index, task);
+        if let Some((active_data, active_index)) = &mut self.state.active {
+            if *active_data == data && *active_index >= index {
+                *active_index += 1;
+            }
+        }
+    }
+
+    /// Toggles whether new tasks are inserted at the position given by the sorting.
+    pub fn toggle_sort_on_insert(&mut self) {
+        self.config.sort_on_insert = !self.config.sort_on_insert;
+        log::info!("Sort on insert: {}", self.config.sort_on_insert);
+    }
+
+    /// Toggles showing tasks as raw todo.txt lines. New tasks are then added
+    /// exactly as typed, without the preprocessing of [`ToDo::new_task`].
+    pub fn toggle_raw_lines(&mut self) {
+        self.config.raw_lines = !self.config.raw_lines;
+        self.version += 1;
+        log::info!("Raw lines: {}", self.config.raw_lines);
+    }
+
+    /// Checks whether tasks are shown as raw todo.txt lines.
+    pub fn raw_lines(&self) -> bool {
+        self.config.raw_lines
+    }
+
     /// Removes a task from the ToDo list.
     ///
     /// # Arguments
@@ -226,12 +867,42 @@ impl ToDo {
     /// * `data` - The type of ToDo data from which to remove the task.
     /// * `index` - The index of the task to be removed in the specified data.
     pub fn remove_task(&mut self, data: ToDoData, index: usize) {
-        let index = self.get_actual_index(data, index);
-        if let Some(index) = index {
-            data.get_data_mut(self).remove(index);
-            self.fix_active(index);
-        } else {
-            log::warn!("Layout::get_actual_index is None");
+        match self.get_actual_index(data, index) {
+            Some(index) => self.remove_task_at(data, index),
+            None => log::warn!("Layout::get_actual_index is None"),
+        }
+    }
+
+    /// Removes a task from the ToDo list, the task is put to the trash.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data from which to remove the task.
+    /// * `index` - The index of the task in the unfiltered data.
+    fn remove_task_at(&mut self, data: ToDoData, index: usize) {
+        let task = data.get_data_mut(self).remove(index);
+        self.record(ActivityKind::Delete, &task, None);
+        self.fix_active(data, index);
+        self.trash_task(data, task);
+    }
+
+    /// Inserts a copy of the task right after it, e.g. to create a variant of the task.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data with the task.
+    /// * `index` - The index of the task in the unfiltered data.
+    fn duplicate_task_at(&mut self, data: ToDoData, index: usize) {
+        let Some(task) = data.get_data(self).get(index).cloned() else {
+            return;
+        };
+        self.version += 1;
+        self.record(ActivityKind::Add, &task, None);
+        data.get_data_mut(self).insert(index + 1, task);
+        if let Some((active_data, active_index)) = &mut self.state.active {
+            if *active_data == data && *active_index > index {
+                *active_index += 1;
+            }
         }
     }
 
@@ -246,22 +917,133 @@ impl ToDo {
         let from = self.get_actual_index(data, from);
         let to = self.get_actual_index(data, to);
         match (from, to) {
-            (Some(from), Some(to)) => {
-                data.get_data_mut(self).swap(from, to);
-                if let Some((_, act_index)) = &mut self.state.active {
-                    if *act_index == from {
-                        *act_index = to;
-                    } else if *act_index == to {
-                        *act_index = from;
-                    }
+            (Some(from), Some(to)) => self.swap_tasks_at(data, from, to),
+            _ => {
+                log::warn!("Canot swap from or to is None")
+            }
+        }
+    }
+
+    /// Swaps the positions of two tasks in the ToDo list.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data (Pending or Done) in which to swap the tasks.
+    /// * `from` - The index of the first task in the unfiltered data.
+    /// * `to` - The index of the second task in the unfiltered data.
+    fn swap_tasks_at(&mut self, data: ToDoData, from: usize, to: usize) {
+        data.get_data_mut(self).swap(from, to);
+        match &mut self.state.active {
+            Some((act_data, act_index)) if *act_data == data => {
+                if *act_index == from {
+                    *act_index = to;
+                } else if *act_index == to {
+                    *act_index = from;
                 }
             }
+            _ => {}
+        }
+    }
+
+    /// Moves a task to another position in the ToDo list. Tasks between
+    /// both positions are shifted by one towards the original position.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data (Pending or Done) in which to move the task.
+    /// * `from` - The index of the task to be moved.
+    /// * `to` - The index the task is moved to.
+    pub fn reorder_task(&mut self, data: ToDoData, from: usize, to: usize) {
+        let from = self.get_actual_index(data, from);
+        let to = self.get_actual_index(data, to);
+        match (from, to) {
+            (Some(from), Some(to)) => self.reorder_task_at(data, from, to),
             _ => {
-                log::warn!("Canot swap from or to is None")
+                log::warn!("Cannot reorder, from or to is None")
             }
         }
     }
 
+    /// Moves a task to another position in the ToDo list.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data (Pending or Done) in which to move the task.
+    /// * `from` - The index of the task in the unfiltered data.
+    /// * `to` - The index in the unfiltered data the task is moved to.
+    fn reorder_task_at(&mut self, data: ToDoData, from: usize, to: usize) {
+        let tasks = data.get_data_mut(self);
+        let task = tasks.remove(from);
+        tasks.insert(to, task);
+        match &mut self.state.active {
+            Some((act_data, act_index)) if *act_data == data => {
+                if *act_index == from {
+                    *act_index = to;
+                } else if from < *act_index && *act_index <= to {
+                    *act_index -= 1;
+                } else if to <= *act_index && *act_index < from {
+                    *act_index += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Cycles the priority of a task through A, B, C and no priority.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data where the task is located.
+    /// * `index` - The index of the task in the unfiltered data.
+    fn cycle_priority_at(&mut self, data: ToDoData, index: usize) {
+        let task = &mut data.get_data_mut(self)[index];
+        task.priority = match u8::from(task.priority.clone()) {
+            priority @ (0 | 1) => Priority::from(priority + 1),
+            2 => Priority::lowest(),
+            _ => Priority::from(0),
+        };
+    }
+
+    /// Raises or lowers the priority of a task by the number of letters. No priority is
+    /// below `Z`, so raising a task without priority gives it `Z`.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data where the task is located.
+    /// * `index` - The index of the task in the unfiltered data.
+    /// * `steps` - The number of letters to raise the priority by, negative to lower it.
+    fn shift_priority_at(&mut self, data: ToDoData, index: usize, steps: i8) {
+        let task = &mut data.get_data_mut(self)[index];
+        let lowest = u8::from(Priority::lowest()) as i16;
+        let priority = u8::from(task.priority.clone()) as i16 - steps as i16;
+        task.priority = Priority::from(priority.clamp(0, lowest) as u8);
+    }
+
+    /// Removes the priority of a task.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data where the task is located.
+    /// * `index` - The index of the task in the unfiltered data.
+    fn clear_priority_at(&mut self, data: ToDoData, index: usize) {
+        data.get_data_mut(self)[index].priority = Priority::lowest();
+    }
+
+    /// Defers a task by moving its due date. A task without a due date
+    /// becomes due the given number of days from today.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data where the task is located.
+    /// * `index` - The index of the task in the unfiltered data.
+    /// * `days` - The number of days to defer the task by.
+    fn defer_task_at(&mut self, data: ToDoData, index: usize, days: i64) {
+        let today = self.config.clock.today();
+        let task = &mut data.get_data_mut(self)[index];
+        let due = task.due_date.unwrap_or(today);
+        task.due_date = Some(due + Duration::days(days));
+    }
+
     /// Sets a task as the active task for potential editing.
     ///
     /// # Arguments
@@ -289,7 +1071,9 @@ impl ToDo {
         }
     }
 
-    /// Updates the content of the active task.
+    /// Updates the content of the active task. The task stays at its index and keeps
+    /// its completion state, so it does not end up done in the pending list or vice versa.
+    /// Date expressions such as `due:fri` are replaced with dates, see [`expand_dates`].
     ///
     /// # Arguments
     ///
@@ -300,8 +1084,39 @@ impl ToDo {
     /// A `Result` indicating success or an error if the updated task string cannot be parsed.
     pub fn update_active(&mut self, task: &str) -> Result<(), todo_txt::Error> {
         if let Some((data, index)) = self.state.active {
-            data.get_data_mut(self)[index] = Task::from_str(task)?;
+            self.update_task_at(data, index, task)?;
+        }
+        Ok(())
+    }
+
+    /// Updates the content of the task at the index, see [`ToDo::update_active`].
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data of the task.
+    /// * `index` - The index of the task in the unfiltered list.
+    /// * `task` - The updated task string.
+    fn update_task_at(
+        &mut self,
+        data: ToDoData,
+        index: usize,
+        task: &str,
+    ) -> Result<(), todo_txt::Error> {
+        let mut task = match self.config.raw_lines {
+            true => parse_task(task)?,
+            false => parse_task(&expand_dates(task, self.config.clock.today()))?,
+        };
+        let finished = data == ToDoData::Done;
+        if task.finished != finished {
+            task.finished = finished;
+            task.finish_date = finished
+                .then(|| data.get_data(self)[index].finish_date)
+                .flatten();
         }
+        let previous = std::mem::replace(&mut data.get_data_mut(self)[index], task.clone());
+        self.record(ActivityKind::Edit, &task, Some(&previous));
+        self.rename_meta(&previous, &task);
+        self.rename_timer(&previous, &task);
         Ok(())
     }
 
@@ -312,9 +1127,13 @@ impl ToDo {
     ///
     /// # Arguments
     ///
+    /// * `data` - The type of ToDo data from which the task was moved or removed.
     /// * `index` - The index of a task that was moved or removed.
-    fn fix_active(&mut self, index: usize) {
-        if let Some((_, act_index)) = &mut self.state.active {
+    fn fix_active(&mut self, data: ToDoData, index: usize) {
+        if let Some((act_data, act_index)) = &mut self.state.active {
+            if *act_data != data {
+                return;
+            }
             log::trace!("act: {}, moved: {}", act_index, index);
             match index.cmp(act_index) {
                 std::cmp::Ordering::Less => *act_index -= 1,
@@ -348,7 +1167,7 @@ impl ToDo {
 
 impl Default for ToDo {
     fn default() -> Self {
-        ToDo::new(&Config::default())
+        ToDo::new(ToDoConfig::default(), Styles::default())
     }
 }
 
@@ -438,10 +1257,35 @@ mod tests {
         assert_eq!(todo.pending[1].hashtags.len(), 0);
     }
 
-    fn create_vec(items: &[String]) -> Vec<(&String, Option<FilterState>)> {
-        let mut vec: Vec<(&String, Option<FilterState>)> = Vec::new();
+    #[test]
+    fn reopen_last_completed() {
+        let mut todo = example_todo();
+        assert_eq!(
+            todo.reopen_last_completed().unwrap().subject,
+            "measure space for 1 +project1 @context1 #hashtag1"
+        );
+        assert_eq!(todo.done.len(), 1);
+
+        let mut todo = example_todo();
+        todo.move_task_at(ToDoData::Pending, 1);
+        todo.move_task_at(ToDoData::Pending, 0);
+        assert_eq!(todo.done.len(), 4);
+        let task = todo.reopen_last_completed().unwrap();
+        assert_eq!(task.subject, "measure space for 2 +project2 @context2");
+        assert!(todo
+            .pending
+            .iter()
+            .any(|pending| pending.subject == task.subject));
+        assert_eq!(todo.done.len(), 3);
+
+        todo.done.clear();
+        assert!(todo.reopen_last_completed().is_none());
+    }
+
+    fn create_vec(items: &[String]) -> Vec<(Cow<str>, Option<FilterState>)> {
+        let mut vec: Vec<(Cow<str>, Option<FilterState>)> = Vec::new();
         items.iter().for_each(|item| {
-            vec.push((item, None));
+            vec.push((Cow::from(item.as_str()), None));
         });
         vec
     }
@@ -483,6 +1327,12 @@ mod tests {
             todo.get_categories(ToDoCategory::Hashtags).vec,
             create_vec(&[String::from("hashtag1"), String::from("hashtag2")])
         );
+        // A widget can leave out done tasks regardless of the global setting.
+        assert_eq!(
+            todo.get_categories_with(ToDoCategory::Contexts, false)
+                .len(),
+            2
+        );
 
         Ok(())
     }
@@ -613,6 +1463,256 @@ mod tests {
         assert_eq!(todo.get_active().unwrap().subject, subject);
     }
 
+    #[test]
+    fn active_in_other_data() {
+        let mut todo = example_todo();
+        todo.set_active(ToDoData::Done, 0);
+        let subject = todo.get_active().unwrap().subject.clone();
+        todo.remove_task(ToDoData::Pending, 0);
+        todo.move_task(ToDoData::Pending, 0);
+        todo.swap_tasks(ToDoData::Pending, 0, 1);
+        assert_eq!(todo.get_active().unwrap().subject, subject);
+    }
+
+    #[test]
+    fn subprojects() {
+        let mut todo = ToDo::default();
+        for task in [
+            "weed +home.garden",
+            "water +home.garden +home",
+            "paint +home.kitchen",
+            "call +work",
+        ] {
+            todo.add_task(Task::from_str(task).unwrap());
+        }
+        let (counts, total) = todo.category_counts(ToDoCategory::Projects);
+        assert_eq!(total, 4);
+        assert_eq!(counts.get("home"), Some(&3));
+        assert_eq!(counts.get("home.garden"), Some(&2));
+        assert_eq!(counts.get("work"), Some(&1));
+
+        todo.toggle_filter(ToDoCategory::Projects, "home", FilterState::Select);
+        assert_eq!(todo.len(ToDoData::Pending), 3);
+        todo.toggle_filter(ToDoCategory::Projects, "home.garden", FilterState::Remove);
+        assert_eq!(todo.len(ToDoData::Pending), 1);
+    }
+
+    #[test]
+    fn tag_categories() {
+        let mut todo = ToDo::default();
+        for task in [
+            "call mom due:2024-07-03 person:Ann",
+            "buy milk due:2024-07-01",
+            "pay rent due:2024-07-03",
+            "fix the bike person:Bob",
+        ] {
+            todo.add_task(Task::from_str(task).unwrap());
+        }
+        let due = ToDoCategory::Tag(TagKey::new("due"));
+        assert_eq!(
+            todo.get_categories(due).vec,
+            vec![
+                (Cow::from("2024-07-01"), None),
+                (Cow::from("2024-07-03"), None)
+            ]
+        );
+        let person = ToDoCategory::Tag(TagKey::new("person"));
+        assert_eq!(todo.category_counts(person).0.len(), 2);
+
+        todo.toggle_filter(due, "2024-07-03", FilterState::Select);
+        assert_eq!(todo.len(ToDoData::Pending), 2);
+        todo.toggle_filter(person, "Ann", FilterState::Remove);
+        assert_eq!(todo.len(ToDoData::Pending), 1);
+        assert_eq!(
+            todo.get_categories(person).vec,
+            vec![
+                (Cow::from("Ann"), Some(FilterState::Remove)),
+                (Cow::from("Bob"), None)
+            ]
+        );
+        todo.toggle_filter(due, "2024-07-03", FilterState::Select);
+        todo.toggle_filter(person, "Ann", FilterState::Remove);
+        assert_eq!(todo.len(ToDoData::Pending), 4);
+        assert!(todo.get_state().tag_filters.is_empty());
+    }
+
+    #[test]
+    fn assignees() {
+        let config = ToDoConfig {
+            my_name: Some(String::from("me")),
+            ..ToDoConfig::default()
+        };
+        let mut todo = ToDo::new(config, Styles::default());
+        for task in [
+            "task 1 assignee:me",
+            "task 2 by:bob",
+            "task 3",
+            "task 4 by:me",
+        ] {
+            todo.add_task(Task::from_str(task).unwrap());
+        }
+        assert_eq!(
+            todo.get_categories(ToDoCategory::Assignees).vec,
+            vec![(Cow::from("bob"), None), (Cow::from("me"), None)]
+        );
+
+        todo.cycle_my_tasks();
+        assert_eq!(todo.len(ToDoData::Pending), 2);
+        todo.cycle_my_tasks();
+        assert_eq!(todo.len(ToDoData::Pending), 2);
+        assert!(todo.get_filtered_and_sorted(ToDoData::Pending)[0]
+            .subject
+            .starts_with("task 2"));
+        todo.cycle_my_tasks();
+        assert_eq!(todo.len(ToDoData::Pending), 4);
+    }
+
+    #[test]
+    fn toggle_filters() {
+        let mut todo = example_todo();
+        let all = todo.len(ToDoData::Pending);
+        todo.toggle_filter(ToDoCategory::Projects, "project2", FilterState::Select);
+        let shown = todo.len(ToDoData::Pending);
+        assert!(shown < all);
+        assert_eq!(todo.hidden(ToDoData::Pending), all - shown);
+
+        todo.toggle_filters();
+        assert!(todo.filters_cleared());
+        assert_eq!(todo.hidden(ToDoData::Pending), 0);
+        assert_eq!(todo.len(ToDoData::Pending), all);
+
+        todo.toggle_filters();
+        assert!(!todo.filters_cleared());
+        assert_eq!(todo.len(ToDoData::Pending), shown);
+    }
+
+    #[test]
+    fn raw_lines() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        todo.new_task("call mom")?;
+        assert!(todo.pending[0].create_date.is_some());
+
+        todo.toggle_raw_lines();
+        assert!(todo.raw_lines());
+        todo.new_task("call dad")?;
+        assert_eq!(todo.pending[1].to_string(), "call dad");
+        // The value that is not a date is kept as typed.
+        todo.new_task("call dad due:today")?;
+        assert_eq!(todo.pending[2].to_string(), "call dad due:today");
+        Ok(())
+    }
+
+    #[test]
+    fn parse_task_values_that_are_not_dates() -> Result<(), todo_txt::Error> {
+        let task = parse_task("call dad due:today t:soon")?;
+        assert_eq!(task.due_date, None);
+        assert_eq!(task.threshold_date, None);
+        assert_eq!(task.tags.get("due").map(String::as_str), Some("today"));
+        assert_eq!(task.tags.get("t").map(String::as_str), Some("soon"));
+        assert_eq!(task.to_string(), "call dad due:today t:soon");
+
+        // Dates are read as dates and not kept as tags.
+        let task = parse_task("call dad due:2023-05-03")?;
+        assert!(task.due_date.is_some());
+        assert!(!task.tags.contains_key("due"));
+        Ok(())
+    }
+
+    #[test]
+    fn filter_mode() {
+        let mut todo = example_todo();
+        todo.toggle_filter(ToDoCategory::Projects, "project2", FilterState::Select);
+        todo.toggle_filter(ToDoCategory::Projects, "project3", FilterState::Select);
+        assert_eq!(todo.len(ToDoData::Pending), 0);
+
+        todo.toggle_filter_mode(ToDoCategory::Projects);
+        assert!(todo.get_state().is_any(ToDoCategory::Projects));
+        assert_eq!(todo.len(ToDoData::Pending), 4);
+        todo.toggle_filter(ToDoCategory::Projects, "project1", FilterState::Remove);
+        assert_eq!(todo.len(ToDoData::Pending), 4);
+        todo.toggle_filter(ToDoCategory::Contexts, "context3", FilterState::Remove);
+        assert_eq!(todo.len(ToDoData::Pending), 2);
+
+        todo.toggle_filter_mode(ToDoCategory::Projects);
+        assert!(!todo.get_state().is_any(ToDoCategory::Projects));
+        assert_eq!(todo.len(ToDoData::Pending), 0);
+    }
+
+    #[test]
+    fn filter_stack() {
+        let mut todo = example_todo();
+        let all = todo.len(ToDoData::Pending);
+        todo.pop_filters();
+        assert_eq!(todo.len(ToDoData::Pending), all);
+
+        todo.push_filters();
+        todo.toggle_filter(ToDoCategory::Projects, "project2", FilterState::Select);
+        let project = todo.len(ToDoData::Pending);
+        assert!(project < all);
+        todo.push_filters();
+        todo.toggle_filter(ToDoCategory::Contexts, "context2", FilterState::Select);
+        let context = todo.len(ToDoData::Pending);
+        assert!(context < project);
+        assert_eq!(todo.filter_stack_len(), 2);
+
+        todo.pop_filters();
+        assert_eq!(todo.len(ToDoData::Pending), project);
+        todo.clear_filters();
+        assert_eq!(todo.len(ToDoData::Pending), all);
+        assert_eq!(todo.filter_stack_len(), 2);
+        todo.pop_filters();
+        assert_eq!(todo.len(ToDoData::Pending), project);
+        todo.pop_filters();
+        assert_eq!(todo.len(ToDoData::Pending), all);
+        assert_eq!(todo.filter_stack_len(), 0);
+
+        // Filters cleared for a while are saved and cleared for good.
+        todo.toggle_filter(ToDoCategory::Projects, "project2", FilterState::Select);
+        todo.toggle_filters();
+        todo.clear_filters();
+        assert!(!todo.filters_cleared());
+        assert_eq!(todo.len(ToDoData::Pending), all);
+        todo.pop_filters();
+        assert_eq!(todo.len(ToDoData::Pending), project);
+    }
+
+    #[test]
+    fn reorder_task() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        for i in 0..5 {
+            todo.new_task(&format!("task {i}"))?;
+        }
+        let subjects = |todo: &ToDo| -> Vec<String> {
+            todo.pending.iter().map(|t| t.subject.clone()).collect()
+        };
+        todo.set_active(ToDoData::Pending, 2);
+
+        todo.reorder_task(ToDoData::Pending, 4, 0);
+        assert_eq!(
+            subjects(&todo),
+            ["task 4", "task 0", "task 1", "task 2", "task 3"]
+        );
+        assert_eq!(todo.get_active().unwrap().subject, "task 2");
+
+        todo.reorder_task(ToDoData::Pending, 0, 4);
+        assert_eq!(
+            subjects(&todo),
+            ["task 0", "task 1", "task 2", "task 3", "task 4"]
+        );
+        assert_eq!(todo.get_active().unwrap().subject, "task 2");
+
+        todo.reorder_task(ToDoData::Pending, 2, 4);
+        assert_eq!(
+            subjects(&todo),
+            ["task 0", "task 1", "task 3", "task 4", "task 2"]
+        );
+        assert_eq!(todo.get_active().unwrap().subject, "task 2");
+
+        todo.reorder_task(ToDoData::Pending, 0, 10);
+        assert_eq!(todo.pending[0].subject, "task 0");
+        Ok(())
+    }
+
     #[test]
     fn move_data() {
         let todo = example_todo();
@@ -633,6 +1733,30 @@ mod tests {
         todo.move_task(ToDoData::Done, 1);
     }
 
+    #[test]
+    fn dirty() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        assert!(!todo.is_dirty());
+        todo.new_task("call mom")?;
+        assert!(todo.is_dirty());
+        todo.mark_saved();
+        assert!(!todo.is_dirty());
+        todo.toggle_filter(ToDoCategory::Projects, "project", FilterState::Select);
+        assert!(!todo.is_dirty());
+        Ok(())
+    }
+
+    #[test]
+    fn selected_categories() {
+        let mut todo = example_todo();
+        assert_eq!(todo.selected_categories(), "");
+        todo.toggle_filter(ToDoCategory::Contexts, "context1", FilterState::Select);
+        todo.toggle_filter(ToDoCategory::Projects, "project2", FilterState::Select);
+        todo.toggle_filter(ToDoCategory::Projects, "project1", FilterState::Remove);
+        todo.toggle_filter(ToDoCategory::Hashtags, "tag", FilterState::Select);
+        assert_eq!(todo.selected_categories(), "+project2 @context1");
+    }
+
     #[test]
     fn toggle_filter() {
         let mut todo = example_todo();
@@ -678,6 +1802,56 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn new_task_sort_on_insert() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        todo.set_sort(ToDoData::Pending, TaskSort::Alphanumeric);
+        todo.new_task("b")?;
+        todo.new_task("a")?;
+        assert_eq!(todo.pending[1].subject, "a");
+
+        todo.remove_task(ToDoData::Pending, 0);
+        todo.remove_task(ToDoData::Pending, 0);
+        todo.apply(Action::ToggleSortOnInsert)?;
+        todo.new_task("b")?;
+        todo.new_task("d")?;
+        todo.state.active = Some((ToDoData::Pending, 1));
+        todo.new_task("a")?;
+        todo.new_task("c")?;
+        todo.new_task("b")?;
+        let subjects: Vec<_> = todo.pending.iter().map(|t| t.subject.as_str()).collect();
+        assert_eq!(subjects, ["a", "b", "b", "c", "d"]);
+        assert_eq!(todo.state.active, Some((ToDoData::Pending, 4)));
+
+        todo.set_sort(ToDoData::Pending, TaskSort::None);
+        todo.new_task("a")?;
+        assert_eq!(todo.pending[5].subject, "a");
+        Ok(())
+    }
+
+    #[test]
+    fn urgency_sort() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        todo.set_sort(ToDoData::Pending, TaskSort::Urgency);
+        todo.new_task("buy milk")?;
+        todo.new_task("(B) call mom")?;
+        let subjects = |todo: &ToDo| -> Vec<String> {
+            todo.get_filtered_and_sorted(ToDoData::Pending)
+                .vec
+                .iter()
+                .map(|(_, task)| task.subject.clone())
+                .collect()
+        };
+        assert_eq!(subjects(&todo), ["call mom", "buy milk"]);
+
+        todo.state.active = Some((ToDoData::Pending, 0));
+        todo.update_active("(A) buy milk")?;
+        assert_eq!(subjects(&todo), ["buy milk", "call mom"]);
+        todo.set_sort(ToDoData::Pending, TaskSort::UrgencyReverse);
+        assert_eq!(subjects(&todo), ["call mom", "buy milk"]);
+        Ok(())
+    }
+
     #[test]
     fn update_active() -> Result<(), todo_txt::Error> {
         let mut todo = example_todo();
@@ -688,6 +1862,17 @@ mod tests {
         todo.state.active = Some((ToDoData::Done, 0));
         todo.update_active("New done subject")?;
         assert_eq!(todo.done[0].subject, "New done subject");
+        assert!(todo.done[0].finished);
+
+        todo.state.active = Some((ToDoData::Pending, 1));
+        todo.update_active("x 2023-05-03 Completed by the edit")?;
+        assert_eq!(todo.pending[1].subject, "Completed by the edit");
+        assert!(!todo.pending[1].finished);
+        assert_eq!(todo.pending[1].finish_date, None);
+
+        todo.update_active("Edited with a date due:tomorrow")?;
+        let tomorrow = todo.config.clock.today().succ_opt();
+        assert_eq!(todo.pending[1].due_date, tomorrow);
 
         Ok(())
     }
@@ -0,0 +1,541 @@
+use super::{DateTag, FilterState, ReviewAction, TaskSort, ToDo, ToDoCategory, ToDoData};
+use serde::{Deserialize, Serialize};
+use todo_txt::Task;
+
+/// Identifies a task in the todo list by its index in the unfiltered list and its content.
+///
+/// Positions in the filtered and sorted list change with every filter, sorting or
+/// reload, so actions refer to tasks by their identity instead. An action is not applied
+/// to another task when the list has changed after the action was created.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskId {
+    pub index: usize,
+    pub task: String,
+}
+
+impl TaskId {
+    /// Creates the identity of the task stored at the index of the unfiltered list.
+    pub fn new(index: usize, task: &Task) -> Self {
+        Self {
+            index,
+            task: task.to_string(),
+        }
+    }
+}
+
+/// Represents a semantic change of the todo list.
+///
+/// Widgets and the UI do not modify the todo list directly, they emit actions
+/// that are processed by [`ToDo::apply`]. Because every change goes through the
+/// same place, actions can be recorded, replayed or received from outside of the UI.
+///
+/// Tasks are referred by their [`TaskId`], see [`ToDo::task_id`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Action {
+    /// Adds a new task parsed from the string.
+    NewTask { task: String },
+    /// Adds the task parsed from the line as it is, e.g. copied from another todo file.
+    CopyTask { task: String },
+    /// Replaces the active task with a task parsed from the string.
+    UpdateActive { task: String },
+    /// Replaces the task with a task parsed from the line, see [`ToDo::update_active`].
+    UpdateTask {
+        data: ToDoData,
+        task: TaskId,
+        line: String,
+    },
+    /// Removes the task from the list, the task is put to the trash.
+    RemoveTask { data: ToDoData, task: TaskId },
+    /// Inserts a copy of the task right after it.
+    DuplicateTask { data: ToDoData, task: TaskId },
+    /// Returns the task from the trash to the list it was deleted from,
+    /// the index of the task is its position in the trash, see [`ToDo::trashed_id`].
+    RestoreTask { task: TaskId },
+    /// Completes a pending task or returns a done task back to pending.
+    CompleteTask { data: ToDoData, task: TaskId },
+    /// Returns the most recently completed task back to pending.
+    ReopenLastCompleted,
+    /// Swaps positions of two tasks.
+    SwapTasks {
+        data: ToDoData,
+        from: TaskId,
+        to: TaskId,
+    },
+    /// Moves the task to the position of another task, tasks between both positions are shifted.
+    ReorderTask {
+        data: ToDoData,
+        from: TaskId,
+        to: TaskId,
+    },
+    /// Cycles the priority of the task through A, B, C and no priority.
+    CyclePriority { data: ToDoData, task: TaskId },
+    /// Raises the priority of the task by the number of letters, a negative number lowers it.
+    /// No priority is below `Z`, the priority stops at `A` and at no priority.
+    ShiftPriority {
+        data: ToDoData,
+        task: TaskId,
+        steps: i8,
+    },
+    /// Removes the priority of the task.
+    ClearPriority { data: ToDoData, task: TaskId },
+    /// Moves the due date of the task by the number of days.
+    DeferTask {
+        data: ToDoData,
+        task: TaskId,
+        days: i64,
+    },
+    /// Sets the due date of the task by the date expression, e.g. `+1w` or `next monday`,
+    /// see [`super::DateExpr`]. The expression is resolved when the action is applied.
+    SetDue {
+        data: ToDoData,
+        task: TaskId,
+        due: String,
+    },
+    /// Moves the due date or the threshold date of the task by the number of days,
+    /// e.g. to plan the task for another day of the week.
+    ShiftDate {
+        data: ToDoData,
+        task: TaskId,
+        tag: DateTag,
+        days: i64,
+    },
+    /// Starts tracking the time spent on the task or stops it, see [`super::Timer`].
+    ToggleTimer { data: ToDoData, task: TaskId },
+    /// Adds the projects and contexts, e.g. `+garden` or `@phone`, the task does not have yet.
+    AddCategories {
+        data: ToDoData,
+        task: TaskId,
+        categories: Vec<String>,
+    },
+    /// Writes the aged priorities to the pending tasks, see [`ToDo::aging_changes`].
+    AgePriorities,
+    /// Moves completed routines back to pending once their interval has passed,
+    /// see [`ToDo::due_routines`].
+    ResetRoutines,
+    /// Applies the decision about the reviewed pending task and marks it as reviewed today.
+    ReviewTask { task: TaskId, action: ReviewAction },
+    /// Flushes all tasks from the trash, so they can no longer be restored.
+    FlushTrash,
+    /// Applies the actions one after another, e.g. to all marked tasks.
+    Batch { actions: Vec<Action> },
+    /// Sets the task as the active task for editing.
+    SetActive { data: ToDoData, task: TaskId },
+    /// Sets the project highlighted in the projects widget, `None` when the widget loses focus.
+    SelectProject { name: Option<String> },
+    /// Toggles the filter of the category.
+    ToggleFilter {
+        category: ToDoCategory,
+        name: String,
+        state: FilterState,
+    },
+    /// Switches the selected filters of the category between matching all and any of them.
+    ToggleFilterMode { category: ToDoCategory },
+    /// Changes the sorting of the list.
+    SetSort { data: ToDoData, sort: TaskSort },
+    /// Toggles whether new tasks are inserted at their sorted position.
+    ToggleSortOnInsert,
+    /// Toggles showing tasks as raw todo.txt lines and adding tasks as typed.
+    ToggleRawLines,
+    /// Switches between all tasks, tasks assigned to the user and tasks assigned to others.
+    CycleMyTasks,
+    /// Clears all filters for a while or restores the cleared filters.
+    ToggleFilters,
+    /// Saves the filters to the filter stack and clears all of them.
+    ClearFilters,
+    /// Saves the filters to the filter stack and replaces them by the given ones.
+    SetFilters {
+        filters: Vec<(ToDoCategory, String, FilterState)>,
+    },
+    /// Saves a copy of the filters to the filter stack.
+    PushFilters,
+    /// Restores the filters saved last to the filter stack.
+    PopFilters,
+    /// Shows only tasks with the subject matching the text, `None` ends the search.
+    SetSearch { search: Option<String> },
+    /// Shows only tasks matching the query of the filter bar, `None` or an invalid
+    /// query clears it, see [`super::TaskQuery`].
+    SetFilterBar { query: Option<String> },
+}
+
+impl ToDo {
+    /// Gets the identity of the active task, `None` if no task is active.
+    pub fn active_id(&self) -> Option<(ToDoData, TaskId)> {
+        let (data, index) = self.state.active?;
+        Some((data, TaskId::new(index, data.get_data(self).get(index)?)))
+    }
+
+    /// Gets the identity of the task shown at the index of the filtered and sorted list.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data (Pending or Done).
+    /// * `index` - The index of the task in the filtered and sorted list.
+    pub fn task_id(&self, data: ToDoData, index: usize) -> Option<TaskId> {
+        let index = self.get_actual_index(data, index)?;
+        Some(TaskId::new(index, &data.get_data(self)[index]))
+    }
+
+    /// Gets the index of the task in the filtered and sorted list.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data (Pending or Done).
+    /// * `id` - The identity of the task.
+    ///
+    /// # Returns
+    ///
+    /// The index of the task or `None` if the task does not exist or is filtered out.
+    pub fn task_index(&self, data: ToDoData, id: &TaskId) -> Option<usize> {
+        let index = self.find_task(data, id)?;
+        self.get_filtered_and_sorted(data)
+            .vec
+            .iter()
+            .position(|(actual, _)| *actual == index)
+    }
+
+    /// Finds the task in the unfiltered list. The task is looked up at its
+    /// index first and by its content if the list has changed since.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data (Pending or Done).
+    /// * `id` - The identity of the task.
+    ///
+    /// # Returns
+    ///
+    /// The index of the task in the unfiltered list or `None` if the task does not exist anymore.
+    pub fn find_task(&self, data: ToDoData, id: &TaskId) -> Option<usize> {
+        let tasks = data.get_data(self);
+        match tasks.get(id.index) {
+            Some(task) if task.to_string() == id.task => Some(id.index),
+            _ => tasks.iter().position(|task| task.to_string() == id.task),
+        }
+    }
+
+    /// Applies the action to the todo list. This is the single place where
+    /// actions emitted by the user interface change the todo list.
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The action to apply.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or an error if a task string cannot be parsed.
+    pub fn apply(&mut self, action: Action) -> Result<(), todo_txt::Error> {
+        log::debug!("Apply action {:?}", action);
+        use Action::*;
+        let change = action.change();
+        match action {
+            NewTask { task } => self.new_task(&task)?,
+            CopyTask { task } => self.copy_task(&task)?,
+            UpdateActive { task } => self.update_active(&task)?,
+            UpdateTask { data, task, line } => {
+                if let Some(index) = self.find(data, &task) {
+                    self.update_task_at(data, index, &line)?
+                }
+            }
+            RemoveTask { data, task } => {
+                if let Some(index) = self.find(data, &task) {
+                    self.stop_timer_at(data, index);
+                    self.remove_task_at(data, index)
+                }
+            }
+            DuplicateTask { data, task } => {
+                if let Some(index) = self.find(data, &task) {
+                    self.duplicate_task_at(data, index)
+                }
+            }
+            RestoreTask { task } => match self.find_trashed(&task) {
+                Some(index) => self.restore_at(index),
+                None => log::warn!("Task {:?} is not in the trash", task),
+            },
+            CompleteTask { data, task } => {
+                if let Some(index) = self.find(data, &task) {
+                    self.stop_timer_at(data, index);
+                    self.move_task_at(data, index)
+                }
+            }
+            ReopenLastCompleted => {
+                if self.reopen_last_completed().is_none() {
+                    log::warn!("There is no completed task to reopen");
+                }
+            }
+            SwapTasks { data, from, to } => {
+                if let (Some(from), Some(to)) = (self.find(data, &from), self.find(data, &to)) {
+                    self.swap_tasks_at(data, from, to)
+                }
+            }
+            ReorderTask { data, from, to } => {
+                if let (Some(from), Some(to)) = (self.find(data, &from), self.find(data, &to)) {
+                    self.reorder_task_at(data, from, to)
+                }
+            }
+            CyclePriority { data, task } => {
+                if let Some(index) = self.find(data, &task) {
+                    self.cycle_priority_at(data, index)
+                }
+            }
+            ShiftPriority { data, task, steps } => {
+                if let Some(index) = self.find(data, &task) {
+                    self.shift_priority_at(data, index, steps)
+                }
+            }
+            ClearPriority { data, task } => {
+                if let Some(index) = self.find(data, &task) {
+                    self.clear_priority_at(data, index)
+                }
+            }
+            DeferTask { data, task, days } => {
+                if let Some(index) = self.find(data, &task) {
+                    self.defer_task_at(data, index, days)
+                }
+            }
+            SetDue { data, task, due } => {
+                if let Some(index) = self.find(data, &task) {
+                    self.set_due_at(data, index, &due)
+                }
+            }
+            ShiftDate {
+                data,
+                task,
+                tag,
+                days,
+            } => {
+                if let Some(index) = self.find(data, &task) {
+                    self.shift_date_at(data, index, tag, days)
+                }
+            }
+            ToggleTimer { data, task } => {
+                if let Some(index) = self.find(data, &task) {
+                    self.toggle_timer_at(data, index)
+                }
+            }
+            AddCategories {
+                data,
+                task,
+                categories,
+            } => {
+                if let Some(index) = self.find(data, &task) {
+                    self.add_categories_at(data, index, &categories)?
+                }
+            }
+            AgePriorities => self.apply_aging(),
+            ResetRoutines => self.reset_routines(),
+            ReviewTask { task, action } => self.review(&task, action)?,
+            FlushTrash => self.flush_trash(),
+            Batch { actions } => self.apply_batch(actions)?,
+            SetActive { data, task } => {
+                if let Some(index) = self.find(data, &task) {
+                    self.state.active = Some((data, index))
+                }
+            }
+            SelectProject { name } => self.selected_project = name,
+            ToggleFilter {
+                category,
+                name,
+                state,
+            } => self.toggle_filter(category, &name, state),
+            ToggleFilterMode { category } => self.toggle_filter_mode(category),
+            SetSort { data, sort } => self.set_sort(data, sort),
+            ToggleSortOnInsert => self.toggle_sort_on_insert(),
+            ToggleRawLines => self.toggle_raw_lines(),
+            CycleMyTasks => self.cycle_my_tasks(),
+            ToggleFilters => self.toggle_filters(),
+            ClearFilters => self.clear_filters(),
+            SetFilters { filters } => self.set_filters(filters),
+            PushFilters => self.push_filters(),
+            PopFilters => self.pop_filters(),
+            SetSearch { search } => self.set_search(search),
+            SetFilterBar { query } => {
+                self.set_filter_bar(query.and_then(|query| query.parse().ok()))
+            }
+        }
+        self.notify(change);
+        Ok(())
+    }
+
+    /// Finds the task of an action, a missing task is logged.
+    fn find(&self, data: ToDoData, id: &TaskId) -> Option<usize> {
+        let index = self.find_task(data, id);
+        if index.is_none() {
+            log::warn!("Task {:?} of the action does not exist", id);
+        }
+        index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use todo_txt::Priority;
+
+    #[test]
+    fn apply() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        todo.apply(Action::NewTask {
+            task: String::from("task 1 +project"),
+        })?;
+        todo.apply(Action::NewTask {
+            task: String::from("task 2"),
+        })?;
+        assert_eq!(todo.pending.len(), 2);
+
+        todo.apply(Action::CompleteTask {
+            data: ToDoData::Pending,
+            task: todo.task_id(ToDoData::Pending, 0).unwrap(),
+        })?;
+        assert_eq!(todo.pending.len(), 1);
+        assert_eq!(todo.done[0].subject, "task 1 +project");
+        assert!(todo.done[0].finished);
+
+        todo.apply(Action::ToggleFilter {
+            category: ToDoCategory::Projects,
+            name: String::from("project"),
+            state: FilterState::Select,
+        })?;
+        assert_eq!(todo.len(ToDoData::Pending), 0);
+        assert_eq!(todo.len(ToDoData::Done), 1);
+
+        todo.apply(Action::RemoveTask {
+            data: ToDoData::Done,
+            task: todo.task_id(ToDoData::Done, 0).unwrap(),
+        })?;
+        assert!(todo.done.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn duplicate() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        for task in ["task 1", "task 2 +project", "task 3"] {
+            todo.new_task(task)?;
+        }
+        todo.set_active(ToDoData::Pending, 2);
+        todo.apply(Action::DuplicateTask {
+            data: ToDoData::Pending,
+            task: todo.task_id(ToDoData::Pending, 1).unwrap(),
+        })?;
+        let subjects: Vec<&str> = todo.pending.iter().map(|t| t.subject.as_str()).collect();
+        assert_eq!(
+            subjects,
+            ["task 1", "task 2 +project", "task 2 +project", "task 3"]
+        );
+        assert_eq!(todo.get_active().unwrap().subject, "task 3");
+        Ok(())
+    }
+
+    #[test]
+    fn apply_to_task_identity() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        for task in ["task 1 +project", "task 2", "task 3 +project"] {
+            todo.new_task(task)?;
+        }
+        todo.toggle_filter(ToDoCategory::Projects, "project", FilterState::Select);
+        let task = todo.task_id(ToDoData::Pending, 1).unwrap();
+        assert_eq!(task.index, 2);
+
+        // The list changes before the action is applied.
+        todo.toggle_filter(ToDoCategory::Projects, "project", FilterState::Select);
+        todo.remove_task(ToDoData::Pending, 0);
+        todo.apply(Action::CompleteTask {
+            data: ToDoData::Pending,
+            task: task.clone(),
+        })?;
+        assert_eq!(todo.pending.len(), 1);
+        assert_eq!(todo.pending[0].subject, "task 2");
+        assert_eq!(todo.done[0].subject, "task 3 +project");
+
+        // The task does not exist anymore.
+        todo.apply(Action::RemoveTask {
+            data: ToDoData::Pending,
+            task,
+        })?;
+        assert_eq!(todo.pending.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn cycle_priority_and_defer() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        todo.new_task("task due:2023-06-30")?;
+        let cycle = |todo: &mut ToDo| {
+            let task = todo.task_id(ToDoData::Pending, 0).unwrap();
+            todo.apply(Action::CyclePriority {
+                data: ToDoData::Pending,
+                task,
+            })
+            .unwrap();
+            todo.pending[0].to_string()
+        };
+        assert!(cycle(&mut todo).starts_with("(A) "));
+        assert!(cycle(&mut todo).starts_with("(B) "));
+        assert!(cycle(&mut todo).starts_with("(C) "));
+        assert!(!cycle(&mut todo).starts_with('('));
+
+        todo.apply(Action::DeferTask {
+            data: ToDoData::Pending,
+            task: todo.task_id(ToDoData::Pending, 0).unwrap(),
+            days: 2,
+        })?;
+        assert_eq!(
+            todo.pending[0].due_date,
+            Some(chrono::NaiveDate::from_ymd_opt(2023, 7, 2).unwrap())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn shift_and_clear_priority() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        todo.new_task("task")?;
+        let apply = |todo: &mut ToDo, action: fn(ToDoData, TaskId) -> Action| {
+            let task = todo.task_id(ToDoData::Pending, 0).unwrap();
+            todo.apply(action(ToDoData::Pending, task)).unwrap();
+            // The priority is kept when the task is saved and loaded again.
+            let line = todo.pending[0].to_string();
+            assert_eq!(Task::from_str(&line).unwrap(), todo.pending[0]);
+            line
+        };
+        let raise = |data, task| Action::ShiftPriority {
+            data,
+            task,
+            steps: 1,
+        };
+        let lower = |data, task| Action::ShiftPriority {
+            data,
+            task,
+            steps: -1,
+        };
+        assert!(apply(&mut todo, raise).starts_with("(Z) "));
+        assert!(apply(&mut todo, raise).starts_with("(Y) "));
+        assert!(apply(&mut todo, lower).starts_with("(Z) "));
+        assert!(!apply(&mut todo, lower).starts_with('('));
+        assert!(!apply(&mut todo, lower).starts_with('('));
+
+        todo.pending[0].priority = Priority::from(1);
+        assert!(apply(&mut todo, raise).starts_with("(A) "));
+        assert!(apply(&mut todo, raise).starts_with("(A) "));
+        let clear = |data, task| Action::ClearPriority { data, task };
+        assert!(!apply(&mut todo, clear).starts_with('('));
+        Ok(())
+    }
+
+    #[test]
+    fn task_index() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        for task in ["b +project", "c", "a +project"] {
+            todo.new_task(task)?;
+        }
+        let task = todo.task_id(ToDoData::Pending, 2).unwrap();
+        assert_eq!(todo.task_index(ToDoData::Pending, &task), Some(2));
+
+        todo.set_sort(ToDoData::Pending, TaskSort::Alphanumeric);
+        assert_eq!(todo.task_index(ToDoData::Pending, &task), Some(0));
+
+        todo.toggle_filter(ToDoCategory::Projects, "project", FilterState::Remove);
+        assert_eq!(todo.task_index(ToDoData::Pending, &task), None);
+        Ok(())
+    }
+}
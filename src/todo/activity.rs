@@ -0,0 +1,146 @@
+use super::ToDo;
+use chrono::NaiveDateTime;
+use std::fmt;
+use todo_txt::Task;
+
+/// The kind of change of a task recorded in the activity log.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ActivityKind {
+    Add,
+    Complete,
+    Reopen,
+    Edit,
+    Delete,
+    Restore,
+}
+
+impl fmt::Display for ActivityKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ActivityKind::Add => "add",
+            ActivityKind::Complete => "complete",
+            ActivityKind::Reopen => "reopen",
+            ActivityKind::Edit => "edit",
+            ActivityKind::Delete => "delete",
+            ActivityKind::Restore => "restore",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// A change of a task recorded in the activity log.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Activity {
+    pub time: NaiveDateTime,
+    pub kind: ActivityKind,
+    /// The task after the change, or the removed task.
+    pub task: String,
+    /// The task before it was edited.
+    pub previous: Option<String>,
+}
+
+impl fmt::Display for Activity {
+    /// Formats the activity as a line of the activity log, e.g.
+    /// `2023-05-01 14:30:00 complete call mom`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.time.format("%Y-%m-%d %H:%M:%S"),
+            self.kind,
+            self.task
+        )?;
+        if let Some(previous) = &self.previous {
+            write!(f, " (was: {previous})")?;
+        }
+        Ok(())
+    }
+}
+
+impl ToDo {
+    /// Records a change of the task, nothing is recorded if the activity log is disabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of the change.
+    /// * `task` - The task after the change, or the removed task.
+    /// * `previous` - The task before it was edited.
+    pub(super) fn record(&mut self, kind: ActivityKind, task: &Task, previous: Option<&Task>) {
+        if self.config.activity_log {
+            self.activity.push(Activity {
+                time: self.config.clock.now(),
+                kind,
+                task: task.to_string(),
+                previous: previous.map(Task::to_string),
+            });
+        }
+    }
+
+    /// Takes the changes recorded since the last call, so they can be appended to the activity log.
+    pub fn take_activity(&mut self) -> Vec<Activity> {
+        std::mem::take(&mut self.activity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::todo::ToDoData;
+    use chrono::NaiveDate;
+    use std::str::FromStr;
+
+    #[test]
+    fn record() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        todo.new_task("call mom")?;
+        assert!(todo.take_activity().is_empty());
+
+        let mut todo = ToDo::default();
+        todo.config.activity_log = true;
+        todo.new_task("call mom")?;
+        todo.new_task("buy milk")?;
+        todo.set_active(ToDoData::Pending, 0);
+        todo.update_active("call mom +family")?;
+        todo.move_task(ToDoData::Pending, 0);
+        todo.move_task(ToDoData::Done, 0);
+        todo.remove_task(ToDoData::Pending, 0);
+        let kinds: Vec<(ActivityKind, String)> = todo
+            .take_activity()
+            .into_iter()
+            .map(|activity| {
+                (
+                    activity.kind,
+                    Task::from_str(&activity.task).unwrap().subject,
+                )
+            })
+            .collect();
+        use ActivityKind::*;
+        assert_eq!(
+            kinds,
+            vec![
+                (Add, String::from("call mom")),
+                (Add, String::from("buy milk")),
+                (Edit, String::from("call mom +family")),
+                (Complete, String::from("call mom +family")),
+                (Reopen, String::from("call mom +family")),
+                (Delete, String::from("buy milk")),
+            ]
+        );
+        assert!(todo.take_activity().is_empty());
+
+        let activity = Activity {
+            time: NaiveDate::from_ymd_opt(2023, 5, 1)
+                .unwrap()
+                .and_hms_opt(14, 30, 0)
+                .unwrap(),
+            kind: Edit,
+            task: String::from("call mom +family"),
+            previous: Some(String::from("call mom")),
+        };
+        assert_eq!(
+            activity.to_string(),
+            "2023-05-01 14:30:00 edit call mom +family (was: call mom)"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,122 @@
+use super::ToDo;
+use chrono::{Datelike, Duration, NaiveDate};
+use std::fmt::{self, Display};
+use todo_txt::Task;
+
+/// Represents a section of the daily agenda, tasks are placed in the sections
+/// by their due dates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AgendaGroup {
+    /// Tasks due before today.
+    Overdue,
+    /// Tasks due today.
+    Today,
+    /// Tasks due tomorrow.
+    Tomorrow,
+    /// Tasks due later in the current week, the week ends on Sunday.
+    ThisWeek,
+    /// Tasks due after the current week.
+    Later,
+    /// Tasks without a due date.
+    NoDueDate,
+}
+
+impl AgendaGroup {
+    /// Gets the section of the agenda the task belongs to.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The task placed in the agenda.
+    /// * `today` - The date the due date is compared with.
+    pub fn of(task: &Task, today: NaiveDate) -> Self {
+        let Some(due) = task.due_date else {
+            return Self::NoDueDate;
+        };
+        let week_end = today + Duration::days(6 - today.weekday().num_days_from_monday() as i64);
+        match due {
+            due if due < today => Self::Overdue,
+            due if due == today => Self::Today,
+            due if Some(due) == today.succ_opt() => Self::Tomorrow,
+            due if due <= week_end => Self::ThisWeek,
+            _ => Self::Later,
+        }
+    }
+}
+
+impl Display for AgendaGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Overdue => "overdue",
+            Self::Today => "today",
+            Self::Tomorrow => "tomorrow",
+            Self::ThisWeek => "this week",
+            Self::Later => "later",
+            Self::NoDueDate => "no due date",
+        })
+    }
+}
+
+impl ToDo {
+    /// Groups the pending tasks into the sections of the agenda.
+    ///
+    /// # Arguments
+    ///
+    /// * `today` - The date due dates are compared with.
+    ///
+    /// # Returns
+    ///
+    /// The non-empty sections in the order of the agenda, the tasks of a section
+    /// are ordered by their due dates.
+    pub fn agenda(&self, today: NaiveDate) -> Vec<(AgendaGroup, Vec<&Task>)> {
+        let mut tasks: Vec<&Task> = self.pending.iter().collect();
+        tasks.sort_by_key(|task| (AgendaGroup::of(task, today), task.due_date));
+        let mut agenda: Vec<(AgendaGroup, Vec<&Task>)> = Vec::new();
+        for task in tasks {
+            let group = AgendaGroup::of(task, today);
+            match agenda.last_mut() {
+                Some((last, tasks)) if *last == group => tasks.push(task),
+                _ => agenda.push((group, vec![task])),
+            }
+        }
+        agenda
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn agenda() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        todo.new_task("clean the desk")?;
+        todo.new_task("plan the trip due:2024-07-20")?;
+        todo.new_task("water plants due:2024-07-05")?;
+        todo.new_task("pay rent due:2024-06-30")?;
+        todo.new_task("call mom due:2024-07-02")?;
+        todo.new_task("write the report due:2024-07-03")?;
+        todo.new_task("buy milk due:2024-07-07")?;
+        // 2024-07-02 is a Tuesday.
+        let today = NaiveDate::from_ymd_opt(2024, 7, 2).unwrap();
+        let agenda: Vec<(String, Vec<&str>)> = todo
+            .agenda(today)
+            .into_iter()
+            .map(|(group, tasks)| {
+                let tasks = tasks.iter().map(|task| task.subject.as_str()).collect();
+                (group.to_string(), tasks)
+            })
+            .collect();
+        assert_eq!(
+            agenda,
+            [
+                (String::from("overdue"), vec!["pay rent"]),
+                (String::from("today"), vec!["call mom"]),
+                (String::from("tomorrow"), vec!["write the report"]),
+                (String::from("this week"), vec!["water plants", "buy milk"]),
+                (String::from("later"), vec!["plan the trip"]),
+                (String::from("no due date"), vec!["clean the desk"]),
+            ]
+        );
+        Ok(())
+    }
+}
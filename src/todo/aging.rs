@@ -0,0 +1,236 @@
+use super::{ActivityKind, TaskList, TaskSort, ToDo};
+use crate::error::{ToDoError, ToDoRes};
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use todo_txt::{Priority, Task};
+
+/// Represents the aging of priorities by the age of pending tasks.
+///
+/// Unprioritized tasks are boosted into the priority range, e.g. `C-B`: a task
+/// older than the boost period is sorted as `(C)` and one priority higher for every
+/// further period, up to `(B)`. Priorities above the range are decayed one priority
+/// for every decay period down to the top of the range. The age is counted from
+/// the creation date, tasks without it do not age.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PriorityAging {
+    boost_days: i64,
+    decay_days: i64,
+    low: u8,
+    high: u8,
+}
+
+impl PriorityAging {
+    /// Creates a new `PriorityAging`.
+    ///
+    /// # Arguments
+    ///
+    /// * `boost_days` - The boost period in days, 0 disables boosting.
+    /// * `decay_days` - The decay period in days, 0 disables decaying.
+    /// * `range` - The lowest and the highest priority of the range, e.g. `C-B`.
+    pub fn new(boost_days: usize, decay_days: usize, range: &str) -> ToDoRes<Self> {
+        let err = || ToDoError::ParseAgingRange(range.to_string());
+        let priority = |letter: &str| {
+            let mut chars = letter.trim().chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if c.is_ascii_uppercase() => {
+                    Priority::try_from(c).map(u8::from).map_err(|_| err())
+                }
+                _ => Err(err()),
+            }
+        };
+        let (low, high) = range.split_once('-').ok_or_else(err)?;
+        let (low, high) = (priority(low)?, priority(high)?);
+        if low < high {
+            return Err(err());
+        }
+        Ok(Self {
+            boost_days: boost_days as i64,
+            decay_days: decay_days as i64,
+            low,
+            high,
+        })
+    }
+
+    /// Checks whether boosting or decaying is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.boost_days > 0 || self.decay_days > 0
+    }
+
+    /// Gets the priority the task is sorted by.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The task.
+    /// * `today` - The date the age of the task is computed to.
+    ///
+    /// # Returns
+    ///
+    /// The aged priority, the priority of the task if it does not age.
+    pub fn priority(&self, task: &Task, today: NaiveDate) -> Priority {
+        let age = match task.create_date {
+            Some(created) if !task.finished => (today - created).num_days(),
+            _ => return task.priority.clone(),
+        };
+        let priority = u8::from(task.priority.clone()) as i64;
+        if task.priority.is_lowest() && self.boost_days > 0 && age >= self.boost_days {
+            let steps = age / self.boost_days - 1;
+            return Priority::from((self.low as i64 - steps).max(self.high as i64) as u8);
+        }
+        if priority < self.high as i64 && self.decay_days > 0 {
+            let steps = age / self.decay_days;
+            return Priority::from((priority + steps).min(self.high as i64) as u8);
+        }
+        task.priority.clone()
+    }
+
+    /// Sorts the task list by the aged priorities, the tasks are not changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_list` - The sorted task list.
+    /// * `sort` - The sorting criteria to apply.
+    /// * `ties` - The sorting criteria used to break ties.
+    /// * `today` - The date the age of the tasks is computed to.
+    pub fn sort(
+        &self,
+        task_list: &mut TaskList,
+        sort: TaskSort,
+        ties: &[TaskSort],
+        today: NaiveDate,
+    ) {
+        if !self.is_enabled() || matches!(sort, TaskSort::None | TaskSort::Reverse) {
+            return task_list.sort_with_ties(sort, ties);
+        }
+        let aged: HashMap<usize, Task> = task_list
+            .vec
+            .iter()
+            .map(|(index, task)| {
+                let mut task = (*task).clone();
+                task.priority = self.priority(&task, today);
+                (*index, task)
+            })
+            .collect();
+        task_list
+            .vec
+            .sort_by(|(a, _), (b, _)| sort.compare_with_ties(ties, &aged[a], &aged[b]));
+    }
+}
+
+impl ToDo {
+    /// Gets the indexes of the pending tasks whose aged priority differs from their
+    /// priority, together with the aged priority.
+    fn aged_priorities(&self) -> Vec<(usize, Priority)> {
+        let today = self.config.clock.today();
+        self.pending
+            .iter()
+            .enumerate()
+            .filter_map(|(index, task)| {
+                let priority = self.config.priority_aging.priority(task, today);
+                (priority != task.priority).then_some((index, priority))
+            })
+            .collect()
+    }
+
+    /// Describes the priorities written by [`super::Action::AgePriorities`].
+    ///
+    /// # Returns
+    ///
+    /// Descriptions of the changes, e.g. `clean the garage: priority (C)`.
+    pub fn aging_changes(&self) -> Vec<String> {
+        self.aged_priorities()
+            .into_iter()
+            .map(|(index, priority)| {
+                format!("{}: priority ({priority})", self.pending[index].subject)
+            })
+            .collect()
+    }
+
+    /// Writes the aged priorities to the pending tasks, so the aging the lists are
+    /// sorted by is kept in the todo list.
+    pub(super) fn apply_aging(&mut self) {
+        let aged = self.aged_priorities();
+        for (index, priority) in &aged {
+            let previous = self.pending[*index].clone();
+            self.pending[*index].priority = priority.clone();
+            let task = self.pending[*index].clone();
+            self.record(ActivityKind::Edit, &task, Some(&previous));
+        }
+        if !aged.is_empty() {
+            self.version += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::todo::{Action, ToDoData};
+
+    #[test]
+    fn parse() {
+        assert!(PriorityAging::new(7, 30, "C-B").is_ok());
+        assert!(PriorityAging::new(7, 30, " C - C ").is_ok());
+        for range in ["B-C", "C", "c-b", "CD-B", "-B"] {
+            assert_eq!(
+                PriorityAging::new(7, 30, range),
+                Err(ToDoError::ParseAgingRange(range.to_string()))
+            );
+        }
+        assert!(!PriorityAging::new(0, 0, "C-B").unwrap().is_enabled());
+    }
+
+    #[test]
+    fn aging() -> ToDoRes<()> {
+        let mut todo = ToDo::default();
+        todo.config.priority_aging = PriorityAging::new(7, 30, "C-B")?;
+        todo.config.pending_sort = TaskSort::Priority;
+        let today = todo.config.clock.today();
+        let created = |days| (today - chrono::Duration::days(days)).format("%Y-%m-%d");
+        todo.new_task(&format!("(A) {} ignored report", created(40)))
+            .unwrap();
+        todo.new_task(&format!("(B) {} fresh call", created(2)))
+            .unwrap();
+        todo.new_task(&format!("{} clean the garage", created(10)))
+            .unwrap();
+        todo.new_task(&format!("{} water plants", created(60)))
+            .unwrap();
+        todo.new_task(&format!("{} buy milk", created(3))).unwrap();
+        todo.new_task("pay rent").unwrap();
+        let shown = |todo: &ToDo| {
+            todo.get_filtered_and_sorted(ToDoData::Pending)
+                .vec
+                .iter()
+                .map(|(_, task)| task.subject.clone())
+                .collect::<Vec<_>>()
+        };
+
+        // The decayed and boosted tasks are sorted by their aged priorities, (B) keeps its place.
+        assert_eq!(
+            shown(&todo),
+            [
+                "ignored report",
+                "fresh call",
+                "water plants",
+                "clean the garage",
+                "buy milk",
+                "pay rent"
+            ]
+        );
+        assert_eq!(todo.pending[0].priority, 0);
+        assert!(todo.pending[2].priority.is_lowest());
+
+        assert_eq!(
+            todo.aging_changes(),
+            [
+                "ignored report: priority (B)",
+                "clean the garage: priority (C)",
+                "water plants: priority (B)",
+            ]
+        );
+        todo.apply(Action::AgePriorities).unwrap();
+        assert_eq!(todo.pending[0].priority, 1);
+        assert_eq!(todo.pending[2].priority, 2);
+        assert!(todo.aging_changes().is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use super::{
+    reminder::{task_notices, task_reminder},
+    Change, ToDo,
+};
+use chrono::{NaiveDate, NaiveDateTime};
+
+impl ToDo {
+    /// Gets the next moment a pending task changes with the time: its threshold
+    /// date or due date starts, its due date passes, its reminder or a notification
+    /// before its deadline comes or working hours of contexts start or end.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The current time, moments at this time or before are skipped.
+    ///
+    /// # Returns
+    ///
+    /// The earliest moment after `now` or `None` if no task changes with the time.
+    pub fn next_alarm(&self, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        let start = |date: NaiveDate| date.and_hms_opt(0, 0, 0);
+        self.pending
+            .iter()
+            .flat_map(|task| {
+                [
+                    task.threshold_date.and_then(start),
+                    task.due_date.and_then(start),
+                    task.due_date.and_then(|due| due.succ_opt()).and_then(start),
+                    task_reminder(task),
+                ]
+                .into_iter()
+                .flatten()
+                .chain(task_notices(task))
+            })
+            .filter(|alarm| *alarm > now)
+            .chain(self.config.context_hours.next_change(now))
+            .min()
+    }
+
+    /// Gets pending tasks whose threshold date or due date came or whose due date
+    /// passed when the date changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The date of the previous check.
+    /// * `to` - The current date.
+    ///
+    /// # Returns
+    ///
+    /// The descriptions of the tasks, e.g. `Overdue: call mom`.
+    pub fn date_alarms(&self, from: NaiveDate, to: NaiveDate) -> Vec<String> {
+        self.pending
+            .iter()
+            .filter_map(|task| {
+                let label = match (task.due_date, task.threshold_date) {
+                    (Some(due), _) if from <= due && due < to => "Overdue",
+                    (Some(due), _) if from < due && due == to => "Due today",
+                    (_, Some(threshold)) if from < threshold && threshold == to => "Starts today",
+                    _ => return None,
+                };
+                Some(format!("{label}: {}", task.subject))
+            })
+            .collect()
+    }
+
+    /// Notifies the subscribers that the current date has changed,
+    /// e.g. overdue tasks or task queries relative to today.
+    pub fn date_changed(&mut self) {
+        self.notify(Change::Date);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 7, day).unwrap()
+    }
+
+    #[test]
+    fn alarms() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        assert_eq!(
+            todo.next_alarm(date(1).and_hms_opt(12, 0, 0).unwrap()),
+            None
+        );
+
+        todo.new_task("pay rent due:2024-07-01")?;
+        todo.new_task("call the client remind:2024-07-01T14:30")?;
+        todo.new_task("plan the trip t:2024-07-02")?;
+        todo.new_task("write the report due:2024-07-03")?;
+        let next = |hour: u32| todo.next_alarm(date(1).and_hms_opt(hour, 0, 0).unwrap());
+        assert_eq!(next(12), date(1).and_hms_opt(14, 30, 0));
+        assert_eq!(next(15), date(2).and_hms_opt(0, 0, 0));
+
+        assert_eq!(todo.date_alarms(date(1), date(1)), Vec::<String>::new());
+        assert_eq!(
+            todo.date_alarms(date(1), date(2)),
+            ["Overdue: pay rent", "Starts today: plan the trip"]
+        );
+        assert_eq!(
+            todo.date_alarms(date(2), date(3)),
+            ["Due today: write the report"]
+        );
+
+        let changes = todo.subscribe();
+        todo.date_changed();
+        assert_eq!(changes.try_recv(), Ok(Change::Date));
+        Ok(())
+    }
+}
@@ -0,0 +1,349 @@
+use super::{is_scratch, parse_task, search::fuzzy_match, Change, Routine, ToDo, ToDoData};
+use chrono::NaiveDate;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, ErrorKind, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::mpsc::Sender,
+};
+use todo_txt::Task;
+
+/// Reads the tasks archived in the done file. A missing file has no tasks,
+/// lines that cannot be parsed are skipped.
+///
+/// # Arguments
+///
+/// * `path` - The path to the done file.
+pub fn read_archive(path: impl AsRef<Path>) -> io::Result<Vec<Task>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .filter_map(parse_archived)
+        .collect())
+}
+
+/// Streams the archived tasks whose subject matches the search, see [`fuzzy_match`].
+/// The done file is read line by line, so only the matches are kept in memory.
+///
+/// # Arguments
+///
+/// * `path` - The path to the done file, a missing file has no tasks.
+/// * `search` - The searched pattern, an empty pattern matches all tasks.
+/// * `tx` - The matched tasks are sent to it in the order of the file, reading stops
+///   when the receiver is dropped.
+pub fn search_archive(path: impl AsRef<Path>, search: &str, tx: Sender<Task>) -> io::Result<()> {
+    let reader = match File::open(path) {
+        Ok(file) => BufReader::new(file),
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    for line in reader.lines() {
+        let line = line?;
+        let Some(task) = Some(line.trim())
+            .filter(|line| !line.is_empty())
+            .and_then(parse_archived)
+        else {
+            continue;
+        };
+        if fuzzy_match(search, &task.subject).is_some() && tx.send(task).is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Parses the archived line, lines that cannot be parsed are logged and skipped.
+pub(super) fn parse_archived(line: &str) -> Option<Task> {
+    match parse_task(line) {
+        Ok(task) => Some(task),
+        Err(e) => {
+            log::warn!("Archived task cannot be load due {e}: {line}");
+            None
+        }
+    }
+}
+
+/// Gets the month the archived line was completed in, e.g. `2023-05`,
+/// without parsing the whole task.
+fn completion_month(line: &str) -> Option<String> {
+    let date = line.strip_prefix("x ")?.get(..10)?;
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+    Some(date[..7].to_string())
+}
+
+/// The tasks of a month in the done file, see [`ArchiveIndex`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArchiveMonth {
+    /// The month the tasks were completed in, e.g. `2023-05`,
+    /// `None` for tasks without the completion date.
+    pub month: Option<String>,
+    /// The positions of the lines of the tasks in the done file.
+    offsets: Vec<u64>,
+}
+
+impl ArchiveMonth {
+    /// Gets the name of the month shown to the user.
+    pub fn name(&self) -> &str {
+        self.month.as_deref().unwrap_or("no date")
+    }
+
+    /// Gets the number of tasks completed in the month.
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// Checks whether no task was completed in the month.
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+}
+
+/// The tasks of the done file grouped by the month they were completed in, the latest month
+/// first and tasks without the completion date last.
+///
+/// Building the index reads only the completion dates, the tasks of a month are parsed
+/// when the month is loaded, so browsing years of completions does not load all of them.
+#[derive(Clone, Debug, Default)]
+pub struct ArchiveIndex {
+    path: PathBuf,
+    months: Vec<ArchiveMonth>,
+}
+
+impl ArchiveIndex {
+    /// Reads the completion months of the tasks in the done file. A missing file has no tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the done file.
+    pub fn read(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut index = Self {
+            path: path.as_ref().to_path_buf(),
+            months: Vec::new(),
+        };
+        let mut reader = match File::open(&path) {
+            Ok(file) => BufReader::new(file),
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(index),
+            Err(e) => return Err(e),
+        };
+        let mut months: BTreeMap<Option<String>, Vec<u64>> = BTreeMap::new();
+        let mut offset = 0;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = reader.read_line(&mut line)?;
+            if read == 0 {
+                break;
+            }
+            let task = line.trim();
+            if !task.is_empty() {
+                months
+                    .entry(completion_month(task))
+                    .or_default()
+                    .push(offset);
+            }
+            offset += read as u64;
+        }
+        // `None` is the lowest key, so it is the last one in the reversed order.
+        index.months = months
+            .into_iter()
+            .rev()
+            .map(|(month, offsets)| ArchiveMonth { month, offsets })
+            .collect();
+        Ok(index)
+    }
+
+    /// Gets the months of the archive, the latest month first.
+    pub fn months(&self) -> &[ArchiveMonth] {
+        &self.months
+    }
+
+    /// Parses the tasks completed in the month, lines that cannot be parsed are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the month in [`ArchiveIndex::months`].
+    pub fn load(&self, index: usize) -> io::Result<Vec<Task>> {
+        let Some(month) = self.months.get(index) else {
+            return Ok(Vec::new());
+        };
+        let mut reader = BufReader::new(File::open(&self.path)?);
+        let mut tasks = Vec::with_capacity(month.len());
+        let mut line = String::new();
+        for offset in &month.offsets {
+            reader.seek(SeekFrom::Start(*offset))?;
+            line.clear();
+            reader.read_line(&mut line)?;
+            tasks.extend(parse_archived(line.trim()));
+        }
+        Ok(tasks)
+    }
+}
+
+impl ToDo {
+    /// Gets the completed lines loaded from the todo file.
+    pub fn todo_file_done(&self) -> &[String] {
+        &self.todo_file_done
+    }
+
+    /// Sets the completed lines loaded from the todo file, they are saved to the todo file
+    /// again instead of the archive file, see [`ToDo::split_done`].
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - The completed lines of the todo file.
+    pub fn set_todo_file_done(&mut self, lines: Vec<String>) {
+        self.todo_file_done = lines;
+    }
+
+    /// Splits the completed tasks by the file they are saved to when the todo file has
+    /// an archive file (`archive_path`). Completed routines and the completed lines loaded
+    /// from the todo file stay in the todo file, the other completed tasks are saved to
+    /// the archive file. A task completed again after it was edited or reopened
+    /// goes to the archive file.
+    ///
+    /// # Returns
+    ///
+    /// The completed tasks of the todo file and the tasks of the archive file.
+    pub fn split_done(&self) -> (Vec<&Task>, Vec<&Task>) {
+        let mut lines: HashMap<&str, usize> = HashMap::new();
+        for line in &self.todo_file_done {
+            *lines.entry(line).or_default() += 1;
+        }
+        self.done.iter().partition(|task| {
+            if Routine::of(task).is_some() {
+                return true;
+            }
+            match lines.get_mut(task.to_string().as_str()) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    true
+                }
+                _ => false,
+            }
+        })
+    }
+
+    /// Moves the completed tasks to the end of the done file as `todo.sh archive` does.
+    /// Routines stay in the list, they are reset to pending again. Scratch tasks stay
+    /// in the list as well, they are never written to a file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the done file, it is created if it does not exist.
+    ///
+    /// # Returns
+    ///
+    /// The number of archived tasks or an error if the done file cannot be written,
+    /// the todo list is not changed then.
+    pub fn archive_done(&mut self, path: impl AsRef<Path>) -> io::Result<usize> {
+        let (routines, archived): (Vec<Task>, Vec<Task>) = self
+            .done
+            .drain(..)
+            .partition(|task| Routine::of(task).is_some() || is_scratch(task));
+        self.done = routines;
+        if archived.is_empty() {
+            return Ok(0);
+        }
+        let mut content = String::new();
+        // Do not join the first task with the last line of a file without the final newline.
+        if fs::read(&path).is_ok_and(|file| !file.is_empty() && !file.ends_with(b"\n")) {
+            content.push('\n');
+        }
+        for task in &archived {
+            content += &format!("{task}\n");
+        }
+        let written = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(content.as_bytes()));
+        if let Err(e) = written {
+            self.done.extend(archived);
+            return Err(e);
+        }
+        if matches!(self.state.active, Some((ToDoData::Done, _))) {
+            self.state.active = None;
+        }
+        self.version += 1;
+        self.notify(Change::Tasks);
+        Ok(archived.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env::temp_dir, process};
+
+    #[test]
+    fn archive_done() -> io::Result<()> {
+        let path = temp_dir().join(format!("todotxt-tui-archive-done-{}.txt", process::id()));
+        fs::write(&path, "x 2023-05-01 old task")?;
+        let mut todo = ToDo::default();
+        for task in [
+            "write the report",
+            "x 2023-05-02 call mom",
+            "x 2023-05-03 stretch routine:daily",
+            "x 2023-05-03 water plants",
+        ] {
+            todo.add_task(parse_task(task).unwrap());
+        }
+
+        assert_eq!(todo.archive_done(&path)?, 2);
+        assert_eq!(todo.pending.len(), 1);
+        assert_eq!(todo.done.len(), 1);
+        assert!(Routine::of(&todo.done[0]).is_some());
+        assert_eq!(
+            fs::read_to_string(&path)?,
+            "x 2023-05-01 old task\nx 2023-05-02 call mom\nx 2023-05-03 water plants\n"
+        );
+        assert_eq!(todo.archive_done(&path)?, 0);
+
+        let archived = read_archive(&path)?;
+        assert_eq!(archived.len(), 3);
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        search_archive(&path, "cmom", tx)?;
+        let found: Vec<String> = rx.iter().map(|task| task.subject).collect();
+        assert_eq!(found, ["call mom"]);
+        assert!(archived.iter().all(|task| task.finished));
+        fs::remove_file(&path)?;
+        assert!(read_archive(&path)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn archive_index() -> io::Result<()> {
+        let path = temp_dir().join(format!("todotxt-tui-archive-index-{}.txt", process::id()));
+        fs::write(
+            &path,
+            "x 2023-04-28 pay rent\n\nx 2023-05-02 call mom\nx done without date\n\
+             x 2023-05-03 water plants +home\nx 2023-04-30 clean the desk",
+        )?;
+        let index = ArchiveIndex::read(&path)?;
+        let months: Vec<(&str, usize)> = index
+            .months()
+            .iter()
+            .map(|month| (month.name(), month.len()))
+            .collect();
+        assert_eq!(months, vec![("2023-05", 2), ("2023-04", 2), ("no date", 1)]);
+
+        let tasks = index.load(0)?;
+        assert_eq!(tasks[0].subject, "call mom");
+        assert_eq!(tasks[1].subject, "water plants +home");
+        assert_eq!(index.load(1)?[1].subject, "clean the desk");
+        assert_eq!(index.load(2)?[0].subject, "done without date");
+        assert!(index.load(3)?.is_empty());
+
+        fs::remove_file(&path)?;
+        assert!(ArchiveIndex::read(&path)?.months().is_empty());
+        Ok(())
+    }
+}
@@ -34,7 +34,7 @@ pub fn autocomplete(todo: &ToDo, input: &str) -> Option<String> {
         return None;
     }
 
-    let mut new_act = list[0].as_str();
+    let mut new_act = list[0];
     if list.len() != 1 {
         list.iter()
             .skip(1)
@@ -45,10 +45,134 @@ pub fn autocomplete(todo: &ToDo, input: &str) -> Option<String> {
     }
 }
 
+/// A project, context or hashtag typed at the cursor, e.g. `+gar` in `water plants +gar`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CategoryToken {
+    /// The byte range of the whole word in the input, the part after the cursor included.
+    start: usize,
+    end: usize,
+    pub category: ToDoCategory,
+    /// The part of the name typed between the prefix and the cursor.
+    pub pattern: String,
+}
+
+impl CategoryToken {
+    /// Finds the token the cursor is in.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The typed text.
+    /// * `cursor` - The position of the cursor in characters.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the word at the cursor does not start with `+`, `@` or `#`.
+    pub fn at(input: &str, cursor: usize) -> Option<Self> {
+        let cursor = input
+            .char_indices()
+            .nth(cursor)
+            .map_or(input.len(), |(i, _)| i);
+        let start = input[..cursor]
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+            .map_or(0, |(i, c)| i + c.len_utf8());
+        let end = input[cursor..]
+            .find(char::is_whitespace)
+            .map_or(input.len(), |i| cursor + i);
+        let mut chars = input[start..cursor].chars();
+        let category = match chars.next()? {
+            '+' => ToDoCategory::Projects,
+            '@' => ToDoCategory::Contexts,
+            '#' => ToDoCategory::Hashtags,
+            _ => return None,
+        };
+        Some(Self {
+            start,
+            end,
+            category,
+            pattern: chars.as_str().to_string(),
+        })
+    }
+
+    /// Gets the names of the category starting with the typed part, the name typed
+    /// in full is left out.
+    pub fn completions(&self, todo: &ToDo) -> Vec<String> {
+        todo.get_categories(self.category)
+            .start_with(&self.pattern)
+            .into_iter()
+            .filter(|name| *name != self.pattern)
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Replaces the word of the token with the completed name followed by a space.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The typed text the token was found in.
+    /// * `name` - The completed name without the prefix.
+    ///
+    /// # Returns
+    ///
+    /// The completed text and the position of the cursor after the space in characters.
+    pub fn complete(&self, input: &str, name: &str) -> (String, usize) {
+        let mut value = format!(
+            "{}{}{name}",
+            &input[..self.start],
+            &input[self.start..self.start + 1]
+        );
+        let rest = &input[self.end..];
+        if !rest.starts_with(char::is_whitespace) {
+            value.push(' ');
+        }
+        let cursor = value.chars().count() + rest.starts_with(char::is_whitespace) as usize;
+        value.push_str(rest);
+        (value, cursor)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn token() {
+        let mut todo = ToDo::default();
+        todo.new_task("t +garden +garage @home #idea").unwrap();
+        todo.new_task("t +gym @phone").unwrap();
+
+        let input = "water +ga plants";
+        let token = CategoryToken::at(input, 9).unwrap();
+        assert_eq!(token.category, ToDoCategory::Projects);
+        assert_eq!(token.pattern, "ga");
+        assert_eq!(token.completions(&todo), ["garage", "garden"]);
+        assert_eq!(
+            token.complete(input, "garden"),
+            (String::from("water +garden plants"), 14)
+        );
+
+        // The part of the word after the cursor is replaced as well.
+        let token = CategoryToken::at("call @ph", 6).unwrap();
+        assert_eq!(token.pattern, "");
+        assert_eq!(token.completions(&todo), ["home", "phone"]);
+        assert_eq!(
+            token.complete("call @ph", "phone"),
+            (String::from("call @phone "), 12)
+        );
+        assert_eq!(
+            CategoryToken::at("#id", 3).unwrap().completions(&todo),
+            ["idea"]
+        );
+        assert!(CategoryToken::at("#idea", 5)
+            .unwrap()
+            .completions(&todo)
+            .is_empty());
+        assert_eq!(CategoryToken::at("water +ga plants", 12), None);
+        assert_eq!(CategoryToken::at("", 0), None);
+        assert_eq!(CategoryToken::at("čaj +ga", 7).unwrap().pattern, "ga");
+    }
+
     #[test]
     fn autocomplete_basic() {
         let mut todo = ToDo::default();
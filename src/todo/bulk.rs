@@ -0,0 +1,111 @@
+use super::{parse_task, Action, ActivityKind, ToDo, ToDoData};
+
+impl ToDo {
+    /// Applies the actions one after another, e.g. the same action to all marked tasks.
+    /// The tasks are found by their content when the previous actions have moved them,
+    /// e.g. when completing a recurring task has added its next occurrence.
+    ///
+    /// # Arguments
+    ///
+    /// * `actions` - The actions to apply.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or the error of the first action that failed,
+    /// the actions before it stay applied.
+    pub fn apply_batch(&mut self, actions: Vec<Action>) -> Result<(), todo_txt::Error> {
+        log::info!("Apply a batch of {} actions", actions.len());
+        actions
+            .into_iter()
+            .try_for_each(|action| self.apply(action))
+    }
+
+    /// Adds the projects and contexts to the task, those the task has already are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data where the task is located.
+    /// * `index` - The index of the task in the unfiltered data.
+    /// * `categories` - The projects and contexts, e.g. `+garden` or `@phone`.
+    pub(super) fn add_categories_at(
+        &mut self,
+        data: ToDoData,
+        index: usize,
+        categories: &[String],
+    ) -> Result<(), todo_txt::Error> {
+        let previous = data.get_data(self)[index].clone();
+        let mut line = previous.to_string();
+        for category in categories {
+            let present = match category.split_at(1) {
+                ("+", project) => previous.projects().iter().any(|p| p == project),
+                ("@", context) => previous.contexts().iter().any(|c| c == context),
+                _ => true,
+            };
+            if !present && !line.split_whitespace().any(|word| word == category) {
+                line.push(' ');
+                line.push_str(category);
+            }
+        }
+        let task = parse_task(&line)?;
+        if task == previous {
+            return Ok(());
+        }
+        data.get_data_mut(self)[index] = task.clone();
+        self.record(ActivityKind::Edit, &task, Some(&previous));
+        self.rename_meta(&previous, &task);
+        self.rename_timer(&previous, &task);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::todo::{Action, TaskId, ToDo, ToDoData};
+
+    fn ids(todo: &ToDo, indices: &[usize]) -> Vec<TaskId> {
+        indices
+            .iter()
+            .map(|index| TaskId::new(*index, &todo.pending[*index]))
+            .collect()
+    }
+
+    #[test]
+    fn batch() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        todo.new_task("water plants @home rec:1d due:2024-07-01")?;
+        todo.new_task("call mom")?;
+        todo.new_task("buy milk +shopping")?;
+        todo.new_task("fix the bike")?;
+
+        let actions = ids(&todo, &[0, 2, 3])
+            .into_iter()
+            .map(|task| Action::AddCategories {
+                data: ToDoData::Pending,
+                task,
+                categories: vec![String::from("+shopping"), String::from("@home")],
+            })
+            .collect();
+        todo.apply(Action::Batch { actions })?;
+        assert_eq!(todo.pending[0].subject, "water plants @home +shopping");
+        assert_eq!(todo.pending[1].subject, "call mom");
+        assert_eq!(todo.pending[2].subject, "buy milk +shopping @home");
+        assert_eq!(todo.pending[3].subject, "fix the bike +shopping @home");
+
+        // The recurring task adds its next occurrence, the next tasks are found anyway.
+        let actions = ids(&todo, &[0, 1, 2])
+            .into_iter()
+            .map(|task| Action::CompleteTask {
+                data: ToDoData::Pending,
+                task,
+            })
+            .collect();
+        todo.apply(Action::Batch { actions })?;
+        assert_eq!(todo.done.len(), 3);
+        assert_eq!(todo.pending.len(), 2);
+        assert!(todo
+            .pending
+            .iter()
+            .any(|task| task.subject == "water plants @home +shopping"));
+        Ok(())
+    }
+}
@@ -0,0 +1,397 @@
+use super::{FilterState, TagKey, ToDoCategory, ToDoData, ToDoState};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    ops::Bound,
+};
+use todo_txt::Task;
+
+/// Inverted index of one category: the names of the category mapped to the ascending
+/// indices of the tasks having them.
+type Postings = BTreeMap<String, Vec<usize>>;
+
+/// Index of the tasks of one ToDo data (Pending or Done).
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct DataIndex {
+    len: usize,
+    /// The postings of the categories in the order of [`ToDoCategory::get_all`].
+    categories: [Postings; 4],
+    /// The postings of the values of `key:value` tags by the keys.
+    tags: BTreeMap<String, Postings>,
+}
+
+/// Adds the task to the postings of the name.
+fn add(postings: &mut Postings, name: &str, task: usize) {
+    match postings.get_mut(name) {
+        // A task can repeat the same name, e.g. `+work +work`.
+        Some(tasks) if tasks.last() == Some(&task) => {}
+        Some(tasks) => tasks.push(task),
+        None => {
+            postings.insert(name.to_string(), vec![task]);
+        }
+    }
+}
+
+impl DataIndex {
+    fn new(tasks: &[Task]) -> Self {
+        let mut index = Self::default();
+        for task in tasks {
+            index.push(task);
+        }
+        index
+    }
+
+    fn push(&mut self, task: &Task) {
+        for (postings, category) in self.categories.iter_mut().zip(ToDoCategory::get_all()) {
+            for name in category.get_data(task).iter() {
+                add(postings, name, self.len);
+            }
+        }
+        for (key, value) in TagKey::task_tags(task) {
+            if !self.tags.contains_key(key) {
+                self.tags.insert(key.to_string(), Postings::new());
+            }
+            add(self.tags.get_mut(key).unwrap(), &value, self.len);
+        }
+        self.len += 1;
+    }
+}
+
+/// Inverted indexes of projects, contexts, hashtags and assignees to the tasks having
+/// them. Filters and category lists are computed from the postings of the indexed names
+/// instead of scanning all tasks on every redraw.
+///
+/// The index is tied to the version of the ToDo data it was built from. Appended tasks
+/// are added to it, other changes make it outdated and it is built again on the next use.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CategoryIndex {
+    version: usize,
+    pending: DataIndex,
+    done: DataIndex,
+}
+
+impl CategoryIndex {
+    /// Builds the index of the tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The version of the ToDo data the tasks belong to.
+    /// * `pending` - The pending tasks.
+    /// * `done` - The done tasks.
+    pub fn new(version: usize, pending: &[Task], done: &[Task]) -> Self {
+        Self {
+            version,
+            pending: DataIndex::new(pending),
+            done: DataIndex::new(done),
+        }
+    }
+
+    fn data(&self, data: ToDoData) -> &DataIndex {
+        match data {
+            ToDoData::Pending => &self.pending,
+            ToDoData::Done => &self.done,
+        }
+    }
+
+    fn postings(&self, data: ToDoData, category: ToDoCategory) -> &Postings {
+        static NO_POSTINGS: Postings = BTreeMap::new();
+        let index = self.data(data);
+        match category {
+            ToDoCategory::Tag(key) => index.tags.get(key.as_str()).unwrap_or(&NO_POSTINGS),
+            category => {
+                let slot = ToDoCategory::get_all().iter().position(|c| *c == category);
+                &index.categories[slot.unwrap()]
+            }
+        }
+    }
+
+    /// Checks whether the index describes the tasks of the given version.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The version of the ToDo data.
+    /// * `pending` - The number of pending tasks.
+    /// * `done` - The number of done tasks.
+    pub fn is_current(&self, version: usize, pending: usize, done: usize) -> bool {
+        self.version == version && self.pending.len == pending && self.done.len == done
+    }
+
+    /// Adds a task appended to the end of the data, the index then describes the new version.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The version of the ToDo data with the appended task.
+    /// * `data` - The type of ToDo data the task was appended to.
+    /// * `task` - The appended task.
+    pub fn push(&mut self, version: usize, data: ToDoData, task: &Task) {
+        self.version = version;
+        match data {
+            ToDoData::Pending => self.pending.push(task),
+            ToDoData::Done => self.done.push(task),
+        }
+    }
+
+    /// Gets the names of the category with the indices of the tasks having them.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data.
+    /// * `category` - The type of category.
+    pub fn names(
+        &self,
+        data: ToDoData,
+        category: ToDoCategory,
+    ) -> impl Iterator<Item = (&String, &[usize])> {
+        self.postings(data, category)
+            .iter()
+            .map(|(name, tasks)| (name, tasks.as_slice()))
+    }
+
+    /// Gets the indices of the tasks matching the category filters of the state,
+    /// the result is the same as [`ToDoState::filter_out`] applied to every task.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data to filter.
+    /// * `state` - The state with the category filters.
+    ///
+    /// # Returns
+    ///
+    /// The ascending indices of the matching tasks.
+    pub fn filter(&self, data: ToDoData, state: &ToDoState) -> Vec<usize> {
+        let mut matching: Option<Vec<usize>> = None;
+        let mut removed = BTreeSet::<usize>::new();
+        for category in state.categories() {
+            let postings = self.postings(data, category);
+            let filters = state.get_category(category);
+            let mut selected = Vec::new();
+            for (name, filter) in filters {
+                let tasks = matching_tasks(postings, category, name);
+                match filter {
+                    FilterState::Select => selected.push(tasks),
+                    FilterState::Remove => removed.extend(tasks.iter()),
+                }
+            }
+            if selected.is_empty() {
+                continue;
+            }
+            let category_matching = match state.is_any(category) {
+                true => selected
+                    .iter()
+                    .flat_map(|tasks| tasks.iter())
+                    .copied()
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect(),
+                false => {
+                    // Intersecting from the shortest postings keeps the candidates few.
+                    selected.sort_by_key(|tasks| tasks.len());
+                    let (first, rest) = selected.split_first().unwrap();
+                    let rest: Vec<&[usize]> = rest.iter().map(|tasks| tasks.as_ref()).collect();
+                    intersect(first, &rest)
+                }
+            };
+            matching = Some(match matching {
+                Some(matching) => intersect(&matching, &[&category_matching]),
+                None => category_matching,
+            });
+        }
+        matching
+            .unwrap_or_else(|| (0..self.data(data).len).collect())
+            .into_iter()
+            .filter(|index| !removed.contains(index))
+            .collect()
+    }
+}
+
+/// Gets the ascending indices of the tasks matching the filter of the name, a filter
+/// of a project also matches the tasks of its subprojects, see [`ToDoCategory::matches`].
+///
+/// # Arguments
+///
+/// * `postings` - The postings of the category.
+/// * `category` - The type of category.
+/// * `name` - The name of the filter.
+fn matching_tasks<'a>(
+    postings: &'a Postings,
+    category: ToDoCategory,
+    name: &str,
+) -> Cow<'a, [usize]> {
+    // Subprojects start with the name of the project, so they follow it in the postings.
+    let mut matching = postings
+        .range::<str, _>((Bound::Included(name), Bound::Unbounded))
+        .take_while(|(other, _)| other.starts_with(name))
+        .filter(|(other, _)| category.matches(other, name))
+        .map(|(_, tasks)| tasks.as_slice());
+    match (matching.next(), matching.next()) {
+        (None, _) => Cow::Borrowed(&[]),
+        (Some(tasks), None) => Cow::Borrowed(tasks),
+        (Some(first), Some(second)) => Cow::Owned(
+            first
+                .iter()
+                .chain(second)
+                .chain(matching.flatten())
+                .copied()
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+        ),
+    }
+}
+
+/// Keeps the indices of `first` contained in all `rest` postings.
+fn intersect(first: &[usize], rest: &[&[usize]]) -> Vec<usize> {
+    first
+        .iter()
+        .filter(|index| rest.iter().all(|tasks| tasks.binary_search(index).is_ok()))
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::str::FromStr;
+
+    fn tasks(lines: &[&str]) -> Vec<Task> {
+        lines
+            .iter()
+            .map(|line| Task::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn names() {
+        let pending = tasks(&["call +mom @phone", "visit +mom +mom", "buy milk @shop"]);
+        let done = tasks(&["fix +bike"]);
+        let index = CategoryIndex::new(1, &pending, &done);
+        let projects: Vec<(&String, &[usize])> = index
+            .names(ToDoData::Pending, ToDoCategory::Projects)
+            .collect();
+        assert_eq!(projects, [(&String::from("mom"), &[0, 1][..])]);
+        let contexts: Vec<&String> = index
+            .names(ToDoData::Pending, ToDoCategory::Contexts)
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(contexts, ["phone", "shop"]);
+        let due = tasks(&[
+            "call mom due:2024-07-03 person:ann",
+            "visit mom due:2024-07-03",
+        ]);
+        let index = CategoryIndex::new(1, &due, &[]);
+        let dates: Vec<(&String, &[usize])> = index
+            .names(ToDoData::Pending, ToDoCategory::Tag(TagKey::new("due")))
+            .collect();
+        assert_eq!(dates, [(&String::from("2024-07-03"), &[0, 1][..])]);
+        let people = index.names(ToDoData::Pending, ToDoCategory::Tag(TagKey::new("person")));
+        assert_eq!(people.count(), 1);
+        let index = CategoryIndex::new(1, &pending, &done);
+        assert!(index.is_current(1, 3, 1));
+        assert!(!index.is_current(2, 3, 1));
+        assert!(!index.is_current(1, 4, 1));
+    }
+
+    #[test]
+    fn push() {
+        let pending = tasks(&["call +mom", "buy milk"]);
+        let mut index = CategoryIndex::new(1, &pending[..1], &[]);
+        index.push(2, ToDoData::Pending, &pending[1]);
+        assert_eq!(index, CategoryIndex::new(2, &pending, &[]));
+    }
+
+    #[test]
+    fn filter() {
+        let pending = tasks(&[
+            "call +mom @phone",
+            "visit +mom @car",
+            "fix +bike @garage",
+            "buy milk @shop #errand",
+        ]);
+        let index = CategoryIndex::new(0, &pending, &[]);
+        let mut state = ToDoState::default();
+        assert_eq!(index.filter(ToDoData::Pending, &state), [0, 1, 2, 3]);
+
+        state.set_filter(ToDoCategory::Projects, "mom", FilterState::Select);
+        assert_eq!(index.filter(ToDoData::Pending, &state), [0, 1]);
+        state.set_filter(ToDoCategory::Contexts, "car", FilterState::Remove);
+        assert_eq!(index.filter(ToDoData::Pending, &state), [0]);
+        state.set_filter(ToDoCategory::Projects, "bike", FilterState::Select);
+        assert!(index.filter(ToDoData::Pending, &state).is_empty());
+        state.toggle_any(ToDoCategory::Projects);
+        assert_eq!(index.filter(ToDoData::Pending, &state), [0, 2]);
+        state.set_filter(ToDoCategory::Hashtags, "unknown", FilterState::Select);
+        assert!(index.filter(ToDoData::Pending, &state).is_empty());
+    }
+
+    #[test]
+    fn filter_subprojects() {
+        let pending = tasks(&[
+            "weed +home.garden",
+            "clean +home",
+            "paint +home.kitchen.walls",
+            "call +homework",
+        ]);
+        let index = CategoryIndex::new(0, &pending, &[]);
+        let mut state = ToDoState::default();
+        state.set_filter(ToDoCategory::Projects, "home", FilterState::Select);
+        assert_eq!(index.filter(ToDoData::Pending, &state), [0, 1, 2]);
+        state.set_filter(ToDoCategory::Projects, "home.kitchen", FilterState::Remove);
+        assert_eq!(index.filter(ToDoData::Pending, &state), [0, 1]);
+        assert!(state.filter_out(&pending[0]));
+        assert!(!state.filter_out(&pending[2]));
+    }
+
+    /// `a.b` is a subproject of `a`.
+    const NAMES: [&str; 3] = ["a", "b", "a.b"];
+
+    /// The built-in categories followed by the values of the `person:` tag.
+    fn category(index: usize) -> ToDoCategory {
+        match ToDoCategory::get_all().get(index) {
+            Some(category) => *category,
+            None => ToDoCategory::Tag(TagKey::new("person")),
+        }
+    }
+
+    fn line() -> impl Strategy<Value = String> {
+        prop::collection::vec((0..5usize, 0..3usize), 0..5).prop_map(|words| {
+            let mut line = String::from("task");
+            for (category, name) in words {
+                let prefix = ["+", "@", "#", "assignee:", "person:"][category];
+                line += &format!(" {prefix}{}", NAMES[name]);
+            }
+            line
+        })
+    }
+
+    fn filters() -> impl Strategy<Value = Vec<(usize, usize, bool)>> {
+        prop::collection::vec((0..5usize, 0..3usize, any::<bool>()), 0..4)
+    }
+
+    proptest! {
+        #[test]
+        fn filter_matches_scan(
+            lines in prop::collection::vec(line(), 0..20),
+            filters in filters(),
+            any in prop::collection::vec(0..5usize, 0..2),
+        ) {
+            let pending: Vec<Task> = lines.iter().map(|l| Task::from_str(l).unwrap()).collect();
+            let mut state = ToDoState::default();
+            for (index, name, select) in filters {
+                let filter = if select { FilterState::Select } else { FilterState::Remove };
+                state.set_filter(category(index), NAMES[name], filter);
+            }
+            for index in any {
+                state.toggle_any(category(index));
+            }
+            let index = CategoryIndex::new(0, &pending, &[]);
+            let scanned: Vec<usize> = pending
+                .iter()
+                .enumerate()
+                .filter(|(_, task)| state.filter_out(task))
+                .map(|(index, _)| index)
+                .collect();
+            prop_assert_eq!(index.filter(ToDoData::Pending, &state), scanned);
+        }
+    }
+}
@@ -1,14 +1,28 @@
-use super::FilterState;
-use crate::config::Styles;
-use tui::text::Span;
+use super::{FilterState, TreeNode};
+use crate::style::Styles;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+use tui::style::{Color, Style};
+use tui::text::{Line, Span};
 use tui::widgets::ListItem;
 
-/// Represents a list of categories, where each category is a tuple of `(&'a String, bool)`.
-/// The `String` value represents name of category and the `bool` value represents
+/// Badge drawn after the name of a category with a warning.
+const WARNING_BADGE: &str = " ⚠";
+
+/// Characters of a bar filled from one eighth to the whole character.
+const BAR_EIGHTHS: [char; 8] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+
+/// Represents a list of categories, where each category is a tuple of `(Cow<'a, str>, bool)`.
+/// The `Cow` value represents name of category and the `bool` value represents
 /// whether the category is selected or not.
 pub struct CategoryList<'a> {
-    pub vec: Vec<(&'a String, Option<FilterState>)>,
+    pub vec: Vec<(Cow<'a, str>, Option<FilterState>)>,
     pub styles: &'a Styles,
+    /// Categories drawn with a warning badge, e.g. projects close to their deadline.
+    pub warnings: BTreeSet<String>,
+    /// The places of the categories in the tree of projects, empty if the list is flat,
+    /// see [`CategoryList::into_tree`].
+    pub nodes: Vec<TreeNode>,
 }
 
 impl<'a> CategoryList<'a> {
@@ -21,11 +35,11 @@ impl<'a> CategoryList<'a> {
     /// # Returns
     ///
     /// A vector of references to the matching categories.
-    pub fn start_with(&self, pattern: &str) -> Vec<&String> {
+    pub fn start_with(&self, pattern: &str) -> Vec<&str> {
         self.vec
             .iter()
             .filter(|(item, _)| item.starts_with(pattern))
-            .map(|(item, _)| *item)
+            .map(|(item, _)| item.as_ref())
             .collect()
     }
 
@@ -52,28 +66,133 @@ impl<'a> CategoryList<'a> {
     /// # Panics
     ///
     /// Panics if the index is out of bounds.
-    pub fn get_name(&self, index: usize) -> &String {
-        self.vec[index].0
+    pub fn get_name(&self, index: usize) -> &str {
+        &self.vec[index].0
+    }
+
+    /// Keeps only the categories from `first` (inclusive) to `last` (exclusive),
+    /// e.g. the rows visible in the list, so only they are turned into list items.
+    ///
+    /// # Arguments
+    ///
+    /// * `first` - The index of the first category to keep.
+    /// * `last` - The index of the first category to drop after the kept ones.
+    ///
+    /// # Returns
+    ///
+    /// The list with the kept categories.
+    pub fn slice(mut self, first: usize, last: usize) -> Self {
+        self.vec.truncate(last);
+        self.vec.drain(..first.min(self.vec.len()));
+        self.nodes.truncate(last);
+        self.nodes.drain(..first.min(self.nodes.len()));
+        self
+    }
+
+    /// Gets the text of the category at the index, the name of a category in a tree
+    /// is indented by its depth and shortened to the last part, e.g. `  garden`.
+    fn label(&self, index: usize) -> Cow<'_, str> {
+        let name = &self.vec[index].0;
+        match self.nodes.get(index) {
+            Some(node) => Cow::Owned(node.label(name)),
+            None => Cow::Borrowed(name),
+        }
+    }
+
+    /// Creates list items with a bar of the share of open tasks next to every category.
+    ///
+    /// # Arguments
+    ///
+    /// * `counts` - The number of open tasks of every category and the number of all open tasks.
+    /// * `width` - The width of the items.
+    ///
+    /// # Returns
+    ///
+    /// The list items with the names padded so the bars are aligned to the right.
+    pub fn with_bars(
+        &self,
+        (counts, total): &(BTreeMap<Cow<str>, usize>, usize),
+        width: usize,
+    ) -> Vec<ListItem<'a>> {
+        let bar_width = (width / 3).min(10);
+        self.vec
+            .iter()
+            .enumerate()
+            .map(|(index, (category, _))| {
+                let count = counts.get(category).copied().unwrap_or(0);
+                let bar = share_bar(count, *total, bar_width);
+                let mut spans = self.name_spans(index);
+                let name: usize = spans.iter().map(|span| span.content.chars().count()).sum();
+                let padding = width.saturating_sub(name + bar.chars().count());
+                spans.push(Span::raw(" ".repeat(padding)));
+                spans.push(Span::styled(bar, Style::default().fg(Color::DarkGray)));
+                ListItem::new(Line::from(spans))
+            })
+            .collect()
+    }
+
+    /// Gets the name of the category at the index followed by the warning badge
+    /// if it has a warning.
+    fn name_spans(&self, index: usize) -> Vec<Span<'a>> {
+        let (category, active) = &self.vec[index];
+        let mut spans = vec![Span::styled(
+            self.label(index).into_owned(),
+            self.style(active),
+        )];
+        if self.warnings.contains(category.as_ref()) {
+            spans.push(Span::styled(
+                WARNING_BADGE,
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        spans
+    }
+
+    fn style(&self, active: &Option<FilterState>) -> Style {
+        match active {
+            Some(FilterState::Select) => self.styles.category_select_style.get_style(),
+            Some(FilterState::Remove) => self.styles.category_remove_style.get_style(),
+            None => Style::default(),
+        }
     }
 }
 
+/// Creates a bar as long as the share of the count in the total, a non zero count
+/// has at least the smallest bar.
+///
+/// # Arguments
+///
+/// * `count` - The count shown by the bar.
+/// * `total` - The count of the full bar.
+/// * `width` - The width of the full bar.
+fn share_bar(count: usize, total: usize, width: usize) -> String {
+    if count == 0 || total == 0 || width == 0 {
+        return String::new();
+    }
+    let eighths = (count.min(total) * width * 8 + total / 2) / total;
+    let eighths = eighths.max(1);
+    let mut bar = BAR_EIGHTHS[7].to_string().repeat(eighths / 8);
+    match eighths % 8 {
+        0 => {}
+        rest => bar.push(BAR_EIGHTHS[rest - 1]),
+    }
+    bar
+}
+
 impl<'a> From<CategoryList<'a>> for Vec<ListItem<'a>> {
     fn from(val: CategoryList<'a>) -> Self {
         val.vec
             .iter()
-            .map(|(category, active)| {
-                use FilterState::*;
-                match active {
-                    Some(Select) => ListItem::new(Span::styled(
-                        (*category).clone(),
-                        val.styles.category_select_style.get_style(),
-                    )),
-                    Some(Remove) => ListItem::new(Span::styled(
-                        (*category).clone(),
-                        val.styles.category_remove_style.get_style(),
-                    )),
-                    None => ListItem::new((*category).clone()),
+            .enumerate()
+            .map(|(index, (category, active))| match active {
+                _ if val.warnings.contains(category.as_ref()) => {
+                    ListItem::new(Line::from(val.name_spans(index)))
                 }
+                Some(_) => ListItem::new(Span::styled(
+                    val.label(index).into_owned(),
+                    val.style(active),
+                )),
+                None => ListItem::new(val.label(index).into_owned()),
             })
             .collect()
     }
@@ -81,7 +200,6 @@ impl<'a> From<CategoryList<'a>> for Vec<ListItem<'a>> {
 
 #[cfg(test)]
 mod tests {
-    use crate::config::Config;
 
     use super::*;
 
@@ -94,12 +212,14 @@ mod tests {
         let third2 = String::from("third2");
         let categories = CategoryList {
             vec: vec![
-                (&first, None),
-                (&second, None),
-                (&third, None),
-                (&third2, None),
+                (Cow::from(first.as_str()), None),
+                (Cow::from(second.as_str()), None),
+                (Cow::from(third.as_str()), None),
+                (Cow::from(third2.as_str()), None),
             ],
             styles: &styles,
+            warnings: BTreeSet::new(),
+            nodes: Vec::new(),
         };
 
         assert!(!categories.is_empty());
@@ -115,40 +235,111 @@ mod tests {
         let third2 = String::from("third2");
         let categories = CategoryList {
             vec: vec![
-                (&first, None),
-                (&second, None),
-                (&third, None),
-                (&third2, None),
+                (Cow::from(first.as_str()), None),
+                (Cow::from(second.as_str()), None),
+                (Cow::from(third.as_str()), None),
+                (Cow::from(third2.as_str()), None),
             ],
             styles: &styles,
+            warnings: BTreeSet::new(),
+            nodes: Vec::new(),
         };
         assert!(categories.start_with("none").is_empty());
 
         let match_fi = categories.start_with("fi");
         assert_eq!(match_fi.len(), 1);
-        assert_eq!(match_fi[0], &first);
+        assert_eq!(match_fi[0], first);
 
         let match_fi = categories.start_with("th");
         assert_eq!(match_fi.len(), 2);
-        assert_eq!(match_fi[0], &third);
-        assert_eq!(match_fi[1], &third2);
+        assert_eq!(match_fi[0], third);
+        assert_eq!(match_fi[1], third2);
+    }
+
+    #[test]
+    fn slice() {
+        let styles = Styles::default();
+        let names: Vec<String> = (0..5).map(|i| i.to_string()).collect();
+        let categories = |first, last| {
+            CategoryList {
+                vec: names
+                    .iter()
+                    .map(|name| (Cow::from(name.as_str()), None))
+                    .collect(),
+                styles: &styles,
+                warnings: BTreeSet::new(),
+                nodes: Vec::new(),
+            }
+            .slice(first, last)
+            .vec
+            .into_iter()
+            .map(|(name, _)| name.into_owned())
+            .collect::<Vec<_>>()
+        };
+        assert_eq!(categories(1, 3), ["1", "2"]);
+        assert_eq!(categories(3, 10), ["3", "4"]);
+        assert!(categories(7, 10).is_empty());
+    }
+
+    #[test]
+    fn bars() {
+        assert_eq!(share_bar(0, 10, 10), "");
+        assert_eq!(share_bar(5, 10, 10), "█████");
+        assert_eq!(share_bar(10, 10, 4), "████");
+        assert_eq!(share_bar(4, 16, 2), "▌");
+        assert_eq!(share_bar(1, 1000, 10), "▏");
+        assert_eq!(share_bar(3, 16, 4), "▊");
+
+        let styles = Styles::default();
+        let first = String::from("first");
+        let second = String::from("second");
+        let categories = CategoryList {
+            vec: vec![
+                (Cow::from(first.as_str()), None),
+                (Cow::from(second.as_str()), None),
+            ],
+            styles: &styles,
+            warnings: BTreeSet::from([second.clone()]),
+            nodes: Vec::new(),
+        };
+        let counts = (BTreeMap::from([(Cow::from(first.as_str()), 3)]), 4);
+        let items = categories.with_bars(&counts, 15);
+        assert_eq!(
+            items[0],
+            ListItem::new(Line::from(vec![
+                Span::raw("first"),
+                Span::raw("      "),
+                Span::styled("███▊", Style::default().fg(Color::DarkGray)),
+            ]))
+        );
+        assert_eq!(
+            items[1],
+            ListItem::new(Line::from(vec![
+                Span::raw("second"),
+                Span::styled(WARNING_BADGE, Style::default().fg(Color::Yellow)),
+                Span::raw("       "),
+                Span::styled("", Style::default().fg(Color::DarkGray)),
+            ]))
+        );
     }
 
     #[test]
     fn create_list_of_items() {
-        let styles = Styles::new(&Config::default());
+        let styles = Styles::default();
         let first = String::from("first");
         let second = String::from("second");
         let third = String::from("third");
         let third2 = String::from("third2");
         let categories = CategoryList {
             vec: vec![
-                (&first, None),
-                (&second, None),
-                (&third, Some(FilterState::Select)),
-                (&third2, None),
+                (Cow::from(first.as_str()), None),
+                (Cow::from(second.as_str()), None),
+                (Cow::from(third.as_str()), Some(FilterState::Select)),
+                (Cow::from(third2.as_str()), None),
             ],
             styles: &styles,
+            warnings: BTreeSet::new(),
+            nodes: Vec::new(),
         };
 
         let items = Vec::<ListItem>::from(categories);
@@ -0,0 +1,166 @@
+use super::{CategoryList, FilterState, ToDoCategory};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+};
+
+/// The place of a category in the tree of projects, see [`ToDoCategory::parents`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TreeNode {
+    /// The number of parents of the category.
+    pub depth: usize,
+    /// Whether the category has subcategories.
+    pub parent: bool,
+    /// Whether the subcategories are shown.
+    pub expanded: bool,
+    /// The byte offset of the last part of the name, e.g. of `garden` in `home.garden`.
+    leaf: usize,
+}
+
+impl TreeNode {
+    /// Gets the text of the category in the tree, the last part of the name indented
+    /// by the depth and preceded by a marker of a collapsed or expanded parent.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The full name of the category.
+    pub fn label(&self, name: &str) -> String {
+        let marker = match (self.parent, self.expanded) {
+            (true, true) => "▾ ",
+            (true, false) => "▸ ",
+            (false, _) => "  ",
+        };
+        format!("{}{marker}{}", "  ".repeat(self.depth), &name[self.leaf..])
+    }
+}
+
+impl<'a> CategoryList<'a> {
+    /// Arranges the categories as a tree, every project follows its parent project
+    /// and parents without tasks of their own are added, e.g. `home` of `home.garden`.
+    /// The list stays flat if no category has a parent.
+    ///
+    /// # Arguments
+    ///
+    /// * `category` - The type of the categories.
+    /// * `collapsed` - The names of the parents whose subcategories are hidden.
+    ///
+    /// # Returns
+    ///
+    /// The shown categories of the tree with their nodes.
+    pub fn into_tree(mut self, category: ToDoCategory, collapsed: &BTreeSet<String>) -> Self {
+        if self
+            .vec
+            .iter()
+            .all(|(name, _)| category.parents(name).next().is_none())
+        {
+            return self;
+        }
+        // The path of the parents and the name sorts every category right after its parent.
+        type Entry<'a> = (Cow<'a, str>, Option<FilterState>);
+        let mut tree: BTreeMap<Vec<String>, Entry<'a>> = BTreeMap::new();
+        for (name, state) in std::mem::take(&mut self.vec) {
+            let mut path: Vec<String> = category.parents(&name).map(String::from).collect();
+            for depth in 0..path.len() {
+                tree.entry(path[..=depth].to_vec())
+                    .or_insert_with(|| (Cow::Owned(path[depth].clone()), None));
+            }
+            path.push(name.to_string());
+            tree.insert(path, (name, state));
+        }
+        let entries: Vec<(Vec<String>, Entry<'a>)> = tree.into_iter().collect();
+        for (index, (path, entry)) in entries.iter().enumerate() {
+            let (_, parents) = path.split_last().unwrap();
+            if parents.iter().any(|parent| collapsed.contains(parent)) {
+                continue;
+            }
+            let parent = entries
+                .get(index + 1)
+                .is_some_and(|(next, _)| next.len() > path.len() && next.starts_with(path));
+            self.nodes.push(TreeNode {
+                depth: parents.len(),
+                parent,
+                expanded: !collapsed.contains(entry.0.as_ref()),
+                leaf: parents.last().map_or(0, |parent| parent.len() + 1),
+            });
+            self.vec.push(entry.clone());
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::Styles;
+
+    fn tree(names: &[&'static str], collapsed: &[&str]) -> Vec<String> {
+        let styles = Styles::default();
+        let list = CategoryList {
+            vec: names.iter().map(|name| (Cow::from(*name), None)).collect(),
+            styles: &styles,
+            warnings: BTreeSet::new(),
+            nodes: Vec::new(),
+        };
+        let collapsed = collapsed.iter().map(|name| name.to_string()).collect();
+        let list = list.into_tree(ToDoCategory::Projects, &collapsed);
+        list.vec
+            .iter()
+            .zip(&list.nodes)
+            .map(|((name, _), node)| node.label(name))
+            .collect()
+    }
+
+    #[test]
+    fn into_tree() {
+        let names = [
+            "home-office",
+            "home.garden",
+            "home.kitchen",
+            "home.kitchen.walls",
+            "work",
+        ];
+        assert_eq!(
+            tree(&names, &[]),
+            [
+                "▾ home",
+                "    garden",
+                "  ▾ kitchen",
+                "      walls",
+                "  home-office",
+                "  work"
+            ]
+        );
+        assert_eq!(
+            tree(&names, &["home.kitchen"]),
+            [
+                "▾ home",
+                "    garden",
+                "  ▸ kitchen",
+                "  home-office",
+                "  work"
+            ]
+        );
+        assert_eq!(
+            tree(&names, &["home"]),
+            ["▸ home", "  home-office", "  work"]
+        );
+
+        let styles = Styles::default();
+        let flat = CategoryList {
+            vec: vec![(Cow::from("home"), Some(FilterState::Select))],
+            styles: &styles,
+            warnings: BTreeSet::new(),
+            nodes: Vec::new(),
+        }
+        .into_tree(ToDoCategory::Projects, &BTreeSet::new());
+        assert!(flat.nodes.is_empty());
+        let contexts = CategoryList {
+            vec: vec![(Cow::from("home.garden"), None)],
+            styles: &styles,
+            warnings: BTreeSet::new(),
+            nodes: Vec::new(),
+        }
+        .into_tree(ToDoCategory::Contexts, &BTreeSet::new());
+        assert!(contexts.nodes.is_empty());
+    }
+}
@@ -0,0 +1,122 @@
+use super::{Action, ToDo};
+use std::sync::mpsc::{self, Receiver};
+
+/// Describes what part of the todo list has changed.
+///
+/// Subscribers of [`ToDo::subscribe`] receive the changes, so they can refresh
+/// what they computed from the todo list only when it is out of date.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Change {
+    /// Tasks were added, edited, moved or removed.
+    Tasks,
+    /// Filters, sorting, the active task or the selected project have changed.
+    View,
+    /// The information of projects was replaced.
+    Projects,
+    /// The metadata of a task used only by the user interface has changed.
+    Meta,
+    /// All tasks were replaced by the tasks loaded from the file.
+    Reloaded,
+    /// The current date has changed, what depends on today is out of date.
+    Date,
+}
+
+impl Action {
+    /// Gets the change of the todo list made by the action.
+    pub fn change(&self) -> Change {
+        use Action::*;
+        match self {
+            NewTask { .. }
+            | CopyTask { .. }
+            | UpdateActive { .. }
+            | UpdateTask { .. }
+            | RemoveTask { .. }
+            | DuplicateTask { .. }
+            | RestoreTask { .. }
+            | CompleteTask { .. }
+            | ReopenLastCompleted
+            | SwapTasks { .. }
+            | ReorderTask { .. }
+            | CyclePriority { .. }
+            | ShiftPriority { .. }
+            | ClearPriority { .. }
+            | DeferTask { .. }
+            | SetDue { .. }
+            | ShiftDate { .. }
+            | ToggleTimer { .. }
+            | AddCategories { .. }
+            | AgePriorities
+            | ResetRoutines
+            | ReviewTask { .. } => Change::Tasks,
+            Batch { actions } => match actions.iter().any(|a| a.change() == Change::Tasks) {
+                true => Change::Tasks,
+                false => Change::View,
+            },
+            SetActive { .. }
+            | SelectProject { .. }
+            | ToggleFilter { .. }
+            | ToggleFilterMode { .. }
+            | SetSort { .. }
+            | ToggleSortOnInsert
+            | ToggleRawLines
+            | CycleMyTasks
+            | ToggleFilters
+            | ClearFilters
+            | SetFilters { .. }
+            | PushFilters
+            | PopFilters
+            | SetSearch { .. }
+            | SetFilterBar { .. }
+            | FlushTrash => Change::View,
+        }
+    }
+}
+
+impl ToDo {
+    /// Subscribes to changes of the todo list. The subscription ends when the receiver is dropped.
+    ///
+    /// # Returns
+    ///
+    /// The receiver of every following change.
+    pub fn subscribe(&mut self) -> Receiver<Change> {
+        let (tx, rx) = mpsc::channel();
+        self.subscribers.push(tx);
+        rx
+    }
+
+    /// Sends the change to all subscribers, subscribers that were dropped are removed.
+    pub(super) fn notify(&mut self, change: Change) {
+        log::trace!("Todo list changed: {:?}", change);
+        self.subscribers.retain(|tx| tx.send(change).is_ok());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::todo::{FilterState, ToDoCategory};
+
+    #[test]
+    fn subscribe() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        let changes = todo.subscribe();
+        let dropped = todo.subscribe();
+        drop(dropped);
+
+        todo.apply(Action::NewTask {
+            task: String::from("task +project"),
+        })?;
+        todo.apply(Action::ToggleFilter {
+            category: ToDoCategory::Projects,
+            name: String::from("project"),
+            state: FilterState::Select,
+        })?;
+        todo.move_data(ToDo::default());
+        assert_eq!(
+            changes.try_iter().collect::<Vec<_>>(),
+            vec![Change::Tasks, Change::View, Change::Reloaded]
+        );
+        assert_eq!(todo.subscribers.len(), 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+use super::ToDo;
+use chrono::{Duration, NaiveDateTime};
+use todo_txt::Task;
+
+/// Tasks due within this many hours show the time left to their deadline.
+const COUNTDOWN_HOURS: i64 = 48;
+
+/// Gets the end of the due date of the task, the task is due by the end of the day.
+pub(super) fn deadline(task: &Task) -> Option<NaiveDateTime> {
+    task.due_date?.succ_opt()?.and_hms_opt(0, 0, 0)
+}
+
+/// Gets the time left to the deadline of a pending task due within 48 hours.
+///
+/// # Arguments
+///
+/// * `task` - The task with the due date.
+/// * `now` - The current time.
+///
+/// # Returns
+///
+/// `None` if the task is finished, has no due date, is overdue or is not due within 48 hours.
+pub fn countdown(task: &Task, now: NaiveDateTime) -> Option<Duration> {
+    if task.finished {
+        return None;
+    }
+    let left = deadline(task)? - now;
+    (left > Duration::zero() && left <= Duration::hours(COUNTDOWN_HOURS)).then_some(left)
+}
+
+/// Formats the time left in hours, e.g. `6h left`, or in minutes in the final hour, e.g. `45m left`.
+pub fn format_countdown(left: Duration) -> String {
+    match left.num_hours() {
+        0 => format!("{}m left", (left.num_seconds() + 59) / 60),
+        hours => format!("{hours}h left"),
+    }
+}
+
+impl ToDo {
+    /// Gets the next moment the countdown of a pending task changes: it starts 48 hours
+    /// before the deadline, changes every hour and every minute of the final hour.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The current time, moments at this time or before are skipped.
+    ///
+    /// # Returns
+    ///
+    /// The earliest moment after `now` or `None` if no countdown changes.
+    pub fn next_countdown(&self, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        self.pending
+            .iter()
+            .filter_map(deadline)
+            .filter(|deadline| *deadline > now)
+            .map(|deadline| {
+                let start = deadline - Duration::hours(COUNTDOWN_HOURS);
+                if start > now {
+                    return start;
+                }
+                let left = (deadline - now).num_seconds();
+                let unit = if left > 3600 { 3600 } else { 60 };
+                deadline - Duration::seconds((left - 1) / unit * unit)
+            })
+            .min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use std::str::FromStr;
+
+    fn at(day: u32, hour: u32, min: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 7, day)
+            .unwrap()
+            .and_hms_opt(hour, min, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn left() -> Result<(), todo_txt::Error> {
+        let task = Task::from_str("pay rent due:2024-07-03")?;
+        let left = |time| countdown(&task, time).map(format_countdown);
+        assert_eq!(countdown(&task, at(2, 0, 0)), Some(Duration::hours(48)));
+        assert_eq!(left(at(1, 23, 59)), None);
+        assert_eq!(left(at(3, 18, 0)), Some(String::from("6h left")));
+        assert_eq!(left(at(3, 17, 30)), Some(String::from("6h left")));
+        assert_eq!(left(at(3, 23, 15)), Some(String::from("45m left")));
+        assert_eq!(left(at(4, 0, 0)), None);
+        let done = Task::from_str("x 2024-07-01 pay rent due:2024-07-03")?;
+        assert_eq!(countdown(&done, at(3, 18, 0)), None);
+        Ok(())
+    }
+
+    #[test]
+    fn next_change() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        assert_eq!(todo.next_countdown(at(1, 12, 0)), None);
+        todo.new_task("pay rent due:2024-07-03")?;
+        assert_eq!(todo.next_countdown(at(1, 12, 0)), Some(at(2, 0, 0)));
+        assert_eq!(todo.next_countdown(at(2, 0, 0)), Some(at(2, 1, 0)));
+        assert_eq!(todo.next_countdown(at(3, 17, 30)), Some(at(3, 18, 0)));
+        assert_eq!(todo.next_countdown(at(3, 23, 0)), Some(at(3, 23, 1)));
+        assert_eq!(todo.next_countdown(at(3, 23, 59)), Some(at(4, 0, 0)));
+        assert_eq!(todo.next_countdown(at(4, 0, 0)), None);
+        Ok(())
+    }
+}
@@ -0,0 +1,297 @@
+use super::{ToDo, ToDoData};
+use crate::error::{ToDoError, ToDoRes};
+use chrono::{Datelike, Duration, Months, NaiveDate, Weekday};
+use std::str::FromStr;
+
+/// The unit of a relative date.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateUnit {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+/// A date typed by the user to set the due date of a task.
+///
+/// The expression is one of:
+///
+/// - a date, e.g. `2023-06-01`,
+/// - `today` or `tomorrow`,
+/// - an amount of days, weeks, months or years from today, e.g. `3d` or `1w`,
+/// - the amount with a sign moves the current due date, e.g. `+1w` or `-2d`,
+///   a task without the due date is moved from today,
+/// - the next day of the week after today, e.g. `mon`, `friday` or `next monday`,
+/// - `none` removes the due date.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateExpr {
+    Date(NaiveDate),
+    Relative {
+        amount: i32,
+        unit: DateUnit,
+        /// Whether the date is moved from the current due date instead of today.
+        from_due: bool,
+    },
+    Weekday(Weekday),
+    None,
+}
+
+impl DateExpr {
+    /// Computes the due date given by the expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `due` - The current due date of the task.
+    /// * `today` - The date relative dates are computed from.
+    ///
+    /// # Returns
+    ///
+    /// The new due date, `None` if the due date is removed.
+    pub fn resolve(&self, due: Option<NaiveDate>, today: NaiveDate) -> Option<NaiveDate> {
+        match *self {
+            DateExpr::Date(date) => Some(date),
+            DateExpr::Relative {
+                amount,
+                unit,
+                from_due,
+            } => {
+                let from = match from_due {
+                    true => due.unwrap_or(today),
+                    false => today,
+                };
+                let months = |months: i32| match months >= 0 {
+                    true => from.checked_add_months(Months::new(months.unsigned_abs())),
+                    false => from.checked_sub_months(Months::new(months.unsigned_abs())),
+                };
+                let moved = match unit {
+                    DateUnit::Day => from.checked_add_signed(Duration::days(amount.into())),
+                    DateUnit::Week => from.checked_add_signed(Duration::weeks(amount.into())),
+                    DateUnit::Month => months(amount),
+                    DateUnit::Year => months(amount.saturating_mul(12)),
+                };
+                Some(moved.unwrap_or(from))
+            }
+            DateExpr::Weekday(weekday) => {
+                let days = (weekday.num_days_from_monday() + 6
+                    - today.weekday().num_days_from_monday())
+                    % 7
+                    + 1;
+                Some(today + Duration::days(days.into()))
+            }
+            DateExpr::None => None,
+        }
+    }
+}
+
+impl FromStr for DateExpr {
+    type Err = ToDoError;
+
+    fn from_str(s: &str) -> ToDoRes<Self> {
+        let error = || ToDoError::ParseDate(s.to_string());
+        let expr = s.trim().to_lowercase();
+        let day = |amount| DateExpr::Relative {
+            amount,
+            unit: DateUnit::Day,
+            from_due: false,
+        };
+        match expr.as_str() {
+            "today" => return Ok(day(0)),
+            "tomorrow" => return Ok(day(1)),
+            "none" => return Ok(DateExpr::None),
+            _ => {}
+        }
+        if let Ok(date) = NaiveDate::parse_from_str(&expr, "%Y-%m-%d") {
+            return Ok(DateExpr::Date(date));
+        }
+        let weekday = expr.strip_prefix("next ").unwrap_or(&expr);
+        if let Ok(weekday) = Weekday::from_str(weekday) {
+            return Ok(DateExpr::Weekday(weekday));
+        }
+        let (from_due, sign, rest) = match expr.strip_prefix('+') {
+            Some(rest) => (true, 1, rest),
+            None => match expr.strip_prefix('-') {
+                Some(rest) => (true, -1, rest),
+                None => (false, 1, expr.as_str()),
+            },
+        };
+        let unit = match rest.chars().last().ok_or_else(error)? {
+            'd' => DateUnit::Day,
+            'w' => DateUnit::Week,
+            'm' => DateUnit::Month,
+            'y' => DateUnit::Year,
+            _ => return Err(error()),
+        };
+        let amount: i32 = match &rest[..rest.len() - 1] {
+            // The amount can be left out, `+w` is a week later.
+            "" => 1,
+            amount if amount.starts_with(['+', '-']) => return Err(error()),
+            amount => amount.parse().map_err(|_| error())?,
+        };
+        Ok(DateExpr::Relative {
+            amount: sign * amount,
+            unit,
+            from_due,
+        })
+    }
+}
+
+/// Replaces the date expression of a `due:` or `t:` token with the date, e.g. `due:fri`
+/// or `t:+2w`. Signed amounts are counted from today, as the token is the only date the
+/// task has. Other tokens and invalid expressions are returned unchanged.
+///
+/// # Arguments
+///
+/// * `token` - A word of the task.
+/// * `today` - The date relative dates are computed from.
+pub fn expand_date(token: &str, today: NaiveDate) -> String {
+    let expanded = token.split_once(':').and_then(|(key, expr)| {
+        if !matches!(key, "due" | "t") || expr.is_empty() {
+            return None;
+        }
+        let date = DateExpr::from_str(expr).ok()?.resolve(None, today)?;
+        Some(format!("{key}:{date}"))
+    });
+    expanded.unwrap_or_else(|| token.to_string())
+}
+
+/// Replaces the date expressions of all `due:` and `t:` tokens of the task with dates,
+/// see [`expand_date`]. The spacing of the task is kept.
+///
+/// # Arguments
+///
+/// * `task` - The task string entered by the user.
+/// * `today` - The date relative dates are computed from.
+pub fn expand_dates(task: &str, today: NaiveDate) -> String {
+    task.split(' ')
+        .map(|token| expand_date(token, today))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl ToDo {
+    /// Sets the due date of a task to the date given by the expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data where the task is located.
+    /// * `index` - The index of the task in the unfiltered data.
+    /// * `expr` - The date expression, see [`DateExpr`]. An invalid expression is logged.
+    pub(super) fn set_due_at(&mut self, data: ToDoData, index: usize, expr: &str) {
+        let expr = match DateExpr::from_str(expr) {
+            Ok(expr) => expr,
+            Err(e) => {
+                log::warn!("The due date is not changed: {e}");
+                return;
+            }
+        };
+        let today = self.config.clock.today();
+        let task = &mut data.get_data_mut(self)[index];
+        task.due_date = expr.resolve(task.due_date, today);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::todo::Action;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn resolve(expr: &str, due: Option<NaiveDate>) -> Option<NaiveDate> {
+        // 2023-05-03 is a Wednesday.
+        DateExpr::from_str(expr)
+            .unwrap()
+            .resolve(due, date(2023, 5, 3))
+    }
+
+    #[test]
+    fn parse_and_resolve() {
+        let due = Some(date(2023, 5, 10));
+        assert_eq!(resolve("2023-06-01", due), Some(date(2023, 6, 1)));
+        assert_eq!(resolve("today", due), Some(date(2023, 5, 3)));
+        assert_eq!(resolve("Tomorrow", due), Some(date(2023, 5, 4)));
+        assert_eq!(resolve("3d", due), Some(date(2023, 5, 6)));
+        assert_eq!(resolve("+1d", due), Some(date(2023, 5, 11)));
+        assert_eq!(resolve("+1d", None), Some(date(2023, 5, 4)));
+        assert_eq!(resolve("+w", due), Some(date(2023, 5, 17)));
+        assert_eq!(resolve("-2w", due), Some(date(2023, 4, 26)));
+        assert_eq!(
+            resolve("+1m", Some(date(2023, 1, 31))),
+            Some(date(2023, 2, 28))
+        );
+        assert_eq!(resolve("-1y", due), Some(date(2022, 5, 10)));
+        assert_eq!(resolve("next monday", due), Some(date(2023, 5, 8)));
+        assert_eq!(resolve("wed", due), Some(date(2023, 5, 10)));
+        assert_eq!(resolve("thu", due), Some(date(2023, 5, 4)));
+        assert_eq!(resolve("none", due), None);
+        for invalid in ["", "+", "1", "1x", "+-1d", "2023-02-30", "next"] {
+            assert_eq!(
+                DateExpr::from_str(invalid),
+                Err(ToDoError::ParseDate(invalid.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn expand() {
+        // 2023-05-03 is a Wednesday.
+        let today = date(2023, 5, 3);
+        assert_eq!(expand_date("due:tomorrow", today), "due:2023-05-04");
+        assert_eq!(expand_date("due:fri", today), "due:2023-05-05");
+        assert_eq!(expand_date("t:Monday", today), "t:2023-05-08");
+        assert_eq!(expand_date("due:+2w", today), "due:2023-05-17");
+        assert_eq!(expand_date("due:3d", today), "due:2023-05-06");
+        assert_eq!(expand_date("due:+3m", today), "due:2023-08-03");
+        assert_eq!(expand_date("due:2023-06-01", today), "due:2023-06-01");
+        for unchanged in [
+            "due:", "due:none", "due:soon", "t:+", "link:fri", "fri", "@work",
+        ] {
+            assert_eq!(expand_date(unchanged, today), unchanged);
+        }
+        assert_eq!(
+            expand_dates("call mom  due:tomorrow t:mon +family", today),
+            "call mom  due:2023-05-04 t:2023-05-08 +family"
+        );
+    }
+
+    #[test]
+    fn expand_across_boundaries() {
+        // The last day of the year is a Saturday.
+        let today = date(2022, 12, 31);
+        assert_eq!(expand_date("due:tomorrow", today), "due:2023-01-01");
+        assert_eq!(expand_date("due:fri", today), "due:2023-01-06");
+        assert_eq!(expand_date("t:sat", today), "t:2023-01-07");
+        assert_eq!(expand_date("due:+1w", today), "due:2023-01-07");
+        assert_eq!(expand_date("due:+1m", today), "due:2023-01-31");
+        assert_eq!(expand_date("due:1y", today), "due:2023-12-31");
+        // Months are clamped to their last day, including leap years.
+        assert_eq!(expand_date("due:+1m", date(2024, 1, 31)), "due:2024-02-29");
+        assert_eq!(expand_date("due:+1m", date(2023, 1, 31)), "due:2023-02-28");
+        assert_eq!(expand_date("due:1y", date(2024, 2, 29)), "due:2025-02-28");
+        assert_eq!(expand_date("due:+2d", date(2024, 2, 28)), "due:2024-03-01");
+        assert_eq!(expand_date("t:tomorrow", date(2023, 4, 30)), "t:2023-05-01");
+    }
+
+    #[test]
+    fn set_due() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        todo.new_task("write the report due:2023-05-10")?;
+        let set_due = |todo: &mut ToDo, due: &str| {
+            let task = todo.task_id(ToDoData::Pending, 0).unwrap();
+            todo.apply(Action::SetDue {
+                data: ToDoData::Pending,
+                task,
+                due: due.to_string(),
+            })
+            .unwrap();
+            todo.pending[0].due_date
+        };
+        assert_eq!(set_due(&mut todo, "+1w"), Some(date(2023, 5, 17)));
+        assert_eq!(set_due(&mut todo, "invalid"), Some(date(2023, 5, 17)));
+        assert_eq!(set_due(&mut todo, "none"), None);
+        assert!(!todo.pending[0].to_string().contains("due:"));
+        Ok(())
+    }
+}
@@ -0,0 +1,191 @@
+use super::ToDo;
+use chrono::Datelike;
+use std::collections::BTreeMap;
+use todo_txt::Task;
+
+/// The tag with the estimated time of a task, e.g. `effort:2h`.
+pub const EFFORT_TAG: &str = "effort";
+/// The tag with the time tracked on a task, e.g. `spent:1h30m`.
+pub const SPENT_TAG: &str = "spent";
+
+/// Label of the row of tasks without a project.
+const NO_PROJECT: &str = "(no project)";
+
+/// Parses a duration in minutes, e.g. `45m`, `2h`, `1h30m` or `1.5h`.
+/// A number without a unit is in hours.
+///
+/// # Returns
+///
+/// The duration in minutes or `None` if the value is not a duration.
+pub fn parse_duration(value: &str) -> Option<u32> {
+    let value = value.trim().to_lowercase();
+    if value.is_empty() {
+        return None;
+    }
+    if let Ok(hours) = value.parse::<f64>() {
+        return (hours >= 0.0).then(|| (hours * 60.0).round() as u32);
+    }
+    let mut minutes = 0.0;
+    let mut number = String::new();
+    for c in value.chars() {
+        match c {
+            '0'..='9' | '.' => number.push(c),
+            'h' | 'm' if !number.is_empty() => {
+                let amount: f64 = number.parse().ok()?;
+                minutes += if c == 'h' { amount * 60.0 } else { amount };
+                number.clear();
+            }
+            _ => return None,
+        }
+    }
+    number.is_empty().then(|| minutes.round() as u32)
+}
+
+/// Formats the minutes as hours and minutes, e.g. `1h30m`, `2h` or `45m`.
+pub fn format_duration(minutes: u32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{minutes}m"),
+        (hours, 0) => format!("{hours}h"),
+        (hours, minutes) => format!("{hours}h{minutes}m"),
+    }
+}
+
+/// Compares the estimated and spent time of completed tasks of a project.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EffortRow {
+    /// The ISO week the tasks were completed in, e.g. `2023-W18`, `None` for all weeks.
+    pub week: Option<String>,
+    pub project: String,
+    /// The number of compared tasks.
+    pub tasks: usize,
+    /// The estimated time in minutes.
+    pub estimated: u32,
+    /// The spent time in minutes.
+    pub spent: u32,
+}
+
+impl EffortRow {
+    /// Gets the spent time as a percentage of the estimated time, `None` without an estimate.
+    pub fn ratio(&self) -> Option<u32> {
+        (self.estimated > 0)
+            .then(|| (self.spent as f64 * 100.0 / self.estimated as f64).round() as u32)
+    }
+}
+
+/// Gets the estimated and spent minutes of the task, `None` if the task misses one of them.
+fn effort(task: &Task) -> Option<(u32, u32)> {
+    Some((
+        parse_duration(task.tags.get(EFFORT_TAG)?)?,
+        parse_duration(task.tags.get(SPENT_TAG)?)?,
+    ))
+}
+
+impl ToDo {
+    /// Compares the estimated and spent time of completed tasks having both
+    /// the `effort:` and the `spent:` tag. A task of more projects counts for each of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `by_week` - Groups the tasks also by the week they were completed in,
+    ///   tasks without the completion date are left out.
+    ///
+    /// # Returns
+    ///
+    /// The rows sorted by the week, the latest first, and by the project.
+    pub fn effort_report(&self, by_week: bool) -> Vec<EffortRow> {
+        let mut rows: BTreeMap<(Option<String>, String), EffortRow> = BTreeMap::new();
+        for task in &self.done {
+            let Some((estimated, spent)) = effort(task) else {
+                continue;
+            };
+            let week = match (by_week, task.finish_date) {
+                (false, _) => None,
+                (true, Some(date)) => {
+                    let week = date.iso_week();
+                    Some(format!("{}-W{:02}", week.year(), week.week()))
+                }
+                (true, None) => continue,
+            };
+            let projects = match task.projects().is_empty() {
+                true => vec![NO_PROJECT.to_string()],
+                false => task.projects().to_vec(),
+            };
+            for project in projects {
+                let row = rows
+                    .entry((week.clone(), project.clone()))
+                    .or_insert_with(|| EffortRow {
+                        week: week.clone(),
+                        project,
+                        tasks: 0,
+                        estimated: 0,
+                        spent: 0,
+                    });
+                row.tasks += 1;
+                row.estimated += estimated;
+                row.spent += spent;
+            }
+        }
+        let mut rows: Vec<EffortRow> = rows.into_values().collect();
+        rows.sort_by(|a, b| b.week.cmp(&a.week).then(a.project.cmp(&b.project)));
+        rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::todo::parse_task;
+
+    #[test]
+    fn duration() {
+        assert_eq!(parse_duration("45m"), Some(45));
+        assert_eq!(parse_duration("2h"), Some(120));
+        assert_eq!(parse_duration("1h30m"), Some(90));
+        assert_eq!(parse_duration("1.5H"), Some(90));
+        assert_eq!(parse_duration("3"), Some(180));
+        assert_eq!(parse_duration("2d"), None);
+        assert_eq!(parse_duration("h"), None);
+        assert_eq!(parse_duration("1h30"), None);
+        assert_eq!(parse_duration(""), None);
+
+        assert_eq!(format_duration(45), "45m");
+        assert_eq!(format_duration(120), "2h");
+        assert_eq!(format_duration(90), "1h30m");
+    }
+
+    #[test]
+    fn effort_report() {
+        let mut todo = ToDo::default();
+        for task in [
+            "x 2023-05-02 write the report +work effort:2h spent:3h",
+            "x 2023-05-04 review the code +work +review effort:1h spent:30m",
+            "x 2023-05-09 fix the bug +work effort:1h spent:1h",
+            "x 2023-05-09 call mom effort:15m spent:15m",
+            "x 2023-05-09 clean the desk +home effort:1h",
+            "water plants +home effort:10m spent:5m",
+        ] {
+            todo.add_task(parse_task(task).unwrap());
+        }
+
+        let row = |week: Option<&str>, project: &str, tasks, estimated, spent| EffortRow {
+            week: week.map(String::from),
+            project: project.to_string(),
+            tasks,
+            estimated,
+            spent,
+        };
+        assert_eq!(
+            todo.effort_report(true),
+            vec![
+                row(Some("2023-W19"), "(no project)", 1, 15, 15),
+                row(Some("2023-W19"), "work", 1, 60, 60),
+                row(Some("2023-W18"), "review", 1, 60, 30),
+                row(Some("2023-W18"), "work", 2, 180, 210),
+            ]
+        );
+        let report = todo.effort_report(false);
+        assert_eq!(report.len(), 3);
+        assert_eq!(report[2], row(None, "work", 3, 240, 270));
+        assert_eq!(report[2].ratio(), Some(113));
+    }
+}
@@ -0,0 +1,232 @@
+use super::{parse_task, ActivityKind, Change, ToDo};
+use crate::error::{ToDoError, ToDoRes};
+use chrono::NaiveDate;
+use std::{collections::HashMap, fmt};
+use todo_txt::{Priority, Task};
+
+/// The condition of an [`EscalationRule`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Condition {
+    /// The task is overdue by more than the number of days.
+    Overdue(i64),
+    /// The task was created more than the number of days ago.
+    Age(i64),
+}
+
+/// The change of a task made by an [`EscalationRule`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Escalation {
+    /// Raises the priority of the task, a higher priority is kept.
+    Priority(Priority),
+    /// Appends the text to the task if the task does not contain it yet, e.g. `#stale`.
+    Append(String),
+}
+
+impl fmt::Display for Escalation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Escalation::Priority(priority) => write!(f, "priority ({priority})"),
+            Escalation::Append(text) => write!(f, "added {text}"),
+        }
+    }
+}
+
+/// Represents a rule escalating old or overdue pending tasks.
+///
+/// The rule is configured as a condition and a change, e.g. `"overdue>7" = "(A)"`.
+/// Supported conditions are `overdue>N`, the task is overdue by more than N days,
+/// and `age>N`, the task was created more than N days ago. The change is either
+/// a priority `(A)` to `(Z)` the priority of the task is raised to, or any text
+/// appended to the task, e.g. `#stale`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EscalationRule {
+    rule: String,
+    condition: Condition,
+    escalation: Escalation,
+}
+
+impl EscalationRule {
+    /// Creates a new `EscalationRule`.
+    ///
+    /// # Arguments
+    ///
+    /// * `when` - The condition of the rule, e.g. `overdue>7`.
+    /// * `then` - The change of the task, e.g. `(A)` or `#stale`.
+    pub fn new(when: &str, then: &str) -> ToDoRes<Self> {
+        let err = || ToDoError::ParseEscalation(format!("{when} = {then}"));
+        let (name, days) = when.trim().split_once('>').ok_or_else(err)?;
+        let days: i64 = days.trim().parse().map_err(|_| err())?;
+        let condition = match name.trim() {
+            "overdue" => Condition::Overdue(days),
+            "age" => Condition::Age(days),
+            _ => return Err(err()),
+        };
+        let then = then.trim();
+        let escalation = match then
+            .strip_prefix('(')
+            .and_then(|priority| priority.strip_suffix(')'))
+        {
+            Some(priority) => {
+                let mut chars = priority.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if c.is_ascii_uppercase() => {
+                        Escalation::Priority(Priority::try_from(c).map_err(|_| err())?)
+                    }
+                    _ => return Err(err()),
+                }
+            }
+            None if then.is_empty() => return Err(err()),
+            None => Escalation::Append(then.to_string()),
+        };
+        Ok(Self {
+            rule: when.trim().to_string(),
+            condition,
+            escalation,
+        })
+    }
+
+    /// Parses the rules of the configuration, the rules are applied in the order of their conditions.
+    ///
+    /// # Arguments
+    ///
+    /// * `rules` - Changes of tasks by their conditions.
+    pub fn parse_all(rules: &HashMap<String, String>) -> ToDoRes<Vec<Self>> {
+        let mut rules = rules
+            .iter()
+            .map(|(when, then)| Self::new(when, then))
+            .collect::<ToDoRes<Vec<_>>>()?;
+        rules.sort_by(|a, b| a.rule.cmp(&b.rule));
+        Ok(rules)
+    }
+
+    /// Applies the rule to the task.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The pending task.
+    /// * `today` - The date ages and overdue durations are computed to.
+    ///
+    /// # Returns
+    ///
+    /// The escalated task or `None` if the rule does not change the task.
+    fn escalate(&self, task: &Task, today: NaiveDate) -> Option<Task> {
+        let days = match self.condition {
+            Condition::Overdue(days) => task.due_date.map(|due| (today - due, days)),
+            Condition::Age(days) => task.create_date.map(|created| (today - created, days)),
+        };
+        match days {
+            Some((duration, days)) if duration.num_days() > days => {}
+            _ => return None,
+        }
+        match &self.escalation {
+            Escalation::Priority(priority) if *priority > task.priority => {
+                let mut task = task.clone();
+                task.priority = priority.clone();
+                Some(task)
+            }
+            Escalation::Priority(_) => None,
+            Escalation::Append(text) => append_text(task, text),
+        }
+    }
+}
+
+/// Appends the text to the task unless the task contains all its words already.
+///
+/// # Returns
+///
+/// The changed task or `None` if the task is not changed.
+pub(super) fn append_text(task: &Task, text: &str) -> Option<Task> {
+    let line = task.to_string();
+    let words: Vec<&str> = line.split_whitespace().collect();
+    if text.split_whitespace().all(|word| words.contains(&word)) {
+        return None;
+    }
+    parse_task(&format!("{line} {text}")).ok()
+}
+
+impl ToDo {
+    /// Applies the escalation rules to pending tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `today` - The date ages and overdue durations are computed to.
+    ///
+    /// # Returns
+    ///
+    /// Descriptions of the changes, e.g. `call mom: priority (A) (overdue>7)`.
+    pub fn escalate(&mut self, today: NaiveDate) -> Vec<String> {
+        let mut changes = Vec::new();
+        for index in 0..self.pending.len() {
+            let previous = self.pending[index].clone();
+            for rule in &self.config.escalation_rules {
+                if let Some(task) = rule.escalate(&self.pending[index], today) {
+                    changes.push(format!(
+                        "{}: {} ({})",
+                        previous.subject, rule.escalation, rule.rule
+                    ));
+                    self.pending[index] = task;
+                }
+            }
+            if self.pending[index] != previous {
+                let task = self.pending[index].clone();
+                self.record(ActivityKind::Edit, &task, Some(&previous));
+            }
+        }
+        if !changes.is_empty() {
+            self.version += 1;
+            self.notify(Change::Tasks);
+        }
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert!(EscalationRule::new("overdue>7", "(A)").is_ok());
+        assert!(EscalationRule::new("age > 30", "#stale").is_ok());
+        for (when, then) in [
+            ("overdue<7", "(A)"),
+            ("overdue>week", "(A)"),
+            ("late>7", "(A)"),
+            ("age>30", "(a)"),
+            ("age>30", "(AB)"),
+            ("age>30", " "),
+        ] {
+            assert_eq!(
+                EscalationRule::new(when, then),
+                Err(ToDoError::ParseEscalation(format!("{when} = {then}")))
+            );
+        }
+    }
+
+    #[test]
+    fn escalate() -> ToDoRes<()> {
+        let mut todo = ToDo::default();
+        todo.config.escalation_rules = EscalationRule::parse_all(&HashMap::from([
+            (String::from("overdue>7"), String::from("(A)")),
+            (String::from("age>30"), String::from("#stale")),
+        ]))?;
+        todo.new_task("(C) pay the bills due:2023-05-01").unwrap();
+        todo.new_task("(A) call mom due:2023-05-01").unwrap();
+        todo.new_task("write the report due:2023-05-08").unwrap();
+        todo.new_task("2023-03-01 clean the garage").unwrap();
+        let today = NaiveDate::from_ymd_opt(2023, 5, 10).unwrap();
+
+        assert_eq!(
+            todo.escalate(today),
+            vec![
+                String::from("pay the bills: priority (A) (overdue>7)"),
+                String::from("clean the garage: added #stale (age>30)"),
+            ]
+        );
+        assert_eq!(todo.pending[0].priority, 0);
+        assert!(todo.pending[3].hashtags.contains(&String::from("stale")));
+        assert!(todo.pending[2].priority.is_lowest());
+        assert!(todo.escalate(today).is_empty());
+        Ok(())
+    }
+}
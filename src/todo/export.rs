@@ -0,0 +1,391 @@
+use super::{Parser, ToDo, ToDoData};
+use crate::error::{ToDoError, ToDoRes};
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use serde::Serialize;
+use std::{collections::BTreeMap, fmt::Write, path::Path, str::FromStr};
+use todo_txt::{Date, Task};
+
+/// Represents the format the tasks are exported to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// An array of task objects with all parsed fields.
+    Json,
+    /// A checklist, completed tasks are checked.
+    Markdown,
+    /// A calendar with an all-day event on the due date of every task that has one.
+    ICalendar,
+}
+
+impl ExportFormat {
+    /// Gets the format from the extension of the destination file, e.g. `tasks.ics`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the extension is not `json`, `md`, `markdown` or `ics`.
+    pub fn from_path(path: &Path) -> ToDoRes<Self> {
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default()
+            .parse()
+            .map_err(|_| ToDoError::ParseExportFormat(path.display().to_string()))
+    }
+
+    /// Serializes the tasks in the format.
+    ///
+    /// # Arguments
+    ///
+    /// * `tasks` - The exported tasks in the order they are written.
+    /// * `now` - The time of the export, calendar events are stamped with it.
+    pub fn serialize<'a>(
+        &self,
+        tasks: impl IntoIterator<Item = &'a Task>,
+        now: NaiveDateTime,
+    ) -> String {
+        match self {
+            Self::Json => to_json(tasks),
+            Self::Markdown => to_markdown(tasks, None),
+            Self::ICalendar => to_icalendar(tasks, now, None),
+        }
+    }
+
+    /// Serializes the tasks in the format, the text of a task is rendered by the template.
+    /// The template replaces the priority, the subject and the due date of Markdown items
+    /// and the summary of calendar events, JSON is written with all fields as they are.
+    ///
+    /// # Arguments
+    ///
+    /// * `tasks` - The exported tasks in the order they are written.
+    /// * `now` - The time of the export, calendar events are stamped with it.
+    /// * `template` - The template of the text of a task, see `export_task_format`.
+    /// * `todo` - The todo list the template reads the variables of tasks from.
+    pub fn serialize_with<'a>(
+        &self,
+        tasks: impl IntoIterator<Item = &'a Task>,
+        now: NaiveDateTime,
+        template: &Parser,
+        todo: &ToDo,
+    ) -> String {
+        let text = |task: &Task| template.fill_text(task, todo);
+        match self {
+            Self::Json => to_json(tasks),
+            Self::Markdown => to_markdown(tasks, Some(&text)),
+            Self::ICalendar => to_icalendar(tasks, now, Some(&text)),
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = ToDoError;
+
+    fn from_str(s: &str) -> ToDoRes<Self> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "md" | "markdown" => Ok(Self::Markdown),
+            "ics" | "ical" | "icalendar" => Ok(Self::ICalendar),
+            _ => Err(ToDoError::ParseExportFormat(s.to_string())),
+        }
+    }
+}
+
+/// The fields of a task written by the JSON export.
+#[derive(Serialize)]
+struct JsonTask<'a> {
+    subject: &'a str,
+    priority: Option<String>,
+    finished: bool,
+    create_date: Option<String>,
+    finish_date: Option<String>,
+    due_date: Option<String>,
+    threshold_date: Option<String>,
+    projects: &'a [String],
+    contexts: &'a [String],
+    hashtags: &'a [String],
+    tags: &'a BTreeMap<String, String>,
+}
+
+impl<'a> From<&'a Task> for JsonTask<'a> {
+    fn from(task: &'a Task) -> Self {
+        let date = |date: Option<Date>| date.map(|date| date.to_string());
+        Self {
+            subject: &task.subject,
+            priority: (!task.priority.is_lowest()).then(|| task.priority.to_string()),
+            finished: task.finished,
+            create_date: date(task.create_date),
+            finish_date: date(task.finish_date),
+            due_date: date(task.due_date),
+            threshold_date: date(task.threshold_date),
+            projects: task.projects(),
+            contexts: task.contexts(),
+            hashtags: &task.hashtags,
+            tags: &task.tags,
+        }
+    }
+}
+
+fn to_json<'a>(tasks: impl IntoIterator<Item = &'a Task>) -> String {
+    let tasks: Vec<JsonTask> = tasks.into_iter().map(JsonTask::from).collect();
+    // Serializing strings and derived structs cannot fail.
+    serde_json::to_string_pretty(&tasks).unwrap_or_default() + "\n"
+}
+
+/// Renders the text of a task in an export.
+type TaskText<'a> = Option<&'a dyn Fn(&Task) -> String>;
+
+fn to_markdown<'a>(tasks: impl IntoIterator<Item = &'a Task>, text: TaskText) -> String {
+    tasks
+        .into_iter()
+        .map(|task| {
+            let mut line = format!("- [{}] ", if task.finished { 'x' } else { ' ' });
+            if let Some(text) = text {
+                return line + &text(task).replace('[', "\\[") + "\n";
+            }
+            if !task.priority.is_lowest() {
+                let _ = write!(line, "({}) ", task.priority);
+            }
+            // Brackets at the start would be read as a link, the subject is kept as written.
+            line += &task.subject.replace('[', "\\[");
+            if let Some(due) = task.due_date {
+                let _ = write!(line, " (due {due})");
+            }
+            line + "\n"
+        })
+        .collect()
+}
+
+/// Escapes the text of an iCalendar property value.
+fn ics_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Folds the content line to lines of at most 75 bytes as iCalendar requires,
+/// continuation lines start with a space.
+fn ics_line(ics: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            *ics += "\r\n ";
+            width = 1;
+        }
+        ics.push(c);
+        width += c.len_utf8();
+    }
+    *ics += "\r\n";
+}
+
+fn ics_date(date: NaiveDate) -> String {
+    date.format("%Y%m%d").to_string()
+}
+
+fn to_icalendar<'a>(
+    tasks: impl IntoIterator<Item = &'a Task>,
+    now: NaiveDateTime,
+    text: TaskText,
+) -> String {
+    let mut ics = String::new();
+    ics_line(&mut ics, "BEGIN:VCALENDAR");
+    ics_line(&mut ics, "VERSION:2.0");
+    ics_line(
+        &mut ics,
+        &format!(
+            "PRODID:-//{}//{}//EN",
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION")
+        ),
+    );
+    for task in tasks {
+        let Some(due) = task.due_date else {
+            continue;
+        };
+        // The same task is exported with the same identifier, so a calendar updates
+        // its event instead of adding another one.
+        let uid = task
+            .subject
+            .bytes()
+            .fold(0xcbf29ce484222325_u64, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+            });
+        ics_line(&mut ics, "BEGIN:VEVENT");
+        ics_line(
+            &mut ics,
+            &format!("UID:{uid:016x}@{}", env!("CARGO_PKG_NAME")),
+        );
+        ics_line(
+            &mut ics,
+            &format!("DTSTAMP:{}", now.format("%Y%m%dT%H%M%S")),
+        );
+        ics_line(&mut ics, &format!("DTSTART;VALUE=DATE:{}", ics_date(due)));
+        ics_line(
+            &mut ics,
+            &format!("DTEND;VALUE=DATE:{}", ics_date(due + Duration::days(1))),
+        );
+        let summary = text.map_or_else(|| task.subject.clone(), |text| text(task));
+        ics_line(&mut ics, &format!("SUMMARY:{}", ics_text(&summary)));
+        if !task.priority.is_lowest() {
+            // Priority A is the highest iCalendar priority 1, the letters after I are 9.
+            let priority = (task.priority.to_string().as_bytes()[0] - b'A' + 1).min(9);
+            ics_line(&mut ics, &format!("PRIORITY:{priority}"));
+        }
+        if task.finished {
+            ics_line(&mut ics, "STATUS:COMPLETED");
+        }
+        if !task.projects().is_empty() {
+            let projects: Vec<String> = task.projects().iter().map(|p| ics_text(p)).collect();
+            ics_line(&mut ics, &format!("CATEGORIES:{}", projects.join(",")));
+        }
+        ics_line(&mut ics, "END:VEVENT");
+    }
+    ics_line(&mut ics, "END:VCALENDAR");
+    ics
+}
+
+impl ToDo {
+    /// Exports the pending tasks followed by the completed ones, both filtered by the
+    /// active filters and sorted the same way as they are shown in the lists.
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - The format of the export.
+    /// * `now` - The time of the export.
+    /// * `template` - The template of the text of a task, the subject is exported if not set.
+    pub fn export(
+        &self,
+        format: ExportFormat,
+        now: NaiveDateTime,
+        template: Option<&Parser>,
+    ) -> String {
+        let pending = self.get_filtered_and_sorted(ToDoData::Pending);
+        let done = self.get_filtered_and_sorted(ToDoData::Done);
+        let tasks = pending
+            .vec
+            .iter()
+            .chain(done.vec.iter())
+            .map(|(_, task)| *task);
+        match template {
+            Some(template) => format.serialize_with(tasks, now, template, self),
+            None => format.serialize(tasks, now),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::Styles;
+
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 7, 1)
+            .unwrap()
+            .and_hms_opt(9, 30, 0)
+            .unwrap()
+    }
+
+    fn todo() -> ToDo {
+        let mut todo = ToDo::default();
+        todo.new_task("(A) pay \"rent\" +home @bank id:rent due:2024-07-03")
+            .unwrap();
+        todo.new_task("x 2024-06-30 water plants, balcony +home")
+            .unwrap();
+        todo.new_task("read [book]").unwrap();
+        todo
+    }
+
+    #[test]
+    fn format() -> ToDoRes<()> {
+        assert_eq!(
+            ExportFormat::from_path(Path::new("out/tasks.JSON"))?,
+            ExportFormat::Json
+        );
+        assert_eq!(
+            ExportFormat::from_path(Path::new("tasks.md"))?,
+            ExportFormat::Markdown
+        );
+        assert_eq!(
+            ExportFormat::from_path(Path::new("tasks.ics"))?,
+            ExportFormat::ICalendar
+        );
+        assert!(ExportFormat::from_path(Path::new("tasks.txt")).is_err());
+        assert!(ExportFormat::from_path(Path::new("tasks")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn json() {
+        let json = todo().export(ExportFormat::Json, now(), None);
+        assert!(json.starts_with("[\n  {\n"));
+        assert!(json.contains(r#""subject": "pay \"rent\" +home @bank""#));
+        let tasks: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let rent = tasks
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|task| task["tags"]["id"] == "rent")
+            .unwrap();
+        assert_eq!(rent["subject"], "pay \"rent\" +home @bank");
+        assert_eq!(rent["priority"], "A");
+        assert_eq!(rent["due_date"], "2024-07-03");
+        assert_eq!(rent["threshold_date"], serde_json::Value::Null);
+        assert_eq!(rent["projects"], serde_json::json!(["home"]));
+        assert_eq!(rent["contexts"], serde_json::json!(["bank"]));
+        assert!(json.contains(r#""finish_date": "2024-06-30""#));
+        assert_eq!(json.matches("\"subject\"").count(), 3);
+        assert_eq!(ExportFormat::Json.serialize([], now()), "[]\n");
+    }
+
+    #[test]
+    fn markdown() {
+        let markdown = todo().export(ExportFormat::Markdown, now(), None);
+        assert_eq!(
+            markdown.lines().collect::<Vec<_>>(),
+            [
+                "- [ ] (A) pay \"rent\" +home @bank (due 2024-07-03)",
+                "- [ ] read \\[book]",
+                "- [x] water plants, balcony +home",
+            ]
+        );
+    }
+
+    #[test]
+    fn icalendar() {
+        let ics = todo().export(ExportFormat::ICalendar, now(), None);
+        let lines: Vec<&str> = ics.split("\r\n").collect();
+        assert_eq!(lines[0], "BEGIN:VCALENDAR");
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+        assert!(lines.contains(&"DTSTAMP:20240701T093000"));
+        assert!(lines.contains(&"DTSTART;VALUE=DATE:20240703"));
+        assert!(lines.contains(&"DTEND;VALUE=DATE:20240704"));
+        assert!(lines.contains(&"SUMMARY:pay \"rent\" +home @bank"));
+        assert!(lines.contains(&"PRIORITY:1"));
+        assert!(lines.contains(&"CATEGORIES:home"));
+        assert_eq!(lines[lines.len() - 2], "END:VCALENDAR");
+
+        let mut long = ToDo::default();
+        long.new_task(&format!("{} due:2024-07-03", "a".repeat(100)))
+            .unwrap();
+        let ics = long.export(ExportFormat::ICalendar, now(), None);
+        assert!(ics.split("\r\n").all(|line| line.len() <= 75));
+        assert!(ics.contains(&format!("\r\n {}", "a".repeat(33))));
+    }
+
+    #[test]
+    fn template() -> ToDoRes<()> {
+        let todo = todo();
+        let template = Parser::new("[$priority ]$subject[ due $due_date]", Styles::default())?;
+        let markdown = todo.export(ExportFormat::Markdown, now(), Some(&template));
+        assert_eq!(
+            markdown.lines().collect::<Vec<_>>(),
+            [
+                "- [ ] A pay \"rent\" +home @bank due 2024-07-03",
+                "- [ ] read \\[book]",
+                "- [x] water plants, balcony +home",
+            ]
+        );
+        let ics = todo.export(ExportFormat::ICalendar, now(), Some(&template));
+        assert!(ics.contains("\r\nSUMMARY:A pay \"rent\" +home @bank due 2024-07-03\r\n"));
+        let json = todo.export(ExportFormat::Json, now(), Some(&template));
+        assert_eq!(json, todo.export(ExportFormat::Json, now(), None));
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use super::{TaskQuery, ToDo};
+use chrono::NaiveDateTime;
+use todo_txt::Task;
+
+impl ToDo {
+    /// Gets the query of the filter bar, `None` if the tasks are not filtered by it.
+    pub fn filter_bar(&self) -> Option<&TaskQuery> {
+        self.filter_bar.as_ref()
+    }
+
+    /// Sets the query of the filter bar, a query without terms clears the filter bar.
+    pub(super) fn set_filter_bar(&mut self, query: Option<TaskQuery>) {
+        self.filter_bar = query.filter(|query| !query.to_string().is_empty());
+    }
+
+    /// Checks whether the task matches the query of the filter bar, every task matches without it.
+    pub(super) fn matches_filter_bar(&self, task: &Task, now: NaiveDateTime) -> bool {
+        self.filter_bar
+            .as_ref()
+            .is_none_or(|query| query.matches(task, now, &self.config.context_hours))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::todo::{Action, FilterState, ToDo, ToDoCategory, ToDoData};
+
+    #[test]
+    fn filter_bar() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        todo.new_task("(B) write the report +work @pc")?;
+        todo.new_task("(A) call the boss +work @phone")?;
+        todo.new_task("buy milk +home")?;
+        let shown = |todo: &ToDo| {
+            todo.get_filtered_and_sorted(ToDoData::Pending)
+                .vec
+                .iter()
+                .map(|(_, task)| task.subject.clone())
+                .collect::<Vec<_>>()
+        };
+
+        todo.apply(Action::SetFilterBar {
+            query: Some(String::from("+work !b")),
+        })?;
+        assert_eq!(todo.filter_bar().unwrap().to_string(), "+work !b");
+        assert_eq!(shown(&todo), ["write the report +work @pc"]);
+
+        // The filter bar is combined with the filters of the category panes.
+        todo.apply(Action::SetFilterBar {
+            query: Some(String::from("+work")),
+        })?;
+        todo.apply(Action::ToggleFilter {
+            category: ToDoCategory::Contexts,
+            name: String::from("phone"),
+            state: FilterState::Select,
+        })?;
+        assert_eq!(shown(&todo), ["call the boss +work @phone"]);
+
+        todo.apply(Action::SetFilterBar {
+            query: Some(String::from("  ")),
+        })?;
+        assert_eq!(todo.filter_bar(), None);
+        todo.apply(Action::SetFilterBar { query: None })?;
+        assert_eq!(shown(&todo), ["call the boss +work @phone"]);
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use super::ToDo;
+use chrono::{Duration, NaiveDate};
+use std::collections::BTreeMap;
+
+/// Represents the estimate of when the open tasks of a project are completed
+/// at the pace the tasks of the project were completed recently.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Forecast {
+    /// The project, `None` for all tasks.
+    pub project: Option<String>,
+    /// The number of pending tasks.
+    pub open: usize,
+    /// The number of tasks completed in the recent days.
+    pub completed: usize,
+    /// The number of tasks completed per week.
+    pub per_week: f64,
+    /// The date the pending tasks are completed by, `None` if no task was completed recently.
+    pub clear_on: Option<NaiveDate>,
+}
+
+impl Forecast {
+    /// Creates the forecast from the number of open and recently completed tasks.
+    fn new(
+        project: Option<String>,
+        open: usize,
+        completed: usize,
+        today: NaiveDate,
+        days: usize,
+    ) -> Self {
+        let clear_on = match (open, completed) {
+            (0, _) => Some(today),
+            (_, 0) => None,
+            _ => Some(today + Duration::days((open * days).div_ceil(completed) as i64)),
+        };
+        Self {
+            project,
+            open,
+            completed,
+            per_week: completed as f64 * 7.0 / days.max(1) as f64,
+            clear_on,
+        }
+    }
+}
+
+impl ToDo {
+    /// Forecasts when the pending tasks are completed from the tasks completed in the recent
+    /// days. A task of more projects counts for each of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `today` - The last day of the recent days, the forecast starts from it.
+    /// * `days` - The number of recent days the pace of completing tasks is measured over.
+    ///
+    /// # Returns
+    ///
+    /// The forecast of all tasks followed by the projects with pending tasks, the earliest
+    /// cleared first, the projects without recently completed tasks last.
+    pub fn forecast(&self, today: NaiveDate, days: usize) -> Vec<Forecast> {
+        let since = today - Duration::days(days as i64);
+        let recent: Vec<_> = self
+            .done
+            .iter()
+            .filter(|task| {
+                task.finish_date
+                    .is_some_and(|date| date > since && date <= today)
+            })
+            .collect();
+        let mut projects: BTreeMap<&String, (usize, usize)> = BTreeMap::new();
+        for task in &self.pending {
+            for project in task.projects() {
+                projects.entry(project).or_default().0 += 1;
+            }
+        }
+        for task in &recent {
+            for project in task.projects() {
+                if let Some((_, completed)) = projects.get_mut(project) {
+                    *completed += 1;
+                }
+            }
+        }
+        let mut rows: Vec<Forecast> = projects
+            .into_iter()
+            .map(|(project, (open, completed))| {
+                Forecast::new(Some(project.clone()), open, completed, today, days)
+            })
+            .collect();
+        rows.sort_by_key(|row| (row.clear_on.is_none(), row.clear_on));
+        rows.insert(
+            0,
+            Forecast::new(None, self.pending.len(), recent.len(), today, days),
+        );
+        rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forecast() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        todo.new_task("write the report +work")?;
+        todo.new_task("review the budget +work")?;
+        todo.new_task("fix the bike +home")?;
+        todo.new_task("call mom")?;
+        for task in [
+            "x 2024-06-20 send the invoice +work",
+            "x 2024-06-27 plan the sprint +work",
+            "x 2024-06-28 clean the garage +garden",
+            "x 2024-05-01 paint the fence +home",
+        ] {
+            todo.new_task(task)?;
+        }
+        let today = NaiveDate::from_ymd_opt(2024, 7, 1).unwrap();
+        let forecast = todo.forecast(today, 28);
+        let rows: Vec<(Option<&str>, usize, usize, Option<NaiveDate>)> = forecast
+            .iter()
+            .map(|row| {
+                (
+                    row.project.as_deref(),
+                    row.open,
+                    row.completed,
+                    row.clear_on,
+                )
+            })
+            .collect();
+        let date = |m, d| NaiveDate::from_ymd_opt(2024, m, d);
+        assert_eq!(
+            rows,
+            [
+                (None, 4, 3, date(8, 8)),
+                (Some("work"), 2, 2, date(7, 29)),
+                (Some("home"), 1, 0, None),
+            ]
+        );
+        assert_eq!(forecast[1].per_week, 0.5);
+        Ok(())
+    }
+}
@@ -0,0 +1,260 @@
+use super::{archive::parse_archived, ToDo};
+use chrono::{Datelike, Duration, NaiveDate};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::{self, File},
+    io::{self, BufRead, BufReader, ErrorKind},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+use todo_txt::Task;
+
+/// The completions of tasks over all time, the tasks archived in the done file
+/// together with the completed tasks of the todo list.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct History {
+    days: BTreeMap<NaiveDate, usize>,
+    undated: usize,
+    projects: HashMap<String, usize>,
+}
+
+impl History {
+    /// Reads the completions of the tasks archived in the done file. The file is
+    /// read line by line, a missing file has no tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the done file.
+    pub fn read(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut history = Self::default();
+        let reader = match File::open(path) {
+            Ok(file) => BufReader::new(file),
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(history),
+            Err(e) => return Err(e),
+        };
+        for line in reader.lines() {
+            let line = line?;
+            if let Some(task) = Some(line.trim())
+                .filter(|line| !line.is_empty())
+                .and_then(parse_archived)
+            {
+                history.add(&task);
+            }
+        }
+        Ok(history)
+    }
+
+    fn add(&mut self, task: &Task) {
+        if !task.finished {
+            return;
+        }
+        match task.finish_date {
+            Some(date) => *self.days.entry(date).or_default() += 1,
+            None => self.undated += 1,
+        }
+        for project in task.projects() {
+            *self.projects.entry(project.clone()).or_default() += 1;
+        }
+    }
+
+    /// Gets the history extended by the completed tasks, e.g. those not archived yet.
+    pub fn with_tasks<'a>(&self, tasks: impl IntoIterator<Item = &'a Task>) -> Self {
+        let mut history = self.clone();
+        tasks.into_iter().for_each(|task| history.add(task));
+        history
+    }
+
+    /// Gets the number of completed tasks.
+    pub fn total(&self) -> usize {
+        self.days.values().sum::<usize>() + self.undated
+    }
+
+    /// Gets the number of completed tasks without the completion date.
+    pub fn undated(&self) -> usize {
+        self.undated
+    }
+
+    /// Gets the date of the first completion.
+    pub fn first(&self) -> Option<NaiveDate> {
+        self.days.keys().next().copied()
+    }
+
+    /// Gets the number of tasks completed on the date.
+    pub fn completed_on(&self, date: NaiveDate) -> usize {
+        self.days.get(&date).copied().unwrap_or_default()
+    }
+
+    /// Gets the streaks of days with a completed task.
+    ///
+    /// # Arguments
+    ///
+    /// * `today` - The current date, the current streak lasts while a task is completed
+    ///   today or was completed yesterday.
+    ///
+    /// # Returns
+    ///
+    /// The current and the longest streak in days.
+    pub fn streaks(&self, today: NaiveDate) -> (usize, usize) {
+        let mut longest = 0;
+        let mut streak = 0;
+        let mut last: Option<NaiveDate> = None;
+        for date in self.days.keys().copied().filter(|date| *date <= today) {
+            streak = match last {
+                Some(last) if date - last == Duration::days(1) => streak + 1,
+                _ => 1,
+            };
+            longest = longest.max(streak);
+            last = Some(date);
+        }
+        let current = match last {
+            Some(last) if today - last <= Duration::days(1) => streak,
+            _ => 0,
+        };
+        (current, longest)
+    }
+
+    /// Gets the numbers of completed tasks per day of the week, Monday first.
+    pub fn weekdays(&self) -> [usize; 7] {
+        let mut weekdays = [0; 7];
+        for (date, count) in &self.days {
+            weekdays[date.weekday().num_days_from_monday() as usize] += count;
+        }
+        weekdays
+    }
+
+    /// Gets the numbers of completed tasks per day of the recent weeks.
+    ///
+    /// # Arguments
+    ///
+    /// * `today` - The current date, its week is the last one.
+    /// * `weeks` - The number of weeks.
+    ///
+    /// # Returns
+    ///
+    /// The weeks, the earliest first, with the days from Monday to Sunday.
+    pub fn heatmap(&self, today: NaiveDate, weeks: usize) -> Vec<[usize; 7]> {
+        let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+        let start = monday - Duration::weeks(weeks.saturating_sub(1) as i64);
+        (0..weeks)
+            .map(|week| {
+                let mut days = [0; 7];
+                for (day, count) in days.iter_mut().enumerate() {
+                    let date = start + Duration::days((week * 7 + day) as i64);
+                    *count = self.completed_on(date);
+                }
+                days
+            })
+            .collect()
+    }
+
+    /// Gets the projects with the most completed tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The maximal number of projects.
+    pub fn top_projects(&self, count: usize) -> Vec<(String, usize)> {
+        let mut projects: Vec<_> = self
+            .projects
+            .iter()
+            .map(|(project, tasks)| (project.clone(), *tasks))
+            .collect();
+        projects.sort_by(|(a, a_tasks), (b, b_tasks)| b_tasks.cmp(a_tasks).then(a.cmp(b)));
+        projects.truncate(count);
+        projects
+    }
+}
+
+/// Keeps the history of the done file, so the file is read again only when it changes.
+#[derive(Default)]
+pub struct HistoryCache {
+    path: PathBuf,
+    stamp: Option<(u64, SystemTime)>,
+    history: History,
+}
+
+impl HistoryCache {
+    /// Gets the history of the done file, it is read when the path, the size or
+    /// the modification time of the file changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the done file.
+    pub fn get(&mut self, path: impl AsRef<Path>) -> io::Result<&History> {
+        let path = path.as_ref();
+        let stamp = match fs::metadata(path) {
+            Ok(metadata) => Some((metadata.len(), metadata.modified()?)),
+            Err(e) if e.kind() == ErrorKind::NotFound => None,
+            Err(e) => return Err(e),
+        };
+        if self.path != path || self.stamp != stamp {
+            self.history = History::read(path)?;
+            self.path = path.to_path_buf();
+            self.stamp = stamp;
+        }
+        Ok(&self.history)
+    }
+}
+
+impl ToDo {
+    /// Gets the history of all completed tasks, the archived ones and those in the todo list.
+    ///
+    /// # Arguments
+    ///
+    /// * `archived` - The history of the done file, see [`HistoryCache`].
+    pub fn history(&self, archived: &History) -> History {
+        archived.with_tasks(&self.done)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env::temp_dir, process};
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, month, day).unwrap()
+    }
+
+    #[test]
+    fn read() -> io::Result<()> {
+        let path = temp_dir().join(format!("todotxt-tui-history-test-{}.txt", process::id()));
+        fs::write(
+            &path,
+            "x 2024-07-01 send the invoice +work\n\
+             x 2024-07-02 plan the sprint +work\n\
+             \n\
+             x 2024-07-02 fix the bike +home\n\
+             x clean the garage +home\n\
+             x 2024-07-04 call mom\n",
+        )?;
+        let mut cache = HistoryCache::default();
+        let history = cache.get(&path)?.clone();
+        assert_eq!(history.total(), 5);
+        assert_eq!(history.undated(), 1);
+        assert_eq!(history.first(), Some(date(7, 1)));
+        assert_eq!(history.completed_on(date(7, 2)), 2);
+        assert_eq!(
+            history.top_projects(1),
+            [(String::from("home"), 2)].to_vec()
+        );
+
+        let mut todo = ToDo::default();
+        todo.new_task("x 2024-07-05 water plants +home").unwrap();
+        todo.new_task("buy milk").unwrap();
+        let all = todo.history(&history);
+        assert_eq!(all.total(), 6);
+        assert_eq!(all.streaks(date(7, 5)), (2, 2));
+        assert_eq!(all.streaks(date(7, 7)), (0, 2));
+        assert_eq!(all.weekdays(), [1, 2, 0, 1, 1, 0, 0]);
+        assert_eq!(
+            all.heatmap(date(7, 10), 2),
+            [[1, 2, 0, 1, 1, 0, 0], [0; 7]].to_vec()
+        );
+
+        fs::write(&path, "x 2024-07-01 send the invoice +work\n")?;
+        assert_eq!(cache.get(&path)?.total(), 1);
+        fs::remove_file(&path)?;
+        assert_eq!(cache.get(&path)?.total(), 0);
+        Ok(())
+    }
+}
@@ -0,0 +1,356 @@
+use super::{Action, ToDo};
+use crate::error::{ToDoError, ToDoRes};
+use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone};
+use serde::Deserialize;
+use std::{fmt::Write, path::Path, str::FromStr};
+
+/// Represents the format tasks are imported from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// The JSON output of `task export` of Taskwarrior.
+    Taskwarrior,
+    /// A checklist of `- [ ]` items, checked items are completed.
+    Markdown,
+}
+
+impl ImportFormat {
+    /// Gets the format from the extension of the imported file, e.g. `tasks.json`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the extension is not `json`, `md` or `markdown`.
+    pub fn from_path(path: &Path) -> ToDoRes<Self> {
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default()
+            .parse()
+            .map_err(|_| ToDoError::ParseImportFormat(path.display().to_string()))
+    }
+
+    /// Converts the content of the imported file to todo.txt lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The content of the imported file.
+    /// * `today` - The completion date of checked items, Markdown has none.
+    ///
+    /// # Returns
+    ///
+    /// The lines of the tasks in the order of the file, or an error if the JSON is invalid.
+    pub fn parse(&self, content: &str, today: NaiveDate) -> ToDoRes<Vec<String>> {
+        match self {
+            Self::Taskwarrior => from_taskwarrior(content, &Local),
+            Self::Markdown => Ok(content
+                .lines()
+                .filter_map(|line| from_markdown(line, today))
+                .collect()),
+        }
+    }
+}
+
+impl FromStr for ImportFormat {
+    type Err = ToDoError;
+
+    fn from_str(s: &str) -> ToDoRes<Self> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(Self::Taskwarrior),
+            "md" | "markdown" => Ok(Self::Markdown),
+            _ => Err(ToDoError::ParseImportFormat(s.to_string())),
+        }
+    }
+}
+
+/// The parts of an imported task, written as a todo.txt line.
+#[derive(Default)]
+struct ImportedTask {
+    finished: Option<NaiveDate>,
+    priority: Option<char>,
+    created: Option<NaiveDate>,
+    subject: String,
+    categories: Vec<String>,
+    due: Option<NaiveDate>,
+    threshold: Option<NaiveDate>,
+}
+
+impl ImportedTask {
+    fn line(&self) -> String {
+        let mut line = String::new();
+        match (self.finished, self.priority) {
+            (Some(finished), _) => {
+                let _ = write!(line, "x {finished} ");
+            }
+            (None, Some(priority)) => {
+                let _ = write!(line, "({priority}) ");
+            }
+            (None, None) => {}
+        }
+        if let Some(created) = self.created {
+            let _ = write!(line, "{created} ");
+        }
+        // Line breaks would split the task into more tasks.
+        line += &self
+            .subject
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        for category in &self.categories {
+            let _ = write!(line, " {category}");
+        }
+        // A completed task has no priority, the priority is kept as a tag.
+        if let (Some(_), Some(priority)) = (self.finished, self.priority) {
+            let _ = write!(line, " pri:{priority}");
+        }
+        if let Some(due) = self.due {
+            let _ = write!(line, " due:{due}");
+        }
+        if let Some(threshold) = self.threshold {
+            let _ = write!(line, " t:{threshold}");
+        }
+        line
+    }
+}
+
+/// A task of a Taskwarrior export, only the fields kept in todo.txt are read.
+#[derive(Deserialize)]
+struct TaskwarriorTask {
+    status: Option<String>,
+    #[serde(default)]
+    description: String,
+    project: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    priority: Option<String>,
+    entry: Option<String>,
+    end: Option<String>,
+    modified: Option<String>,
+    due: Option<String>,
+    wait: Option<String>,
+    scheduled: Option<String>,
+}
+
+/// A value of a Taskwarrior export, the export is an array of tasks or one task per line.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TaskwarriorExport {
+    Tasks(Vec<TaskwarriorTask>),
+    Task(Box<TaskwarriorTask>),
+}
+
+/// Converts a Taskwarrior export, either an array of tasks or one task per line.
+/// Deleted tasks and templates of recurring tasks are left out.
+///
+/// # Arguments
+///
+/// * `content` - The exported JSON.
+/// * `zone` - The time zone the UTC timestamps of the export are converted to.
+fn from_taskwarrior<Tz: TimeZone>(content: &str, zone: &Tz) -> ToDoRes<Vec<String>> {
+    let mut tasks = Vec::new();
+    for value in serde_json::Deserializer::from_str(content).into_iter() {
+        match value.map_err(|e| ToDoError::ParseImport(e.to_string()))? {
+            TaskwarriorExport::Tasks(values) => tasks.extend(values),
+            TaskwarriorExport::Task(task) => tasks.push(*task),
+        }
+    }
+    Ok(tasks
+        .iter()
+        .filter(|task| !matches!(task.status.as_deref(), Some("deleted" | "recurring")))
+        .map(|task| {
+            let date = |stamp: &Option<String>| {
+                stamp
+                    .as_deref()
+                    .and_then(|stamp| taskwarrior_date(stamp, zone))
+            };
+            let mut categories = Vec::new();
+            if let Some(project) = &task.project {
+                categories.push(format!("+{}", project.replace(' ', "-")));
+            }
+            categories.extend(
+                task.tags
+                    .iter()
+                    .map(|tag| format!("@{}", tag.replace(' ', "-"))),
+            );
+            let finished = (task.status.as_deref() == Some("completed"))
+                .then(|| {
+                    date(&task.end)
+                        .or(date(&task.modified))
+                        .or(date(&task.entry))
+                })
+                .flatten();
+            ImportedTask {
+                finished,
+                priority: match task.priority.as_deref() {
+                    Some("H") => Some('A'),
+                    Some("M") => Some('B'),
+                    Some("L") => Some('C'),
+                    _ => None,
+                },
+                created: date(&task.entry),
+                subject: task.description.clone(),
+                categories,
+                due: date(&task.due),
+                threshold: date(&task.wait).or(date(&task.scheduled)),
+            }
+            .line()
+        })
+        .collect())
+}
+
+/// Gets the date of a Taskwarrior timestamp, e.g. `20230501T120000Z`, in the time zone.
+/// The timestamps are in UTC, so the date of a moment near midnight differs by the zone.
+fn taskwarrior_date<Tz: TimeZone>(stamp: &str, zone: &Tz) -> Option<NaiveDate> {
+    let time = NaiveDateTime::parse_from_str(stamp, "%Y%m%dT%H%M%SZ").ok()?;
+    Some(zone.from_utc_datetime(&time).date_naive())
+}
+
+/// Converts a `- [ ]` or `- [x]` item of a checklist. The priority and the due date
+/// written by the Markdown export, e.g. `- [ ] (A) call mom (due 2023-05-01)`, are kept.
+fn from_markdown(line: &str, today: NaiveDate) -> Option<String> {
+    let item = line
+        .trim_start()
+        .strip_prefix(['-', '*', '+'])?
+        .strip_prefix(' ')?
+        .trim_start();
+    let (finished, text) = match item.get(..3)? {
+        "[ ]" => (None, &item[3..]),
+        "[x]" | "[X]" => (Some(today), &item[3..]),
+        _ => return None,
+    };
+    let mut text = text.trim().replace("\\[", "[");
+    let mut task = ImportedTask {
+        finished,
+        ..ImportedTask::default()
+    };
+    let mut chars = text.chars();
+    if let (Some('('), Some(priority @ 'A'..='Z'), Some(')'), Some(' ')) =
+        (chars.next(), chars.next(), chars.next(), chars.next())
+    {
+        task.priority = Some(priority);
+        text.replace_range(..4, "");
+    }
+    if let Some(start) = text.rfind(" (due ").filter(|_| text.ends_with(')')) {
+        if let Ok(due) = NaiveDate::from_str(&text[start + 6..text.len() - 1]) {
+            task.due = Some(due);
+            text.truncate(start);
+        }
+    }
+    task.subject = text;
+    (!task.subject.is_empty()).then(|| task.line())
+}
+
+impl ToDo {
+    /// Appends the imported tasks to the todo list as they are, completed tasks
+    /// go to the done list.
+    ///
+    /// # Arguments
+    ///
+    /// * `tasks` - The todo.txt lines of the tasks, see [`ImportFormat::parse`].
+    pub fn import(&mut self, tasks: &[String]) -> Result<(), todo_txt::Error> {
+        self.apply(Action::Batch {
+            actions: tasks
+                .iter()
+                .map(|task| Action::CopyTask { task: task.clone() })
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, Utc};
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2023, 5, 20).unwrap()
+    }
+
+    #[test]
+    fn format() -> ToDoRes<()> {
+        assert_eq!(
+            ImportFormat::from_path(Path::new("tasks.json"))?,
+            ImportFormat::Taskwarrior
+        );
+        assert_eq!(
+            ImportFormat::from_path(Path::new("list.MD"))?,
+            ImportFormat::Markdown
+        );
+        assert!(ImportFormat::from_path(Path::new("tasks.ics")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn taskwarrior() -> ToDoRes<()> {
+        let export = r#"[
+{"id":1,"description":"Water the plants","entry":"20230501T081500Z","modified":"20230501T081500Z","priority":"H","project":"home.garden","status":"pending","tags":["weekend","outside"],"due":"20230510T220000Z","urgency":12.5},
+{"id":0,"description":"Pay \"rent\"\nonline \ud83c\udfe0","end":"20230503T100000Z","entry":"20230502T100000Z","status":"completed","priority":"L"},
+{"id":0,"description":"Old idea","status":"deleted"},
+{"id":2,"description":"Call mom","status":"waiting","wait":"20230601T000000Z","annotations":[{"entry":"20230501T081500Z","description":"about the trip"}]}
+]"#;
+        assert_eq!(
+            from_taskwarrior(export, &Utc)?,
+            [
+                "(A) 2023-05-01 Water the plants +home.garden @weekend @outside due:2023-05-10",
+                "x 2023-05-03 2023-05-02 Pay \"rent\" online \u{1F3E0} pri:C",
+                "Call mom t:2023-06-01",
+            ]
+        );
+
+        let lines = "{\"description\":\"one\",\"status\":\"pending\"}\n\
+                     {\"description\":\"two\",\"status\":\"pending\",\"priority\":\"M\"}\n";
+        assert_eq!(
+            ImportFormat::Taskwarrior.parse(lines, today())?,
+            ["one", "(B) two"]
+        );
+        assert_eq!(ImportFormat::Taskwarrior.parse("[]", today())?, [""; 0]);
+        assert!(ImportFormat::Taskwarrior
+            .parse("[{\"description\": \"one\"", today())
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn taskwarrior_midnight() {
+        let date = |day| NaiveDate::from_ymd_opt(2023, 5, day);
+        let west = FixedOffset::west_opt(5 * 3600).unwrap();
+        let east = FixedOffset::east_opt(2 * 3600).unwrap();
+        assert_eq!(taskwarrior_date("20230510T220000Z", &Utc), date(10));
+        assert_eq!(taskwarrior_date("20230510T220000Z", &east), date(11));
+        assert_eq!(taskwarrior_date("20230511T030000Z", &west), date(10));
+        assert_eq!(taskwarrior_date("20230511T030000Z", &east), date(11));
+        assert_eq!(taskwarrior_date("20230511", &Utc), None);
+    }
+
+    #[test]
+    fn markdown() -> ToDoRes<()> {
+        let checklist = "# Shopping\n\
+                         - [ ] buy milk +home\n\
+                         * [x] post letter\n\
+                         \x20 - [ ] (A) call mom (due 2023-05-01)\n\
+                         - [ ] \\[draft] report (due soon)\n\
+                         - plain item\n\
+                         - [ ]\n";
+        assert_eq!(
+            ImportFormat::Markdown.parse(checklist, today())?,
+            [
+                "buy milk +home",
+                "x 2023-05-20 post letter",
+                "(A) call mom due:2023-05-01",
+                "[draft] report (due soon)",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn import() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        todo.new_task("existing task")?;
+        todo.import(&[
+            String::from("(A) imported task"),
+            String::from("x 2023-05-20 imported done task"),
+        ])?;
+        assert_eq!(todo.pending.len(), 2);
+        assert_eq!(todo.pending[1].subject, "imported task");
+        assert_eq!(todo.done.len(), 1);
+        Ok(())
+    }
+}
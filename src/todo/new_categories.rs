@@ -0,0 +1,148 @@
+use super::{ToDo, ToDoCategory};
+use std::borrow::Cow;
+use std::str::FromStr;
+use todo_txt::Task;
+
+/// The largest number of suggestions offered for a new category.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// A project or context of a task that is not used by any task of the todo list yet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NewCategory {
+    /// The category with its prefix, e.g. `+wrok`.
+    pub name: String,
+    /// Existing categories with names close to the new one, the closest first.
+    pub suggestions: Vec<String>,
+}
+
+impl ToDo {
+    /// Finds projects and contexts of the task that are not used by any task yet,
+    /// so typos do not create categories by mistake.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The task string entered by the user.
+    ///
+    /// # Returns
+    ///
+    /// The new projects followed by the new contexts, empty if the task cannot be parsed.
+    pub fn new_categories(&self, task: &str) -> Vec<NewCategory> {
+        let task = match Task::from_str(task) {
+            Ok(task) => task,
+            Err(_) => return Vec::new(),
+        };
+        [(ToDoCategory::Projects, '+'), (ToDoCategory::Contexts, '@')]
+            .into_iter()
+            .flat_map(|(category, prefix)| {
+                let known: Vec<&String> = self
+                    .pending
+                    .iter()
+                    .chain(self.done.iter())
+                    // Projects and contexts are borrowed from the tasks.
+                    .flat_map(|task| match category.get_data(task) {
+                        Cow::Borrowed(names) => names,
+                        Cow::Owned(_) => &[],
+                    })
+                    .collect();
+                category
+                    .get_data(&task)
+                    .iter()
+                    .filter(|name| !known.contains(name))
+                    .map(|name| NewCategory {
+                        name: format!("{prefix}{name}"),
+                        suggestions: suggestions(name, &known)
+                            .into_iter()
+                            .map(|suggestion| format!("{prefix}{suggestion}"))
+                            .collect(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+/// Finds names close to the new name, at most a third of the characters differ.
+fn suggestions<'a>(name: &str, known: &[&'a String]) -> Vec<&'a String> {
+    let limit = (name.chars().count() / 3).max(1);
+    let mut close: Vec<(usize, &String)> = known
+        .iter()
+        .map(|known| (distance(name, known), *known))
+        .filter(|(distance, _)| *distance <= limit)
+        .collect();
+    close.sort();
+    close.dedup();
+    close
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, known)| known)
+        .collect()
+}
+
+/// Computes the edit distance of two strings where swapping two adjacent
+/// characters is a single edit, the case is ignored.
+fn distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+    // d[i][j] is the distance of the first i characters of a and the first j characters of b.
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_categories() {
+        let mut todo = ToDo::default();
+        todo.new_task("write the report +work @office").unwrap();
+        todo.new_task("buy milk +home @shop").unwrap();
+        todo.new_task("x call the boss +word").unwrap();
+
+        assert!(todo.new_categories("fix bugs +work @office").is_empty());
+        assert_eq!(
+            todo.new_categories("fix bugs +wrok @garden @ofice"),
+            vec![
+                NewCategory {
+                    name: String::from("+wrok"),
+                    suggestions: vec![String::from("+work")],
+                },
+                NewCategory {
+                    name: String::from("@garden"),
+                    suggestions: vec![],
+                },
+                NewCategory {
+                    name: String::from("@ofice"),
+                    suggestions: vec![String::from("@office")],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn edit_distance() {
+        assert_eq!(distance("work", "work"), 0);
+        assert_eq!(distance("wrok", "work"), 1);
+        assert_eq!(distance("wrok", "word"), 2);
+        assert_eq!(distance("Work", "work"), 0);
+        assert_eq!(distance("ofice", "office"), 1);
+        assert_eq!(distance("", "abc"), 3);
+    }
+}
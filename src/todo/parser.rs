@@ -3,13 +3,65 @@ mod line_block;
 mod parts;
 
 use super::{ToDo, ToDoData};
-use crate::{config::Styles, ToDoError, ToDoRes};
+use crate::{style::Styles, ToDoError, ToDoRes};
 use line::Line;
 use line_block::LineBlock;
 use parts::Parts;
-use std::iter::Peekable;
+use std::{iter::Peekable, str::Chars};
+use todo_txt::Task;
 use tui::style::Style;
 
+/// Iterator over template characters which keeps track of the position
+/// of the last returned character.
+struct TemplateIter<'a> {
+    iter: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> TemplateIter<'a> {
+    fn new(template: &'a str) -> Self {
+        Self {
+            iter: template.chars().peekable(),
+            line: 1,
+            column: 0,
+        }
+    }
+
+    fn peek(&mut self) -> Option<&char> {
+        self.iter.peek()
+    }
+
+    /// Gets the line and column of the last returned character.
+    fn position(&self) -> (usize, usize) {
+        (self.line, self.column)
+    }
+}
+
+/// Wraps the error with the position in the template where it occurred.
+fn error_at((line, column): (usize, usize), err: ToDoError) -> ToDoError {
+    ToDoError::ParseTemplate {
+        line,
+        column,
+        err: Box::new(err),
+    }
+}
+
+impl<'a> Iterator for TemplateIter<'a> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.iter.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 0;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+}
+
 pub struct Parser {
     lines: Vec<Line>,
     styles: Styles,
@@ -22,7 +74,9 @@ impl Parser {
         Ok(Parser { lines, styles })
     }
 
-    fn read_block(iter: &mut Peekable<std::str::Chars<'_>>, delimiter: char) -> ToDoRes<String> {
+    /// Reads the block until the delimiter. Escape sequences and raw text are
+    /// kept untouched so they can be processed together with variables.
+    fn read_block(iter: &mut TemplateIter, delimiter: char) -> ToDoRes<String> {
         let mut read = String::default();
         loop {
             let c = match iter.next() {
@@ -30,10 +84,18 @@ impl Parser {
                 None => return Err(ToDoError::ParseBlockNotClosed(read.to_string())),
             };
             match c {
-                '\\' => read.push(match iter.next() {
-                    Some(ch) => ch,
-                    None => return Err(ToDoError::ParseBlockEscapeOnEnd(read + "\\")),
-                }),
+                '\\' => {
+                    read.push(c);
+                    read.push(match iter.next() {
+                        Some(ch) => ch,
+                        None => return Err(ToDoError::ParseBlockEscapeOnEnd(read)),
+                    })
+                }
+                '`' => {
+                    read.push(c);
+                    read += &Parser::read_raw(iter)?;
+                    read.push(c);
+                }
                 c if c == delimiter => break,
                 _ => read.push(c),
             };
@@ -41,30 +103,115 @@ impl Parser {
         Ok(read)
     }
 
+    /// Reads raw text until the closing backtick.
+    fn read_raw(iter: &mut TemplateIter) -> ToDoRes<String> {
+        let mut read = String::default();
+        loop {
+            match iter.next() {
+                Some('`') => return Ok(read),
+                Some(c) => read.push(c),
+                None => return Err(ToDoError::ParseBlockNotClosed(read)),
+            }
+        }
+    }
+
+    /// Splits the block content on `\n` escape sequences outside of raw text.
+    fn split_lines(block: &str) -> Vec<String> {
+        let mut ret = vec![String::default()];
+        let mut iter = block.chars();
+        let mut raw = false;
+        while let Some(c) = iter.next() {
+            let act = ret.last_mut().unwrap();
+            match c {
+                '`' => {
+                    raw = !raw;
+                    act.push(c);
+                }
+                '\\' if !raw => match iter.next() {
+                    Some('n') => ret.push(String::default()),
+                    Some(ch) => {
+                        act.push(c);
+                        act.push(ch);
+                    }
+                    None => act.push(c),
+                },
+                _ => act.push(c),
+            }
+        }
+        ret
+    }
+
+    /// Parses the template into lines of styled blocks.
+    ///
+    /// Errors are reported together with the line and column of the block
+    /// in the template that caused them.
     fn parse(template: &str, styles: &Styles) -> ToDoRes<Vec<Line>> {
         let mut ret = Vec::new();
         let mut line = Line::default();
         let mut act = String::default();
-        let mut iter = template.chars().peekable();
+        let mut act_position = (1, 1);
+        let mut iter = TemplateIter::new(template);
         while let Some(c) = iter.next() {
+            let position = iter.position();
+            if act.is_empty() {
+                act_position = position;
+            }
             match c {
                 '[' => {
-                    let block = Parser::read_block(&mut iter, ']')?;
-                    line.add_span_styled(&act, None, styles)?;
+                    line.add_span_styled(&act, None, styles)
+                        .map_err(|e| error_at(act_position, e))?;
                     act = String::default();
+                    let block =
+                        Parser::read_block(&mut iter, ']').map_err(|e| error_at(position, e))?;
                     let mut style = None;
                     if Some(&'(') == iter.peek() {
                         iter.next();
-                        style = Some(Parser::read_block(&mut iter, ')')?);
+                        style = Some(
+                            Parser::read_block(&mut iter, ')')
+                                .map_err(|e| error_at(position, e))?,
+                        );
+                    }
+                    // A block is rendered only when all its variables have a value,
+                    // `?` after the block marks it as conditional explicitly.
+                    if Some(&'?') == iter.peek() {
+                        iter.next();
                     }
-                    line.add_span_styled(&block, style, styles)?;
+                    for (i, block) in Parser::split_lines(&block).iter().enumerate() {
+                        if i > 0 {
+                            ret.push(line);
+                            line = Line::default();
+                        }
+                        line.add_span_styled(block, style.clone(), styles)
+                            .map_err(|e| error_at(position, e))?;
+                    }
+                }
+                '`' => {
+                    act.push(c);
+                    act += &Parser::read_raw(&mut iter).map_err(|e| error_at(position, e))?;
+                    act.push(c);
                 }
-                '\\' => act.push(match iter.next() {
-                    Some(ch) => ch,
-                    None => return Err(ToDoError::ParseBlockEscapeOnEnd(act + "\\")),
-                }),
+                '\\' => match iter.next() {
+                    Some('n') => {
+                        line.add_span_styled(&act, None, styles)
+                            .map_err(|e| error_at(act_position, e))?;
+                        act = String::default();
+                        ret.push(line);
+                        line = Line::default();
+                    }
+                    Some(ch) => {
+                        act.push(c);
+                        act.push(ch);
+                    }
+                    None => {
+                        return Err(error_at(
+                            position,
+                            ToDoError::ParseBlockEscapeOnEnd(act + "\\"),
+                        ))
+                    }
+                },
                 '\n' => {
-                    line.add_span_styled(&act, None, styles)?;
+                    line.add_span_styled(&act, None, styles)
+                        .map_err(|e| error_at(act_position, e))?;
                     act = String::default();
                     ret.push(line);
                     line = Line::default();
@@ -72,55 +219,86 @@ impl Parser {
                 _ => act.push(c),
             }
         }
-        line.add_span_styled(&act, None, styles)?;
+        line.add_span_styled(&act, None, styles)
+            .map_err(|e| error_at(act_position, e))?;
         ret.push(line);
         Ok(ret)
     }
 
+    /// Fills the template with data of the active task.
+    /// Returns empty vector if there is no active task.
     pub fn fill(&self, todo: &ToDo) -> Vec<Vec<(String, Style)>> {
+        match todo.get_active() {
+            Some(task) => self.fill_task(task, todo),
+            None => Vec::new(),
+        }
+    }
+
+    /// Fills the template with data of the given task.
+    pub fn fill_task(&self, task: &Task, todo: &ToDo) -> Vec<Vec<(String, Style)>> {
         self.lines
             .iter()
-            .filter_map(|line| line.fill(todo, &self.styles))
+            .filter_map(|line| line.fill(task, todo, &self.styles))
             .collect()
     }
+
+    /// Fills the template with data of the given task as plain text without styles.
+    /// The lines of the template are joined with a space.
+    pub fn fill_text(&self, task: &Task, todo: &ToDo) -> String {
+        self.fill_task(task, todo)
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|(text, _)| text.as_str())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::Line;
     use super::*;
+    use proptest::prelude::*;
     use tui::style::Color;
     use tui::style::Modifier;
 
     #[test]
     fn read_block() -> ToDoRes<()> {
-        let mut iter = "block to parse]".chars().peekable();
+        let mut iter = TemplateIter::new("block to parse]");
         assert_eq!(&Parser::read_block(&mut iter, ']')?, "block to parse");
         assert_eq!(&iter.collect::<String>(), "");
 
-        let mut iter = "Some style block)".chars().peekable();
+        let mut iter = TemplateIter::new("Some style block)");
         assert_eq!(&Parser::read_block(&mut iter, ')')?, "Some style block");
         assert_eq!(&iter.collect::<String>(), "");
 
-        let mut iter = "block to parse] some other text".chars().peekable();
+        let mut iter = TemplateIter::new("block to parse] some other text");
         assert_eq!(&Parser::read_block(&mut iter, ']')?, "block to parse");
         assert_eq!(&iter.collect::<String>(), " some other text");
 
-        let mut iter = "block to parse \\] with some \\\\ escapes]"
-            .chars()
-            .peekable();
+        let mut iter = TemplateIter::new("block to parse \\] with some \\\\ escapes]");
         assert_eq!(
             &Parser::read_block(&mut iter, ']')?,
-            "block to parse ] with some \\ escapes"
+            "block to parse \\] with some \\\\ escapes"
         );
         assert_eq!(&iter.collect::<String>(), "");
 
+        let mut iter = TemplateIter::new("raw `block ] with` delimiter] text");
+        assert_eq!(
+            &Parser::read_block(&mut iter, ']')?,
+            "raw `block ] with` delimiter"
+        );
+        assert_eq!(&iter.collect::<String>(), " text");
+
         Ok(())
     }
 
     #[test]
     fn read_block_error() {
-        let mut iter = "not closed block".chars().peekable();
+        let mut iter = TemplateIter::new("not closed block");
         assert_eq!(
             Parser::read_block(&mut iter, ']'),
             Err(ToDoError::ParseBlockNotClosed(
@@ -128,7 +306,7 @@ mod tests {
             ))
         );
 
-        let mut iter = "not closed block \\".chars().peekable();
+        let mut iter = TemplateIter::new("not closed block \\");
         assert_eq!(
             Parser::read_block(&mut iter, ']'),
             Err(ToDoError::ParseBlockEscapeOnEnd(
@@ -219,6 +397,18 @@ mod tests {
                 }
             ])
         );
+        assert_eq!(
+            Parser::parse("[some text](#ff8800 bg:236 strikethrough)", &styles)?[0],
+            Line(vec![LineBlock {
+                parts: vec![Parts::Text("some text".to_string())],
+                style: styles.get_style_from_style(
+                    Style::default()
+                        .fg(Color::Rgb(255, 136, 0))
+                        .bg(Color::Indexed(236))
+                        .add_modifier(Modifier::CROSSED_OUT)
+                ),
+            },])
+        );
         assert_eq!(
             Parser::parse("[some text](priority:A)", &Styles::default())?[0],
             Line(vec![LineBlock {
@@ -246,13 +436,121 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn parse_escapes() -> ToDoRes<()> {
+        let styles = Styles::default();
+        let text = |text: &str| {
+            Line(vec![LineBlock {
+                parts: vec![Parts::Text(text.to_string())],
+                style: styles.get_style_default(),
+            }])
+        };
+        assert_eq!(
+            Parser::parse("\\$ \\[ \\] \\( \\) \\\\", &styles)?[0],
+            text("$ [ ] ( ) \\")
+        );
+        assert_eq!(
+            Parser::parse("tab\\tseparated", &styles)?[0],
+            text("tab\tseparated")
+        );
+        assert_eq!(
+            Parser::parse("[escaped \\] in block \\$](red)", &styles)?[0],
+            Line(vec![LineBlock {
+                parts: vec![Parts::Text("escaped ] in block $".to_string())],
+                style: styles.get_style_from_style(Style::default().fg(Color::Red)),
+            }])
+        );
+        assert_eq!(
+            Parser::parse("raw `$subject [text] \\n`", &styles)?[0],
+            text("raw $subject [text] \\n")
+        );
+
+        let parse = Parser::parse("first\\nsecond [block\\nsplit](red)", &styles)?;
+        assert_eq!(parse.len(), 3);
+        assert_eq!(parse[0], text("first"));
+        assert_eq!(
+            parse[1],
+            Line(vec![
+                LineBlock {
+                    parts: vec![Parts::Text("second ".to_string())],
+                    style: styles.get_style_default(),
+                },
+                LineBlock {
+                    parts: vec![Parts::Text("block".to_string())],
+                    style: styles.get_style_from_style(Style::default().fg(Color::Red)),
+                }
+            ])
+        );
+        assert_eq!(
+            parse[2],
+            Line(vec![LineBlock {
+                parts: vec![Parts::Text("split".to_string())],
+                style: styles.get_style_from_style(Style::default().fg(Color::Red)),
+            }])
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn parse_error() {
         assert_eq!(
             Parser::parse("escape on end of line \\", &Styles::default()),
-            Err(ToDoError::ParseBlockEscapeOnEnd(
-                "escape on end of line \\".to_string()
-            ))
+            Err(ToDoError::ParseTemplate {
+                line: 1,
+                column: 23,
+                err: Box::new(ToDoError::ParseBlockEscapeOnEnd(
+                    "escape on end of line \\".to_string()
+                )),
+            })
+        );
+        assert_eq!(
+            Parser::parse("first line\nsome [not closed", &Styles::default()),
+            Err(ToDoError::ParseTemplate {
+                line: 2,
+                column: 6,
+                err: Box::new(ToDoError::ParseBlockNotClosed("not closed".to_string())),
+            })
+        );
+        assert_eq!(
+            Parser::parse("text [block](red) [style](not closed", &Styles::default()),
+            Err(ToDoError::ParseTemplate {
+                line: 1,
+                column: 19,
+                err: Box::new(ToDoError::ParseBlockNotClosed("not closed".to_string())),
+            })
+        );
+        assert_eq!(
+            Parser::parse("[block](red) text ${foo bar}", &Styles::default()),
+            Err(ToDoError::ParseTemplate {
+                line: 1,
+                column: 13,
+                err: Box::new(ToDoError::ParseUnknownVariable("foo bar".to_string())),
+            })
+        );
+        assert_eq!(
+            Parser::parse("[block](red bald)", &Styles::default()),
+            Err(ToDoError::ParseTemplate {
+                line: 1,
+                column: 1,
+                err: Box::new(ToDoError::ParseTextStyle("bald".to_string())),
+            })
+        );
+        assert_eq!(
+            Parser::parse("text [block](bg:#12345)", &Styles::default()),
+            Err(ToDoError::ParseTemplate {
+                line: 1,
+                column: 6,
+                err: Box::new(ToDoError::ParseHexColor("#12345".to_string())),
+            })
+        );
+        assert_eq!(
+            Parser::parse("[block](priority:AB)", &Styles::default()),
+            Err(ToDoError::ParseTemplate {
+                line: 1,
+                column: 1,
+                err: Box::new(ToDoError::ParseTextStyle("priority:AB".to_string())),
+            })
         );
     }
 
@@ -289,4 +587,100 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn fill_conditional() -> ToDoRes<()> {
+        let parser = Parser::new(
+            "[$priority ](red)?$subject[ due: $due](red)? created: $created\\?",
+            Styles::default(),
+        )?;
+        let mut todo = ToDo::default();
+        todo.new_task("(A) 2023-11-01 task due:2023-11-12").unwrap();
+        todo.new_task("2023-11-01 task without due date").unwrap();
+
+        assert_eq!(
+            parser.fill_task(&todo.pending[0], &todo),
+            vec![vec![
+                (String::from("A "), Style::default().fg(Color::Red)),
+                (String::from("task"), Style::default()),
+                (
+                    String::from(" due: 2023-11-12"),
+                    Style::default().fg(Color::Red)
+                ),
+                (String::from(" created: 2023-11-01?"), Style::default()),
+            ]]
+        );
+        assert_eq!(
+            parser.fill_task(&todo.pending[1], &todo),
+            vec![vec![
+                (String::from("task without due date"), Style::default()),
+                (String::from(" created: 2023-11-01?"), Style::default()),
+            ]]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn fill_task() -> ToDoRes<()> {
+        let parser = Parser::new("[$subject](red)[ due: $due_date]", Styles::default())?;
+        let mut todo = ToDo::default();
+        todo.new_task("task due:2023-11-12").unwrap();
+        todo.new_task("task without due date").unwrap();
+
+        assert_eq!(
+            parser.fill_task(&todo.pending[0], &todo),
+            vec![vec![
+                (String::from("task"), Style::default().fg(Color::Red)),
+                (String::from(" due: 2023-11-12"), Style::default()),
+            ]]
+        );
+        assert_eq!(
+            parser.fill_task(&todo.pending[1], &todo),
+            vec![vec![(
+                String::from("task without due date"),
+                Style::default().fg(Color::Red)
+            )]]
+        );
+
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn parse_arbitrary_template(template in "\\PC*") {
+            // Any template is either parsed or rejected with an error, never panics.
+            let _ = Parser::parse(&template, &Styles::default());
+        }
+
+        #[test]
+        fn parse_arbitrary_template_syntax(template in "[a-z $\\\\\\[\\](){}`\n]*") {
+            let _ = Parser::parse(&template, &Styles::default());
+        }
+
+        #[test]
+        fn parse_plain_text(text in "[a-zA-Z0-9 .,:;!?]+") {
+            let parser = Parser::new(&text, Styles::default()).unwrap();
+            let mut todo = ToDo::default();
+            todo.new_task("task").unwrap();
+            todo.set_active(ToDoData::Pending, 0);
+            prop_assert_eq!(parser.fill(&todo), vec![vec![(text, Style::default())]]);
+        }
+
+        #[test]
+        fn parse_escaped_text(text in "[a-zA-Z0-9 $\\[\\]()\\\\]+") {
+            let mut escaped = String::new();
+            for c in text.chars() {
+                if "$[]()\\".contains(c) {
+                    escaped.push('\\');
+                }
+                escaped.push(c);
+            }
+            let parser = Parser::new(&escaped, Styles::default()).unwrap();
+            let mut todo = ToDo::default();
+            todo.new_task("task").unwrap();
+            todo.set_active(ToDoData::Pending, 0);
+            prop_assert_eq!(parser.fill(&todo), vec![vec![(text, Style::default())]]);
+        }
+    }
 }
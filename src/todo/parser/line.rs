@@ -1,7 +1,8 @@
 use super::LineBlock;
 
 use super::ToDo;
-use crate::{config::Styles, error::ToDoRes};
+use crate::{error::ToDoRes, style::Styles};
+use todo_txt::Task;
 use tui::style::Style;
 
 #[derive(Default, Debug)]
@@ -22,14 +23,14 @@ impl Line {
         Ok(())
     }
 
-    pub fn fill(&self, todo: &ToDo, styles: &Styles) -> Option<Vec<(String, Style)>> {
+    pub fn fill(&self, task: &Task, todo: &ToDo, styles: &Styles) -> Option<Vec<(String, Style)>> {
         if self.0.is_empty() {
             return None;
         }
         let ret: Vec<(String, Style)> = self
             .0
             .iter()
-            .filter_map(|block| block.fill(todo, styles))
+            .filter_map(|block| block.fill(task, todo, styles))
             .collect();
         if ret.is_empty() {
             None
@@ -53,13 +54,14 @@ mod tests {
         todo.new_task("Some task 2").unwrap();
         todo.new_task("Some task 3").unwrap();
         todo.set_active(ToDoData::Pending, 0);
+        let task = todo.get_active().unwrap();
 
         assert_eq!(
             Line(vec![
                 LineBlock::try_from_styled("some text", None, &styles).unwrap(),
                 LineBlock::try_from_styled("not empty $done", None, &styles).unwrap(),
             ])
-            .fill(&todo, &styles),
+            .fill(task, &todo, &styles),
             Some(vec![
                 (String::from("some text"), Style::default()),
                 (String::from("not empty 0"), Style::default())
@@ -71,7 +73,7 @@ mod tests {
                 LineBlock::try_from_styled("some text", None, &styles).unwrap(),
                 LineBlock::try_from_styled("empty $priority", None, &styles).unwrap(),
             ])
-            .fill(&todo, &styles),
+            .fill(task, &todo, &styles),
             Some(vec![(String::from("some text"), Style::default())])
         );
     }
@@ -1,8 +1,17 @@
 use super::Parts;
 use super::ToDo;
-use crate::config::{Styles, StylesValue};
+use crate::style::{Styles, StylesValue};
+use crate::todo::countdown::countdown;
 use crate::{ToDoError, ToDoRes};
-use tui::style::Style;
+use chrono::Duration;
+use todo_txt::Task;
+use tui::style::{Color, Modifier, Style};
+
+/// Tags that can be used in templates as variables, e.g. `$link`.
+const KNOWN_TAGS: [&str; 12] = [
+    "link", "rec", "t", "effort", "spent", "id", "remind", "notify", "routine", "review",
+    "assignee", "by",
+];
 
 #[derive(Debug)]
 #[cfg_attr(test, derive(PartialEq))]
@@ -12,6 +21,38 @@ pub struct LineBlock {
 }
 
 impl LineBlock {
+    /// Creates a variable part from its name.
+    /// The name may consist only of alphanumeric characters, `_` and `-`.
+    /// Besides the variables, only the tags in `KNOWN_TAGS` can be used
+    /// by their name, other tags are written as `$tag:name`.
+    fn variable(name: String, block: &str) -> ToDoRes<Parts> {
+        if name.is_empty() {
+            return Err(ToDoError::EmptyVariableName(block.to_string()));
+        }
+        let key = name.strip_prefix("tag:").unwrap_or(&name);
+        if key.is_empty()
+            || !key
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        {
+            Err(ToDoError::ParseUnknownVariable(name))
+        } else if key.len() != name.len() {
+            Ok(Parts::Special(key.to_string()))
+        } else {
+            match Parts::from(name) {
+                Parts::Special(name) if !KNOWN_TAGS.contains(&name.as_str()) => {
+                    Err(ToDoError::ParseUnknownVariable(name))
+                }
+                part => Ok(part),
+            }
+        }
+    }
+
+    /// Splits the block into text and variable parts.
+    ///
+    /// Escape sequences `\n` and `\t` produce a new line and a tab, any other
+    /// escaped character is taken literally. Text between backticks is raw and
+    /// is not searched for variables or escapes.
     fn parse_variables(block: &str) -> ToDoRes<Vec<Parts>> {
         let mut ret = Vec::new();
         let mut iter = block.chars();
@@ -19,8 +60,16 @@ impl LineBlock {
         let mut variable_block = false;
         let mut read = String::new();
         while let Some(c) = iter.next() {
+            if read_variable
+                && !variable_block
+                && (c.is_whitespace() || matches!(c, '$' | '\\' | '`'))
+            {
+                read_variable = false;
+                ret.push(Self::variable(read, block)?);
+                read = String::new();
+            }
             match c {
-                '$' => {
+                '$' if !read_variable => {
                     read_variable = true;
                     ret.push(Parts::Text(read));
                     read = String::new();
@@ -36,18 +85,22 @@ impl LineBlock {
                 '}' if read_variable && variable_block => {
                     variable_block = false;
                     read_variable = false;
-                    ret.push(Parts::from(read));
+                    ret.push(Self::variable(read, block)?);
                     read = String::new();
                 }
-                c if read_variable && !variable_block && c.is_whitespace() => {
-                    read_variable = false;
-                    ret.push(Parts::from(read));
-                    read = String::from(c);
-                }
                 '\\' => read.push(match iter.next() {
+                    Some('n') => '\n',
+                    Some('t') => '\t',
                     Some(ch) => ch,
                     None => return Err(ToDoError::ParseBlockEscapeOnEnd(block.to_string())),
                 }),
+                '`' if !read_variable => loop {
+                    match iter.next() {
+                        Some('`') => break,
+                        Some(ch) => read.push(ch),
+                        None => return Err(ToDoError::ParseBlockNotClosed(block.to_string())),
+                    }
+                },
                 _ => read.push(c),
             };
         }
@@ -55,7 +108,7 @@ impl LineBlock {
             if variable_block {
                 return Err(ToDoError::ParseVariableNotClosed(read));
             }
-            Parts::from(read)
+            Self::variable(read, block)?
         } else {
             Parts::Text(read)
         });
@@ -63,18 +116,24 @@ impl LineBlock {
         Ok(ret)
     }
 
-    pub fn fill(&self, todo: &ToDo, styles: &Styles) -> Option<(String, Style)> {
+    pub fn fill(&self, task: &Task, todo: &ToDo, styles: &Styles) -> Option<(String, Style)> {
         let mut ret = String::new();
         for part in &self.parts {
-            ret += &part.fill(todo)?;
+            ret += &part.fill_task(task, todo)?;
         }
-        Some((
-            ret,
-            match todo.get_active() {
-                Some(task) => self.style.get_style(task, styles),
-                None => Style::default(),
-            },
-        ))
+        let style = self.style.get_style(task, styles);
+        // The countdown pulses in red in the final hour before the deadline.
+        let final_hour = self.parts.contains(&Parts::Countdown)
+            && countdown(task, todo.clock().now()).is_some_and(|left| left < Duration::hours(1));
+        let style = match final_hour {
+            true => style.patch(
+                Style::default()
+                    .fg(Color::Red)
+                    .add_modifier(Modifier::SLOW_BLINK),
+            ),
+            false => style,
+        };
+        Some((ret, style))
     }
 
     pub fn try_from_styled(value: &str, style: Option<String>, styles: &Styles) -> ToDoRes<Self> {
@@ -110,10 +169,14 @@ mod tests {
         assert_eq!(parts[1], Parts::Pending);
         assert_eq!(parts[2], Parts::Text("x pending tasks".into()));
 
-        let parts = LineBlock::parse_variables("special task text $some-special")?;
+        let parts = LineBlock::parse_variables("special task text $tag:some-special")?;
         assert_eq!(parts[0], Parts::Text("special task text ".into()));
         assert_eq!(parts[1], Parts::Special("some-special".into()));
 
+        let parts = LineBlock::parse_variables("$link ${tag:due}")?;
+        assert_eq!(parts[1], Parts::Special("link".into()));
+        assert_eq!(parts[3], Parts::Special("due".into()));
+
         let parts = LineBlock::parse_variables("special \\$ character")?;
         assert_eq!(parts[0], Parts::Text("special $ character".into()));
 
@@ -153,5 +216,27 @@ mod tests {
             LineBlock::parse_variables("variable block not closed ${variable "),
             Err(ToDoError::ParseVariableNotClosed(String::from("variable ")))
         );
+
+        assert_eq!(
+            LineBlock::parse_variables("empty variable block ${}"),
+            Err(ToDoError::EmptyVariableName(String::from(
+                "empty variable block ${}"
+            )))
+        );
+
+        assert_eq!(
+            LineBlock::parse_variables("unknown variable $due_date, text"),
+            Err(ToDoError::ParseUnknownVariable(String::from("due_date,")))
+        );
+
+        assert_eq!(
+            LineBlock::parse_variables("misspelled variable $subjcet"),
+            Err(ToDoError::ParseUnknownVariable(String::from("subjcet")))
+        );
+
+        assert_eq!(
+            LineBlock::parse_variables("empty tag name $tag:"),
+            Err(ToDoError::ParseUnknownVariable(String::from("tag:")))
+        );
     }
 }
@@ -1,5 +1,7 @@
 use super::ToDo;
 use super::ToDoData;
+use crate::todo::countdown::{countdown, format_countdown};
+use todo_txt::Task;
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum Parts {
@@ -8,11 +10,14 @@ pub enum Parts {
     Done,
     Subject,
     Priority,
+    PriorityLabel,
     CreateDate,
     FinishDate,
     Finished,
     TresholdDate,
     DueDate,
+    Countdown,
+    Urgency,
     Contexts,
     Projects,
     Hashtags,
@@ -20,7 +25,8 @@ pub enum Parts {
 }
 
 impl Parts {
-    pub fn fill(&self, todo: &ToDo) -> Option<String> {
+    /// Fills the part with data of the given task.
+    pub fn fill_task(&self, task: &Task, todo: &ToDo) -> Option<String> {
         use Parts::*;
         let process_vec = |vec: &[String]| {
             if vec.is_empty() {
@@ -29,35 +35,37 @@ impl Parts {
                 Some(vec.join(", "))
             }
         };
-        match todo.get_active() {
-            Some(task) => match self {
-                Text(text) => Some(text.to_string()),
-                Pending => Some(todo.len(ToDoData::Pending).to_string()),
-                Done => Some(todo.len(ToDoData::Done).to_string()),
-                Subject => Some(task.subject.clone()),
-                Priority => {
-                    if task.priority.is_lowest() {
-                        None
-                    } else {
-                        Some(task.priority.to_string())
-                    }
+        match self {
+            Text(text) => Some(text.to_string()),
+            Pending => Some(todo.len(ToDoData::Pending).to_string()),
+            Done => Some(todo.len(ToDoData::Done).to_string()),
+            Subject => Some(task.subject.clone()),
+            Priority => {
+                if task.priority.is_lowest() {
+                    None
+                } else {
+                    Some(task.priority.to_string())
                 }
-                CreateDate => task.create_date.map(|d| d.to_string()),
-                FinishDate => task.finish_date.map(|d| d.to_string()),
-                Finished => Some(task.finished.to_string()),
-                TresholdDate => task.threshold_date.map(|d| d.to_string()),
-                DueDate => task.due_date.map(|d| d.to_string()),
-                Contexts => process_vec(task.contexts()),
-                Projects => process_vec(task.projects()),
-                Hashtags => process_vec(&task.hashtags),
-                Special(special) => task.tags.get(special).cloned(),
-            },
-            None => None,
+            }
+            PriorityLabel => todo.styles.priority_label(&task.priority),
+            CreateDate => task.create_date.map(|d| d.to_string()),
+            FinishDate => task.finish_date.map(|d| d.to_string()),
+            Finished => Some(task.finished.to_string()),
+            TresholdDate => task.threshold_date.map(|d| d.to_string()),
+            DueDate => task.due_date.map(|d| d.to_string()),
+            Countdown => countdown(task, todo.clock().now()).map(format_countdown),
+            Urgency => Some(format!("{:.1}", todo.urgency(task))),
+            Contexts => process_vec(task.contexts()),
+            Projects => process_vec(task.projects()),
+            Hashtags => process_vec(&task.hashtags),
+            Special(special) => task.tags.get(special).cloned(),
         }
     }
 }
 
 impl From<String> for Parts {
+    /// Gets the part of the variable name. Dates have short names as well,
+    /// e.g. `$due` is the same as `$due_date`.
     fn from(value: String) -> Self {
         use Parts::*;
         match value.to_lowercase().as_str() {
@@ -65,11 +73,14 @@ impl From<String> for Parts {
             "done" => Done,
             "subject" => Subject,
             "priority" => Priority,
-            "create_date" => CreateDate,
-            "finish_date" => FinishDate,
+            "priority_label" => PriorityLabel,
+            "create_date" | "created" => CreateDate,
+            "finish_date" | "completed" => FinishDate,
             "finished" => Finished,
-            "treshold_date" => TresholdDate,
-            "due_date" => DueDate,
+            "treshold_date" | "threshold_date" | "threshold" => TresholdDate,
+            "due_date" | "due" => DueDate,
+            "countdown" => Countdown,
+            "urgency" => Urgency,
             "contexts" => Contexts,
             "projects" => Projects,
             "hashtags" => Hashtags,
@@ -81,7 +92,16 @@ impl From<String> for Parts {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::error::ToDoRes;
+    use crate::{
+        error::ToDoRes,
+        style::Styles,
+        todo::{ToDoConfig, UrgencyWeights},
+    };
+    use std::collections::HashMap;
+
+    fn fill_active(part: Parts, todo: &ToDo) -> Option<String> {
+        part.fill_task(todo.get_active()?, todo)
+    }
 
     #[test]
     fn fill() -> ToDoRes<()> {
@@ -97,78 +117,159 @@ mod tests {
         todo.new_task("task spec:some-text").unwrap();
         todo.new_task("x 2023-11-12 2023-11-12 done task").unwrap();
 
-        assert_eq!(Parts::Text("Text".to_string()).fill(&todo), None);
+        assert_eq!(fill_active(Parts::Text("Text".to_string()), &todo), None);
 
         todo.set_active(ToDoData::Pending, 0);
         assert_eq!(
-            Parts::Text("Text".to_string()).fill(&todo),
+            fill_active(Parts::Text("Text".to_string()), &todo),
             Some(String::from("Text"))
         );
 
-        assert_eq!(Parts::Pending.fill(&todo), Some(String::from("9")));
+        assert_eq!(fill_active(Parts::Pending, &todo), Some(String::from("9")));
 
-        assert_eq!(Parts::Done.fill(&todo), Some(String::from("1")));
+        assert_eq!(fill_active(Parts::Done, &todo), Some(String::from("1")));
 
-        assert_eq!(Parts::Subject.fill(&todo), Some(String::from("task")));
+        assert_eq!(
+            fill_active(Parts::Subject, &todo),
+            Some(String::from("task"))
+        );
 
-        assert_eq!(Parts::Priority.fill(&todo), None);
+        assert_eq!(fill_active(Parts::Priority, &todo), None);
 
         todo.set_active(ToDoData::Pending, 1);
-        assert_eq!(Parts::Priority.fill(&todo), Some(String::from("A")));
+        assert_eq!(fill_active(Parts::Priority, &todo), Some(String::from("A")));
+        assert_eq!(
+            fill_active(Parts::PriorityLabel, &todo),
+            Some(String::from("A"))
+        );
 
         todo.set_active(ToDoData::Pending, 2);
         assert_eq!(
-            Parts::CreateDate.fill(&todo),
+            fill_active(Parts::CreateDate, &todo),
             Some(String::from("2023-11-12"))
         );
 
-        assert_eq!(Parts::FinishDate.fill(&todo), None);
+        assert_eq!(fill_active(Parts::FinishDate, &todo), None);
 
         todo.set_active(ToDoData::Done, 0);
         assert_eq!(
-            Parts::FinishDate.fill(&todo),
+            fill_active(Parts::FinishDate, &todo),
             Some(String::from("2023-11-12"))
         );
 
         todo.set_active(ToDoData::Done, 0);
-        assert_eq!(Parts::Finished.fill(&todo), Some(String::from("true")));
+        assert_eq!(
+            fill_active(Parts::Finished, &todo),
+            Some(String::from("true"))
+        );
 
-        assert_eq!(Parts::TresholdDate.fill(&todo), None);
+        assert_eq!(fill_active(Parts::TresholdDate, &todo), None);
 
         todo.set_active(ToDoData::Pending, 3);
         assert_eq!(
-            Parts::TresholdDate.fill(&todo),
+            fill_active(Parts::TresholdDate, &todo),
             Some(String::from("2023-11-12"))
         );
 
-        assert_eq!(Parts::DueDate.fill(&todo), None);
+        assert_eq!(fill_active(Parts::DueDate, &todo), None);
 
         todo.set_active(ToDoData::Pending, 4);
-        assert_eq!(Parts::DueDate.fill(&todo), Some(String::from("2023-11-12")));
+        assert_eq!(
+            fill_active(Parts::DueDate, &todo),
+            Some(String::from("2023-11-12"))
+        );
 
-        assert_eq!(Parts::Contexts.fill(&todo), None);
+        assert_eq!(fill_active(Parts::Contexts, &todo), None);
 
         todo.set_active(ToDoData::Pending, 5);
-        assert_eq!(Parts::Contexts.fill(&todo), Some(String::from("context")));
+        assert_eq!(
+            fill_active(Parts::Contexts, &todo),
+            Some(String::from("context"))
+        );
 
-        assert_eq!(Parts::Projects.fill(&todo), None);
+        assert_eq!(fill_active(Parts::Projects, &todo), None);
 
         todo.set_active(ToDoData::Pending, 6);
-        assert_eq!(Parts::Projects.fill(&todo), Some(String::from("project")));
+        assert_eq!(
+            fill_active(Parts::Projects, &todo),
+            Some(String::from("project"))
+        );
 
-        assert_eq!(Parts::Hashtags.fill(&todo), None);
+        assert_eq!(fill_active(Parts::Hashtags, &todo), None);
 
         todo.set_active(ToDoData::Pending, 7);
-        assert_eq!(Parts::Hashtags.fill(&todo), Some(String::from("hashtag")));
+        assert_eq!(
+            fill_active(Parts::Hashtags, &todo),
+            Some(String::from("hashtag"))
+        );
 
-        assert_eq!(Parts::Special(String::from("spec")).fill(&todo), None);
+        assert_eq!(
+            fill_active(Parts::Special(String::from("spec")), &todo),
+            None
+        );
 
         todo.set_active(ToDoData::Pending, 8);
         assert_eq!(
-            Parts::Special(String::from("spec")).fill(&todo),
+            fill_active(Parts::Special(String::from("spec")), &todo),
             Some(String::from("some-text"))
         );
 
         Ok(())
     }
+
+    #[test]
+    fn priority_label() {
+        let labels = HashMap::from([(String::from("A"), String::from("Critical"))]);
+        let styles = Styles {
+            priority_labels: labels.clone(),
+            ..Styles::default()
+        };
+        let config = ToDoConfig {
+            priority_labels: labels,
+            ..ToDoConfig::default()
+        };
+        let mut todo = ToDo::new(config, styles);
+        todo.new_task("(A) task").unwrap();
+        todo.new_task("(B) task").unwrap();
+        todo.new_task("task").unwrap();
+
+        todo.set_active(ToDoData::Pending, 0);
+        assert_eq!(
+            fill_active(Parts::PriorityLabel, &todo),
+            Some(String::from("Critical"))
+        );
+        todo.set_active(ToDoData::Pending, 1);
+        assert_eq!(
+            fill_active(Parts::PriorityLabel, &todo),
+            Some(String::from("B"))
+        );
+        todo.set_active(ToDoData::Pending, 2);
+        assert_eq!(fill_active(Parts::PriorityLabel, &todo), None);
+    }
+
+    #[test]
+    fn urgency() {
+        let config = ToDoConfig {
+            urgency_weights: UrgencyWeights::new(&HashMap::from([
+                (String::from("priority"), 2.0),
+                (String::from("+work"), 1.5),
+            ])),
+            ..ToDoConfig::default()
+        };
+        let mut todo = ToDo::new(config, Styles::default());
+        todo.new_task("(A) task +work").unwrap();
+        todo.new_task("task").unwrap();
+
+        assert_eq!(Parts::from(String::from("urgency")), Parts::Urgency);
+        todo.set_active(ToDoData::Pending, 0);
+        assert_eq!(
+            fill_active(Parts::Urgency, &todo),
+            Some(String::from("3.5"))
+        );
+        todo.set_active(ToDoData::Pending, 1);
+        assert_eq!(
+            fill_active(Parts::Urgency, &todo),
+            Some(String::from("0.0"))
+        );
+    }
 }
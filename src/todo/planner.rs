@@ -0,0 +1,129 @@
+use super::{ToDo, ToDoData};
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use todo_txt::Task;
+
+/// The date tag placing a task on a day of the week planner.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DateTag {
+    /// The due date, `due:`.
+    Due,
+    /// The threshold date the task is scheduled for, `t:`.
+    Threshold,
+}
+
+/// Represents a pending task placed on a day of the week planner.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlannedTask {
+    /// The index of the task in the pending tasks.
+    pub index: usize,
+    pub task: Task,
+    /// The tag whose date placed the task on the day.
+    pub tag: DateTag,
+}
+
+/// Gets the Monday starting the week of the date.
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+impl ToDo {
+    /// Places the pending tasks on the days of the week. A task is placed on its due
+    /// date, or on its threshold date when it is not due in the week.
+    ///
+    /// # Arguments
+    ///
+    /// * `monday` - The first day of the week.
+    ///
+    /// # Returns
+    ///
+    /// The tasks of the days from Monday to Sunday, in the order of the todo list.
+    pub fn week(&self, monday: NaiveDate) -> [Vec<PlannedTask>; 7] {
+        let mut days: [Vec<PlannedTask>; 7] = Default::default();
+        let day_of = |date: Option<NaiveDate>| {
+            let days = (date? - monday).num_days();
+            (0..7).contains(&days).then_some(days as usize)
+        };
+        for (index, task) in self.pending.iter().enumerate() {
+            let placed = match (day_of(task.due_date), day_of(task.threshold_date)) {
+                (Some(day), _) => Some((day, DateTag::Due)),
+                (None, Some(day)) => Some((day, DateTag::Threshold)),
+                (None, None) => None,
+            };
+            if let Some((day, tag)) = placed {
+                days[day].push(PlannedTask {
+                    index,
+                    task: task.clone(),
+                    tag,
+                });
+            }
+        }
+        days
+    }
+
+    /// Moves the date of the tag by the number of days, the tag is added from today
+    /// if the task does not have it.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data where the task is located.
+    /// * `index` - The index of the task in the unfiltered data.
+    /// * `tag` - The moved date tag.
+    /// * `days` - The number of days, negative to move the date back.
+    pub(super) fn shift_date_at(&mut self, data: ToDoData, index: usize, tag: DateTag, days: i64) {
+        let today = self.config.clock.today();
+        let task = &mut data.get_data_mut(self)[index];
+        let date = match tag {
+            DateTag::Due => &mut task.due_date,
+            DateTag::Threshold => &mut task.threshold_date,
+        };
+        *date = Some(date.unwrap_or(today) + Duration::days(days));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::todo::{Action, TaskId};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn week() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        todo.new_task("call mom due:2024-07-03")?;
+        todo.new_task("buy milk t:2024-07-01")?;
+        todo.new_task("pay rent due:2024-07-08 t:2024-07-05")?;
+        todo.new_task("fix the bike due:2024-07-07 t:2024-07-01")?;
+        todo.new_task("water plants")?;
+        // 2024-07-03 is a Wednesday.
+        let monday = week_start(date(2024, 7, 3));
+        assert_eq!(monday, date(2024, 7, 1));
+
+        let week = todo.week(monday);
+        let subjects = |day: usize| -> Vec<(&str, DateTag)> {
+            week[day]
+                .iter()
+                .map(|planned| (planned.task.subject.as_str(), planned.tag))
+                .collect()
+        };
+        assert_eq!(subjects(0), [("buy milk", DateTag::Threshold)]);
+        assert_eq!(subjects(2), [("call mom", DateTag::Due)]);
+        assert_eq!(subjects(4), [("pay rent", DateTag::Threshold)]);
+        assert_eq!(subjects(6), [("fix the bike", DateTag::Due)]);
+        assert_eq!(week.iter().map(Vec::len).sum::<usize>(), 4);
+
+        let planned = &week[4][0];
+        todo.apply(Action::ShiftDate {
+            data: ToDoData::Pending,
+            task: TaskId::new(planned.index, &planned.task),
+            tag: planned.tag,
+            days: -2,
+        })?;
+        assert_eq!(todo.pending[2].threshold_date, Some(date(2024, 7, 3)));
+        assert_eq!(todo.pending[2].due_date, Some(date(2024, 7, 8)));
+        Ok(())
+    }
+}
@@ -0,0 +1,198 @@
+use super::{
+    date_expr::{expand_date, expand_dates},
+    parse_task,
+};
+use crate::todo::ToDoConfig;
+use chrono::NaiveDate;
+use todo_txt::Task;
+
+/// Prepares a task entered by the user before it is added to the todo list.
+///
+/// The task is processed in steps:
+///
+/// 1. A priority written as a number, `(1)` is `(A)`, or as a label from
+///    `priority_labels` is replaced with the priority letter.
+/// 2. Date expressions of `due:` and `t:`, e.g. `due:tomorrow` or `t:+2w`, are replaced
+///    with the dates, see [`expand_dates`]. An empty `due:` is the date of today.
+/// 3. Default metadata of its projects from `project_defaults` are added.
+/// 4. The creation date is set to today if the task does not have one.
+///
+/// # Arguments
+///
+/// * `task` - The task string entered by the user.
+/// * `config` - The configuration with default metadata of projects.
+/// * `today` - The date relative dates are computed from.
+///
+/// # Returns
+///
+/// The parsed task or an error if the task string cannot be parsed.
+pub fn preprocess(
+    task: &str,
+    config: &ToDoConfig,
+    today: NaiveDate,
+) -> Result<Task, todo_txt::Error> {
+    let task = expand_priority_alias(task, config);
+    let task = expand_dates(&task, today);
+    let mut task = task.replace("due: ", &format!("due:{}", today));
+    let parsed = parse_task(&task)?;
+    let defaults: Vec<String> = parsed
+        .projects()
+        .iter()
+        .filter_map(|project| config.project_defaults.get(project))
+        .flat_map(|defaults| defaults.split_whitespace())
+        .filter(|token| !has_metadata(&parsed, token))
+        .map(|token| expand_date(token, today))
+        .collect();
+    let mut parsed = if defaults.is_empty() {
+        parsed
+    } else {
+        task.push(' ');
+        task.push_str(&defaults.join(" "));
+        parse_task(&task)?
+    };
+    if parsed.create_date.is_none() {
+        parsed.create_date = Some(today);
+    }
+    Ok(parsed)
+}
+
+/// Replaces the priority alias at the start of the task with the priority letter.
+/// Tasks without an alias are returned unchanged.
+fn expand_priority_alias(task: &str, config: &ToDoConfig) -> String {
+    let expanded = task.strip_prefix('(').and_then(|rest| {
+        let (alias, rest) = rest.split_once(')')?;
+        let letter = match alias.parse::<u8>() {
+            Ok(number @ 1..=26) => char::from(b'A' + number - 1).to_string(),
+            Ok(_) => return None,
+            Err(_) => config
+                .priority_labels
+                .iter()
+                .find(|(_, label)| label.eq_ignore_ascii_case(alias))?
+                .0
+                .clone(),
+        };
+        Some(format!("({letter}){rest}"))
+    });
+    expanded.unwrap_or_else(|| task.to_string())
+}
+
+/// Checks whether the task already has the metadata of the token, so a default
+/// never overrides what the user has written.
+fn has_metadata(task: &Task, token: &str) -> bool {
+    if let Some(project) = token.strip_prefix('+') {
+        task.projects().iter().any(|p| p == project)
+    } else if let Some(context) = token.strip_prefix('@') {
+        task.contexts().iter().any(|c| c == context)
+    } else if let Some(hashtag) = token.strip_prefix('#') {
+        task.hashtags.iter().any(|h| h == hashtag)
+    } else if let Some((key, _)) = token.split_once(':') {
+        match key {
+            "due" => task.due_date.is_some(),
+            "t" => task.threshold_date.is_some(),
+            key => task.tags.contains_key(key),
+        }
+    } else {
+        task.subject.split_whitespace().any(|word| word == token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config() -> ToDoConfig {
+        ToDoConfig {
+            project_defaults: HashMap::from([
+                (
+                    String::from("client-x"),
+                    String::from("@work due:+14d review:yes"),
+                ),
+                (String::from("home"), String::from("@home +chores")),
+            ]),
+            ..ToDoConfig::default()
+        }
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2023, 6, 1).unwrap()
+    }
+
+    #[test]
+    fn project_defaults() -> Result<(), todo_txt::Error> {
+        let task = preprocess("call the client +client-x", &config(), today())?;
+        assert_eq!(task.contexts().len(), 1);
+        assert!(task.contexts().contains(&String::from("work")));
+        assert_eq!(task.due_date, NaiveDate::from_ymd_opt(2023, 6, 15));
+        assert_eq!(task.tags.get("review"), Some(&String::from("yes")));
+        assert_eq!(task.create_date, Some(today()));
+
+        let task = preprocess("2023-05-01 fix the sink +home @garage", &config(), today())?;
+        assert_eq!(task.contexts().len(), 2);
+        assert!(task.contexts().contains(&String::from("home")));
+        assert!(task.projects().contains(&String::from("chores")));
+        assert_eq!(task.create_date, NaiveDate::from_ymd_opt(2023, 5, 1));
+
+        let task = preprocess(
+            "meeting +client-x due:2023-06-02 @office",
+            &config(),
+            today(),
+        )?;
+        assert_eq!(task.due_date, NaiveDate::from_ymd_opt(2023, 6, 2));
+        assert!(task.contexts().contains(&String::from("work")));
+
+        let task = preprocess("plain task", &config(), today())?;
+        assert!(task.contexts().is_empty());
+        assert!(task.due_date.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn priority_aliases() {
+        let config = ToDoConfig {
+            priority_labels: HashMap::from([(String::from("A"), String::from("Critical"))]),
+            ..ToDoConfig::default()
+        };
+        assert_eq!(
+            expand_priority_alias("(1) call mom", &config),
+            "(A) call mom"
+        );
+        assert_eq!(
+            expand_priority_alias("(3) call mom", &config),
+            "(C) call mom"
+        );
+        assert_eq!(
+            expand_priority_alias("(critical) call mom", &config),
+            "(A) call mom"
+        );
+        assert_eq!(
+            expand_priority_alias("(B) call mom", &config),
+            "(B) call mom"
+        );
+        assert_eq!(
+            expand_priority_alias("(27) call mom", &config),
+            "(27) call mom"
+        );
+        assert_eq!(
+            expand_priority_alias("call (1) mom", &config),
+            "call (1) mom"
+        );
+    }
+
+    #[test]
+    fn natural_dates() -> Result<(), todo_txt::Error> {
+        // 2023-06-01 is a Thursday.
+        let task = preprocess("call mom due:tomorrow t:mon", &config(), today())?;
+        assert_eq!(task.due_date, NaiveDate::from_ymd_opt(2023, 6, 2));
+        assert_eq!(task.threshold_date, NaiveDate::from_ymd_opt(2023, 6, 5));
+
+        let task = preprocess("pay rent due:today", &config(), today())?;
+        assert_eq!(task.due_date, Some(today()));
+        assert_eq!(task.subject, "pay rent");
+
+        // The date typed by the user is kept over the default of the project.
+        let task = preprocess("call the client +client-x due:fri", &config(), today())?;
+        assert_eq!(task.due_date, NaiveDate::from_ymd_opt(2023, 6, 2));
+        Ok(())
+    }
+}
@@ -0,0 +1,203 @@
+use super::{Change, ToDo, ToDoCategory};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::{collections::HashMap, error::Error, fs, io::ErrorKind, path::Path};
+
+/// Information about a project stored in the projects file, e.g.
+///
+/// ```toml
+/// [work]
+/// description = "Tasks for the day job"
+/// goal = "Ship the new release"
+/// deadline = "2024-06-30"
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectInfo {
+    pub description: Option<String>,
+    pub goal: Option<String>,
+    pub deadline: Option<String>,
+}
+
+impl ProjectInfo {
+    /// Loads the information of all projects by their names. A missing file has no projects.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the projects file.
+    pub fn load_all(path: impl AsRef<Path>) -> Result<HashMap<String, Self>, Box<dyn Error>> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(HashMap::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let projects: HashMap<String, Self> = toml::from_str(&content)?;
+        for (name, info) in &projects {
+            if info.deadline.is_some() && info.deadline_date().is_none() {
+                return Err(format!("Deadline of the project '{name}' is not a date").into());
+            }
+        }
+        Ok(projects)
+    }
+
+    /// Gets the deadline as a date, `None` if there is no deadline.
+    pub fn deadline_date(&self) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(self.deadline.as_ref()?, "%Y-%m-%d").ok()
+    }
+
+    /// Gets the information as labeled values, fields that are not set are left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `today` - The current date, the days left to the deadline are counted from it.
+    pub fn fields(&self, today: NaiveDate) -> Vec<(&'static str, String)> {
+        let mut fields = Vec::new();
+        if let Some(description) = &self.description {
+            fields.push(("Description", description.clone()));
+        }
+        if let Some(goal) = &self.goal {
+            fields.push(("Goal", goal.clone()));
+        }
+        if let Some(deadline) = self.deadline_date() {
+            let left = time_left(deadline, today);
+            fields.push(("Deadline", format!("{deadline} ({left})")));
+        }
+        fields
+    }
+}
+
+/// Describes the time left to the deadline, e.g. `tomorrow`, `in 3 days` or `2 days overdue`.
+///
+/// # Arguments
+///
+/// * `deadline` - The date of the deadline.
+/// * `today` - The current date.
+pub fn time_left(deadline: NaiveDate, today: NaiveDate) -> String {
+    match (deadline - today).num_days() {
+        0 => String::from("today"),
+        1 => String::from("tomorrow"),
+        days if days < 0 => format!("{} days overdue", -days),
+        days => format!("in {days} days"),
+    }
+}
+
+/// A project whose deadline approaches or has passed while it still has open tasks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProjectRisk {
+    pub name: String,
+    pub deadline: NaiveDate,
+    /// The number of pending tasks of the project.
+    pub open: usize,
+}
+
+impl ToDo {
+    /// Gets the project highlighted in the projects widget, `None` if the widget is not focused.
+    pub fn selected_project(&self) -> Option<&str> {
+        self.selected_project.as_deref()
+    }
+
+    /// Replaces the information of projects loaded from the projects file.
+    pub fn set_projects(&mut self, projects: HashMap<String, ProjectInfo>) {
+        self.projects = projects;
+        self.version += 1;
+        self.notify(Change::Projects);
+    }
+
+    /// Gets the information of the project, `None` if the projects file does not describe it.
+    pub fn project_info(&self, name: &str) -> Option<&ProjectInfo> {
+        self.projects.get(name)
+    }
+
+    /// Gets projects with open tasks whose deadline is at most `deadline_warning_days`
+    /// away or has passed, the closest deadlines first.
+    ///
+    /// # Arguments
+    ///
+    /// * `today` - The current date.
+    pub fn at_risk_projects(&self, today: NaiveDate) -> Vec<ProjectRisk> {
+        let (counts, _) = self.category_counts(ToDoCategory::Projects);
+        let mut risks: Vec<ProjectRisk> = self
+            .projects
+            .iter()
+            .filter_map(|(name, info)| {
+                let deadline = info.deadline_date()?;
+                let open = counts.get(name.as_str()).copied().unwrap_or(0);
+                let close = (deadline - today).num_days() <= self.config.deadline_warning_days;
+                (open > 0 && close).then(|| ProjectRisk {
+                    name: name.clone(),
+                    deadline,
+                    open,
+                })
+            })
+            .collect();
+        risks.sort_by(|a, b| a.deadline.cmp(&b.deadline).then(a.name.cmp(&b.name)));
+        risks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env::temp_dir, process};
+
+    #[test]
+    fn load_all() -> Result<(), Box<dyn Error>> {
+        let path = temp_dir().join(format!("todotxt-tui-projects-{}.toml", process::id()));
+        fs::write(
+            &path,
+            "[work]\ndescription = \"Day job\"\ndeadline = \"2023-05-10\"\n\n[home]\ngoal = \"Clean garage\"\n",
+        )?;
+        let projects = ProjectInfo::load_all(&path)?;
+        let today = NaiveDate::from_ymd_opt(2023, 5, 3).unwrap();
+        assert_eq!(
+            projects["work"].fields(today),
+            vec![
+                ("Description", String::from("Day job")),
+                ("Deadline", String::from("2023-05-10 (in 7 days)")),
+            ]
+        );
+        assert_eq!(
+            projects["home"].fields(today),
+            vec![("Goal", String::from("Clean garage"))]
+        );
+
+        assert_eq!(time_left(today, today), "today");
+        assert_eq!(
+            time_left(NaiveDate::from_ymd_opt(2023, 5, 1).unwrap(), today),
+            "2 days overdue"
+        );
+
+        fs::write(&path, "[work]\ndeadline = \"next week\"\n")?;
+        assert!(ProjectInfo::load_all(&path).is_err());
+        fs::remove_file(&path)?;
+        assert!(ProjectInfo::load_all(&path)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn at_risk_projects() {
+        let mut todo = ToDo::default();
+        todo.new_task("write the report +work").unwrap();
+        todo.new_task("book the flights +trip").unwrap();
+        todo.new_task("x 2023-05-01 paint the fence +garden")
+            .unwrap();
+        let info = |deadline: &str| ProjectInfo {
+            deadline: Some(deadline.to_string()),
+            ..Default::default()
+        };
+        todo.set_projects(HashMap::from([
+            (String::from("work"), info("2023-05-10")),
+            (String::from("trip"), info("2023-05-01")),
+            (String::from("garden"), info("2023-05-04")),
+            (String::from("house"), info("2023-05-04")),
+        ]));
+
+        let today = NaiveDate::from_ymd_opt(2023, 5, 3).unwrap();
+        let names = |risks: Vec<ProjectRisk>| -> Vec<String> {
+            risks.into_iter().map(|risk| risk.name).collect()
+        };
+        assert_eq!(names(todo.at_risk_projects(today)), vec!["trip", "work"]);
+        let today = NaiveDate::from_ymd_opt(2023, 4, 20).unwrap();
+        assert!(todo.at_risk_projects(today).is_empty());
+    }
+}
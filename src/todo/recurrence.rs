@@ -0,0 +1,264 @@
+use crate::error::{ToDoError, ToDoRes};
+use chrono::{Datelike, Duration, Months, NaiveDate, Weekday};
+use std::str::FromStr;
+use todo_txt::Task;
+
+/// The tag making a task recurring, e.g. `rec:1w` or `rec:+1m`.
+pub const REC_TAG: &str = "rec";
+
+/// The unit of the interval of a recurring task.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecurrenceUnit {
+    Day,
+    /// A day from Monday to Friday.
+    BusinessDay,
+    Week,
+    Month,
+    Year,
+}
+
+/// The interval of a recurring task given by the `rec:` tag.
+///
+/// When a recurring task is completed, a new pending copy is created with its dates moved by
+/// the interval. The dates are moved from the completion date, or with a strict recurrence
+/// written with `+`, e.g. `rec:+1w`, from the dates of the completed task.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Recurrence {
+    pub strict: bool,
+    pub amount: u32,
+    pub unit: RecurrenceUnit,
+}
+
+impl Recurrence {
+    /// Gets the recurrence of the task, `None` if the task is not recurring.
+    /// An invalid `rec:` tag is logged.
+    pub fn of(task: &Task) -> Option<Self> {
+        let value = task.tags.get(REC_TAG)?;
+        match Self::from_str(value) {
+            Ok(recurrence) => Some(recurrence),
+            Err(e) => {
+                log::warn!("Task '{}' does not recur: {}", task.subject, e);
+                None
+            }
+        }
+    }
+
+    /// Moves the date by the interval. Months and years keep the day of the month,
+    /// or use the last day of the month if the month is shorter.
+    pub fn add_to(&self, date: NaiveDate) -> NaiveDate {
+        let moved = match self.unit {
+            RecurrenceUnit::Day => date.checked_add_signed(Duration::days(self.amount.into())),
+            RecurrenceUnit::Week => date.checked_add_signed(Duration::weeks(self.amount.into())),
+            RecurrenceUnit::Month => date.checked_add_months(Months::new(self.amount)),
+            RecurrenceUnit::Year => date.checked_add_months(Months::new(self.amount * 12)),
+            RecurrenceUnit::BusinessDay => add_business_days(date, self.amount),
+        };
+        moved.unwrap_or(date)
+    }
+
+    /// Creates the next pending task of the completed recurring task.
+    ///
+    /// The due date and the threshold date are moved by the interval, the threshold date
+    /// keeps its distance to the due date. A task without both dates gets a due date.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The completed recurring task.
+    /// * `today` - The completion date.
+    pub fn next_task(&self, task: &Task, today: NaiveDate) -> Task {
+        let mut next = task.clone();
+        next.uncomplete();
+        if next.create_date.is_some() {
+            next.create_date = Some(today);
+        }
+        match (self.strict, task.due_date, task.threshold_date) {
+            (_, None, None) => next.due_date = Some(self.add_to(today)),
+            (true, due, threshold) => {
+                next.due_date = due.map(|due| self.add_to(due));
+                next.threshold_date = threshold.map(|threshold| self.add_to(threshold));
+            }
+            (false, Some(due), threshold) => {
+                let next_due = self.add_to(today);
+                next.due_date = Some(next_due);
+                next.threshold_date = threshold.map(|threshold| next_due - (due - threshold));
+            }
+            (false, None, Some(_)) => next.threshold_date = Some(self.add_to(today)),
+        }
+        next
+    }
+}
+
+/// Moves the date by the number of days from Monday to Friday, weekends are skipped.
+fn add_business_days(mut date: NaiveDate, mut days: u32) -> Option<NaiveDate> {
+    while days > 0 {
+        date = date.succ_opt()?;
+        if !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            days -= 1;
+        }
+    }
+    Some(date)
+}
+
+impl FromStr for Recurrence {
+    type Err = ToDoError;
+
+    fn from_str(s: &str) -> ToDoRes<Self> {
+        let error = || ToDoError::ParseRecurrence(s.to_string());
+        let (strict, rest) = match s.strip_prefix('+') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        let unit = match rest.chars().last().ok_or_else(error)?.to_ascii_lowercase() {
+            'd' => RecurrenceUnit::Day,
+            'b' => RecurrenceUnit::BusinessDay,
+            'w' => RecurrenceUnit::Week,
+            'm' => RecurrenceUnit::Month,
+            'y' => RecurrenceUnit::Year,
+            _ => return Err(error()),
+        };
+        let amount = match &rest[..rest.len() - 1] {
+            // The amount can be left out, `rec:w` is every week.
+            "" => 1,
+            amount => amount.parse().map_err(|_| error())?,
+        };
+        if amount == 0 {
+            return Err(error());
+        }
+        Ok(Self {
+            strict,
+            amount,
+            unit,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::Clock;
+    use crate::style::Styles;
+    use crate::todo::{Action, ToDo, ToDoConfig, ToDoData};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn rec(s: &str) -> Recurrence {
+        Recurrence::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            rec("3d"),
+            Recurrence {
+                strict: false,
+                amount: 3,
+                unit: RecurrenceUnit::Day
+            }
+        );
+        assert_eq!(
+            rec("+1W"),
+            Recurrence {
+                strict: true,
+                amount: 1,
+                unit: RecurrenceUnit::Week
+            }
+        );
+        assert_eq!(rec("m").amount, 1);
+        assert_eq!(rec("2b").unit, RecurrenceUnit::BusinessDay);
+        for invalid in ["", "+", "1", "0d", "1x", "-1d", "1.5w"] {
+            assert_eq!(
+                Recurrence::from_str(invalid),
+                Err(ToDoError::ParseRecurrence(invalid.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn add_to() {
+        assert_eq!(rec("3d").add_to(date(2023, 12, 30)), date(2024, 1, 2));
+        assert_eq!(rec("2w").add_to(date(2023, 5, 3)), date(2023, 5, 17));
+        assert_eq!(rec("1m").add_to(date(2023, 1, 31)), date(2023, 2, 28));
+        assert_eq!(rec("1m").add_to(date(2024, 1, 31)), date(2024, 2, 29));
+        assert_eq!(rec("1m").add_to(date(2023, 12, 15)), date(2024, 1, 15));
+        assert_eq!(rec("14m").add_to(date(2023, 11, 30)), date(2025, 1, 30));
+        assert_eq!(rec("1y").add_to(date(2024, 2, 29)), date(2025, 2, 28));
+        assert_eq!(rec("4y").add_to(date(2024, 2, 29)), date(2028, 2, 29));
+        // 2023-05-05 is a Friday.
+        assert_eq!(rec("1b").add_to(date(2023, 5, 5)), date(2023, 5, 8));
+        assert_eq!(rec("6b").add_to(date(2023, 5, 5)), date(2023, 5, 15));
+    }
+
+    #[test]
+    fn next_task() {
+        let task = |s: &str| Task::from_str(s).unwrap();
+        let today = date(2023, 5, 10);
+
+        let next = rec("1w").next_task(&task("water plants due:2023-05-03 rec:1w"), today);
+        assert_eq!(next.due_date, Some(date(2023, 5, 17)));
+        let next = rec("+1w").next_task(&task("water plants due:2023-05-03 rec:+1w"), today);
+        assert_eq!(next.due_date, Some(date(2023, 5, 10)));
+
+        let next = rec("1m").next_task(
+            &task("pay rent due:2023-05-31 t:2023-05-25 rec:1m"),
+            date(2023, 5, 30),
+        );
+        assert_eq!(next.due_date, Some(date(2023, 6, 30)));
+        assert_eq!(next.threshold_date, Some(date(2023, 6, 24)));
+        let next =
+            rec("+1m").next_task(&task("pay rent due:2023-01-31 t:2023-01-25 rec:+1m"), today);
+        assert_eq!(next.due_date, Some(date(2023, 2, 28)));
+        assert_eq!(next.threshold_date, Some(date(2023, 2, 25)));
+
+        let next = rec("3d").next_task(&task("stretch rec:3d"), today);
+        assert_eq!(next.due_date, Some(date(2023, 5, 13)));
+        assert!(!next.finished);
+    }
+
+    #[test]
+    fn complete_recurring() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        todo.add_task(Task::from_str("water plants due:2023-05-03 rec:+1w")?);
+        todo.add_task(Task::from_str("call mom")?);
+        todo.set_active(ToDoData::Pending, 1);
+        let task = todo.task_id(ToDoData::Pending, 0).unwrap();
+        todo.apply(Action::CompleteTask {
+            data: ToDoData::Pending,
+            task,
+        })?;
+
+        assert_eq!(todo.done.len(), 1);
+        assert_eq!(todo.done[0].due_date, Some(date(2023, 5, 3)));
+        assert_eq!(todo.pending.len(), 2);
+        assert_eq!(todo.pending[1].due_date, Some(date(2023, 5, 10)));
+        assert_eq!(todo.get_active().unwrap().subject, "call mom");
+
+        // Reopening the task does not create another copy.
+        let task = todo.task_id(ToDoData::Done, 0).unwrap();
+        todo.apply(Action::CompleteTask {
+            data: ToDoData::Done,
+            task,
+        })?;
+        assert_eq!(todo.pending.len(), 3);
+        assert!(todo.done.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn complete_with_fixed_clock() -> Result<(), todo_txt::Error> {
+        let config = ToDoConfig {
+            clock: Clock::fixed(date(2023, 5, 10)),
+            ..ToDoConfig::default()
+        };
+        let mut todo = ToDo::new(config, Styles::default());
+        todo.add_task(Task::from_str("water plants due:2023-05-03 rec:1w")?);
+        let task = todo.task_id(ToDoData::Pending, 0).unwrap();
+        todo.apply(Action::CompleteTask {
+            data: ToDoData::Pending,
+            task,
+        })?;
+        assert_eq!(todo.pending[0].due_date, Some(date(2023, 5, 17)));
+        Ok(())
+    }
+}
@@ -0,0 +1,185 @@
+use super::{
+    countdown::{deadline, format_countdown},
+    ToDo,
+};
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use todo_txt::Task;
+
+/// The tag with the date and time of a reminder, e.g. `remind:2024-07-01T14:30`.
+pub const REMIND_TAG: &str = "remind";
+
+/// The tag with the times before the deadline of a task a notification comes at,
+/// e.g. `notify:1d,2h`. The deadline is the end of the due date.
+pub const NOTIFY_TAG: &str = "notify";
+
+/// Parses a time before the deadline, a number with the unit `m` (minutes), `h` (hours),
+/// `d` (days) or `w` (weeks), e.g. `30m`.
+fn parse_offset(value: &str) -> Option<Duration> {
+    let unit = value.chars().last()?;
+    let number: i64 = value[..value.len() - unit.len_utf8()].parse().ok()?;
+    match unit {
+        'm' => Duration::try_minutes(number),
+        'h' => Duration::try_hours(number),
+        'd' => Duration::try_days(number),
+        'w' => Duration::try_weeks(number),
+        _ => None,
+    }
+}
+
+/// Gets the moments the task notifies of its coming deadline, see [`NOTIFY_TAG`].
+/// Invalid offsets are left out.
+pub fn task_notices(task: &Task) -> Vec<NaiveDateTime> {
+    let (Some(deadline), Some(offsets)) = (deadline(task), task.tags.get(NOTIFY_TAG)) else {
+        return Vec::new();
+    };
+    offsets
+        .split(',')
+        .filter_map(|offset| parse_offset(offset.trim()))
+        .filter_map(|offset| deadline.checked_sub_signed(offset))
+        .collect()
+}
+
+/// Parses the value of the `remind:` tag.
+///
+/// The value is a date with a time, `2024-07-01T14:30` or `2024-07-01T14:30:15`.
+/// A date alone, `2024-07-01`, reminds at the start of the day.
+pub fn parse_remind(value: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M")
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S"))
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()?
+                .and_hms_opt(0, 0, 0)
+        })
+}
+
+/// Gets the date and time of the reminder of the task, if it has a valid one.
+pub fn task_reminder(task: &Task) -> Option<NaiveDateTime> {
+    task.tags
+        .get(REMIND_TAG)
+        .and_then(|value| parse_remind(value))
+}
+
+impl ToDo {
+    /// Gets pending tasks whose reminder falls into the time range.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The time of the previous check, reminders at this time are excluded.
+    /// * `to` - The current time, reminders at this time are included.
+    ///
+    /// # Returns
+    ///
+    /// Subjects of the tasks to remind.
+    pub fn reminders(&self, from: NaiveDateTime, to: NaiveDateTime) -> Vec<String> {
+        self.pending
+            .iter()
+            .filter(|task| task_reminder(task).is_some_and(|remind| from < remind && remind <= to))
+            .map(|task| task.subject.clone())
+            .collect()
+    }
+
+    /// Gets pending tasks with a notification before their deadline in the time range,
+    /// see [`NOTIFY_TAG`].
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The time of the previous check, notifications at this time are excluded.
+    /// * `to` - The current time, notifications at this time are included.
+    ///
+    /// # Returns
+    ///
+    /// The descriptions of the tasks with the time left, e.g. `2h left: pay rent`.
+    pub fn due_notices(&self, from: NaiveDateTime, to: NaiveDateTime) -> Vec<String> {
+        self.pending
+            .iter()
+            .filter_map(|task| {
+                let notice = task_notices(task)
+                    .into_iter()
+                    .filter(|notice| from < *notice && *notice <= to)
+                    .max()?;
+                let left = deadline(task)? - notice;
+                Some(format!("{}: {}", format_countdown(left), task.subject))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(date: &str) -> NaiveDateTime {
+        parse_remind(date).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let date = NaiveDate::from_ymd_opt(2024, 7, 1).unwrap();
+        assert_eq!(
+            parse_remind("2024-07-01T14:30"),
+            date.and_hms_opt(14, 30, 0)
+        );
+        assert_eq!(
+            parse_remind("2024-07-01T14:30:15"),
+            date.and_hms_opt(14, 30, 15)
+        );
+        assert_eq!(parse_remind("2024-07-01"), date.and_hms_opt(0, 0, 0));
+        assert_eq!(parse_remind("2024-07-01T25:00"), None);
+        assert_eq!(parse_remind("tomorrow"), None);
+    }
+
+    #[test]
+    fn reminders() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        todo.new_task("call the client remind:2024-07-01T14:30")?;
+        todo.new_task("water plants remind:2024-07-01T15:00")?;
+        todo.new_task("no reminder")?;
+        todo.new_task("x done task remind:2024-07-01T14:30")?;
+
+        let reminders = todo.reminders(at("2024-07-01T14:00"), at("2024-07-01T14:30"));
+        assert_eq!(reminders.len(), 1);
+        assert!(reminders[0].starts_with("call the client"));
+
+        assert!(todo
+            .reminders(at("2024-07-01T14:30"), at("2024-07-01T14:59"))
+            .is_empty());
+        assert_eq!(todo.reminders(at("2024-07-01"), at("2024-07-02")).len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn notices() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        todo.new_task("pay rent due:2024-07-03 notify:1d,2h,soon")?;
+        todo.new_task("call mom notify:1h")?;
+        assert_eq!(
+            task_notices(&todo.pending[0]),
+            [at("2024-07-03"), at("2024-07-03T22:00")]
+        );
+        assert!(task_notices(&todo.pending[1]).is_empty());
+        assert_eq!(
+            todo.next_alarm(at("2024-07-03T12:00")),
+            Some(at("2024-07-03T22:00"))
+        );
+
+        assert_eq!(
+            todo.due_notices(at("2024-07-02T23:00"), at("2024-07-03T01:00")),
+            ["24h left: pay rent"]
+        );
+        assert_eq!(
+            todo.due_notices(at("2024-07-03T21:00"), at("2024-07-03T22:00")),
+            ["2h left: pay rent"]
+        );
+        // Both notifications passed while the application was not checking, only the last is shown.
+        assert_eq!(
+            todo.due_notices(at("2024-07-01"), at("2024-07-03T23:00")),
+            ["2h left: pay rent"]
+        );
+        assert!(todo
+            .due_notices(at("2024-07-03T22:00"), at("2024-07-03T23:00"))
+            .is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,226 @@
+use super::{is_scratch, Action, TaskId, ToDo, ToDoData};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use todo_txt::{Priority, Task};
+
+/// The format of the date of the last review stored in the task metadata.
+const REVIEWED_FORMAT: &str = "%Y-%m-%d";
+
+/// The decision made about a stale task during the review.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReviewAction {
+    /// Keeps the task as it is.
+    Keep,
+    /// Sets the priority of the task, `None` removes it.
+    Priority(Option<char>),
+    /// Sets the due date of the task by the date expression, see [`super::DateExpr`].
+    Reschedule(String),
+    /// Removes the task, it is put to the trash.
+    Delete,
+}
+
+/// The review of stale tasks, pending tasks without activity for a number of days.
+///
+/// The activity of a task is its creation and its last review. The stale tasks are
+/// walked through one by one and a decision is made about each of them, see
+/// [`super::Action::ReviewTask`]. A reviewed task is not stale again until the number
+/// of days passes.
+pub struct Review {
+    tasks: Vec<TaskId>,
+    index: usize,
+}
+
+impl Review {
+    /// Starts the review of the tasks without activity for the number of days.
+    ///
+    /// # Arguments
+    ///
+    /// * `todo` - The todo list to review.
+    /// * `days` - The number of days after which a task is stale.
+    pub fn new(todo: &ToDo, days: usize) -> Self {
+        Self {
+            tasks: todo.stale_tasks(days),
+            index: 0,
+        }
+    }
+
+    /// Gets the task being reviewed or `None` if all tasks were walked through.
+    pub fn current(&self) -> Option<&TaskId> {
+        self.tasks.get(self.index)
+    }
+
+    /// Gets the position of the current task counted from 1 and the number of tasks.
+    pub fn position(&self) -> (usize, usize) {
+        (self.index + 1, self.tasks.len())
+    }
+
+    /// Continues with the next task.
+    pub fn next(&mut self) {
+        self.index += 1;
+    }
+}
+
+impl ToDo {
+    /// Gets the date of the last activity of the task, the later of its creation date
+    /// and its last review, `None` if the task has neither.
+    pub fn last_activity(&self, task: &Task) -> Option<NaiveDate> {
+        let reviewed = self
+            .task_meta(task)
+            .and_then(|meta| meta.reviewed.as_ref())
+            .and_then(|date| NaiveDate::parse_from_str(date, REVIEWED_FORMAT).ok());
+        task.create_date.max(reviewed)
+    }
+
+    /// Gets the pending tasks without activity for the number of days in the order
+    /// of the file. Tasks without any activity are stale, scratch tasks are left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `days` - The number of days after which a task is stale.
+    pub fn stale_tasks(&self, days: usize) -> Vec<TaskId> {
+        let today = self.clock().today();
+        self.pending
+            .iter()
+            .enumerate()
+            .filter(|(_, task)| !is_scratch(task))
+            .filter(|(_, task)| {
+                self.last_activity(task)
+                    .is_none_or(|date| (today - date).num_days() >= days as i64)
+            })
+            .map(|(index, task)| TaskId::new(index, task))
+            .collect()
+    }
+
+    /// Applies the decision about the reviewed task and marks the task as reviewed today.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The identity of the pending task.
+    /// * `action` - The decision about the task.
+    ///
+    /// # Returns
+    ///
+    /// An error if the changed task cannot be parsed.
+    pub(super) fn review(
+        &mut self,
+        task: &TaskId,
+        action: ReviewAction,
+    ) -> Result<(), todo_txt::Error> {
+        let data = ToDoData::Pending;
+        let Some(index) = self.find_task(data, task) else {
+            log::warn!("Reviewed task {:?} does not exist", task);
+            return Ok(());
+        };
+        let task = task.clone();
+        match action {
+            ReviewAction::Keep => {}
+            ReviewAction::Priority(priority) => {
+                let mut edited = self.pending[index].clone();
+                edited.priority = priority
+                    .and_then(|priority| Priority::try_from(priority).ok())
+                    .unwrap_or_else(Priority::lowest);
+                let line = edited.to_string();
+                self.apply(Action::UpdateTask { data, task, line })?;
+            }
+            ReviewAction::Reschedule(due) => self.apply(Action::SetDue { data, task, due })?,
+            ReviewAction::Delete => return self.apply(Action::RemoveTask { data, task }),
+        }
+        // The task is edited in place, so it keeps its index.
+        let today = self.clock().today().format(REVIEWED_FORMAT).to_string();
+        self.update_meta(data, index, |meta| meta.reviewed = Some(today));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::Clock;
+
+    fn testing_todo() -> ToDo {
+        let mut todo = ToDo::default();
+        todo.config.clock = Clock::fixed(NaiveDate::from_ymd_opt(2023, 5, 31).unwrap());
+        for task in [
+            "2023-01-10 call mom",
+            "2023-05-20 buy milk",
+            "water plants",
+            "2023-02-01 write the report due:2023-06-30",
+        ] {
+            todo.copy_task(task).unwrap();
+        }
+        todo
+    }
+
+    fn subjects(todo: &ToDo, tasks: &[TaskId]) -> Vec<String> {
+        tasks
+            .iter()
+            .map(|id| {
+                todo.pending[todo.find_task(ToDoData::Pending, id).unwrap()]
+                    .subject
+                    .clone()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn stale_tasks() {
+        let todo = testing_todo();
+        assert_eq!(
+            subjects(&todo, &todo.stale_tasks(30)),
+            ["call mom", "water plants", "write the report"]
+        );
+        assert_eq!(
+            subjects(&todo, &todo.stale_tasks(130)),
+            ["call mom", "water plants"]
+        );
+        assert_eq!(todo.stale_tasks(0).len(), 4);
+
+        let mut review = Review::new(&todo, 30);
+        assert_eq!(review.position(), (1, 3));
+        review.next();
+        review.next();
+        review.next();
+        assert_eq!(review.current(), None);
+    }
+
+    #[test]
+    fn review() -> Result<(), todo_txt::Error> {
+        let mut todo = testing_todo();
+        let stale = todo.stale_tasks(30);
+        todo.apply(Action::ReviewTask {
+            task: stale[0].clone(),
+            action: ReviewAction::Keep,
+        })?;
+        todo.apply(Action::ReviewTask {
+            task: stale[1].clone(),
+            action: ReviewAction::Priority(Some('B')),
+        })?;
+        todo.apply(Action::ReviewTask {
+            task: stale[2].clone(),
+            action: ReviewAction::Delete,
+        })?;
+        assert_eq!(todo.pending.len(), 3);
+        assert_eq!(todo.pending[2].priority, Priority::try_from('B').unwrap());
+        assert_eq!(
+            todo.last_activity(&todo.pending[2]),
+            NaiveDate::from_ymd_opt(2023, 5, 31)
+        );
+        // Reviewed tasks are not stale anymore.
+        assert!(todo.stale_tasks(30).is_empty());
+
+        let task = TaskId::new(0, &todo.pending[0]);
+        todo.apply(Action::ReviewTask {
+            task: task.clone(),
+            action: ReviewAction::Reschedule(String::from("2023-06-05")),
+        })?;
+        assert_eq!(
+            todo.pending[0].due_date,
+            NaiveDate::from_ymd_opt(2023, 6, 5)
+        );
+        assert_eq!(
+            todo.last_activity(&todo.pending[0]),
+            NaiveDate::from_ymd_opt(2023, 5, 31)
+        );
+        Ok(())
+    }
+}
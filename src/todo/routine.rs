@@ -0,0 +1,143 @@
+use super::{ActivityKind, ToDo, ToDoData};
+use chrono::{Datelike, NaiveDate};
+use todo_txt::Task;
+
+/// The tag making a task a routine, e.g. `routine:daily`.
+pub const ROUTINE_TAG: &str = "routine";
+
+/// The interval of a routine task. A completed routine is reset to pending once
+/// the interval has passed instead of being archived, e.g. for habit checklists.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Routine {
+    /// The routine is reset the day after it was completed.
+    Daily,
+    /// The routine is reset on the Monday after it was completed.
+    Weekly,
+}
+
+impl Routine {
+    /// Gets the routine of the task given by the `routine:` tag, `None` if the task is not a routine.
+    pub fn of(task: &Task) -> Option<Self> {
+        match task.tags.get(ROUTINE_TAG)?.to_lowercase().as_str() {
+            "daily" => Some(Routine::Daily),
+            "weekly" => Some(Routine::Weekly),
+            _ => None,
+        }
+    }
+
+    /// Checks whether the routine completed on the date is pending again.
+    ///
+    /// # Arguments
+    ///
+    /// * `finished` - The completion date of the routine.
+    /// * `today` - The current date.
+    fn is_due(&self, finished: NaiveDate, today: NaiveDate) -> bool {
+        match self {
+            Routine::Daily => finished < today,
+            Routine::Weekly => finished < today && finished.iso_week() != today.iso_week(),
+        }
+    }
+}
+
+impl ToDo {
+    /// Checks whether the task is a completed routine whose interval has passed.
+    /// Routines without a completion date are pending again right away.
+    fn is_due_routine(task: &Task, today: NaiveDate) -> bool {
+        match (Routine::of(task), task.finish_date) {
+            (Some(routine), Some(finished)) => routine.is_due(finished, today),
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+
+    /// Gets the subjects of the routines moved back to pending by
+    /// [`super::Action::ResetRoutines`].
+    pub fn due_routines(&self) -> Vec<String> {
+        let today = self.clock().today();
+        self.done
+            .iter()
+            .filter(|task| Self::is_due_routine(task, today))
+            .map(|task| task.subject.clone())
+            .collect()
+    }
+
+    /// Moves completed routines back to pending once their interval has passed.
+    pub(super) fn reset_routines(&mut self) {
+        let today = self.clock().today();
+        let mut reset = false;
+        let mut index = 0;
+        while index < self.done.len() {
+            if !Self::is_due_routine(&self.done[index], today) {
+                index += 1;
+                continue;
+            }
+            let mut task = self.done.remove(index);
+            self.fix_active(ToDoData::Done, index);
+            task.uncomplete();
+            self.record(ActivityKind::Reopen, &task, None);
+            self.insert_task(ToDoData::Pending, task);
+            reset = true;
+        }
+        if reset {
+            self.version += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        clock::Clock,
+        todo::{parse_task, Action},
+    };
+
+    #[test]
+    fn routine() {
+        let task = |s: &str| parse_task(s).unwrap();
+        assert_eq!(
+            Routine::of(&task("stretch routine:daily")),
+            Some(Routine::Daily)
+        );
+        assert_eq!(
+            Routine::of(&task("water the plants routine:Weekly")),
+            Some(Routine::Weekly)
+        );
+        assert_eq!(Routine::of(&task("stretch routine:hourly")), None);
+        assert_eq!(Routine::of(&task("stretch")), None);
+
+        // 2023-05-03 is a Wednesday.
+        let date = |day| NaiveDate::from_ymd_opt(2023, 5, day).unwrap();
+        assert!(!Routine::Daily.is_due(date(3), date(3)));
+        assert!(Routine::Daily.is_due(date(3), date(4)));
+        assert!(!Routine::Weekly.is_due(date(3), date(7)));
+        assert!(Routine::Weekly.is_due(date(3), date(8)));
+    }
+
+    #[test]
+    fn reset_routines() {
+        let mut todo = ToDo::default();
+        for task in [
+            "x 2023-05-03 stretch routine:daily",
+            "x 2023-05-03 water the plants routine:weekly",
+            "x 2023-05-03 call mom",
+            "x read the news routine:daily",
+        ] {
+            todo.add_task(parse_task(task).unwrap());
+        }
+
+        let mut reset = |day| {
+            todo.config.clock = Clock::fixed(NaiveDate::from_ymd_opt(2023, 5, day).unwrap());
+            let routines = todo.due_routines();
+            todo.apply(Action::ResetRoutines).unwrap();
+            routines
+        };
+
+        assert_eq!(reset(3), vec![String::from("read the news")]);
+        assert_eq!(reset(4), vec![String::from("stretch")]);
+        assert!(reset(4).is_empty());
+        assert_eq!(todo.pending.len(), 2);
+        assert_eq!(todo.pending[1].to_string(), "stretch routine:daily");
+        assert_eq!(todo.done.len(), 2);
+    }
+}
@@ -0,0 +1,71 @@
+use super::ToDo;
+use todo_txt::Task;
+
+/// The tag marking a scratch task, e.g. `scratch:1`. Scratch tasks live only in memory
+/// for the current session, they are never written to the todo file or the archive.
+pub const SCRATCH_TAG: &str = "scratch";
+
+/// Checks whether the task is a scratch task.
+pub fn is_scratch(task: &Task) -> bool {
+    task.tags.contains_key(SCRATCH_TAG)
+}
+
+/// Makes a scratch task of the todo.txt line of a new task.
+///
+/// # Arguments
+///
+/// * `line` - The todo.txt line typed by the user.
+///
+/// # Returns
+///
+/// The line with the scratch tag, the line is not changed if it has the tag already.
+pub fn scratch_line(line: &str) -> String {
+    let prefix = format!("{SCRATCH_TAG}:");
+    match line
+        .split_whitespace()
+        .any(|word| word.starts_with(&prefix))
+    {
+        true => line.to_string(),
+        false => format!("{} {SCRATCH_TAG}:1", line.trim_end()),
+    }
+}
+
+impl ToDo {
+    /// Moves the scratch tasks of this todo list to the other one, so they are kept
+    /// when the data is replaced by the tasks loaded from the file.
+    pub(super) fn keep_scratch(&mut self, other: &mut ToDo) {
+        other
+            .pending
+            .extend(self.pending.drain(..).filter(is_scratch));
+        other.done.extend(self.done.drain(..).filter(is_scratch));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scratch_tasks_survive_reload() -> Result<(), todo_txt::Error> {
+        assert_eq!(scratch_line("call mom "), "call mom scratch:1");
+        assert_eq!(scratch_line("call mom scratch:x"), "call mom scratch:x");
+
+        let mut todo = ToDo::default();
+        todo.new_task("write the report")?;
+        todo.new_task(&scratch_line("stretch"))?;
+        assert!(is_scratch(&todo.pending[1]));
+        assert_eq!(todo.pending[1].subject, "stretch");
+
+        let mut loaded = ToDo::default();
+        loaded.new_task("write the report")?;
+        loaded.new_task("buy milk")?;
+        todo.move_data(loaded);
+        let subjects: Vec<&str> = todo
+            .pending
+            .iter()
+            .map(|task| task.subject.as_str())
+            .collect();
+        assert_eq!(subjects, ["write the report", "buy milk", "stretch"]);
+        Ok(())
+    }
+}
@@ -0,0 +1,220 @@
+use super::ToDo;
+use todo_txt::Task;
+use tui::{style::Style, text::Span};
+
+/// Finds the characters of the pattern in the text in the same order, other characters
+/// may be between them, e.g. `wrrep` matches `write the report`. The case is ignored.
+///
+/// # Returns
+///
+/// The indexes of the matched characters of the text or `None` if the text does not match.
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<Vec<usize>> {
+    let mut pattern = pattern.chars().flat_map(char::to_lowercase).peekable();
+    let mut matched = Vec::new();
+    for (index, c) in text.chars().enumerate() {
+        let Some(next) = pattern.peek() else {
+            break;
+        };
+        if c.to_lowercase().eq(std::iter::once(*next)) {
+            matched.push(index);
+            pattern.next();
+        }
+    }
+    pattern.peek().is_none().then_some(matched)
+}
+
+/// Finds the occurrences of the words in the text. The case is ignored.
+///
+/// # Returns
+///
+/// The ascending indexes of the characters of the text inside an occurrence of any word.
+pub fn word_matches(words: &[&str], text: &str) -> Vec<usize> {
+    let lower = |c: char| c.to_lowercase().next().unwrap_or(c);
+    let text: Vec<char> = text.chars().map(lower).collect();
+    let mut matched = vec![false; text.len()];
+    for word in words {
+        let word: Vec<char> = word.chars().map(lower).collect();
+        if word.is_empty() || word.len() > text.len() {
+            continue;
+        }
+        for start in 0..=text.len() - word.len() {
+            if text[start..start + word.len()] == word[..] {
+                matched[start..start + word.len()].fill(true);
+            }
+        }
+    }
+    (0..text.len()).filter(|&index| matched[index]).collect()
+}
+
+/// Highlights the characters of the rendered text matching the search.
+///
+/// # Arguments
+///
+/// * `spans` - The rendered text.
+/// * `search` - The searched pattern, see [`fuzzy_match`].
+/// * `style` - The style patched into the style of matched characters.
+pub fn highlight<'a>(spans: Vec<Span<'a>>, search: &str, style: Style) -> Vec<Span<'a>> {
+    let text: String = spans.iter().map(|span| span.content.as_ref()).collect();
+    match fuzzy_match(search, &text) {
+        Some(matched) => highlight_chars(spans, matched, style),
+        None => spans,
+    }
+}
+
+/// Highlights the occurrences of the words in the rendered text, see [`word_matches`].
+///
+/// # Arguments
+///
+/// * `spans` - The rendered text.
+/// * `words` - The words to highlight, e.g. the text terms of a query.
+/// * `style` - The style patched into the style of matched characters.
+pub fn highlight_words<'a>(spans: Vec<Span<'a>>, words: &[&str], style: Style) -> Vec<Span<'a>> {
+    if words.is_empty() {
+        return spans;
+    }
+    let text: String = spans.iter().map(|span| span.content.as_ref()).collect();
+    highlight_chars(spans, word_matches(words, &text), style)
+}
+
+/// Splits the spans so the matched characters get the style patched into their own.
+///
+/// # Arguments
+///
+/// * `spans` - The rendered text.
+/// * `matched` - The ascending indexes of the matched characters of the text.
+/// * `style` - The style patched into the style of matched characters.
+fn highlight_chars<'a>(spans: Vec<Span<'a>>, matched: Vec<usize>, style: Style) -> Vec<Span<'a>> {
+    let mut matched = matched.into_iter().peekable();
+    let mut highlighted = Vec::new();
+    let mut index = 0;
+    for span in spans {
+        if matched
+            .peek()
+            .is_none_or(|&next| next >= index + span.content.chars().count())
+        {
+            index += span.content.chars().count();
+            highlighted.push(span);
+            continue;
+        }
+        let mut part = String::new();
+        let mut part_matched = false;
+        for c in span.content.chars() {
+            let is_match = matched.next_if_eq(&index).is_some();
+            if is_match != part_matched && !part.is_empty() {
+                let part_style = match part_matched {
+                    true => span.style.patch(style),
+                    false => span.style,
+                };
+                highlighted.push(Span::styled(std::mem::take(&mut part), part_style));
+            }
+            part_matched = is_match;
+            part.push(c);
+            index += 1;
+        }
+        let part_style = match part_matched {
+            true => span.style.patch(style),
+            false => span.style,
+        };
+        highlighted.push(Span::styled(part, part_style));
+    }
+    highlighted
+}
+
+impl ToDo {
+    /// Gets the text the subjects of shown tasks are searched for, `None` if nothing is searched.
+    pub fn search(&self) -> Option<&str> {
+        self.search.as_deref()
+    }
+
+    /// Sets the text the subjects of shown tasks are searched for, an empty text ends the search.
+    pub(super) fn set_search(&mut self, search: Option<String>) {
+        self.search = search.filter(|search| !search.is_empty());
+    }
+
+    /// Checks whether the subject of the task matches the search, every task matches without it.
+    pub(super) fn matches_search(&self, task: &Task) -> bool {
+        self.search
+            .as_ref()
+            .is_none_or(|search| fuzzy_match(search, &task.subject).is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::todo::{Action, ToDoData};
+    use std::str::FromStr;
+    use tui::style::{Color, Modifier};
+
+    #[test]
+    fn fuzzy() {
+        assert_eq!(
+            fuzzy_match("wrrep", "Write the report"),
+            Some(vec![0, 1, 10, 11, 12])
+        );
+        assert_eq!(fuzzy_match("", "write"), Some(vec![]));
+        assert_eq!(fuzzy_match("tw", "write the report"), None);
+        assert_eq!(fuzzy_match("write!", "write"), None);
+    }
+
+    #[test]
+    fn highlight_spans() {
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let red = Style::default().fg(Color::Red);
+        let spans = vec![Span::raw("call "), Span::styled("+mom", red)];
+        assert_eq!(
+            highlight(spans.clone(), "cam", bold),
+            vec![
+                Span::styled("ca", bold),
+                Span::raw("ll "),
+                Span::styled("+", red),
+                Span::styled("m", red.patch(bold)),
+                Span::styled("om", red),
+            ]
+        );
+        assert_eq!(highlight(spans.clone(), "xyz", bold), spans);
+    }
+
+    #[test]
+    fn highlight_query_words() {
+        assert_eq!(
+            word_matches(&["Te", "port"], "Write the report"),
+            vec![3, 4, 12, 13, 14, 15]
+        );
+        assert_eq!(word_matches(&["reports"], "report"), Vec::<usize>::new());
+
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let red = Style::default().fg(Color::Red);
+        let spans = vec![Span::raw("call "), Span::styled("+mom", red)];
+        assert_eq!(
+            highlight_words(spans.clone(), &["l +m", "mom"], bold),
+            vec![
+                Span::raw("cal"),
+                Span::styled("l ", bold),
+                Span::styled("+mom", red.patch(bold)),
+            ]
+        );
+        assert_eq!(highlight_words(spans.clone(), &[], bold), spans);
+    }
+
+    #[test]
+    fn search() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        for task in ["write the report", "call mom", "x water plants"] {
+            todo.add_task(Task::from_str(task)?);
+        }
+        todo.apply(Action::SetSearch {
+            search: Some(String::from("wrt")),
+        })?;
+        assert_eq!(todo.get_filtered_and_sorted(ToDoData::Pending).len(), 1);
+        assert_eq!(todo.get_filtered_and_sorted(ToDoData::Done).len(), 1);
+        assert_eq!(todo.hidden(ToDoData::Pending), 1);
+
+        todo.apply(Action::SetSearch {
+            search: Some(String::new()),
+        })?;
+        assert_eq!(todo.search(), None);
+        assert_eq!(todo.get_filtered_and_sorted(ToDoData::Pending).len(), 2);
+        Ok(())
+    }
+}
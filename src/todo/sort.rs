@@ -0,0 +1,135 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use todo_txt::Task;
+
+/// Represents the possible sorting options for tasks.
+#[derive(Clone, Copy, Serialize, Deserialize, Default, ValueEnum, PartialEq, Eq, Debug)]
+pub enum TaskSort {
+    #[default]
+    None,
+    Reverse,
+    Priority,
+    PriorityReverse,
+    Alphanumeric,
+    AlphanumericReverse,
+    Due,
+    DueReverse,
+    Project,
+    ProjectReverse,
+    Completed,
+    CompletedReverse,
+    Created,
+    CreatedReverse,
+    Urgency,
+    UrgencyReverse,
+}
+
+impl TaskSort {
+    /// Gets the name of the sorting as it is written on the command line, e.g. `due`.
+    pub fn name(&self) -> String {
+        self.to_possible_value()
+            .map(|value| value.get_name().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Gets the sorting following this one in the cycle. Sorting that is not
+    /// in the cycle is followed by the first sorting of the cycle.
+    ///
+    /// # Arguments
+    ///
+    /// * `cycle` - The sorting options in the order they are cycled through.
+    pub fn next_in(&self, cycle: &[TaskSort]) -> TaskSort {
+        let next = match cycle.iter().position(|sort| sort == self) {
+            Some(index) => index + 1,
+            Option::None => 0,
+        };
+        cycle
+            .get(next % cycle.len().max(1))
+            .copied()
+            .unwrap_or(*self)
+    }
+
+    /// Compares two tasks by the sorting criteria. Sorting that does not compare
+    /// tasks (`None` and `Reverse`) considers all tasks equal, so does sorting by
+    /// the urgency, which is sorted by the scores of the tasks, see [`UrgencyCache`].
+    ///
+    /// [`UrgencyCache`]: super::urgency::UrgencyCache
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The first task to compare.
+    /// * `b` - The second task to compare.
+    pub fn compare(&self, a: &Task, b: &Task) -> Ordering {
+        use TaskSort::*;
+        match self {
+            None | Reverse | Urgency | UrgencyReverse => Ordering::Equal,
+            Priority => b.priority.cmp(&a.priority),
+            PriorityReverse => a.priority.cmp(&b.priority),
+            Alphanumeric => a.subject.cmp(&b.subject),
+            AlphanumericReverse => b.subject.cmp(&a.subject),
+            Due => Self::compare_missing_last(a.due_date, b.due_date, false),
+            DueReverse => Self::compare_missing_last(a.due_date, b.due_date, true),
+            Project => {
+                Self::compare_missing_last(a.projects().first(), b.projects().first(), false)
+            }
+            ProjectReverse => {
+                Self::compare_missing_last(a.projects().first(), b.projects().first(), true)
+            }
+            Completed => Self::compare_missing_last(a.finish_date, b.finish_date, false),
+            CompletedReverse => Self::compare_missing_last(a.finish_date, b.finish_date, true),
+            Created => Self::compare_missing_last(a.create_date, b.create_date, false),
+            CreatedReverse => Self::compare_missing_last(a.create_date, b.create_date, true),
+        }
+    }
+
+    /// Compares two tasks by the sorting criteria, tasks that are equal are
+    /// compared by the tie-breaking criteria in their order.
+    ///
+    /// # Arguments
+    ///
+    /// * `ties` - The sorting criteria used to break ties.
+    /// * `a` - The first task to compare.
+    /// * `b` - The second task to compare.
+    pub fn compare_with_ties(&self, ties: &[TaskSort], a: &Task, b: &Task) -> Ordering {
+        if matches!(self, TaskSort::None | TaskSort::Reverse) {
+            return Ordering::Equal;
+        }
+        ties.iter().fold(self.compare(a, b), |ordering, sort| {
+            ordering.then_with(|| sort.compare(a, b))
+        })
+    }
+
+    /// Compares keys that may be missing. Missing keys are always greater,
+    /// so tasks without the key are placed at the end of the list.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The key of the first task.
+    /// * `b` - The key of the second task.
+    /// * `reverse` - Compare present keys in descending order.
+    fn compare_missing_last<K: Ord>(a: Option<K>, b: Option<K>, reverse: bool) -> Ordering {
+        match (a, b) {
+            (Some(a), Some(b)) if reverse => b.cmp(&a),
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), Option::None) => Ordering::Less,
+            (Option::None, Some(_)) => Ordering::Greater,
+            (Option::None, Option::None) => Ordering::Equal,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sort_cycle() {
+        let cycle = [TaskSort::None, TaskSort::Priority, TaskSort::Due];
+        assert_eq!(TaskSort::None.next_in(&cycle), TaskSort::Priority);
+        assert_eq!(TaskSort::Due.next_in(&cycle), TaskSort::None);
+        assert_eq!(TaskSort::CompletedReverse.next_in(&cycle), TaskSort::None);
+        assert_eq!(TaskSort::Due.next_in(&[]), TaskSort::Due);
+        assert_eq!(TaskSort::DueReverse.name(), "due-reverse");
+    }
+}
@@ -1,6 +1,5 @@
-use crate::config::Styles;
-use clap::ValueEnum;
-use serde::{Deserialize, Serialize};
+use super::TaskSort;
+use crate::style::Styles;
 use std::convert::From;
 use std::ops::Index;
 use todo_txt::Task;
@@ -10,18 +9,6 @@ use tui::widgets::ListItem;
 
 type Item<'a> = (usize, &'a Task);
 
-/// Represents the possible sorting options for tasks.
-#[derive(Clone, Copy, Serialize, Deserialize, Default, ValueEnum)]
-#[cfg_attr(test, derive(PartialEq, Debug))]
-pub enum TaskSort {
-    #[default]
-    None,
-    Reverse,
-    Priority,
-    Alphanumeric,
-    AlphanumericReverse,
-}
-
 /// Represents a list of tasks, where each task is a tuple of `(usize, &'a Task)`.
 /// The `usize` value is the index of the task in the original list.
 pub struct TaskList<'a> {
@@ -86,19 +73,23 @@ impl<'a> TaskList<'a> {
     ///
     /// * `sort` - The sorting criteria to apply.
     pub fn sort(&mut self, sort: TaskSort) {
-        use TaskSort::*;
+        self.sort_with_ties(sort, &[])
+    }
+
+    /// Sorts the task list based on the specified sorting criteria, ties are broken
+    /// by the tie-breaking criteria. Tasks that are still equal keep their order.
+    ///
+    /// # Arguments
+    ///
+    /// * `sort` - The sorting criteria to apply.
+    /// * `ties` - The sorting criteria used to break ties.
+    pub fn sort_with_ties(&mut self, sort: TaskSort, ties: &[TaskSort]) {
         match sort {
-            None => {}
-            Reverse => self.vec.reverse(),
-            Priority => self
-                .vec
-                .sort_by(|(_, a_task), (_, b_task)| b_task.priority.cmp(&a_task.priority)),
-            Alphanumeric => self
-                .vec
-                .sort_by(|(_, a_task), (_, b_task)| a_task.subject.cmp(&b_task.subject)),
-            AlphanumericReverse => self
+            TaskSort::None => {}
+            TaskSort::Reverse => self.vec.reverse(),
+            sort => self
                 .vec
-                .sort_by(|(_, a_task), (_, b_task)| b_task.subject.cmp(&a_task.subject)),
+                .sort_by(|(_, a_task), (_, b_task)| sort.compare_with_ties(ties, a_task, b_task)),
         }
     }
 
@@ -116,12 +107,11 @@ impl<'a> TaskList<'a> {
 
         let mut collect_indexes = |separator, iter: core::slice::Iter<'_, String>| {
             iter.for_each(|project| {
-                indexes.push((
-                    task.subject
-                        .find(&(String::from(separator) + project))
-                        .unwrap(),
-                    project.len() + 1,
-                ));
+                // Categories are parsed from the subject, but skip any that cannot be found
+                // rather than failing to render the task.
+                if let Some(index) = task.subject.find(&(String::from(separator) + project)) {
+                    indexes.push((index, project.len() + 1));
+                }
             });
         };
 
@@ -293,4 +283,76 @@ mod tests {
             alpha_reverse,
         );
     }
+
+    #[test]
+    fn sort_missing_last() {
+        let styles = Styles::default();
+        let task1 = Task::from_str("task 1 due:2023-06-30").unwrap();
+        let task2 = Task::from_str("task 2 +project").unwrap();
+        let task3 = Task::from_str("task 3 due:2023-05-30 +other").unwrap();
+        let new = || TaskList {
+            vec: vec![(0, &task1), (1, &task2), (2, &task3)],
+            styles: &styles,
+        };
+        let indexes = |list: TaskList| list.vec.iter().map(|(i, _)| *i).collect::<Vec<_>>();
+
+        let mut due = new();
+        due.sort(TaskSort::Due);
+        assert_eq!(indexes(due), vec![2, 0, 1]);
+
+        let mut due_reverse = new();
+        due_reverse.sort(TaskSort::DueReverse);
+        assert_eq!(indexes(due_reverse), vec![0, 2, 1]);
+
+        let mut project = new();
+        project.sort(TaskSort::Project);
+        assert_eq!(indexes(project), vec![2, 1, 0]);
+    }
+
+    #[test]
+    fn sort_completed() {
+        let styles = Styles::default();
+        let task1 = Task::from_str("x 2023-05-02 2023-04-01 task 1").unwrap();
+        let task2 = Task::from_str("x task 2").unwrap();
+        let task3 = Task::from_str("x 2023-06-10 2023-04-01 task 3").unwrap();
+        let new = || TaskList {
+            vec: vec![(0, &task1), (1, &task2), (2, &task3)],
+            styles: &styles,
+        };
+        let indexes = |list: TaskList| list.vec.iter().map(|(i, _)| *i).collect::<Vec<_>>();
+
+        let mut completed = new();
+        completed.sort(TaskSort::Completed);
+        assert_eq!(indexes(completed), vec![0, 2, 1]);
+
+        let mut newest = new();
+        newest.sort(TaskSort::CompletedReverse);
+        assert_eq!(indexes(newest), vec![2, 0, 1]);
+    }
+
+    #[test]
+    fn sort_with_ties() {
+        let styles = Styles::default();
+        let task1 = Task::from_str("2023-04-03 task 1 due:2023-05-01").unwrap();
+        let task2 = Task::from_str("(B) 2023-04-02 task 2 due:2023-05-01").unwrap();
+        let task3 = Task::from_str("(B) 2023-04-01 task 3 due:2023-05-01").unwrap();
+        let task4 = Task::from_str("(A) task 4").unwrap();
+        let new = || TaskList {
+            vec: vec![(0, &task1), (1, &task2), (2, &task3), (3, &task4)],
+            styles: &styles,
+        };
+        let indexes = |list: TaskList| list.vec.iter().map(|(i, _)| *i).collect::<Vec<_>>();
+
+        let mut due = new();
+        due.sort(TaskSort::Due);
+        assert_eq!(indexes(due), vec![0, 1, 2, 3]);
+
+        let mut ties = new();
+        ties.sort_with_ties(TaskSort::Due, &[TaskSort::Priority, TaskSort::Created]);
+        assert_eq!(indexes(ties), vec![2, 1, 0, 3]);
+
+        let mut unsorted = new();
+        unsorted.sort_with_ties(TaskSort::None, &[TaskSort::Priority]);
+        assert_eq!(indexes(unsorted), vec![0, 1, 2, 3]);
+    }
 }
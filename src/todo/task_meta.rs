@@ -0,0 +1,221 @@
+use super::{Change, ToDo, ToDoData};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    error::Error,
+    fs,
+    io::ErrorKind,
+    path::Path,
+};
+use todo_txt::{Priority, Task};
+
+/// Metadata of a task used only by the user interface, e.g. whether the task is pinned.
+///
+/// The metadata is stored in a sidecar file next to the todo file, so it does not
+/// add tags to the todo.txt lines that other tools would show.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaskMeta {
+    #[serde(skip_serializing_if = "is_false")]
+    pub pinned: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    pub marked: bool,
+    #[serde(skip_serializing_if = "is_false")]
+    pub collapsed: bool,
+    /// Times the user was notified about the task, e.g. `2023-05-03 09:00`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub notified: Vec<String>,
+    /// The date of the last review of the stale task, e.g. `2023-05-03`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reviewed: Option<String>,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+/// Gets the key the metadata of the task is stored under.
+///
+/// The key is the todo.txt line without the completion and the priority,
+/// so the metadata stays with the task when it is completed or reprioritized.
+pub fn meta_key(task: &Task) -> String {
+    let mut task = task.clone();
+    task.finished = false;
+    task.finish_date = None;
+    task.priority = Priority::lowest();
+    task.to_string()
+}
+
+/// The metadata of tasks stored by their keys, see [`meta_key`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetaStore {
+    tasks: BTreeMap<String, TaskMeta>,
+}
+
+impl MetaStore {
+    /// Loads the metadata from the file. A missing file has no metadata.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the metadata file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(toml::from_str(&content)?),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Gets the metadata of the task, `None` if the task has no metadata.
+    pub fn get(&self, task: &Task) -> Option<&TaskMeta> {
+        self.tasks.get(&meta_key(task))
+    }
+
+    /// Changes the metadata of the task, metadata left with default values is removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The task the metadata belongs to.
+    /// * `update` - The function changing the metadata.
+    pub fn update(&mut self, task: &Task, update: impl FnOnce(&mut TaskMeta)) {
+        let key = meta_key(task);
+        let meta = self.tasks.entry(key.clone()).or_default();
+        update(meta);
+        if *meta == TaskMeta::default() {
+            self.tasks.remove(&key);
+        }
+    }
+
+    /// Moves the metadata of the task to its edited version.
+    fn rename(&mut self, previous: &Task, task: &Task) {
+        if let Some(meta) = self.tasks.remove(&meta_key(previous)) {
+            self.tasks.insert(meta_key(task), meta);
+        }
+    }
+}
+
+impl ToDo {
+    /// Replaces the metadata of tasks loaded from the metadata file.
+    pub fn set_meta(&mut self, meta: MetaStore) {
+        self.meta = meta;
+        self.notify(Change::Meta);
+    }
+
+    /// Gets the metadata of the task, `None` if the task has no metadata.
+    pub fn task_meta(&self, task: &Task) -> Option<&TaskMeta> {
+        self.meta.get(task)
+    }
+
+    /// Changes the metadata of the task. The todo list itself is not changed,
+    /// so its version stays the same and the todo file is not saved.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data (Pending or Done).
+    /// * `index` - The index of the task in the unfiltered list.
+    /// * `update` - The function changing the metadata.
+    pub fn update_meta(
+        &mut self,
+        data: ToDoData,
+        index: usize,
+        update: impl FnOnce(&mut TaskMeta),
+    ) {
+        let Some(task) = data.get_data(self).get(index) else {
+            return;
+        };
+        let task = task.clone();
+        self.meta.update(&task, update);
+        self.notify(Change::Meta);
+    }
+
+    /// Moves the metadata of the edited task to its new version.
+    pub(super) fn rename_meta(&mut self, previous: &Task, task: &Task) {
+        self.meta.rename(previous, task);
+    }
+
+    /// Writes the metadata to the file. Metadata of tasks that are not in the todo list
+    /// anymore is left out, the file is not created when there is no metadata.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the metadata file.
+    pub fn save_meta(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn Error>> {
+        let keys: BTreeSet<String> = self
+            .pending
+            .iter()
+            .chain(&self.done)
+            .map(meta_key)
+            .collect();
+        let store = MetaStore {
+            tasks: self
+                .meta
+                .tasks
+                .iter()
+                .filter(|(key, _)| keys.contains(*key))
+                .map(|(key, meta)| (key.clone(), meta.clone()))
+                .collect(),
+        };
+        if store.tasks.is_empty() && !path.as_ref().exists() {
+            return Ok(());
+        }
+        fs::write(path, toml::to_string_pretty(&store)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::todo::{parse_task, Action};
+    use std::{env::temp_dir, process};
+
+    #[test]
+    fn task_meta() -> Result<(), Box<dyn Error>> {
+        let mut todo = ToDo::default();
+        todo.new_task("(A) write the report +work")?;
+        todo.new_task("call mom")?;
+        let changes = todo.subscribe();
+        let version = todo.get_version();
+        todo.update_meta(ToDoData::Pending, 0, |meta| meta.pinned = true);
+        assert_eq!(changes.try_recv(), Ok(Change::Meta));
+        assert_eq!(todo.get_version(), version);
+
+        // The metadata follows the task when it is completed and edited.
+        let task = todo.task_id(ToDoData::Pending, 0).unwrap();
+        todo.apply(Action::CompleteTask {
+            data: ToDoData::Pending,
+            task,
+        })?;
+        assert!(todo.task_meta(&todo.done[0]).unwrap().pinned);
+        let task = todo.task_id(ToDoData::Done, 0).unwrap();
+        todo.apply(Action::SetActive {
+            data: ToDoData::Done,
+            task,
+        })?;
+        todo.apply(Action::UpdateActive {
+            task: String::from("x write the final report +work"),
+        })?;
+        assert!(todo.task_meta(&todo.done[0]).unwrap().pinned);
+        assert!(todo.task_meta(&todo.pending[0]).is_none());
+
+        let path = temp_dir().join(format!("todotxt-tui-task-meta-{}.toml", process::id()));
+        todo.update_meta(ToDoData::Pending, 0, |meta| meta.marked = true);
+        todo.update_meta(ToDoData::Pending, 0, |meta| meta.marked = false);
+        todo.save_meta(&path)?;
+        let loaded = MetaStore::load(&path)?;
+        assert_eq!(loaded.tasks.len(), 1);
+        assert!(loaded.get(&todo.done[0]).unwrap().pinned);
+
+        todo.remove_task(ToDoData::Done, 0);
+        todo.save_meta(&path)?;
+        assert_eq!(MetaStore::load(&path)?, MetaStore::default());
+        fs::remove_file(&path)?;
+        todo.save_meta(&path)?;
+        assert!(!path.exists());
+        assert_eq!(
+            meta_key(&parse_task("x 2023-05-03 call mom")?),
+            meta_key(&parse_task("(B) call mom")?)
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,374 @@
+use super::{ContextHours, DateExpr, FilterState, ToDoCategory};
+use crate::error::{ToDoError, ToDoRes};
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use std::{fmt, str::FromStr};
+use todo_txt::Task;
+
+/// A date of a task a query term can select a range of.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DateField {
+    /// The due date, `due:`.
+    Due,
+    /// The threshold date, `t:`.
+    Threshold,
+    /// The creation date.
+    Created,
+    /// The completion date.
+    Done,
+}
+
+impl DateField {
+    /// Gets the field by the key of the term, e.g. `created` of `created:2023-01-01..`.
+    fn from_key(key: &str) -> Option<Self> {
+        Some(match key.to_ascii_lowercase().as_str() {
+            "due" => Self::Due,
+            "t" => Self::Threshold,
+            "created" => Self::Created,
+            "done" => Self::Done,
+            _ => return None,
+        })
+    }
+
+    /// Gets the date of the task.
+    fn date(&self, task: &Task) -> Option<NaiveDate> {
+        match self {
+            Self::Due => task.due_date,
+            Self::Threshold => task.threshold_date,
+            Self::Created => task.create_date,
+            Self::Done => task.finish_date,
+        }
+    }
+}
+
+/// A single condition of a [`TaskQuery`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Term {
+    /// The task has the category, e.g. `+project`.
+    Category(ToDoCategory, String),
+    /// The task is due in less than the number of days, overdue tasks included.
+    DueWithin(i64),
+    /// The task has a priority from the first to the last one, 0 is the priority A.
+    Priority(u8, u8),
+    /// The date of the task is between both dates, both included. A missing bound
+    /// leaves the range open, the expressions are resolved on the day of the match.
+    DateRange(DateField, Option<DateExpr>, Option<DateExpr>),
+    /// The task is due today or overdue and within the working hours of its contexts.
+    Today,
+    /// The subject of the task contains the text, the case is ignored.
+    Text(String),
+}
+
+/// Represents a query selecting tasks shown by a task list widget.
+///
+/// The query is a list of terms separated by whitespace, a task is selected
+/// if it matches all of them. Supported terms are:
+///
+/// - `+project`, `@context` and `#hashtag`: The task has the category.
+/// - `due<N` or `<due:N`: The task is due in less than N days, overdue tasks included.
+///   The number may be followed by a unit, e.g. `2w` is two weeks, see [`days`].
+/// - `!A`: The task has the priority, `!A-C` one of the priorities from A to C.
+/// - `due:FROM..TO`: The due date is between both dates, both included, e.g.
+///   `due:today..1w`. A date can be left out, e.g. `due:..today`, and a single date
+///   selects the day. The dates are [`DateExpr`] expressions, a signed amount such as
+///   `-2w` is counted from today. `t:`, `created:` and `done:` select the threshold date,
+///   the creation date and the completion date.
+/// - `today`: The task is due today or overdue and can be acted on now, tasks outside
+///   the working hours of their contexts are left out.
+/// - Any other word: The subject of the task contains the word.
+///
+/// A term prefixed with `-` selects tasks that do not match it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TaskQuery {
+    query: String,
+    terms: Vec<(bool, Term)>,
+}
+
+impl TaskQuery {
+    /// Checks whether the task matches all terms of the query.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The task to check.
+    /// * `now` - The moment relative due dates and working hours are computed from.
+    /// * `hours` - The working hours of contexts.
+    pub fn matches(&self, task: &Task, now: NaiveDateTime, hours: &ContextHours) -> bool {
+        let today = now.date();
+        self.terms.iter().all(|(negated, term)| {
+            let matches = match term {
+                Term::Category(category, name) => category.get_data(task).contains(name),
+                Term::DueWithin(days) => task
+                    .due_date
+                    .is_some_and(|due| due < today + Duration::days(*days)),
+                Term::Priority(first, last) => {
+                    (*first..=*last).contains(&u8::from(task.priority.clone()))
+                }
+                Term::DateRange(field, from, to) => field.date(task).is_some_and(|date| {
+                    let resolve = |expr: &Option<DateExpr>| {
+                        expr.and_then(|expr| expr.resolve(Option::None, today))
+                    };
+                    resolve(from).is_none_or(|from| from <= date)
+                        && resolve(to).is_none_or(|to| date <= to)
+                }),
+                Term::Today => {
+                    task.due_date.is_some_and(|due| due <= today) && !hours.is_off_hours(task, now)
+                }
+                Term::Text(text) => task.subject.to_lowercase().contains(text),
+            };
+            matches != *negated
+        })
+    }
+
+    /// Gets the words the subject of a selected task contains, the terms of negated words
+    /// and other terms are left out.
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        self.terms.iter().filter_map(|(negated, term)| match term {
+            Term::Text(text) if !negated => Some(text.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Gets the category terms of the query as filters, a negated term removes the category.
+    /// Other terms cannot be expressed by filters and are left out.
+    pub fn filters(&self) -> Vec<(ToDoCategory, String, FilterState)> {
+        self.terms
+            .iter()
+            .filter_map(|(negated, term)| match term {
+                Term::Category(category, name) => Some((
+                    *category,
+                    name.clone(),
+                    match negated {
+                        true => FilterState::Remove,
+                        false => FilterState::Select,
+                    },
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Gets the number of days of the period, e.g. `3` or `3d` is three days, `2w` two weeks,
+/// `1m` a month of 30 days and `1y` a year of 365 days.
+fn days(period: &str) -> Option<i64> {
+    let (number, unit) = match period.find(|c: char| !c.is_ascii_digit()) {
+        Some(index) => period.split_at(index),
+        None => (period, "d"),
+    };
+    let days = match unit.to_ascii_lowercase().as_str() {
+        "d" => 1,
+        "w" => 7,
+        "m" => 30,
+        "y" => 365,
+        _ => return None,
+    };
+    Some(number.parse::<i64>().ok()? * days)
+}
+
+/// Gets the range of priorities of a term like `A` or `A-C`, 0 is the priority A.
+fn priority(range: &str) -> Option<(u8, u8)> {
+    let letter = |letter: &str| match letter.as_bytes() {
+        [letter] if letter.is_ascii_alphabetic() => Some(letter.to_ascii_uppercase() - b'A'),
+        _ => Option::None,
+    };
+    match range.split_once('-') {
+        Some((first, last)) => {
+            let (first, last) = (letter(first)?, letter(last)?);
+            Some((first.min(last), first.max(last)))
+        }
+        Option::None => letter(range).map(|priority| (priority, priority)),
+    }
+}
+
+/// A range of dates with optional bounds.
+type DateBounds = (Option<DateExpr>, Option<DateExpr>);
+
+/// Parses a term like `due:today..1w` selecting a range of the date of a task.
+///
+/// # Returns
+///
+/// `None` if the word is not a date term, otherwise the field with the range,
+/// which is `None` if a date of the range is invalid.
+fn date_range(word: &str) -> Option<(DateField, Option<DateBounds>)> {
+    let (key, range) = word.split_once(':')?;
+    let field = DateField::from_key(key)?;
+    let date = |expr: &str| match expr {
+        "" => Some(Option::None),
+        expr => expr.parse::<DateExpr>().ok().map(Some),
+    };
+    let bounds = match range.split_once("..") {
+        Some((from, to)) => date(from).zip(date(to)),
+        Option::None if !range.is_empty() => date(range).map(|date| (date, date)),
+        Option::None => Option::None,
+    };
+    Some((field, bounds))
+}
+
+impl FromStr for TaskQuery {
+    type Err = ToDoError;
+
+    fn from_str(s: &str) -> ToDoRes<Self> {
+        let terms = s
+            .split_whitespace()
+            .map(|word| {
+                let (negated, word) = match word.strip_prefix('-') {
+                    Some(word) if !word.is_empty() => (true, word),
+                    _ => (false, word),
+                };
+                let mut chars = word.chars();
+                let term = match (chars.next(), chars.as_str()) {
+                    (Some('+'), name) if !name.is_empty() => {
+                        Term::Category(ToDoCategory::Projects, name.to_string())
+                    }
+                    (Some('@'), name) if !name.is_empty() => {
+                        Term::Category(ToDoCategory::Contexts, name.to_string())
+                    }
+                    (Some('#'), name) if !name.is_empty() => {
+                        Term::Category(ToDoCategory::Hashtags, name.to_string())
+                    }
+                    (Some('!'), name) if priority(name).is_some() => {
+                        let (first, last) = priority(name).unwrap();
+                        Term::Priority(first, last)
+                    }
+                    _ if word.eq_ignore_ascii_case("today") => Term::Today,
+                    _ if date_range(word).is_some() => {
+                        let (field, range) = date_range(word).unwrap();
+                        let (from, to) =
+                            range.ok_or_else(|| ToDoError::ParseQuery(s.to_string()))?;
+                        Term::DateRange(field, from, to)
+                    }
+                    _ => match word
+                        .strip_prefix("due<")
+                        .or_else(|| word.strip_prefix("<due:"))
+                    {
+                        Some(within) => Term::DueWithin(
+                            days(within).ok_or_else(|| ToDoError::ParseQuery(s.to_string()))?,
+                        ),
+                        None => Term::Text(word.to_lowercase()),
+                    },
+                };
+                Ok((negated, term))
+            })
+            .collect::<ToDoRes<_>>()?;
+        Ok(Self {
+            query: s.trim().to_string(),
+            terms,
+        })
+    }
+}
+
+impl fmt::Display for TaskQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use std::collections::HashMap;
+
+    #[test]
+    fn matches() -> ToDoRes<()> {
+        // 2023-05-01 is a Monday.
+        let now = NaiveDate::from_ymd_opt(2023, 5, 1)
+            .unwrap()
+            .and_hms_opt(20, 0, 0)
+            .unwrap();
+        let hours = ContextHours::default();
+        let task = |s: &str| Task::from_str(s).unwrap();
+        let week = TaskQuery::from_str("due<7")?;
+        assert!(week.matches(&task("call mom due:2023-05-03"), now, &hours));
+        assert!(week.matches(&task("call mom due:2023-04-20"), now, &hours));
+        assert!(!week.matches(&task("call mom due:2023-05-08"), now, &hours));
+        assert!(!week.matches(&task("call mom"), now, &hours));
+
+        let work = TaskQuery::from_str("+work -@waiting Report")?;
+        assert!(work.matches(&task("write the report +work @office"), now, &hours));
+        assert!(!work.matches(&task("write the report +work @waiting"), now, &hours));
+        assert!(!work.matches(&task("write the report +home"), now, &hours));
+        assert!(!work.matches(&task("call the boss +work"), now, &hours));
+        assert_eq!(work.to_string(), "+work -@waiting Report");
+        assert_eq!(work.words().collect::<Vec<_>>(), ["report"]);
+        let words = TaskQuery::from_str("call -mom !a due<1")?;
+        assert_eq!(words.words().collect::<Vec<_>>(), ["call"]);
+        assert_eq!(
+            work.filters(),
+            vec![
+                (
+                    ToDoCategory::Projects,
+                    String::from("work"),
+                    FilterState::Select
+                ),
+                (
+                    ToDoCategory::Contexts,
+                    String::from("waiting"),
+                    FilterState::Remove
+                ),
+            ]
+        );
+
+        let today = TaskQuery::from_str("today")?;
+        let hours = ContextHours::parse_all(&HashMap::from([(
+            String::from("office"),
+            String::from("9-17 weekdays"),
+        )]))?;
+        assert!(today.matches(&task("call mom due:2023-04-20"), now, &hours));
+        assert!(today.matches(&task("call mom due:2023-05-01"), now, &hours));
+        assert!(!today.matches(&task("call mom due:2023-05-02"), now, &hours));
+        assert!(!today.matches(&task("call the boss @office due:2023-05-01"), now, &hours));
+
+        let urgent = TaskQuery::from_str("!b <due:2w")?;
+        assert!(urgent.matches(&task("(B) call mom due:2023-05-14"), now, &hours));
+        assert!(!urgent.matches(&task("(B) call mom due:2023-05-15"), now, &hours));
+        assert!(!urgent.matches(&task("(A) call mom due:2023-05-03"), now, &hours));
+        assert!(TaskQuery::from_str("!")?.matches(&task("call mom!"), now, &hours));
+
+        assert!(TaskQuery::from_str("")?.matches(&task("anything"), now, &hours));
+        Ok(())
+    }
+
+    #[test]
+    fn ranges() -> ToDoRes<()> {
+        // 2023-05-01 is a Monday.
+        let now = NaiveDate::from_ymd_opt(2023, 5, 1)
+            .unwrap()
+            .and_hms_opt(20, 0, 0)
+            .unwrap();
+        let hours = ContextHours::default();
+        let matches = |query: &str, task: &str| -> ToDoRes<bool> {
+            Ok(TaskQuery::from_str(query)?.matches(&Task::from_str(task).unwrap(), now, &hours))
+        };
+        assert!(matches("!A-C", "(B) call mom")?);
+        assert!(matches("!c-a", "(A) call mom")?);
+        assert!(!matches("!A-C", "(D) call mom")?);
+        assert!(!matches("!A-C", "call mom")?);
+        assert!(matches("-!A-B", "call mom")?);
+
+        assert!(matches("due:today..1w", "call mom due:2023-05-08")?);
+        assert!(!matches("due:today..1w", "call mom due:2023-05-09")?);
+        assert!(!matches("due:today..1w", "call mom due:2023-04-30")?);
+        assert!(matches("due:..today", "call mom due:2023-04-30")?);
+        assert!(matches("due:fri", "call mom due:2023-05-05")?);
+        assert!(!matches("due:..today", "call mom")?);
+        assert!(matches("t:2023-05-01..", "call mom t:2023-06-01")?);
+        assert!(matches("created:-1w..", "2023-04-25 call mom")?);
+        assert!(!matches("created:-1w..", "2023-04-23 call mom")?);
+        assert!(matches(
+            "done:2023-04-01..2023-04-30",
+            "x 2023-04-30 2023-04-01 call mom"
+        )?);
+
+        for query in ["due:", "due:soon..", "created:..2023-13-01"] {
+            assert_eq!(
+                TaskQuery::from_str(query),
+                Err(ToDoError::ParseQuery(String::from(query)))
+            );
+        }
+        assert_eq!(
+            TaskQuery::from_str("due<week"),
+            Err(ToDoError::ParseQuery(String::from("due<week")))
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,480 @@
+use super::{escalation::append_text, ActivityKind, Change, ToDo};
+use crate::{
+    error::{ToDoError, ToDoRes},
+    style::Styles,
+};
+use chrono::NaiveDate;
+use std::{cmp::Ordering, collections::HashMap};
+use todo_txt::{Priority, Task};
+use tui::style::Style;
+
+/// Parses a priority letter of a rule, `none` is no priority.
+fn parse_priority(letter: &str) -> Option<Priority> {
+    if letter == "none" {
+        return Some(Priority::lowest());
+    }
+    let mut chars = letter.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_uppercase() => Priority::try_from(c).ok(),
+        _ => None,
+    }
+}
+
+/// Describes a priority in the changes of a rule, e.g. `priority (A)`.
+fn describe_priority(priority: &Priority) -> String {
+    match priority.is_lowest() {
+        true => String::from("priority cleared"),
+        false => format!("priority ({priority})"),
+    }
+}
+
+/// The change of the priority by a [`TaskRule`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum PriorityAction {
+    /// Sets the priority, `priority=A`.
+    Set(Priority),
+    /// Lowers a higher priority to the priority, `priority<=C`.
+    AtMost(Priority),
+    /// Raises a lower priority to the priority, `priority>=C`.
+    AtLeast(Priority),
+}
+
+impl PriorityAction {
+    /// Parses an action such as `priority=A`, `priority <= C` or `priority=none`.
+    fn parse(s: &str) -> Option<Self> {
+        let rest = s.strip_prefix("priority")?.trim_start();
+        let (action, letter): (fn(Priority) -> Self, &str) =
+            if let Some(letter) = rest.strip_prefix("<=") {
+                (Self::AtMost, letter)
+            } else if let Some(letter) = rest.strip_prefix(">=") {
+                (Self::AtLeast, letter)
+            } else {
+                (Self::Set, rest.strip_prefix('=')?)
+            };
+        parse_priority(letter.trim()).map(action)
+    }
+
+    /// Gets the priority of the task after the action.
+    fn apply(&self, priority: &Priority) -> Priority {
+        match self {
+            Self::Set(value) => value.clone(),
+            Self::AtMost(value) if priority > value => value.clone(),
+            Self::AtLeast(value) if priority < value => value.clone(),
+            _ => priority.clone(),
+        }
+    }
+}
+
+/// A single condition of a [`TaskRule`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Condition {
+    /// The priority of the task compares to the priority as given, a task without
+    /// priority is below `(Z)` and equal to `none`.
+    Priority(Vec<Ordering>, Priority),
+    /// The pending task is overdue by more than the number of days.
+    Overdue(i64),
+    /// The pending task is due today.
+    Today,
+    /// The pending task is due in at most the number of days, overdue tasks included.
+    DueWithin(i64),
+    /// The task was created more than the number of days ago.
+    Age(i64),
+    /// The task has the project, context or hashtag, e.g. `+work`.
+    Category(String),
+}
+
+impl Condition {
+    /// Parses a condition such as `priority >= B`, `priority = none`, `overdue`, `due<=3`
+    /// or `@phone`.
+    fn parse(s: &str) -> Option<Self> {
+        let days = |s: &str| s.trim().parse::<i64>().ok();
+        if let Some(rest) = s.strip_prefix("priority") {
+            let rest = rest.trim_start();
+            let (orderings, letter) = [
+                (">=", vec![Ordering::Greater, Ordering::Equal]),
+                ("<=", vec![Ordering::Less, Ordering::Equal]),
+                (">", vec![Ordering::Greater]),
+                ("<", vec![Ordering::Less]),
+                ("=", vec![Ordering::Equal]),
+            ]
+            .into_iter()
+            .find_map(|(op, orderings)| Some((orderings, rest.strip_prefix(op)?.trim())))?;
+            return Some(Self::Priority(orderings, parse_priority(letter)?));
+        }
+        match s {
+            "overdue" => return Some(Self::Overdue(0)),
+            "today" => return Some(Self::Today),
+            _ => {}
+        }
+        if s.starts_with(['+', '@', '#']) && s.len() > 1 && !s.contains(char::is_whitespace) {
+            return Some(Self::Category(s.to_string()));
+        }
+        if let Some((name, value)) = s.split_once("<=") {
+            return match name.trim() {
+                "due" => Some(Self::DueWithin(days(value)?)),
+                _ => None,
+            };
+        }
+        let (name, value) = s.split_once('>')?;
+        match name.trim() {
+            "overdue" => Some(Self::Overdue(days(value)?)),
+            "age" => Some(Self::Age(days(value)?)),
+            _ => None,
+        }
+    }
+
+    /// Checks whether the task meets the condition.
+    fn matches(&self, task: &Task, today: NaiveDate) -> bool {
+        let due_in = task
+            .due_date
+            .filter(|_| !task.finished)
+            .map(|due| (due - today).num_days());
+        match self {
+            Self::Priority(orderings, priority) => orderings.contains(&task.priority.cmp(priority)),
+            Self::Overdue(days) => due_in.is_some_and(|due_in| -due_in > *days),
+            Self::Today => due_in == Some(0),
+            Self::DueWithin(days) => due_in.is_some_and(|due_in| due_in <= *days),
+            Self::Age(days) => task
+                .create_date
+                .is_some_and(|created| (today - created).num_days() > *days),
+            Self::Category(category) => {
+                let (prefix, name) = category.split_at(1);
+                let name = name.to_string();
+                match prefix {
+                    "+" => task.projects().contains(&name),
+                    "@" => task.contexts().contains(&name),
+                    _ => task.hashtags.contains(&name),
+                }
+            }
+        }
+    }
+}
+
+/// Represents a rule styling, tagging and prioritizing tasks meeting its conditions.
+///
+/// The rule is configured as conditions joined by `and` and actions separated by `;`,
+/// e.g. `"priority >= B and overdue" = "style=alert; add @urgent"`. A condition is
+/// negated by `not`. Supported conditions are `priority OP X` with `>=`, `<=`, `>`,
+/// `<` or `=`, where `(A)` is the highest priority and `none` no priority, `overdue`,
+/// `overdue>N`, `today`, `due<=N`, `age>N` and a project, context or hashtag, e.g. `+work`.
+/// The actions are `style=STYLE`, a style or a named style the task is rendered with,
+/// `add TEXT`, the text appended to the task unless it is already there, and
+/// `priority=X`, `priority<=X` or `priority>=X`, the priority the task is set to,
+/// lowered to or raised to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TaskRule {
+    rule: String,
+    conditions: Vec<(bool, Condition)>,
+    style: Option<String>,
+    additions: Vec<String>,
+    priority: Option<PriorityAction>,
+}
+
+impl TaskRule {
+    /// Creates a new `TaskRule`.
+    ///
+    /// # Arguments
+    ///
+    /// * `when` - The conditions of the rule, e.g. `priority >= B and overdue`.
+    /// * `then` - The actions of the rule, e.g. `style=alert; add @urgent`.
+    /// * `styles` - Styles the style of the rule is checked with.
+    pub fn new(when: &str, then: &str, styles: &Styles) -> ToDoRes<Self> {
+        let err = || ToDoError::ParseTaskRule(format!("{when} = {then}"));
+        let conditions = when
+            .split(" and ")
+            .map(|condition| {
+                let condition = condition.trim();
+                let (negated, condition) = match condition.strip_prefix("not ") {
+                    Some(condition) => (true, condition.trim()),
+                    None => (false, condition),
+                };
+                Condition::parse(condition).map(|condition| (negated, condition))
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(err)?;
+        let mut style = None;
+        let mut additions = Vec::new();
+        let mut priority = None;
+        for action in then.split(';').map(str::trim) {
+            if let Some(name) = action.strip_prefix("style=") {
+                styles.get_style(name.trim()).map_err(|_| err())?;
+                style = Some(name.trim().to_string());
+            } else if let Some(text) = action.strip_prefix("add ") {
+                additions.push(text.trim().to_string());
+            } else if action.starts_with("priority") {
+                priority = Some(PriorityAction::parse(action).ok_or_else(err)?);
+            } else {
+                return Err(err());
+            }
+        }
+        Ok(Self {
+            rule: when.trim().to_string(),
+            conditions,
+            style,
+            additions,
+            priority,
+        })
+    }
+
+    /// Parses the rules of the configuration, the rules are applied in the order of their conditions.
+    ///
+    /// # Arguments
+    ///
+    /// * `rules` - Actions by the conditions of the rules.
+    /// * `styles` - Styles the styles of the rules are checked with.
+    pub fn parse_all(rules: &HashMap<String, String>, styles: &Styles) -> ToDoRes<Vec<Self>> {
+        let mut rules = rules
+            .iter()
+            .map(|(when, then)| Self::new(when, then, styles))
+            .collect::<ToDoRes<Vec<_>>>()?;
+        rules.sort_by(|a, b| a.rule.cmp(&b.rule));
+        Ok(rules)
+    }
+
+    /// Checks whether the task meets all conditions of the rule.
+    fn matches(&self, task: &Task, today: NaiveDate) -> bool {
+        self.conditions
+            .iter()
+            .all(|(negated, condition)| condition.matches(task, today) != *negated)
+    }
+}
+
+impl ToDo {
+    /// Gets the style of the task given by the task rules it meets, styles of later
+    /// rules are patched over the earlier ones.
+    pub fn rule_style(&self, task: &Task) -> Style {
+        let today = self.config.clock.today();
+        self.config
+            .task_rules
+            .iter()
+            .filter(|rule| rule.matches(task, today))
+            .filter_map(|rule| rule.style.as_ref())
+            .filter_map(|name| self.styles.get_style(name).ok())
+            .fold(Style::default(), |style, value| {
+                style.patch(value.get_style(task, &self.styles))
+            })
+    }
+
+    /// Gets the pending tasks the task rules would change, the tasks are not changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `today` - The date due dates and ages are compared with.
+    ///
+    /// # Returns
+    ///
+    /// The indexes of the changed tasks with the changed tasks and the descriptions
+    /// of the changes.
+    fn ruled_tasks(&self, today: NaiveDate) -> Vec<(usize, Task, Vec<String>)> {
+        let mut ruled = Vec::new();
+        for (index, previous) in self.pending.iter().enumerate() {
+            let mut task = previous.clone();
+            let mut changes = Vec::new();
+            for rule in &self.config.task_rules {
+                if !rule.matches(&task, today) {
+                    continue;
+                }
+                for text in &rule.additions {
+                    if let Some(added) = append_text(&task, text) {
+                        changes.push(format!(
+                            "{}: added {text} ({})",
+                            previous.subject, rule.rule
+                        ));
+                        task = added;
+                    }
+                }
+                if let Some(action) = &rule.priority {
+                    let priority = action.apply(&task.priority);
+                    if priority != task.priority {
+                        changes.push(format!(
+                            "{}: {} ({})",
+                            previous.subject,
+                            describe_priority(&priority),
+                            rule.rule
+                        ));
+                        task.priority = priority;
+                    }
+                }
+            }
+            if task != *previous {
+                ruled.push((index, task, changes));
+            }
+        }
+        ruled
+    }
+
+    /// Describes the changes the task rules would make to the pending tasks, so they
+    /// can be reviewed before [`ToDo::apply_task_rules`] makes them.
+    ///
+    /// # Arguments
+    ///
+    /// * `today` - The date due dates and ages are compared with.
+    pub fn preview_task_rules(&self, today: NaiveDate) -> Vec<String> {
+        self.ruled_tasks(today)
+            .into_iter()
+            .flat_map(|(_, _, changes)| changes)
+            .collect()
+    }
+
+    /// Appends the text and changes the priorities of the task rules to the pending
+    /// tasks meeting their conditions.
+    ///
+    /// # Arguments
+    ///
+    /// * `today` - The date due dates and ages are compared with.
+    ///
+    /// # Returns
+    ///
+    /// Descriptions of the changes, e.g. `call mom: added @urgent (priority >= B and overdue)`.
+    pub fn apply_task_rules(&mut self, today: NaiveDate) -> Vec<String> {
+        let ruled = self.ruled_tasks(today);
+        if ruled.is_empty() {
+            return Vec::new();
+        }
+        let mut changes = Vec::new();
+        for (index, task, task_changes) in ruled {
+            let previous = std::mem::replace(&mut self.pending[index], task.clone());
+            self.record(ActivityKind::Edit, &task, Some(&previous));
+            changes.extend(task_changes);
+        }
+        self.version += 1;
+        self.notify(Change::Tasks);
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use tui::style::Color;
+
+    #[test]
+    fn parse() {
+        let styles = Styles::default();
+        for (when, then) in [
+            ("priority >= B and overdue", "style=red; add @urgent"),
+            ("not +work and age>30", "add #stale"),
+            ("due<=3 and @phone", "style=bold"),
+            ("priority=A and today and overdue>2", "style=green"),
+            ("due<=2 and priority = none", "priority=A"),
+            ("@waiting", "priority <= C; style=dim"),
+            ("age>30", "priority>=none"),
+        ] {
+            assert!(TaskRule::new(when, then, &styles).is_ok(), "{when}");
+        }
+        for (when, then) in [
+            ("priority >= b", "style=red"),
+            ("priority ~ B", "style=red"),
+            ("late", "style=red"),
+            ("due>=3", "style=red"),
+            ("overdue", "style=nocolor"),
+            ("overdue", "color=red"),
+            ("overdue", "priority=a"),
+            ("overdue", "priority<A"),
+            ("priority = nothing", "style=red"),
+        ] {
+            assert_eq!(
+                TaskRule::new(when, then, &styles),
+                Err(ToDoError::ParseTaskRule(format!("{when} = {then}")))
+            );
+        }
+    }
+
+    #[test]
+    fn apply() -> ToDoRes<()> {
+        let mut todo = ToDo::default();
+        todo.config.task_rules = TaskRule::parse_all(
+            &HashMap::from([
+                (
+                    String::from("priority >= B and overdue"),
+                    String::from("style=red; add @urgent"),
+                ),
+                (
+                    String::from("not +work and due<=1"),
+                    String::from("style=bold"),
+                ),
+            ]),
+            &todo.styles,
+        )?;
+        todo.new_task("(A) pay the bills due:2023-05-01").unwrap();
+        todo.new_task("(C) call mom due:2023-05-01").unwrap();
+        todo.new_task("(B) write the report +work due:2023-05-20")
+            .unwrap();
+        let today = NaiveDate::from_ymd_opt(2023, 5, 10).unwrap();
+        todo.config.clock = crate::clock::Clock::fixed(today);
+
+        assert_eq!(
+            todo.rule_style(&todo.pending[0]),
+            Style::default()
+                .fg(Color::Red)
+                .add_modifier(tui::style::Modifier::BOLD)
+        );
+        assert_eq!(
+            todo.rule_style(&todo.pending[1]),
+            Style::default().add_modifier(tui::style::Modifier::BOLD)
+        );
+        assert_eq!(todo.rule_style(&todo.pending[2]), Style::default());
+        let finished = Task::from_str("x (A) 2023-05-02 pay rent due:2023-05-01").unwrap();
+        assert_eq!(todo.rule_style(&finished), Style::default());
+
+        assert_eq!(
+            todo.apply_task_rules(today),
+            [String::from(
+                "pay the bills: added @urgent (priority >= B and overdue)"
+            )]
+        );
+        assert!(todo.pending[0].contexts().contains(&String::from("urgent")));
+        assert!(todo.apply_task_rules(today).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn prioritize() -> ToDoRes<()> {
+        let mut todo = ToDo::default();
+        todo.config.task_rules = TaskRule::parse_all(
+            &HashMap::from([
+                (
+                    String::from("due<=2 and priority=none"),
+                    String::from("priority=A"),
+                ),
+                (String::from("@waiting"), String::from("priority<=C")),
+                (String::from("age>30"), String::from("priority>=D")),
+                (String::from("+someday"), String::from("priority=none")),
+            ]),
+            &todo.styles,
+        )?;
+        todo.new_task("pay the bills due:2023-05-11").unwrap();
+        todo.new_task("(A) call the plumber @waiting due:2023-05-11")
+            .unwrap();
+        todo.new_task("(E) 2023-01-01 clean the garage").unwrap();
+        todo.new_task("(B) learn japanese +someday").unwrap();
+        todo.new_task("(D) write the report due:2023-05-11")
+            .unwrap();
+        let today = NaiveDate::from_ymd_opt(2023, 5, 10).unwrap();
+
+        let preview = todo.preview_task_rules(today);
+        assert_eq!(
+            preview,
+            [
+                "pay the bills: priority (A) (due<=2 and priority=none)",
+                "call the plumber @waiting: priority (C) (@waiting)",
+                "clean the garage: priority (D) (age>30)",
+                "learn japanese +someday: priority cleared (+someday)",
+            ]
+        );
+        assert!(todo.pending[0].priority.is_lowest());
+        let version = todo.get_version();
+
+        assert_eq!(todo.apply_task_rules(today), preview);
+        assert_eq!(todo.get_version(), version + 1);
+        let priorities: Vec<String> = todo
+            .pending
+            .iter()
+            .map(|task| task.priority.to_string())
+            .collect();
+        assert_eq!(priorities, ["A", "C", "D", "", "D"]);
+        assert!(todo.preview_task_rules(today).is_empty());
+        Ok(())
+    }
+}
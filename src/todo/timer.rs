@@ -0,0 +1,259 @@
+use super::{
+    effort::{format_duration, parse_duration, SPENT_TAG},
+    ToDo, ToDoData,
+};
+use chrono::{Duration, NaiveDateTime};
+use todo_txt::Task;
+
+/// Represents the timer tracking the time spent on a pending task. The tracked time
+/// is added to the `spent:` tag of the task when the timer stops or the list is saved.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Timer {
+    index: usize,
+    subject: String,
+    started: NaiveDateTime,
+    /// The end of the pomodoro, `None` if the timer runs until it is stopped.
+    pomodoro: Option<NaiveDateTime>,
+}
+
+impl ToDo {
+    /// Finds the pending task of the timer, at its index first and by its subject
+    /// if the list has changed since.
+    fn timed_index(&self) -> Option<usize> {
+        let timer = self.timer.as_ref()?;
+        match self.pending.get(timer.index) {
+            Some(task) if task.subject == timer.subject => Some(timer.index),
+            _ => self
+                .pending
+                .iter()
+                .position(|task| task.subject == timer.subject),
+        }
+    }
+
+    /// Gets the status of the timer shown to the user, e.g. `⏱ 1h05m write the report`
+    /// with the time spent on the task, or `⏱ 12m left: write the report` during a pomodoro.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The current time.
+    ///
+    /// # Returns
+    ///
+    /// `None` if no timer runs.
+    pub fn timer_status(&self, now: NaiveDateTime) -> Option<String> {
+        let timer = self.timer.as_ref()?;
+        let task = &self.pending[self.timed_index()?];
+        Some(match timer.pomodoro {
+            Some(end) => {
+                let left = ((end - now).num_seconds().max(0) + 59) / 60;
+                format!("⏱ {}m left: {}", left, task.subject)
+            }
+            None => {
+                let elapsed = (now - timer.started).num_minutes().max(0) as u32;
+                format!(
+                    "⏱ {} {}",
+                    format_duration(spent(task) + elapsed),
+                    task.subject
+                )
+            }
+        })
+    }
+
+    /// Gets the next moment the status of the timer changes, every minute since the start
+    /// of the timer, see [`ToDo::timer_status`].
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The current time.
+    ///
+    /// # Returns
+    ///
+    /// `None` if no timer runs.
+    pub fn next_timer_change(&self, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        let timer = self.timer.as_ref()?;
+        let minute = timer.started + Duration::minutes((now - timer.started).num_minutes() + 1);
+        Some(timer.pomodoro.map_or(minute, |end| end.min(minute)))
+    }
+
+    /// Adds the time tracked by the timer to the `spent:` tag of its task, the timer
+    /// keeps running. Whole minutes are added, the rest is kept for the next time.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the task was changed.
+    pub fn flush_timer(&mut self) -> bool {
+        let Some(index) = self.timed_index() else {
+            // The task is not in the list anymore, e.g. it was removed by another program.
+            self.timer = None;
+            return false;
+        };
+        let now = self.config.clock.now();
+        let timer = self.timer.as_mut().unwrap();
+        timer.index = index;
+        let minutes = (now - timer.started).num_minutes();
+        if minutes <= 0 {
+            return false;
+        }
+        timer.started += Duration::minutes(minutes);
+        let task = &mut ToDoData::Pending.get_data_mut(self)[index];
+        let spent = format_duration(spent(task) + minutes as u32);
+        task.tags.insert(SPENT_TAG.to_string(), spent);
+        true
+    }
+
+    /// Starts the timer of the pending task or stops it if it tracks the task already.
+    /// The timer of another task is stopped first, only one task is tracked at a time.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data where the task is located.
+    /// * `index` - The index of the task in the unfiltered data.
+    pub(super) fn toggle_timer_at(&mut self, data: ToDoData, index: usize) {
+        if data != ToDoData::Pending {
+            log::warn!("Time is tracked only for pending tasks");
+            return;
+        }
+        let running = self.timed_index();
+        self.flush_timer();
+        self.timer = None;
+        if running != Some(index) {
+            let now = self.config.clock.now();
+            let pomodoro = self.config.pomodoro_minutes;
+            self.timer = Some(Timer {
+                index,
+                subject: self.pending[index].subject.clone(),
+                started: now,
+                pomodoro: (pomodoro > 0).then(|| now + Duration::minutes(pomodoro as i64)),
+            });
+        }
+    }
+
+    /// Stops the timer if it tracks the task, e.g. before the task is completed or removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data where the task is located.
+    /// * `index` - The index of the task in the unfiltered data.
+    pub(super) fn stop_timer_at(&mut self, data: ToDoData, index: usize) {
+        if data == ToDoData::Pending && self.timed_index() == Some(index) {
+            self.flush_timer();
+            self.timer = None;
+        }
+    }
+
+    /// Keeps the timer on the task after its subject is edited.
+    pub(super) fn rename_timer(&mut self, previous: &Task, task: &Task) {
+        if let Some(timer) = self
+            .timer
+            .as_mut()
+            .filter(|timer| timer.subject == previous.subject)
+        {
+            timer.subject = task.subject.clone();
+        }
+    }
+
+    /// Stops the timer once its pomodoro is over.
+    ///
+    /// # Returns
+    ///
+    /// The subject of the task or `None` if no pomodoro is over.
+    pub fn finish_pomodoro(&mut self) -> Option<String> {
+        let timer = self.timer.as_ref()?;
+        if timer.pomodoro? > self.config.clock.now() {
+            return None;
+        }
+        let subject = timer.subject.clone();
+        self.flush_timer();
+        self.timer = None;
+        Some(subject)
+    }
+}
+
+/// Gets the minutes of the `spent:` tag of the task, 0 without the tag.
+fn spent(task: &Task) -> u32 {
+    task.tags
+        .get(SPENT_TAG)
+        .and_then(|spent| parse_duration(spent))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::todo::{Action, TaskId, ToDo, ToDoData};
+    use chrono::Duration;
+
+    fn toggle(todo: &mut ToDo, index: usize) -> Result<(), todo_txt::Error> {
+        let task = TaskId::new(index, &todo.pending[index]);
+        todo.apply(Action::ToggleTimer {
+            data: ToDoData::Pending,
+            task,
+        })
+    }
+
+    fn rewind(todo: &mut ToDo, minutes: i64) {
+        let timer = todo.timer.as_mut().unwrap();
+        timer.started -= Duration::minutes(minutes);
+        if let Some(end) = timer.pomodoro.as_mut() {
+            *end -= Duration::minutes(minutes);
+        }
+    }
+
+    #[test]
+    fn track() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        todo.new_task("write the report spent:1h")?;
+        todo.new_task("call mom")?;
+        let status = |todo: &ToDo| todo.timer_status(todo.config.clock.now());
+
+        toggle(&mut todo, 0)?;
+        rewind(&mut todo, 30);
+        assert_eq!(status(&todo).as_deref(), Some("⏱ 1h30m write the report"));
+        assert!(todo.flush_timer());
+        assert_eq!(todo.pending[0].tags["spent"], "1h30m");
+        assert!(!todo.flush_timer());
+
+        // Starting the timer of another task stops the running one.
+        rewind(&mut todo, 15);
+        toggle(&mut todo, 1)?;
+        assert_eq!(todo.pending[0].tags["spent"], "1h45m");
+        assert_eq!(status(&todo).as_deref(), Some("⏱ 0m call mom"));
+
+        // The time is kept when the task is edited and added when it is completed.
+        rewind(&mut todo, 5);
+        todo.state.active = Some((ToDoData::Pending, 1));
+        todo.update_active("call mom and dad")?;
+        let task = TaskId::new(1, &todo.pending[1]);
+        todo.apply(Action::CompleteTask {
+            data: ToDoData::Pending,
+            task,
+        })?;
+        assert_eq!(todo.done[0].tags["spent"], "5m");
+        assert_eq!(todo.timer, None);
+        Ok(())
+    }
+
+    #[test]
+    fn pomodoro() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        todo.config.pomodoro_minutes = 25;
+        todo.new_task("write the report")?;
+        let status = |todo: &ToDo| todo.timer_status(todo.config.clock.now());
+
+        toggle(&mut todo, 0)?;
+        assert_eq!(
+            status(&todo).as_deref(),
+            Some("⏱ 25m left: write the report")
+        );
+        let started = todo.timer.as_ref().unwrap().started;
+        assert_eq!(
+            todo.next_timer_change(started),
+            Some(started + Duration::minutes(1))
+        );
+        assert_eq!(todo.finish_pomodoro(), None);
+        rewind(&mut todo, 25);
+        assert_eq!(todo.finish_pomodoro().as_deref(), Some("write the report"));
+        assert_eq!(todo.pending[0].tags["spent"], "25m");
+        assert_eq!(status(&todo), None);
+        Ok(())
+    }
+}
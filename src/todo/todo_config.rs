@@ -0,0 +1,58 @@
+use super::{
+    sort::TaskSort, ContextHours, EscalationRule, PriorityAging, TaskRule, UrgencyWeights,
+};
+use crate::clock::Clock;
+use std::{collections::HashMap, env::var};
+
+/// Options of the todo list, the application fills them from its configuration.
+pub struct ToDoConfig {
+    pub use_done: bool,
+    pub pending_sort: TaskSort,
+    pub done_sort: TaskSort,
+    pub pending_sort_ties: Vec<TaskSort>,
+    pub done_sort_ties: Vec<TaskSort>,
+    pub sort_on_insert: bool,
+    pub raw_lines: bool,
+    pub my_name: Option<String>,
+    pub project_defaults: HashMap<String, String>,
+    pub priority_labels: HashMap<String, String>,
+    pub activity_log: bool,
+    pub trash_size: usize,
+    pub escalation_rules: Vec<EscalationRule>,
+    pub task_rules: Vec<TaskRule>,
+    pub priority_aging: PriorityAging,
+    pub urgency_weights: UrgencyWeights,
+    pub pomodoro_minutes: usize,
+    pub deadline_warning_days: i64,
+    pub context_hours: ContextHours,
+    pub clock: Clock,
+    pub done_path: String,
+}
+
+impl Default for ToDoConfig {
+    fn default() -> Self {
+        Self {
+            use_done: false,
+            pending_sort: TaskSort::None,
+            done_sort: TaskSort::CompletedReverse,
+            pending_sort_ties: Vec::new(),
+            done_sort_ties: Vec::new(),
+            sort_on_insert: false,
+            raw_lines: false,
+            my_name: None,
+            project_defaults: HashMap::new(),
+            priority_labels: HashMap::new(),
+            activity_log: false,
+            trash_size: 50,
+            escalation_rules: Vec::new(),
+            task_rules: Vec::new(),
+            priority_aging: PriorityAging::new(0, 0, "C-B").unwrap_or_default(),
+            urgency_weights: UrgencyWeights::default(),
+            pomodoro_minutes: 0,
+            deadline_warning_days: 7,
+            context_hours: ContextHours::default(),
+            clock: Clock::default(),
+            done_path: var("HOME").unwrap_or(String::from("~")) + "/done.txt",
+        }
+    }
+}
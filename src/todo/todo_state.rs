@@ -1,13 +1,16 @@
-use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::sync::Mutex;
 use todo_txt::Task;
 
-use crate::config::ToDoConfig;
+use crate::todo::ToDoConfig;
 
-use super::{task_list::TaskSort, ToDo};
+use super::{sort::TaskSort, ToDo};
 
 /// Enum to represent the state of ToDo data (pending or done).
-#[derive(Clone, Copy, Serialize, Deserialize)]
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub enum ToDoData {
     Pending,
     Done,
@@ -46,29 +49,200 @@ impl ToDoData {
             Done => config.done_sort,
         }
     }
+
+    /// Gets the sorting used to break ties of the sorting of the data.
+    pub fn get_sort_ties<'a>(&self, config: &'a ToDoConfig) -> &'a [TaskSort] {
+        use ToDoData::*;
+        match self {
+            Pending => &config.pending_sort_ties,
+            Done => &config.done_sort_ties,
+        }
+    }
+}
+
+/// Tags holding the person a task is assigned to, the first tag present is used.
+pub const ASSIGNEE_TAGS: [&str; 2] = ["assignee", "by"];
+
+/// The key of a `key:value` tag whose values are a category, e.g. `person`.
+/// Keys are interned, so the categories stay cheap to copy and compare.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TagKey(&'static str);
+
+impl TagKey {
+    /// Creates the key of the tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the tag without the colon, e.g. `due`.
+    pub fn new(key: &str) -> Self {
+        static KEYS: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+        let mut keys = KEYS.lock().unwrap();
+        match keys.get(key) {
+            Some(key) => Self(key),
+            None => {
+                let key: &'static str = Box::leak(key.to_string().into_boxed_str());
+                keys.insert(key);
+                Self(key)
+            }
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        self.0
+    }
+
+    /// Gets the values of the tag of the task. The due and threshold dates are parsed
+    /// out of the tags, they are written as in todo.txt lines.
+    pub fn values<'a>(&self, task: &'a Task) -> Cow<'a, [String]> {
+        let date = match self.0 {
+            "due" => task.due_date,
+            "t" => task.threshold_date,
+            key => {
+                let value = task.tags.get(key).map(std::slice::from_ref);
+                return Cow::Borrowed(value.unwrap_or_default());
+            }
+        };
+        Cow::Owned(date.iter().map(|date| date.to_string()).collect())
+    }
+
+    /// Gets the `key:value` tags of the task including the due and threshold dates.
+    pub fn task_tags(task: &Task) -> impl Iterator<Item = (&str, Cow<'_, str>)> {
+        let dates = [("due", task.due_date), ("t", task.threshold_date)];
+        dates
+            .into_iter()
+            .filter_map(|(key, date)| Some((key, Cow::Owned(date?.to_string()))))
+            .chain(
+                task.tags
+                    .iter()
+                    .map(|(key, value)| (key.as_str(), Cow::Borrowed(value.as_str()))),
+            )
+    }
+}
+
+impl fmt::Display for TagKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl fmt::Debug for TagKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.0, f)
+    }
+}
+
+impl Serialize for TagKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for TagKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self::new(&String::deserialize(deserializer)?))
+    }
+}
+
+/// Separates the parts of the name of a project in the hierarchy of projects,
+/// e.g. `+home.garden` is a subproject of `+home`.
+pub const PROJECT_SEPARATOR: char = '.';
+
+/// Gets the projects of the task with their subprojects, e.g. `home.garden` of `+home.garden`.
+/// The todo.txt parser ends the names of projects at the [`PROJECT_SEPARATOR`], so the
+/// dotted names are read from the subject.
+fn dotted_projects(task: &Task) -> Cow<'_, [String]> {
+    if !task.subject.contains(PROJECT_SEPARATOR) {
+        return Cow::Borrowed(task.projects());
+    }
+    let mut projects: Vec<String> = task
+        .subject
+        .split_whitespace()
+        .filter_map(|word| word.strip_prefix('+'))
+        .map(|name| {
+            let end = name
+                .find(|c: char| {
+                    !(c.is_alphanumeric() || c == '_' || c == '-' || c == PROJECT_SEPARATOR)
+                })
+                .unwrap_or(name.len());
+            name[..end].trim_end_matches(PROJECT_SEPARATOR).to_string()
+        })
+        .filter(|name| !name.is_empty())
+        .collect();
+    projects.sort();
+    projects.dedup();
+    Cow::Owned(projects)
 }
 
 /// Enum to represent different categories.
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub enum ToDoCategory {
     Projects,
     Contexts,
     Hashtags,
+    Assignees,
+    /// Values of a `key:value` tag, e.g. the due dates of `due:`.
+    Tag(TagKey),
 }
 
 impl ToDoCategory {
-    pub fn get_data<'a>(&self, task: &'a Task) -> &'a [String] {
+    pub fn get_data<'a>(&self, task: &'a Task) -> Cow<'a, [String]> {
         use ToDoCategory::*;
-        match self {
-            Projects => task.projects(),
+        Cow::Borrowed(match self {
+            Projects => return dotted_projects(task),
             Contexts => task.contexts(),
             Hashtags => &task.hashtags,
-        }
+            Assignees => ASSIGNEE_TAGS
+                .iter()
+                .find_map(|tag| task.tags.get(*tag))
+                .map(std::slice::from_ref)
+                .unwrap_or_default(),
+            Tag(key) => return key.values(task),
+        })
+    }
+
+    /// Gets the prefix of the category in todo.txt lines, e.g. `+` of projects.
+    pub fn prefix(&self) -> Cow<'static, str> {
+        use ToDoCategory::*;
+        Cow::Borrowed(match self {
+            Projects => "+",
+            Contexts => "@",
+            Hashtags => "#",
+            Assignees => "assignee:",
+            Tag(key) => return Cow::Owned(format!("{key}:")),
+        })
+    }
+
+    /// Gets the parent projects of the project from the root, e.g. `home` and `home.garden`
+    /// of `home.garden.roses`. The parts of the name of a project are separated by
+    /// [`PROJECT_SEPARATOR`], other categories have no parents.
+    pub fn parents<'a>(&self, name: &'a str) -> impl Iterator<Item = &'a str> {
+        let hierarchy = *self == ToDoCategory::Projects;
+        name.match_indices(PROJECT_SEPARATOR)
+            .map(|(index, _)| index)
+            .filter(move |index| {
+                hierarchy
+                    && *index > 0
+                    && *index + 1 < name.len()
+                    && !name[..*index].ends_with(PROJECT_SEPARATOR)
+                    && !name[*index + 1..].starts_with(PROJECT_SEPARATOR)
+            })
+            .map(|index| &name[..index])
+    }
+
+    /// Checks whether a filter of the name matches the category of a task, a filter
+    /// of a project also matches its subprojects, e.g. `home` matches `home.garden`.
+    ///
+    /// # Arguments
+    ///
+    /// * `category` - The name of the category of the task.
+    /// * `name` - The name of the filter.
+    pub fn matches(&self, category: &str, name: &str) -> bool {
+        category == name || self.parents(category).any(|parent| parent == name)
     }
 
     pub fn get_all() -> &'static [ToDoCategory] {
         use ToDoCategory::*;
-        static ALL_CATEGORIES: [ToDoCategory; 3] = [Projects, Contexts, Hashtags];
+        static ALL_CATEGORIES: [ToDoCategory; 4] = [Projects, Contexts, Hashtags, Assignees];
         &ALL_CATEGORIES
     }
 }
@@ -85,15 +259,26 @@ pub struct ToDoState {
     pub project_filters: BTreeMap<String, FilterState>,
     pub context_filters: BTreeMap<String, FilterState>,
     pub hashtag_filters: BTreeMap<String, FilterState>,
+    #[serde(default)]
+    pub assignee_filters: BTreeMap<String, FilterState>,
+    /// Filters of the values of `key:value` tags by the keys.
+    #[serde(default)]
+    pub tag_filters: BTreeMap<TagKey, BTreeMap<String, FilterState>>,
+    /// Categories whose selected filters match tasks having any of them instead of all.
+    #[serde(default)]
+    pub any_categories: Vec<ToDoCategory>,
 }
 
 impl ToDoState {
     pub fn get_category(&self, category: ToDoCategory) -> &BTreeMap<String, FilterState> {
+        static NO_FILTERS: BTreeMap<String, FilterState> = BTreeMap::new();
         use ToDoCategory::*;
         match category {
             Projects => &self.project_filters,
             Contexts => &self.context_filters,
             Hashtags => &self.hashtag_filters,
+            Assignees => &self.assignee_filters,
+            Tag(key) => self.tag_filters.get(&key).unwrap_or(&NO_FILTERS),
         }
     }
 
@@ -106,36 +291,94 @@ impl ToDoState {
             Projects => &mut self.project_filters,
             Contexts => &mut self.context_filters,
             Hashtags => &mut self.hashtag_filters,
+            Assignees => &mut self.assignee_filters,
+            Tag(key) => self.tag_filters.entry(key).or_default(),
+        }
+    }
+
+    /// Gets the categories that may have filters, the tags only if they have any.
+    pub fn categories(&self) -> impl Iterator<Item = ToDoCategory> + '_ {
+        ToDoCategory::get_all().iter().copied().chain(
+            self.tag_filters
+                .iter()
+                .filter(|(_, filters)| !filters.is_empty())
+                .map(|(key, _)| ToDoCategory::Tag(*key)),
+        )
+    }
+
+    /// Checks whether the selected filters of the category match tasks having any of them.
+    pub fn is_any(&self, category: ToDoCategory) -> bool {
+        self.any_categories.contains(&category)
+    }
+
+    /// Switches the selected filters of the category between matching tasks having all
+    /// of them and tasks having any of them. Removed filters always exclude tasks.
+    pub fn toggle_any(&mut self, category: ToDoCategory) {
+        match self.any_categories.iter().position(|c| *c == category) {
+            Some(index) => {
+                self.any_categories.remove(index);
+            }
+            None => self.any_categories.push(category),
         }
     }
 
     pub fn filter_out(&self, task: &Task) -> bool {
-        fn filter(category: &BTreeMap<String, FilterState>, task_categories: &[String]) -> bool {
-            category.iter().all(|(category, state)| {
-                let contains = task_categories.contains(category);
-                match state {
-                    FilterState::Select => contains,
-                    FilterState::Remove => !contains,
-                }
+        self.categories().all(|category| {
+            let task_categories = category.get_data(task);
+            let contains = |name: &str| task_categories.iter().any(|c| category.matches(c, name));
+            let filters = self.get_category(category);
+            let removed = filters
+                .iter()
+                .all(|(name, state)| *state == FilterState::Select || !contains(name));
+            let mut selected = filters
+                .iter()
+                .filter(|(_, state)| **state == FilterState::Select)
+                .map(|(name, _)| contains(name))
+                .peekable();
+            let selected = match self.is_any(category) {
+                true => selected.peek().is_none() || selected.any(|contains| contains),
+                false => selected.all(|contains| contains),
+            };
+            removed && selected
+        })
+    }
+
+    /// Gets the active filters and whether the task matches each of them.
+    pub fn matched_filters(&self, task: &Task) -> Vec<(ToDoCategory, &String, FilterState, bool)> {
+        self.categories()
+            .flat_map(|category| {
+                let task_categories = category.get_data(task);
+                self.get_category(category)
+                    .iter()
+                    .map(move |(name, state)| {
+                        let contains = task_categories.iter().any(|c| category.matches(c, name));
+                        let matches = match state {
+                            FilterState::Select => contains,
+                            FilterState::Remove => !contains,
+                        };
+                        (category, name, *state, matches)
+                    })
             })
-        }
-        filter(&self.project_filters, task.projects())
-            && filter(&self.context_filters, task.contexts())
-            && filter(&self.hashtag_filters, &task.hashtags)
+            .collect()
     }
 
     pub fn set_filter(&mut self, category: ToDoCategory, filter: &str, filter_state: FilterState) {
-        let category = self.get_mut_category(category);
-        match category.get_mut(filter) {
+        let filters = self.get_mut_category(category);
+        match filters.get_mut(filter) {
             Some(a) => {
                 if filter_state == *a {
-                    category.remove(filter);
+                    filters.remove(filter);
                 } else {
                     *a = filter_state;
                 }
             }
             None => {
-                category.insert(filter.to_owned(), filter_state);
+                filters.insert(filter.to_owned(), filter_state);
+            }
+        }
+        if let ToDoCategory::Tag(key) = category {
+            if self.tag_filters.get(&key).is_some_and(BTreeMap::is_empty) {
+                self.tag_filters.remove(&key);
             }
         }
     }
@@ -0,0 +1,187 @@
+use super::{ActivityKind, TaskId, ToDo, ToDoData};
+use chrono::NaiveDateTime;
+use std::{
+    fmt,
+    fs::OpenOptions,
+    io::{self, Write},
+    path::Path,
+};
+use todo_txt::Task;
+
+/// A task deleted from the todo list and kept in the trash, so it can be restored.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrashedTask {
+    /// The list the task was deleted from.
+    pub data: ToDoData,
+    pub task: Task,
+    pub deleted: NaiveDateTime,
+}
+
+impl fmt::Display for TrashedTask {
+    /// Formats the task as a line of the trash file, e.g. `2023-05-01 14:30:00 call mom`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            self.deleted.format("%Y-%m-%d %H:%M:%S"),
+            self.task
+        )
+    }
+}
+
+/// Appends the tasks flushed from the trash to the end of the trash file, the file
+/// is created if it does not exist.
+///
+/// # Arguments
+///
+/// * `path` - The path to the trash file.
+/// * `tasks` - The flushed tasks, see [`ToDo::take_flushed`].
+pub fn append_trash(path: &Path, tasks: &[TrashedTask]) -> io::Result<()> {
+    if tasks.is_empty() {
+        return Ok(());
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let content: String = tasks.iter().map(|task| format!("{task}\n")).collect();
+    file.write_all(content.as_bytes())
+}
+
+impl ToDo {
+    /// Puts the deleted task to the trash. The oldest tasks are flushed from the trash
+    /// when it holds more than `trash_size` tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The type of ToDo data the task was deleted from.
+    /// * `task` - The deleted task.
+    pub(super) fn trash_task(&mut self, data: ToDoData, task: Task) {
+        self.trash.push(TrashedTask {
+            data,
+            task,
+            deleted: self.config.clock.now(),
+        });
+        let over = self.trash.len().saturating_sub(self.config.trash_size);
+        let flushed: Vec<TrashedTask> = self.trash.drain(..over).collect();
+        self.flushed.extend(flushed);
+    }
+
+    /// Gets the tasks in the trash, the most recently deleted last.
+    pub fn trash(&self) -> &[TrashedTask] {
+        &self.trash
+    }
+
+    /// Gets the identity of the task in the trash, the index is the position in the trash.
+    pub fn trashed_id(&self, index: usize) -> Option<TaskId> {
+        Some(TaskId::new(index, &self.trash.get(index)?.task))
+    }
+
+    /// Finds the task in the trash by its position first and by its content if
+    /// the trash has changed since.
+    pub(super) fn find_trashed(&self, id: &TaskId) -> Option<usize> {
+        match self.trash.get(id.index) {
+            Some(trashed) if trashed.task.to_string() == id.task => Some(id.index),
+            _ => self
+                .trash
+                .iter()
+                .position(|trashed| trashed.task.to_string() == id.task),
+        }
+    }
+
+    /// Returns the task from the trash to the list it was deleted from.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The position of the task in the trash.
+    pub(super) fn restore_at(&mut self, index: usize) {
+        let trashed = self.trash.remove(index);
+        self.record(ActivityKind::Restore, &trashed.task, None);
+        self.insert_task(trashed.data, trashed.task);
+    }
+
+    /// Flushes all tasks from the trash, e.g. when the application exits.
+    pub(super) fn flush_trash(&mut self) {
+        let trash = std::mem::take(&mut self.trash);
+        self.flushed.extend(trash);
+    }
+
+    /// Takes the tasks flushed from the trash since the last call, so they can be
+    /// appended to the trash file.
+    pub fn take_flushed(&mut self) -> Vec<TrashedTask> {
+        std::mem::take(&mut self.flushed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::todo::Action;
+    use std::{env::temp_dir, fs, process};
+
+    #[test]
+    fn restore() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        for task in ["call mom", "buy milk", "x 2023-05-01 pay rent"] {
+            todo.new_task(task)?;
+        }
+        todo.remove_task(ToDoData::Pending, 0);
+        todo.remove_task(ToDoData::Done, 0);
+        assert_eq!(todo.pending.len(), 1);
+        assert_eq!(todo.trash().len(), 2);
+        assert_eq!(todo.trash()[0].data, ToDoData::Pending);
+
+        let task = todo.trashed_id(1).unwrap();
+        todo.apply(Action::RestoreTask { task: task.clone() })?;
+        assert_eq!(todo.done[0].subject, "pay rent");
+        assert_eq!(todo.trash().len(), 1);
+        // The task is not in the trash anymore.
+        todo.apply(Action::RestoreTask { task })?;
+        assert_eq!(todo.done.len(), 1);
+
+        todo.apply(Action::RestoreTask {
+            task: todo.trashed_id(0).unwrap(),
+        })?;
+        assert!(todo.trash().is_empty());
+        assert_eq!(todo.pending.len(), 2);
+        assert!(todo.pending.iter().any(|task| task.subject == "call mom"));
+        Ok(())
+    }
+
+    #[test]
+    fn flush() -> Result<(), Box<dyn std::error::Error>> {
+        let mut todo = ToDo::default();
+        todo.config.trash_size = 2;
+        for task in ["task 1", "task 2", "task 3", "task 4"] {
+            todo.new_task(task)?;
+        }
+        for _ in 0..3 {
+            todo.remove_task(ToDoData::Pending, 0);
+        }
+        let flushed = todo.take_flushed();
+        assert_eq!(flushed.len(), 1);
+        assert_eq!(flushed[0].task.subject, "task 1");
+        assert_eq!(todo.trash().len(), 2);
+        assert!(todo.take_flushed().is_empty());
+
+        todo.flush_trash();
+        assert!(todo.trash().is_empty());
+        let flushed = todo.take_flushed();
+        assert_eq!(flushed.len(), 2);
+
+        let path = temp_dir().join(format!("todotxt-tui-trash-test-{}.txt", process::id()));
+        let _ = fs::remove_file(&path);
+        append_trash(&path, &flushed)?;
+        append_trash(&path, &[])?;
+        let content = fs::read_to_string(&path)?;
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("task 2"));
+        assert!(lines[1].ends_with("task 3"));
+        fs::remove_file(&path)?;
+
+        // Without the trash deleted tasks are flushed right away.
+        todo.config.trash_size = 0;
+        todo.remove_task(ToDoData::Pending, 0);
+        assert!(todo.trash().is_empty());
+        assert_eq!(todo.take_flushed().len(), 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,237 @@
+use super::{TaskList, TaskSort, ToDoCategory, ToDoData};
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use todo_txt::Task;
+
+/// Represents the weights of the parts of the urgency score of a task, in the manner
+/// of taskwarrior. Every part is a factor from 0 to 1 multiplied by its weight:
+///
+/// - `priority`: `(A)` counts fully, `(B)` 0.65, `(C)` 0.3 and lower priorities 0.1.
+/// - `due`: 0.2 for tasks due in two weeks or later, rising to 1 for tasks overdue
+///   by a week or more.
+/// - `age`: The age from the creation date, full after a year.
+///
+/// The weights of projects are added to tasks of the project or its subprojects.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UrgencyWeights {
+    priority: f64,
+    due: f64,
+    age: f64,
+    projects: Vec<(String, f64)>,
+}
+
+impl Default for UrgencyWeights {
+    fn default() -> Self {
+        Self {
+            priority: 6.0,
+            due: 12.0,
+            age: 2.0,
+            projects: Vec::new(),
+        }
+    }
+}
+
+impl UrgencyWeights {
+    /// Creates the weights from the `urgency_weights` table, parts that are not set
+    /// keep their default weights.
+    ///
+    /// # Arguments
+    ///
+    /// * `weights` - The weights by the name of the part or the project, e.g. `+work`.
+    pub fn new(weights: &HashMap<String, f64>) -> Self {
+        let mut urgency = Self::default();
+        for (name, weight) in weights {
+            match name.as_str() {
+                "priority" => urgency.priority = *weight,
+                "due" => urgency.due = *weight,
+                "age" => urgency.age = *weight,
+                name => match name.strip_prefix('+') {
+                    Some(project) => urgency.projects.push((project.to_string(), *weight)),
+                    None => log::warn!("Unknown urgency weight '{name}'"),
+                },
+            }
+        }
+        urgency
+    }
+
+    /// Computes the urgency score of the task.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The task.
+    /// * `today` - The date the due date and the age are computed to.
+    pub fn score(&self, task: &Task, today: NaiveDate) -> f64 {
+        let priority = match u8::from(task.priority.clone()) {
+            _ if task.priority.is_lowest() => 0.0,
+            priority => (1.0 - 0.35 * priority as f64).max(0.1),
+        };
+        let due = task.due_date.map_or(0.0, |due| {
+            let overdue = (today - due).num_days() as f64;
+            ((overdue + 14.0) * 0.8 / 21.0 + 0.2).clamp(0.2, 1.0)
+        });
+        let age = task.create_date.map_or(0.0, |created| {
+            ((today - created).num_days() as f64 / 365.0).clamp(0.0, 1.0)
+        });
+        let projects: f64 = self
+            .projects
+            .iter()
+            .filter(|(name, _)| {
+                task.projects()
+                    .iter()
+                    .any(|project| ToDoCategory::Projects.matches(project, name))
+            })
+            .map(|(_, weight)| weight)
+            .sum();
+        self.priority * priority + self.due * due + self.age * age + projects
+    }
+}
+
+/// Represents the urgency scores of all tasks, computed again when the tasks
+/// or the date change.
+#[derive(Debug, Default)]
+pub struct UrgencyCache {
+    version: usize,
+    today: Option<NaiveDate>,
+    pending: Vec<f64>,
+    done: Vec<f64>,
+}
+
+impl UrgencyCache {
+    /// Computes the scores of the tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The version of the tasks.
+    /// * `today` - The date the scores are computed to.
+    /// * `weights` - The weights of the parts of the score.
+    /// * `pending` - The pending tasks.
+    /// * `done` - The done tasks.
+    pub fn new(
+        version: usize,
+        today: NaiveDate,
+        weights: &UrgencyWeights,
+        pending: &[Task],
+        done: &[Task],
+    ) -> Self {
+        let scores = |tasks: &[Task]| {
+            tasks
+                .iter()
+                .map(|task| weights.score(task, today))
+                .collect()
+        };
+        Self {
+            version,
+            today: Some(today),
+            pending: scores(pending),
+            done: scores(done),
+        }
+    }
+
+    /// Checks whether the scores were computed for the tasks of the version on the date.
+    pub fn is_current(
+        &self,
+        version: usize,
+        today: NaiveDate,
+        pending: usize,
+        done: usize,
+    ) -> bool {
+        self.version == version
+            && self.today == Some(today)
+            && self.pending.len() == pending
+            && self.done.len() == done
+    }
+
+    /// Sorts the task list by the urgency if the sorting is `Urgency` or `UrgencyReverse`.
+    /// The sort is stable, so tasks with the same score keep the order given by the ties.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_list` - The task list sorted by the ties.
+    /// * `data` - The data the tasks of the list belong to.
+    /// * `sort` - The sorting of the list.
+    pub fn sort(&self, task_list: &mut TaskList, data: ToDoData, sort: TaskSort) {
+        let scores = match data {
+            ToDoData::Pending => &self.pending,
+            ToDoData::Done => &self.done,
+        };
+        let score = |index: &usize| scores.get(*index).copied().unwrap_or_default();
+        match sort {
+            TaskSort::Urgency => task_list
+                .vec
+                .sort_by(|(a, _), (b, _)| score(b).total_cmp(&score(a))),
+            TaskSort::UrgencyReverse => task_list
+                .vec
+                .sort_by(|(a, _), (b, _)| score(a).total_cmp(&score(b))),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn score(weights: &UrgencyWeights, task: &str) -> f64 {
+        let today = NaiveDate::from_ymd_opt(2023, 5, 1).unwrap();
+        let score = weights.score(&Task::from_str(task).unwrap(), today);
+        (score * 100.0).round() / 100.0
+    }
+
+    #[test]
+    fn scores() {
+        let weights = UrgencyWeights::default();
+        assert_eq!(score(&weights, "call mom"), 0.0);
+        assert_eq!(score(&weights, "(A) call mom"), 6.0);
+        assert_eq!(score(&weights, "(B) call mom"), 3.9);
+        assert_eq!(score(&weights, "(Z) call mom"), 0.6);
+        assert_eq!(score(&weights, "call mom due:2023-04-24"), 12.0);
+        assert_eq!(score(&weights, "call mom due:2023-04-01"), 12.0);
+        assert_eq!(score(&weights, "call mom due:2023-05-15"), 2.4);
+        assert_eq!(score(&weights, "call mom due:2023-08-01"), 2.4);
+        assert_eq!(score(&weights, "call mom due:2023-05-01"), 8.8);
+        assert_eq!(score(&weights, "2022-05-01 call mom"), 2.0);
+        assert_eq!(score(&weights, "2023-05-01 call mom"), 0.0);
+
+        let weights = UrgencyWeights::new(&HashMap::from([
+            (String::from("priority"), 1.0),
+            (String::from("due"), 0.0),
+            (String::from("+home"), 3.0),
+            (String::from("+work"), -1.0),
+        ]));
+        assert_eq!(score(&weights, "(A) water plants +home.garden"), 4.0);
+        assert_eq!(score(&weights, "(A) call boss +work +home"), 3.0);
+        assert_eq!(score(&weights, "call boss +homework due:2023-04-01"), 0.0);
+    }
+
+    #[test]
+    fn sort() {
+        let today = NaiveDate::from_ymd_opt(2023, 5, 1).unwrap();
+        let tasks: Vec<Task> = [
+            "(C) call mom",
+            "buy milk",
+            "pay rent due:2023-05-01",
+            "(A) x",
+        ]
+        .iter()
+        .map(|task| Task::from_str(task).unwrap())
+        .collect();
+        let cache = UrgencyCache::new(3, today, &UrgencyWeights::default(), &tasks, &[]);
+        assert!(cache.is_current(3, today, 4, 0));
+        assert!(!cache.is_current(4, today, 4, 0));
+        assert!(!cache.is_current(3, today.succ_opt().unwrap(), 4, 0));
+        assert!(!cache.is_current(3, today, 5, 0));
+
+        let styles = Default::default();
+        let mut list = TaskList {
+            vec: tasks.iter().enumerate().collect(),
+            styles: &styles,
+        };
+        cache.sort(&mut list, ToDoData::Pending, TaskSort::Urgency);
+        let order: Vec<usize> = list.vec.iter().map(|(index, _)| *index).collect();
+        assert_eq!(order, [2, 3, 0, 1]);
+        cache.sort(&mut list, ToDoData::Pending, TaskSort::UrgencyReverse);
+        let order: Vec<usize> = list.vec.iter().map(|(index, _)| *index).collect();
+        assert_eq!(order, [1, 0, 3, 2]);
+    }
+}
@@ -0,0 +1,210 @@
+use super::ToDo;
+use crate::error::{ToDoError, ToDoRes};
+use chrono::{Datelike, Duration, NaiveDateTime, NaiveTime, Weekday};
+use std::{collections::HashMap, str::FromStr};
+use todo_txt::Task;
+
+/// Represents the hours when tasks of a context can be acted on, e.g. `9-17 weekdays`.
+///
+/// The hours are given as `FROM-TO` with optional minutes, e.g. `8:30-17`, the end is
+/// not included and hours ending before they start pass midnight, e.g. `22-6`.
+/// The days follow the hours and are one of `weekdays`, `weekends`, `daily` or days
+/// of the week separated by commas, e.g. `mon,wed,fri`. The hours apply every day
+/// if the days are left out.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WorkingHours {
+    from: NaiveTime,
+    to: NaiveTime,
+    days: Vec<Weekday>,
+}
+
+impl WorkingHours {
+    /// Checks whether the given moment is within the working hours.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The checked moment, hours passing midnight belong to the day they start.
+    pub fn contains(&self, now: NaiveDateTime) -> bool {
+        let time = now.time();
+        let (day, within) = match self.from <= self.to {
+            true => (now.date(), self.from <= time && time < self.to),
+            false if time >= self.from => (now.date(), true),
+            false => (now.date() - Duration::days(1), time < self.to),
+        };
+        within && self.days.contains(&day.weekday())
+    }
+
+    /// Gets the next moment the working hours start or end after the given moment.
+    pub fn next_change(&self, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        (0..=1)
+            .flat_map(|days| {
+                let date = now.date() + Duration::days(days);
+                [date.and_time(self.from), date.and_time(self.to)]
+            })
+            .filter(|change| *change > now)
+            .min()
+    }
+}
+
+impl FromStr for WorkingHours {
+    type Err = ToDoError;
+
+    fn from_str(s: &str) -> ToDoRes<Self> {
+        let err = || ToDoError::ParseWorkingHours(s.to_string());
+        let mut words = s.split_whitespace();
+        let (from, to) = words
+            .next()
+            .and_then(|hours| hours.split_once('-'))
+            .ok_or_else(err)?;
+        let time = |time: &str| {
+            let (hour, minute) = time.split_once(':').unwrap_or((time, "0"));
+            match (hour.parse::<u32>().ok()?, minute.parse::<u32>().ok()?) {
+                // The end of the day is the midnight of the next day.
+                (24, 0) => Some(NaiveTime::MIN),
+                (hour, minute) => NaiveTime::from_hms_opt(hour, minute, 0),
+            }
+        };
+        let (from, to) = (time(from).ok_or_else(err)?, time(to).ok_or_else(err)?);
+        let week = [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ];
+        let days = match words.next() {
+            None | Some("daily") => week.to_vec(),
+            Some("weekdays") => week[..5].to_vec(),
+            Some("weekends") => week[5..].to_vec(),
+            Some(days) => days
+                .split(',')
+                .map(|day| Weekday::from_str(day).map_err(|_| err()))
+                .collect::<ToDoRes<_>>()?,
+        };
+        if words.next().is_some() {
+            return Err(err());
+        }
+        Ok(Self { from, to, days })
+    }
+}
+
+/// Working hours of contexts, tasks of a context are dimmed outside its hours.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContextHours(HashMap<String, WorkingHours>);
+
+impl ContextHours {
+    /// Parses the working hours of the configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `hours` - Working hours by the names of contexts, the `@` of the names is optional.
+    pub fn parse_all(hours: &HashMap<String, String>) -> ToDoRes<Self> {
+        hours
+            .iter()
+            .map(|(context, hours)| {
+                let context = context.strip_prefix('@').unwrap_or(context);
+                Ok((context.to_string(), hours.parse()?))
+            })
+            .collect::<ToDoRes<_>>()
+            .map(Self)
+    }
+
+    /// Checks whether the task cannot be acted on at the given moment. That is when
+    /// the task has contexts with working hours and none of them is within its hours.
+    pub fn is_off_hours(&self, task: &Task, now: NaiveDateTime) -> bool {
+        let mut hours = task
+            .contexts()
+            .iter()
+            .filter_map(|context| self.0.get(context))
+            .peekable();
+        hours.peek().is_some() && !hours.any(|hours| hours.contains(now))
+    }
+
+    /// Gets the contexts within their working hours at the given moment, ordered by name.
+    pub fn open(&self, now: NaiveDateTime) -> Vec<String> {
+        let mut open: Vec<String> = self
+            .0
+            .iter()
+            .filter(|(_, hours)| hours.contains(now))
+            .map(|(context, _)| context.clone())
+            .collect();
+        open.sort();
+        open
+    }
+
+    /// Gets the next moment working hours of any context start or end.
+    pub fn next_change(&self, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        self.0
+            .values()
+            .filter_map(|hours| hours.next_change(now))
+            .min()
+    }
+}
+
+impl ToDo {
+    /// Checks whether the task is outside the working hours of its contexts now.
+    pub fn is_off_hours(&self, task: &Task) -> bool {
+        self.config
+            .context_hours
+            .is_off_hours(task, self.config.clock.now())
+    }
+
+    /// Gets the contexts within their working hours now, ordered by name.
+    pub fn open_contexts(&self) -> Vec<String> {
+        self.config.context_hours.open(self.config.clock.now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        // 2024-07-01 is a Monday.
+        NaiveDate::from_ymd_opt(2024, 7, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn working_hours() -> ToDoRes<()> {
+        let office = WorkingHours::from_str("9-17 weekdays")?;
+        assert!(office.contains(at(1, 9)));
+        assert!(!office.contains(at(1, 17)));
+        assert!(!office.contains(at(6, 10)));
+        assert_eq!(office.next_change(at(1, 12)), Some(at(1, 17)));
+        assert_eq!(office.next_change(at(1, 18)), Some(at(2, 9)));
+
+        let night = WorkingHours::from_str("22-6 fri")?;
+        assert!(night.contains(at(5, 23)));
+        assert!(night.contains(at(6, 5)));
+        assert!(!night.contains(at(6, 23)));
+        assert!(WorkingHours::from_str("18:30-24")?.contains(at(7, 23)));
+        for invalid in ["", "9", "9-25", "9-17 someday", "9-17 mon tue"] {
+            assert_eq!(
+                WorkingHours::from_str(invalid),
+                Err(ToDoError::ParseWorkingHours(invalid.to_string()))
+            );
+        }
+
+        let hours = ContextHours::parse_all(&HashMap::from([
+            (String::from("@office"), String::from("9-17 weekdays")),
+            (
+                String::from("shop"),
+                String::from("8-20 mon,tue,wed,thu,fri,sat"),
+            ),
+        ]))?;
+        let task = |s: &str| Task::from_str(s).unwrap();
+        assert!(hours.is_off_hours(&task("write the report @office"), at(1, 18)));
+        assert!(!hours.is_off_hours(&task("write the report @office"), at(1, 10)));
+        assert!(!hours.is_off_hours(&task("print it @office @shop"), at(1, 18)));
+        assert!(!hours.is_off_hours(&task("call mom @phone"), at(1, 23)));
+        assert_eq!(hours.open(at(6, 10)), ["shop"]);
+        assert_eq!(hours.next_change(at(1, 18)), Some(at(1, 20)));
+        Ok(())
+    }
+}
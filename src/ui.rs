@@ -1,20 +1,102 @@
+mod action_bar;
+mod activity_view;
+mod archive_results;
+mod breadcrumb;
+mod category_prompt;
+mod changelog_view;
+mod clipboard;
+mod command;
+mod compare_view;
+mod completion_popup;
+mod confirm_prompt;
+mod create_prompt;
+mod deadline_report;
+mod effort_report;
+mod error_screen;
+mod filter_bar;
+mod forecast_report;
+mod health_report;
+mod history_report;
+mod input_history;
+mod keybind_editor;
+mod planner_view;
+mod popup;
+mod quick_add;
+mod review_mode;
+mod save_review;
+mod status_bar;
+mod switcher;
+mod toast;
+mod trash_view;
+mod triage_mode;
 mod ui_event;
 mod ui_state;
 
+pub use action_bar::*;
+pub use activity_view::*;
+pub use archive_results::*;
+pub use breadcrumb::*;
+pub use category_prompt::*;
+pub use changelog_view::*;
+pub use clipboard::*;
+pub use command::*;
+pub use compare_view::*;
+pub use completion_popup::*;
+pub use confirm_prompt::*;
+pub use create_prompt::*;
+pub use deadline_report::*;
+pub use effort_report::*;
+pub use error_screen::*;
+pub use filter_bar::*;
+pub use forecast_report::*;
+pub use health_report::*;
+pub use history_report::*;
+pub use input_history::*;
+pub use keybind_editor::*;
+pub use planner_view::*;
+pub use popup::*;
+pub use quick_add::*;
+pub use review_mode::*;
+pub use save_review::*;
+pub use status_bar::*;
+pub use switcher::*;
+pub use toast::*;
+pub use trash_view::*;
+pub use triage_mode::*;
 pub use ui_event::*;
 pub use ui_state::*;
 
 use crate::{
-    config::Config,
-    file_worker::{FileWorker, FileWorkerCommands},
+    activity_log::ActivityLog,
+    clock::Clock,
+    clock::{ticked, until_tick},
+    config::{key_name, ColorSupport, Config, ConfigWatcher, KeybindScope, Styles, ToDoConfig},
+    file_worker::{FileWorker, FileWorkerCommands, FileWorkerError, Problem},
+    inbox::Inbox,
+    instance::{default_signal_path, InstanceLock, RunningInstance},
+    layout::widget::widget_type::WidgetType,
     layout::Layout,
     layout::Render,
-    todo::autocomplete,
-    todo::ToDo,
+    profiles::Profiles,
+    scripts::Scripts,
+    signals::Signals,
+    status,
+    todo::{autocomplete, scratch_line, trash::append_trash},
+    todo::{
+        Action, DateExpr, ExportFormat, History, HistoryCache, ImportFormat, MetaStore,
+        ProjectInfo, Review, ReviewAction, TaskId, ToDo, ToDoData,
+    },
+    update_check::{check_update, Release},
+    ToDoError, ToDoIoError, ToDoRes,
 };
+use chrono::{NaiveDate, NaiveDateTime};
 use crossterm::{
     self,
-    event::{self, read, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    cursor::Show,
+    event::{
+        self, read, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent,
+        KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{
         disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen, SetTitle,
@@ -22,45 +104,148 @@ use crossterm::{
     ExecutableCommand,
 };
 use std::{
+    env,
     error::Error,
-    io,
-    path::PathBuf,
-    sync::mpsc::Sender,
+    fmt::Write as _,
+    fs, io, panic,
+    path::{Path, PathBuf},
+    process::{self, Stdio},
+    sync::mpsc::{self, Receiver, Sender, TryRecvError},
     sync::{Arc, Mutex},
-    time::Duration,
+    thread,
+    time::{Duration, Instant},
 };
 use tui::{
     backend::{Backend, CrosstermBackend},
-    layout::{Constraint, Direction, Layout as tuiLayout, Rect},
-    style::{Color, Style},
+    layout::{Alignment, Constraint, Direction, Layout as tuiLayout, Rect},
+    style::{Color, Modifier, Style},
+    text::Span,
     widgets::Paragraph,
-    widgets::{Block, BorderType, Borders},
+    widgets::{block::Title, Block, BorderType, Borders, Clear},
     Terminal,
 };
-use tui_input::{backend::crossterm::EventHandler, Input};
+use tui_input::{backend::crossterm::EventHandler, Input, InputRequest};
+
+/// How long quitting waits for the file worker to save the todo list.
+const SAVE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often the results of the archive search are refreshed while the done file is searched.
+const ARCHIVE_SEARCH_REFRESH: Duration = Duration::from_millis(100);
 
 /// Enum representing the different modes of the UI.
 #[derive(Debug, PartialEq, Eq)]
 enum Mode {
     Input,
     Edit,
+    Command,
+    Search,
+    Triage,
+    Review,
+    Fix,
+    Due,
+    Export,
+    Compare,
+    Filter,
     Normal,
 }
 
 /// The struct representing the UI for the application.
 pub struct UI {
     input_chunk: Rect,
+    input_overlay: bool,
+    layout_chunk: Rect,
+    breadcrumb_chunk: Rect,
+    filter_bar_chunk: Rect,
+    action_bar_chunk: Rect,
+    status_bar_chunk: Rect,
+    main_chunk: Rect,
     tinput: Input,
+    input_history: InputHistory,
     layout: Layout,
     mode: Mode,
     data: Arc<Mutex<ToDo>>,
     tx: Sender<FileWorkerCommands>,
+    errors: Receiver<FileWorkerError>,
+    error: Option<ErrorScreen>,
     event_handler: EventHandlerUI,
+    review_keybind: EventHandlerUI,
     quit: bool,
+    suspend: bool,
     window_title: String,
     list_refresh_rate: Duration,
     active_color: Color,
+    color_support: ColorSupport,
     save_state_path: Option<PathBuf>,
+    inbox_path: Option<String>,
+    triage: Option<TriageMode>,
+    /// The review of stale tasks in progress.
+    review: Option<ReviewMode>,
+    review_age: usize,
+    signals: Option<Signals>,
+    /// The lock of the todo file, it is removed when the UI is dropped.
+    instance: Option<InstanceLock>,
+    /// The instance editing the todo file, the file is opened read-only then.
+    other_instance: Option<RunningInstance>,
+    toast: Option<Toast>,
+    reminders_checked: NaiveDateTime,
+    countdown_checked: NaiveDateTime,
+    timer_checked: NaiveDateTime,
+    refreshed_at: NaiveDateTime,
+    clock_format: String,
+    alarms_checked: NaiveDate,
+    open_contexts: Vec<String>,
+    reminder_command: String,
+    due_notifications: bool,
+    clipboard_command: String,
+    /// Text put to the clipboard by the terminal, the main loop writes it to the terminal.
+    terminal_clipboard: Option<String>,
+    /// Receives the newer release found by the update check at startup.
+    update_check: Option<Receiver<Release>>,
+    release: Option<Release>,
+    review_save: bool,
+    scratch_input: bool,
+    confirm_prompt: Option<ConfirmPrompt>,
+    confirm_delete: bool,
+    confirm_archive: bool,
+    confirm_quit: bool,
+    confirm_task_rules: bool,
+    create_prompt: Option<CreatePrompt>,
+    read_only: bool,
+    profiles: Profiles,
+    scripts: Scripts,
+    todo_path: String,
+    archive_path: Option<String>,
+    dry_run: bool,
+    health_report: Option<HealthReport>,
+    fixing: Option<Problem>,
+    due_task: Option<(ToDoData, TaskId)>,
+    export_path: String,
+    compare_path: String,
+    trash_path: Option<String>,
+    /// Completes the category typed at the cursor while a task is written.
+    completion: Option<CompletionPopup>,
+    /// The popup open over the window, e.g. a report or the planner.
+    popup: Option<Popup>,
+    /// The file and the line opened in the editor by the main loop.
+    open_editor: Option<(String, usize)>,
+    confirm_new_categories: bool,
+    category_prompt: Option<CategoryPrompt>,
+    categories_confirmed: bool,
+    activity_log: Option<ActivityLog>,
+    escalated_on: Option<NaiveDate>,
+    ruled_version: Option<usize>,
+    breadcrumb: Option<Breadcrumb>,
+    action_bar: Option<ActionBar>,
+    status_bar: Option<StatusBar>,
+    filter_bar: FilterBar,
+    show_filter_bar: bool,
+    quick_add_filters: bool,
+    config_watcher: Option<ConfigWatcher>,
+    history: HistoryCache,
+    save_delay: Duration,
+    changed_at: Option<Instant>,
+    clock: Clock,
+    config: Config,
 }
 
 impl UI {
@@ -71,6 +256,7 @@ impl UI {
     /// * `layout` - The initial layout configuration for the UI.
     /// * `data` - Shared data representing the to-do list.
     /// * `tx` - Sender for communicating with the file worker.
+    /// * `errors` - Receiver of errors reported by the file worker.
     ///
     /// # Returns
     ///
@@ -79,46 +265,346 @@ impl UI {
         layout: Layout,
         data: Arc<Mutex<ToDo>>,
         tx: Sender<FileWorkerCommands>,
+        errors: Receiver<FileWorkerError>,
         config: &Config,
     ) -> UI {
+        let open_contexts = data.lock().unwrap().open_contexts();
         UI {
             input_chunk: Rect::default(),
+            input_overlay: config.get_input_overlay(),
+            layout_chunk: Rect::default(),
+            breadcrumb_chunk: Rect::default(),
+            filter_bar_chunk: Rect::default(),
+            action_bar_chunk: Rect::default(),
+            status_bar_chunk: Rect::default(),
+            main_chunk: Rect::default(),
             tinput: Input::default(),
+            input_history: Self::load_input_history(config),
             layout,
             mode: Mode::Normal,
             data,
             tx,
+            errors,
+            error: None,
             event_handler: config.get_window_keybind(),
+            review_keybind: config.get_review_keybind(),
             quit: false,
+            suspend: false,
             window_title: config.get_window_title(),
             list_refresh_rate: config.get_list_refresh_rate(),
             active_color: config.get_active_color(),
+            color_support: config.get_color_support().resolve(),
             save_state_path: config.get_save_state_path(),
+            inbox_path: config.get_inbox_path(),
+            review: None,
+            review_age: config.get_review_age(),
+            signals: config.get_signal_path().map(Signals::new),
+            instance: None,
+            other_instance: None,
+            triage: None,
+            toast: None,
+            reminders_checked: config.get_clock().now(),
+            countdown_checked: config.get_clock().now(),
+            timer_checked: config.get_clock().now(),
+            refreshed_at: config.get_clock().now(),
+            clock_format: config.get_clock_format(),
+            alarms_checked: config.get_clock().today(),
+            open_contexts,
+            reminder_command: config.get_reminder_command(),
+            due_notifications: config.get_due_notifications(),
+            clipboard_command: config.get_clipboard_command(),
+            terminal_clipboard: None,
+            update_check: config
+                .get_update_check()
+                .then(|| check_update(env!("CARGO_PKG_VERSION"))),
+            release: None,
+            review_save: config.get_review_save(),
+            scratch_input: false,
+            confirm_prompt: None,
+            confirm_delete: config.get_confirm_delete(),
+            confirm_archive: config.get_confirm_archive(),
+            confirm_quit: config.get_confirm_quit(),
+            confirm_task_rules: config.get_confirm_task_rules(),
+            create_prompt: None,
+            read_only: false,
+            profiles: Profiles::new(config),
+            scripts: Scripts::new(config),
+            todo_path: config.get_todo_path(),
+            archive_path: config.get_archive_path(),
+            dry_run: config.get_dry_run(),
+            health_report: None,
+            fixing: None,
+            due_task: None,
+            export_path: String::new(),
+            compare_path: String::new(),
+            trash_path: config.get_trash_path(),
+            completion: None,
+            popup: None,
+            open_editor: None,
+            confirm_new_categories: config.get_confirm_new_categories(),
+            category_prompt: None,
+            categories_confirmed: false,
+            activity_log: config.get_activity_log_path().map(ActivityLog::new),
+            escalated_on: None,
+            ruled_version: None,
+            breadcrumb: config.get_breadcrumb().then(|| Breadcrumb::new(config)),
+            action_bar: config.get_action_bar().then(|| ActionBar::new(config)),
+            status_bar: config.get_status_bar().then(StatusBar::default),
+            filter_bar: FilterBar::default(),
+            show_filter_bar: config.get_filter_bar(),
+            quick_add_filters: config.get_quick_add_filters(),
+            config_watcher: config
+                .get_watch_config()
+                .then(|| ConfigWatcher::new(config.get_config_path()))
+                .and_then(|watcher| {
+                    watcher
+                        .map_err(|e| log::error!("Cannot watch the configuration file: {}", e))
+                        .ok()
+                }),
+            history: HistoryCache::default(),
+            save_delay: config.get_save_delay(),
+            changed_at: None,
+            clock: config.get_clock(),
+            config: config.clone(),
         }
     }
 
+    /// Builds the UI from the configuration and loads the todo list.
+    ///
+    /// Failures of loading the todo list or the saved state do not stop the
+    /// application, they are shown to the user once the UI is running.
     pub fn build(config: &Config) -> Result<UI, Box<dyn Error>> {
-        let mut todo = ToDo::new(config);
+        config.validate()?;
+        let (file_config, instance, other_instance) = Self::lock_todo_file(config);
+        let mut todo = ToDo::new(ToDoConfig::from(config), Styles::from(config));
+        let mut error = None;
+
+        let mut state = None;
+        if let Some(path) = config.get_save_state_path().filter(|path| path.exists()) {
+            match UIState::load(&path) {
+                Ok(loaded) => state = Some(loaded),
+                Err(e) => {
+                    error = Some(ErrorScreen::new(format!("Cannot load UI state: {e}"), None))
+                }
+            }
+        }
 
-        if let Some(path) = &config.get_save_state_path() {
-            let state = UIState::load(path)?;
-            let (_active, todo_state) = (state.active, state.todo_state);
-            todo.update_state(todo_state);
+        if let Some(e) = Self::load_todo_extras(config, &mut todo) {
+            error = Some(e);
         }
 
         let todo = Arc::new(Mutex::new(todo));
-        let file_worker = FileWorker::new(
+        let (tx, errors, problems, load_error) = Self::start_file_worker(&file_config, &todo);
+        if let Some(e) = load_error {
+            error = Some(e);
+        }
+        {
+            // The state is restored to the loaded tasks, the todo file may have changed since.
+            let mut todo = todo.lock().unwrap();
+            if let Some(state) = &state {
+                state.restore_todo(&mut todo);
+            }
+            if let Some(name) = config.get_init_filter() {
+                todo.set_filters(config.get_list_query(&name)?.filters());
+            }
+        }
+
+        let (mut layout, layout_error) = Layout::from_config(todo.clone(), config)?;
+        if let Some(e) = layout_error {
+            error = Some(ErrorScreen::new(
+                format!("The layout is not valid, the default layout is used: {e}"),
+                None,
+            ));
+        }
+        let restored = state
+            .as_ref()
+            .is_some_and(|state| state.restore_layout(&mut layout));
+        if !restored && !layout.focus_widget(config.get_init_widget()) {
+            log::warn!(
+                "The layout does not have a focusable {} widget",
+                config.get_init_widget()
+            );
+        }
+
+        let mut ui = UI::new(layout, todo, tx.clone(), errors, config);
+        ui.error = error;
+        let conflicts: Vec<String> = config
+            .keybind_conflicts()
+            .iter()
+            .map(|conflict| conflict.to_string())
+            .chain(
+                config
+                    .script_keybind_conflicts()
+                    .iter()
+                    .map(|conflict| conflict.to_string()),
+            )
+            .collect();
+        if !conflicts.is_empty() {
+            conflicts
+                .iter()
+                .for_each(|conflict| log::warn!("Keybinding conflict: {conflict}"));
+            ui.toast = Some(Toast::new(
+                "Keybinding conflicts",
+                conflicts,
+                Duration::from_secs(30),
+            ));
+        }
+        if !problems.is_empty() {
+            ui.health_report = Some(HealthReport::new(problems));
+        }
+        ui.create_prompt = Self::missing_file_prompt(config);
+        ui.set_instance(&file_config, instance, other_instance);
+        Ok(ui)
+    }
+
+    /// Locks the todo file of the configuration for this instance, see [`InstanceLock`].
+    /// The todo file locked by another instance is opened read-only, the changes are kept
+    /// only in memory as in the dry run, so the instances do not overwrite each other.
+    ///
+    /// # Returns
+    ///
+    /// The configuration the todo file is opened with, the lock and the other instance.
+    fn lock_todo_file(config: &Config) -> (Config, Option<InstanceLock>, Option<RunningInstance>) {
+        if config.get_dry_run() {
+            return (config.clone(), None, None);
+        }
+        let todo_path = config.get_todo_path();
+        let signal_path = config
+            .get_signal_path()
+            .map_or_else(|| default_signal_path(&todo_path), PathBuf::from);
+        match InstanceLock::acquire(&todo_path, &signal_path) {
+            Ok(Ok(lock)) => (config.clone(), Some(lock), None),
+            Ok(Err(running)) => {
+                log::warn!("The todo file is edited by the instance {}", running.pid);
+                (config.with_dry_run(), None, Some(running))
+            }
+            Err(e) => {
+                log::error!("Cannot lock the todo file {}: {}", todo_path, e);
+                (config.clone(), None, None)
+            }
+        }
+    }
+
+    /// Keeps the lock of the todo file, the locking instance listens on its signal file,
+    /// so other programs and instances can send it tasks. The read-only instance listens
+    /// on none, the signals are left to the instance editing the file.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration the todo file is opened with.
+    /// * `instance` - The lock of the todo file.
+    /// * `other_instance` - The instance editing the todo file.
+    fn set_instance(
+        &mut self,
+        config: &Config,
+        instance: Option<InstanceLock>,
+        other_instance: Option<RunningInstance>,
+    ) {
+        self.signals = match (&instance, &other_instance) {
+            (Some(lock), _) => Some(Signals::new(lock.signal_path())),
+            (None, Some(_)) => None,
+            (None, None) => config.get_signal_path().map(Signals::new),
+        };
+        self.dry_run = config.get_dry_run();
+        if let Some(running) = &other_instance {
+            self.toast = Some(Toast::new(
+                "Read-only",
+                vec![
+                    format!(
+                        "The todo file is edited by another instance (process {}).",
+                        running.pid
+                    ),
+                    String::from("Changes are not saved, add tasks to it with --add."),
+                ],
+                Duration::from_secs(30),
+            ));
+        }
+        self.instance = instance;
+        self.other_instance = other_instance;
+    }
+
+    /// Loads the project information and the task metadata of the todo file.
+    ///
+    /// # Returns
+    ///
+    /// The error shown to the user if any of them cannot be loaded.
+    fn load_todo_extras(config: &Config, todo: &mut ToDo) -> Option<ErrorScreen> {
+        let mut error = None;
+        match ProjectInfo::load_all(config.get_projects_path()) {
+            Ok(projects) => todo.set_projects(projects),
+            Err(e) => {
+                error = Some(ErrorScreen::new(
+                    format!("Cannot load the projects file: {e}"),
+                    None,
+                ))
+            }
+        }
+
+        match MetaStore::load(config.get_metadata_path()) {
+            Ok(meta) => todo.set_meta(meta),
+            Err(e) => {
+                error = Some(ErrorScreen::new(
+                    format!("Cannot load the task metadata: {e}"),
+                    None,
+                ))
+            }
+        }
+        error
+    }
+
+    /// Loads the todo file of the configuration and starts the file worker saving it.
+    ///
+    /// # Returns
+    ///
+    /// The sender of commands and the receiver of errors of the file worker, the lines
+    /// of the file with problems and the error of loading the file shown to the user.
+    fn start_file_worker(
+        config: &Config,
+        todo: &Arc<Mutex<ToDo>>,
+    ) -> (
+        Sender<FileWorkerCommands>,
+        Receiver<FileWorkerError>,
+        Vec<Problem>,
+        Option<ErrorScreen>,
+    ) {
+        let mut file_worker = FileWorker::new(
             config.get_todo_path(),
             config.get_archive_path(),
             todo.clone(),
         );
+        file_worker.set_dry_run(config.get_dry_run());
+        file_worker.set_unsaved_path(config.get_unsaved_path());
 
-        file_worker.load()?;
-        let tx = file_worker.run(config.get_autosave_duration(), config.get_file_watcher());
-
-        let layout = Layout::from_str(&config.get_layout(), todo.clone(), config)?;
+        let mut error = None;
+        let remote = config
+            .get_todo_url()
+            .map_or(Ok(()), |url| file_worker.set_remote_url(&url));
+        if let Err(e) = remote.and_then(|_| file_worker.load()) {
+            log::error!("Cannot load todo list: {}", e);
+            error = Some(ErrorScreen::new(
+                format!(
+                    "Cannot load todo list from {}: {e}",
+                    config.get_todo_url().unwrap_or(config.get_todo_path())
+                ),
+                Some(UIEvent::Load),
+            ));
+        }
+        let problems = file_worker.problems().to_vec();
+        let (errors_tx, errors) = mpsc::channel();
+        let tx = file_worker.run(
+            config.get_autosave_duration(),
+            config.get_file_watcher(),
+            errors_tx,
+        );
+        (tx, errors, problems, error)
+    }
 
-        Ok(UI::new(layout, todo, tx.clone(), config))
+    /// Gets the prompt offering to create the todo file if it does not exist.
+    fn missing_file_prompt(config: &Config) -> Option<CreatePrompt> {
+        let path = config.get_todo_path();
+        // A missing remote file is created by the first save.
+        (!config.get_dry_run() && config.get_todo_url().is_none() && !Path::new(&path).exists())
+            .then(|| CreatePrompt::new(path))
     }
 
     /// Updates the input chunk of the UI based on the main chunk's dimensions.
@@ -130,56 +616,101 @@ impl UI {
     ///
     /// * `main_chunk` - The main chunk's dimensions, typically representing the entire terminal window.
     fn update_chunk(&mut self, main_chunk: Rect) {
+        let input_height = if self.input_overlay { 0 } else { 3 };
+        let breadcrumb_height = if self.breadcrumb.is_some() { 1 } else { 0 };
+        let filter_bar_height = if self.filter_bar_visible() { 1 } else { 0 };
+        let action_bar_height = if self.action_bar.is_some() { 1 } else { 0 };
+        let status_bar_height = if self.status_bar.is_some() { 1 } else { 0 };
         let layout = tuiLayout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Length(3), Constraint::Min(1)])
+            .constraints([
+                Constraint::Length(input_height),
+                Constraint::Length(breadcrumb_height),
+                Constraint::Length(filter_bar_height),
+                Constraint::Min(1),
+                Constraint::Length(action_bar_height),
+                Constraint::Length(status_bar_height),
+            ])
             .split(main_chunk);
-        self.input_chunk = layout[0];
-        self.layout.update_chunk(layout[1]);
+        self.main_chunk = main_chunk;
+        self.input_chunk = if self.input_overlay {
+            let width = (main_chunk.width * 4 / 5).max(20).min(main_chunk.width);
+            let height = main_chunk.height.min(3);
+            Rect::new(
+                main_chunk.x + (main_chunk.width - width) / 2,
+                main_chunk.y + (main_chunk.height - height) / 2,
+                width,
+                height,
+            )
+        } else {
+            layout[0]
+        };
+        self.breadcrumb_chunk = layout[1];
+        self.filter_bar_chunk = layout[2];
+        self.layout_chunk = layout[3];
+        self.action_bar_chunk = layout[4];
+        self.status_bar_chunk = layout[5];
+        self.layout.update_chunk(layout[3]);
+    }
+
+    /// Checks whether the filter bar is shown, it is shown while the query is typed
+    /// or filters the tasks, or always if `filter_bar` is set.
+    fn filter_bar_visible(&self) -> bool {
+        self.show_filter_bar
+            || self.mode == Mode::Filter
+            || !self.filter_bar.is_empty(&self.data.lock().unwrap())
+    }
+
+    /// Checks whether the input line is used, the filter bar has its own line.
+    fn uses_input(&self) -> bool {
+        !matches!(self.mode, Mode::Normal | Mode::Filter)
     }
 
     /// Runs the user interface, handling setup and cleanup of terminal interactions.
     ///
     /// This method enables raw mode, sets up the terminal, and enters the main event loop.
+    /// The terminal is restored when the user interface ends, even if it ends with an error
+    /// or a panic, so the shell is not left in raw mode or in the alternate screen.
     ///
     /// # Returns
     ///
     /// An `io::Result` indicating the success of running the user interface.
     pub fn run(&mut self) -> io::Result<()> {
         fn run_ui(this: &mut UI) -> io::Result<()> {
-            // setup terminal
-            enable_raw_mode()?;
-            let mut stdout = io::stdout();
-            execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+            setup_terminal()?;
 
-            let mut backend = CrosstermBackend::new(stdout);
-            backend.execute(SetTitle(this.window_title.clone()))?;
+            let mut backend = CrosstermBackend::new(io::stdout());
+            let title = if this.dry_run {
+                format!("{} [DRY RUN]", this.window_title)
+            } else {
+                this.window_title.clone()
+            };
+            backend.execute(SetTitle(title))?;
 
             let mut terminal = Terminal::new(backend)?;
             terminal.hide_cursor()?;
             this.update_chunk(terminal.size()?);
 
             this.draw(&mut terminal)?;
-            this.main_loop(&mut terminal)?;
-
-            // restore terminal
-            disable_raw_mode()?;
-            execute!(
-                terminal.backend_mut(),
-                LeaveAlternateScreen,
-                DisableMouseCapture
-            )?;
-            terminal.show_cursor()?;
-
-            Ok(())
+            this.main_loop(&mut terminal)
         }
 
-        if let Err(e) = run_ui(self) {
-            self.tx.send(FileWorkerCommands::Exit).unwrap();
-            Err(e)
-        } else {
-            Ok(())
+        let hook: Arc<dyn Fn(&panic::PanicHookInfo) + Send + Sync> = panic::take_hook().into();
+        let previous = Arc::clone(&hook);
+        panic::set_hook(Box::new(move |info| {
+            let _ = restore_terminal();
+            previous(info);
+        }));
+
+        let result = run_ui(self);
+        let restored = restore_terminal();
+        // Put back the hook that was there before, the terminal is not ours anymore.
+        let _ = panic::take_hook();
+        panic::set_hook(Box::new(move |info| hook(info)));
+        if result.is_err() {
+            let _ = self.tx.send(FileWorkerCommands::Exit);
         }
+        result.and(restored)
     }
 
     /// Handles the main event loop of the UI.
@@ -194,219 +725,2687 @@ impl UI {
     fn main_loop<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()> {
         let mut version = self.data.lock().unwrap().get_version();
         let mut new_version;
+        self.layout.data_changed();
         loop {
-            if event::poll(self.list_refresh_rate)? {
-                if self.process_event()? {
+            // Wake up in time to save the pending changes and when a task changes with the time.
+            // Archived tasks found in the background are shown while the search goes on.
+            let timeout = match self.changed_at {
+                Some(_) => self.list_refresh_rate.min(self.save_delay),
+                None => self.list_refresh_rate,
+            };
+            let timeout = match &self.popup {
+                Some(Popup::ArchiveResults(results)) if results.is_searching() => {
+                    timeout.min(ARCHIVE_SEARCH_REFRESH)
+                }
+                _ => timeout,
+            };
+            let timeout = self
+                .until_alarm()
+                .map_or(timeout, |alarm| alarm.min(timeout));
+            let timeout = self
+                .until_refresh()
+                .map_or(timeout, |refresh| refresh.min(timeout));
+            if event::poll(timeout)? {
+                if self.process(read()?) {
                     break;
                 }
+                if self.suspend {
+                    self.suspend = false;
+                    Self::suspend(terminal)?;
+                }
+                if let Some((path, line)) = self.open_editor.take() {
+                    self.edit_file(terminal, &path, line)?;
+                    self.layout.data_changed();
+                }
+                if let Some(text) = self.terminal_clipboard.take() {
+                    if let Err(e) = copy(&text, "") {
+                        log::error!("Cannot copy to the clipboard: {}", e);
+                        self.error = Some(ErrorScreen::new(
+                            format!("Cannot copy to the clipboard: {e}"),
+                            None,
+                        ));
+                    }
+                }
                 version = self.data.lock().unwrap().get_version();
                 self.draw(terminal)?;
             } else {
+                // Escalation and routines change tasks, they run before the version is checked.
+                let escalated = self.check_escalation();
+                let reset = self.check_routines();
                 new_version = self.data.lock().unwrap().get_version();
+                let received_error = self.receive_error();
+                let alarmed = self.check_alarms();
+                let found = match &mut self.popup {
+                    Some(Popup::ArchiveResults(results)) => results.receive(),
+                    _ => false,
+                };
+                let reminded = self.check_reminders();
+                let signaled = self.check_signals();
+                let reloaded = self.check_config();
+                let updated = self.receive_release();
+                let counted = self.check_countdown();
+                let timed = self.check_timer();
+                let refreshed = self.check_refresh();
+                let posted = self.poll_status();
                 if new_version != version {
+                    self.layout.data_changed();
+                }
+                if new_version != version
+                    || received_error
+                    || alarmed
+                    || found
+                    || reminded
+                    || signaled
+                    || reloaded
+                    || updated
+                    || counted
+                    || timed
+                    || refreshed
+                    || posted
+                    || escalated
+                    || reset
+                {
+                    self.mark_changed(version, new_version);
                     version = self.data.lock().unwrap().get_version();
                     self.draw(terminal)?;
                 }
             }
+            self.save_changes();
         }
         Ok(())
     }
 
-    /// Draws the UI on the terminal.
+    /// Remembers when the todo list has changed, so it is saved after `save_delay`.
     ///
     /// # Arguments
     ///
-    /// * `terminal` - The TUI Terminal.
+    /// * `version` - The version of the todo list before the change.
+    /// * `new_version` - The current version of the todo list.
+    fn mark_changed(&mut self, version: usize, new_version: usize) {
+        if version != new_version && !self.save_delay.is_zero() {
+            self.changed_at = Some(Instant::now());
+        }
+    }
+
+    /// Suspends the application as the shell does on Ctrl-Z. The terminal is restored
+    /// before the process is stopped and set up again once the process is resumed.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// An `io::Result` indicating the success of drawing.
-    fn draw<B: Backend>(&self, terminal: &mut Terminal<B>) -> io::Result<()> {
-        let mut block = Block::default()
-            .borders(Borders::ALL)
-            .title("Input")
-            .border_type(BorderType::Rounded);
-        if self.mode == Mode::Input || self.mode == Mode::Edit {
-            block = block.border_style(Style::default().fg(self.active_color));
+    /// * `terminal` - The TUI Terminal.
+    fn suspend<B: Backend>(terminal: &mut Terminal<B>) -> io::Result<()> {
+        restore_terminal()?;
+        #[cfg(unix)]
+        // SAFETY: raise only sends a signal to the current process.
+        unsafe {
+            libc::raise(libc::SIGTSTP);
         }
-        terminal.draw(|f| {
-            f.render_widget(
-                Paragraph::new(self.tinput.value()).block(block),
-                self.input_chunk,
-            );
-            self.layout.render(f);
+        setup_terminal()?;
+        terminal.clear()
+    }
 
-            if self.mode == Mode::Input || self.mode == Mode::Edit {
-                let width = self.input_chunk.width.max(3) - 3;
-                let scroll = self.tinput.visual_scroll(width as usize);
-                f.set_cursor(
-                    self.input_chunk.x
-                        + (self.tinput.visual_cursor().max(scroll) - scroll) as u16
-                        + 1,
-                    self.input_chunk.y + 1,
-                );
+    /// Opens the file at the line in the editor given by `$VISUAL` or `$EDITOR`.
+    /// The terminal is restored while the editor runs and the todo list is loaded
+    /// again once the editor exits.
+    ///
+    /// # Arguments
+    ///
+    /// * `terminal` - The TUI Terminal.
+    /// * `path` - The path to the edited file.
+    /// * `line` - The line the editor starts at, counted from 1.
+    fn edit_file<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        path: &str,
+        line: usize,
+    ) -> io::Result<()> {
+        let editor = env::var("VISUAL")
+            .or_else(|_| env::var("EDITOR"))
+            .unwrap_or_else(|_| String::from("vi"));
+        restore_terminal()?;
+        let status = process::Command::new(&editor)
+            .arg(format!("+{line}"))
+            .arg(path)
+            .status();
+        setup_terminal()?;
+        terminal.clear()?;
+        match status {
+            Ok(_) => self.handle_event(UIEvent::Load),
+            Err(e) => {
+                self.error = Some(ErrorScreen::new(
+                    format!("Cannot run the editor {editor}: {e}"),
+                    None,
+                ));
+                false
             }
-        })?;
+        };
         Ok(())
     }
 
-    /// Handles various user events.
+    /// Takes the messages posted to the status bar, they are dropped if the status bar
+    /// is not shown.
     ///
     /// # Returns
     ///
-    /// An `io::Result` indicating whether the application should exit.
-    fn process_event(&mut self) -> io::Result<bool> {
-        self.handle_event_window(read()?);
-        Ok(self.quit)
+    /// `true` if the status bar has changed and the UI needs to be redrawn.
+    fn poll_status(&mut self) -> bool {
+        match &mut self.status_bar {
+            Some(status_bar) => status_bar.poll(),
+            None => {
+                status::take();
+                false
+            }
+        }
     }
 
-    fn handle_event_window(&mut self, e: Event) {
-        match e {
-            Event::Resize(width, height) => {
-                log::debug!("Resize event: width {width}, height {height}");
-                self.update_chunk(Rect::new(0, 0, width, height));
+    /// Shows the error reported by the file worker, if there is any.
+    ///
+    /// # Returns
+    ///
+    /// `true` if an error was received and the UI needs to be redrawn.
+    fn receive_error(&mut self) -> bool {
+        if self.read_only && !self.data.lock().unwrap().is_dirty() {
+            // A save has succeeded since, the file can be written again.
+            self.read_only = false;
+            return true;
+        }
+        match self.errors.try_recv() {
+            Ok(ref e @ FileWorkerError::Conflict(ref conflicts)) => {
+                self.confirm_prompt = Some(ConfirmPrompt::conflict(e.to_string(), conflicts));
+                true
             }
-            Event::Mouse(event) => {
-                log::debug!("Mouse event: {:?}", event);
+            Ok(e @ FileWorkerError::LoadFailed) => {
+                self.confirm_prompt = Some(ConfirmPrompt::new(
+                    Confirmation::SaveAnyway,
+                    format!("{e} Save anyway?"),
+                ));
+                true
             }
-            Event::Key(event) => match self.mode {
-                Mode::Input => match event.code {
-                    KeyCode::Enter => {
-                        self.data
-                            .lock()
-                            .unwrap()
-                            .new_task(self.tinput.value())
-                            .unwrap(); // TODO fix
-                        self.tinput.reset();
-                        self.mode = Mode::Normal;
-                        self.layout.focus();
-                    }
-                    KeyCode::Esc => {
-                        self.mode = Mode::Normal;
-                        self.layout.focus();
-                    }
-                    KeyCode::Tab => {
-                        if let Some(input) =
-                            autocomplete(&self.data.lock().unwrap(), self.tinput.value())
-                        {
-                            self.tinput = input.into();
-                        }
-                    }
-                    _ => {
-                        self.tinput.handle_event(&e);
-                    }
-                },
-                Mode::Edit => match event.code {
-                    KeyCode::Enter => {
-                        self.data
-                            .lock()
-                            .unwrap()
-                            .update_active(self.tinput.value())
-                            .unwrap();
-                        self.tinput.reset();
-                        self.mode = Mode::Normal;
-                        self.layout.focus();
-                    }
-                    KeyCode::Esc => {
-                        self.tinput.reset();
-                        self.mode = Mode::Normal;
-                        self.layout.focus();
-                    }
-                    KeyCode::Tab => {
-                        if let Some(input) =
-                            autocomplete(&self.data.lock().unwrap(), self.tinput.value())
-                        {
-                            self.tinput = input.into();
-                        }
-                    }
-                    _ => {
-                        self.tinput.handle_event(&e);
-                    }
-                },
-                Mode::Normal => {
-                    let _ = self.handle_key(&event.code) || self.layout.handle_key(&event);
-                }
-            },
-            _ => {}
+            Ok(e @ FileWorkerError::ReadOnly(_)) => {
+                self.read_only = true;
+                self.error = Some(ErrorScreen::new(
+                    format!("{e} Press Enter to try saving again."),
+                    Some(UIEvent::Save),
+                ));
+                true
+            }
+            Ok(e) => {
+                self.error = Some(ErrorScreen::new(e.to_string(), None));
+                true
+            }
+            Err(_) => false,
         }
     }
-}
 
-impl HandleEvent for UI {
-    fn get_event(&self, key: &KeyCode) -> UIEvent {
-        self.event_handler.get_event(key)
+    /// Saves the todo list once `save_delay` has passed since its last change.
+    fn save_changes(&mut self) {
+        let Some(changed_at) = self.changed_at else {
+            return;
+        };
+        if changed_at.elapsed() >= self.save_delay {
+            self.changed_at = None;
+            if let Err(e) = self.tx.send(FileWorkerCommands::Save) {
+                log::error!("Error while send signal to save todo list: {}", e);
+            }
+        }
     }
 
-    fn handle_event(&mut self, event: UIEvent) -> bool {
-        use UIEvent::*;
-        match event {
-            Quit => {
-                if let Some(path) = &self.save_state_path {
-                    if let Err(e) =
-                        UIState::new(&self.layout, &self.data.lock().unwrap()).save(path)
-                    {
-                        log::error!("Error while saveing UI state: {}", e);
-                    }
-                }
-                self.quit = true;
+    /// Shows tasks whose `remind:` or `notify:` time has come since the last check in a toast
+    /// and as desktop notifications, and hides the toast shown long enough.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the toast has changed and the UI needs to be redrawn.
+    fn check_reminders(&mut self) -> bool {
+        let now = self.clock.now();
+        let reminders = {
+            let data = self.data.lock().unwrap();
+            let mut reminders = data.reminders(self.reminders_checked, now);
+            if self.due_notifications {
+                reminders.extend(data.due_notices(self.reminders_checked, now));
             }
-            InsertMode => {
-                self.mode = Mode::Input;
-                self.layout.unfocus();
+            reminders
+        };
+        self.reminders_checked = now;
+        if reminders.is_empty() {
+            let expired = self.toast.as_ref().is_some_and(Toast::expired);
+            if expired {
+                self.toast = None;
             }
-            MoveRight => {
-                self.layout.right();
+            return expired;
+        }
+        if !self.reminder_command.is_empty() {
+            for reminder in &reminders {
+                self.notify(reminder);
             }
-            MoveLeft => {
-                self.layout.left();
+        }
+        self.toast = Some(Toast::new("Reminder", reminders, Duration::from_secs(30)));
+        true
+    }
+
+    /// Asks before the key deletes tasks of the focused list.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The pressed key.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the key deletes tasks and the confirmation prompt is shown.
+    fn confirm_delete(&mut self, key: &KeyCode) -> bool {
+        if !self.confirm_delete || self.layout.get_event(key) != UIEvent::RemoveItem {
+            return false;
+        }
+        let question = match self.layout.target_tasks().as_slice() {
+            [] => return false,
+            [task] => format!("Delete the task \"{task}\"?"),
+            tasks => format!("Delete {} marked tasks?", tasks.len()),
+        };
+        self.confirm_prompt = Some(ConfirmPrompt::new(Confirmation::Delete, question));
+        true
+    }
+
+    /// Asks before the review deletes the reviewed task.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the confirmation prompt is shown.
+    fn confirm_review_delete(&mut self) -> bool {
+        if !self.confirm_delete {
+            return false;
+        }
+        let todo = self.data.lock().unwrap();
+        let Some(index) = self
+            .review
+            .as_ref()
+            .and_then(ReviewMode::current)
+            .and_then(|id| todo.find_task(ToDoData::Pending, id))
+        else {
+            return false;
+        };
+        let question = format!("Delete the task \"{}\"?", todo.pending[index].subject);
+        drop(todo);
+        self.confirm_prompt = Some(ConfirmPrompt::new(Confirmation::Delete, question));
+        true
+    }
+
+    /// Does the action confirmed in the prompt.
+    ///
+    /// # Parameters
+    ///
+    /// - `confirmation`: The confirmed action.
+    fn confirmed(&mut self, confirmation: Confirmation) {
+        match confirmation {
+            Confirmation::Delete if self.mode == Mode::Review => {
+                self.review_task(ReviewAction::Delete)
             }
-            MoveUp => {
-                self.layout.up();
+            Confirmation::Delete => {
+                self.layout.handle_event(UIEvent::RemoveItem);
             }
-            MoveDown => {
-                self.layout.down();
+            Confirmation::Archive => self.archive(),
+            Confirmation::Quit => self.save_and_quit(),
+            Confirmation::Conflict => self.keep_both(),
+            Confirmation::TaskRules => self.apply_task_rules(),
+            Confirmation::SaveAnyway => self.save_anyway(),
+        }
+    }
+
+    /// Reads the history of the new-task input, the history starts empty if the file cannot be read.
+    fn load_input_history(config: &Config) -> InputHistory {
+        let path = PathBuf::from(config.get_input_history_path());
+        let mut history = InputHistory::new(Some(path), config.get_input_history_size());
+        if let Err(e) = history.load() {
+            log::error!("Cannot read the input history: {}", e);
+        }
+        history
+    }
+
+    /// Passes the key to the task input. Word movements with `Alt` and word deletions
+    /// with `Ctrl-Backspace` and `Alt-Delete` are added to the keys of the input.
+    ///
+    /// # Parameters
+    ///
+    /// - `e`: The key event.
+    fn edit_input(&mut self, e: &Event) {
+        let request = match e {
+            Event::Key(event) => match (event.code, event.modifiers) {
+                (KeyCode::Left, KeyModifiers::ALT) => Some(InputRequest::GoToPrevWord),
+                (KeyCode::Right, KeyModifiers::ALT) => Some(InputRequest::GoToNextWord),
+                (KeyCode::Backspace, KeyModifiers::CONTROL) => Some(InputRequest::DeletePrevWord),
+                (KeyCode::Delete, KeyModifiers::ALT) => Some(InputRequest::DeleteNextWord),
+                _ => None,
+            },
+            _ => None,
+        };
+        match request {
+            Some(request) => self.tinput.handle(request),
+            None => self.tinput.handle_event(e),
+        };
+    }
+
+    /// Opens, updates or closes the completion popup for the word at the cursor of the input.
+    fn update_completion(&mut self) {
+        self.completion = CompletionPopup::new(&self.data.lock().unwrap(), &self.tinput);
+    }
+
+    /// Handles the key pressed while the completion popup is opened.
+    ///
+    /// # Parameters
+    ///
+    /// - `code`: The pressed key, `Up` and `Down` move the selection, `Tab` accepts
+    ///   the selected completion and `Esc` closes the popup.
+    fn handle_completion(&mut self, code: KeyCode) {
+        let Some(completion) = &mut self.completion else {
+            return;
+        };
+        match code {
+            KeyCode::Up => completion.select_prev(),
+            KeyCode::Down => completion.select_next(),
+            KeyCode::Tab => {
+                self.tinput = completion.complete(&self.tinput);
+                self.completion = None;
             }
-            Save => {
-                if let Err(e) = self.tx.send(FileWorkerCommands::ForceSave) {
-                    log::error!("Error while send signal to save todo list: {}", e);
-                    // TODO show something on screen
-                }
+            _ => self.completion = None,
+        }
+    }
+
+    /// Applies the signals other programs have appended to the signal file since
+    /// the last check, the changes are shown in a toast.
+    ///
+    /// # Returns
+    ///
+    /// `true` if a signal was received and the UI needs to be redrawn.
+    fn check_signals(&mut self) -> bool {
+        let Some(signals) = &mut self.signals else {
+            return false;
+        };
+        let (signals, unknown) = match signals.take() {
+            Ok(taken) => taken,
+            Err(e) => {
+                log::error!("Cannot read the signal file: {}", e);
+                return false;
             }
-            Load => {
-                if let Err(e) = self.tx.send(FileWorkerCommands::Load) {
-                    log::error!("Error while send signal to load todo list: {}", e);
-                    // TODO show something on screen
-                }
+        };
+        if signals.is_empty() && unknown.is_empty() {
+            return false;
+        }
+        let mut lines: Vec<String> = {
+            let mut data = self.data.lock().unwrap();
+            signals
+                .iter()
+                .map(|signal| signal.apply(&mut data))
+                .collect()
+        };
+        lines.extend(unknown.iter().map(|line| format!("Unknown signal: {line}")));
+        lines.iter().for_each(|line| log::info!("Signal: {line}"));
+        self.toast = Some(Toast::new("Signals", lines, Duration::from_secs(10)));
+        true
+    }
+
+    /// Reloads the configuration once its file has changed, see [`UI::reload_config`].
+    ///
+    /// # Returns
+    ///
+    /// `true` if the configuration file has changed and the UI needs to be redrawn.
+    fn check_config(&mut self) -> bool {
+        if !self
+            .config_watcher
+            .as_mut()
+            .is_some_and(ConfigWatcher::changed)
+        {
+            return false;
+        }
+        self.reload_config();
+        true
+    }
+
+    /// Loads the configuration file again and applies its keybindings, styles, layout
+    /// and list options without a restart. An invalid configuration is shown in a popup
+    /// and the running configuration is kept.
+    fn reload_config(&mut self) {
+        let config = match self.config.reload() {
+            Ok(config) => config,
+            Err(e) => {
+                log::error!("Cannot reload the configuration: {}", e);
+                self.error = Some(ErrorScreen::new(
+                    format!("Cannot reload the configuration: {e}"),
+                    None,
+                ));
+                return;
             }
-            EditMode => {
-                if let Some(active) = self.data.lock().unwrap().get_active() {
-                    self.tinput = active.to_string().into();
-                    self.mode = Mode::Edit;
-                    self.layout.unfocus();
-                    // self.in
+        };
+        let previous = std::mem::replace(&mut self.config, config);
+        if let Some(e) = self.rebuild_layout() {
+            self.config = previous;
+            self.error = Some(ErrorScreen::new(
+                format!("The layout is not valid, the configuration is not reloaded: {e}"),
+                None,
+            ));
+            return;
+        }
+        let config = &self.config;
+        self.data
+            .lock()
+            .unwrap()
+            .set_config(ToDoConfig::from(config));
+        self.event_handler = config.get_window_keybind();
+        self.review_keybind = config.get_review_keybind();
+        self.input_overlay = config.get_input_overlay();
+        self.list_refresh_rate = config.get_list_refresh_rate();
+        self.active_color = config.get_active_color();
+        self.color_support = config.get_color_support().resolve();
+        self.clock_format = config.get_clock_format();
+        self.review_age = config.get_review_age();
+        self.quick_add_filters = config.get_quick_add_filters();
+        self.confirm_delete = config.get_confirm_delete();
+        self.confirm_archive = config.get_confirm_archive();
+        self.confirm_quit = config.get_confirm_quit();
+        self.confirm_task_rules = config.get_confirm_task_rules();
+        self.confirm_new_categories = config.get_confirm_new_categories();
+        self.review_save = config.get_review_save();
+        self.save_delay = config.get_save_delay();
+        self.show_filter_bar = config.get_filter_bar();
+        self.breadcrumb = config.get_breadcrumb().then(|| Breadcrumb::new(config));
+        self.action_bar = config.get_action_bar().then(|| ActionBar::new(config));
+        self.status_bar = config.get_status_bar().then(StatusBar::default);
+        self.update_chunk(self.main_chunk);
+        self.layout.data_changed();
+        status::info("Configuration reloaded");
+    }
+
+    /// Builds the layout again from the configuration, so its widgets use the changed
+    /// keybindings and styles. The focused widget and the selections of the lists are kept.
+    ///
+    /// # Returns
+    ///
+    /// The error of an invalid layout, the current layout is kept in that case.
+    fn rebuild_layout(&mut self) -> Option<ToDoError> {
+        let state = UIState::new(&self.layout, &self.data);
+        match Layout::from_config(self.data.clone(), &self.config) {
+            Ok((_, Some(e))) | Err(e) => {
+                log::error!("Cannot rebuild the layout: {}", e);
+                Some(e)
+            }
+            Ok((mut layout, None)) => {
+                if !state.restore_layout(&mut layout) {
+                    layout.focus_widget(self.layout.get_active_widget());
                 }
+                layout.update_chunk(self.layout_chunk);
+                self.layout = layout;
+                None
             }
-            _ => {
+        }
+    }
+
+    /// Shows a toast when the update check has found a newer release.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the toast is shown and the UI needs to be redrawn.
+    fn receive_release(&mut self) -> bool {
+        let release = match self.update_check.as_ref().map(Receiver::try_recv) {
+            Some(Ok(release)) => release,
+            Some(Err(TryRecvError::Disconnected)) => {
+                self.update_check = None;
                 return false;
             }
-        }
+            _ => return false,
+        };
+        self.update_check = None;
+        let key = self
+            .event_handler
+            .bindings()
+            .into_iter()
+            .find(|(_, event)| *event == UIEvent::ShowChangelog)
+            .map_or_else(|| String::from("ShowChangelog"), |(key, _)| key_name(key));
+        self.toast = Some(Toast::new(
+            "Update available",
+            vec![
+                format!(
+                    "Version {} is available, you have {}",
+                    release.version,
+                    env!("CARGO_PKG_VERSION")
+                ),
+                format!("Press {key} to see what's new"),
+            ],
+            Duration::from_secs(30),
+        ));
+        self.release = Some(release);
         true
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crossterm::event::{KeyEvent, KeyModifiers};
-    use std::env;
-    use test_log::test;
+    /// Gets the time until the next moment a task changes with the time, see [`ToDo::next_alarm`]
+    /// and [`ToDo::next_countdown`].
+    fn until_alarm(&self) -> Option<Duration> {
+        let now = self.clock.now();
+        let data = self.data.lock().unwrap();
+        let alarm = [data.next_alarm(now), data.next_countdown(now)]
+            .into_iter()
+            .flatten()
+            .min()?;
+        (alarm - now).to_std().ok()
+    }
 
-    use super::*;
+    /// Checks whether the countdown of a task due soon has changed since the last check,
+    /// see [`ToDo::next_countdown`].
+    ///
+    /// # Returns
+    ///
+    /// `true` if the lists need to be redrawn.
+    fn check_countdown(&mut self) -> bool {
+        let now = self.clock.now();
+        let next = self
+            .data
+            .lock()
+            .unwrap()
+            .next_countdown(self.countdown_checked);
+        self.countdown_checked = now;
+        next.is_some_and(|next| next <= now)
+    }
 
-    fn default_ui() -> Result<UI, Box<dyn Error>> {
-        let config = Config::load_from_buffer(
-            format!(
-                r#"
-            todo_path = "{}todo.txt"
+    /// Checks whether the timer of a task has changed since the last check, see
+    /// [`ToDo::next_timer_change`]. A pomodoro that is over is shown in a toast.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the timer needs to be redrawn.
+    fn check_timer(&mut self) -> bool {
+        let now = self.clock.now();
+        let (next, finished) = {
+            let mut todo = self.data.lock().unwrap();
+            (
+                todo.next_timer_change(self.timer_checked),
+                todo.finish_pomodoro(),
+            )
+        };
+        self.timer_checked = now;
+        if let Some(subject) = finished {
+            let message = format!("Pomodoro is over: {subject}");
+            if !self.reminder_command.is_empty() {
+                self.notify(&message);
+            }
+            self.toast = Some(Toast::new(
+                "Pomodoro",
+                vec![message],
+                Duration::from_secs(30),
+            ));
+            return true;
+        }
+        next.is_some_and(|next| next <= now)
+    }
+
+    /// Gets the interval the clock in the input bar changes at, every second if its format
+    /// shows seconds and every minute otherwise.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the clock is hidden.
+    fn clock_rate(&self) -> Option<Duration> {
+        if self.clock_format.is_empty() {
+            return None;
+        }
+        let seconds = ["%S", "%T", "%X", "%r", "%s", "%+", "%c"]
+            .iter()
+            .any(|spec| self.clock_format.contains(spec));
+        Some(Duration::from_secs(if seconds { 1 } else { 60 }))
+    }
+
+    /// Gets the time until the clock, the timer or a widget with a refresh rate is redrawn,
+    /// see [`Layout::refresh`].
+    fn until_refresh(&self) -> Option<Duration> {
+        let now = self.clock.now();
+        let clock = self.clock_rate().map(|rate| until_tick(rate, now));
+        let timer = self
+            .data
+            .lock()
+            .unwrap()
+            .next_timer_change(now)
+            .and_then(|next| (next - now).to_std().ok());
+        [clock, timer, self.layout.until_refresh(now)]
+            .into_iter()
+            .flatten()
+            .min()
+    }
+
+    /// Refreshes the widgets whose refresh rate has passed since the last check,
+    /// the other widgets are not refreshed.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the clock or a widget needs to be redrawn.
+    fn check_refresh(&mut self) -> bool {
+        let now = self.clock.now();
+        let from = std::mem::replace(&mut self.refreshed_at, now);
+        let refreshed = self.layout.refresh(from, now);
+        refreshed
+            || self
+                .clock_rate()
+                .is_some_and(|rate| ticked(rate, from, now))
+    }
+
+    /// Refreshes the panes once the date changes or working hours of contexts start
+    /// or end, so overdue tasks, dimmed tasks and task queries relative to today are
+    /// up to date without a key press. Tasks which became due or overdue are shown
+    /// in a toast and as desktop notifications.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the date or the open contexts have changed and the UI needs to be redrawn.
+    fn check_alarms(&mut self) -> bool {
+        let today = self.clock.today();
+        let open_contexts = self.data.lock().unwrap().open_contexts();
+        if today == self.alarms_checked && open_contexts == self.open_contexts {
+            return false;
+        }
+        self.open_contexts = open_contexts;
+        let alarms = {
+            let mut data = self.data.lock().unwrap();
+            data.date_changed();
+            data.date_alarms(self.alarms_checked, today)
+        };
+        self.alarms_checked = today;
+        self.layout.data_changed();
+        if self.due_notifications && !alarms.is_empty() {
+            if !self.reminder_command.is_empty() {
+                for alarm in &alarms {
+                    self.notify(alarm);
+                }
+            }
+            self.toast = Some(Toast::new("Due", alarms, Duration::from_secs(30)));
+        }
+        true
+    }
+
+    /// Resets completed routines whose interval has passed, e.g. daily routines
+    /// completed yesterday. Reset routines are shown in a toast.
+    ///
+    /// # Returns
+    ///
+    /// `true` if a routine was reset.
+    fn check_routines(&mut self) -> bool {
+        let mut todo = self.data.lock().unwrap();
+        let reset = todo.due_routines();
+        if reset.is_empty() {
+            return false;
+        }
+        if let Err(e) = todo.apply(Action::ResetRoutines) {
+            log::error!("Cannot reset the routines: {}", e);
+        }
+        drop(todo);
+        self.toast = Some(Toast::new("Routines reset", reset, Duration::from_secs(30)));
+        true
+    }
+
+    /// Applies the escalation rules once a day, the first time right after the start,
+    /// and the task rules whenever the tasks or the date have changed. Changed tasks
+    /// are shown in a toast. With `confirm_task_rules` the changes of the task rules
+    /// are previewed in the confirmation prompt instead.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the toast has changed and the UI needs to be redrawn.
+    fn check_escalation(&mut self) -> bool {
+        let today = self.clock.today();
+        let mut todo = self.data.lock().unwrap();
+        let mut changes = Vec::new();
+        if self.escalated_on != Some(today) {
+            self.escalated_on = Some(today);
+            self.ruled_version = None;
+            changes = todo.escalate(today);
+        }
+        let title = match changes.is_empty() {
+            true => "Task rules",
+            false => "Escalated",
+        };
+        let mut prompted = false;
+        if self.ruled_version != Some(todo.get_version()) {
+            if !self.confirm_task_rules {
+                changes.extend(todo.apply_task_rules(today));
+                self.ruled_version = Some(todo.get_version());
+            } else if self.confirm_prompt.is_none() {
+                let preview = todo.preview_task_rules(today);
+                prompted = !preview.is_empty();
+                if prompted {
+                    self.confirm_prompt = Some(ConfirmPrompt::task_rules(&preview));
+                }
+                self.ruled_version = Some(todo.get_version());
+            }
+        }
+        if changes.is_empty() {
+            return prompted;
+        }
+        self.toast = Some(Toast::new(title, changes, Duration::from_secs(30)));
+        true
+    }
+
+    /// Applies the task rules whose changes were confirmed in the preview, the changed
+    /// tasks are shown in a toast.
+    fn apply_task_rules(&mut self) {
+        let mut todo = self.data.lock().unwrap();
+        let changes = todo.apply_task_rules(self.clock.today());
+        self.ruled_version = Some(todo.get_version());
+        drop(todo);
+        if !changes.is_empty() {
+            self.toast = Some(Toast::new("Task rules", changes, Duration::from_secs(30)));
+        }
+    }
+
+    /// Runs the reminder command with the task subject in the background.
+    fn notify(&self, reminder: &str) {
+        let mut command = process::Command::new(&self.reminder_command);
+        command
+            .arg(reminder)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        thread::spawn(move || {
+            if let Err(e) = command.status() {
+                log::warn!("Cannot run the reminder command: {e}");
+            }
+        });
+    }
+
+    /// Draws the UI on the terminal.
+    ///
+    /// # Arguments
+    ///
+    /// * `terminal` - The TUI Terminal.
+    ///
+    /// # Returns
+    ///
+    /// An `io::Result` indicating the success of drawing.
+    fn draw<B: Backend>(&self, terminal: &mut Terminal<B>) -> io::Result<()> {
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .title(match (&self.mode, &self.triage, &self.review) {
+                (Mode::Command, _, _) => String::from("Command"),
+                (Mode::Search, _, _) => String::from(
+                    "Search (Enter: keep the search, Tab: search the archive, Esc: clear it)",
+                ),
+                (Mode::Fix, _, _) => String::from("Fix (Enter: save, Esc: back to the report)"),
+                (Mode::Due, _, _) => String::from(
+                    "Due date, e.g. 2023-06-01, 3d, +1w, mon or none (Enter: set, Esc: cancel)",
+                ),
+                (Mode::Export, _, _) => {
+                    String::from("Export to a .json, .md or .ics file (Enter: export, Esc: cancel)")
+                }
+                (Mode::Compare, _, _) => {
+                    String::from("Compare with the todo file (Enter: compare, Esc: cancel)")
+                }
+                (Mode::Triage, Some(triage), _) => triage.title(),
+                (Mode::Review, _, Some(review)) => review.title(),
+                (Mode::Input, _, _) if self.scratch_input => {
+                    String::from("Scratch task (kept until you quit, never saved)")
+                }
+                (Mode::Edit | Mode::Input, _, _) if self.data.lock().unwrap().raw_lines() => {
+                    String::from("Input (raw line)")
+                }
+                _ => String::from("Input"),
+            })
+            .border_type(BorderType::Rounded);
+        if self.clock.is_fixed() {
+            block = block.title(
+                Title::from(Span::styled(
+                    format!(" TODAY: {} ", self.clock.today()),
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .alignment(Alignment::Right),
+            );
+        }
+        if let Some(running) = &self.other_instance {
+            block = block.title(
+                Title::from(Span::styled(
+                    format!(" READ-ONLY: edited by the instance {} ", running.pid),
+                    Style::default()
+                        .fg(Color::White)
+                        .bg(Color::Red)
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .alignment(Alignment::Right),
+            );
+        } else if self.dry_run {
+            block = block.title(
+                Title::from(Span::styled(
+                    " DRY RUN: changes are not saved ",
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .alignment(Alignment::Right),
+            );
+        }
+        if self.read_only {
+            block = block.title(
+                Title::from(Span::styled(
+                    " READ-ONLY: changes are not saved ",
+                    Style::default()
+                        .fg(Color::White)
+                        .bg(Color::Red)
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .alignment(Alignment::Right),
+            );
+        }
+        if let Some(timer) = self.data.lock().unwrap().timer_status(self.clock.now()) {
+            block = block.title(Title::from(format!(" {timer} ")).alignment(Alignment::Right));
+        }
+        // An invalid format is not shown instead of failing the whole frame.
+        let mut clock = String::new();
+        if !self.clock_format.is_empty()
+            && write!(clock, " {} ", self.clock.now().format(&self.clock_format)).is_ok()
+        {
+            block = block.title(Title::from(clock).alignment(Alignment::Right));
+        }
+        if self.uses_input() {
+            block = block.border_style(Style::default().fg(self.active_color));
+        }
+        terminal.draw(|f| {
+            if let Some(breadcrumb) = &self.breadcrumb {
+                breadcrumb.render(f, self.breadcrumb_chunk, &self.data.lock().unwrap());
+            }
+            if let Some(action_bar) = &self.action_bar {
+                action_bar.render(f, self.action_bar_chunk, self.layout.get_active_widget());
+            }
+            if let Some(status_bar) = &self.status_bar {
+                status_bar.render(
+                    f,
+                    self.status_bar_chunk,
+                    self.layout.get_active_widget(),
+                    &self.data.lock().unwrap(),
+                );
+            }
+            if self.filter_bar_chunk.height > 0 {
+                self.filter_bar.render(
+                    f,
+                    self.filter_bar_chunk,
+                    self.mode == Mode::Filter,
+                    self.active_color,
+                );
+            }
+            self.layout.render(f);
+
+            // The overlay is drawn over the lists only while the input is used.
+            if self.input_overlay && self.uses_input() {
+                f.render_widget(Clear, self.input_chunk);
+            }
+            if !self.input_overlay || self.uses_input() {
+                f.render_widget(
+                    Paragraph::new(self.tinput.value()).block(block),
+                    self.input_chunk,
+                );
+            }
+
+            if self.uses_input() {
+                let width = self.input_chunk.width.max(3) - 3;
+                let scroll = self.tinput.visual_scroll(width as usize);
+                let x = self.input_chunk.x
+                    + (self.tinput.visual_cursor().max(scroll) - scroll) as u16
+                    + 1;
+                f.set_cursor(x, self.input_chunk.y + 1);
+                if let Some(completion) = self
+                    .completion
+                    .as_ref()
+                    .filter(|_| matches!(self.mode, Mode::Input | Mode::Edit))
+                {
+                    completion.render(f, self.input_chunk, x);
+                }
+            }
+
+            if let Some(toast) = &self.toast {
+                toast.render(f, self.layout_chunk);
+            }
+
+            if let Some(report) = self
+                .health_report
+                .as_ref()
+                .filter(|_| self.mode == Mode::Normal)
+            {
+                report.render(f, self.layout_chunk);
+            }
+
+            if let Some(prompt) = &self.category_prompt {
+                prompt.render(f, self.layout_chunk);
+            }
+
+            if let Some(popup) = &self.popup {
+                popup.render(f, f.size(), self.layout_chunk);
+            }
+
+            if let Some(prompt) = &self.confirm_prompt {
+                prompt.render(f, f.size());
+            }
+
+            if let Some(prompt) = &self.create_prompt {
+                prompt.render(f, f.size());
+            }
+
+            if let Some(error) = &self.error {
+                error.render(f, f.size());
+            }
+            f.render_widget(self.color_support, f.size());
+        })?;
+        Ok(())
+    }
+
+    /// Processes an event of the terminal: a key, a mouse event or resizing.
+    ///
+    /// The terminal is not touched, so the application can be driven by synthetic
+    /// events, e.g. in tests. Suspending, opening the editor and copying to the clipboard
+    /// of the terminal are left to the main loop, the UI is not drawn either.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The processed event.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the application should exit.
+    pub fn process(&mut self, event: Event) -> bool {
+        let version = self.data.lock().unwrap().get_version();
+        self.handle_event_window(event);
+        self.write_activity();
+        self.write_trash();
+        self.check_alarms();
+        self.poll_status();
+        // Filters and sorting do not change the version, widgets are notified of them by the todo list.
+        self.layout.data_changed();
+        if self.filter_bar_visible() != (self.filter_bar_chunk.height > 0) {
+            self.update_chunk(self.main_chunk);
+        }
+        let new_version = self.data.lock().unwrap().get_version();
+        self.mark_changed(version, new_version);
+        self.quit
+    }
+
+    /// Gets the todo list shared by the widgets, e.g. to check the effect of processed events.
+    #[cfg(test)]
+    pub fn todo(&self) -> std::sync::MutexGuard<'_, ToDo> {
+        self.data.lock().unwrap()
+    }
+
+    fn handle_event_window(&mut self, e: Event) {
+        match e {
+            Event::Resize(width, height) => {
+                log::debug!("Resize event: width {width}, height {height}");
+                self.update_chunk(Rect::new(0, 0, width, height));
+            }
+            Event::Mouse(event) => {
+                log::debug!("Mouse event: {:?}", event);
+                if self.mode == Mode::Normal
+                    && !self.click_breadcrumb(&event)
+                    && !self.click_action_bar(&event)
+                {
+                    self.layout.handle_mouse(&event);
+                }
+            }
+            Event::Key(event)
+                if event.code == KeyCode::Char('z')
+                    && event.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                self.suspend = cfg!(unix);
+            }
+            Event::Key(event) if self.error.is_some() => {
+                let retry = self.error.take().and_then(|error| error.retry());
+                if let (KeyCode::Enter, Some(retry)) = (event.code, retry) {
+                    self.handle_event(retry);
+                }
+            }
+            Event::Key(event) if self.create_prompt.is_some() => match event.code {
+                KeyCode::Enter | KeyCode::Char('c') => {
+                    let prompt = self.create_prompt.take().unwrap();
+                    if let Err(e) = prompt.create() {
+                        log::error!("Cannot create the todo file: {}", e);
+                        self.error = Some(ErrorScreen::new(
+                            format!("Cannot create the todo file: {e}"),
+                            None,
+                        ));
+                    }
+                }
+                KeyCode::Esc => self.create_prompt = None,
+                _ => {}
+            },
+            Event::Key(event) if self.confirm_prompt.is_some() => {
+                let prompt = self.confirm_prompt.take().unwrap();
+                match (prompt.confirmation(), event.code) {
+                    (Confirmation::Quit, KeyCode::Enter | KeyCode::Char('s' | 'y')) => {
+                        self.save_and_quit()
+                    }
+                    (Confirmation::Quit, KeyCode::Char('d')) => self.quit(),
+                    (Confirmation::Conflict, KeyCode::Char('b')) => self.keep_both(),
+                    (Confirmation::Conflict, KeyCode::Char('m')) => self.save(),
+                    (Confirmation::Conflict, KeyCode::Char('f')) => {
+                        self.handle_event(UIEvent::Load);
+                    }
+                    (Confirmation::SaveAnyway, KeyCode::Char('s')) => self.save_anyway(),
+                    // The file is overwritten only by an explicit answer.
+                    (Confirmation::SaveAnyway, KeyCode::Enter) => {
+                        self.confirm_prompt = Some(prompt)
+                    }
+                    (confirmation, KeyCode::Enter | KeyCode::Char('y')) => {
+                        self.confirmed(confirmation)
+                    }
+                    (_, KeyCode::Esc | KeyCode::Char('n' | 'c')) => {}
+                    _ => self.confirm_prompt = Some(prompt),
+                }
+            }
+            Event::Key(event) if self.popup.is_some() => self.handle_popup_key(&event),
+            Event::Key(event) if self.category_prompt.is_some() => self.handle_category_key(&event),
+            Event::Key(event) if self.mode == Mode::Normal && self.health_report.is_some() => {
+                self.handle_report_key(&event)
+            }
+            Event::Key(event)
+                if self.mode == Mode::Normal
+                    && matches!(event.code, KeyCode::Char('n' | 'N') | KeyCode::Esc)
+                    && self.data.lock().unwrap().search().is_some() =>
+            {
+                match event.code {
+                    KeyCode::Char('n') => self.layout.handle_event(UIEvent::NextMatch),
+                    KeyCode::Char('N') => self.layout.handle_event(UIEvent::PrevMatch),
+                    _ => {
+                        self.search(None);
+                        true
+                    }
+                };
+            }
+            Event::Key(event)
+                if matches!(self.mode, Mode::Input | Mode::Edit)
+                    && self.completion.is_some()
+                    && matches!(
+                        event.code,
+                        KeyCode::Up | KeyCode::Down | KeyCode::Tab | KeyCode::Esc
+                    ) =>
+            {
+                self.handle_completion(event.code)
+            }
+            Event::Key(event) => match self.mode {
+                Mode::Input => self.handle_input_key(&event),
+                Mode::Edit => self.handle_edit_key(&event),
+                Mode::Due => self.handle_due_key(&event),
+                Mode::Export => self.handle_path_key(&event, Self::export_view),
+                Mode::Compare => self.handle_path_key(&event, Self::open_compare_view),
+                Mode::Fix => self.handle_fix_key(&event),
+                Mode::Command => self.handle_command_key(&event),
+                Mode::Filter => self.handle_filter_key(&event),
+                Mode::Search => self.handle_search_key(&event),
+                Mode::Review => self.handle_review_key(&event),
+                Mode::Triage => self.handle_triage_key(&event),
+                Mode::Normal => {
+                    let _ = self.run_bound_script(&event.code)
+                        || self.handle_key(&event.code)
+                        || self.confirm_delete(&event.code)
+                        || self.layout.handle_key(&event);
+                }
+            },
+            _ => {}
+        }
+    }
+}
+
+impl UI {
+    /// Handles the key pressed while a popup is open and does what the popup asks for.
+    fn handle_popup_key(&mut self, key: &KeyEvent) {
+        let Some(popup) = &mut self.popup else {
+            return;
+        };
+        let next = popup.handle_key(key, &mut self.data.lock().unwrap());
+        match next {
+            PopupKey::Keep => {}
+            PopupKey::Close => self.popup = None,
+            PopupKey::Save => {
+                self.popup = None;
+                self.save();
+            }
+            PopupKey::SaveKeybinds => self.save_keybinds(),
+            PopupKey::FilterActivity(filter) => self.show_activity(&filter),
+            PopupKey::ShowEffort { by_week } => self.show_effort_report(by_week),
+            PopupKey::CopyCompared => self.copy_compared(),
+            PopupKey::AddTask => self.quick_add_task(),
+        }
+    }
+
+    /// Handles the key pressed while the prompt for new categories is open.
+    fn handle_category_key(&mut self, key: &KeyEvent) {
+        let Some(prompt) = &self.category_prompt else {
+            return;
+        };
+        match prompt.handle_key(key) {
+            CategoryKey::Keep => {}
+            CategoryKey::Close => self.category_prompt = None,
+            CategoryKey::Next => self.next_category(),
+            CategoryKey::Replace { name, suggestion } => {
+                self.tinput = replace_word(self.tinput.value(), &name, &suggestion).into();
+                self.next_category();
+            }
+        }
+    }
+
+    /// Handles the key pressed while the health report is open.
+    fn handle_report_key(&mut self, key: &KeyEvent) {
+        let Some(report) = &mut self.health_report else {
+            return;
+        };
+        match report.handle_key(key) {
+            ReportKey::Keep => {}
+            ReportKey::Close => self.health_report = None,
+            ReportKey::Fix(problem) => {
+                self.tinput = problem.text.as_str().into();
+                self.fixing = Some(problem);
+                self.mode = Mode::Fix;
+                self.layout.unfocus();
+            }
+            ReportKey::Skip => self.remove_problem(),
+            ReportKey::OpenEditor { path, line } => {
+                self.open_editor = Some((path, line));
+                self.health_report = None;
+            }
+        }
+    }
+
+    /// Leaves the input and returns the focus to the widgets.
+    fn leave_input(&mut self) {
+        self.tinput.reset();
+        self.mode = Mode::Normal;
+        self.layout.focus();
+    }
+
+    /// Completes the task in the input with the projects, contexts and tags of the todo list.
+    fn complete_input(&mut self) {
+        if let Some(input) = autocomplete(&self.data.lock().unwrap(), self.tinput.value()) {
+            self.tinput = input.into();
+        }
+    }
+
+    /// Handles the key pressed while a new task is typed.
+    fn handle_input_key(&mut self, key: &KeyEvent) {
+        match key.code {
+            KeyCode::Enter if self.prompt_new_categories() => {}
+            KeyCode::Enter => {
+                let task = match self.scratch_input {
+                    true => scratch_line(self.tinput.value()),
+                    false => self.tinput.value().to_string(),
+                };
+                let result = self.data.lock().unwrap().apply(Action::NewTask { task });
+                match result {
+                    Ok(()) => {
+                        status::info("Task added");
+                        self.completion = None;
+                        self.input_history.push(self.tinput.value());
+                        if !self.dry_run {
+                            if let Err(e) = self.input_history.save() {
+                                log::error!("Cannot save the input history: {}", e);
+                            }
+                        }
+                        self.leave_input();
+                    }
+                    Err(e) => {
+                        self.error = Some(ErrorScreen::new(format!("Cannot add task: {e}"), None))
+                    }
+                }
+            }
+            KeyCode::Esc => {
+                self.input_history.reset();
+                self.mode = Mode::Normal;
+                self.layout.focus();
+            }
+            KeyCode::Up => {
+                if let Some(line) = self.input_history.older(self.tinput.value()) {
+                    self.tinput = Input::new(line.to_string());
+                }
+            }
+            KeyCode::Down => {
+                if let Some(line) = self.input_history.newer() {
+                    self.tinput = Input::new(line.to_string());
+                }
+            }
+            KeyCode::Tab => self.complete_input(),
+            _ => {
+                self.edit_input(&Event::Key(*key));
+                self.update_completion();
+            }
+        }
+    }
+
+    /// Handles the key pressed while the active task is edited.
+    fn handle_edit_key(&mut self, key: &KeyEvent) {
+        match key.code {
+            KeyCode::Enter if self.prompt_new_categories() => {}
+            KeyCode::Enter => {
+                let result = self.data.lock().unwrap().apply(Action::UpdateActive {
+                    task: self.tinput.value().to_string(),
+                });
+                match result {
+                    Ok(()) => {
+                        status::info("Task updated");
+                        self.completion = None;
+                        self.leave_input();
+                    }
+                    Err(e) => {
+                        self.error = Some(ErrorScreen::new(format!("Cannot edit task: {e}"), None))
+                    }
+                }
+            }
+            KeyCode::Esc => self.leave_input(),
+            KeyCode::Tab => self.complete_input(),
+            _ => {
+                self.edit_input(&Event::Key(*key));
+                self.update_completion();
+            }
+        }
+    }
+
+    /// Handles the key pressed while the due date is typed.
+    fn handle_due_key(&mut self, key: &KeyEvent) {
+        match key.code {
+            KeyCode::Enter => self.set_due(),
+            // The review continues with the same task.
+            KeyCode::Esc if self.review.is_some() => self.next_review_task(),
+            KeyCode::Esc => {
+                self.due_task = None;
+                self.leave_input();
+            }
+            _ => {
+                self.tinput.handle_event(&Event::Key(*key));
+            }
+        }
+    }
+
+    /// Handles the key pressed while a path is typed, e.g. of the export.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The pressed key.
+    /// * `confirm` - Uses the typed path, called on Enter.
+    fn handle_path_key(&mut self, key: &KeyEvent, confirm: fn(&mut Self)) {
+        match key.code {
+            KeyCode::Enter => confirm(self),
+            KeyCode::Esc => self.leave_input(),
+            _ => {
+                self.tinput.handle_event(&Event::Key(*key));
+            }
+        }
+    }
+
+    /// Handles the key pressed while a problem of the health report is fixed.
+    fn handle_fix_key(&mut self, key: &KeyEvent) {
+        match key.code {
+            KeyCode::Enter => self.fix_problem(),
+            KeyCode::Esc => {
+                self.fixing = None;
+                self.leave_input();
+            }
+            KeyCode::Tab => self.complete_input(),
+            _ => {
+                self.tinput.handle_event(&Event::Key(*key));
+            }
+        }
+    }
+
+    /// Handles the key pressed while a command is typed.
+    fn handle_command_key(&mut self, key: &KeyEvent) {
+        match key.code {
+            KeyCode::Enter => {
+                let result = self
+                    .tinput
+                    .value()
+                    .parse::<Command>()
+                    .and_then(|command| self.run_command(&command));
+                self.leave_input();
+                if let Err(e) = result {
+                    self.error = Some(ErrorScreen::new(e.to_string(), None));
+                }
+            }
+            KeyCode::Esc => self.leave_input(),
+            KeyCode::Tab => {
+                let (completed, candidates) = complete_command(
+                    self.tinput.value(),
+                    &self.profiles.others(),
+                    &self.scripts.names(),
+                );
+                if candidates.len() > 1 {
+                    self.toast = Some(Toast::new("Commands", candidates, Duration::from_secs(5)));
+                }
+                self.tinput = completed.into();
+            }
+            _ => {
+                self.tinput.handle_event(&Event::Key(*key));
+            }
+        }
+    }
+
+    /// Handles the key pressed while the filter bar is focused.
+    fn handle_filter_key(&mut self, key: &KeyEvent) {
+        match key.code {
+            KeyCode::Enter => {
+                self.mode = Mode::Normal;
+                self.layout.focus();
+            }
+            KeyCode::Esc => {
+                self.filter_bar.clear(&mut self.data.lock().unwrap());
+                self.mode = Mode::Normal;
+                self.layout.focus();
+            }
+            _ => {
+                self.filter_bar
+                    .handle_event(&Event::Key(*key), &mut self.data.lock().unwrap());
+            }
+        }
+    }
+
+    /// Handles the key pressed while the search is typed.
+    fn handle_search_key(&mut self, key: &KeyEvent) {
+        match key.code {
+            KeyCode::Enter => self.leave_input(),
+            KeyCode::Tab => {
+                self.popup = Some(Popup::ArchiveResults(ArchiveResults::new(
+                    self.config.get_done_path(),
+                    self.tinput.value().to_string(),
+                )));
+                self.leave_input();
+            }
+            KeyCode::Esc => {
+                self.search(None);
+                self.leave_input();
+            }
+            _ => {
+                self.tinput.handle_event(&Event::Key(*key));
+                self.search(Some(self.tinput.value().to_string()));
+            }
+        }
+    }
+
+    /// Handles the key pressed during the review of stale tasks.
+    fn handle_review_key(&mut self, key: &KeyEvent) {
+        let Some(review) = &mut self.review else {
+            return;
+        };
+        match review.handle_key(key, &self.review_keybind) {
+            ReviewKey::Decide(action) => self.review_task(action),
+            ReviewKey::Delete if !self.confirm_review_delete() => {
+                self.review_task(ReviewAction::Delete)
+            }
+            ReviewKey::Delete => {}
+            ReviewKey::Reschedule => {
+                let todo = self.data.lock().unwrap();
+                // The prompt starts with the current due date, so it can be corrected.
+                self.tinput = self
+                    .review
+                    .as_ref()
+                    .and_then(ReviewMode::current)
+                    .and_then(|id| todo.find_task(ToDoData::Pending, id))
+                    .and_then(|index| todo.pending[index].due_date)
+                    .map(|due| due.to_string())
+                    .unwrap_or_default()
+                    .into();
+                drop(todo);
+                self.mode = Mode::Due;
+            }
+            ReviewKey::Next => self.next_review_task(),
+            ReviewKey::Stop => {
+                self.review = Option::None;
+                self.next_review_task();
+            }
+            ReviewKey::Ignore => {}
+        }
+    }
+
+    /// Handles the key pressed during the triage of the inbox.
+    fn handle_triage_key(&mut self, key: &KeyEvent) {
+        let Some(triage) = &mut self.triage else {
+            return;
+        };
+        match triage.handle_key(key) {
+            TriageKey::Add if self.prompt_new_categories() => {}
+            TriageKey::Add => {
+                let result = self.data.lock().unwrap().apply(Action::NewTask {
+                    task: self.tinput.value().to_string(),
+                });
+                match result {
+                    Ok(()) => {
+                        let taken = self
+                            .triage
+                            .as_mut()
+                            .map_or(Ok(()), |triage| triage.take(self.dry_run));
+                        if let Err(e) = taken {
+                            self.error = Some(ErrorScreen::new(
+                                format!("Cannot remove the item from the inbox: {e}"),
+                                None,
+                            ));
+                        }
+                        self.next_triage_item();
+                    }
+                    Err(e) => {
+                        self.error = Some(ErrorScreen::new(format!("Cannot add task: {e}"), None))
+                    }
+                }
+            }
+            TriageKey::Next => self.next_triage_item(),
+            TriageKey::Stop => {
+                self.triage = None;
+                self.next_triage_item();
+            }
+            TriageKey::Input if key.code == KeyCode::Tab => self.complete_input(),
+            TriageKey::Input => {
+                self.tinput.handle_event(&Event::Key(*key));
+            }
+        }
+    }
+}
+
+impl UI {
+    /// Shows only tasks with the subject matching the search.
+    ///
+    /// # Arguments
+    ///
+    /// * `search` - The searched text, `None` or an empty text shows all tasks again.
+    fn search(&mut self, search: Option<String>) {
+        if let Err(e) = self
+            .data
+            .lock()
+            .unwrap()
+            .apply(Action::SetSearch { search })
+        {
+            log::error!("Cannot search the tasks: {}", e);
+        }
+    }
+
+    /// Opens the prompt for projects and contexts of the input not used by any
+    /// task yet, if new categories have to be confirmed.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the prompt was opened and the input must not be saved yet.
+    fn prompt_new_categories(&mut self) -> bool {
+        if !self.confirm_new_categories || std::mem::take(&mut self.categories_confirmed) {
+            return false;
+        }
+        let categories = self
+            .data
+            .lock()
+            .unwrap()
+            .new_categories(self.tinput.value());
+        if categories.is_empty() {
+            return false;
+        }
+        self.category_prompt = Some(CategoryPrompt::new(categories));
+        true
+    }
+
+    /// Appends the changes of tasks made since the last call to the activity log,
+    /// the changes are dropped in the dry run.
+    fn write_activity(&mut self) {
+        let activity = self.data.lock().unwrap().take_activity();
+        if let Some(activity_log) = self.activity_log.as_ref().filter(|_| !self.dry_run) {
+            if let Err(e) = activity_log.append(&activity) {
+                log::error!("Cannot write the activity log: {}", e);
+                self.error = Some(ErrorScreen::new(
+                    format!("Cannot write the activity log: {e}"),
+                    None,
+                ));
+            }
+        }
+    }
+
+    /// Appends the tasks flushed from the trash since the last call to the trash file,
+    /// the tasks are dropped without the file and in the dry run.
+    fn write_trash(&mut self) {
+        let flushed = self.data.lock().unwrap().take_flushed();
+        if let Some(path) = self.trash_path.as_ref().filter(|_| !self.dry_run) {
+            if let Err(e) = append_trash(Path::new(path), &flushed) {
+                log::error!("Cannot write the trash file {}: {}", path, e);
+            }
+        }
+    }
+
+    /// Shows the lines of the activity log matching the filter in a popup.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - Words a line must contain to be shown.
+    fn show_activity(&mut self, filter: &str) {
+        let activity_log = match &self.activity_log {
+            Some(activity_log) => activity_log,
+            None => {
+                self.error = Some(ErrorScreen::new(
+                    "The activity log is disabled, set activity_log_path to enable it",
+                    None,
+                ));
+                return;
+            }
+        };
+        match activity_log.read(filter) {
+            Ok(lines) => self.popup = Some(Popup::Activity(ActivityView::new(lines, filter))),
+            Err(e) => {
+                self.popup = None;
+                self.error = Some(ErrorScreen::new(
+                    format!("Cannot read the activity log: {e}"),
+                    None,
+                ));
+            }
+        }
+    }
+
+    /// Selects the crumb under a left click on the breadcrumb line.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The mouse event.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the event is a click on the breadcrumb line.
+    fn click_breadcrumb(&mut self, event: &MouseEvent) -> bool {
+        let chunk = self.breadcrumb_chunk;
+        let breadcrumb = match &self.breadcrumb {
+            Some(breadcrumb)
+                if event.kind == MouseEventKind::Down(MouseButton::Left)
+                    && event.row == chunk.y
+                    && (chunk.x..chunk.x + chunk.width).contains(&event.column) =>
+            {
+                breadcrumb
+            }
+            _ => return false,
+        };
+        let mut todo = self.data.lock().unwrap();
+        if let Some(index) = breadcrumb.crumb_at(&todo, event.column - chunk.x) {
+            breadcrumb.select(&mut todo, index);
+        }
+        true
+    }
+
+    /// Triggers the action under a left click on the action bar.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The mouse event.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the event is a click on the action bar.
+    fn click_action_bar(&mut self, event: &MouseEvent) -> bool {
+        let chunk = self.action_bar_chunk;
+        let action = match &self.action_bar {
+            Some(action_bar)
+                if event.kind == MouseEventKind::Down(MouseButton::Left)
+                    && chunk.height > 0
+                    && event.row == chunk.y
+                    && (chunk.x..chunk.x + chunk.width).contains(&event.column) =>
+            {
+                action_bar
+                    .action_at(self.layout.get_active_widget(), event.column - chunk.x)
+                    .cloned()
+            }
+            _ => return false,
+        };
+        if let Some(action) = action {
+            log::debug!("Action bar triggers {:?}", action.event);
+            match action.scope {
+                KeybindScope::Window => self.handle_event(action.event),
+                _ => self.layout.handle_event(action.event),
+            };
+        }
+        true
+    }
+
+    /// Moves the category prompt to the next new category, the input is saved
+    /// once all of them are confirmed.
+    fn next_category(&mut self) {
+        if let Some(prompt) = &mut self.category_prompt {
+            if !prompt.advance() {
+                self.confirm_categories();
+            }
+        }
+    }
+
+    /// Closes the category prompt and saves the input, all its categories were confirmed.
+    fn confirm_categories(&mut self) {
+        self.category_prompt = None;
+        self.categories_confirmed = true;
+        self.handle_event_window(Event::Key(KeyEvent::new(
+            KeyCode::Enter,
+            KeyModifiers::NONE,
+        )));
+        self.categories_confirmed = false;
+    }
+
+    /// Replaces the task of the problem being fixed with the input, a line that
+    /// was not loaded at all is added as a new task.
+    fn fix_problem(&mut self) {
+        let task = self.tinput.value().to_string();
+        let result = {
+            let mut todo = self.data.lock().unwrap();
+            match self
+                .fixing
+                .as_ref()
+                .and_then(|problem| problem.task.clone())
+            {
+                Some((data, id)) if todo.find_task(data, &id).is_none() => {
+                    Err(format!("The task {} is not in the list anymore", id.task))
+                }
+                Some((data, id)) => todo
+                    .apply(Action::SetActive { data, task: id })
+                    .and_then(|_| todo.apply(Action::UpdateActive { task }))
+                    .map_err(|e| format!("Cannot fix task: {e}")),
+                None => todo
+                    .apply(Action::NewTask { task })
+                    .map_err(|e| format!("Cannot fix task: {e}")),
+            }
+        };
+        match result {
+            Ok(()) => {
+                self.fixing = None;
+                self.leave_input();
+                self.remove_problem();
+            }
+            Err(message) => self.error = Some(ErrorScreen::new(message, Option::None)),
+        }
+    }
+
+    /// Removes the selected problem from the health report,
+    /// the report is closed once all problems are resolved.
+    fn remove_problem(&mut self) {
+        if let Some(report) = &mut self.health_report {
+            if !report.remove_selected() {
+                self.health_report = None;
+            }
+        }
+    }
+
+    /// Merges the unsaved changes with the changes of the file(s), the lines of both are kept
+    /// where they conflict.
+    fn keep_both(&mut self) {
+        if let Err(e) = self.tx.send(FileWorkerCommands::KeepBoth) {
+            log::error!("Error while send signal to merge todo list: {}", e);
+            self.error = Some(ErrorScreen::new("Cannot merge todo list", Option::None));
+        }
+    }
+
+    /// Saves the todo list to the file(s).
+    fn save(&mut self) {
+        if let Err(e) = self.tx.send(FileWorkerCommands::ForceSave) {
+            log::error!("Error while send signal to save todo list: {}", e);
+            self.error = Some(ErrorScreen::new("Cannot save todo list", Option::None));
+        }
+    }
+
+    /// Saves the todo list to the file(s) even if they could not be loaded.
+    fn save_anyway(&mut self) {
+        if let Err(e) = self.tx.send(FileWorkerCommands::SaveAnyway) {
+            log::error!("Error while send signal to save todo list: {}", e);
+            self.error = Some(ErrorScreen::new("Cannot save todo list", Option::None));
+        }
+    }
+
+    /// Saves the todo list and quits once the file worker has saved it,
+    /// an error of the save is shown instead of quitting.
+    fn save_and_quit(&mut self) {
+        if self.dry_run || self.save_now(UIEvent::Quit) {
+            self.quit();
+        }
+    }
+
+    /// Saves the todo list and waits until the file worker has saved it.
+    ///
+    /// # Arguments
+    ///
+    /// * `retry` - The event handled again when the user retries after the save timed out.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the todo list was not saved, the error is shown to the user.
+    fn save_now(&mut self, retry: UIEvent) -> bool {
+        self.save();
+        let deadline = Instant::now() + SAVE_TIMEOUT;
+        while self.data.lock().unwrap().is_dirty() {
+            if self.receive_error() {
+                return false;
+            }
+            if Instant::now() >= deadline {
+                self.error = Some(ErrorScreen::new(
+                    "The todo list was not saved in time.",
+                    Some(retry),
+                ));
+                return false;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        true
+    }
+
+    /// Saves the UI state and the task metadata, empties the trash and quits the application.
+    fn quit(&mut self) {
+        if let Some(path) = self.save_state_path.as_ref().filter(|_| !self.dry_run) {
+            if let Err(e) = UIState::new(&self.layout, &self.data).save(path) {
+                log::error!("Error while saveing UI state: {}", e);
+            }
+        }
+        self.save_meta();
+        self.flush_trash();
+        self.quit = true;
+    }
+
+    /// Flushes the trash and appends the flushed tasks to the trash file.
+    fn flush_trash(&mut self) {
+        if let Err(e) = self.data.lock().unwrap().apply(Action::FlushTrash) {
+            log::error!("Cannot flush the trash: {}", e);
+        }
+        self.write_trash();
+    }
+
+    /// Saves the task metadata of the open todo file, nothing is saved in the dry run.
+    fn save_meta(&self) {
+        if !self.dry_run {
+            let path = self.config.get_metadata_path();
+            if let Err(e) = self.data.lock().unwrap().save_meta(&path) {
+                log::error!("Cannot save the task metadata to {}: {}", path, e);
+            }
+        }
+    }
+
+    /// Opens another profile of the `profiles` table in the same layout. The todo list
+    /// is saved first, the filters of every profile are kept while the profile is closed.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The position of the opened profile relative to the open profile.
+    fn switch_profile(&mut self, offset: isize) {
+        if !self.profiles.can_switch() || (!self.dry_run && !self.save_now(UIEvent::Save)) {
+            return;
+        }
+        self.save_meta();
+        // The deleted tasks belong to the closed todo file.
+        self.flush_trash();
+        let index = self.profiles.offset(offset);
+        if !self.profiles.switch(index, &mut self.data.lock().unwrap()) {
+            return;
+        }
+        self.open_todo(self.profiles.config(&self.config));
+        self.toast = Some(Toast::new(
+            "Profile",
+            vec![format!("{} ({})", self.profiles.name(), self.todo_path)],
+            Duration::from_secs(3),
+        ));
+    }
+
+    /// Opens another todo file in the same layout, the todo list is saved first.
+    /// The filters and the sorting are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the todo file.
+    fn open_todo_file(&mut self, path: &str) {
+        if !self.dry_run && !self.save_now(UIEvent::Save) {
+            return;
+        }
+        self.save_meta();
+        self.flush_trash();
+        self.open_todo(self.config.with_todo_file(path, Option::None));
+        status::info(format!("Opened {path}"));
+    }
+
+    /// Loads the todo file of the configuration and starts a new file worker for it,
+    /// the file worker of the closed todo file is stopped.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration with the paths of the opened todo file.
+    fn open_todo(&mut self, config: Config) {
+        if let Err(e) = self.tx.send(FileWorkerCommands::Exit) {
+            log::error!("Error while send signal to stop the file worker: {}", e);
+        }
+        // The lock of the closed todo file is released before the opened one is locked.
+        self.instance = None;
+        let (file_config, instance, other_instance) = Self::lock_todo_file(&config);
+        {
+            let mut todo = self.data.lock().unwrap();
+            todo.set_config(ToDoConfig::from(&config));
+            // Tasks of the closed profile must not be saved to a missing todo file.
+            todo.move_data(ToDo::new(ToDoConfig::from(&config), Styles::from(&config)));
+            todo.mark_saved();
+            self.error = Self::load_todo_extras(&config, &mut todo);
+        }
+        let (tx, errors, problems, error) = Self::start_file_worker(&file_config, &self.data);
+        self.set_instance(&file_config, instance, other_instance);
+        self.tx = tx;
+        self.errors = errors;
+        self.error = error.or(self.error.take());
+        self.health_report = (!problems.is_empty()).then(|| HealthReport::new(problems));
+        self.create_prompt = Self::missing_file_prompt(&config);
+        self.read_only = false;
+        self.changed_at = None;
+        self.todo_path = config.get_todo_path();
+        self.archive_path = config.get_archive_path();
+        self.trash_path = config.get_trash_path();
+        if self.breadcrumb.is_some() {
+            self.breadcrumb = Some(Breadcrumb::new(&config));
+        }
+        if self.action_bar.is_some() {
+            self.action_bar = Some(ActionBar::new(&config));
+        }
+        self.config = config;
+    }
+
+    /// Runs a command entered in the command mode, the commands saving
+    /// and quitting are handled here, the others by the layout.
+    fn run_command(&mut self, command: &Command) -> ToDoRes<()> {
+        match command {
+            Command::Write => {
+                self.handle_event(UIEvent::Save);
+            }
+            Command::Quit { force: false } => {
+                self.handle_event(UIEvent::Quit);
+            }
+            Command::Quit { force: true } => self.quit(),
+            Command::WriteQuit => self.save_and_quit(),
+            Command::Sort(sort) => {
+                let data = match self.layout.get_active_widget() {
+                    WidgetType::Done => ToDoData::Done,
+                    _ => ToDoData::Pending,
+                };
+                let action = Action::SetSort { data, sort: *sort };
+                if let Err(e) = self.data.lock().unwrap().apply(action) {
+                    log::error!("Cannot sort the tasks: {}", e);
+                }
+            }
+            Command::Filter(query) => {
+                self.filter_bar.set(&mut self.data.lock().unwrap(), query);
+            }
+            Command::Archive => {
+                self.handle_event(UIEvent::Archive);
+            }
+            Command::Edit(path) => self.open_todo_file(path),
+            Command::Send(profile) => self.send_tasks(profile)?,
+            Command::Run(name) => self.run_script(name)?,
+            Command::Import(path) => self.import_tasks(path)?,
+            Command::Age => {
+                let mut todo = self.data.lock().unwrap();
+                let changes = todo.aging_changes();
+                if let Err(e) = todo.apply(Action::AgePriorities) {
+                    log::error!("Cannot age the priorities: {}", e);
+                }
+                drop(todo);
+                let (title, changes) = match changes.is_empty() {
+                    true => ("Aging", vec![String::from("No priority is aged")]),
+                    false => ("Aged", changes),
+                };
+                self.toast = Some(Toast::new(title, changes, Duration::from_secs(10)));
+            }
+            command => {
+                self.layout.focus();
+                return self.layout.handle_command(command);
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs the script bound to the key, see [`Scripts`].
+    ///
+    /// # Returns
+    ///
+    /// `true` if a script is bound to the key.
+    fn run_bound_script(&mut self, key: &KeyCode) -> bool {
+        let Some(name) = self.scripts.bound(key).map(String::from) else {
+            return false;
+        };
+        if let Err(e) = self.run_script(&name) {
+            status::error(e.to_string());
+        }
+        true
+    }
+
+    /// Runs the script on the todo list, changes made before an error are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the script.
+    fn run_script(&mut self, name: &str) -> ToDoRes<()> {
+        self.scripts.run(name, &mut self.data.lock().unwrap())?;
+        status::info(format!("Script '{name}' finished"));
+        Ok(())
+    }
+
+    /// Appends the tasks of a Taskwarrior JSON export or a Markdown checklist to the todo list.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the imported file, its extension gives the format.
+    fn import_tasks(&mut self, path: &str) -> ToDoRes<()> {
+        let format = ImportFormat::from_path(Path::new(path))?;
+        let content = fs::read_to_string(path).map_err(|err| ToDoIoError {
+            path: PathBuf::from(path),
+            err,
+        })?;
+        let mut todo = self.data.lock().unwrap();
+        let tasks = format.parse(&content, todo.clock().today())?;
+        todo.import(&tasks)
+            .map_err(|e| ToDoError::ParseImport(e.to_string()))?;
+        status::info(format!("{} tasks imported from {path}", tasks.len()));
+        Ok(())
+    }
+
+    /// Moves the marked tasks or the selected task of the focused list to the todo file
+    /// of another profile. The tasks are put to the trash of the open todo list, so they
+    /// can be restored if they were sent by mistake.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - The name of the profile the tasks are sent to.
+    fn send_tasks(&mut self, profile: &str) -> ToDoRes<()> {
+        let tasks = self.layout.target_tasks();
+        if tasks.is_empty() {
+            return Err(ToDoError::CommandNotSupported);
+        }
+        if !self.profiles.others().contains(&profile) {
+            return Err(ToDoError::UnknownProfile(profile.to_string()));
+        }
+        if self.dry_run {
+            status::error("Tasks are not sent in the dry run.");
+            return Ok(());
+        }
+        self.profiles.send(profile, &tasks)?;
+        self.layout.handle_event(UIEvent::RemoveItem);
+        status::info(format!("{} tasks moved to {profile}", tasks.len()));
+        Ok(())
+    }
+
+    /// Moves the completed tasks to the done file and saves the todo list without them.
+    fn archive(&mut self) {
+        let done_path = self.config.get_done_path();
+        let message = if self.dry_run {
+            Err(String::from("Tasks are not archived in the dry run."))
+        } else if self
+            .archive_path
+            .as_ref()
+            .is_some_and(|path| Path::new(path) == Path::new(&done_path))
+        {
+            Err(format!(
+                "Completed tasks are already stored in the archive file {done_path}."
+            ))
+        } else {
+            match self.data.lock().unwrap().archive_done(&done_path) {
+                Ok(0) => Ok(String::from("There is no completed task to archive")),
+                Ok(count) => {
+                    if let Err(e) = self.tx.send(FileWorkerCommands::Save) {
+                        log::error!("Error while send signal to save todo list: {}", e);
+                    }
+                    Ok(format!("{count} completed tasks moved to {done_path}"))
+                }
+                Err(e) => Err(format!("Cannot archive tasks to {done_path}: {e}")),
+            }
+        };
+        match message {
+            Ok(message) => {
+                self.toast = Some(Toast::new(
+                    "Archived",
+                    vec![message],
+                    Duration::from_secs(5),
+                ))
+            }
+            Err(message) => self.error = Some(ErrorScreen::new(message, None)),
+        }
+    }
+
+    /// Sets the due date of the task the due date prompt was opened for by the typed date.
+    /// The prompt stays open if the date is invalid.
+    fn set_due(&mut self) {
+        let due = self.tinput.value().to_string();
+        if let Err(e) = due.parse::<DateExpr>() {
+            self.error = Some(ErrorScreen::new(e.to_string(), None));
+            return;
+        }
+        if self.review.is_some() {
+            return self.review_task(ReviewAction::Reschedule(due));
+        }
+        if let Some((data, task)) = self.due_task.take() {
+            if let Err(e) = self
+                .data
+                .lock()
+                .unwrap()
+                .apply(Action::SetDue { data, task, due })
+            {
+                log::error!("Cannot set the due date: {}", e);
+            }
+        }
+        self.leave_input();
+    }
+
+    /// Copies the focused task list as it is shown, filtered and sorted, to the clipboard.
+    fn copy_view(&mut self) {
+        let Some(text) = self.layout.active_text() else {
+            self.toast = Some(Toast::new(
+                "Copy",
+                vec![String::from("There are no tasks to copy")],
+                Duration::from_secs(5),
+            ));
+            return;
+        };
+        let copied = match self.clipboard_command.is_empty() {
+            true => {
+                self.terminal_clipboard = Some(text.clone());
+                Ok(())
+            }
+            false => copy(&text, &self.clipboard_command),
+        };
+        match copied {
+            Ok(()) => {
+                self.toast = Some(Toast::new(
+                    "Copied",
+                    vec![format!(
+                        "{} lines copied to the clipboard",
+                        text.lines().count()
+                    )],
+                    Duration::from_secs(5),
+                ))
+            }
+            Err(e) => {
+                self.error = Some(ErrorScreen::new(
+                    format!("Cannot copy to the clipboard: {e}"),
+                    None,
+                ))
+            }
+        }
+    }
+
+    /// Exports the focused task list as it is shown, filtered and sorted, to the file
+    /// typed in the prompt. The format is given by the extension of the file.
+    fn export_view(&mut self) {
+        let path = self.tinput.value().trim().to_string();
+        let format = match ExportFormat::from_path(Path::new(&path)) {
+            Ok(format) => format,
+            Err(e) => {
+                self.error = Some(ErrorScreen::new(e.to_string(), None));
+                return;
+            }
+        };
+        let template = match self.config.export_template() {
+            Ok(template) => template,
+            Err(e) => {
+                self.error = Some(ErrorScreen::new(e.to_string(), None));
+                return;
+            }
+        };
+        let tasks = self.layout.active_tasks().unwrap_or_default();
+        let content = match &template {
+            Some(template) => {
+                let todo = self.data.lock().unwrap();
+                format.serialize_with(&tasks, self.clock.now(), template, &todo)
+            }
+            None => format.serialize(&tasks, self.clock.now()),
+        };
+        match fs::write(&path, content) {
+            Ok(()) => {
+                self.toast = Some(Toast::new(
+                    "Exported",
+                    vec![format!("{} tasks exported to {path}", tasks.len())],
+                    Duration::from_secs(5),
+                ));
+                self.export_path = path;
+                self.leave_input();
+            }
+            Err(e) => {
+                self.error = Some(ErrorScreen::new(
+                    format!("Cannot export to {path}: {e}"),
+                    None,
+                ))
+            }
+        }
+    }
+
+    /// Opens the view comparing the todo list with the todo file typed in the prompt.
+    fn open_compare_view(&mut self) {
+        let path = self.tinput.value().trim().to_string();
+        let view = CompareView::new(&self.data.lock().unwrap(), &path);
+        match view {
+            Ok(view) => {
+                self.popup = Some(Popup::Compare(view));
+                self.compare_path = path;
+                self.leave_input();
+            }
+            Err(e) => self.error = Some(ErrorScreen::new(format!("Cannot read {path}: {e}"), None)),
+        }
+    }
+
+    /// Copies the task selected in the compare view to the other side.
+    fn copy_compared(&mut self) {
+        let Some(Popup::Compare(view)) = &mut self.popup else {
+            return;
+        };
+        match view.copy_selected() {
+            Ok(Some(task)) => {
+                let mut todo = self.data.lock().unwrap();
+                if let Err(e) = todo.apply(Action::CopyTask { task }) {
+                    log::error!("Cannot copy the task: {}", e);
+                }
+                view.reload(&todo);
+                drop(todo);
+                self.layout.data_changed();
+            }
+            Ok(None) => {}
+            Err(e) => {
+                self.error = Some(ErrorScreen::new(
+                    format!("Cannot write {}: {e}", self.compare_path),
+                    None,
+                ))
+            }
+        }
+    }
+
+    /// Shows the history of all completed tasks. The done file is read only when it has
+    /// changed since the last time, completed tasks not archived yet are added to it.
+    fn show_history(&mut self) {
+        let done_path = self.config.get_done_path();
+        // The done file loaded as the archive is already in the completed tasks.
+        let loaded = self
+            .archive_path
+            .as_ref()
+            .is_some_and(|path| Path::new(path) == Path::new(&done_path));
+        let archived = match loaded {
+            true => Ok(History::default()),
+            false => self.history.get(&done_path).cloned(),
+        };
+        match archived {
+            Ok(archived) => {
+                let history = self.data.lock().unwrap().history(&archived);
+                let report = HistoryReport::new(history, self.clock.today());
+                self.popup = Some(Popup::HistoryReport(report));
+            }
+            Err(e) => {
+                self.toast = Some(Toast::new(
+                    "History",
+                    vec![format!("Cannot read {done_path}: {e}")],
+                    Duration::from_secs(5),
+                ))
+            }
+        }
+    }
+
+    /// Shows the report comparing the estimated and spent time of completed tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `by_week` - Whether the tasks are grouped by the week they were completed in.
+    fn show_effort_report(&mut self, by_week: bool) {
+        let rows = self.data.lock().unwrap().effort_report(by_week);
+        self.popup = Some(Popup::EffortReport(EffortReport::new(rows, by_week)));
+    }
+
+    /// Applies the keybindings of the editor and writes them to the configuration file.
+    /// The layout is rebuilt so its widgets use the new keybindings.
+    fn save_keybinds(&mut self) {
+        let Some(Popup::KeybindEditor(editor)) = self.popup.take() else {
+            return;
+        };
+        editor.apply(&mut self.config);
+        self.event_handler = self.config.get_window_keybind();
+        self.review_keybind = self.config.get_review_keybind();
+        if self.action_bar.is_some() {
+            self.action_bar = Some(ActionBar::new(&self.config));
+        }
+        self.rebuild_layout();
+        match self.config.save_keybinds() {
+            Ok(()) => {
+                // The written keybindings are applied already.
+                if let Some(watcher) = &mut self.config_watcher {
+                    watcher.sync();
+                }
+                self.toast = Some(Toast::new(
+                    "Keybindings",
+                    vec![format!(
+                        "Saved to {}",
+                        self.config.get_config_path().display()
+                    )],
+                    Duration::from_secs(5),
+                ))
+            }
+            Err(e) => {
+                self.error = Some(ErrorScreen::new(
+                    format!("Cannot save the keybindings: {e}"),
+                    Option::None,
+                ))
+            }
+        }
+    }
+
+    /// Shows the changes of the files before the todo list is saved,
+    /// the todo list is saved right away when nothing changes.
+    fn review_save(&mut self) {
+        let diff = FileWorker::save_preview(
+            &self.todo_path,
+            self.archive_path.as_deref(),
+            &self.data.lock().unwrap(),
+        );
+        match diff {
+            Ok(diff) if diff.is_empty() => self.save(),
+            Ok(diff) => self.popup = Some(Popup::SaveReview(SaveReview::new(&diff))),
+            Err(e) => {
+                self.error = Some(ErrorScreen::new(
+                    format!("Cannot compare the todo list with the file: {e}"),
+                    Option::None,
+                ))
+            }
+        }
+    }
+
+    /// Starts the triage of the inbox.
+    fn start_triage(&mut self) {
+        let path = match &self.inbox_path {
+            Some(path) => path,
+            None => {
+                self.error = Some(ErrorScreen::new(
+                    "The inbox is not set, set its path with inbox_path.",
+                    Option::None,
+                ));
+                return;
+            }
+        };
+        match Inbox::load(path) {
+            Ok(inbox) if inbox.current().is_none() => {
+                self.error = Some(ErrorScreen::new(
+                    format!("The inbox {path} is empty."),
+                    Option::None,
+                ));
+            }
+            Ok(inbox) => {
+                self.triage = Some(TriageMode::new(inbox));
+                self.layout.unfocus();
+                self.next_triage_item();
+            }
+            Err(e) => {
+                self.error = Some(ErrorScreen::new(
+                    format!("Cannot load the inbox {path}: {e}"),
+                    Option::None,
+                ));
+            }
+        }
+    }
+
+    /// Adds the task typed in the quick-add popup and closes the popup,
+    /// the focus and the filters stay as they are.
+    fn quick_add_task(&mut self) {
+        let Some(Popup::QuickAdd(quick_add)) = &self.popup else {
+            return;
+        };
+        let task = quick_add.input.value().trim().to_string();
+        if task.is_empty() {
+            self.popup = None;
+            return;
+        }
+        match self.data.lock().unwrap().apply(Action::NewTask { task }) {
+            Ok(()) => status::info("Task added"),
+            Err(e) => {
+                self.error = Some(ErrorScreen::new(
+                    format!("Cannot add task: {e}"),
+                    Option::None,
+                ));
+                return;
+            }
+        }
+        self.popup = None;
+    }
+
+    /// Starts the review of tasks without activity for `review_age` days.
+    fn start_review(&mut self) {
+        let review = Review::new(&self.data.lock().unwrap(), self.review_age);
+        if review.current().is_none() {
+            self.error = Some(ErrorScreen::new(
+                format!("No task is older than {} days.", self.review_age),
+                Option::None,
+            ));
+            return;
+        }
+        self.review = Some(ReviewMode::new(review));
+        self.layout.unfocus();
+        self.next_review_task();
+    }
+
+    /// Applies the decision about the reviewed task and continues with the next task.
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The decision about the task.
+    fn review_task(&mut self, action: ReviewAction) {
+        let Some(review) = &mut self.review else {
+            return;
+        };
+        if let Some(task) = review.current() {
+            let action = Action::ReviewTask {
+                task: task.clone(),
+                action,
+            };
+            if let Err(e) = self.data.lock().unwrap().apply(action) {
+                self.error = Some(ErrorScreen::new(
+                    format!("Cannot review the task: {e}"),
+                    Option::None,
+                ));
+            }
+        }
+        review.next();
+        self.next_review_task();
+    }
+
+    /// Shows the task being reviewed in the input or ends the review when all tasks
+    /// were walked through. Tasks removed or edited meanwhile are skipped.
+    fn next_review_task(&mut self) {
+        while let Some(review) = &mut self.review {
+            let Some(id) = review.current() else {
+                break;
+            };
+            let todo = self.data.lock().unwrap();
+            match todo.find_task(ToDoData::Pending, id) {
+                Some(index) => {
+                    self.tinput = todo.pending[index].to_string().into();
+                    self.mode = Mode::Review;
+                    return;
+                }
+                None => review.next(),
+            }
+        }
+        self.review = Option::None;
+        self.leave_input();
+    }
+
+    /// Shows the next item of the inbox in the input or ends
+    /// the triage when all items were walked through.
+    fn next_triage_item(&mut self) {
+        match self.triage.as_ref().and_then(TriageMode::current) {
+            Some(item) => {
+                self.tinput = item.into();
+                self.mode = Mode::Triage;
+            }
+            None => {
+                self.triage = None;
+                self.leave_input();
+            }
+        }
+    }
+}
+
+/// Enables raw mode and switches to the alternate screen with mouse capture.
+fn setup_terminal() -> io::Result<()> {
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)
+}
+
+/// Returns the terminal to the state it had before the user interface started.
+fn restore_terminal() -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(
+        io::stdout(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        Show
+    )
+}
+
+impl HandleEvent for UI {
+    fn get_event(&self, key: &KeyCode) -> UIEvent {
+        self.event_handler.get_event(key)
+    }
+
+    fn handle_event(&mut self, event: UIEvent) -> bool {
+        use UIEvent::*;
+        match event {
+            Quit => {
+                if self.dry_run || !self.data.lock().unwrap().is_dirty() {
+                    self.quit();
+                } else if self.confirm_quit {
+                    self.confirm_prompt = Some(ConfirmPrompt::new(
+                        Confirmation::Quit,
+                        "The todo list has unsaved changes.",
+                    ));
+                } else {
+                    self.save_and_quit();
+                }
+            }
+            InsertMode | InsertScratch => {
+                self.scratch_input = event == InsertScratch;
+                self.mode = Mode::Input;
+                self.completion = Option::None;
+                self.layout.unfocus();
+            }
+            TriageMode => self.start_triage(),
+            ReviewMode => self.start_review(),
+            Reveal => {
+                self.layout.reveal();
+            }
+            CommandMode => {
+                self.tinput.reset();
+                self.mode = Mode::Command;
+                self.layout.unfocus();
+            }
+            FilterMode => {
+                self.filter_bar.edit(&self.data.lock().unwrap());
+                self.mode = Mode::Filter;
+                self.layout.unfocus();
+            }
+            SearchMode => {
+                let search = self.data.lock().unwrap().search().map(String::from);
+                self.tinput = search.unwrap_or_default().into();
+                self.mode = Mode::Search;
+                self.layout.unfocus();
+            }
+            MoveRight => {
+                self.layout.right();
+            }
+            MoveLeft => {
+                self.layout.left();
+            }
+            MoveUp => {
+                self.layout.up();
+            }
+            MoveDown => {
+                self.layout.down();
+            }
+            Save => {
+                if self.review_save {
+                    self.review_save();
+                } else {
+                    self.save();
+                }
+            }
+            Load => {
+                if let Err(e) = self.tx.send(FileWorkerCommands::ForceLoad) {
+                    log::error!("Error while send signal to load todo list: {}", e);
+                    self.error = Some(ErrorScreen::new("Cannot load todo list", Option::None));
+                }
+            }
+            ToggleRawLines => {
+                if let Err(e) = self.data.lock().unwrap().apply(Action::ToggleRawLines) {
+                    log::error!("Cannot toggle raw lines: {}", e);
+                }
+                // The raw lines do not have the header of the table view.
+                self.layout.update_chunk(self.layout_chunk);
+            }
+            ToggleSortOnInsert => {
+                if let Err(e) = self.data.lock().unwrap().apply(Action::ToggleSortOnInsert) {
+                    log::error!("Cannot toggle sort on insert: {}", e);
+                }
+            }
+            CycleMyTasks => {
+                if let Err(e) = self.data.lock().unwrap().apply(Action::CycleMyTasks) {
+                    log::error!("Cannot change the assignee filter: {}", e);
+                }
+            }
+            ToggleFilters => {
+                if let Err(e) = self.data.lock().unwrap().apply(Action::ToggleFilters) {
+                    log::error!("Cannot toggle the filters: {}", e);
+                }
+            }
+            ShowActivity => self.show_activity(""),
+            SplitHorizontal | SplitVertical => {
+                let direction = match event {
+                    SplitHorizontal => Direction::Horizontal,
+                    _ => Direction::Vertical,
+                };
+                if let Err(e) = self.layout.split(direction, &self.config) {
+                    self.error = Some(ErrorScreen::new(e.to_string(), Option::None));
+                }
+            }
+            CloseSplit => {
+                self.layout.close_split();
+            }
+            OpenSwitcher => {
+                self.popup = Some(Popup::Switcher(Switcher::build(
+                    &self.data.lock().unwrap(),
+                    &self.config.get_list_queries(),
+                )));
+            }
+            QuickAdd => {
+                let initial = match self.quick_add_filters {
+                    true => self.data.lock().unwrap().selected_categories(),
+                    false => String::new(),
+                };
+                self.popup = Some(Popup::QuickAdd(QuickAddPopup::new(&initial)));
+            }
+            ShowEffortReport => self.show_effort_report(true),
+            ShowChangelog => {
+                if let Some(release) = &self.release {
+                    self.toast = Option::None;
+                    self.popup = Some(Popup::Changelog(ChangelogView::new(release)));
+                } else {
+                    self.toast = Some(Toast::new(
+                        "Changelog",
+                        vec![String::from("No newer version has been found")],
+                        Duration::from_secs(5),
+                    ));
+                }
+            }
+            ShowForecast => {
+                let days = self.config.get_forecast_days();
+                let rows = self.data.lock().unwrap().forecast(self.clock.today(), days);
+                self.popup = Some(Popup::ForecastReport(ForecastReport::new(rows, days)));
+            }
+            ShowHistory => self.show_history(),
+            ShowPlanner => {
+                let view = PlannerView::new(&self.data.lock().unwrap(), self.clock.today());
+                self.popup = Some(Popup::Planner(view));
+            }
+            ShowTrash => {
+                let view = TrashView::new(&self.data.lock().unwrap());
+                self.popup = Some(Popup::Trash(view));
+            }
+            NextProfile => self.switch_profile(1),
+            PrevProfile => self.switch_profile(-1),
+            Archive if self.confirm_archive => {
+                let count = self.data.lock().unwrap().done.len();
+                self.confirm_prompt = Some(ConfirmPrompt::new(
+                    Confirmation::Archive,
+                    format!("Move {count} completed tasks to the done file?"),
+                ));
+            }
+            Archive => self.archive(),
+            ShowDeadlines => {
+                let today = self.clock.today();
+                let risks = self.data.lock().unwrap().at_risk_projects(today);
+                self.popup = Some(Popup::DeadlineReport(DeadlineReport::new(risks, today)));
+            }
+            EditKeybinds => {
+                self.popup = Some(Popup::KeybindEditor(KeybindEditor::new(&self.config)));
+            }
+            BreadcrumbBack => {
+                if let Some(breadcrumb) = &self.breadcrumb {
+                    breadcrumb.back(&mut self.data.lock().unwrap());
+                }
+            }
+            ReopenLast => {
+                if let Err(e) = self.data.lock().unwrap().apply(Action::ReopenLastCompleted) {
+                    log::error!("Cannot reopen the last completed task: {}", e);
+                }
+            }
+            ClearFilters | PushFilters | PopFilters => {
+                let action = match event {
+                    ClearFilters => Action::ClearFilters,
+                    PushFilters => Action::PushFilters,
+                    _ => Action::PopFilters,
+                };
+                if let Err(e) = self.data.lock().unwrap().apply(action) {
+                    log::error!("Cannot change the filter stack: {}", e);
+                }
+            }
+            EditMode => {
+                if let Some(active) = self.data.lock().unwrap().get_active() {
+                    self.tinput = active.to_string().into();
+                    self.mode = Mode::Edit;
+                    self.completion = Option::None;
+                    self.layout.unfocus();
+                    // self.in
+                }
+            }
+            CopyView => self.copy_view(),
+            ExportMode => {
+                let is_task_list = matches!(
+                    self.layout.get_active_widget(),
+                    WidgetType::List | WidgetType::Done
+                );
+                if !is_task_list {
+                    return false;
+                }
+                // The prompt starts with the last destination, so exporting again is one key.
+                self.tinput = self.export_path.clone().into();
+                self.mode = Mode::Export;
+                self.layout.unfocus();
+            }
+            CompareMode => {
+                self.tinput = self.compare_path.clone().into();
+                self.mode = Mode::Compare;
+                self.layout.unfocus();
+            }
+            DueMode => {
+                let is_task_list = matches!(
+                    self.layout.get_active_widget(),
+                    WidgetType::List | WidgetType::Done
+                );
+                if !is_task_list || !self.layout.handle_event(UIEvent::Select) {
+                    return false;
+                }
+                let todo = self.data.lock().unwrap();
+                self.due_task = todo.active_id();
+                // The prompt starts with the current due date, so it can be corrected.
+                self.tinput = todo
+                    .get_active()
+                    .and_then(|task| task.due_date)
+                    .map(|due| due.to_string())
+                    .unwrap_or_default()
+                    .into();
+                drop(todo);
+                self.mode = Mode::Due;
+                self.layout.unfocus();
+            }
+            EditItem => {
+                let is_task_list = matches!(
+                    self.layout.get_active_widget(),
+                    WidgetType::List | WidgetType::Done
+                );
+                // Selecting the task makes it active, so it is edited the same way as by EditMode.
+                if is_task_list && self.layout.handle_event(UIEvent::Select) {
+                    return self.handle_event(EditMode);
+                }
+            }
+            _ => {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyEvent, KeyModifiers};
+    use std::env;
+    use test_log::test;
+
+    use super::*;
+    use crate::todo::TaskSort;
+
+    fn default_ui() -> Result<UI, Box<dyn Error>> {
+        let config = Config::load_from_buffer(
+            format!(
+                r#"
+            todo_path = "{}todo.txt"
 
             [[list_keybind.events]]
             event = "ListDown"
@@ -488,4 +3487,703 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn headless_keys() -> Result<(), Box<dyn Error>> {
+        let config = Config::load_from_buffer(
+            format!(
+                r#"
+            todo_path = "{}todo.txt"
+            dry_run = true
+            "#,
+                env::var("TODO_TUI_TEST_DIR")?
+            )
+            .as_bytes(),
+        );
+        let mut ui = UI::build(&config)?;
+        let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        let (pending, done) = {
+            let todo = ui.todo();
+            (todo.pending.len(), todo.done.len())
+        };
+
+        let mut events = vec![Event::Resize(80, 30), key(KeyCode::Char('I'))];
+        events.extend("buy milk +home".chars().map(|c| key(KeyCode::Char(c))));
+        events.push(key(KeyCode::Enter));
+        for event in events {
+            assert!(!ui.process(event));
+        }
+        assert_eq!(ui.mode, Mode::Normal);
+        assert_eq!(ui.todo().pending.len(), pending + 1);
+        assert!(ui
+            .todo()
+            .pending
+            .iter()
+            .any(|task| task.subject == "buy milk +home"));
+
+        assert!(!ui.process(key(KeyCode::Char('d'))));
+        assert_eq!(ui.todo().pending.len(), pending);
+        assert_eq!(ui.todo().done.len(), done + 1);
+
+        // Nothing is asked before quitting the dry run.
+        assert!(ui.process(key(KeyCode::Char('q'))));
+        Ok(())
+    }
+
+    #[test]
+    fn refresh_rates() -> Result<(), Box<dyn Error>> {
+        let load = |extra: &str| -> Result<UI, Box<dyn Error>> {
+            let config = Config::load_from_buffer(
+                format!(
+                    r#"
+            todo_path = "{}todo.txt"
+            dry_run = true
+            {extra}
+            "#,
+                    env::var("TODO_TUI_TEST_DIR")?
+                )
+                .as_bytes(),
+            );
+            UI::build(&config)
+        };
+        let mut ui = load("")?;
+        assert_eq!(ui.until_refresh(), Option::None);
+        assert!(!ui.check_refresh());
+
+        let mut ui = load("clock_format = \"%H:%M:%S\"")?;
+        assert!(ui.until_refresh() <= Some(Duration::from_secs(1)));
+        ui.refreshed_at -= chrono::Duration::seconds(2);
+        assert!(ui.check_refresh());
+
+        let mut ui = load("[widget_refresh_rates]\nDone = 300")?;
+        assert!(ui.until_refresh() <= Some(Duration::from_secs(300)));
+        assert!(!ui.check_refresh());
+        ui.refreshed_at -= chrono::Duration::minutes(5);
+        assert!(ui.check_refresh());
+        Ok(())
+    }
+
+    #[test]
+    fn filter_bar() -> Result<(), Box<dyn Error>> {
+        let config = Config::load_from_buffer(
+            format!(
+                r#"
+            todo_path = "{}todo.txt"
+            dry_run = true
+            "#,
+                env::var("TODO_TUI_TEST_DIR")?
+            )
+            .as_bytes(),
+        );
+        let mut ui = UI::build(&config)?;
+        let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        ui.process(Event::Resize(80, 30));
+        let height = ui.layout_chunk.height;
+        assert_eq!(ui.filter_bar_chunk.height, 0);
+
+        ui.process(key(KeyCode::Char('b')));
+        assert_eq!(ui.mode, Mode::Filter);
+        assert_eq!(ui.layout_chunk.height, height - 1);
+        for c in "due<".chars() {
+            ui.process(key(KeyCode::Char(c)));
+        }
+        // The unfinished term keeps the last valid query.
+        assert_eq!(ui.todo().filter_bar().unwrap().to_string(), "due");
+        ui.process(key(KeyCode::Char('7')));
+        assert_eq!(ui.todo().filter_bar().unwrap().to_string(), "due<7");
+        ui.process(key(KeyCode::Enter));
+        assert_eq!(ui.mode, Mode::Normal);
+        assert_eq!(ui.filter_bar_chunk.height, 1);
+
+        // Typing continues with the active query and Esc clears it.
+        ui.process(key(KeyCode::Char('b')));
+        ui.process(key(KeyCode::Esc));
+        assert_eq!(ui.todo().filter_bar(), Option::None);
+        assert_eq!(ui.layout_chunk.height, height);
+        Ok(())
+    }
+
+    #[test]
+    fn export_view() -> Result<(), Box<dyn Error>> {
+        let config = Config::load_from_buffer(
+            format!(
+                r#"
+            todo_path = "{}todo.txt"
+            dry_run = true
+            "#,
+                env::var("TODO_TUI_TEST_DIR")?
+            )
+            .as_bytes(),
+        );
+        let mut ui = UI::build(&config)?;
+        let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        let path = env::temp_dir().join(format!("todotxt-tui-export-test-{}.md", process::id()));
+        let _ = fs::remove_file(&path);
+
+        ui.process(Event::Resize(80, 30));
+        ui.process(key(KeyCode::F(4)));
+        assert_eq!(ui.mode, Mode::Export);
+        for c in path.to_string_lossy().chars() {
+            ui.process(key(KeyCode::Char(c)));
+        }
+        ui.process(key(KeyCode::Enter));
+        assert_eq!(ui.mode, Mode::Normal);
+        let exported = fs::read_to_string(&path)?;
+        assert_eq!(exported.lines().count(), ui.todo().pending.len());
+        assert!(exported.lines().all(|line| line.starts_with("- [ ] ")));
+
+        // The prompt starts with the last destination.
+        ui.process(key(KeyCode::F(4)));
+        assert_eq!(ui.tinput.value(), path.to_string_lossy());
+        ui.process(key(KeyCode::Esc));
+        fs::remove_file(&path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn compare_view() -> Result<(), Box<dyn Error>> {
+        let config = Config::load_from_buffer(
+            format!(
+                r#"
+            todo_path = "{}todo.txt"
+            dry_run = true
+            "#,
+                env::var("TODO_TUI_TEST_DIR")?
+            )
+            .as_bytes(),
+        );
+        let mut ui = UI::build(&config)?;
+        let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        let path = env::temp_dir().join(format!(
+            "todotxt-tui-compare-view-test-{}.txt",
+            std::process::id()
+        ));
+        fs::write(&path, "synced task +phone\n")?;
+        let pending = ui.todo().pending.len();
+
+        ui.process(Event::Resize(80, 30));
+        ui.process(key(KeyCode::F(6)));
+        assert_eq!(ui.mode, Mode::Compare);
+        for c in path.to_string_lossy().chars() {
+            ui.process(key(KeyCode::Char(c)));
+        }
+        ui.process(key(KeyCode::Enter));
+        assert_eq!(ui.mode, Mode::Normal);
+        assert!(matches!(ui.popup, Some(Popup::Compare(_))));
+
+        // The task of the other file is copied to the todo list as it is written.
+        ui.process(key(KeyCode::Tab));
+        ui.process(key(KeyCode::Enter));
+        assert_eq!(ui.todo().pending.len(), pending + 1);
+        assert_eq!(
+            ui.todo().pending.last().unwrap().to_string(),
+            "synced task +phone"
+        );
+        ui.process(key(KeyCode::Esc));
+        assert!(ui.popup.is_none());
+        fs::remove_file(&path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn bulk_actions() -> Result<(), Box<dyn Error>> {
+        let config = Config::load_from_buffer(
+            format!(
+                r#"
+            todo_path = "{}todo.txt"
+            dry_run = true
+            "#,
+                env::var("TODO_TUI_TEST_DIR")?
+            )
+            .as_bytes(),
+        );
+        let mut ui = UI::build(&config)?;
+        let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        for task in [
+            "call mom",
+            "buy milk",
+            "fix the bike",
+            "water plants",
+            "pay rent",
+        ] {
+            ui.todo().new_task(task)?;
+        }
+        ui.process(Event::Resize(80, 30));
+        let tagged = |ui: &UI| -> Vec<String> {
+            ui.todo()
+                .pending
+                .iter()
+                .filter(|task| task.projects().contains(&String::from("bulk")))
+                .map(|task| task.subject.clone())
+                .collect()
+        };
+
+        // The first task is marked alone, the third and the fourth as a range.
+        for c in [' ', 'j', 'j', 'r', 'j', 'r'] {
+            ui.process(key(KeyCode::Char(c)));
+        }
+        for c in ":tag +bulk".chars() {
+            ui.process(key(KeyCode::Char(c)));
+        }
+        ui.process(key(KeyCode::Enter));
+        assert_eq!(
+            tagged(&ui),
+            ["call mom +bulk", "fix the bike +bulk", "water plants +bulk"]
+        );
+
+        // The marks stay on the changed tasks until they are completed.
+        ui.process(key(KeyCode::Char('(')));
+        assert!(ui.todo().pending.iter().all(|task| {
+            task.projects().contains(&String::from("bulk")) != task.priority.is_lowest()
+        }));
+        ui.process(key(KeyCode::Char('d')));
+        assert!(tagged(&ui).is_empty());
+        assert_eq!(ui.todo().pending.len(), 2);
+        assert_eq!(ui.todo().done.len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn completion() -> Result<(), Box<dyn Error>> {
+        let config = Config::load_from_buffer(
+            format!(
+                r#"
+            todo_path = "{}todo.txt"
+            dry_run = true
+            "#,
+                env::var("TODO_TUI_TEST_DIR")?
+            )
+            .as_bytes(),
+        );
+        let mut ui = UI::build(&config)?;
+        let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        ui.todo().new_task("dig +garden @home")?;
+        ui.todo().new_task("clean +garage @hobby")?;
+        ui.process(Event::Resize(80, 30));
+
+        ui.process(key(KeyCode::Char('I')));
+        for c in "water +ga".chars() {
+            ui.process(key(KeyCode::Char(c)));
+        }
+        assert!(ui.completion.is_some());
+        ui.process(key(KeyCode::Down));
+        ui.process(key(KeyCode::Tab));
+        assert_eq!(ui.tinput.value(), "water +garden ");
+        assert!(ui.completion.is_none());
+
+        // Esc closes the popup only, the next Esc leaves the input.
+        for c in "@ho".chars() {
+            ui.process(key(KeyCode::Char(c)));
+        }
+        assert!(ui.completion.is_some());
+        ui.process(key(KeyCode::Esc));
+        assert!(ui.completion.is_none());
+        assert_eq!(ui.mode, Mode::Input);
+        ui.process(key(KeyCode::Enter));
+        assert!(ui.completion.is_none());
+        assert_eq!(ui.todo().pending[2].subject, "water +garden @ho");
+        Ok(())
+    }
+
+    #[test]
+    fn action_bar() -> Result<(), Box<dyn Error>> {
+        let config = Config::load_from_buffer(
+            format!(
+                r#"
+            todo_path = "{}todo.txt"
+            dry_run = true
+            action_bar = true
+            "#,
+                env::var("TODO_TUI_TEST_DIR")?
+            )
+            .as_bytes(),
+        );
+        let mut ui = UI::build(&config)?;
+        ui.todo().new_task("call mom")?;
+        ui.todo().new_task("buy milk")?;
+        ui.process(Event::Resize(80, 30));
+        assert_eq!(ui.action_bar_chunk, Rect::new(0, 29, 80, 1));
+        let click = |column| {
+            Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column,
+                row: 29,
+                modifiers: KeyModifiers::NONE,
+            })
+        };
+
+        // `d complete  E edit  w defer  ( priority+  ) priority-  I new`
+        ui.process(click(3));
+        assert_eq!(ui.todo().done.len(), 1);
+        assert_eq!(ui.todo().pending.len(), 1);
+        ui.process(click(54));
+        assert_eq!(ui.mode, Mode::Normal);
+        ui.process(click(56));
+        assert_eq!(ui.mode, Mode::Input);
+        Ok(())
+    }
+
+    #[test]
+    fn status_bar() -> Result<(), Box<dyn Error>> {
+        let config = Config::load_from_buffer(
+            format!(
+                r#"
+            todo_path = "{}todo.txt"
+            dry_run = true
+            action_bar = true
+            status_bar = true
+            "#,
+                env::var("TODO_TUI_TEST_DIR")?
+            )
+            .as_bytes(),
+        );
+        let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        let mut ui = UI::build(&config)?;
+        ui.process(Event::Resize(80, 30));
+        assert_eq!(ui.action_bar_chunk, Rect::new(0, 28, 80, 1));
+        assert_eq!(ui.status_bar_chunk, Rect::new(0, 29, 80, 1));
+
+        ui.process(key(KeyCode::Char('I')));
+        for c in "call mom".chars() {
+            ui.process(key(KeyCode::Char(c)));
+        }
+        ui.process(key(KeyCode::Enter));
+        let parts = ui
+            .status_bar
+            .as_ref()
+            .unwrap()
+            .parts(ui.layout.get_active_widget(), &ui.todo());
+        assert_eq!(parts.last().unwrap(), "1/1 tasks");
+        assert!(ui.todo().is_dirty());
+        Ok(())
+    }
+
+    #[test]
+    fn command_mode() -> Result<(), Box<dyn Error>> {
+        let dir = env::var("TODO_TUI_TEST_DIR")?;
+        let config = Config::load_from_buffer(
+            format!(
+                r#"
+            todo_path = "{dir}todo.txt"
+            dry_run = true
+            "#
+            )
+            .as_bytes(),
+        );
+        let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        let mut ui = UI::build(&config)?;
+        for task in ["call mom +family due:2024-07-03", "fix bike +work"] {
+            ui.todo().new_task(task)?;
+        }
+        ui.process(Event::Resize(80, 30));
+        let command = |ui: &mut UI, text: &str| {
+            ui.process(key(KeyCode::Char(':')));
+            for c in text.chars() {
+                ui.process(key(KeyCode::Char(c)));
+            }
+            ui.process(key(KeyCode::Enter));
+        };
+
+        command(&mut ui, "sort due");
+        assert_eq!(ui.todo().get_sort(ToDoData::Pending), TaskSort::Due);
+        command(&mut ui, "filter +work");
+        assert_eq!(ui.todo().hidden(ToDoData::Pending), 1);
+        command(&mut ui, "filter");
+        assert!(ui.todo().filter_bar().is_none());
+        command(&mut ui, "sort size");
+        assert!(ui.error.take().is_some());
+        assert_eq!(ui.mode, Mode::Normal);
+
+        ui.process(key(KeyCode::Char(':')));
+        ui.process(key(KeyCode::Char('s')));
+        ui.process(key(KeyCode::Char('o')));
+        ui.process(key(KeyCode::Tab));
+        assert_eq!(ui.tinput.value(), "sort ");
+        ui.process(key(KeyCode::Esc));
+
+        command(&mut ui, "dup");
+        ui.process(key(KeyCode::Char('=')));
+        assert_eq!(ui.todo().pending.len(), 4);
+        command(&mut ui, "send work");
+        assert!(ui.error.take().is_some());
+        assert_eq!(ui.todo().pending.len(), 4);
+
+        let other = format!("{dir}other-command-mode.txt");
+        command(&mut ui, &format!("e {other}"));
+        assert_eq!(ui.todo_path, other);
+        assert!(ui.todo().pending.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn input_history() -> Result<(), Box<dyn Error>> {
+        let history_path =
+            env::temp_dir().join(format!("todotxt-tui-ui-history-test-{}.txt", process::id()));
+        let config = Config::load_from_buffer(
+            format!(
+                r#"
+            todo_path = "{}todo.txt"
+            input_history_path = "{}"
+            dry_run = true
+            "#,
+                env::var("TODO_TUI_TEST_DIR")?,
+                history_path.display()
+            )
+            .as_bytes(),
+        );
+        let mut ui = UI::build(&config)?;
+        let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        ui.process(Event::Resize(80, 30));
+        for task in ["call mom", "buy milk"] {
+            ui.process(key(KeyCode::Char('I')));
+            for c in task.chars() {
+                ui.process(key(KeyCode::Char(c)));
+            }
+            ui.process(key(KeyCode::Enter));
+        }
+
+        ui.process(key(KeyCode::Char('I')));
+        ui.process(key(KeyCode::Char('w')));
+        ui.process(key(KeyCode::Up));
+        ui.process(key(KeyCode::Up));
+        assert_eq!(ui.tinput.value(), "call mom");
+        ui.process(key(KeyCode::Down));
+        assert_eq!(ui.tinput.value(), "buy milk");
+        ui.process(key(KeyCode::Down));
+        assert_eq!(ui.tinput.value(), "w");
+
+        // The word before the cursor is deleted.
+        ui.process(key(KeyCode::Up));
+        ui.process(Event::Key(KeyEvent::new(
+            KeyCode::Backspace,
+            KeyModifiers::CONTROL,
+        )));
+        assert_eq!(ui.tinput.value(), "buy ");
+        // The dry run does not write the history.
+        assert!(!history_path.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn planner() -> Result<(), Box<dyn Error>> {
+        let config = Config::load_from_buffer(
+            format!(
+                r#"
+            todo_path = "{}todo.txt"
+            dry_run = true
+            "#,
+                env::var("TODO_TUI_TEST_DIR")?
+            )
+            .as_bytes(),
+        );
+        let mut ui = UI::build(&config)?;
+        let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        let today = ui.clock.today();
+        ui.todo().new_task(&format!("call mom due:{today}"))?;
+        ui.process(Event::Resize(80, 30));
+
+        ui.process(key(KeyCode::F(7)));
+        assert!(matches!(ui.popup, Some(Popup::Planner(_))));
+        ui.process(key(KeyCode::Char('L')));
+        ui.process(key(KeyCode::Char('L')));
+        ui.process(key(KeyCode::Char('H')));
+        assert_eq!(ui.todo().pending[0].due_date, today.succ_opt());
+        ui.process(key(KeyCode::Esc));
+        assert!(ui.popup.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn confirm_delete() -> Result<(), Box<dyn Error>> {
+        let load = |extra: &str| -> Result<UI, Box<dyn Error>> {
+            let config = Config::load_from_buffer(
+                format!(
+                    r#"
+                todo_path = "{}todo.txt"
+                dry_run = true
+                {extra}
+                "#,
+                    env::var("TODO_TUI_TEST_DIR")?
+                )
+                .as_bytes(),
+            );
+            let ui = UI::build(&config)?;
+            for task in ["call mom", "buy milk", "pay rent"] {
+                ui.todo().new_task(task)?;
+            }
+            Ok(ui)
+        };
+        let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+
+        let mut ui = load("")?;
+        ui.process(Event::Resize(80, 30));
+        ui.process(key(KeyCode::Char('x')));
+        assert!(ui.confirm_prompt.is_some());
+        ui.process(key(KeyCode::Char('n')));
+        assert!(ui.confirm_prompt.is_none());
+        assert_eq!(ui.todo().pending.len(), 3);
+        ui.process(key(KeyCode::Char('x')));
+        ui.process(key(KeyCode::Char('y')));
+        assert_eq!(ui.todo().pending.len(), 2);
+
+        // Marked tasks are deleted together after one confirmation.
+        for c in [' ', 'j', ' ', 'x'] {
+            ui.process(key(KeyCode::Char(c)));
+        }
+        ui.process(key(KeyCode::Enter));
+        assert!(ui.todo().pending.is_empty());
+
+        let mut ui = load("confirm_delete = false")?;
+        ui.process(Event::Resize(80, 30));
+        ui.process(key(KeyCode::Char('x')));
+        assert!(ui.confirm_prompt.is_none());
+        assert_eq!(ui.todo().pending.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn review_delete() -> Result<(), Box<dyn Error>> {
+        let load = |extra: &str| -> Result<UI, Box<dyn Error>> {
+            let config = Config::load_from_buffer(
+                format!(
+                    r#"
+                todo_path = "{}todo.txt"
+                dry_run = true
+                {extra}
+                "#,
+                    env::var("TODO_TUI_TEST_DIR")?
+                )
+                .as_bytes(),
+            );
+            let ui = UI::build(&config)?;
+            for task in ["2020-01-01 call mom", "2020-01-01 buy milk"] {
+                ui.todo().new_task(task)?;
+            }
+            Ok(ui)
+        };
+        let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+
+        let mut ui = load("")?;
+        ui.process(Event::Resize(80, 30));
+        ui.process(key(KeyCode::F(9)));
+        assert_eq!(ui.mode, Mode::Review);
+        ui.process(key(KeyCode::Char('d')));
+        assert!(ui.confirm_prompt.is_some());
+        ui.process(key(KeyCode::Char('n')));
+        assert_eq!(ui.todo().pending.len(), 2);
+        assert_eq!(ui.mode, Mode::Review);
+        ui.process(key(KeyCode::Char('d')));
+        ui.process(key(KeyCode::Char('y')));
+        assert_eq!(ui.todo().pending.len(), 1);
+        assert_eq!(ui.mode, Mode::Review);
+
+        // The keys of the review are remapped in review_keybind.
+        let mut ui = load(
+            r#"
+                confirm_delete = false
+                [[review_keybind.events]]
+                event = "RemoveItem"
+                key.Char = "x"
+            "#,
+        )?;
+        ui.process(Event::Resize(80, 30));
+        ui.process(key(KeyCode::F(9)));
+        ui.process(key(KeyCode::Char('d')));
+        assert_eq!(ui.todo().pending.len(), 2);
+        ui.process(key(KeyCode::Char('x')));
+        assert!(ui.confirm_prompt.is_none());
+        assert_eq!(ui.todo().pending.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn confirm_task_rules() -> Result<(), Box<dyn Error>> {
+        let config = Config::load_from_buffer(
+            format!(
+                r#"
+            todo_path = "{}todo.txt"
+            dry_run = true
+            confirm_task_rules = true
+
+            [task_rules]
+            "@waiting" = "priority<=C"
+            "#,
+                env::var("TODO_TUI_TEST_DIR")?
+            )
+            .as_bytes(),
+        );
+        let mut ui = UI::build(&config)?;
+        ui.todo().new_task("(A) call the plumber @waiting")?;
+        let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        let priorities = |ui: &UI| -> Vec<String> {
+            ui.todo()
+                .pending
+                .iter()
+                .map(|task| task.priority.to_string())
+                .collect()
+        };
+        ui.process(Event::Resize(80, 30));
+
+        assert!(ui.check_escalation());
+        assert!(ui.confirm_prompt.is_some());
+        ui.process(key(KeyCode::Char('n')));
+        assert!(ui.confirm_prompt.is_none());
+        assert_eq!(priorities(&ui), ["A"]);
+        // Declined changes are not previewed again until the tasks change.
+        assert!(!ui.check_escalation());
+
+        ui.todo().new_task("(B) ask the landlord @waiting")?;
+        assert!(ui.check_escalation());
+        ui.process(key(KeyCode::Char('y')));
+        assert_eq!(priorities(&ui), ["C", "C"]);
+        assert!(ui.toast.is_some());
+        assert!(!ui.check_escalation());
+        Ok(())
+    }
+
+    #[test]
+    fn trash() -> Result<(), Box<dyn Error>> {
+        let config = Config::load_from_buffer(
+            format!(
+                r#"
+            todo_path = "{}todo.txt"
+            dry_run = true
+            confirm_delete = false
+            "#,
+                env::var("TODO_TUI_TEST_DIR")?
+            )
+            .as_bytes(),
+        );
+        let mut ui = UI::build(&config)?;
+        let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        for task in ["call mom", "buy milk"] {
+            ui.todo().new_task(task)?;
+        }
+        ui.process(Event::Resize(80, 30));
+        ui.process(key(KeyCode::Char('x')));
+        assert_eq!(ui.todo().pending.len(), 1);
+
+        ui.process(key(KeyCode::F(8)));
+        assert!(matches!(ui.popup, Some(Popup::Trash(_))));
+        ui.process(key(KeyCode::Enter));
+        assert_eq!(ui.todo().pending.len(), 2);
+        assert!(ui.todo().trash().is_empty());
+        ui.process(key(KeyCode::Esc));
+        assert!(ui.popup.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn input_overlay() -> Result<(), Box<dyn Error>> {
+        let mut ui = default_ui()?;
+        ui.update_chunk(Rect::new(0, 0, 50, 20));
+        assert_eq!(ui.input_chunk, Rect::new(0, 0, 50, 3));
+        assert_eq!(ui.layout_chunk, Rect::new(0, 4, 50, 16));
+
+        ui.input_overlay = true;
+        ui.update_chunk(Rect::new(0, 0, 50, 20));
+        assert_eq!(ui.input_chunk, Rect::new(5, 8, 40, 3));
+        assert_eq!(ui.layout_chunk, Rect::new(0, 1, 50, 19));
+        Ok(())
+    }
 }
@@ -0,0 +1,213 @@
+use crate::{
+    config::{key_name, Config, KeybindScope},
+    layout::widget::widget_type::WidgetType,
+    ui::UIEvent,
+};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+/// Space drawn between actions.
+const SEPARATOR: &str = "  ";
+
+/// An action shown in the action bar with the key triggering it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BarAction {
+    pub key: String,
+    pub event: UIEvent,
+    /// The table of the keybinding, events of `window_keybind` are handled by the window,
+    /// the others by the focused widget.
+    pub scope: KeybindScope,
+}
+
+impl BarAction {
+    /// Gets the name of the action shown after its key, e.g. `complete` for `MoveItem`.
+    pub fn label(&self) -> String {
+        use UIEvent::*;
+        match self.event {
+            MoveItem => String::from("complete"),
+            RemoveItem => String::from("delete"),
+            InsertMode => String::from("new"),
+            EditMode => String::from("edit"),
+            DeferItem => String::from("defer"),
+            PriorityUp => String::from("priority+"),
+            PriorityDown => String::from("priority-"),
+            DueMode => String::from("due"),
+            Select => String::from("select"),
+            Remove => String::from("remove"),
+            ToggleFilterMode => String::from("filter mode"),
+            event => {
+                // Splits the name of the event into words, e.g. `ToggleTimer` to `toggle timer`.
+                let mut label = String::new();
+                for c in format!("{event:?}").chars() {
+                    if c.is_uppercase() && !label.is_empty() {
+                        label.push(' ');
+                    }
+                    label.extend(c.to_lowercase());
+                }
+                label
+            }
+        }
+    }
+
+    /// Gets the number of columns the action takes in the bar.
+    fn width(&self) -> usize {
+        self.key.chars().count() + 1 + self.label().chars().count()
+    }
+}
+
+/// Represents the line at the bottom of the window with the most common actions
+/// of the focused widget and their keys. Clicking an action triggers it.
+pub struct ActionBar {
+    tasks: Vec<BarAction>,
+    categories: Vec<BarAction>,
+}
+
+impl ActionBar {
+    /// Creates a new `ActionBar`, actions without a key are left out.
+    ///
+    /// # Parameters
+    ///
+    /// - `config`: The configuration with the actions and the keybindings.
+    pub fn new(config: &Config) -> Self {
+        let actions = |events: Vec<UIEvent>, scope: KeybindScope| {
+            let bindings = [
+                (KeybindScope::Window, config.get_window_keybind().bindings()),
+                (scope, config.get_keybind(scope).bindings()),
+            ];
+            events
+                .into_iter()
+                .filter_map(|event| {
+                    bindings.iter().find_map(|(scope, bindings)| {
+                        let (key, _) = bindings.iter().find(|(_, e)| *e == event)?;
+                        Some(BarAction {
+                            key: key_name(*key),
+                            event,
+                            scope: *scope,
+                        })
+                    })
+                })
+                .collect()
+        };
+        Self {
+            tasks: actions(config.get_tasks_actions(), KeybindScope::Tasks),
+            categories: actions(config.get_category_actions(), KeybindScope::Category),
+        }
+    }
+
+    /// Gets the actions of the widget, the preview has none.
+    ///
+    /// # Parameters
+    ///
+    /// - `widget`: The type of the focused widget.
+    pub fn actions(&self, widget: WidgetType) -> &[BarAction] {
+        use WidgetType::*;
+        match widget {
+            List | Done => &self.tasks,
+            Project | Context | Hashtag | Assignee | Tag => &self.categories,
+            Preview => &[],
+        }
+    }
+
+    /// Finds the action drawn at the column of the bar.
+    ///
+    /// # Parameters
+    ///
+    /// - `widget`: The type of the focused widget.
+    /// - `column`: The column counted from the start of the line.
+    pub fn action_at(&self, widget: WidgetType, column: u16) -> Option<&BarAction> {
+        let mut start = 0;
+        for action in self.actions(widget) {
+            let end = start + action.width();
+            if (start..end).contains(&(column as usize)) {
+                return Some(action);
+            }
+            start = end + SEPARATOR.len();
+        }
+        None
+    }
+
+    /// Renders the action bar.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `area`: The area of the line.
+    /// - `widget`: The type of the focused widget.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect, widget: WidgetType) {
+        let mut spans = Vec::new();
+        for (index, action) in self.actions(widget).iter().enumerate() {
+            if index > 0 {
+                spans.push(Span::raw(SEPARATOR));
+            }
+            spans.push(Span::styled(
+                action.key.clone(),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ));
+            spans.push(Span::styled(
+                format!(" {}", action.label()),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        f.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn actions() {
+        let config = Config::default();
+        let bar = ActionBar::new(&config);
+        let keys: Vec<(String, String)> = bar
+            .actions(WidgetType::List)
+            .iter()
+            .map(|action| (action.key.clone(), action.label()))
+            .collect();
+        assert_eq!(
+            keys[..3],
+            [
+                (String::from("d"), String::from("complete")),
+                (String::from("E"), String::from("edit")),
+                (String::from("w"), String::from("defer")),
+            ]
+        );
+        assert_eq!(bar.actions(WidgetType::List)[1].scope, KeybindScope::Window);
+        assert_eq!(bar.actions(WidgetType::List)[0].scope, KeybindScope::Tasks);
+        assert!(bar.actions(WidgetType::Preview).is_empty());
+
+        // `d complete  E edit`
+        assert_eq!(
+            bar.action_at(WidgetType::List, 0).unwrap().event,
+            UIEvent::MoveItem
+        );
+        assert_eq!(bar.action_at(WidgetType::List, 10), None);
+        assert_eq!(
+            bar.action_at(WidgetType::List, 12).unwrap().event,
+            UIEvent::EditMode
+        );
+        assert_eq!(
+            bar.action_at(WidgetType::Project, 0).unwrap().event,
+            UIEvent::Select
+        );
+    }
+
+    #[test]
+    fn label() {
+        let action = BarAction {
+            key: String::from("z"),
+            event: UIEvent::ToggleTimer,
+            scope: KeybindScope::Tasks,
+        };
+        assert_eq!(action.label(), "toggle timer");
+    }
+}
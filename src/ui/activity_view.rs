@@ -0,0 +1,130 @@
+use super::PopupKey;
+use crossterm::event::{KeyCode, KeyEvent};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Represents the activity log shown in a popup, the lines are filtered by the typed text.
+pub struct ActivityView {
+    lines: Vec<String>,
+    filter: String,
+    scroll: usize,
+}
+
+impl ActivityView {
+    /// Creates a new `ActivityView`.
+    ///
+    /// # Parameters
+    ///
+    /// - `lines`: Lines of the activity log matching the filter, the newest first.
+    /// - `filter`: The text the lines are filtered by.
+    pub fn new(lines: Vec<String>, filter: impl Into<String>) -> Self {
+        Self {
+            lines,
+            filter: filter.into(),
+            scroll: 0,
+        }
+    }
+
+    /// Gets the text the lines are filtered by.
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    /// Scrolls the log down by the number of lines.
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.scroll = (self.scroll + lines).min(self.lines.len().saturating_sub(1));
+    }
+
+    /// Scrolls the log up by the number of lines.
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    /// Handles a key pressed while the popup is open, typed characters change the filter.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> PopupKey {
+        match key.code {
+            KeyCode::Esc => return PopupKey::Close,
+            KeyCode::Down => self.scroll_down(1),
+            KeyCode::Up => self.scroll_up(1),
+            KeyCode::PageDown => self.scroll_down(10),
+            KeyCode::PageUp => self.scroll_up(10),
+            KeyCode::Backspace => {
+                let mut filter = self.filter().to_string();
+                filter.pop();
+                return PopupKey::FilterActivity(filter);
+            }
+            KeyCode::Char(c) => return PopupKey::FilterActivity(format!("{}{c}", self.filter())),
+            _ => {}
+        }
+        PopupKey::Keep
+    }
+
+    /// Renders the log in a popup in the middle of the given area.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `area`: The area over which the popup is centered.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let width = (area.width * 4 / 5).max(20).min(area.width);
+        let height = (area.height * 4 / 5).max(5).min(area.height);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        let mut text = vec![
+            Line::from(vec![
+                Span::styled("Filter: ", Style::default().fg(Color::DarkGray)),
+                Span::raw(self.filter.as_str()),
+            ]),
+            Line::from(""),
+        ];
+        if self.lines.is_empty() {
+            text.push(Line::from(Span::styled(
+                "No activity",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        text.extend(self.lines.iter().skip(self.scroll).map(|line| {
+            // Lines start with the date and the time followed by the kind of the change.
+            let mut parts = line.splitn(4, ' ');
+            let time = [parts.next(), parts.next()]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(" ");
+            let kind = parts.next().unwrap_or_default();
+            let color = match kind {
+                "add" | "restore" => Color::Green,
+                "complete" => Color::Cyan,
+                "delete" => Color::Red,
+                _ => Color::Yellow,
+            };
+            Line::from(vec![
+                Span::styled(time, Style::default().fg(Color::DarkGray)),
+                Span::raw(" "),
+                Span::styled(kind, Style::default().fg(color)),
+                Span::raw(" "),
+                Span::raw(parts.next().unwrap_or_default()),
+            ])
+        }));
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(format!(
+                "Activity log: {} entries (type to filter, Up/Down: scroll, Esc: close)",
+                self.lines.len()
+            ));
+        f.render_widget(Clear, popup);
+        f.render_widget(Paragraph::new(text).block(block), popup);
+    }
+}
@@ -0,0 +1,184 @@
+use super::PopupKey;
+use crate::todo::archive::search_archive;
+use crossterm::event::{KeyCode, KeyEvent};
+use std::{
+    io,
+    sync::mpsc::{self, Receiver},
+    thread::{self, JoinHandle},
+};
+use todo_txt::Task;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Represents the popup with archived tasks matching the search. The done file is
+/// searched in the background and the matches are shown while they are found.
+pub struct ArchiveResults {
+    search: String,
+    tasks: Vec<Task>,
+    rx: Receiver<Task>,
+    worker: Option<JoinHandle<io::Result<()>>>,
+    error: Option<String>,
+    scroll: usize,
+}
+
+impl ArchiveResults {
+    /// Starts searching the done file.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The path to the done file.
+    /// - `search`: The searched pattern, see [`crate::todo::search::fuzzy_match`].
+    pub fn new(path: String, search: String) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let pattern = search.clone();
+        let worker = thread::spawn(move || search_archive(path, &pattern, tx));
+        Self {
+            search,
+            tasks: Vec::new(),
+            rx,
+            worker: Some(worker),
+            error: None,
+            scroll: 0,
+        }
+    }
+
+    /// Takes the tasks found since the last call, the latest completions are shown first.
+    ///
+    /// # Returns
+    ///
+    /// `true` if tasks were found or the search has ended and the popup needs to be redrawn.
+    pub fn receive(&mut self) -> bool {
+        // The worker has sent all tasks before it finished, so none of them is left behind.
+        let finished = self.worker.as_ref().is_some_and(JoinHandle::is_finished);
+        let count = self.tasks.len();
+        self.tasks.extend(self.rx.try_iter());
+        if finished {
+            self.error = match self.worker.take().map(JoinHandle::join) {
+                Some(Ok(Err(e))) => Some(format!("Cannot read the archive: {e}")),
+                Some(Err(_)) => Some(String::from("Searching the archive has failed")),
+                _ => None,
+            };
+        }
+        if count == self.tasks.len() && !finished {
+            return false;
+        }
+        self.tasks
+            .sort_by_key(|task| std::cmp::Reverse(task.finish_date));
+        true
+    }
+
+    /// Checks whether the done file is still being searched.
+    pub fn is_searching(&self) -> bool {
+        self.worker.is_some()
+    }
+
+    /// Scrolls the results down by the number of lines.
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.scroll = (self.scroll + lines).min(self.tasks.len().saturating_sub(1));
+    }
+
+    /// Scrolls the results up by the number of lines.
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    /// Handles a key pressed while the popup is open, the keys scroll the results.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> PopupKey {
+        match key.code {
+            KeyCode::Esc => return PopupKey::Close,
+            KeyCode::Down | KeyCode::Char('j') => self.scroll_down(1),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll_up(1),
+            KeyCode::PageDown => self.scroll_down(10),
+            KeyCode::PageUp => self.scroll_up(10),
+            _ => {}
+        }
+        PopupKey::Keep
+    }
+
+    /// Renders the results in a popup in the middle of the given area.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `area`: The area over which the popup is centered.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let width = (area.width * 4 / 5).max(20).min(area.width);
+        let height = (area.height * 4 / 5).max(5).min(area.height);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        let gray = Style::default().fg(Color::DarkGray);
+        let mut text = vec![
+            Line::from(vec![
+                Span::styled("Search: ", gray),
+                Span::raw(self.search.as_str()),
+            ]),
+            Line::from(""),
+        ];
+        if let Some(error) = &self.error {
+            text.push(Line::from(Span::styled(
+                error.as_str(),
+                Style::default().fg(Color::Red),
+            )));
+        } else if self.tasks.is_empty() && !self.is_searching() {
+            text.push(Line::from(Span::styled("No archived task matches", gray)));
+        }
+        text.extend(self.tasks.iter().skip(self.scroll).map(|task| {
+            let finished = task
+                .finish_date
+                .map_or_else(|| String::from("no date   "), |date| date.to_string());
+            Line::from(vec![
+                Span::styled(format!("{finished}  "), Style::default().fg(Color::Green)),
+                Span::raw(task.subject.as_str()),
+            ])
+        }));
+        let title = match self.is_searching() {
+            true => format!("Archive ({} found, searching...)", self.tasks.len()),
+            false => format!(
+                "Archive ({} found, j/k: scroll, Esc: close)",
+                self.tasks.len()
+            ),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(title);
+        f.render_widget(Clear, popup);
+        f.render_widget(Paragraph::new(text).block(block), popup);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env::temp_dir, fs, process, time::Duration};
+
+    #[test]
+    fn streams_matches() -> io::Result<()> {
+        let path = temp_dir().join(format!("todotxt-tui-archive-results-{}.txt", process::id()));
+        fs::write(
+            &path,
+            "x 2023-05-01 write the report\nx 2023-05-03 call mom\nx 2023-05-02 rewrite the report\n",
+        )?;
+        let mut results = ArchiveResults::new(path.to_string_lossy().to_string(), "report".into());
+        while results.is_searching() {
+            results.receive();
+            thread::sleep(Duration::from_millis(10));
+        }
+        let found: Vec<&str> = results.tasks.iter().map(|t| t.subject.as_str()).collect();
+        assert_eq!(found, ["rewrite the report", "write the report"]);
+        assert_eq!(results.error, None);
+        fs::remove_file(path)?;
+        Ok(())
+    }
+}
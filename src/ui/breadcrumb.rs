@@ -0,0 +1,316 @@
+use crate::{
+    config::Config,
+    todo::{Action, FilterState, TaskSort, ToDo, ToDoCategory, ToDoData},
+};
+use std::path::Path;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+/// Separator drawn between crumbs.
+const SEPARATOR: &str = " ▸ ";
+
+/// A part of the state of the view shown in the breadcrumb.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Crumb {
+    /// The configuration the application was started with.
+    Profile(String),
+    /// The todo file.
+    File(String),
+    /// An active filter of a category.
+    Filter {
+        category: ToDoCategory,
+        name: String,
+        state: FilterState,
+    },
+    /// The sorting of the tasks.
+    Sort(ToDoData, TaskSort),
+}
+
+impl Crumb {
+    /// Gets the text of the crumb, e.g. `+work` or `-@home`.
+    pub fn label(&self) -> String {
+        match self {
+            Crumb::Profile(name) | Crumb::File(name) => name.clone(),
+            Crumb::Filter {
+                category,
+                name,
+                state,
+            } => {
+                let negation = match state {
+                    FilterState::Select => "",
+                    FilterState::Remove => "-",
+                };
+                format!("{negation}{}{name}", category.prefix())
+            }
+            Crumb::Sort(data, sort) => {
+                let sort = sort.name();
+                match data {
+                    ToDoData::Pending => format!("sort: {sort}"),
+                    ToDoData::Done => format!("done sort: {sort}"),
+                }
+            }
+        }
+    }
+
+    fn style(&self) -> Style {
+        let color = match self {
+            Crumb::Profile(_) | Crumb::File(_) => Color::DarkGray,
+            Crumb::Filter {
+                state: FilterState::Select,
+                ..
+            } => Color::Green,
+            Crumb::Filter {
+                state: FilterState::Remove,
+                ..
+            } => Color::Red,
+            Crumb::Sort(..) => Color::Cyan,
+        };
+        Style::default().fg(color)
+    }
+}
+
+/// Represents the breadcrumb line showing the state of the view:
+/// profile ▸ file ▸ filters ▸ sort.
+///
+/// Selecting a crumb clears everything after it, filters are removed and
+/// the sorting is set back to the configured one.
+pub struct Breadcrumb {
+    profile: String,
+    file: String,
+    pending_sort: TaskSort,
+    done_sort: TaskSort,
+}
+
+impl Breadcrumb {
+    /// Creates a new `Breadcrumb`.
+    ///
+    /// # Parameters
+    ///
+    /// - `config`: The configuration with the profile, the todo file and the default sorting.
+    pub fn new(config: &Config) -> Self {
+        let file_name = |path: &Path| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default()
+        };
+        let mut profile = config
+            .get_config_path()
+            .file_stem()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if config.get_power_mode() {
+            profile += " (power mode)";
+        }
+        Self {
+            profile,
+            file: file_name(Path::new(&config.get_todo_path())),
+            pending_sort: config.get_pending_sort(),
+            done_sort: config.get_done_sort(),
+        }
+    }
+
+    /// Gets the crumbs of the current state of the view. The sorting of done
+    /// tasks is shown only if it differs from the configured one.
+    ///
+    /// # Parameters
+    ///
+    /// - `todo`: The todo list with the filters and the sorting.
+    pub fn crumbs(&self, todo: &ToDo) -> Vec<Crumb> {
+        let mut crumbs = vec![
+            Crumb::Profile(self.profile.clone()),
+            Crumb::File(self.file.clone()),
+        ];
+        let state = todo.get_state();
+        for category in state.categories() {
+            crumbs.extend(
+                state
+                    .get_category(category)
+                    .iter()
+                    .map(|(name, state)| Crumb::Filter {
+                        category,
+                        name: name.clone(),
+                        state: *state,
+                    }),
+            );
+        }
+        crumbs.push(Crumb::Sort(
+            ToDoData::Pending,
+            todo.get_sort(ToDoData::Pending),
+        ));
+        let done_sort = todo.get_sort(ToDoData::Done);
+        if done_sort != self.done_sort {
+            crumbs.push(Crumb::Sort(ToDoData::Done, done_sort));
+        }
+        crumbs
+    }
+
+    /// Gets the action clearing the crumb or `None` if the crumb does not change the view.
+    fn clear(&self, crumb: &Crumb) -> Option<Action> {
+        match crumb {
+            Crumb::Profile(_) | Crumb::File(_) => None,
+            Crumb::Filter {
+                category,
+                name,
+                state,
+            } => Some(Action::ToggleFilter {
+                category: *category,
+                name: name.clone(),
+                state: *state,
+            }),
+            Crumb::Sort(data, sort) => {
+                let default = match data {
+                    ToDoData::Pending => self.pending_sort,
+                    ToDoData::Done => self.done_sort,
+                };
+                (*sort != default).then_some(Action::SetSort {
+                    data: *data,
+                    sort: default,
+                })
+            }
+        }
+    }
+
+    /// Selects the crumb, crumbs after it are cleared.
+    ///
+    /// # Parameters
+    ///
+    /// - `todo`: The todo list with the filters and the sorting.
+    /// - `index`: The index of the selected crumb.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the view has changed.
+    pub fn select(&self, todo: &mut ToDo, index: usize) -> bool {
+        let actions: Vec<Action> = self
+            .crumbs(todo)
+            .iter()
+            .skip(index + 1)
+            .filter_map(|crumb| self.clear(crumb))
+            .collect();
+        let changed = !actions.is_empty();
+        for action in actions {
+            if let Err(e) = todo.apply(action) {
+                log::error!("Cannot clear the crumb: {}", e);
+            }
+        }
+        changed
+    }
+
+    /// Clears the last crumb that changes the view.
+    ///
+    /// # Parameters
+    ///
+    /// - `todo`: The todo list with the filters and the sorting.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the view has changed.
+    pub fn back(&self, todo: &mut ToDo) -> bool {
+        let crumbs = self.crumbs(todo);
+        match crumbs.iter().rposition(|crumb| self.clear(crumb).is_some()) {
+            Some(index) => self.select(todo, index - 1),
+            None => false,
+        }
+    }
+
+    /// Finds the crumb drawn at the column of the breadcrumb line.
+    ///
+    /// # Parameters
+    ///
+    /// - `todo`: The todo list with the filters and the sorting.
+    /// - `column`: The column counted from the start of the line.
+    pub fn crumb_at(&self, todo: &ToDo, column: u16) -> Option<usize> {
+        let mut start = 0;
+        for (index, crumb) in self.crumbs(todo).iter().enumerate() {
+            let end = start + crumb.label().chars().count();
+            if (start..end).contains(&(column as usize)) {
+                return Some(index);
+            }
+            start = end + SEPARATOR.chars().count();
+        }
+        None
+    }
+
+    /// Renders the breadcrumb line.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `area`: The area of the line.
+    /// - `todo`: The todo list with the filters and the sorting.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect, todo: &ToDo) {
+        let mut spans = Vec::new();
+        for (index, crumb) in self.crumbs(todo).iter().enumerate() {
+            if index > 0 {
+                spans.push(Span::styled(
+                    SEPARATOR,
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            spans.push(Span::styled(crumb.label(), crumb.style()));
+        }
+        f.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select() -> Result<(), todo_txt::Error> {
+        let breadcrumb = Breadcrumb {
+            profile: String::from("config"),
+            file: String::from("todo.txt"),
+            pending_sort: TaskSort::None,
+            done_sort: TaskSort::CompletedReverse,
+        };
+        let mut todo = ToDo::default();
+        todo.apply(Action::ToggleFilter {
+            category: ToDoCategory::Projects,
+            name: String::from("work"),
+            state: FilterState::Select,
+        })?;
+        todo.apply(Action::ToggleFilter {
+            category: ToDoCategory::Contexts,
+            name: String::from("home"),
+            state: FilterState::Remove,
+        })?;
+        todo.apply(Action::SetSort {
+            data: ToDoData::Pending,
+            sort: TaskSort::Due,
+        })?;
+        let labels = |todo: &ToDo| -> Vec<String> {
+            breadcrumb.crumbs(todo).iter().map(Crumb::label).collect()
+        };
+        assert_eq!(
+            labels(&todo),
+            vec!["config", "todo.txt", "+work", "-@home", "sort: due"]
+        );
+        assert_eq!(breadcrumb.crumb_at(&todo, 0), Some(0));
+        assert_eq!(breadcrumb.crumb_at(&todo, 6), None);
+        assert_eq!(breadcrumb.crumb_at(&todo, 20), Some(2));
+
+        assert!(breadcrumb.back(&mut todo));
+        assert_eq!(
+            labels(&todo),
+            vec!["config", "todo.txt", "+work", "-@home", "sort: none"]
+        );
+        assert!(breadcrumb.select(&mut todo, 2));
+        assert_eq!(
+            labels(&todo),
+            vec!["config", "todo.txt", "+work", "sort: none"]
+        );
+        assert!(breadcrumb.back(&mut todo));
+        assert!(!breadcrumb.back(&mut todo));
+        assert!(!breadcrumb.select(&mut todo, 0));
+        assert_eq!(labels(&todo), vec!["config", "todo.txt", "sort: none"]);
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+use crate::todo::NewCategory;
+use crossterm::event::{KeyCode, KeyEvent};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// What the input does after the category prompt handled a key.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CategoryKey {
+    /// The prompt stays open.
+    Keep,
+    /// The prompt is closed, the input is not saved.
+    Close,
+    /// The category is confirmed, the prompt continues with the next new category.
+    Next,
+    /// The category is replaced by the suggestion in the input,
+    /// then the prompt continues with the next new category.
+    Replace { name: String, suggestion: String },
+}
+
+/// Represents the prompt asking whether categories not used by any task yet should be
+/// created, so a typo like `+wrok` does not create a new project.
+pub struct CategoryPrompt {
+    categories: Vec<NewCategory>,
+    current: usize,
+}
+
+impl CategoryPrompt {
+    /// Creates a new `CategoryPrompt`.
+    ///
+    /// # Parameters
+    ///
+    /// - `categories`: The new categories of the task, the user confirms them one by one.
+    pub fn new(categories: Vec<NewCategory>) -> Self {
+        Self {
+            categories,
+            current: 0,
+        }
+    }
+
+    /// Gets the new category the user is asked about.
+    pub fn current(&self) -> Option<&NewCategory> {
+        self.categories.get(self.current)
+    }
+
+    /// Moves to the next new category.
+    ///
+    /// # Returns
+    ///
+    /// `true` if there is a category left to confirm.
+    pub fn advance(&mut self) -> bool {
+        self.current += 1;
+        self.current < self.categories.len()
+    }
+
+    /// Handles a key pressed while the prompt is open, a number picks the suggestion instead of the category.
+    pub fn handle_key(&self, key: &KeyEvent) -> CategoryKey {
+        match key.code {
+            KeyCode::Enter | KeyCode::Char('y') => CategoryKey::Next,
+            KeyCode::Char(c @ '1'..='9') => {
+                let index = c as usize - '1' as usize;
+                self.current()
+                    .and_then(|category| {
+                        Some(CategoryKey::Replace {
+                            name: category.name.clone(),
+                            suggestion: category.suggestions.get(index)?.clone(),
+                        })
+                    })
+                    .unwrap_or(CategoryKey::Keep)
+            }
+            KeyCode::Esc | KeyCode::Char('n') => CategoryKey::Close,
+            _ => CategoryKey::Keep,
+        }
+    }
+
+    /// Renders the prompt in a popup in the middle of the given area.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `area`: The area over which the popup is centered.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let category = match self.current() {
+            Some(category) => category,
+            None => return,
+        };
+        let kind = match category.name.starts_with('@') {
+            true => "context",
+            false => "project",
+        };
+        let mut text = vec![Line::from(format!("Create new {kind} {}?", category.name))];
+        if !category.suggestions.is_empty() {
+            text.push(Line::from(""));
+            text.push(Line::from("Did you mean:"));
+        }
+        for (i, suggestion) in category.suggestions.iter().enumerate() {
+            text.push(Line::from(format!("  {}: {suggestion}", i + 1)));
+        }
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(
+            match category.suggestions.len() {
+                0 => String::from("Enter/y: create, Esc/n: back to the input"),
+                len => {
+                    format!("Enter/y: create, 1-{len}: use suggestion, Esc/n: back to the input")
+                }
+            },
+            Style::default().fg(Color::DarkGray),
+        )));
+        let width = (area.width * 3 / 5).max(20).min(area.width);
+        let height = (text.len() as u16 + 2).min(area.height);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(format!(
+                "New category {}/{}",
+                self.current + 1,
+                self.categories.len()
+            ));
+        f.render_widget(Clear, popup);
+        f.render_widget(Paragraph::new(text).block(block), popup);
+    }
+}
+
+/// Replaces every whitespace separated word of the text equal to the word with the replacement.
+///
+/// # Parameters
+///
+/// - `text`: The text with the word.
+/// - `word`: The word to replace, e.g. `+wrok`.
+/// - `replacement`: The word used instead, e.g. `+work`.
+pub fn replace_word(text: &str, word: &str, replacement: &str) -> String {
+    text.split(' ')
+        .map(|part| if part == word { replacement } else { part })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prompt() {
+        let category = |name: &str| NewCategory {
+            name: String::from(name),
+            suggestions: Vec::new(),
+        };
+        let mut prompt = CategoryPrompt::new(vec![category("+wrok"), category("@hmoe")]);
+        assert_eq!(prompt.current().unwrap().name, "+wrok");
+        assert!(prompt.advance());
+        assert_eq!(prompt.current().unwrap().name, "@hmoe");
+        assert!(!prompt.advance());
+        assert!(prompt.current().is_none());
+
+        assert_eq!(
+            replace_word("fix +wrok bugs +wroks +wrok", "+wrok", "+work"),
+            "fix +work bugs +wroks +work"
+        );
+    }
+}
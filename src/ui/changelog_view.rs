@@ -0,0 +1,104 @@
+use super::PopupKey;
+use crate::update_check::Release;
+use crossterm::event::{KeyCode, KeyEvent};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Represents the popup with the changelog of a newer release.
+pub struct ChangelogView {
+    version: String,
+    lines: Vec<String>,
+    scroll: usize,
+}
+
+impl ChangelogView {
+    /// Creates a new `ChangelogView`.
+    ///
+    /// # Parameters
+    ///
+    /// - `release`: The release whose notes are shown.
+    pub fn new(release: &Release) -> Self {
+        Self {
+            version: release.version.clone(),
+            lines: release.notes.lines().map(String::from).collect(),
+            scroll: 0,
+        }
+    }
+
+    /// Scrolls the changelog down by the number of lines.
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.scroll = (self.scroll + lines).min(self.lines.len().saturating_sub(1));
+    }
+
+    /// Scrolls the changelog up by the number of lines.
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    /// Handles a key pressed while the popup is open, the keys scroll the changelog.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> PopupKey {
+        match key.code {
+            KeyCode::Esc => return PopupKey::Close,
+            KeyCode::Down | KeyCode::Char('j') => self.scroll_down(1),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll_up(1),
+            KeyCode::PageDown => self.scroll_down(10),
+            KeyCode::PageUp => self.scroll_up(10),
+            _ => {}
+        }
+        PopupKey::Keep
+    }
+
+    /// Renders the changelog in a popup in the middle of the given area.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `area`: The area over which the popup is centered.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let width = (area.width * 4 / 5).max(20).min(area.width);
+        let height = (area.height * 4 / 5).max(5).min(area.height);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        let mut text: Vec<Line> = self
+            .lines
+            .iter()
+            .skip(self.scroll)
+            .map(|line| match line.starts_with('#') {
+                true => Line::from(Span::styled(
+                    line.trim_start_matches('#').trim(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                )),
+                false => Line::from(line.as_str()),
+            })
+            .collect();
+        if self.lines.is_empty() {
+            text.push(Line::from(Span::styled(
+                "The release has no notes",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(format!(
+                "What's new in {} (j/k: scroll, Esc: close)",
+                self.version
+            ));
+        f.render_widget(Clear, popup);
+        f.render_widget(
+            Paragraph::new(text).block(block).wrap(Wrap { trim: false }),
+            popup,
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use std::{
+    io::{self, Write},
+    process::{Command, Stdio},
+};
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes the bytes to base64 with padding.
+fn base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, byte)| {
+            group | (*byte as u32) << (16 - 8 * i)
+        });
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => encoded.push(BASE64[(group >> (18 - 6 * i) & 0x3f) as usize] as char),
+                false => encoded.push('='),
+            }
+        }
+    }
+    encoded
+}
+
+/// Copies the text to the system clipboard.
+///
+/// Without a command the text is sent to the terminal by the OSC 52 escape sequence,
+/// the terminal puts it to the clipboard, which works over SSH too. Terminals
+/// without the support ignore the sequence, then a clipboard command is needed.
+///
+/// # Arguments
+///
+/// * `text` - The copied text.
+/// * `command` - The command reading the text from its standard input, e.g. `wl-copy`
+///   or `xclip -selection clipboard`, empty to use the terminal.
+pub fn copy(text: &str, command: &str) -> io::Result<()> {
+    let mut args = command.split_whitespace();
+    let Some(program) = args.next() else {
+        let mut stdout = io::stdout();
+        write!(stdout, "\x1b]52;c;{}\x07", base64(text.as_bytes()))?;
+        return stdout.flush();
+    };
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    let status = child.wait()?;
+    match status.success() {
+        true => Ok(()),
+        false => Err(io::Error::other(format!("{program} exited with {status}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_padding() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64("(A) úkol".as_bytes()), "KEEpIMO6a29s");
+    }
+
+    #[test]
+    fn copy_by_command() -> io::Result<()> {
+        copy("task", "cat")?;
+        assert!(copy("task", "false").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,315 @@
+use crate::{
+    todo::{TaskQuery, TaskSort},
+    ToDoError, ToDoRes,
+};
+use clap::ValueEnum;
+use std::str::FromStr;
+
+/// The commands of the command mode with their arguments, the first name of a command
+/// is completed and the others are its aliases.
+pub const COMMANDS: [(&[&str], &str); 15] = [
+    (&["move", "m"], "<N> | to <N> | after <text>"),
+    (&["write", "w"], ""),
+    (&["wq", "x"], ""),
+    (&["quit", "q"], ""),
+    (&["quit!", "q!"], ""),
+    (&["age"], ""),
+    (&["tag", "t"], "<+project|@context>..."),
+    (&["sort"], "<sorting>"),
+    (&["filter", "f"], "[query]"),
+    (&["archive"], ""),
+    (&["edit", "e"], "<file>"),
+    (&["duplicate", "dup"], ""),
+    (&["send"], "<profile>"),
+    (&["run"], "<script>"),
+    (&["import"], "<file>"),
+];
+
+/// Represents a command entered in the command palette.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    /// Moves the selected task to another position in the list.
+    Move(MoveTarget),
+    /// Saves the todo list.
+    Write,
+    /// Saves the todo list and quits.
+    WriteQuit,
+    /// Quits, unsaved changes are asked about unless the quit is forced.
+    Quit { force: bool },
+    /// Writes the aged priorities the lists are sorted by to the tasks.
+    Age,
+    /// Adds the projects and contexts to the marked tasks or to the selected task.
+    Tag(Vec<String>),
+    /// Sorts the focused task list, the pending tasks if a category list is focused.
+    Sort(TaskSort),
+    /// Filters the task lists by the query of the filter bar, an empty query clears it.
+    Filter(String),
+    /// Moves the completed tasks to the done file.
+    Archive,
+    /// Opens another todo file.
+    Edit(String),
+    /// Inserts a copy of the selected task after it.
+    Duplicate,
+    /// Moves the marked tasks or the selected task to the todo file of another profile.
+    Send(String),
+    /// Runs the Lua script of the scripts directory.
+    Run(String),
+    /// Appends the tasks of a Taskwarrior JSON export or a Markdown checklist.
+    Import(String),
+}
+
+/// Represents the position the selected task is moved to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MoveTarget {
+    /// Position in the list as the user sees it, starting from 1.
+    Index(usize),
+    /// Position after the first task whose subject contains the text.
+    After(String),
+}
+
+impl FromStr for Command {
+    type Err = ToDoError;
+
+    /// Parses a command, supported commands are:
+    ///
+    /// - `move <N>` or `move to <N>`: Move the selected task to position N.
+    /// - `move after <text>`: Move the selected task after the task containing the text.
+    /// - `w` or `write`: Save the todo list.
+    /// - `wq` or `x`: Save the todo list and quit.
+    /// - `q` or `quit`: Quit, asking to save unsaved changes.
+    /// - `q!` or `quit!`: Quit without saving.
+    /// - `age`: Keep the aged priorities of the tasks.
+    /// - `tag <+project|@context>...`: Add the projects and contexts to the marked tasks.
+    /// - `sort <sorting>`: Sort the focused list, e.g. `sort due`.
+    /// - `filter [query]` or `f [query]`: Filter the lists like the filter bar, e.g. `filter +work`.
+    /// - `archive`: Move the completed tasks to the done file.
+    /// - `e <file>` or `edit <file>`: Open another todo file.
+    /// - `duplicate` or `dup`: Insert a copy of the selected task after it.
+    /// - `send <profile>`: Move the task to the todo file of another profile.
+    /// - `run <script>`: Run the Lua script.
+    /// - `import <file>`: Import the tasks of a Taskwarrior export or a Markdown checklist.
+    fn from_str(s: &str) -> ToDoRes<Self> {
+        let s = s.trim();
+        let (name, args) = s.split_once(char::is_whitespace).unwrap_or((s, ""));
+        let args = args.trim();
+        match name {
+            "move" | "m" => {
+                let target = if let Some(text) = args.strip_prefix("after ") {
+                    MoveTarget::After(text.trim().to_string())
+                } else {
+                    let index = args.strip_prefix("to ").unwrap_or(args).trim();
+                    match index.parse::<usize>() {
+                        Ok(index) if index > 0 => MoveTarget::Index(index),
+                        _ => return Err(ToDoError::ParseCommand(s.to_string())),
+                    }
+                };
+                Ok(Command::Move(target))
+            }
+            "w" | "write" if args.is_empty() => Ok(Command::Write),
+            "wq" | "x" if args.is_empty() => Ok(Command::WriteQuit),
+            "q" | "quit" if args.is_empty() => Ok(Command::Quit { force: false }),
+            "q!" | "quit!" if args.is_empty() => Ok(Command::Quit { force: true }),
+            "age" if args.is_empty() => Ok(Command::Age),
+            "tag" | "t" => {
+                let categories: Vec<String> = args.split_whitespace().map(String::from).collect();
+                let valid = |category: &String| {
+                    category.len() > 1 && (category.starts_with('+') || category.starts_with('@'))
+                };
+                if categories.is_empty() || !categories.iter().all(valid) {
+                    return Err(ToDoError::ParseCommand(s.to_string()));
+                }
+                Ok(Command::Tag(categories))
+            }
+            "sort" => match <TaskSort as ValueEnum>::from_str(args, true) {
+                Ok(sort) => Ok(Command::Sort(sort)),
+                Err(_) => Err(ToDoError::ParseCommand(s.to_string())),
+            },
+            "filter" | "f" => {
+                if !args.is_empty() {
+                    args.parse::<TaskQuery>()?;
+                }
+                Ok(Command::Filter(args.to_string()))
+            }
+            "archive" if args.is_empty() => Ok(Command::Archive),
+            "e" | "edit" if !args.is_empty() => Ok(Command::Edit(args.to_string())),
+            "duplicate" | "dup" if args.is_empty() => Ok(Command::Duplicate),
+            "send" if !args.is_empty() => Ok(Command::Send(args.to_string())),
+            "run" if !args.is_empty() => Ok(Command::Run(args.to_string())),
+            "import" if !args.is_empty() => Ok(Command::Import(args.to_string())),
+            _ => Err(ToDoError::ParseCommand(s.to_string())),
+        }
+    }
+}
+
+/// Completes the name of the command, the sorting of `sort`, the profile of `send`
+/// or the script of `run`.
+///
+/// # Arguments
+///
+/// * `input` - The typed command.
+/// * `profiles` - The names of the profiles the tasks can be sent to.
+/// * `scripts` - The names of the scripts.
+///
+/// # Returns
+///
+/// The input completed as far as all candidates agree and the candidates, e.g.
+/// `sort due` with `sort due` and `sort due-reverse` for `sort du`.
+pub fn complete_command(input: &str, profiles: &[&str], scripts: &[&str]) -> (String, Vec<String>) {
+    let input = input.trim_start();
+    let candidates: Vec<String> = match input.split_once(' ') {
+        None => COMMANDS
+            .iter()
+            .map(|(names, args)| match args.is_empty() {
+                true => names[0].to_string(),
+                false => format!("{} ", names[0]),
+            })
+            .filter(|name| name.starts_with(input))
+            .collect(),
+        Some(("sort", sort)) => TaskSort::value_variants()
+            .iter()
+            .map(|variant| format!("sort {}", variant.name()))
+            .filter(|candidate| candidate[5..].starts_with(sort.trim_start()))
+            .collect(),
+        Some(("send", profile)) => profiles
+            .iter()
+            .filter(|name| name.starts_with(profile.trim_start()))
+            .map(|name| format!("send {name}"))
+            .collect(),
+        Some(("run", script)) => scripts
+            .iter()
+            .filter(|name| name.starts_with(script.trim_start()))
+            .map(|name| format!("run {name}"))
+            .collect(),
+        Some(_) => Vec::new(),
+    };
+    let completed = match candidates.split_first() {
+        Some((first, rest)) => rest.iter().fold(first.clone(), |prefix, candidate| {
+            prefix
+                .chars()
+                .zip(candidate.chars())
+                .take_while(|(a, b)| a == b)
+                .map(|(a, _)| a)
+                .collect()
+        }),
+        None => input.to_string(),
+    };
+    (completed, candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() -> ToDoRes<()> {
+        assert_eq!(
+            Command::from_str("move 3")?,
+            Command::Move(MoveTarget::Index(3))
+        );
+        assert_eq!(
+            Command::from_str(" move to 12 ")?,
+            Command::Move(MoveTarget::Index(12))
+        );
+        assert_eq!(
+            Command::from_str("m after buy milk")?,
+            Command::Move(MoveTarget::After(String::from("buy milk")))
+        );
+        assert!(Command::from_str("move 0").is_err());
+        assert!(Command::from_str("move").is_err());
+        assert!(Command::from_str("move after").is_err());
+        assert_eq!(Command::from_str("w")?, Command::Write);
+        assert_eq!(Command::from_str(" wq ")?, Command::WriteQuit);
+        assert_eq!(Command::from_str("q")?, Command::Quit { force: false });
+        assert_eq!(Command::from_str("quit!")?, Command::Quit { force: true });
+        assert!(Command::from_str("w todo.txt").is_err());
+        assert_eq!(Command::from_str("age")?, Command::Age);
+        assert_eq!(
+            Command::from_str("tag +garden  @home")?,
+            Command::Tag(vec![String::from("+garden"), String::from("@home")])
+        );
+        assert!(Command::from_str("tag").is_err());
+        assert!(Command::from_str("tag garden").is_err());
+        assert!(Command::from_str("t + @home").is_err());
+        assert_eq!(Command::from_str("sort due")?, Command::Sort(TaskSort::Due));
+        assert_eq!(
+            Command::from_str("sort priority-reverse")?,
+            Command::Sort(TaskSort::PriorityReverse)
+        );
+        assert!(Command::from_str("sort size").is_err());
+        assert_eq!(
+            Command::from_str("filter +work @pc")?,
+            Command::Filter(String::from("+work @pc"))
+        );
+        assert_eq!(Command::from_str("f")?, Command::Filter(String::new()));
+        assert!(Command::from_str("filter due<").is_err());
+        assert_eq!(Command::from_str("archive")?, Command::Archive);
+        assert_eq!(
+            Command::from_str("e other.txt")?,
+            Command::Edit(String::from("other.txt"))
+        );
+        assert!(Command::from_str("edit").is_err());
+        assert_eq!(Command::from_str("dup")?, Command::Duplicate);
+        assert!(Command::from_str("duplicate 2").is_err());
+        assert_eq!(
+            Command::from_str("send work")?,
+            Command::Send(String::from("work"))
+        );
+        assert!(Command::from_str("send").is_err());
+        assert_eq!(
+            Command::from_str("run errands")?,
+            Command::Run(String::from("errands"))
+        );
+        assert!(Command::from_str("run").is_err());
+        assert_eq!(
+            Command::from_str("import tasks.json")?,
+            Command::Import(String::from("tasks.json"))
+        );
+        assert!(Command::from_str("import").is_err());
+        assert_eq!(
+            Command::from_str("jump 3"),
+            Err(ToDoError::ParseCommand(String::from("jump 3")))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn completion() {
+        assert_eq!(
+            complete_command("a", &[], &[]),
+            (
+                String::from("a"),
+                vec![String::from("age"), String::from("archive")]
+            )
+        );
+        assert_eq!(
+            complete_command("ar", &[], &[]),
+            (String::from("archive"), vec![String::from("archive")])
+        );
+        assert_eq!(complete_command("so", &[], &[]).0, "sort ");
+        assert_eq!(complete_command("q", &[], &[]).0, "quit");
+        assert_eq!(
+            complete_command("sort du", &[], &[]).1,
+            [String::from("sort due"), String::from("sort due-reverse")]
+        );
+        assert_eq!(complete_command("sort du", &[], &[]).0, "sort due");
+        assert_eq!(
+            complete_command("jump", &[], &[]),
+            (String::from("jump"), Vec::new())
+        );
+        assert_eq!(
+            complete_command("tag +w", &[], &[]),
+            (String::from("tag +w"), Vec::new())
+        );
+        assert_eq!(
+            complete_command("send w", &["home", "work"], &[]),
+            (String::from("send work"), vec![String::from("send work")])
+        );
+        assert_eq!(
+            complete_command("run e", &[], &["errands", "weekly"]),
+            (
+                String::from("run errands"),
+                vec![String::from("run errands")]
+            )
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use super::PopupKey;
+use crate::todo::{is_scratch, ToDo};
+use crossterm::event::{KeyCode, KeyEvent};
+use std::{collections::HashSet, fs, io};
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Represents the view comparing the todo list with another todo file side by side,
+/// e.g. a copy synchronized from another device. Tasks missing on the other side
+/// are highlighted and can be copied across.
+pub struct CompareView {
+    path: String,
+    sides: [Vec<String>; 2],
+    right: bool,
+    selected: [usize; 2],
+    only_differences: bool,
+}
+
+impl CompareView {
+    /// Creates a new `CompareView`.
+    ///
+    /// # Parameters
+    ///
+    /// - `todo`: The todo list shown on the left.
+    /// - `path`: The path to the todo file shown on the right.
+    pub fn new(todo: &ToDo, path: &str) -> io::Result<Self> {
+        let other = fs::read_to_string(path)?
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(String::from)
+            .collect();
+        let mut view = Self {
+            path: path.to_string(),
+            sides: [Vec::new(), other],
+            right: false,
+            selected: [0, 0],
+            only_differences: false,
+        };
+        view.reload(todo);
+        Ok(view)
+    }
+
+    /// Loads the tasks of the todo list again, e.g. after a task was copied to it.
+    pub fn reload(&mut self, todo: &ToDo) {
+        self.sides[0] = todo
+            .pending
+            .iter()
+            .chain(&todo.done)
+            .filter(|task| !is_scratch(task))
+            .map(|task| task.to_string())
+            .collect();
+        self.clamp();
+    }
+
+    /// Gets the lines of the side shown to the user, the flag marks lines missing on the
+    /// other side. Only the missing lines are shown if only differences are shown.
+    fn lines(&self, right: bool) -> Vec<(&str, bool)> {
+        let side = right as usize;
+        let other: HashSet<&str> = self.sides[1 - side].iter().map(String::as_str).collect();
+        self.sides[side]
+            .iter()
+            .map(|line| (line.as_str(), !other.contains(line.as_str())))
+            .filter(|(_, missing)| *missing || !self.only_differences)
+            .collect()
+    }
+
+    fn clamp(&mut self) {
+        for right in [false, true] {
+            let len = self.lines(right).len();
+            let selected = &mut self.selected[right as usize];
+            *selected = (*selected).min(len.saturating_sub(1));
+        }
+    }
+
+    /// Gets the number of lines missing on the other side, left and right.
+    pub fn differences(&self) -> (usize, usize) {
+        let count = |right| self.lines(right).iter().filter(|(_, m)| *m).count();
+        (count(false), count(true))
+    }
+
+    /// Moves the focus to the other side.
+    pub fn switch_side(&mut self) {
+        self.right = !self.right;
+    }
+
+    /// Switches between showing all lines and only the lines missing on the other side.
+    pub fn toggle_differences(&mut self) {
+        self.only_differences = !self.only_differences;
+        self.clamp();
+    }
+
+    /// Selects the next line of the focused side.
+    pub fn next(&mut self) {
+        self.selected[self.right as usize] += 1;
+        self.clamp();
+    }
+
+    /// Selects the previous line of the focused side.
+    pub fn prev(&mut self) {
+        let selected = &mut self.selected[self.right as usize];
+        *selected = selected.saturating_sub(1);
+    }
+
+    /// Copies the selected line to the other side. A line of the todo list is appended
+    /// to the other file right away, a line of the other file is returned to be added
+    /// to the todo list.
+    ///
+    /// # Returns
+    ///
+    /// The line to add to the todo list, `None` if the line was written to the other
+    /// file or is on both sides already.
+    pub fn copy_selected(&mut self) -> io::Result<Option<String>> {
+        let lines = self.lines(self.right);
+        let Some((line, true)) = lines.get(self.selected[self.right as usize]).copied() else {
+            return Ok(None);
+        };
+        let line = line.to_string();
+        if self.right {
+            return Ok(Some(line));
+        }
+        let mut other = self.sides[1].clone();
+        other.push(line);
+        fs::write(&self.path, other.join("\n") + "\n")?;
+        self.sides[1] = other;
+        self.clamp();
+        Ok(None)
+    }
+
+    /// Handles a key pressed while the view is open, Enter copies the selected task.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> PopupKey {
+        match key.code {
+            KeyCode::Esc => return PopupKey::Close,
+            KeyCode::Tab => self.switch_side(),
+            KeyCode::Down | KeyCode::Char('j') => self.next(),
+            KeyCode::Up | KeyCode::Char('k') => self.prev(),
+            KeyCode::Char('d') => self.toggle_differences(),
+            KeyCode::Enter => return PopupKey::CopyCompared,
+            _ => {}
+        }
+        PopupKey::Keep
+    }
+
+    /// Renders the view over the given area.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `area`: The area of the view.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let (left, right) = self.differences();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(format!(
+                "Compare: {left} tasks only here, {right} only in {} \
+                 (Tab: switch, Enter: copy across, d: only differences, Esc: close)",
+                self.path
+            ));
+        let inner = block.inner(area);
+        f.render_widget(Clear, area);
+        f.render_widget(block, area);
+        let panes = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(inner);
+        for (right, title) in [
+            (false, String::from("Todo list")),
+            (true, self.path.clone()),
+        ] {
+            let pane = panes[right as usize];
+            let selected = self.selected[right as usize];
+            let height = pane.height.saturating_sub(2) as usize;
+            let skip = selected.saturating_sub(height.saturating_sub(1));
+            let text: Vec<Line> = self
+                .lines(right)
+                .into_iter()
+                .enumerate()
+                .skip(skip)
+                .map(|(index, (line, missing))| {
+                    let mut style = match missing {
+                        true => Style::default().fg(if right { Color::Cyan } else { Color::Green }),
+                        false => Style::default(),
+                    };
+                    if index == selected && right == self.right {
+                        style = style.add_modifier(Modifier::REVERSED);
+                    }
+                    Line::from(Span::styled(line, style))
+                })
+                .collect();
+            let border = match right == self.right {
+                true => Style::default().fg(Color::Yellow),
+                false => Style::default().fg(Color::DarkGray),
+            };
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_style(border)
+                .title(title);
+            f.render_widget(Paragraph::new(text).block(block), pane);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env::temp_dir, process};
+
+    #[test]
+    fn compare() -> Result<(), Box<dyn std::error::Error>> {
+        let path = temp_dir().join(format!("todotxt-tui-compare-test-{}.txt", process::id()));
+        let path = path.to_string_lossy().to_string();
+        let mut todo = ToDo::default();
+        todo.new_task("call mom")?;
+        todo.new_task("write the report")?;
+        let call = todo.pending[0].to_string();
+        fs::write(&path, format!("{call}\nbuy milk\n\nwater plants\n"))?;
+
+        let mut view = CompareView::new(&todo, &path)?;
+        assert_eq!(view.differences(), (1, 2));
+
+        // A task of the todo list is appended to the other file.
+        view.next();
+        assert_eq!(view.copy_selected()?, None);
+        assert_eq!(view.differences(), (0, 2));
+        assert!(fs::read_to_string(&path)?.ends_with(&format!("{}\n", todo.pending[1])));
+
+        // A task of the other file is returned to be added to the todo list.
+        view.switch_side();
+        view.toggle_differences();
+        assert_eq!(view.copy_selected()?, Some(String::from("buy milk")));
+        view.next();
+        assert_eq!(view.copy_selected()?, Some(String::from("water plants")));
+        view.toggle_differences();
+        view.prev();
+        view.prev();
+        assert_eq!(view.copy_selected()?, None);
+        fs::remove_file(&path)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+use crate::todo::{CategoryToken, ToDo, ToDoCategory};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+use tui_input::Input;
+
+/// The maximal number of completions shown at once.
+const MAX_SHOWN: usize = 8;
+
+/// Represents the popup completing the project, context or hashtag typed at the cursor
+/// of the input, e.g. `+gar` is completed to `+garden`.
+pub struct CompletionPopup {
+    token: CategoryToken,
+    items: Vec<String>,
+    selected: usize,
+}
+
+impl CompletionPopup {
+    /// Creates the popup for the word at the cursor of the input.
+    ///
+    /// # Parameters
+    ///
+    /// - `todo`: The todo list the names are taken from.
+    /// - `input`: The typed text with the cursor.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the word at the cursor is not a category or nothing completes it.
+    pub fn new(todo: &ToDo, input: &Input) -> Option<Self> {
+        let token = CategoryToken::at(input.value(), input.cursor())?;
+        let items = token.completions(todo);
+        (!items.is_empty()).then_some(Self {
+            token,
+            items,
+            selected: 0,
+        })
+    }
+
+    /// Selects the next completion, the selection wraps around.
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % self.items.len();
+    }
+
+    /// Selects the previous completion, the selection wraps around.
+    pub fn select_prev(&mut self) {
+        self.selected = (self.selected + self.items.len() - 1) % self.items.len();
+    }
+
+    /// Gets the input with the word at the cursor replaced by the selected completion.
+    ///
+    /// # Parameters
+    ///
+    /// - `input`: The input the popup was created for.
+    pub fn complete(&self, input: &Input) -> Input {
+        let (value, cursor) = self
+            .token
+            .complete(input.value(), &self.items[self.selected]);
+        Input::new(value).with_cursor(cursor)
+    }
+
+    /// Renders the popup under the cursor of the input, or above it if there is no space
+    /// under the input.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `input_chunk`: The area of the input.
+    /// - `x`: The column of the cursor.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, input_chunk: Rect, x: u16) {
+        let area = f.size();
+        let skip = (self.selected + 1).saturating_sub(MAX_SHOWN);
+        let shown = &self.items[skip..self.items.len().min(skip + MAX_SHOWN)];
+        let width = (shown
+            .iter()
+            .map(|item| item.chars().count())
+            .max()
+            .unwrap_or(0)
+            + 3)
+        .min(area.width as usize) as u16;
+        let height = (shown.len() as u16 + 2).min(area.height);
+        // The names start under the typed prefix.
+        let x = x
+            .saturating_sub(self.token.pattern.chars().count() as u16 + 1)
+            .min(area.width.saturating_sub(width));
+        let below = input_chunk.y + input_chunk.height;
+        let y = match below + height <= area.height {
+            true => below,
+            false => input_chunk.y.saturating_sub(height),
+        };
+        let popup = Rect::new(x, y, width, height);
+        let prefix = match self.token.category {
+            ToDoCategory::Projects => "+",
+            ToDoCategory::Contexts => "@",
+            _ => "#",
+        };
+        let text: Vec<Line> = shown
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let style = match index + skip == self.selected {
+                    true => Style::default().add_modifier(Modifier::REVERSED),
+                    false => Style::default(),
+                };
+                Line::from(Span::styled(format!("{prefix}{item}"), style))
+            })
+            .collect();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::DarkGray));
+        f.render_widget(Clear, popup);
+        f.render_widget(Paragraph::new(text).block(block), popup);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn complete() {
+        let mut todo = ToDo::default();
+        todo.new_task("t +garden +garage +gym").unwrap();
+        let input = Input::new(String::from("water +ga plants")).with_cursor(9);
+        let mut popup = CompletionPopup::new(&todo, &input).unwrap();
+        assert_eq!(popup.items, ["garage", "garden"]);
+        popup.select_prev();
+        let input = popup.complete(&input);
+        assert_eq!(input.value(), "water +garden plants");
+        assert_eq!(input.cursor(), 14);
+
+        let input = Input::new(String::from("water +garden")).with_cursor(13);
+        assert!(CompletionPopup::new(&todo, &input).is_none());
+        let input = Input::new(String::from("water")).with_cursor(5);
+        assert!(CompletionPopup::new(&todo, &input).is_none());
+    }
+}
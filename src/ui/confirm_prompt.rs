@@ -0,0 +1,158 @@
+use crate::file_worker::Conflict;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// A destructive action confirmed in the prompt before it is done.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Confirmation {
+    /// Deleting the selected or the marked tasks.
+    Delete,
+    /// Moving the completed tasks to the done file.
+    Archive,
+    /// Quitting while the todo list has unsaved changes.
+    Quit,
+    /// Resolving the conflict of unsaved changes with changes of the todo file.
+    Conflict,
+    /// Making the changes of the task rules to the tasks.
+    TaskRules,
+    /// Saving the todo list over the todo file that could not be loaded.
+    SaveAnyway,
+}
+
+impl Confirmation {
+    /// Gets the title of the popup.
+    fn title(&self) -> &'static str {
+        match self {
+            Confirmation::Delete => "Delete",
+            Confirmation::Archive => "Archive",
+            Confirmation::Quit => "Quit",
+            Confirmation::Conflict => "Conflict",
+            Confirmation::TaskRules => "Task rules",
+            Confirmation::SaveAnyway => "Load failed",
+        }
+    }
+
+    /// Gets the keys answering the question.
+    fn help(&self) -> &'static str {
+        match self {
+            Confirmation::Quit => "s/Enter: save and quit, d: discard and quit, Esc: cancel",
+            Confirmation::Conflict => {
+                "b/Enter: keep both, m: keep mine, f: keep the file, Esc: decide later"
+            }
+            Confirmation::SaveAnyway => "s/y: save anyway, Esc: cancel",
+            _ => "y/Enter: yes, n/Esc: no",
+        }
+    }
+}
+
+/// Represents the yes/no question shown in a popup before a destructive action.
+pub struct ConfirmPrompt {
+    confirmation: Confirmation,
+    question: String,
+}
+
+impl ConfirmPrompt {
+    /// Creates a new `ConfirmPrompt`.
+    ///
+    /// # Parameters
+    ///
+    /// - `confirmation`: The confirmed action.
+    /// - `question`: The question shown to the user, e.g. `Delete the task "call mom"?`.
+    pub fn new(confirmation: Confirmation, question: impl Into<String>) -> Self {
+        Self {
+            confirmation,
+            question: question.into(),
+        }
+    }
+
+    /// Creates the prompt resolving conflicts of unsaved changes with changes of the todo file.
+    ///
+    /// # Parameters
+    ///
+    /// - `message`: The description of the conflict.
+    /// - `conflicts`: The conflicting lines, the first few of them are shown.
+    pub fn conflict(message: impl Into<String>, conflicts: &[Conflict]) -> Self {
+        const SHOWN: usize = 3;
+        let side = |lines: &[String]| match lines.is_empty() {
+            true => String::from("(removed)"),
+            false => lines.join(" | "),
+        };
+        let mut question = message.into();
+        for conflict in conflicts.iter().take(SHOWN) {
+            question += &format!(
+                "\nmine: {}\nfile: {}",
+                side(&conflict.local),
+                side(&conflict.remote)
+            );
+        }
+        if conflicts.len() > SHOWN {
+            question += &format!("\nand {} more", conflicts.len() - SHOWN);
+        }
+        Self::new(Confirmation::Conflict, question)
+    }
+
+    /// Creates the prompt previewing the changes of the task rules before they are made.
+    ///
+    /// # Parameters
+    ///
+    /// - `changes`: The descriptions of the changes, the first few of them are shown.
+    pub fn task_rules(changes: &[String]) -> Self {
+        const SHOWN: usize = 8;
+        let mut question = String::from("Apply the task rules?");
+        for change in changes.iter().take(SHOWN) {
+            question += &format!("\n{change}");
+        }
+        if changes.len() > SHOWN {
+            question += &format!("\nand {} more", changes.len() - SHOWN);
+        }
+        Self::new(Confirmation::TaskRules, question)
+    }
+
+    /// Gets the confirmed action.
+    pub fn confirmation(&self) -> Confirmation {
+        self.confirmation
+    }
+
+    /// Renders the prompt popup in the middle of the given area.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `area`: The area over which the popup is centered.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let help = self.confirmation.help();
+        let mut text: Vec<Line> = self.question.lines().map(Line::from).collect();
+        let question_width = self.question.lines().map(|line| line.chars().count());
+        let width = (help.len().max(question_width.max().unwrap_or(0)) as u16 + 4).min(area.width);
+        let height = (text.len() as u16 + 4).min(area.height);
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(
+            help,
+            Style::default().fg(Color::DarkGray),
+        )));
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(self.confirmation.title());
+        f.render_widget(Clear, popup);
+        f.render_widget(
+            Paragraph::new(text)
+                .block(block)
+                .alignment(Alignment::Center),
+            popup,
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use std::{
+    fs::{self, OpenOptions},
+    io,
+    path::Path,
+};
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Represents the question shown in a popup at startup when the todo file does not exist.
+pub struct CreatePrompt {
+    path: String,
+}
+
+impl CreatePrompt {
+    /// Creates a new `CreatePrompt`.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The path to the missing todo file.
+    pub fn new(path: impl Into<String>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Creates the empty todo file and the directories it is in.
+    pub fn create(&self) -> io::Result<()> {
+        if let Some(dir) = Path::new(&self.path)
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+        {
+            fs::create_dir_all(dir)?;
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map(|_| ())
+    }
+
+    /// Renders the prompt popup in the middle of the given area.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `area`: The area over which the popup is centered.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let help = "c/Enter: create it, Esc: continue, it is created on the first save";
+        let message = format!("The todo file {} does not exist.", self.path);
+        let text = vec![
+            Line::from(message.as_str()),
+            Line::from(""),
+            Line::from(Span::styled(help, Style::default().fg(Color::DarkGray))),
+        ];
+        let width = (message.len().max(help.len()) as u16 + 4).min(area.width);
+        let height = 5.min(area.height);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title("Missing todo file");
+        f.render_widget(Clear, popup);
+        f.render_widget(
+            Paragraph::new(text)
+                .block(block)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            popup,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env::temp_dir, process};
+
+    #[test]
+    fn creates_directories() -> io::Result<()> {
+        let dir = temp_dir().join(format!("todotxt-tui-create-prompt-{}", process::id()));
+        let path = dir.join("todo").join("todo.txt");
+        CreatePrompt::new(path.to_string_lossy()).create()?;
+        assert_eq!(fs::read_to_string(&path)?, "");
+        fs::write(&path, "task\n")?;
+        CreatePrompt::new(path.to_string_lossy()).create()?;
+        assert_eq!(fs::read_to_string(&path)?, "task\n");
+        fs::remove_dir_all(dir)
+    }
+}
@@ -0,0 +1,177 @@
+use super::PopupKey;
+use crate::todo::{project_info::time_left, Action, FilterState, ProjectRisk, ToDo, ToDoCategory};
+use chrono::NaiveDate;
+use crossterm::event::{KeyCode, KeyEvent};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Represents the popup listing projects with open tasks whose deadline approaches or has passed.
+pub struct DeadlineReport {
+    risks: Vec<ProjectRisk>,
+    today: NaiveDate,
+    selected: usize,
+}
+
+impl DeadlineReport {
+    /// Creates a new `DeadlineReport`.
+    ///
+    /// # Parameters
+    ///
+    /// - `risks`: The projects at risk, the closest deadlines first.
+    /// - `today`: The current date, the days left to the deadlines are counted from it.
+    pub fn new(risks: Vec<ProjectRisk>, today: NaiveDate) -> Self {
+        Self {
+            risks,
+            today,
+            selected: 0,
+        }
+    }
+
+    /// Selects the next project.
+    pub fn next(&mut self) {
+        if self.selected + 1 < self.risks.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Selects the previous project.
+    pub fn prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Gets the filters showing only the tasks of the selected project.
+    pub fn filters(&self) -> Option<Vec<(ToDoCategory, String, FilterState)>> {
+        let risk = self.risks.get(self.selected)?;
+        Some(vec![(
+            ToDoCategory::Projects,
+            risk.name.clone(),
+            FilterState::Select,
+        )])
+    }
+
+    /// Handles a key pressed while the popup is open, Enter filters the selected project.
+    pub fn handle_key(&mut self, key: &KeyEvent, todo: &mut ToDo) -> PopupKey {
+        match key.code {
+            KeyCode::Esc => return PopupKey::Close,
+            KeyCode::Down | KeyCode::Char('j') => self.next(),
+            KeyCode::Up | KeyCode::Char('k') => self.prev(),
+            KeyCode::Enter => {
+                if let Some(filters) = self.filters() {
+                    if let Err(e) = todo.apply(Action::SetFilters { filters }) {
+                        log::error!("Cannot apply the filters: {}", e);
+                    }
+                }
+                return PopupKey::Close;
+            }
+            _ => {}
+        }
+        PopupKey::Keep
+    }
+
+    /// Renders the report in a popup in the middle of the given area.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `area`: The area over which the popup is centered.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let width = (area.width * 3 / 5).max(20).min(area.width);
+        let height = (self.risks.len() as u16 + 4)
+            .min(area.height * 4 / 5)
+            .max(5)
+            .min(area.height);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        let mut text: Vec<Line> = Vec::new();
+        if self.risks.is_empty() {
+            text.push(Line::from(Span::styled(
+                "No project deadline is close",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        // Keep the selected project visible.
+        let visible = height.saturating_sub(4).max(1) as usize;
+        let skip = (self.selected + 1).saturating_sub(visible);
+        text.extend(
+            self.risks
+                .iter()
+                .enumerate()
+                .skip(skip)
+                .take(visible)
+                .map(|(i, risk)| {
+                    let style = match i == self.selected {
+                        true => Style::default().add_modifier(Modifier::REVERSED),
+                        false => Style::default(),
+                    };
+                    let color = match risk.deadline < self.today {
+                        true => Color::Red,
+                        false => Color::Yellow,
+                    };
+                    Line::from(vec![
+                        Span::styled(format!("+{} ", risk.name), style),
+                        Span::styled(
+                            format!(
+                                "{} ({})",
+                                risk.deadline,
+                                time_left(risk.deadline, self.today)
+                            ),
+                            style.fg(color),
+                        ),
+                        Span::styled(format!(", {} open", risk.open), style.fg(Color::DarkGray)),
+                    ])
+                }),
+        );
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(
+            "Enter: show the tasks, Esc: close",
+            Style::default().fg(Color::DarkGray),
+        )));
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(format!("{} projects at risk", self.risks.len()));
+        f.render_widget(Clear, popup);
+        f.render_widget(Paragraph::new(text).block(block), popup);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report() {
+        let date = |day| NaiveDate::from_ymd_opt(2023, 5, day).unwrap();
+        let risk = |name: &str, day| ProjectRisk {
+            name: name.to_string(),
+            deadline: date(day),
+            open: 1,
+        };
+        let mut report = DeadlineReport::new(vec![risk("trip", 1), risk("work", 10)], date(3));
+        report.next();
+        report.next();
+        assert_eq!(
+            report.filters(),
+            Some(vec![(
+                ToDoCategory::Projects,
+                String::from("work"),
+                FilterState::Select
+            )])
+        );
+        report.prev();
+        report.prev();
+        assert_eq!(report.filters().unwrap()[0].1, "trip");
+        assert!(DeadlineReport::new(Vec::new(), date(3)).filters().is_none());
+    }
+}
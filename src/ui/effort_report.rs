@@ -0,0 +1,140 @@
+use super::PopupKey;
+use crate::todo::{effort::format_duration, EffortRow};
+use crossterm::event::{KeyCode, KeyEvent};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Represents the popup comparing the estimated and spent time of completed tasks
+/// per project, either per week or for all weeks.
+pub struct EffortReport {
+    rows: Vec<EffortRow>,
+    by_week: bool,
+    scroll: usize,
+}
+
+impl EffortReport {
+    /// Creates a new `EffortReport`.
+    ///
+    /// # Parameters
+    ///
+    /// - `rows`: The compared projects, see [`crate::todo::ToDo::effort_report`].
+    /// - `by_week`: Whether the rows are grouped by weeks.
+    pub fn new(rows: Vec<EffortRow>, by_week: bool) -> Self {
+        Self {
+            rows,
+            by_week,
+            scroll: 0,
+        }
+    }
+
+    /// Checks whether the rows are grouped by weeks.
+    pub fn by_week(&self) -> bool {
+        self.by_week
+    }
+
+    /// Scrolls the report down by the number of rows.
+    pub fn scroll_down(&mut self, rows: usize) {
+        self.scroll = (self.scroll + rows).min(self.rows.len().saturating_sub(1));
+    }
+
+    /// Scrolls the report up by the number of rows.
+    pub fn scroll_up(&mut self, rows: usize) {
+        self.scroll = self.scroll.saturating_sub(rows);
+    }
+
+    /// Handles a key pressed while the popup is open, Tab switches between weeks and days.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> PopupKey {
+        match key.code {
+            KeyCode::Esc => return PopupKey::Close,
+            KeyCode::Down | KeyCode::Char('j') => self.scroll_down(1),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll_up(1),
+            KeyCode::PageDown => self.scroll_down(10),
+            KeyCode::PageUp => self.scroll_up(10),
+            KeyCode::Tab => {
+                return PopupKey::ShowEffort {
+                    by_week: !self.by_week(),
+                }
+            }
+            _ => {}
+        }
+        PopupKey::Keep
+    }
+
+    /// Renders the report in a popup in the middle of the given area.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `area`: The area over which the popup is centered.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let width = (area.width * 4 / 5).max(20).min(area.width);
+        let height = (area.height * 4 / 5).max(6).min(area.height);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        let header = Style::default()
+            .fg(Color::DarkGray)
+            .add_modifier(Modifier::BOLD);
+        let mut text = vec![Line::from(Span::styled(
+            format!(
+                "{:<10}{:<20}{:>6}{:>10}{:>10}{:>7}",
+                "Week", "Project", "Tasks", "Estimate", "Spent", "Ratio"
+            ),
+            header,
+        ))];
+        if self.rows.is_empty() {
+            text.push(Line::from(Span::styled(
+                "No completed task has both the effort: and the spent: tag",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        text.extend(self.rows.iter().skip(self.scroll).map(|row| {
+            let ratio = row.ratio();
+            // Tasks taking much longer or much shorter than estimated are highlighted.
+            let color = match ratio {
+                Some(ratio) if ratio > 125 => Color::Red,
+                Some(ratio) if ratio < 75 => Color::Cyan,
+                _ => Color::Green,
+            };
+            Line::from(vec![
+                Span::raw(format!(
+                    "{:<10}{:<20}{:>6}{:>10}{:>10}",
+                    row.week.as_deref().unwrap_or("all"),
+                    row.project,
+                    row.tasks,
+                    format_duration(row.estimated),
+                    format_duration(row.spent),
+                )),
+                Span::styled(
+                    format!(
+                        "{:>7}",
+                        ratio.map(|ratio| format!("{ratio}%")).unwrap_or_default()
+                    ),
+                    Style::default().fg(color),
+                ),
+            ])
+        }));
+        let grouping = match self.by_week {
+            true => "per week",
+            false => "all weeks",
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(format!(
+                "Estimated vs spent time, {grouping} (Tab: switch, Esc: close)"
+            ));
+        f.render_widget(Clear, popup);
+        f.render_widget(Paragraph::new(text).block(block), popup);
+    }
+}
@@ -0,0 +1,80 @@
+use super::UIEvent;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Represents an error shown to the user in a popup over the layout.
+///
+/// The error can be dismissed to continue working or, if the failed
+/// action is known, the action can be retried.
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub struct ErrorScreen {
+    message: String,
+    retry: Option<UIEvent>,
+}
+
+impl ErrorScreen {
+    /// Creates a new `ErrorScreen`.
+    ///
+    /// # Parameters
+    ///
+    /// - `message`: The description of the error.
+    /// - `retry`: The event that is handled again when the user chooses to retry.
+    pub fn new(message: impl Into<String>, retry: Option<UIEvent>) -> Self {
+        Self {
+            message: message.into(),
+            retry,
+        }
+    }
+
+    /// Gets the event to handle when the user chooses to retry.
+    pub fn retry(&self) -> Option<UIEvent> {
+        self.retry
+    }
+
+    /// Renders the error popup in the middle of the given area.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `area`: The area over which the popup is centered.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let help = match self.retry {
+            Some(_) => "Enter: retry, Esc: continue",
+            None => "Enter/Esc: continue",
+        };
+        let text = vec![
+            Line::from(self.message.as_str()),
+            Line::from(""),
+            Line::from(Span::styled(help, Style::default().fg(Color::DarkGray))),
+        ];
+        let width = (area.width * 3 / 5).max(20).min(area.width);
+        let inner_width = width.saturating_sub(2).max(1) as usize;
+        let lines = self.message.len() / inner_width + 1 + 2;
+        let height = (lines as u16 + 2).min(area.height);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Red))
+            .title("Error");
+        f.render_widget(Clear, popup);
+        f.render_widget(
+            Paragraph::new(text)
+                .block(block)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            popup,
+        );
+    }
+}
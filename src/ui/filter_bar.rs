@@ -0,0 +1,118 @@
+use crate::todo::{Action, TaskQuery, ToDo};
+use crossterm::event::Event;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+use tui_input::{backend::crossterm::EventHandler, Input};
+
+/// Label drawn before the query.
+const LABEL: &str = " Filter: ";
+
+/// Represents the one-line bar with the query filtering the task lists, e.g.
+/// `+work @pc !B <due:1w report`. The lists are updated while the query is typed.
+#[derive(Default)]
+pub struct FilterBar {
+    input: Input,
+    invalid: bool,
+}
+
+impl FilterBar {
+    /// Starts editing the query of the filter bar, the typed query starts with the active one.
+    ///
+    /// # Parameters
+    ///
+    /// - `todo`: The todo list with the active query.
+    pub fn edit(&mut self, todo: &ToDo) {
+        if !self.invalid {
+            self.input = todo
+                .filter_bar()
+                .map(|query| query.to_string())
+                .unwrap_or_default()
+                .into();
+        }
+    }
+
+    /// Passes the key to the typed query and filters the lists by it. An invalid query,
+    /// e.g. an unfinished `due<`, keeps the last valid one until it is fixed.
+    ///
+    /// # Parameters
+    ///
+    /// - `event`: The key event.
+    /// - `todo`: The filtered todo list.
+    pub fn handle_event(&mut self, event: &Event, todo: &mut ToDo) {
+        self.input.handle_event(event);
+        self.invalid = self.input.value().parse::<TaskQuery>().is_err();
+        if !self.invalid {
+            self.apply(todo, Some(self.input.value().to_string()));
+        }
+    }
+
+    /// Sets the query, e.g. by a command, an empty query clears the filter bar.
+    ///
+    /// # Parameters
+    ///
+    /// - `todo`: The filtered todo list.
+    /// - `query`: The valid query.
+    pub fn set(&mut self, todo: &mut ToDo, query: &str) {
+        self.input = query.into();
+        self.invalid = false;
+        self.apply(todo, (!query.is_empty()).then(|| query.to_string()));
+    }
+
+    /// Clears the query, the lists show all tasks matching the other filters.
+    pub fn clear(&mut self, todo: &mut ToDo) {
+        self.input.reset();
+        self.invalid = false;
+        self.apply(todo, None);
+    }
+
+    fn apply(&self, todo: &mut ToDo, query: Option<String>) {
+        if let Err(e) = todo.apply(Action::SetFilterBar { query }) {
+            log::error!("Cannot filter the tasks: {}", e);
+        }
+    }
+
+    /// Checks whether the bar has something to show, the active query or a typed one.
+    pub fn is_empty(&self, todo: &ToDo) -> bool {
+        self.input.value().is_empty() && todo.filter_bar().is_none()
+    }
+
+    /// Renders the bar, the cursor is shown while the query is typed.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `area`: The line of the bar.
+    /// - `focused`: Whether the query is typed.
+    /// - `color`: The color of the label while the query is typed.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect, focused: bool, color: Color) {
+        let label = match focused {
+            true => Style::default().fg(color).add_modifier(Modifier::BOLD),
+            false => Style::default().fg(Color::DarkGray),
+        };
+        let query = match self.invalid {
+            true => Style::default().fg(Color::Red),
+            false => Style::default(),
+        };
+        let mut spans = vec![
+            Span::styled(LABEL, label),
+            Span::styled(self.input.value(), query),
+        ];
+        if self.input.value().is_empty() && focused {
+            spans.push(Span::styled(
+                "+project @context !A due<7 text",
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        f.render_widget(Paragraph::new(Line::from(spans)), area);
+        if focused {
+            let cursor = (LABEL.len() + self.input.visual_cursor()) as u16;
+            f.set_cursor(area.x + cursor.min(area.width.saturating_sub(1)), area.y);
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use super::PopupKey;
+use crate::todo::Forecast;
+use crossterm::event::{KeyCode, KeyEvent};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Represents the popup estimating when the open tasks of the projects are completed.
+pub struct ForecastReport {
+    rows: Vec<Forecast>,
+    days: usize,
+    scroll: usize,
+}
+
+impl ForecastReport {
+    /// Creates a new `ForecastReport`.
+    ///
+    /// # Parameters
+    ///
+    /// - `rows`: The forecasts, see [`crate::todo::ToDo::forecast`].
+    /// - `days`: The number of recent days the pace was measured over.
+    pub fn new(rows: Vec<Forecast>, days: usize) -> Self {
+        Self {
+            rows,
+            days,
+            scroll: 0,
+        }
+    }
+
+    /// Scrolls the report down by the number of rows.
+    pub fn scroll_down(&mut self, rows: usize) {
+        self.scroll = (self.scroll + rows).min(self.rows.len().saturating_sub(1));
+    }
+
+    /// Scrolls the report up by the number of rows.
+    pub fn scroll_up(&mut self, rows: usize) {
+        self.scroll = self.scroll.saturating_sub(rows);
+    }
+
+    /// Handles a key pressed while the popup is open, the keys scroll the report.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> PopupKey {
+        match key.code {
+            KeyCode::Esc => return PopupKey::Close,
+            KeyCode::Down | KeyCode::Char('j') => self.scroll_down(1),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll_up(1),
+            KeyCode::PageDown => self.scroll_down(10),
+            KeyCode::PageUp => self.scroll_up(10),
+            _ => {}
+        }
+        PopupKey::Keep
+    }
+
+    /// Renders the report in a popup in the middle of the given area.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `area`: The area over which the popup is centered.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let width = (area.width * 4 / 5).max(20).min(area.width);
+        let height = (area.height * 4 / 5).max(6).min(area.height);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        let header = Style::default()
+            .fg(Color::DarkGray)
+            .add_modifier(Modifier::BOLD);
+        let mut text = vec![Line::from(Span::styled(
+            format!(
+                "{:<20}{:>6}{:>6}{:>10}{:>14}",
+                "Project", "Open", "Done", "Per week", "Clears"
+            ),
+            header,
+        ))];
+        text.extend(self.rows.iter().skip(self.scroll).map(|row| {
+            // Projects that are not moving are highlighted, they never clear at this pace.
+            let (clears, color) = match row.clear_on {
+                Some(date) => (date.to_string(), Color::Green),
+                None => (String::from("stalled"), Color::Red),
+            };
+            let name = match &row.project {
+                Some(project) => format!("+{project}"),
+                None => String::from("All tasks"),
+            };
+            Line::from(vec![
+                Span::raw(format!(
+                    "{:<20}{:>6}{:>6}{:>10.1}",
+                    name, row.open, row.completed, row.per_week
+                )),
+                Span::styled(format!("{clears:>14}"), Style::default().fg(color)),
+            ])
+        }));
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(format!(
+                "Completion forecast, last {} days (j/k: scroll, Esc: close)",
+                self.days
+            ));
+        f.render_widget(Clear, popup);
+        f.render_widget(Paragraph::new(text).block(block), popup);
+    }
+}
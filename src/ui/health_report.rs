@@ -0,0 +1,212 @@
+use crate::file_worker::Problem;
+use crossterm::event::{KeyCode, KeyEvent};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// What the window does after the health report handled a key.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ReportKey {
+    /// The report stays open.
+    Keep,
+    /// The report is closed.
+    Close,
+    /// The problem is fixed in the input.
+    Fix(Problem),
+    /// The selected problem is removed from the report.
+    Skip,
+    /// The report is closed and the file is opened in the editor at the line.
+    OpenEditor { path: String, line: usize },
+}
+
+/// Represents the report of problems found in the todo file when it was loaded.
+///
+/// Every problem can be fixed in the input, skipped, or the file can be opened in the editor.
+pub struct HealthReport {
+    problems: Vec<Problem>,
+    selected: usize,
+}
+
+impl HealthReport {
+    /// Creates a new `HealthReport`.
+    ///
+    /// # Parameters
+    ///
+    /// - `problems`: Lines of the todo file with problems.
+    pub fn new(problems: Vec<Problem>) -> Self {
+        Self {
+            problems,
+            selected: 0,
+        }
+    }
+
+    /// Gets the selected problem.
+    pub fn selected(&self) -> Option<&Problem> {
+        self.problems.get(self.selected)
+    }
+
+    /// Removes the selected problem from the report, it was fixed or skipped.
+    ///
+    /// # Returns
+    ///
+    /// `true` if there are problems left in the report.
+    pub fn remove_selected(&mut self) -> bool {
+        if self.selected < self.problems.len() {
+            self.problems.remove(self.selected);
+        }
+        self.selected = self.selected.min(self.problems.len().saturating_sub(1));
+        !self.problems.is_empty()
+    }
+
+    /// Selects the next problem.
+    pub fn next(&mut self) {
+        if self.selected + 1 < self.problems.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Selects the previous problem.
+    pub fn prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Handles a key pressed while the report is open, the selected problem is fixed, skipped or opened in the editor.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> ReportKey {
+        match key.code {
+            KeyCode::Enter => {
+                if let Some(problem) = self.selected() {
+                    return ReportKey::Fix(problem.clone());
+                }
+            }
+            KeyCode::Char('s') => return ReportKey::Skip,
+            // Lines change in the editor, the report would not be valid anymore.
+            KeyCode::Char('e') => {
+                return match self.selected() {
+                    Some(problem) => ReportKey::OpenEditor {
+                        path: problem.path.clone(),
+                        line: problem.line,
+                    },
+                    None => ReportKey::Close,
+                }
+            }
+            KeyCode::Esc => return ReportKey::Close,
+            KeyCode::Down | KeyCode::Char('j') => self.next(),
+            KeyCode::Up | KeyCode::Char('k') => self.prev(),
+            _ => {}
+        }
+        ReportKey::Keep
+    }
+
+    /// Renders the report in a popup in the middle of the given area.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `area`: The area over which the popup is centered.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let width = (area.width * 4 / 5).max(20).min(area.width);
+        let height = (self.problems.len() as u16 * 2 + 4)
+            .min(area.height * 4 / 5)
+            .max(6)
+            .min(area.height);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        // Keep the selected problem visible, every problem takes two lines.
+        let visible = (height.saturating_sub(4) / 2).max(1) as usize;
+        let skip = (self.selected + 1).saturating_sub(visible);
+        let mut text: Vec<Line> = Vec::new();
+        for (i, problem) in self.problems.iter().enumerate().skip(skip).take(visible) {
+            let style = if i == self.selected {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            text.push(Line::from(Span::styled(
+                format!("{}:{}: {}", problem.path, problem.line, problem.issue),
+                style.fg(Color::Yellow),
+            )));
+            text.push(Line::from(Span::styled(
+                format!("  {}", problem.text),
+                style,
+            )));
+        }
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(
+            "Enter: fix, s: skip, e: open in editor, Esc: skip all",
+            Style::default().fg(Color::DarkGray),
+        )));
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(format!("{} problems in the todo list", self.problems.len()));
+        f.render_widget(Clear, popup);
+        f.render_widget(Paragraph::new(text).block(block), popup);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_worker::Issue;
+    use crossterm::event::KeyModifiers;
+
+    fn problem(line: usize) -> Problem {
+        Problem {
+            path: String::from("todo.txt"),
+            line,
+            text: String::from("(a) task"),
+            issue: Issue::MalformedPriority(String::from("(a)")),
+            task: None,
+        }
+    }
+
+    #[test]
+    fn navigation() {
+        let mut report = HealthReport::new(vec![problem(1), problem(2), problem(3)]);
+        report.prev();
+        assert_eq!(report.selected().unwrap().line, 1);
+        report.next();
+        report.next();
+        report.next();
+        assert_eq!(report.selected().unwrap().line, 3);
+        assert!(report.remove_selected());
+        assert_eq!(report.selected().unwrap().line, 2);
+        report.prev();
+        assert!(report.remove_selected());
+        assert_eq!(report.selected().unwrap().line, 2);
+        assert!(!report.remove_selected());
+        assert!(report.selected().is_none());
+    }
+
+    #[test]
+    fn keys() {
+        let mut report = HealthReport::new(vec![problem(1), problem(2)]);
+        let press = |report: &mut HealthReport, code| {
+            report.handle_key(&KeyEvent::new(code, KeyModifiers::NONE))
+        };
+        assert_eq!(press(&mut report, KeyCode::Char('j')), ReportKey::Keep);
+        assert_eq!(
+            press(&mut report, KeyCode::Enter),
+            ReportKey::Fix(problem(2))
+        );
+        assert_eq!(press(&mut report, KeyCode::Char('s')), ReportKey::Skip);
+        assert_eq!(
+            press(&mut report, KeyCode::Char('e')),
+            ReportKey::OpenEditor {
+                path: String::from("todo.txt"),
+                line: 2
+            }
+        );
+        assert_eq!(press(&mut report, KeyCode::Esc), ReportKey::Close);
+    }
+}
@@ -0,0 +1,130 @@
+use super::PopupKey;
+use crate::todo::History;
+use chrono::NaiveDate;
+use crossterm::event::{KeyCode, KeyEvent};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Names of the rows of the heatmap.
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// Represents the popup with the statistics of all completed tasks: the totals,
+/// the streaks of days with a completed task and the heatmap of recent weeks.
+pub struct HistoryReport {
+    history: History,
+    today: NaiveDate,
+}
+
+impl HistoryReport {
+    /// Creates a new `HistoryReport`.
+    ///
+    /// # Parameters
+    ///
+    /// - `history`: The completed tasks, see [`crate::todo::ToDo::history`].
+    /// - `today`: The current date, the last day of the heatmap.
+    pub fn new(history: History, today: NaiveDate) -> Self {
+        Self { history, today }
+    }
+
+    /// Handles a key pressed while the popup is open, only Esc closes it.
+    pub fn handle_key(&self, key: &KeyEvent) -> PopupKey {
+        match key.code {
+            KeyCode::Esc => PopupKey::Close,
+            _ => PopupKey::Keep,
+        }
+    }
+
+    /// Renders the report in a popup in the middle of the given area.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `area`: The area over which the popup is centered.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let width = (area.width * 4 / 5).max(20).min(area.width);
+        let height = (area.height * 4 / 5).max(6).min(area.height);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        let header = Style::default()
+            .fg(Color::DarkGray)
+            .add_modifier(Modifier::BOLD);
+        let (current, longest) = self.history.streaks(self.today);
+        let mut text = vec![
+            Line::from(format!(
+                "Completed: {} tasks{}",
+                self.history.total(),
+                self.history
+                    .first()
+                    .map(|first| format!(" since {first}"))
+                    .unwrap_or_default()
+            )),
+            Line::from(format!("Streak: {current} days, longest {longest} days")),
+            Line::default(),
+        ];
+
+        // Every column of the heatmap is a week, the current one on the right.
+        let weeks = (width.saturating_sub(15) as usize).clamp(1, 52);
+        let heatmap = self.history.heatmap(self.today, weeks);
+        let max = heatmap.iter().flatten().copied().max().unwrap_or(0).max(1);
+        let weekdays = self.history.weekdays();
+        text.push(Line::from(Span::styled(
+            format!("Last {weeks} weeks"),
+            header,
+        )));
+        text.extend(WEEKDAYS.iter().enumerate().map(|(day, name)| {
+            let mut spans = vec![Span::styled(format!("{name}  "), header)];
+            spans.extend(heatmap.iter().map(|week| {
+                let (cell, color) = match week[day] {
+                    0 => ("·", Color::DarkGray),
+                    count if count * 3 <= max => ("░", Color::Green),
+                    count if count * 3 <= max * 2 => ("▒", Color::Green),
+                    _ => ("█", Color::Green),
+                };
+                Span::styled(cell, Style::default().fg(color))
+            }));
+            spans.push(Span::styled(
+                format!("  {}", weekdays[day]),
+                Style::default().fg(Color::DarkGray),
+            ));
+            Line::from(spans)
+        }));
+
+        let projects = self.history.top_projects(5);
+        if !projects.is_empty() {
+            text.push(Line::default());
+            text.push(Line::from(Span::styled("Top projects", header)));
+            text.extend(
+                projects
+                    .into_iter()
+                    .map(|(project, tasks)| Line::from(format!("{project:<20}{tasks:>6}"))),
+            );
+        }
+        if self.history.undated() > 0 {
+            text.push(Line::default());
+            text.push(Line::from(Span::styled(
+                format!(
+                    "{} of the tasks have no completion date",
+                    self.history.undated()
+                ),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title("History of completed tasks (Esc: close)");
+        f.render_widget(Clear, popup);
+        f.render_widget(Paragraph::new(text).block(block), popup);
+    }
+}
@@ -0,0 +1,185 @@
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::PathBuf,
+};
+
+/// Represents the lines entered in the new-task input, recalled with `Up` and `Down`
+/// like in a shell. The lines are kept in a file, the oldest lines are dropped
+/// once there are more than the limit.
+pub struct InputHistory {
+    path: Option<PathBuf>,
+    limit: usize,
+    entries: Vec<String>,
+    /// The index of the recalled line, `None` while the line is typed.
+    position: Option<usize>,
+    /// The typed line, restored when the recall moves past the newest line.
+    draft: String,
+}
+
+impl InputHistory {
+    /// Creates an empty history.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The history file, `None` keeps the history only in memory.
+    /// - `limit`: The maximal number of kept lines, `0` turns the history off.
+    pub fn new(path: Option<PathBuf>, limit: usize) -> Self {
+        Self {
+            path,
+            limit,
+            entries: Vec::new(),
+            position: None,
+            draft: String::new(),
+        }
+    }
+
+    /// Reads the lines of the history file, a missing file is an empty history.
+    pub fn load(&mut self) -> io::Result<()> {
+        let Some(path) = self.path.as_ref().filter(|_| self.limit > 0) else {
+            return Ok(());
+        };
+        match fs::read_to_string(path) {
+            Ok(content) => self.entries = content.lines().map(String::from).collect(),
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        self.truncate();
+        Ok(())
+    }
+
+    /// Drops the oldest lines above the limit.
+    fn truncate(&mut self) {
+        let excess = self.entries.len().saturating_sub(self.limit);
+        self.entries.drain(..excess);
+    }
+
+    /// Adds the entered line as the newest one and stops the recall. Empty lines
+    /// and lines repeating the newest one are skipped.
+    ///
+    /// # Parameters
+    ///
+    /// - `line`: The entered line.
+    pub fn push(&mut self, line: &str) {
+        self.reset();
+        if self.limit == 0
+            || line.trim().is_empty()
+            || self.entries.last().is_some_and(|last| last == line)
+        {
+            return;
+        }
+        self.entries.push(line.to_string());
+        self.truncate();
+    }
+
+    /// Writes the lines to the history file.
+    pub fn save(&self) -> io::Result<()> {
+        match self.path.as_ref().filter(|_| self.limit > 0) {
+            Some(path) => {
+                let content: String = self
+                    .entries
+                    .iter()
+                    .map(|line| format!("{line}\n"))
+                    .collect();
+                fs::write(path, content)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Stops the recall, the next recall starts from the newest line.
+    pub fn reset(&mut self) {
+        self.position = None;
+        self.draft.clear();
+    }
+
+    /// Recalls the line before the recalled one.
+    ///
+    /// # Parameters
+    ///
+    /// - `current`: The line in the input, kept to be restored by [`InputHistory::newer`].
+    ///
+    /// # Returns
+    ///
+    /// The recalled line or `None` if there is no older line.
+    pub fn older(&mut self, current: &str) -> Option<&str> {
+        let position = match self.position {
+            Some(0) => return None,
+            Some(position) => position - 1,
+            None if self.entries.is_empty() => return None,
+            None => {
+                self.draft = current.to_string();
+                self.entries.len() - 1
+            }
+        };
+        self.position = Some(position);
+        Some(&self.entries[position])
+    }
+
+    /// Recalls the line after the recalled one.
+    ///
+    /// # Returns
+    ///
+    /// The recalled line, the typed line after the newest one or `None`
+    /// if no line is recalled.
+    pub fn newer(&mut self) -> Option<&str> {
+        let position = self.position? + 1;
+        if position < self.entries.len() {
+            self.position = Some(position);
+            Some(&self.entries[position])
+        } else {
+            self.position = None;
+            Some(&self.draft)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env::temp_dir, process};
+
+    #[test]
+    fn recall() {
+        let mut history = InputHistory::new(None, 3);
+        assert_eq!(history.older("typed"), None);
+        for line in [
+            "call mom",
+            "buy milk",
+            "buy milk",
+            "",
+            "fix the bike",
+            "pay rent",
+        ] {
+            history.push(line);
+        }
+        assert_eq!(history.entries, ["buy milk", "fix the bike", "pay rent"]);
+
+        assert_eq!(history.newer(), None);
+        assert_eq!(history.older("water"), Some("pay rent"));
+        assert_eq!(history.older("pay rent"), Some("fix the bike"));
+        assert_eq!(history.older("fix the bike"), Some("buy milk"));
+        assert_eq!(history.older("buy milk"), None);
+        assert_eq!(history.newer(), Some("fix the bike"));
+        assert_eq!(history.newer(), Some("pay rent"));
+        assert_eq!(history.newer(), Some("water"));
+        assert_eq!(history.newer(), None);
+    }
+
+    #[test]
+    fn save() -> io::Result<()> {
+        let path = temp_dir().join(format!(
+            "todotxt-tui-input-history-test-{}.txt",
+            process::id()
+        ));
+        fs::write(&path, "one\ntwo\nthree\n")?;
+        let mut history = InputHistory::new(Some(path.clone()), 2);
+        history.load()?;
+        assert_eq!(history.entries, ["two", "three"]);
+        history.push("four");
+        history.save()?;
+        assert_eq!(fs::read_to_string(&path)?, "three\nfour\n");
+        fs::remove_file(&path)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,253 @@
+use super::PopupKey;
+use super::{EventHandlerUI, UIEvent};
+use crate::config::{key_name, Config, KeybindScope};
+use crossterm::event::{KeyCode, KeyEvent};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Represents the popup for viewing, rebinding and removing keybindings.
+///
+/// A binding is rebound by pressing the new key, a binding of the same key in the
+/// same scope is replaced. The changes are applied to the configuration when saved.
+pub struct KeybindEditor {
+    bindings: Vec<(KeybindScope, KeyCode, UIEvent)>,
+    selected: usize,
+    capturing: bool,
+    message: Option<String>,
+}
+
+impl KeybindEditor {
+    /// Creates a new `KeybindEditor` with the keybindings of the configuration.
+    ///
+    /// # Parameters
+    ///
+    /// - `config`: The configuration with the keybindings.
+    pub fn new(config: &Config) -> Self {
+        let mut bindings: Vec<_> = KeybindScope::ALL
+            .iter()
+            .flat_map(|scope| {
+                config
+                    .get_keybind(*scope)
+                    .bindings()
+                    .into_iter()
+                    .map(|(key, event)| (*scope, key, event))
+            })
+            .collect();
+        bindings.sort_by_key(|(scope, _, event)| (*scope, format!("{event:?}")));
+        Self {
+            bindings,
+            selected: 0,
+            capturing: false,
+            message: None,
+        }
+    }
+
+    /// Checks whether the next key press is the new key of the selected binding.
+    pub fn is_capturing(&self) -> bool {
+        self.capturing
+    }
+
+    /// Selects the next binding.
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.bindings.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Selects the previous binding.
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Waits for the new key of the selected binding.
+    pub fn start_capture(&mut self) {
+        if self.selected < self.bindings.len() {
+            self.capturing = true;
+            self.message = Some(String::from("Press the new key, Esc: cancel"));
+        }
+    }
+
+    /// Binds the key to the event of the selected binding, `Esc` cancels the rebinding.
+    /// A binding of the same key in the same scope is removed.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The pressed key.
+    pub fn capture(&mut self, key: KeyCode) {
+        self.capturing = false;
+        if key == KeyCode::Esc {
+            self.message = None;
+            return;
+        }
+        let (scope, _, event) = self.bindings[self.selected];
+        self.message = None;
+        if let Some(index) =
+            self.bindings.iter().enumerate().position(|(i, binding)| {
+                i != self.selected && binding.0 == scope && binding.1 == key
+            })
+        {
+            let (_, _, replaced) = self.bindings.remove(index);
+            self.message = Some(format!("{} no longer triggers {replaced:?}", key_name(key)));
+            if index < self.selected {
+                self.selected -= 1;
+            }
+        }
+        self.bindings[self.selected] = (scope, key, event);
+    }
+
+    /// Removes the selected binding.
+    pub fn remove_selected(&mut self) {
+        if self.selected < self.bindings.len() {
+            self.bindings.remove(self.selected);
+        }
+        self.selected = self.selected.min(self.bindings.len().saturating_sub(1));
+    }
+
+    /// Replaces the keybindings of the configuration with the edited ones.
+    ///
+    /// # Parameters
+    ///
+    /// - `config`: The configuration to change.
+    pub fn apply(&self, config: &mut Config) {
+        for scope in KeybindScope::ALL {
+            let bindings: Vec<(KeyCode, UIEvent)> = self
+                .bindings
+                .iter()
+                .filter(|binding| binding.0 == scope)
+                .map(|(_, key, event)| (*key, *event))
+                .collect();
+            config.set_keybind(scope, EventHandlerUI::new(&bindings));
+        }
+    }
+
+    /// Handles a key pressed while the editor is open, a captured key is bound to the selected event.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> PopupKey {
+        if self.is_capturing() {
+            self.capture(key.code);
+            return PopupKey::Keep;
+        }
+        match key.code {
+            KeyCode::Esc => return PopupKey::Close,
+            KeyCode::Down | KeyCode::Char('j') => self.select_next(),
+            KeyCode::Up | KeyCode::Char('k') => self.select_prev(),
+            KeyCode::Enter => self.start_capture(),
+            KeyCode::Delete | KeyCode::Char('x') => self.remove_selected(),
+            KeyCode::Char('s') => return PopupKey::SaveKeybinds,
+            _ => {}
+        }
+        PopupKey::Keep
+    }
+
+    /// Renders the editor in a popup in the middle of the given area.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `area`: The area over which the popup is centered.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let width = (area.width * 3 / 5).max(20).min(area.width);
+        let height = (area.height * 4 / 5).max(6).min(area.height);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        // Keep the selected binding visible below the help lines.
+        let visible = height.saturating_sub(4).max(1) as usize;
+        let skip = (self.selected + 1).saturating_sub(visible);
+        let mut text: Vec<Line> = self
+            .bindings
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .take(visible)
+            .map(|(i, (scope, key, event))| {
+                let mut style = Style::default();
+                if i == self.selected {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+                let key = match self.capturing && i == self.selected {
+                    true => String::from("..."),
+                    false => key_name(*key),
+                };
+                Line::from(vec![
+                    Span::styled(format!("{:<16}", scope.name()), style.fg(Color::DarkGray)),
+                    Span::styled(format!("{key:<10}"), style.fg(Color::Yellow)),
+                    Span::styled(format!("{event:?}"), style),
+                ])
+            })
+            .collect();
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(
+            self.message.clone().unwrap_or_else(|| {
+                String::from("Enter: rebind, x: remove, s: save to the config file, Esc: cancel")
+            }),
+            Style::default().fg(Color::DarkGray),
+        )));
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(format!("Keybindings ({})", self.bindings.len()));
+        f.render_widget(Clear, popup);
+        f.render_widget(Paragraph::new(text).block(block), popup);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn select(editor: &mut KeybindEditor, scope: KeybindScope, event: UIEvent) {
+        editor.selected = editor
+            .bindings
+            .iter()
+            .position(|binding| binding.0 == scope && binding.2 == event)
+            .unwrap();
+    }
+
+    #[test]
+    fn rebind() {
+        let mut config = Config::default();
+        let mut editor = KeybindEditor::new(&config);
+        let count = editor.bindings.len();
+
+        select(&mut editor, KeybindScope::Window, UIEvent::Quit);
+        editor.start_capture();
+        assert!(editor.is_capturing());
+        editor.capture(KeyCode::Esc);
+        assert!(!editor.is_capturing());
+
+        editor.start_capture();
+        editor.capture(KeyCode::Char('S'));
+        assert_eq!(editor.bindings.len(), count - 1);
+        assert_eq!(
+            editor.bindings[editor.selected],
+            (KeybindScope::Window, KeyCode::Char('S'), UIEvent::Quit)
+        );
+
+        select(&mut editor, KeybindScope::List, UIEvent::ListDown);
+        editor.start_capture();
+        editor.capture(KeyCode::Char('q'));
+        select(&mut editor, KeybindScope::Category, UIEvent::Remove);
+        editor.remove_selected();
+
+        editor.apply(&mut config);
+        let window = config.get_window_keybind();
+        assert_eq!(window.get_event(&KeyCode::Char('S')), UIEvent::Quit);
+        assert_eq!(window.get_event(&KeyCode::Char('q')), UIEvent::None);
+        let list = config.get_list_keybind();
+        assert_eq!(list.get_event(&KeyCode::Char('q')), UIEvent::ListDown);
+        assert_eq!(list.get_event(&KeyCode::Char('j')), UIEvent::None);
+        let category = config.get_category_keybind();
+        assert_eq!(category.get_event(&KeyCode::Backspace), UIEvent::None);
+        assert_eq!(category.get_event(&KeyCode::Enter), UIEvent::Select);
+    }
+}
@@ -0,0 +1,231 @@
+use super::PopupKey;
+use crate::todo::{week_start, Action, DateTag, PlannedTask, TaskId, ToDo, ToDoData};
+use chrono::{Duration, NaiveDate};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Represents the week-at-a-glance planner: seven columns from Monday to Sunday
+/// with the pending tasks due or scheduled (`t:`) on each day. The selected task
+/// is planned for another day by moving its date.
+pub struct PlannerView {
+    monday: NaiveDate,
+    today: NaiveDate,
+    days: [Vec<PlannedTask>; 7],
+    day: usize,
+    selected: usize,
+}
+
+impl PlannerView {
+    /// Creates a new `PlannerView` showing the current week with today selected.
+    ///
+    /// # Parameters
+    ///
+    /// - `todo`: The todo list with the planned tasks.
+    /// - `today`: The current date.
+    pub fn new(todo: &ToDo, today: NaiveDate) -> Self {
+        let monday = week_start(today);
+        let mut view = Self {
+            monday,
+            today,
+            days: Default::default(),
+            day: (today - monday).num_days() as usize,
+            selected: 0,
+        };
+        view.reload(todo);
+        view
+    }
+
+    /// Loads the tasks of the week again, e.g. after the todo list has changed.
+    pub fn reload(&mut self, todo: &ToDo) {
+        self.days = todo.week(self.monday);
+        self.clamp();
+    }
+
+    fn clamp(&mut self) {
+        self.selected = self
+            .selected
+            .min(self.days[self.day].len().saturating_sub(1));
+    }
+
+    /// Gets the selected task.
+    pub fn selected(&self) -> Option<&PlannedTask> {
+        self.days[self.day].get(self.selected)
+    }
+
+    /// Selects the next task of the day.
+    pub fn next(&mut self) {
+        self.selected += 1;
+        self.clamp();
+    }
+
+    /// Selects the previous task of the day.
+    pub fn prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Selects the day by the number of days from the selected one, the week changes
+    /// when the day is outside of it.
+    ///
+    /// # Parameters
+    ///
+    /// - `todo`: The todo list with the planned tasks.
+    /// - `days`: The number of days, negative to select an earlier day.
+    pub fn select_day(&mut self, todo: &ToDo, days: i64) {
+        let date = self.monday + Duration::days(self.day as i64 + days);
+        let monday = week_start(date);
+        self.day = (date - monday).num_days() as usize;
+        self.selected = 0;
+        if monday != self.monday {
+            self.monday = monday;
+            self.reload(todo);
+        }
+    }
+
+    /// Moves the selected task by the number of days, the date that placed the task
+    /// on the selected day is rewritten. The task stays selected on its new day.
+    ///
+    /// # Parameters
+    ///
+    /// - `todo`: The todo list with the planned tasks.
+    /// - `days`: The number of days, negative to move the task to an earlier day.
+    pub fn move_selected(&mut self, todo: &mut ToDo, days: i64) -> Result<(), todo_txt::Error> {
+        let Some(planned) = self.selected().cloned() else {
+            return Ok(());
+        };
+        todo.apply(Action::ShiftDate {
+            data: ToDoData::Pending,
+            task: TaskId::new(planned.index, &planned.task),
+            tag: planned.tag,
+            days,
+        })?;
+        self.select_day(todo, days);
+        self.reload(todo);
+        if let Some(position) = self.days[self.day]
+            .iter()
+            .position(|moved| moved.index == planned.index)
+        {
+            self.selected = position;
+        }
+        Ok(())
+    }
+
+    /// Handles a key pressed while the planner is open, shifted keys move the selected task.
+    pub fn handle_key(&mut self, key: &KeyEvent, todo: &mut ToDo) -> PopupKey {
+        let days = match (key.code, key.modifiers) {
+            (KeyCode::Char('H'), _) | (KeyCode::Left, KeyModifiers::SHIFT) => -1,
+            (KeyCode::Char('L'), _) | (KeyCode::Right, KeyModifiers::SHIFT) => 1,
+            (code, _) => {
+                match code {
+                    KeyCode::Esc => return PopupKey::Close,
+                    KeyCode::Left | KeyCode::Char('h') => self.select_day(todo, -1),
+                    KeyCode::Right | KeyCode::Char('l') => self.select_day(todo, 1),
+                    KeyCode::Down | KeyCode::Char('j') => self.next(),
+                    KeyCode::Up | KeyCode::Char('k') => self.prev(),
+                    KeyCode::Char('[') => self.select_day(todo, -7),
+                    KeyCode::Char(']') => self.select_day(todo, 7),
+                    _ => {}
+                }
+                return PopupKey::Keep;
+            }
+        };
+        if let Err(e) = self.move_selected(todo, days) {
+            log::error!("Cannot move the task: {}", e);
+        }
+        PopupKey::Keep
+    }
+
+    /// Renders the view over the given area.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `area`: The area of the view.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(format!(
+                "Week of {} (h/l: day, j/k: task, H/L: move task, [/]: week, Esc: close)",
+                self.monday
+            ));
+        let inner = block.inner(area);
+        f.render_widget(Clear, area);
+        f.render_widget(block, area);
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Ratio(1, 7); 7])
+            .split(inner);
+        for (day, tasks) in self.days.iter().enumerate() {
+            let date = self.monday + Duration::days(day as i64);
+            let text: Vec<Line> = tasks
+                .iter()
+                .enumerate()
+                .map(|(index, planned)| {
+                    // Tasks scheduled by the threshold date are dimmed, they are not due yet.
+                    let mut style = match planned.tag {
+                        DateTag::Due => Style::default(),
+                        DateTag::Threshold => Style::default().fg(Color::DarkGray),
+                    };
+                    if day == self.day && index == self.selected {
+                        style = style.add_modifier(Modifier::REVERSED);
+                    }
+                    Line::from(Span::styled(planned.task.subject.clone(), style))
+                })
+                .collect();
+            let border = match (day == self.day, date == self.today) {
+                (true, _) => Style::default().fg(Color::Yellow),
+                (false, true) => Style::default().fg(Color::Green),
+                (false, false) => Style::default().fg(Color::DarkGray),
+            };
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_style(border)
+                .title(date.format("%a %d").to_string());
+            f.render_widget(Paragraph::new(text).block(block), columns[day]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plan() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        todo.new_task("call mom due:2024-07-03")?;
+        todo.new_task("buy milk t:2024-07-07")?;
+        // 2024-07-03 is a Wednesday.
+        let today = NaiveDate::from_ymd_opt(2024, 7, 3).unwrap();
+        let mut view = PlannerView::new(&todo, today);
+        assert_eq!(view.selected().unwrap().task.subject, "call mom");
+
+        // The task follows its date to Thursday.
+        view.move_selected(&mut todo, 1)?;
+        assert_eq!(todo.pending[0].due_date, today.succ_opt());
+        assert_eq!(view.day, 3);
+        assert_eq!(view.selected().unwrap().task.subject, "call mom");
+
+        // Moving past Sunday shows the next week.
+        view.select_day(&todo, 3);
+        assert_eq!(view.selected().unwrap().task.subject, "buy milk");
+        view.move_selected(&mut todo, 1)?;
+        assert_eq!(view.monday, NaiveDate::from_ymd_opt(2024, 7, 8).unwrap());
+        assert_eq!(view.day, 0);
+        assert_eq!(
+            todo.pending[1].threshold_date,
+            NaiveDate::from_ymd_opt(2024, 7, 8)
+        );
+        view.select_day(&todo, -1);
+        assert!(view.selected().is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+use super::{
+    ActivityView, ArchiveResults, ChangelogView, CompareView, DeadlineReport, EffortReport,
+    ForecastReport, HistoryReport, KeybindEditor, PlannerView, QuickAddPopup, SaveReview, Switcher,
+    TrashView,
+};
+use crate::todo::ToDo;
+use crossterm::event::KeyEvent;
+use tui::{backend::Backend, layout::Rect, Frame};
+
+/// What the window does after a popup handled a key.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PopupKey {
+    /// The popup stays open.
+    Keep,
+    /// The popup is closed.
+    Close,
+    /// The popup is closed and the todo list is saved.
+    Save,
+    /// The keybindings of the editor are applied and saved.
+    SaveKeybinds,
+    /// The activity log is read again with the filter.
+    FilterActivity(String),
+    /// The effort report is built again by weeks or by days.
+    ShowEffort { by_week: bool },
+    /// The selected task of the compare view is copied to the other side.
+    CopyCompared,
+    /// The task typed in the quick-add popup is added.
+    AddTask,
+}
+
+/// Represents the popup open over the window, it takes all keys until it is closed.
+pub enum Popup {
+    SaveReview(SaveReview),
+    ArchiveResults(ArchiveResults),
+    Activity(ActivityView),
+    KeybindEditor(KeybindEditor),
+    EffortReport(EffortReport),
+    Changelog(ChangelogView),
+    Compare(CompareView),
+    Planner(PlannerView),
+    Trash(TrashView),
+    HistoryReport(HistoryReport),
+    ForecastReport(ForecastReport),
+    DeadlineReport(DeadlineReport),
+    QuickAdd(QuickAddPopup),
+    Switcher(Switcher),
+}
+
+impl Popup {
+    /// Handles a key pressed while the popup is open.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The pressed key.
+    /// - `todo`: The todo list changed by popups like the planner or the trash.
+    ///
+    /// # Returns
+    ///
+    /// What the window does next.
+    pub fn handle_key(&mut self, key: &KeyEvent, todo: &mut ToDo) -> PopupKey {
+        match self {
+            Self::SaveReview(review) => review.handle_key(key),
+            Self::ArchiveResults(results) => results.handle_key(key),
+            Self::Activity(view) => view.handle_key(key),
+            Self::KeybindEditor(editor) => editor.handle_key(key),
+            Self::EffortReport(report) => report.handle_key(key),
+            Self::Changelog(changelog) => changelog.handle_key(key),
+            Self::Compare(view) => view.handle_key(key),
+            Self::Planner(view) => view.handle_key(key, todo),
+            Self::Trash(view) => view.handle_key(key, todo),
+            Self::HistoryReport(report) => report.handle_key(key),
+            Self::ForecastReport(report) => report.handle_key(key),
+            Self::DeadlineReport(report) => report.handle_key(key, todo),
+            Self::QuickAdd(quick_add) => quick_add.handle_key(key),
+            Self::Switcher(switcher) => switcher.handle_key(key, todo),
+        }
+    }
+
+    /// Renders the popup, large views cover the whole window
+    /// and small popups only the widgets.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `window`: The area of the whole window.
+    /// - `widgets`: The area of the widgets.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, window: Rect, widgets: Rect) {
+        match self {
+            Self::SaveReview(review) => review.render(f, window),
+            Self::ArchiveResults(results) => results.render(f, window),
+            Self::Activity(view) => view.render(f, window),
+            Self::KeybindEditor(editor) => editor.render(f, widgets),
+            Self::EffortReport(report) => report.render(f, window),
+            Self::Changelog(changelog) => changelog.render(f, window),
+            Self::Compare(view) => view.render(f, window),
+            Self::Planner(view) => view.render(f, window),
+            Self::Trash(view) => view.render(f, window),
+            Self::HistoryReport(report) => report.render(f, window),
+            Self::ForecastReport(report) => report.render(f, window),
+            Self::DeadlineReport(report) => report.render(f, widgets),
+            Self::QuickAdd(quick_add) => quick_add.render(f, widgets),
+            Self::Switcher(switcher) => switcher.render(f, widgets),
+        }
+    }
+}
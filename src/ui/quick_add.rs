@@ -0,0 +1,92 @@
+use super::PopupKey;
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+use tui_input::{backend::crossterm::EventHandler, Input};
+
+/// Represents the popup for adding a new task without leaving the focused widget.
+pub struct QuickAddPopup {
+    /// The text of the new task.
+    pub input: Input,
+}
+
+impl QuickAddPopup {
+    /// Creates a new `QuickAddPopup` with the text at its start.
+    ///
+    /// # Parameters
+    ///
+    /// - `initial`: The text filled in, e.g. the selected projects and contexts.
+    pub fn new(initial: &str) -> Self {
+        let initial = match initial.is_empty() {
+            true => String::new(),
+            false => format!(" {initial}"),
+        };
+        // The cursor is placed before the categories, where the subject is typed.
+        let mut input = Input::from(initial);
+        input.handle(tui_input::InputRequest::GoToStart);
+        Self { input }
+    }
+
+    /// Handles a key pressed while the popup is open, Enter adds the task.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> PopupKey {
+        match key.code {
+            KeyCode::Enter => PopupKey::AddTask,
+            KeyCode::Esc => PopupKey::Close,
+            _ => {
+                self.input.handle_event(&Event::Key(*key));
+                PopupKey::Keep
+            }
+        }
+    }
+
+    /// Renders the popup with the input in the middle of the given area.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `area`: The area over which the popup is centered.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let width = (area.width * 2 / 3).max(30).min(area.width);
+        let height = 3.min(area.height);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        let inner = width.max(3) - 3;
+        let scroll = self.input.visual_scroll(inner as usize);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title("Quick add (Enter: add, Esc: cancel)");
+        f.render_widget(Clear, popup);
+        f.render_widget(
+            Paragraph::new(self.input.value())
+                .scroll((0, scroll as u16))
+                .block(block),
+            popup,
+        );
+        let x = popup.x + (self.input.visual_cursor().max(scroll) - scroll) as u16 + 1;
+        f.set_cursor(x, popup.y + 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn initial_text() {
+        let quick_add = QuickAddPopup::new("+work @office");
+        assert_eq!(quick_add.input.value(), " +work @office");
+        assert_eq!(quick_add.input.cursor(), 0);
+        assert_eq!(QuickAddPopup::new("").input.value(), "");
+    }
+}
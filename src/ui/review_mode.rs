@@ -0,0 +1,112 @@
+use super::{EventHandlerUI, UIEvent};
+use crate::todo::{Review, ReviewAction, TaskId};
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// What the window does after the review handled a key.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ReviewKey {
+    /// The decision is applied to the reviewed task.
+    Decide(ReviewAction),
+    /// The reviewed task is deleted, after a confirmation if it is enabled.
+    Delete,
+    /// The due date of the reviewed task is typed in the input.
+    Reschedule,
+    /// The review continues with the next task, the reviewed one was skipped.
+    Next,
+    /// The review ends.
+    Stop,
+    /// The key is not used by the review.
+    Ignore,
+}
+
+/// Represents the review of stale tasks shown one by one in the input.
+pub struct ReviewMode {
+    review: Review,
+}
+
+impl ReviewMode {
+    /// Creates a new `ReviewMode`.
+    ///
+    /// # Parameters
+    ///
+    /// - `review`: The stale tasks to walk through.
+    pub fn new(review: Review) -> Self {
+        Self { review }
+    }
+
+    /// Gets the task being reviewed or `None` if all tasks were walked through.
+    pub fn current(&self) -> Option<&TaskId> {
+        self.review.current()
+    }
+
+    /// Continues with the next task.
+    pub fn next(&mut self) {
+        self.review.next()
+    }
+
+    /// Handles a key pressed during the review, an uppercase letter sets the priority.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The pressed key.
+    /// - `keybind`: The keybindings of the review.
+    ///
+    /// # Returns
+    ///
+    /// What the window does next.
+    pub fn handle_key(&mut self, key: &KeyEvent, keybind: &EventHandlerUI) -> ReviewKey {
+        match (keybind.get_event(&key.code), key.code) {
+            (UIEvent::Select, _) => ReviewKey::Decide(ReviewAction::Keep),
+            (UIEvent::RemoveItem, _) => ReviewKey::Delete,
+            (UIEvent::PriorityClear, _) => ReviewKey::Decide(ReviewAction::Priority(None)),
+            (UIEvent::DueMode, _) => ReviewKey::Reschedule,
+            (UIEvent::ListDown, _) => {
+                self.review.next();
+                ReviewKey::Next
+            }
+            (_, KeyCode::Char(c)) if c.is_ascii_uppercase() => {
+                ReviewKey::Decide(ReviewAction::Priority(Some(c)))
+            }
+            (_, KeyCode::Esc) => ReviewKey::Stop,
+            _ => ReviewKey::Ignore,
+        }
+    }
+
+    /// Gets the title of the input with the position in the review and the keys.
+    pub fn title(&self) -> String {
+        let (position, len) = self.review.position();
+        format!(
+            "Review {position}/{len} (Enter: keep, A-Z: priority, -: no priority, \
+             s: reschedule, d: delete, Down: skip, Esc: stop)"
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::Config, todo::ToDo};
+    use crossterm::event::KeyModifiers;
+
+    #[test]
+    fn keys() {
+        let keybind = Config::default().get_review_keybind();
+        let mut review = ReviewMode::new(Review::new(&ToDo::default(), 0));
+        let mut press =
+            |code| review.handle_key(&KeyEvent::new(code, KeyModifiers::NONE), &keybind);
+        assert_eq!(press(KeyCode::Enter), ReviewKey::Decide(ReviewAction::Keep));
+        assert_eq!(
+            press(KeyCode::Char('B')),
+            ReviewKey::Decide(ReviewAction::Priority(Some('B')))
+        );
+        assert_eq!(
+            press(KeyCode::Char('-')),
+            ReviewKey::Decide(ReviewAction::Priority(None))
+        );
+        assert_eq!(press(KeyCode::Char('d')), ReviewKey::Delete);
+        assert_eq!(press(KeyCode::Char('s')), ReviewKey::Reschedule);
+        assert_eq!(press(KeyCode::Down), ReviewKey::Next);
+        assert_eq!(press(KeyCode::Esc), ReviewKey::Stop);
+        assert_eq!(press(KeyCode::Char('b')), ReviewKey::Ignore);
+    }
+}
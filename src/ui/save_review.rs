@@ -0,0 +1,92 @@
+use super::PopupKey;
+use crossterm::event::{KeyCode, KeyEvent};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Represents the review of changes shown in a popup before the todo list is saved.
+pub struct SaveReview {
+    lines: Vec<String>,
+    scroll: usize,
+}
+
+impl SaveReview {
+    /// Creates a new `SaveReview`.
+    ///
+    /// # Parameters
+    ///
+    /// - `diff`: The unified diff between the files and the saved todo list.
+    pub fn new(diff: &str) -> Self {
+        Self {
+            lines: diff.lines().map(String::from).collect(),
+            scroll: 0,
+        }
+    }
+
+    /// Scrolls the diff down by the number of lines.
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.scroll = (self.scroll + lines).min(self.lines.len().saturating_sub(1));
+    }
+
+    /// Scrolls the diff up by the number of lines.
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    /// Handles a key pressed while the popup is open, Enter saves the todo list.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> PopupKey {
+        match key.code {
+            KeyCode::Enter => return PopupKey::Save,
+            KeyCode::Esc => return PopupKey::Close,
+            KeyCode::Down | KeyCode::Char('j') => self.scroll_down(1),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll_up(1),
+            KeyCode::PageDown => self.scroll_down(10),
+            KeyCode::PageUp => self.scroll_up(10),
+            _ => {}
+        }
+        PopupKey::Keep
+    }
+
+    /// Renders the diff in a popup in the middle of the given area.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `area`: The area over which the popup is centered.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let width = (area.width * 4 / 5).max(20).min(area.width);
+        let height = (area.height * 4 / 5).max(5).min(area.height);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        let text: Vec<Line> = self
+            .lines
+            .iter()
+            .skip(self.scroll)
+            .map(|line| {
+                let color = match line.chars().next() {
+                    Some('+') => Color::Green,
+                    Some('-') => Color::Red,
+                    Some('@') => Color::Cyan,
+                    _ => Color::Reset,
+                };
+                Line::from(Span::styled(line.as_str(), Style::default().fg(color)))
+            })
+            .collect();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title("Review changes (Enter: save, Esc: cancel, j/k: scroll)");
+        f.render_widget(Clear, popup);
+        f.render_widget(Paragraph::new(text).block(block), popup);
+    }
+}
@@ -0,0 +1,205 @@
+use super::Crumb;
+use crate::{
+    layout::widget::widget_type::WidgetType,
+    status::{self, StatusLevel, StatusMessage},
+    todo::{ToDo, ToDoData},
+};
+use std::time::Duration;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+/// Separator drawn between the parts of the status bar.
+const SEPARATOR: &str = " │ ";
+
+/// How long a posted message is shown.
+const MESSAGE_DURATION: Duration = Duration::from_secs(5);
+
+/// Represents the line at the bottom of the window with the focused widget, the filters
+/// and the sorting, the number of shown tasks, whether the todo list has unsaved changes
+/// and the last message posted to the [`status`] bus.
+#[derive(Default)]
+pub struct StatusBar {
+    message: Option<StatusMessage>,
+}
+
+impl StatusBar {
+    /// Takes the messages posted to the status bus, the last one is shown. A message
+    /// shown long enough is hidden.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the shown message has changed and the UI needs to be redrawn.
+    pub fn poll(&mut self) -> bool {
+        self.show(status::take())
+    }
+
+    /// Shows the last of the messages or hides the message shown long enough.
+    ///
+    /// # Parameters
+    ///
+    /// - `messages`: The messages posted since the last call, the oldest first.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the shown message has changed.
+    fn show(&mut self, mut messages: Vec<StatusMessage>) -> bool {
+        if let Some(message) = messages.pop() {
+            self.message = Some(message);
+            return true;
+        }
+        let expired = self
+            .message
+            .as_ref()
+            .is_some_and(|message| message.posted.elapsed() >= MESSAGE_DURATION);
+        if expired {
+            self.message = None;
+        }
+        expired
+    }
+
+    /// Gets the message shown in the status bar.
+    #[cfg(test)]
+    pub fn message(&self) -> Option<&StatusMessage> {
+        self.message.as_ref()
+    }
+
+    /// Gets the parts of the status bar describing the view.
+    ///
+    /// # Parameters
+    ///
+    /// - `widget`: The type of the focused widget.
+    /// - `todo`: The todo list.
+    pub fn parts(&self, widget: WidgetType, todo: &ToDo) -> Vec<String> {
+        let data = match widget {
+            WidgetType::Done => ToDoData::Done,
+            _ => ToDoData::Pending,
+        };
+        let mut parts = vec![widget.to_string()];
+        let state = todo.get_state();
+        let mut filters: Vec<String> = state
+            .categories()
+            .flat_map(|category| {
+                state
+                    .get_category(category)
+                    .iter()
+                    .map(move |(name, state)| {
+                        Crumb::Filter {
+                            category,
+                            name: name.clone(),
+                            state: *state,
+                        }
+                        .label()
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        if let Some(query) = todo.filter_bar() {
+            filters.push(format!("/{query}"));
+        }
+        if !filters.is_empty() {
+            parts.push(filters.join(" "));
+        }
+        parts.push(Crumb::Sort(data, todo.get_sort(data)).label());
+        let total = data.get_data(todo).len();
+        parts.push(format!("{}/{total} tasks", total - todo.hidden(data)));
+        parts
+    }
+
+    /// Renders the status bar.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `area`: The line of the status bar.
+    /// - `widget`: The type of the focused widget.
+    /// - `todo`: The todo list.
+    pub fn render<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        area: Rect,
+        widget: WidgetType,
+        todo: &ToDo,
+    ) {
+        let gray = Style::default().fg(Color::DarkGray);
+        let mut spans = Vec::new();
+        if todo.is_dirty() {
+            spans.push(Span::styled(
+                "[+] ",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        for (i, part) in self.parts(widget, todo).into_iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::styled(SEPARATOR, gray));
+            }
+            let style = match i {
+                0 => Style::default().add_modifier(Modifier::BOLD),
+                _ => Style::default(),
+            };
+            spans.push(Span::styled(part, style));
+        }
+        if let Some(message) = &self.message {
+            let style = match message.level {
+                StatusLevel::Info => Style::default().fg(Color::Green),
+                StatusLevel::Error => Style::default().fg(Color::Red),
+            };
+            spans.push(Span::styled(SEPARATOR, gray));
+            spans.push(Span::styled(message.text.clone(), style));
+        }
+        f.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::todo::{FilterState, TaskSort, ToDoCategory};
+
+    #[test]
+    fn parts() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        for task in ["call mom +family", "buy milk", "x 2023-05-01 pay rent"] {
+            todo.new_task(task)?;
+        }
+        let bar = StatusBar::default();
+        assert_eq!(
+            bar.parts(WidgetType::List, &todo),
+            ["List", "sort: none", "2/2 tasks"]
+        );
+
+        todo.toggle_filter(ToDoCategory::Projects, "family", FilterState::Select);
+        todo.set_sort(ToDoData::Pending, TaskSort::Priority);
+        assert_eq!(
+            bar.parts(WidgetType::Project, &todo),
+            ["Projects", "+family", "sort: priority", "1/2 tasks"]
+        );
+        assert_eq!(bar.parts(WidgetType::Done, &todo)[3], "0/1 tasks");
+        Ok(())
+    }
+
+    #[test]
+    fn messages() {
+        let mut bar = StatusBar::default();
+        assert!(!bar.show(Vec::new()));
+        assert!(bar.show(vec![
+            StatusMessage::new(StatusLevel::Info, "Task added"),
+            StatusMessage::new(StatusLevel::Error, "Cannot save"),
+        ]));
+        assert_eq!(bar.message().unwrap().text, "Cannot save");
+        assert!(!bar.show(Vec::new()));
+
+        let mut message = StatusMessage::new(StatusLevel::Info, "Task added");
+        message.posted -= MESSAGE_DURATION;
+        bar.show(vec![message]);
+        assert!(bar.show(Vec::new()));
+        assert!(bar.message().is_none());
+    }
+}
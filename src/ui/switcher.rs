@@ -0,0 +1,317 @@
+use super::PopupKey;
+use crate::todo::{Action, FilterState, TaskQuery, ToDo, ToDoCategory};
+use crossterm::event::{KeyCode, KeyEvent};
+use std::{collections::HashMap, str::FromStr};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// An item of the [`Switcher`], a category or a saved filter.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SwitcherEntry {
+    /// The text the entry is matched by, e.g. `+work` or `Work: +work -@phone`.
+    pub label: String,
+    /// The filters applied when the entry is selected.
+    pub filters: Vec<(ToDoCategory, String, FilterState)>,
+}
+
+/// Represents the popup for quick jumps to a category or a saved filter.
+///
+/// The entries are matched by the typed text as a fuzzy pattern, the characters
+/// of the pattern have to appear in the label in the same order.
+pub struct Switcher {
+    entries: Vec<SwitcherEntry>,
+    pattern: String,
+    matches: Vec<usize>,
+    selected: usize,
+}
+
+impl Switcher {
+    /// Creates a new `Switcher` listing all entries.
+    ///
+    /// # Parameters
+    ///
+    /// - `entries`: The categories and saved filters to switch to.
+    pub fn new(entries: Vec<SwitcherEntry>) -> Self {
+        let mut switcher = Self {
+            entries,
+            pattern: String::new(),
+            matches: Vec::new(),
+            selected: 0,
+        };
+        switcher.update_matches();
+        switcher
+    }
+
+    /// Creates a new `Switcher` with the projects, contexts and hashtags of the todo list
+    /// followed by the saved filters.
+    ///
+    /// # Parameters
+    ///
+    /// - `todo`: The todo list with the categories.
+    /// - `queries`: Saved filters by their names, the category terms of the queries are applied.
+    pub fn build(todo: &ToDo, queries: &HashMap<String, String>) -> Self {
+        let mut entries = Vec::new();
+        for (category, prefix) in [
+            (ToDoCategory::Projects, "+"),
+            (ToDoCategory::Contexts, "@"),
+            (ToDoCategory::Hashtags, "#"),
+        ] {
+            entries.extend(todo.get_categories(category).vec.iter().map(|(name, _)| {
+                SwitcherEntry {
+                    label: format!("{prefix}{name}"),
+                    filters: vec![(category, name.to_string(), FilterState::Select)],
+                }
+            }));
+        }
+        let mut queries: Vec<_> = queries
+            .iter()
+            .filter_map(|(name, query)| {
+                let filters = TaskQuery::from_str(query).ok()?.filters();
+                Some(SwitcherEntry {
+                    label: format!("{name}: {query}"),
+                    filters,
+                })
+            })
+            .collect();
+        queries.sort_by(|a, b| a.label.cmp(&b.label));
+        entries.extend(queries);
+        Self::new(entries)
+    }
+
+    /// Gets the typed pattern.
+    #[cfg(test)]
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Appends the character to the pattern.
+    pub fn push(&mut self, c: char) {
+        self.pattern.push(c);
+        self.update_matches();
+    }
+
+    /// Removes the last character of the pattern.
+    pub fn pop(&mut self) {
+        self.pattern.pop();
+        self.update_matches();
+    }
+
+    /// Selects the next matching entry.
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.matches.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Selects the previous matching entry.
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Gets the selected entry, `None` if no entry matches the pattern.
+    pub fn selected(&self) -> Option<&SwitcherEntry> {
+        self.matches
+            .get(self.selected)
+            .map(|index| &self.entries[*index])
+    }
+
+    /// Matches the entries by the pattern, the best matches first.
+    fn update_matches(&mut self) {
+        let mut scores: Vec<(usize, usize)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, entry)| Some((i, fuzzy_score(&self.pattern, &entry.label)?)))
+            .collect();
+        scores.sort_by(|(a, a_score), (b, b_score)| b_score.cmp(a_score).then(a.cmp(b)));
+        self.matches = scores.into_iter().map(|(i, _)| i).collect();
+        self.selected = 0;
+    }
+
+    /// Handles a key pressed while the switcher is open, Enter applies the filters of the selected entry.
+    pub fn handle_key(&mut self, key: &KeyEvent, todo: &mut ToDo) -> PopupKey {
+        match key.code {
+            KeyCode::Esc => return PopupKey::Close,
+            KeyCode::Down => self.select_next(),
+            KeyCode::Up => self.select_prev(),
+            KeyCode::Backspace => self.pop(),
+            KeyCode::Char(c) => self.push(c),
+            KeyCode::Enter => {
+                if let Some(entry) = self.selected() {
+                    let action = Action::SetFilters {
+                        filters: entry.filters.clone(),
+                    };
+                    if let Err(e) = todo.apply(action) {
+                        log::error!("Cannot apply the filters: {}", e);
+                    }
+                    return PopupKey::Close;
+                }
+            }
+            _ => {}
+        }
+        PopupKey::Keep
+    }
+
+    /// Renders the switcher in a popup in the middle of the given area.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `area`: The area over which the popup is centered.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let width = (area.width * 3 / 5).max(20).min(area.width);
+        let height = (self.matches.len() as u16 + 4)
+            .min(area.height * 4 / 5)
+            .max(5)
+            .min(area.height);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        let mut text = vec![
+            Line::from(vec![
+                Span::styled("> ", Style::default().fg(Color::DarkGray)),
+                Span::raw(self.pattern.as_str()),
+            ]),
+            Line::from(""),
+        ];
+        if self.matches.is_empty() {
+            text.push(Line::from(Span::styled(
+                "No match",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        // Keep the selected entry visible.
+        let visible = height.saturating_sub(4).max(1) as usize;
+        let skip = (self.selected + 1).saturating_sub(visible);
+        text.extend(
+            self.matches
+                .iter()
+                .enumerate()
+                .skip(skip)
+                .take(visible)
+                .map(|(i, index)| {
+                    let style = match i == self.selected {
+                        true => Style::default().add_modifier(Modifier::REVERSED),
+                        false => Style::default(),
+                    };
+                    Line::from(Span::styled(self.entries[*index].label.as_str(), style))
+                }),
+        );
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title("Switch to (type to filter, Enter: apply, Esc: close)");
+        f.render_widget(Clear, popup);
+        f.render_widget(Paragraph::new(text).block(block), popup);
+    }
+}
+
+/// Scores how well the text matches the fuzzy pattern, the case is ignored.
+///
+/// Every character of the pattern has to appear in the text in the same order.
+/// Characters following the previous match or starting a word score more.
+///
+/// # Returns
+///
+/// The score of the match or `None` if the text does not match the pattern.
+fn fuzzy_score(pattern: &str, text: &str) -> Option<usize> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous = None;
+    for c in pattern
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+    {
+        let index = position + text[position..].iter().position(|t| *t == c)?;
+        score += 1;
+        if previous.is_some_and(|previous| previous + 1 == index) {
+            score += 2;
+        }
+        if index == 0 || !text[index - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous = Some(index);
+        position = index + 1;
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(switcher: &Switcher) -> Vec<&str> {
+        switcher
+            .matches
+            .iter()
+            .map(|index| switcher.entries[*index].label.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn fuzzy() {
+        assert_eq!(fuzzy_score("", "+work"), Some(0));
+        assert_eq!(fuzzy_score("wrk", "+work"), Some(8));
+        assert_eq!(fuzzy_score("WO", "+work"), Some(7));
+        assert_eq!(fuzzy_score("kw", "+work"), None);
+    }
+
+    #[test]
+    fn switch() {
+        let mut todo = ToDo::default();
+        todo.new_task("write the report +work @office").unwrap();
+        todo.new_task("call mom +family @phone #weekly").unwrap();
+        let queries = HashMap::from([(String::from("Work"), String::from("+work -@phone report"))]);
+        let mut switcher = Switcher::build(&todo, &queries);
+        assert_eq!(
+            labels(&switcher),
+            vec![
+                "+family",
+                "+work",
+                "@office",
+                "@phone",
+                "#weekly",
+                "Work: +work -@phone report"
+            ]
+        );
+
+        switcher.push('w');
+        switcher.push('k');
+        assert_eq!(
+            labels(&switcher),
+            vec!["+work", "#weekly", "Work: +work -@phone report"]
+        );
+        switcher.select_prev();
+        assert_eq!(switcher.selected().unwrap().label, "+work");
+        switcher.select_next();
+        switcher.select_next();
+        switcher.select_next();
+        assert_eq!(
+            switcher.selected().unwrap().label,
+            "Work: +work -@phone report"
+        );
+        switcher.pop();
+        switcher.push('x');
+        assert!(switcher.selected().is_none());
+        switcher.pop();
+        switcher.pop();
+        assert_eq!(switcher.pattern(), "");
+
+        let filters = switcher.entries[5].filters.clone();
+        todo.set_filters(filters);
+        assert_eq!(todo.len(crate::todo::ToDoData::Pending), 1);
+        assert_eq!(todo.filter_stack_len(), 1);
+    }
+}
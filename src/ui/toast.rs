@@ -0,0 +1,62 @@
+use std::time::{Duration, Instant};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Represents a short message shown in the corner of the layout for a while,
+/// it does not take the focus and disappears by itself.
+pub struct Toast {
+    title: String,
+    lines: Vec<String>,
+    until: Instant,
+}
+
+impl Toast {
+    /// Creates a new `Toast`.
+    ///
+    /// # Parameters
+    ///
+    /// - `title`: The title of the toast.
+    /// - `lines`: The lines of the message.
+    /// - `duration`: How long the toast is shown.
+    pub fn new(title: impl Into<String>, lines: Vec<String>, duration: Duration) -> Self {
+        Self {
+            title: title.into(),
+            lines,
+            until: Instant::now() + duration,
+        }
+    }
+
+    /// Checks whether the toast was shown long enough.
+    pub fn expired(&self) -> bool {
+        Instant::now() >= self.until
+    }
+
+    /// Renders the toast in the top right corner of the given area.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `area`: The area in which corner the toast is shown.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let width = (area.width * 2 / 5).max(20).min(area.width);
+        let height = (self.lines.len() as u16 + 2).min(area.height);
+        let popup = Rect::new(area.x + area.width - width, area.y, width, height);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(self.title.as_str());
+        let text: Vec<Line> = self.lines.iter().map(|l| Line::from(l.as_str())).collect();
+        f.render_widget(Clear, popup);
+        f.render_widget(
+            Paragraph::new(text).block(block).wrap(Wrap { trim: true }),
+            popup,
+        );
+    }
+}
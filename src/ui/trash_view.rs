@@ -0,0 +1,172 @@
+use super::PopupKey;
+use crate::todo::{Action, TaskId, ToDo, ToDoData, TrashedTask};
+use crossterm::event::{KeyCode, KeyEvent};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Represents the popup listing the deleted tasks in the trash, the selected task
+/// can be restored to the list it was deleted from.
+pub struct TrashView {
+    /// The tasks in the trash with their identities, the most recently deleted first.
+    tasks: Vec<(TaskId, TrashedTask)>,
+    selected: usize,
+}
+
+impl TrashView {
+    /// Creates a new `TrashView` with the most recently deleted task selected.
+    ///
+    /// # Parameters
+    ///
+    /// - `todo`: The todo list with the trash.
+    pub fn new(todo: &ToDo) -> Self {
+        let mut view = Self {
+            tasks: Vec::new(),
+            selected: 0,
+        };
+        view.reload(todo);
+        view
+    }
+
+    /// Loads the tasks of the trash again, e.g. after a task was restored.
+    pub fn reload(&mut self, todo: &ToDo) {
+        self.tasks = todo
+            .trash()
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(index, trashed)| (TaskId::new(index, &trashed.task), trashed.clone()))
+            .collect();
+        self.selected = self.selected.min(self.tasks.len().saturating_sub(1));
+    }
+
+    /// Selects the next task.
+    pub fn next(&mut self) {
+        if self.selected + 1 < self.tasks.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Selects the previous task.
+    pub fn prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Gets the identity of the selected task in the trash.
+    pub fn selected(&self) -> Option<&TaskId> {
+        self.tasks.get(self.selected).map(|(id, _)| id)
+    }
+
+    /// Handles a key pressed while the popup is open, Enter restores the selected task.
+    pub fn handle_key(&mut self, key: &KeyEvent, todo: &mut ToDo) -> PopupKey {
+        match key.code {
+            KeyCode::Esc => return PopupKey::Close,
+            KeyCode::Down | KeyCode::Char('j') => self.next(),
+            KeyCode::Up | KeyCode::Char('k') => self.prev(),
+            KeyCode::Enter | KeyCode::Char('r') => {
+                if let Some(task) = self.selected().cloned() {
+                    if let Err(e) = todo.apply(Action::RestoreTask { task }) {
+                        log::error!("Cannot restore the task: {}", e);
+                    }
+                    self.reload(todo);
+                }
+            }
+            _ => {}
+        }
+        PopupKey::Keep
+    }
+
+    /// Renders the trash in a popup in the middle of the given area.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: A mutable reference to the TUI frame used for rendering.
+    /// - `area`: The area over which the popup is centered.
+    pub fn render<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
+        let width = (area.width * 4 / 5).max(20).min(area.width);
+        let height = (self.tasks.len() as u16 + 4)
+            .min(area.height * 4 / 5)
+            .max(5)
+            .min(area.height);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        let mut text: Vec<Line> = Vec::new();
+        if self.tasks.is_empty() {
+            text.push(Line::from(Span::styled(
+                "The trash is empty",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        // Keep the selected task visible.
+        let visible = height.saturating_sub(4).max(1) as usize;
+        let skip = (self.selected + 1).saturating_sub(visible);
+        text.extend(self.tasks.iter().enumerate().skip(skip).take(visible).map(
+            |(i, (_, trashed))| {
+                let style = match i == self.selected {
+                    true => Style::default().add_modifier(Modifier::REVERSED),
+                    false => Style::default(),
+                };
+                let list = match trashed.data {
+                    ToDoData::Pending => "pending",
+                    ToDoData::Done => "done",
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!("{} ", trashed.deleted.format("%Y-%m-%d %H:%M")),
+                        style.fg(Color::DarkGray),
+                    ),
+                    Span::styled(format!("{list:<8}"), style.fg(Color::Cyan)),
+                    Span::styled(trashed.task.to_string(), style),
+                ])
+            },
+        ));
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(
+            "Enter/r: restore, j/k: select, Esc: close",
+            Style::default().fg(Color::DarkGray),
+        )));
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(format!("Trash ({} deleted tasks)", self.tasks.len()));
+        f.render_widget(Clear, popup);
+        f.render_widget(Paragraph::new(text).block(block), popup);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::todo::Action;
+
+    #[test]
+    fn select() -> Result<(), todo_txt::Error> {
+        let mut todo = ToDo::default();
+        for task in ["call mom", "buy milk", "pay rent"] {
+            todo.new_task(task)?;
+        }
+        todo.remove_task(ToDoData::Pending, 0);
+        todo.remove_task(ToDoData::Pending, 0);
+        let mut view = TrashView::new(&todo);
+        assert!(view.selected().unwrap().task.ends_with("buy milk"));
+        view.next();
+        view.next();
+        assert_eq!(view.selected().unwrap().index, 0);
+        assert!(view.selected().unwrap().task.ends_with("call mom"));
+
+        todo.apply(Action::FlushTrash)?;
+        view.reload(&todo);
+        assert!(view.selected().is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use crate::inbox::Inbox;
+use crossterm::event::{KeyCode, KeyEvent};
+use std::io;
+
+/// What the window does after the triage handled a key.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TriageKey {
+    /// The item in the input is added to the todo list.
+    Add,
+    /// The triage continues with the next item, the current one stays in the inbox.
+    Next,
+    /// The triage ends.
+    Stop,
+    /// The key edits the item in the input.
+    Input,
+}
+
+/// Represents the triage of the inbox, its items are shown one by one in the input.
+pub struct TriageMode {
+    inbox: Inbox,
+}
+
+impl TriageMode {
+    /// Creates a new `TriageMode`.
+    ///
+    /// # Parameters
+    ///
+    /// - `inbox`: The inbox with the items to triage.
+    pub fn new(inbox: Inbox) -> Self {
+        Self { inbox }
+    }
+
+    /// Gets the item being triaged or `None` if all items were walked through.
+    pub fn current(&self) -> Option<&str> {
+        self.inbox.current()
+    }
+
+    /// Removes the current item from the inbox, it was added to the todo list.
+    ///
+    /// # Parameters
+    ///
+    /// - `dry_run`: Whether the inbox file is left as it is, the item is only skipped.
+    pub fn take(&mut self, dry_run: bool) -> io::Result<()> {
+        match dry_run {
+            true => {
+                self.inbox.skip();
+                Ok(())
+            }
+            false => self.inbox.take(),
+        }
+    }
+
+    /// Handles a key pressed during the triage, Enter adds the item and Down skips it.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The pressed key.
+    ///
+    /// # Returns
+    ///
+    /// What the window does next.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> TriageKey {
+        match key.code {
+            KeyCode::Enter => TriageKey::Add,
+            KeyCode::Down => {
+                self.inbox.skip();
+                TriageKey::Next
+            }
+            KeyCode::Esc => TriageKey::Stop,
+            _ => TriageKey::Input,
+        }
+    }
+
+    /// Gets the title of the input with the position in the inbox and the keys.
+    pub fn title(&self) -> String {
+        let (position, len) = self.inbox.position();
+        format!("Triage {position}/{len} (Enter: move to list, Down: skip, Esc: stop)")
+    }
+}
@@ -18,7 +18,45 @@ pub enum UIEvent {
     MoveUp,
     MoveDown,
     InsertMode,
+    InsertScratch,
     EditMode,
+    EditItem,
+    CopyView,
+    ExportMode,
+    CommandMode,
+    TriageMode,
+    ReviewMode,
+    Reveal,
+    ToggleFilters,
+    ToggleFilterMode,
+    ClearFilters,
+    PushFilters,
+    PopFilters,
+    ToggleSortOnInsert,
+    CycleMyTasks,
+    ReopenLast,
+    ShowActivity,
+    SplitHorizontal,
+    SplitVertical,
+    CloseSplit,
+    BreadcrumbBack,
+    OpenSwitcher,
+    QuickAdd,
+    ToggleRawLines,
+    EditKeybinds,
+    ShowDeadlines,
+    ShowEffortReport,
+    ShowForecast,
+    ShowHistory,
+    CompareMode,
+    ShowPlanner,
+    ShowTrash,
+    ShowChangelog,
+    Archive,
+    SearchMode,
+    FilterMode,
+    NextProfile,
+    PrevProfile,
 
     ListDown, // Widget list
     ListUp,
@@ -26,10 +64,38 @@ pub enum UIEvent {
     ListLast,
     SwapUpItem, // State list
     SwapDownItem,
+    MoveItemTop,
+    MoveItemBottom,
     RemoveItem,
+    DuplicateItem,
     MoveItem,
+    CyclePriority,
+    PriorityUp,
+    PriorityDown,
+    PriorityClear,
+    DeferItem,
+    DeferWeek,
+    ToggleTimer,
+    ToggleMark,
+    VisualMode,
+    ClearMarks,
+    DueNextMonday,
+    DueMode,
+    SortPriority,
+    SortDue,
+    SortSubject,
+    SortProjects,
+    CycleSort,
+    FilterProject,
+    FilterContext,
+    ShowArchive,
+    NextPage,
+    PrevPage,
+    NextMatch,
+    PrevMatch,
     Select, // State categories + State list
     Remove, // State categories
+    ToggleCollapse,
     // State preview
     None, // without bind
 }
@@ -48,7 +114,45 @@ impl FromStr for UIEvent {
             "MoveUp" => MoveUp,
             "MoveDown" => MoveDown,
             "InsertMode" => InsertMode,
+            "InsertScratch" => InsertScratch,
             "EditMode" => EditMode,
+            "EditItem" => EditItem,
+            "CopyView" => CopyView,
+            "ExportMode" => ExportMode,
+            "CommandMode" => CommandMode,
+            "TriageMode" => TriageMode,
+            "ReviewMode" => ReviewMode,
+            "Reveal" => Reveal,
+            "ToggleFilters" => ToggleFilters,
+            "ToggleFilterMode" => ToggleFilterMode,
+            "ClearFilters" => ClearFilters,
+            "PushFilters" => PushFilters,
+            "PopFilters" => PopFilters,
+            "ToggleSortOnInsert" => ToggleSortOnInsert,
+            "CycleMyTasks" => CycleMyTasks,
+            "ReopenLast" => ReopenLast,
+            "ShowActivity" => ShowActivity,
+            "SplitHorizontal" => SplitHorizontal,
+            "SplitVertical" => SplitVertical,
+            "CloseSplit" => CloseSplit,
+            "BreadcrumbBack" => BreadcrumbBack,
+            "OpenSwitcher" => OpenSwitcher,
+            "QuickAdd" => QuickAdd,
+            "ToggleRawLines" => ToggleRawLines,
+            "EditKeybinds" => EditKeybinds,
+            "ShowDeadlines" => ShowDeadlines,
+            "ShowEffortReport" => ShowEffortReport,
+            "ShowForecast" => ShowForecast,
+            "ShowHistory" => ShowHistory,
+            "CompareMode" => CompareMode,
+            "ShowPlanner" => ShowPlanner,
+            "ShowTrash" => ShowTrash,
+            "ShowChangelog" => ShowChangelog,
+            "Archive" => Archive,
+            "SearchMode" => SearchMode,
+            "FilterMode" => FilterMode,
+            "NextProfile" => NextProfile,
+            "PrevProfile" => PrevProfile,
 
             "ListDown" => ListDown,
             "ListUp" => ListUp,
@@ -56,9 +160,37 @@ impl FromStr for UIEvent {
             "ListLast" => ListLast,
             "SwapUpItem" => SwapUpItem,
             "SwapDownItem" => SwapDownItem,
+            "MoveItemTop" => MoveItemTop,
+            "MoveItemBottom" => MoveItemBottom,
             "RemoveItem" => RemoveItem,
+            "DuplicateItem" => DuplicateItem,
             "MoveItem" => MoveItem,
+            "CyclePriority" => CyclePriority,
+            "PriorityUp" => PriorityUp,
+            "PriorityDown" => PriorityDown,
+            "PriorityClear" => PriorityClear,
+            "DeferItem" => DeferItem,
+            "DeferWeek" => DeferWeek,
+            "ToggleTimer" => ToggleTimer,
+            "ToggleMark" => ToggleMark,
+            "VisualMode" => VisualMode,
+            "ClearMarks" => ClearMarks,
+            "DueNextMonday" => DueNextMonday,
+            "DueMode" => DueMode,
+            "SortPriority" => SortPriority,
+            "SortDue" => SortDue,
+            "SortSubject" => SortSubject,
+            "SortProjects" => SortProjects,
+            "CycleSort" => CycleSort,
+            "FilterProject" => FilterProject,
+            "FilterContext" => FilterContext,
+            "ShowArchive" => ShowArchive,
+            "NextPage" => NextPage,
+            "PrevPage" => PrevPage,
+            "NextMatch" => NextMatch,
+            "PrevMatch" => PrevMatch,
             "Select" => Select,
+            "ToggleCollapse" => ToggleCollapse,
             "None" => None,
 
             _ => todo!(), // Error TODO
@@ -145,6 +277,34 @@ impl EventHandlerUI {
         }
     }
 
+    /// Gets all key bindings ordered by their keys.
+    pub fn bindings(&self) -> Vec<(KeyCode, UIEvent)> {
+        self.events
+            .iter()
+            .map(|entry| (entry.key, entry.event))
+            .collect()
+    }
+
+    /// Creates the key bindings with the overriding bindings added, a key bound in
+    /// both keeps only its overriding binding.
+    ///
+    /// # Arguments
+    ///
+    /// * `overrides` - The key bindings taking precedence, e.g. of a single widget.
+    ///
+    /// # Returns
+    ///
+    /// The merged key bindings.
+    pub fn merge(&self, overrides: &EventHandlerUI) -> Self {
+        let mut events = overrides.bindings();
+        events.extend(
+            self.bindings()
+                .into_iter()
+                .filter(|(key, _)| !overrides.events.iter().any(|entry| entry == key)),
+        );
+        Self::new(&events)
+    }
+
     /// Compare two key codes for ordering purposes.
     ///
     /// # Arguments
@@ -6,29 +6,96 @@ use serde::{Deserialize, Serialize};
 
 use crate::error::{ToDoIoError, ToDoRes};
 use crate::layout::widget::widget_type::WidgetType;
+use crate::layout::widget::{ListPosition, RCToDo};
 use crate::layout::Layout;
-use crate::todo::{ToDo, ToDoState};
+use crate::todo::{TaskId, TaskSort, ToDo, ToDoData, ToDoState};
 
+/// The state of the UI saved on exit and restored on the next launch: the focused
+/// widget, the filters, the sorting, the active task and the positions of the lists.
 #[derive(Default, Serialize, Deserialize)]
 pub struct UIState {
     pub active: WidgetType,
     pub todo_state: ToDoState,
+    /// The active task, the saved index of it is not valid if the todo file has changed.
+    #[serde(default)]
+    pub active_task: Option<String>,
+    #[serde(default)]
+    pub pending_sort: Option<TaskSort>,
+    #[serde(default)]
+    pub done_sort: Option<TaskSort>,
+    #[serde(default)]
+    pub positions: Vec<ListPosition>,
 }
 
 impl UIState {
-    pub fn new(layout: &Layout, todo: &ToDo) -> Self {
+    pub fn new(layout: &Layout, data: &RCToDo) -> Self {
+        // The widgets lock the todo list to get their positions.
+        let positions = layout.positions();
+        let todo = data.lock().unwrap();
         Self {
             active: layout.get_active_widget(),
             todo_state: todo.get_state().clone(),
+            active_task: todo.get_active().map(|task| task.to_string()),
+            pending_sort: Some(todo.get_sort(ToDoData::Pending)),
+            done_sort: Some(todo.get_sort(ToDoData::Done)),
+            positions,
         }
     }
 
+    /// Applies the filters, the sorting and the active task to the loaded todo list.
+    /// The active task is looked up by its content if the todo file has changed
+    /// and it is dropped if it is not there anymore.
+    ///
+    /// # Arguments
+    ///
+    /// * `todo` - The todo list loaded from the todo file.
+    pub fn restore_todo(&self, todo: &mut ToDo) {
+        let mut state = self.todo_state.clone();
+        state.active = state.active.and_then(|(data, index)| {
+            let found = match &self.active_task {
+                Some(task) => todo.find_task(
+                    data,
+                    &TaskId {
+                        index,
+                        task: task.clone(),
+                    },
+                ),
+                None => (index < data.get_data(todo).len()).then_some(index),
+            };
+            found.map(|index| (data, index))
+        });
+        todo.update_state(state);
+        if let Some(sort) = self.pending_sort {
+            todo.set_sort(ToDoData::Pending, sort);
+        }
+        if let Some(sort) = self.done_sort {
+            todo.set_sort(ToDoData::Done, sort);
+        }
+    }
+
+    /// Focuses the saved widget and scrolls the lists to their saved positions.
+    ///
+    /// # Arguments
+    ///
+    /// * `layout` - The layout created with the todo list the state was restored to.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the layout does not have the saved widget.
+    pub fn restore_layout(&self, layout: &mut Layout) -> bool {
+        let focused = layout.focus_widget(self.active);
+        layout.restore_positions(&self.positions);
+        focused
+    }
+
     pub fn save(&self, path: &Path) -> ioResult<()> {
         self.serialize(&mut File::create(path)?)
     }
 
     fn serialize<W: Write>(&self, writer: &mut W) -> ioResult<()> {
-        writer.write_all(toml::to_string_pretty(&self).unwrap().as_bytes())
+        let state = toml::to_string_pretty(&self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        writer.write_all(state.as_bytes())
     }
 
     pub fn load(path: &Path) -> ToDoRes<Self> {
@@ -54,3 +121,65 @@ impl UIState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::layout::Render;
+    use crate::todo::{FilterState, ToDoCategory};
+    use crate::ui::UIEvent;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn restore() -> Result<(), Box<dyn std::error::Error>> {
+        let mut todo = ToDo::default();
+        for i in 0..30 {
+            todo.new_task(&format!("task {i} +work")).unwrap();
+        }
+        todo.new_task("call mom @phone").unwrap();
+        todo.toggle_filter(ToDoCategory::Contexts, "phone", FilterState::Remove);
+        todo.set_sort(ToDoData::Pending, TaskSort::Alphanumeric);
+        todo.set_active(ToDoData::Pending, 3);
+        let data = Arc::new(Mutex::new(todo));
+        let mut layout = Layout::from_str("[List, Projects]", data.clone(), &Config::default())?;
+        layout.update_chunk(tui::layout::Rect::new(0, 0, 40, 12));
+        layout.focus_widget(WidgetType::List);
+        for _ in 0..12 {
+            layout.handle_event(UIEvent::ListDown);
+        }
+        layout.focus_widget(WidgetType::Project);
+
+        let mut buf = Vec::new();
+        UIState::new(&layout, &data).serialize(&mut buf)?;
+        let state = UIState::deserialize(buf.as_slice());
+        assert_eq!(state.active, WidgetType::Project);
+        let selected = state.positions[0].item.as_ref().unwrap();
+        assert!(selected.ends_with("task 2 +work"));
+
+        // The todo file changed meanwhile, two tasks were added before the saved ones.
+        let mut todo = ToDo::default();
+        todo.new_task("new task").unwrap();
+        todo.new_task("another new task").unwrap();
+        for task in &data.lock().unwrap().pending {
+            todo.add_task(task.clone());
+        }
+        state.restore_todo(&mut todo);
+        assert_eq!(todo.get_sort(ToDoData::Pending), TaskSort::Alphanumeric);
+        assert_eq!(todo.get_active().unwrap().subject, "task 11 +work");
+        assert_eq!(todo.hidden(ToDoData::Pending), 1);
+
+        let data = Arc::new(Mutex::new(todo));
+        let mut layout = Layout::from_str("[List, Projects]", data, &Config::default())?;
+        layout.update_chunk(tui::layout::Rect::new(0, 0, 40, 12));
+        assert!(state.restore_layout(&mut layout));
+        assert_eq!(layout.get_active_widget(), WidgetType::Project);
+        // The selected task is found two rows lower and keeps its place on the screen.
+        let positions = layout.positions();
+        assert_eq!(positions[0].item, state.positions[0].item);
+        assert_eq!(positions[0].index, state.positions[0].index + 2);
+        assert_eq!(positions[0].first, state.positions[0].first + 2);
+        assert_eq!(positions[1], state.positions[1]);
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+use serde::Deserialize;
+use std::{
+    io,
+    process::{Command, Stdio},
+    sync::mpsc::{self, Receiver},
+    thread,
+};
+
+/// The latest release of the application published on GitHub.
+pub const RELEASES_URL: &str = "https://api.github.com/repos/Fanteria/todotxt-tui/releases/latest";
+
+/// Represents a published release of the application.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Release {
+    /// The version of the release without the leading `v`, e.g. `0.2.0`.
+    pub version: String,
+    /// The changelog of the release as written in its notes.
+    pub notes: String,
+}
+
+/// The fields of the GitHub releases API response the release is read from.
+#[derive(Deserialize)]
+struct ReleaseResponse {
+    tag_name: String,
+    #[serde(default)]
+    body: Option<String>,
+}
+
+impl Release {
+    /// Parses the release from the response of the GitHub releases API.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the response is not a release or has no tag of the release.
+    pub fn parse(json: &str) -> Option<Self> {
+        let response: ReleaseResponse = serde_json::from_str(json).ok()?;
+        Some(Self {
+            version: response.tag_name.trim_start_matches('v').to_string(),
+            notes: response.body.unwrap_or_default(),
+        })
+    }
+}
+
+/// Checks whether the version is newer than the current one. Versions are compared
+/// by their numbers, e.g. `0.10.0` is newer than `0.9.1`, a pre-release suffix is ignored.
+///
+/// # Arguments
+///
+/// * `version` - The checked version, e.g. `v0.2.0`.
+/// * `current` - The version of the running application.
+pub fn is_newer(version: &str, current: &str) -> bool {
+    fn numbers(version: &str) -> Vec<u64> {
+        version
+            .trim_start_matches('v')
+            .split(['-', '+'])
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    }
+    numbers(version) > numbers(current)
+}
+
+/// Downloads the document at the URL with `curl`, so no HTTP client is compiled in.
+fn fetch(url: &str) -> io::Result<String> {
+    let output = Command::new("curl")
+        .args(["--silent", "--fail", "--location", "--max-time", "10", url])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()?;
+    match output.status.success() {
+        true => Ok(String::from_utf8_lossy(&output.stdout).to_string()),
+        false => Err(io::Error::other(format!(
+            "curl exited with {}",
+            output.status
+        ))),
+    }
+}
+
+/// Looks for a newer release in the background, the check never blocks the application.
+///
+/// # Arguments
+///
+/// * `current` - The version of the running application.
+///
+/// # Returns
+///
+/// The receiver of the newer release, nothing is sent if there is none or the check fails.
+pub fn check_update(current: &'static str) -> Receiver<Release> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let release = match fetch(RELEASES_URL).map(|json| Release::parse(&json)) {
+            Ok(Some(release)) => release,
+            Ok(None) => return log::warn!("Cannot read the latest release"),
+            Err(e) => return log::warn!("Cannot check for updates: {e}"),
+        };
+        if is_newer(&release.version, current) {
+            log::info!("Version {} is available", release.version);
+            let _ = tx.send(release);
+        }
+    });
+    rx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn newer() {
+        assert!(is_newer("v0.2.0", "0.1.0"));
+        assert!(is_newer("0.10.0", "0.9.1"));
+        assert!(is_newer("1.0", "0.9.9"));
+        assert!(!is_newer("0.1.0", "0.1.0"));
+        assert!(!is_newer("0.1.0-rc1", "0.1.0"));
+        assert!(!is_newer("0.0.9", "0.1.0"));
+    }
+
+    #[test]
+    fn parse_release() {
+        let json = r###"{
+            "url": "https://api.github.com/repos/Fanteria/todotxt-tui/releases/1",
+            "tag_name" : "v0.2.0",
+            "body": "## What's new\r\n- \"Profiles\" \u00e1 la carte\n- Forecast"
+        }"###;
+        assert_eq!(
+            Release::parse(json),
+            Some(Release {
+                version: String::from("0.2.0"),
+                notes: String::from("## What's new\r\n- \"Profiles\" á la carte\n- Forecast"),
+            })
+        );
+        assert_eq!(Release::parse(r#"{"message": "Not Found"}"#), None);
+    }
+
+    #[test]
+    fn parse_release_nested_and_surrogates() {
+        let json = r#"{
+            "author": {"body": "Not the notes", "tag_name": "v9.9.9"},
+            "assets": [{"tag_name": "v8.8.8"}],
+            "tag_name": "v0.2.1",
+            "body": "Faster \ud83d\ude80 start",
+            "draft": false
+        }"#;
+        assert_eq!(
+            Release::parse(json),
+            Some(Release {
+                version: String::from("0.2.1"),
+                notes: String::from("Faster \u{1F680} start"),
+            })
+        );
+        assert_eq!(
+            Release::parse(r#"{"tag_name": "v0.2.1", "body": null}"#),
+            Some(Release {
+                version: String::from("0.2.1"),
+                notes: String::new(),
+            })
+        );
+    }
+}